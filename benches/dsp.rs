@@ -0,0 +1,91 @@
+//! Criterion benchmarks for every DSP module and the full chain.
+//!
+//!     cargo bench --features bench
+//!     cargo bench --features bench -- punch/96000   # one module, one rate
+//!
+//! Each iteration copies a fixed stereo program signal into the buffer and
+//! processes it once, so every run sees the same input and the compressors
+//! stay in gain reduction. Throughput is reported in samples per channel.
+
+use bus_channel_strip::bench::{BenchStrip, Stage};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use nih_plug::buffer::Buffer;
+use std::time::Duration;
+
+const SAMPLE_RATES: [f32; 3] = [44_100.0, 96_000.0, 192_000.0];
+const BUFFER_SIZES: [usize; 3] = [64, 256, 1024];
+
+/// Kick-ish low tone, midrange chord and a little noise, peaking around
+/// -6 dBFS with the right channel detuned so the stereo modules have a
+/// side signal to work on.
+fn program(sample_rate: f32, len: usize) -> [Vec<f32>; 2] {
+    let mut seed = 0x2545_f491_u32;
+    let mut noise = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as f32 / u32::MAX as f32 * 2.0 - 1.0
+    };
+    let tone = |hz: f32, i: usize| (std::f32::consts::TAU * hz * i as f32 / sample_rate).sin();
+    let mut left = Vec::with_capacity(len);
+    let mut right = Vec::with_capacity(len);
+    for i in 0..len {
+        let body = 0.2 * tone(60.0, i) + 0.1 * tone(440.0, i) + 0.05 * tone(3300.0, i);
+        left.push(body + 0.02 * noise() + 0.05 * tone(554.4, i));
+        right.push(body + 0.02 * noise() + 0.05 * tone(659.3, i));
+    }
+    [left, right]
+}
+
+/// Bench `run` over every rate × buffer size in its own group.
+fn bench_matrix(c: &mut Criterion, group_name: &str, run: impl Fn(&mut BenchStrip, &mut Buffer)) {
+    let mut group = c.benchmark_group(group_name);
+    for sample_rate in SAMPLE_RATES {
+        for size in BUFFER_SIZES {
+            let input = program(sample_rate, size);
+            let mut strip = BenchStrip::new(sample_rate);
+            let (mut left, mut right) = (vec![0.0_f32; size], vec![0.0_f32; size]);
+            let mut buffer = Buffer::default();
+            // SAFETY: both slices outlive `buffer`, which is dropped at the
+            // end of this iteration.
+            unsafe {
+                buffer.set_slices(size, |slices| {
+                    slices.clear();
+                    slices.push(&mut left);
+                    slices.push(&mut right);
+                });
+            }
+            group.throughput(Throughput::Elements(size as u64));
+            group.bench_function(BenchmarkId::new(format!("{sample_rate}"), size), |b| {
+                b.iter(|| {
+                    for (channel, source) in buffer.as_slice().iter_mut().zip(&input) {
+                        channel.copy_from_slice(source);
+                    }
+                    run(&mut strip, &mut buffer);
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn modules(c: &mut Criterion) {
+    for stage in Stage::ALL {
+        bench_matrix(c, stage.name(), |strip, buffer| {
+            strip.process(stage, buffer)
+        });
+    }
+}
+
+fn full_chain(c: &mut Criterion) {
+    bench_matrix(c, "chain", BenchStrip::process_chain);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2));
+    targets = modules, full_chain
+}
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+#!/bin/bash
+# Build the Audio Unit (AUv2) bundle by wrapping the CLAP build (macOS only).
+# Usage: ./bin/build_au.sh [--install] [cargo features]
+#
+# Needs CMake 3.21+ and Xcode's command line tools. The result lands in
+# target/bundled/Bus-Channel-Strip.component next to the VST3 and CLAP.
+
+set -e
+
+if [[ "$(uname)" != "Darwin" ]]; then
+    echo "Audio Units can only be built on macOS" >&2
+    exit 1
+fi
+
+INSTALL=0
+if [[ "$1" == "--install" ]]; then
+    INSTALL=1
+    shift
+fi
+FEATURES="${1:-api5500,buttercomp2,pultec,transformer,punch,dynamic_eq,gui}"
+
+echo "=== Bundling CLAP (features: $FEATURES) ==="
+cargo +nightly run --package xtask -- bundle bus_channel_strip --release --features "$FEATURES"
+
+echo "=== Wrapping CLAP as AUv2 ==="
+cmake -S au -B target/au -DCMAKE_BUILD_TYPE=Release \
+    -DBCS_CLAP_BUNDLE="$PWD/target/bundled/Bus-Channel-Strip.clap"
+cmake --build target/au --config Release
+
+COMPONENT=$(find target/au -name "Bus-Channel-Strip.component" -type d -prune | head -n 1)
+if [[ -z "$COMPONENT" ]]; then
+    echo "CMake finished but no Bus-Channel-Strip.component was produced" >&2
+    exit 1
+fi
+rm -rf target/bundled/Bus-Channel-Strip.component
+cp -R "$COMPONENT" target/bundled/
+echo "Built target/bundled/Bus-Channel-Strip.component"
+
+if [[ $INSTALL == 1 ]]; then
+    echo "=== Installing to ~/Library/Audio/Plug-Ins/Components ==="
+    mkdir -p ~/Library/Audio/Plug-Ins/Components
+    rm -rf ~/Library/Audio/Plug-Ins/Components/Bus-Channel-Strip.component
+    cp -R target/bundled/Bus-Channel-Strip.component ~/Library/Audio/Plug-Ins/Components/
+    # Make Logic pick up the new build without a reboot, then validate it.
+    killall -9 AudioComponentRegistrar 2>/dev/null || true
+    auval -v aufx Bcst Fsec
+fi
@@ -0,0 +1,215 @@
+// Browser demo: drives the wasm build of the DSP core (src/wasm.rs).
+//
+// Settings are the modules' `update_parameters` arguments in order, and
+// their defaults come from the wasm side, so this file only describes how to
+// draw each one. Keep the lists in step with src/wasm.rs.
+
+const BLOCK = 4096;
+
+const range = (name, min, max, step) => ({ name, min, max, step });
+const choice = (name, options) => ({ name, options });
+
+const dyneqBand = (n) => [
+  choice(`Band ${n} mode`, ['Compress down', 'Expand up', 'Gate']),
+  range('Detector freq', 20, 2000, 1),
+  range('Freq', 20, 2000, 1),
+  range('Q', 0.3, 8, 0.1),
+  range('Threshold', -60, 0, 1),
+  range('Ratio', 1, 20, 0.5),
+  range('Attack ms', 0.1, 200, 0.1),
+  range('Release ms', 1, 2000, 1),
+  range('Gain dB', -18, 18, 0.5),
+  choice('Enabled', ['Off', 'On']),
+];
+
+const MODULES = [
+  {
+    name: 'API 5500 EQ',
+    params: [
+      range('LF freq', 20, 400, 1), range('LF gain', -15, 15, 0.5),
+      range('LMF freq', 50, 2000, 1), range('LMF gain', -15, 15, 0.5), range('LMF Q', 0.1, 10, 0.1),
+      range('MF freq', 200, 8000, 10), range('MF gain', -15, 15, 0.5), range('MF Q', 0.1, 10, 0.1),
+      range('HMF freq', 1000, 15000, 10), range('HMF gain', -15, 15, 0.5), range('HMF Q', 0.1, 10, 0.1),
+      range('HF freq', 3000, 20000, 10), range('HF gain', -15, 15, 0.5),
+    ],
+  },
+  {
+    name: 'Pultec EQ',
+    params: [
+      range('LF boost freq', 20, 300, 1), range('LF boost', 0, 18, 0.5), range('LF boost BW', 0, 1, 0.01),
+      range('LF atten freq', 20, 400, 1), range('LF atten', 0, 18, 0.5), range('LF atten BW', 0, 1, 0.01),
+      range('HF boost freq', 5000, 20000, 10), range('HF boost', 0, 10, 0.5), range('HF boost BW', 0, 1, 0.01),
+      range('HF atten freq', 5000, 20000, 10), range('HF atten', 0, 8, 0.5),
+      range('Tube drive', 0, 1, 0.01),
+    ],
+  },
+  {
+    name: 'Transformer',
+    params: [
+      choice('Model', ['Vintage', 'Modern', 'British', 'American']),
+      range('Input drive', 0, 1, 0.01), range('Input saturation', 0, 1, 0.01),
+      range('Output drive', 0, 1, 0.01), range('Output saturation', 0, 1, 0.01),
+      range('Low response', -1, 1, 0.01), range('High response', -1, 1, 0.01),
+      range('Compression', 0, 1, 0.01),
+    ],
+  },
+  {
+    name: 'Punch',
+    params: [
+      range('Clip threshold', -12, 0, 0.1), choice('Clip mode', ['Hard', 'Soft', 'Cubic']),
+      range('Softness', 0, 1, 0.01), choice('Oversampling', ['1×', '4×', '8×', '16×']),
+      range('Attack', -1, 1, 0.01), range('Sustain', -1, 1, 0.01),
+      range('Attack time ms', 0.1, 30, 0.1), range('Release time ms', 10, 500, 1),
+      range('Sensitivity', 0, 1, 0.01),
+      range('Input dB', -12, 12, 0.5), range('Output dB', -12, 12, 0.5),
+      range('Mix', 0, 1, 0.01), range('Wet HPF Hz', 20, 1000, 1),
+    ],
+  },
+  {
+    name: 'Dynamic EQ',
+    params: [1, 2, 3, 4].flatMap(dyneqBand),
+  },
+];
+
+const status = document.getElementById('status');
+const buttons = Object.fromEntries(
+  ['render', 'play-wet', 'play-dry', 'stop'].map((id) => [id, document.getElementById(id)]),
+);
+
+let dsp;
+try {
+  const { instance } = await WebAssembly.instantiateStreaming(fetch('bus_channel_strip.wasm'), {});
+  dsp = instance.exports;
+} catch (error) {
+  status.textContent = `Couldn't load the DSP core (${error.message}). Build it with the wasm feature; see the README.`;
+  throw error;
+}
+
+// Every module's settings and on/off state, seeded from the wasm defaults.
+const state = (() => {
+  const strip = dsp.bcs_new(48000, 1);
+  const modules = MODULES.map((module, id) => ({
+    on: false,
+    values: module.params.map((_, index) => dsp.bcs_get_param(strip, id, index)),
+  }));
+  dsp.bcs_free(strip);
+  return modules;
+})();
+
+function drawControls() {
+  const root = document.getElementById('modules');
+  MODULES.forEach((module, id) => {
+    const set = document.createElement('fieldset');
+    const legend = document.createElement('legend');
+    const toggle = document.createElement('input');
+    toggle.type = 'checkbox';
+    toggle.addEventListener('change', () => { state[id].on = toggle.checked; });
+    legend.append(toggle, ` ${module.name}`);
+    set.append(legend);
+
+    module.params.forEach((param, index) => {
+      if (param.name.startsWith('Band ')) {
+        const heading = document.createElement('h3');
+        heading.textContent = param.name.slice(0, 6);
+        set.append(heading);
+      }
+      const label = document.createElement('label');
+      label.className = 'param';
+      const out = document.createElement('output');
+      let input;
+      if (param.options) {
+        input = document.createElement('select');
+        param.options.forEach((text, i) => input.add(new Option(text, i)));
+        input.value = state[id].values[index];
+      } else {
+        input = document.createElement('input');
+        Object.assign(input, { type: 'range', min: param.min, max: param.max, step: param.step });
+        input.value = state[id].values[index];
+        out.value = input.value;
+      }
+      input.addEventListener('input', () => {
+        state[id].values[index] = Number(input.value);
+        if (!param.options) out.value = input.value;
+      });
+      label.append(param.name.replace(/^Band \d /, ''), input, out);
+      set.append(label);
+    });
+    root.append(set);
+  });
+}
+
+const context = new AudioContext();
+let dry = null;
+let wet = null;
+let playing = null;
+
+function render(source) {
+  const frames = source.length;
+  const strip = dsp.bcs_new(source.sampleRate, BLOCK);
+  state.forEach((module, id) => {
+    module.values.forEach((value, index) => dsp.bcs_set_param(strip, id, index, value));
+    dsp.bcs_set_enabled(strip, id, module.on);
+  });
+
+  const input = [0, 1].map((ch) => source.getChannelData(Math.min(ch, source.numberOfChannels - 1)));
+  const output = context.createBuffer(2, frames, source.sampleRate);
+  const result = [output.getChannelData(0), output.getChannelData(1)];
+  for (let start = 0; start < frames; start += BLOCK) {
+    const n = Math.min(BLOCK, frames - start);
+    // Views are rebuilt per block: a grown wasm memory detaches old ones.
+    const views = [0, 1].map((ch) => new Float32Array(dsp.memory.buffer, dsp.bcs_channel(strip, ch), n));
+    views.forEach((view, ch) => view.set(input[ch].subarray(start, start + n)));
+    dsp.bcs_process(strip, n);
+    views.forEach((view, ch) => result[ch].set(view, start));
+  }
+  dsp.bcs_free(strip);
+  return output;
+}
+
+function play(buffer) {
+  stop();
+  playing = context.createBufferSource();
+  playing.buffer = buffer;
+  playing.connect(context.destination);
+  playing.start();
+  buttons.stop.disabled = false;
+}
+
+function stop() {
+  if (playing) playing.stop();
+  playing = null;
+  buttons.stop.disabled = true;
+}
+
+document.getElementById('file').addEventListener('change', async (event) => {
+  const file = event.target.files[0];
+  if (!file) return;
+  status.textContent = `Decoding ${file.name}…`;
+  try {
+    dry = await context.decodeAudioData(await file.arrayBuffer());
+  } catch {
+    status.textContent = `Couldn't decode ${file.name}.`;
+    return;
+  }
+  wet = null;
+  buttons.render.disabled = false;
+  buttons['play-dry'].disabled = false;
+  buttons['play-wet'].disabled = true;
+  status.textContent = `${file.name}: ${dry.duration.toFixed(1)} s at ${dry.sampleRate} Hz.`;
+});
+
+buttons.render.addEventListener('click', () => {
+  context.resume();
+  const started = performance.now();
+  wet = render(dry);
+  const seconds = (performance.now() - started) / 1000;
+  status.textContent = `Rendered ${dry.duration.toFixed(1)} s in ${seconds.toFixed(2)} s.`;
+  buttons['play-wet'].disabled = false;
+  play(wet);
+});
+buttons['play-wet'].addEventListener('click', () => { context.resume(); play(wet); });
+buttons['play-dry'].addEventListener('click', () => { context.resume(); play(dry); });
+buttons.stop.addEventListener('click', stop);
+
+drawControls();
+status.textContent = 'Load an audio file to start.';
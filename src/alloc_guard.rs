@@ -0,0 +1,78 @@
+// src/alloc_guard.rs
+// Test-only allocation guard: a counting global allocator plus
+// `assert_no_alloc`, which fails the test if the closure touched the heap
+// on the calling thread. DSP tests wrap their `process()` calls in it so an
+// allocation creeping back onto the audio path breaks the build.
+//
+// The counter is per thread, so the harness's own threads don't trip it.
+// For whole-plugin checks inside a host, build with the
+// `assert_process_allocs` feature instead (nih-plug's debug-build guard).
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+#[cfg_attr(feature = "assert_process_allocs", allow(dead_code))]
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_allocation() {
+    // `try_with` so allocations during thread teardown don't panic.
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+// nih-plug installs its own global allocator under `assert_process_allocs`;
+// the guard then checks nothing here and nih-plug's checks take over.
+#[cfg(not(feature = "assert_process_allocs"))]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Run `f` and panic if it allocated on this thread.
+pub fn assert_no_alloc<R>(f: impl FnOnce() -> R) -> R {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let allocations = ALLOCATIONS.with(Cell::get) - before;
+    assert_eq!(
+        allocations, 0,
+        "{allocations} heap allocation(s) on the audio path"
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "assert_process_allocs"))]
+    #[test]
+    fn catches_an_allocation() {
+        let caught = std::panic::catch_unwind(|| {
+            assert_no_alloc(|| std::hint::black_box(vec![0_u8; 16]).len())
+        });
+        assert!(caught.is_err());
+        assert_eq!(assert_no_alloc(|| 2 + 2), 4);
+    }
+}
@@ -0,0 +1,113 @@
+// src/analog.rs
+// The master Analog Amount macro: one control over every coloration stage.
+//
+// The macro never moves the knobs it drives. Each stage keeps its own
+// setting, and the audio path reads it through the curve here, so 100 %
+// is the strip exactly as dialled, 0 % is clean, and 150 % pushes every
+// stage past where it was left. Automating the macro therefore never
+// rewrites a preset or a session.
+//
+// One curve per target, because the stages don't wear the same way:
+//
+// - Pultec tube drive and transformer drive follow the amount straight.
+// - Transformer saturation bends more slowly near clean, so the lower half
+//   of the macro still sounds like iron rather than a weaker EQ.
+// - Transformer age and the vintage EQs' noise floor fall away first: at
+//   50 % the hiss is already down 12 dB while the drive is only halved.
+// - Sheen warmth, the strip's console-style polynomial, pushes at half
+//   rate above 100 %; the Inflator curve gets brittle quickly.
+//
+// Knob-driven targets are clamped to their 0–1 range after scaling; the
+// noise floor is a gain and goes wherever the curve takes it.
+
+/// Highest macro setting: 150 %.
+pub const MAX_AMOUNT: f32 = 1.5;
+
+/// A coloration stage the macro drives.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnalogTarget {
+    PultecTube,
+    TransformerDrive,
+    TransformerSaturation,
+    TransformerAge,
+    SheenWarmth,
+    /// The vintage EQ character's noise floor, as a gain on its level.
+    Noise,
+}
+
+impl AnalogTarget {
+    /// (exponent below 100 %, slope above it) of this target's curve.
+    fn curve(self) -> (f32, f32) {
+        match self {
+            AnalogTarget::PultecTube | AnalogTarget::TransformerDrive => (1.0, 1.0),
+            AnalogTarget::TransformerSaturation => (0.7, 0.8),
+            AnalogTarget::TransformerAge => (1.5, 0.6),
+            AnalogTarget::SheenWarmth => (1.0, 0.5),
+            AnalogTarget::Noise => (2.0, 1.0),
+        }
+    }
+
+    /// Factor the macro at `amount` (0–1.5) puts on this target.
+    pub fn gain(self, amount: f32) -> f32 {
+        let amount = amount.clamp(0.0, MAX_AMOUNT);
+        let (exponent, slope) = self.curve();
+        if amount <= 1.0 {
+            amount.powf(exponent)
+        } else {
+            1.0 + (amount - 1.0) * slope
+        }
+    }
+
+    /// A 0–1 knob `value` as the stage should see it at `amount`.
+    pub fn apply(self, amount: f32, value: f32) -> f32 {
+        (value * self.gain(amount)).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [AnalogTarget; 6] = [
+        AnalogTarget::PultecTube,
+        AnalogTarget::TransformerDrive,
+        AnalogTarget::TransformerSaturation,
+        AnalogTarget::TransformerAge,
+        AnalogTarget::SheenWarmth,
+        AnalogTarget::Noise,
+    ];
+
+    #[test]
+    fn test_full_amount_leaves_every_stage_as_dialled() {
+        for target in ALL {
+            assert_eq!(target.gain(1.0), 1.0, "{target:?}");
+            assert_eq!(target.apply(1.0, 0.37), 0.37, "{target:?}");
+        }
+    }
+
+    #[test]
+    fn test_zero_is_clean_and_the_curves_rise() {
+        for target in ALL {
+            assert_eq!(target.apply(0.0, 0.8), 0.0, "{target:?}");
+            let mut last = 0.0;
+            for step in 1..=15 {
+                let gain = target.gain(step as f32 * 0.1);
+                assert!(gain > last, "{target:?} at {step}0 %");
+                last = gain;
+            }
+        }
+    }
+
+    #[test]
+    fn test_curves_keep_their_voicing() {
+        // At half, the noise is down 12 dB while the drive is only halved,
+        // and saturation holds on above both.
+        assert!((AnalogTarget::Noise.gain(0.5) - 0.25).abs() < 1e-6);
+        assert!((AnalogTarget::PultecTube.gain(0.5) - 0.5).abs() < 1e-6);
+        assert!(AnalogTarget::TransformerSaturation.gain(0.5) > 0.6);
+        // Pushed, the knobs stop at the top of their range.
+        assert!((AnalogTarget::TransformerDrive.apply(MAX_AMOUNT, 0.2) - 0.3).abs() < 1e-6);
+        assert_eq!(AnalogTarget::PultecTube.apply(MAX_AMOUNT, 0.9), 1.0);
+        assert!((AnalogTarget::SheenWarmth.gain(MAX_AMOUNT) - 1.25).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,528 @@
+// src/analyzer.rs
+// Real-time spectrum analyzer feeding `spectral::SpectrumData`.
+//
+// One `SpectrumAnalyzer` analyses one signal: the mono sum, or one side when
+// the channels are split. None of this runs on the audio thread: the audio
+// thread taps the chain into `spectral::AnalyzerRing` and queues
+// `BackgroundTask::AnalyzeSpectrum`; `AnalyzerWorker` drains the ring on
+// nih-plug's background thread, runs the analyzers and publishes each
+// completed frame, plus the sidechain masking analysis when the GUI asks.
+//
+// Samples go into an FFT_SIZE ring. Every hop (the full frame, half or
+// a quarter of it, per the overlap setting) the newest FFT_SIZE samples are
+// windowed and transformed. Per-bin power is averaged exponentially with a
+// time constant in milliseconds, so the display settles at the same speed
+// whatever the overlap and sample rate, and is published as dBFS clamped to
+// the floor.
+//
+// Magnitudes are normalised by the window's coherent gain: a full-scale sine
+// reads 0 dBFS under either window. Everything is allocated in `new`.
+
+use crate::envelope::coeff_for_block;
+use crate::spectral::{
+    AnalysisResult, AnalyzerRing, SpectrumData, ANALYZER_RING_SIZE, FFT_SIZE, SPECTRUM_BINS,
+};
+use crate::{AnalyzerOverlap, AnalyzerWindow};
+use realfft::num_complex::Complex;
+use realfft::{RealFftPlanner, RealToComplex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Per-buffer snapshot of the analyzer params.
+#[derive(Debug, Clone, Copy)]
+pub struct AnalyzerSettings {
+    pub window: AnalyzerWindow,
+    pub overlap: AnalyzerOverlap,
+    /// Averaging time constant; 0 shows every frame as-is.
+    pub averaging_ms: f32,
+    /// Lowest published level, dBFS.
+    pub floor_db: f32,
+}
+
+impl AnalyzerOverlap {
+    /// Samples between frames.
+    pub fn hop(self) -> usize {
+        match self {
+            AnalyzerOverlap::None => FFT_SIZE,
+            AnalyzerOverlap::Half => FFT_SIZE / 2,
+            AnalyzerOverlap::ThreeQuarter => FFT_SIZE / 4,
+        }
+    }
+}
+
+pub struct SpectrumAnalyzer {
+    fft: Arc<dyn RealToComplex<f32>>,
+    hann: Vec<f32>,
+    blackman_harris: Vec<f32>,
+    frame: Vec<f32>,
+    spectrum: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    ring: Vec<f32>,
+    /// Next write position in `ring`; also the oldest sample.
+    pos: usize,
+    /// Samples pushed since the last frame.
+    since_frame: usize,
+    /// Averaged power per published bin.
+    power: Vec<f32>,
+    /// Linear magnitude of the latest frame, before averaging.
+    magnitudes: Vec<f32>,
+    levels_db: Vec<f32>,
+    /// False until the first frame seeds the average.
+    primed: bool,
+    sample_rate: f32,
+}
+
+impl SpectrumAnalyzer {
+    pub fn new() -> Self {
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+        Self {
+            hann: window(AnalyzerWindow::Hann),
+            blackman_harris: window(AnalyzerWindow::BlackmanHarris),
+            frame: fft.make_input_vec(),
+            spectrum: fft.make_output_vec(),
+            scratch: fft.make_scratch_vec(),
+            fft,
+            ring: vec![0.0; FFT_SIZE],
+            pos: 0,
+            since_frame: 0,
+            power: vec![0.0; SPECTRUM_BINS],
+            magnitudes: vec![0.0; SPECTRUM_BINS],
+            levels_db: vec![0.0; SPECTRUM_BINS],
+            primed: false,
+            sample_rate: 44100.0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    pub fn reset(&mut self) {
+        self.ring.fill(0.0);
+        self.pos = 0;
+        self.since_frame = 0;
+        self.primed = false;
+    }
+
+    /// Feed one sample. Returns true when a frame was analysed and
+    /// `levels_db` holds new levels.
+    pub fn push(&mut self, sample: f32, settings: &AnalyzerSettings) -> bool {
+        self.ring[self.pos] = sample;
+        self.pos = (self.pos + 1) % FFT_SIZE;
+        self.since_frame += 1;
+        if self.since_frame < settings.overlap.hop() {
+            return false;
+        }
+        self.since_frame = 0;
+        self.analyse(settings);
+        true
+    }
+
+    /// Averaged levels of the latest frame, dBFS clamped to the floor.
+    pub fn levels_db(&self) -> &[f32] {
+        &self.levels_db
+    }
+
+    /// Linear magnitudes of the latest frame (full-scale sine = 1.0).
+    pub fn frame_magnitudes(&self) -> &[f32] {
+        &self.magnitudes
+    }
+
+    fn analyse(&mut self, settings: &AnalyzerSettings) {
+        let window = match settings.window {
+            AnalyzerWindow::Hann => &self.hann,
+            AnalyzerWindow::BlackmanHarris => &self.blackman_harris,
+        };
+        // Oldest sample first.
+        let (older, newer) = self.ring.split_at(self.pos);
+        for ((dst, &src), &w) in self
+            .frame
+            .iter_mut()
+            .zip(newer.iter().chain(older))
+            .zip(window)
+        {
+            *dst = src * w;
+        }
+        if self
+            .fft
+            .process_with_scratch(&mut self.frame, &mut self.spectrum, &mut self.scratch)
+            .is_err()
+        {
+            return;
+        }
+
+        let scale = 2.0 / window.iter().sum::<f32>();
+        // One average step per hop.
+        let alpha = coeff_for_block(
+            settings.averaging_ms,
+            settings.overlap.hop(),
+            self.sample_rate,
+        );
+        let alpha = if self.primed { alpha } else { 0.0 };
+        self.primed = true;
+
+        for (((bin, mag), power), level) in self
+            .spectrum
+            .iter()
+            .zip(self.magnitudes.iter_mut())
+            .zip(self.power.iter_mut())
+            .zip(self.levels_db.iter_mut())
+        {
+            *mag = bin.norm() * scale;
+            *power = alpha * *power + (1.0 - alpha) * *mag * *mag;
+            *level = (10.0 * power.max(1e-20).log10()).max(settings.floor_db);
+        }
+    }
+}
+
+impl Default for SpectrumAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where a drain publishes its results.
+pub struct AnalyzerOutputs<'a> {
+    pub spectrum: &'a SpectrumData,
+    /// GUI → worker: run the masking analysis on the next frame.
+    pub analysis_requested: &'a AtomicBool,
+    pub analysis_result: &'a AnalysisResult,
+}
+
+/// Background-thread half of the analyzer. Owned by the task executor
+/// behind a Mutex the audio thread never touches.
+pub struct AnalyzerWorker {
+    first: SpectrumAnalyzer,
+    second: SpectrumAnalyzer,
+    split: bool,
+    /// Ring samples consumed so far (a `written` count).
+    read_pos: usize,
+    sample_rate: f32,
+    masking: MaskingAnalysis,
+}
+
+impl AnalyzerWorker {
+    pub fn new() -> Self {
+        Self {
+            first: SpectrumAnalyzer::new(),
+            second: SpectrumAnalyzer::new(),
+            split: false,
+            read_pos: 0,
+            sample_rate: 44100.0,
+            masking: MaskingAnalysis::new(),
+        }
+    }
+
+    /// Analyse everything the audio thread has pushed since the last drain.
+    pub fn drain(
+        &mut self,
+        ring: &AnalyzerRing,
+        settings: &AnalyzerSettings,
+        out: &AnalyzerOutputs,
+    ) {
+        let written = ring.written();
+        let sample_rate = ring.sample_rate();
+        let split = ring.is_split();
+        let reset = ring.take_reset();
+        if reset || sample_rate != self.sample_rate || split != self.split {
+            // Restart both sides together so their frames stay aligned.
+            self.sample_rate = sample_rate;
+            self.split = split;
+            for analyzer in [&mut self.first, &mut self.second] {
+                analyzer.set_sample_rate(sample_rate);
+                analyzer.reset();
+            }
+        }
+        if reset {
+            self.read_pos = written;
+        }
+        // Fell behind by more than the ring holds: skip to the newer half
+        // rather than read slots the writer is reusing.
+        if written - self.read_pos > ANALYZER_RING_SIZE {
+            self.read_pos = written - ANALYZER_RING_SIZE / 2;
+        }
+
+        for index in self.read_pos..written {
+            let (first, second) = ring.sample(index);
+            let frame_done = self.first.push(first, settings);
+            if split {
+                self.second.push(second, settings);
+            }
+            if !frame_done {
+                continue;
+            }
+            out.spectrum.set_floor_db(settings.floor_db);
+            if split {
+                out.spectrum.write_right_from_slice(self.second.levels_db());
+            }
+            out.spectrum.set_split(split);
+            out.spectrum.write_from_slice(self.first.levels_db());
+            if out.analysis_requested.swap(false, Ordering::Relaxed) {
+                self.masking.run(
+                    ring,
+                    self.first.frame_magnitudes(),
+                    sample_rate,
+                    out.analysis_result,
+                );
+            }
+        }
+        self.read_pos = written;
+    }
+}
+
+impl Default for AnalyzerWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One-shot masking analysis: compares the analyzer's latest frame with the
+/// newest FFT_SIZE sidechain samples and publishes the DynEQ band, frequency
+/// and threshold that would duck the program where the two overlap most.
+struct MaskingAnalysis {
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    input: Vec<f32>,
+    output: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+}
+
+impl MaskingAnalysis {
+    fn new() -> Self {
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+        Self {
+            // Hann window: w[n] = 0.5 * (1 - cos(2π*n / (N-1)))
+            window: (0..FFT_SIZE)
+                .map(|n| {
+                    0.5 * (1.0 - (std::f32::consts::TAU * n as f32 / (FFT_SIZE - 1) as f32).cos())
+                })
+                .collect(),
+            input: fft.make_input_vec(),
+            output: fft.make_output_vec(),
+            scratch: fft.make_scratch_vec(),
+            fft,
+        }
+    }
+
+    fn run(
+        &mut self,
+        ring: &AnalyzerRing,
+        main_mags: &[f32],
+        sample_rate: f32,
+        result: &AnalysisResult,
+    ) {
+        // Oldest first; before the sidechain has FFT_SIZE samples the
+        // missing head reads as silence.
+        let end = ring.sidechain_written();
+        for (i, (x, &w)) in self.input.iter_mut().zip(&self.window).enumerate() {
+            *x = match (end + i).checked_sub(FFT_SIZE) {
+                Some(index) => ring.sidechain_sample(index) * w,
+                None => 0.0,
+            };
+        }
+        if self
+            .fft
+            .process_with_scratch(&mut self.input, &mut self.output, &mut self.scratch)
+            .is_err()
+        {
+            return;
+        }
+
+        let scale = 2.0 / FFT_SIZE as f32;
+        let mut peak_overlap = 0.0_f32;
+        let mut peak_bin = 1_usize;
+
+        for (i, ((bin, &main_mag), slot)) in self
+            .output
+            .iter()
+            .zip(main_mags)
+            .zip(&result.overlap_bins)
+            .enumerate()
+            .take(SPECTRUM_BINS)
+            .skip(1)
+        {
+            let overlap = main_mag * bin.norm() * scale;
+            slot.store(overlap.to_bits(), Ordering::Relaxed);
+            if overlap > peak_overlap {
+                peak_overlap = overlap;
+                peak_bin = i;
+            }
+        }
+        result.overlap_bins[0].store(0_u32, Ordering::Relaxed);
+
+        let target_freq = peak_bin as f32 * sample_rate / FFT_SIZE as f32;
+
+        let target_band: u32 = if target_freq < 500.0 {
+            0
+        } else if target_freq < 2000.0 {
+            1
+        } else if target_freq < 6000.0 {
+            2
+        } else {
+            3
+        };
+
+        let sc_mag_at_peak = self.output[peak_bin].norm() * scale;
+        let sc_db = 20.0 * sc_mag_at_peak.max(f32::MIN_POSITIVE).log10();
+        let suggested_threshold = (sc_db - 6.0).clamp(-60.0, 0.0);
+
+        result.target_band.store(target_band, Ordering::Relaxed);
+        result
+            .target_freq
+            .store(target_freq.to_bits(), Ordering::Relaxed);
+        result
+            .target_threshold_db
+            .store(suggested_threshold.to_bits(), Ordering::Relaxed);
+        result.ready.store(true, Ordering::Release);
+    }
+}
+
+/// Periodic window of FFT_SIZE points.
+fn window(kind: AnalyzerWindow) -> Vec<f32> {
+    let n = FFT_SIZE as f32;
+    (0..FFT_SIZE)
+        .map(|i| {
+            let x = std::f32::consts::TAU * i as f32 / n;
+            match kind {
+                AnalyzerWindow::Hann => 0.5 - 0.5 * x.cos(),
+                // 4-term, -92 dB sidelobes.
+                AnalyzerWindow::BlackmanHarris => {
+                    0.35875 - 0.48829 * x.cos() + 0.14128 * (2.0 * x).cos()
+                        - 0.01168 * (3.0 * x).cos()
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(window: AnalyzerWindow, overlap: AnalyzerOverlap) -> AnalyzerSettings {
+        AnalyzerSettings {
+            window,
+            overlap,
+            averaging_ms: 0.0,
+            floor_db: -200.0,
+        }
+    }
+
+    /// Push a sine at `freq_bin` (in FFT bins) until one frame is published.
+    fn run_sine(analyzer: &mut SpectrumAnalyzer, s: &AnalyzerSettings, freq_bin: f32) -> Vec<f32> {
+        let mut n = 0;
+        loop {
+            let x = (std::f32::consts::TAU * freq_bin * n as f32 / FFT_SIZE as f32).sin();
+            n += 1;
+            if analyzer.push(x, s) {
+                return analyzer.levels_db().to_vec();
+            }
+        }
+    }
+
+    #[test]
+    fn full_scale_sine_reads_zero_dbfs_under_both_windows() {
+        for window in [AnalyzerWindow::Hann, AnalyzerWindow::BlackmanHarris] {
+            let mut analyzer = SpectrumAnalyzer::new();
+            let levels = run_sine(
+                &mut analyzer,
+                &settings(window, AnalyzerOverlap::None),
+                64.0,
+            );
+            assert!(levels[64].abs() < 0.1, "{window:?}: {}", levels[64]);
+        }
+    }
+
+    #[test]
+    fn blackman_harris_leaks_less_than_hann() {
+        // A sine between bins leaks into its neighbours; 16 bins away the
+        // Blackman-Harris sidelobes sit well below Hann's.
+        let levels = |window| {
+            let mut analyzer = SpectrumAnalyzer::new();
+            run_sine(
+                &mut analyzer,
+                &settings(window, AnalyzerOverlap::None),
+                64.5,
+            )
+        };
+        let hann = levels(AnalyzerWindow::Hann);
+        let bh = levels(AnalyzerWindow::BlackmanHarris);
+        assert!(bh[80] < hann[80] - 10.0, "hann {} bh {}", hann[80], bh[80]);
+    }
+
+    #[test]
+    fn overlap_sets_frame_rate() {
+        for (overlap, expected) in [
+            (AnalyzerOverlap::None, 2),
+            (AnalyzerOverlap::Half, 4),
+            (AnalyzerOverlap::ThreeQuarter, 8),
+        ] {
+            let mut analyzer = SpectrumAnalyzer::new();
+            let s = settings(AnalyzerWindow::Hann, overlap);
+            let frames = (0..2 * FFT_SIZE).filter(|_| analyzer.push(0.0, &s)).count();
+            assert_eq!(frames, expected, "{overlap:?}");
+        }
+    }
+
+    #[test]
+    fn worker_publishes_frames_from_the_ring() {
+        let ring = AnalyzerRing::new();
+        let spectrum = SpectrumData::new();
+        let requested = AtomicBool::new(false);
+        let result = AnalysisResult::new();
+        let out = AnalyzerOutputs {
+            spectrum: &spectrum,
+            analysis_requested: &requested,
+            analysis_result: &result,
+        };
+        let s = settings(AnalyzerWindow::Hann, AnalyzerOverlap::None);
+        let mut worker = AnalyzerWorker::new();
+        let mut levels = vec![0.0; SPECTRUM_BINS];
+
+        // Split: a sine on the left only. Fed in two drains to cross a
+        // frame boundary mid-drain.
+        ring.set_split(true);
+        for half in 0..2 {
+            for n in half * FFT_SIZE / 2..(half + 1) * FFT_SIZE / 2 {
+                let x = (std::f32::consts::TAU * 64.0 * n as f32 / FFT_SIZE as f32).sin();
+                ring.push(x, 0.0);
+            }
+            worker.drain(&ring, &s, &out);
+        }
+        assert!(spectrum.read_into_slice(&mut levels));
+        assert!(spectrum.is_split());
+        assert!(levels[64].abs() < 0.1, "{}", levels[64]);
+        spectrum.read_right_into_slice(&mut levels);
+        assert!(levels[64] < -100.0, "{}", levels[64]);
+
+        // A masking request is answered on the next frame.
+        requested.store(true, Ordering::Relaxed);
+        for _ in 0..FFT_SIZE {
+            ring.push(0.0, 0.0);
+            ring.push_sidechain(0.0);
+        }
+        worker.drain(&ring, &s, &out);
+        assert!(!requested.load(Ordering::Relaxed));
+        assert!(result.ready.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn floor_clamps_silence_and_averaging_smooths_steps() {
+        let mut analyzer = SpectrumAnalyzer::new();
+        let mut s = settings(AnalyzerWindow::Hann, AnalyzerOverlap::None);
+        s.floor_db = -72.0;
+        for _ in 0..FFT_SIZE {
+            analyzer.push(0.0, &s);
+        }
+        assert!(analyzer.levels_db().iter().all(|&l| l == -72.0));
+
+        // With a long time constant the first loud frame only creeps up.
+        s.averaging_ms = 1000.0;
+        let levels = run_sine(&mut analyzer, &s, 64.0);
+        assert!(levels[64] < -3.0, "{}", levels[64]);
+        let mut instant = SpectrumAnalyzer::new();
+        s.averaging_ms = 0.0;
+        let levels = run_sine(&mut instant, &s, 64.0);
+        assert!(levels[64] > -0.1);
+    }
+}
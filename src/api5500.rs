@@ -1,9 +1,38 @@
+use crate::character::{EqCharacter, VintageStage};
+use crate::linear_phase::{
+    FirDesigner, KernelExchange, LinearPhaseFir, KERNEL_BINS, LATENCY_SAMPLES,
+};
 use crate::shaping::{Filter, FilterType};
-use biquad::Q_BUTTERWORTH_F32;
+use crate::stereo_biquad::StereoBiquads;
+use biquad::{Coefficients, Q_BUTTERWORTH_F32};
+#[cfg(feature = "plugin")]
 use nih_plug::buffer::Buffer;
+use realfft::num_complex::Complex;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
-pub struct Api5500 {
-    sample_rate: f32,
+/// `update_parameters` arguments, in order.
+const SETTINGS: usize = 13;
+/// Where each band's gain sits in the settings (LF, LMF, MF, HMF, HF).
+const GAIN_SETTINGS: [usize; 5] = [1, 3, 6, 9, 12];
+/// Where each bell's Q sits in the settings (LMF, MF, HMF), and its gain.
+const BELL_SETTINGS: [(usize, usize); 3] = [(4, 3), (7, 6), (10, 9)];
+/// Largest L/R offset per band, in dB.
+pub const MAX_LR_OFFSET_DB: f32 = 2.0;
+
+/// Vintage character: share of each band's gain its neighbours pick up
+/// through the shared summing amp.
+const VINTAGE_INTERACTION: f32 = 0.1;
+/// Vintage proportional Q: a bell's Q scales from `MIN` at 0 dB by `SPAN`
+/// per 12 dB of boost or cut, so it is as set at 6 dB.
+const PROPORTIONAL_Q_MIN: f32 = 0.6;
+const PROPORTIONAL_Q_SPAN: f32 = 0.8;
+/// Vintage electronics: allpass corner and noise floor.
+const VINTAGE_CORNER_HZ: f32 = 18_000.0;
+const VINTAGE_NOISE_DB: f32 = -100.0;
+
+/// The five biquads. Shared by the audio path and the background FIR
+/// designer so both clamp and voice the bands identically.
+struct Bands {
     lf: Filter,
     lmf: Filter,
     mf: Filter,
@@ -11,10 +40,9 @@ pub struct Api5500 {
     hf: Filter,
 }
 
-impl Api5500 {
-    pub fn new(sample_rate: f32) -> Self {
+impl Bands {
+    fn new(sample_rate: f32) -> Self {
         Self {
-            sample_rate,
             lf: Filter::new(
                 sample_rate,
                 FilterType::LowShelf,
@@ -53,22 +81,10 @@ impl Api5500 {
         }
     }
 
-    pub fn update_parameters(
-        &mut self,
-        lf_freq: f32,
-        lf_gain: f32,
-        lmf_freq: f32,
-        lmf_gain: f32,
-        lmf_q: f32,
-        mf_freq: f32,
-        mf_gain: f32,
-        mf_q: f32,
-        hmf_freq: f32,
-        hmf_gain: f32,
-        hmf_q: f32,
-        hf_freq: f32,
-        hf_gain: f32,
-    ) {
+    fn update(&mut self, sample_rate: f32, settings: &[f32; SETTINGS]) {
+        let [lf_freq, lf_gain, lmf_freq, lmf_gain, lmf_q, mf_freq, mf_gain, mf_q, hmf_freq, hmf_gain, hmf_q, hf_freq, hf_gain] =
+            *settings;
+
         // Limit gains to prevent instability and distortion
         let safe_lf_gain = lf_gain.clamp(-12.0, 12.0);
         let safe_lmf_gain = lmf_gain.clamp(-12.0, 12.0);
@@ -78,35 +94,30 @@ impl Api5500 {
 
         // Update filters with safe gains
         self.lf.update_parameters(
-            self.sample_rate,
+            sample_rate,
             FilterType::LowShelf,
             lf_freq,
             Q_BUTTERWORTH_F32,
             safe_lf_gain,
         );
         self.lmf.update_parameters(
-            self.sample_rate,
+            sample_rate,
             FilterType::Bell,
             lmf_freq,
             lmf_q,
             safe_lmf_gain,
         );
-        self.mf.update_parameters(
-            self.sample_rate,
-            FilterType::Bell,
-            mf_freq,
-            mf_q,
-            safe_mf_gain,
-        );
+        self.mf
+            .update_parameters(sample_rate, FilterType::Bell, mf_freq, mf_q, safe_mf_gain);
         self.hmf.update_parameters(
-            self.sample_rate,
+            sample_rate,
             FilterType::Bell,
             hmf_freq,
             hmf_q,
             safe_hmf_gain,
         );
         self.hf.update_parameters(
-            self.sample_rate,
+            sample_rate,
             FilterType::HighShelf,
             hf_freq,
             Q_BUTTERWORTH_F32,
@@ -114,19 +125,335 @@ impl Api5500 {
         );
     }
 
-    pub fn process(&mut self, buffer: &mut Buffer) {
-        for mut samples in buffer.iter_samples() {
-            for (ch, sample) in samples.iter_mut().enumerate() {
-                let ch = ch.min(1);
-                let mut s = *sample;
-                s = self.lf.run_ch(s, ch);
-                s = self.lmf.run_ch(s, ch);
-                s = self.mf.run_ch(s, ch);
-                s = self.hmf.run_ch(s, ch);
-                s = self.hf.run_ch(s, ch);
-                *sample = s;
+    fn coefficients(&self) -> [Coefficients<f64>; 5] {
+        [&self.lf, &self.lmf, &self.mf, &self.hmf, &self.hf].map(Filter::coefficients)
+    }
+
+    /// Combined linear magnitude at `omega` radians per sample.
+    fn magnitude(&self, omega: f32) -> f32 {
+        [&self.lf, &self.lmf, &self.mf, &self.hmf, &self.hf]
+            .iter()
+            .map(|band| band.magnitude(omega))
+            .product()
+    }
+}
+
+pub struct Api5500 {
+    sample_rate: f32,
+    /// Left channel's bands; both channels' unless an L/R offset is set.
+    bands: Bands,
+    /// Right channel's bands, for the L/R offsets.
+    bands_right: Bands,
+    /// Minimum-phase path: the five bands as one stereo cascade, L and R
+    /// filtered together in SIMD lanes.
+    chain: StereoBiquads<5>,
+    /// Per-band L/R offsets in effect (zero outside dual-mono mode).
+    lr_offsets: [f32; 5],
+    /// Linear-phase mode: the five bands' combined magnitude as one FIR,
+    /// designed on the background thread by `EqFirWorker`.
+    fir: LinearPhaseFir,
+    linear_phase: bool,
+    /// Last `update_parameters` arguments (NaN until the first call); a
+    /// change recomputes the biquads and marks the FIR stale.
+    settings: [f32; SETTINGS],
+    fir_stale: bool,
+    /// Vintage character: band interaction in the voicing, plus `vintage`.
+    character: EqCharacter,
+    vintage: VintageStage,
+}
+
+impl Api5500 {
+    pub fn new(sample_rate: f32) -> Self {
+        let bands = Bands::new(sample_rate);
+        Self {
+            sample_rate,
+            chain: StereoBiquads::new(bands.coefficients()),
+            bands,
+            bands_right: Bands::new(sample_rate),
+            lr_offsets: [0.0; 5],
+            fir: LinearPhaseFir::new(),
+            linear_phase: false,
+            settings: [f32::NAN; SETTINGS],
+            fir_stale: true,
+            character: EqCharacter::Clean,
+            vintage: VintageStage::new(sample_rate, VINTAGE_CORNER_HZ, VINTAGE_NOISE_DB),
+        }
+    }
+
+    /// Clean runs the filters as set. Vintage lets neighbouring bands pull
+    /// on each other, narrows the bells as they boost or cut, and adds the
+    /// electronics' top-octave phase lag and noise floor (the linear-phase
+    /// mode keeps its flat phase and takes only the noise).
+    pub fn set_character(&mut self, character: EqCharacter) {
+        if character == self.character {
+            return;
+        }
+        if character == EqCharacter::Vintage {
+            self.vintage.reset();
+        }
+        self.character = character;
+        self.fir_stale = true;
+        self.update_chain();
+    }
+
+    /// Level of the Vintage noise floor relative to its voiced level.
+    pub fn set_noise_gain(&mut self, gain: f32) {
+        self.vintage.set_noise_gain(gain);
+    }
+
+    /// Switch between the minimum-phase biquads and the linear-phase FIR.
+    /// Turning it on clears the FIR's history so no stale tail plays.
+    pub fn set_linear_phase(&mut self, on: bool) {
+        if on && !self.linear_phase {
+            self.fir.reset();
+        }
+        self.linear_phase = on;
+    }
+
+    /// Delay the host must compensate; only the linear-phase mode has any.
+    pub fn latency_samples(&self) -> u32 {
+        if self.linear_phase {
+            LATENCY_SAMPLES
+        } else {
+            0
+        }
+    }
+
+    pub fn update_parameters(
+        &mut self,
+        lf_freq: f32,
+        lf_gain: f32,
+        lmf_freq: f32,
+        lmf_gain: f32,
+        lmf_q: f32,
+        mf_freq: f32,
+        mf_gain: f32,
+        mf_q: f32,
+        hmf_freq: f32,
+        hmf_gain: f32,
+        hmf_q: f32,
+        hf_freq: f32,
+        hf_gain: f32,
+    ) {
+        let settings = [
+            lf_freq, lf_gain, lmf_freq, lmf_gain, lmf_q, mf_freq, mf_gain, mf_q, hmf_freq,
+            hmf_gain, hmf_q, hf_freq, hf_gain,
+        ];
+        // Called every buffer; the biquads and the FIR only need work when
+        // an argument actually changed.
+        if settings == self.settings {
+            return;
+        }
+        self.settings = settings;
+        self.fir_stale = true;
+        self.update_chain();
+    }
+
+    /// Per-band L/R offsets in dB (LF, LMF, MF, HMF, HF), for rebalancing a
+    /// stereo bus tonally. Only dual-mono mode applies them: each band's
+    /// gain moves up by half the offset on the left and down by half on the
+    /// right. The linear-phase FIR is one kernel for both channels and
+    /// ignores them.
+    pub fn set_lr_offsets(&mut self, dual_mono: bool, offsets: [f32; 5]) {
+        let offsets = if dual_mono {
+            offsets.map(|db| db.clamp(-MAX_LR_OFFSET_DB, MAX_LR_OFFSET_DB))
+        } else {
+            [0.0; 5]
+        };
+        if offsets == self.lr_offsets {
+            return;
+        }
+        self.lr_offsets = offsets;
+        self.update_chain();
+    }
+
+    /// Recompute the cascade from the settings and L/R offsets.
+    fn update_chain(&mut self) {
+        // Nothing to voice until the first `update_parameters`.
+        if self.settings[0].is_nan() {
+            return;
+        }
+        let voiced = self.voiced();
+        let (mut left, mut right) = (voiced, voiced);
+        for (&index, &offset) in GAIN_SETTINGS.iter().zip(&self.lr_offsets) {
+            left[index] += 0.5 * offset;
+            right[index] -= 0.5 * offset;
+        }
+        self.bands.update(self.sample_rate, &left);
+        self.bands_right.update(self.sample_rate, &right);
+        let sections = self
+            .bands
+            .coefficients()
+            .into_iter()
+            .zip(self.bands_right.coefficients());
+        for (section, (l, r)) in sections.enumerate() {
+            self.chain.set_channel_coefficients(section, l, r);
+        }
+    }
+
+    /// The settings the bands are built from: as set when clean, with the
+    /// vintage band interaction otherwise.
+    fn voiced(&self) -> [f32; SETTINGS] {
+        let mut voiced = self.settings;
+        if self.character == EqCharacter::Clean {
+            return voiced;
+        }
+        let gains = GAIN_SETTINGS.map(|index| self.settings[index].clamp(-12.0, 12.0));
+        for (band, &index) in GAIN_SETTINGS.iter().enumerate() {
+            let below = band.checked_sub(1).map_or(0.0, |b| gains[b]);
+            let above = gains.get(band + 1).copied().unwrap_or(0.0);
+            voiced[index] = gains[band] + VINTAGE_INTERACTION * (below + above);
+        }
+        for (q_index, gain_index) in BELL_SETTINGS {
+            let boost = self.settings[gain_index].abs().min(12.0) / 12.0;
+            voiced[q_index] *= PROPORTIONAL_Q_MIN + PROPORTIONAL_Q_SPAN * boost;
+        }
+        voiced
+    }
+
+    /// In linear-phase mode with a stale kernel, hand the settings to
+    /// `shared` and return true: the caller then queues
+    /// `BackgroundTask::DesignEqFir`. While a design is already queued the
+    /// request waits for a later buffer, so bursts of knob moves collapse.
+    pub fn request_fir_design(&mut self, shared: &EqFirShared) -> bool {
+        if !self.linear_phase || !self.fir_stale || !shared.try_queue() {
+            return false;
+        }
+        shared.store_request(self.sample_rate, &self.voiced());
+        self.fir_stale = false;
+        true
+    }
+
+    #[cfg(feature = "plugin")]
+    pub fn process(&mut self, buffer: &mut Buffer, shared: &EqFirShared) {
+        self.process_channels(buffer.as_slice(), shared);
+    }
+
+    /// Process one block given as per-channel slices (L, R; a lone channel
+    /// runs the mono path). The library entry point behind `process`.
+    pub fn process_channels(&mut self, channels: &mut [&mut [f32]], shared: &EqFirShared) {
+        if self.linear_phase {
+            self.fir.poll_kernel(&shared.kernel);
+            self.fir.set_flat(false);
+            self.fir.process(channels);
+        } else {
+            match channels {
+                [left, right, ..] => self.chain.process(left, right),
+                [mono] => self.chain.process_mono(mono),
+                [] => {}
             }
         }
+        if self.character == EqCharacter::Vintage {
+            self.vintage.process(channels, !self.linear_phase);
+        }
+    }
+
+    /// Bypassed path. In linear-phase mode the signal still runs through the
+    /// flat kernel so its delay matches the reported latency.
+    #[cfg(feature = "plugin")]
+    pub fn process_bypassed(&mut self, buffer: &mut Buffer) {
+        self.process_channels_bypassed(buffer.as_slice());
+    }
+
+    /// Slice form of `process_bypassed`.
+    pub fn process_channels_bypassed(&mut self, channels: &mut [&mut [f32]]) {
+        if !self.linear_phase {
+            return;
+        }
+        self.fir.set_flat(true);
+        self.fir.process(channels);
+    }
+
+    pub fn reset(&mut self) {
+        self.chain.reset();
+        self.fir.reset();
+        self.vintage.reset();
+    }
+
+    /// Clear the biquad history only. The FIR keeps running while bypassed
+    /// in linear-phase mode, so its delay line must not be cut.
+    pub fn reset_iir(&mut self) {
+        self.chain.reset();
+    }
+}
+
+/// Audio ↔ background state for linear-phase mode. The audio thread posts
+/// settings and the background thread posts kernels back; all lock-free.
+pub struct EqFirShared {
+    settings: [AtomicU32; SETTINGS],
+    sample_rate: AtomicU32,
+    /// A design task is queued or running.
+    queued: AtomicBool,
+    kernel: KernelExchange,
+}
+
+impl EqFirShared {
+    pub fn new() -> Self {
+        Self {
+            settings: std::array::from_fn(|_| AtomicU32::new(0)),
+            sample_rate: AtomicU32::new(44100.0_f32.to_bits()),
+            queued: AtomicBool::new(false),
+            kernel: KernelExchange::new(),
+        }
+    }
+
+    fn try_queue(&self) -> bool {
+        !self.queued.swap(true, Ordering::AcqRel)
+    }
+
+    fn store_request(&self, sample_rate: f32, settings: &[f32; SETTINGS]) {
+        self.sample_rate
+            .store(sample_rate.to_bits(), Ordering::Relaxed);
+        for (slot, &value) in self.settings.iter().zip(settings) {
+            slot.store(value.to_bits(), Ordering::Relaxed);
+        }
+    }
+}
+
+impl Default for EqFirShared {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Background-thread designer for linear-phase mode. Owned by the task
+/// executor behind a Mutex the audio thread never touches.
+pub struct EqFirWorker {
+    bands: Bands,
+    designer: FirDesigner,
+    kernel: Vec<Complex<f32>>,
+}
+
+impl EqFirWorker {
+    pub fn new() -> Self {
+        Self {
+            bands: Bands::new(44100.0),
+            designer: FirDesigner::new(),
+            kernel: vec![Complex::new(0.0, 0.0); KERNEL_BINS],
+        }
+    }
+
+    /// Design the kernel for the posted settings and publish it.
+    pub fn run(&mut self, shared: &EqFirShared) {
+        // The task queue orders these loads after the audio thread's stores.
+        // Clearing `queued` only afterwards keeps the audio thread from
+        // rewriting the settings mid-read; a change made meanwhile queues
+        // another run once the flag drops.
+        let sample_rate = f32::from_bits(shared.sample_rate.load(Ordering::Relaxed));
+        let settings: [f32; SETTINGS] =
+            std::array::from_fn(|i| f32::from_bits(shared.settings[i].load(Ordering::Relaxed)));
+        shared.queued.store(false, Ordering::Release);
+        self.bands.update(sample_rate, &settings);
+        let bands = &self.bands;
+        self.designer
+            .design(|omega| bands.magnitude(omega), &mut self.kernel);
+        shared.kernel.publish(&self.kernel);
+    }
+}
+
+impl Default for EqFirWorker {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -170,8 +497,12 @@ mod tests {
             100.0, 100.0, // lf +100 dB — must be clamped to +12
             300.0, 100.0, 0.7, 1000.0, 100.0, 1.0, 5000.0, 100.0, 1.2, 12000.0, 100.0,
         );
-        // Processing a sample should not produce NaN or ±inf
-        // We cannot call process() without a Buffer, so we verify the update didn't crash.
+        // Processing should not produce NaN or ±inf
+        let shared = EqFirShared::new();
+        let mut left: Vec<f32> = (0..512).map(|i| (i as f32 * 0.05).sin() * 0.5).collect();
+        let mut right = left.clone();
+        eq.process_channels(&mut [&mut left[..], &mut right[..]], &shared);
+        assert!(left.iter().chain(&right).all(|x| x.is_finite()));
     }
 
     #[test]
@@ -185,11 +516,89 @@ mod tests {
 
     #[test]
     fn test_api5500_multiple_sample_rates() {
-        for &sr in &[22050.0, 44100.0, 48000.0, 88200.0, 96000.0_f32] {
+        for &sr in &[
+            22050.0,
+            44100.0,
+            48000.0,
+            88200.0,
+            96000.0,
+            176400.0,
+            192000.0_f32,
+        ] {
             let mut eq = Api5500::new(sr);
             eq.update_parameters(
                 200.0, 3.0, 500.0, 2.0, 0.7, 2000.0, -1.0, 1.0, 8000.0, 1.0, 1.0, 15000.0, -2.0,
             );
         }
     }
+
+    /// Peak level in dB of each channel after a 1 kHz sine through `eq`.
+    fn sine_levels_db(eq: &mut Api5500) -> (f32, f32) {
+        let shared = EqFirShared::new();
+        let sine: Vec<f32> = (0..48_000)
+            .map(|i| (std::f32::consts::TAU * 1000.0 * i as f32 / 48_000.0).sin() * 0.25)
+            .collect();
+        let (mut left, mut right) = (sine.clone(), sine);
+        eq.process_channels(&mut [&mut left[..], &mut right[..]], &shared);
+        let peak_db = |ch: &[f32]| {
+            let peak = ch[24_000..].iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+            20.0 * (peak / 0.25).log10()
+        };
+        (peak_db(&left), peak_db(&right))
+    }
+
+    #[test]
+    fn test_api5500_lr_offset_splits_the_band_in_dual_mono_only() {
+        let mut eq = Api5500::new(48000.0);
+        eq.update_parameters(
+            100.0, 0.0, 300.0, 0.0, 0.7, 1000.0, 4.0, 1.0, 5000.0, 0.0, 1.2, 12000.0, 0.0,
+        );
+        let offsets = [0.0, 0.0, 2.0, 0.0, 0.0];
+
+        eq.set_lr_offsets(false, offsets);
+        let (l, r) = sine_levels_db(&mut eq);
+        assert!((l - r).abs() < 0.01, "stereo mode: L {l} R {r}");
+
+        eq.set_lr_offsets(true, offsets);
+        let (l, r) = sine_levels_db(&mut eq);
+        assert!((l - 5.0).abs() < 0.1, "L {l}");
+        assert!((r - 3.0).abs() < 0.1, "R {r}");
+
+        // Out-of-range offsets clamp to ±2 dB.
+        eq.set_lr_offsets(true, [0.0, 0.0, -9.0, 0.0, 0.0]);
+        let (l, r) = sine_levels_db(&mut eq);
+        assert!((r - l - 2.0).abs() < 0.1, "L {l} R {r}");
+    }
+
+    #[test]
+    fn test_api5500_vintage_bands_pull_their_neighbours() {
+        // LMF boosted at 300 Hz, MF flat at 1 kHz: vintage lends the MF
+        // band a tenth of the LMF boost. At 6 dB proportional Q leaves the
+        // LMF bell as set, so the difference at 1 kHz is the MF pull alone.
+        let mut eq = Api5500::new(48000.0);
+        eq.update_parameters(
+            100.0, 0.0, 300.0, 6.0, 0.7, 1000.0, 0.0, 1.0, 5000.0, 0.0, 1.2, 12000.0, 0.0,
+        );
+        let (clean, _) = sine_levels_db(&mut eq);
+        eq.set_character(EqCharacter::Vintage);
+        let (vintage, _) = sine_levels_db(&mut eq);
+        assert!(
+            (vintage - clean - 0.6).abs() < 0.1,
+            "clean {clean} vintage {vintage}"
+        );
+
+        eq.set_character(EqCharacter::Clean);
+        let (back, _) = sine_levels_db(&mut eq);
+        assert!((back - clean).abs() < 0.01, "clean again {back}");
+    }
+
+    #[test]
+    fn test_api5500_latency_follows_linear_phase() {
+        let mut eq = Api5500::new(48000.0);
+        assert_eq!(eq.latency_samples(), 0);
+        eq.set_linear_phase(true);
+        assert_eq!(eq.latency_samples(), LATENCY_SAMPLES);
+        eq.set_linear_phase(false);
+        assert_eq!(eq.latency_samples(), 0);
+    }
 }
@@ -0,0 +1,103 @@
+//! Host automation surface.
+//!
+//! Lean automation hides the parameters hardly anyone automates — detector
+//! and key EQ settings, oversampling, models and character switches, rack
+//! layout and the analyzer — from the host's automation list and generic
+//! UI. Hidden parameters are still saved with the session and presets, and
+//! the editor shows and changes them as before.
+//!
+//! It's on in builds with the `lean_automation` feature. The
+//! `BCS_LEAN_AUTOMATION` environment variable (`1` or `0`) overrides the
+//! build either way. Hosts read parameter flags when they load the plugin,
+//! so a change takes effect on the next load.
+
+use nih_plug::prelude::*;
+use std::sync::OnceLock;
+
+/// Whether this build hides rarely automated parameters by default.
+const BUILT_LEAN: bool = cfg!(feature = "lean_automation");
+
+/// Whether rarely automated parameters are hidden from the host. Read once
+/// per process.
+pub fn lean() -> bool {
+    static LEAN: OnceLock<bool> = OnceLock::new();
+    *LEAN.get_or_init(|| {
+        resolve(
+            std::env::var("BCS_LEAN_AUTOMATION").ok().as_deref(),
+            BUILT_LEAN,
+        )
+    })
+}
+
+/// The build's choice unless the environment says `1`/`true`/`on` or
+/// `0`/`false`/`off`; anything else is ignored.
+fn resolve(env: Option<&str>, built: bool) -> bool {
+    match env.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        Some("1" | "true" | "on") => true,
+        Some("0" | "false" | "off") => false,
+        _ => built,
+    }
+}
+
+/// Marks a parameter as one hosts can do without (see the module docs).
+pub trait RarelyAutomated: Sized {
+    /// Hide the parameter from the host under lean automation.
+    fn rarely_automated(self) -> Self;
+}
+
+impl RarelyAutomated for FloatParam {
+    fn rarely_automated(self) -> Self {
+        if lean() {
+            self.hide()
+        } else {
+            self
+        }
+    }
+}
+
+impl RarelyAutomated for IntParam {
+    fn rarely_automated(self) -> Self {
+        if lean() {
+            self.hide()
+        } else {
+            self
+        }
+    }
+}
+
+impl RarelyAutomated for BoolParam {
+    fn rarely_automated(self) -> Self {
+        if lean() {
+            self.hide()
+        } else {
+            self
+        }
+    }
+}
+
+impl<T: Enum + PartialEq + 'static> RarelyAutomated for EnumParam<T> {
+    fn rarely_automated(self) -> Self {
+        if lean() {
+            self.hide()
+        } else {
+            self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn environment_overrides_the_build_either_way() {
+        assert!(resolve(Some("1"), false));
+        assert!(resolve(Some(" On "), false));
+        assert!(!resolve(Some("0"), true));
+        assert!(!resolve(Some("false"), true));
+        assert!(resolve(None, true));
+        assert!(!resolve(None, false));
+        assert!(resolve(Some("maybe"), true));
+        assert!(!resolve(Some(""), false));
+    }
+}
@@ -0,0 +1,224 @@
+// src/balance.rs
+// Post-compressor stereo trim and balance compensation (ButterComp2 slot).
+//
+// The Classic core runs four interleaved compressors per channel, and every
+// model here detects linked but can still land a fraction of a dB apart on
+// material that is louder on one side. `BalanceTrim` follows the compressor:
+//
+//   • a fixed per-channel trim, for matching a known offset by hand
+//   • optional auto balance: mean-square levels of the compressor's input
+//     and output over a sliding ~300 ms window give the L/R ratio before and
+//     after; any shift between the two is split evenly between the channels
+//     and cancelled, up to ±3 dB
+//
+// The correction only moves while both channels are above -60 dBFS, so
+// fades and silence hold the last value instead of chasing noise. It is
+// recomputed once per block and ramped in per sample.
+
+use crate::envelope::coeff_for_ms;
+
+/// Sliding RMS window for the balance measurement.
+const WINDOW_MS: f32 = 300.0;
+/// Largest balance correction, in dB (applied as ± half to each side).
+pub const MAX_CORRECTION_DB: f32 = 3.0;
+/// Mean-square floor (-60 dBFS) below which the measurement holds.
+const LEVEL_FLOOR: f32 = 1.0e-6;
+/// Ramp time constant for gain changes.
+const RAMP_MS: f32 = 20.0;
+
+pub struct BalanceTrim {
+    window_coeff: f32,
+    ramp_coeff: f32,
+    /// Mean-square level per channel, compressor input and output.
+    in_ms: [f32; 2],
+    out_ms: [f32; 2],
+    /// Balance correction the ramp is heading for, in dB (+ = raise L).
+    correction_db: f32,
+    /// Current per-channel gain.
+    gain: [f32; 2],
+}
+
+impl BalanceTrim {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut trim = Self {
+            window_coeff: 0.0,
+            ramp_coeff: 0.0,
+            in_ms: [0.0; 2],
+            out_ms: [0.0; 2],
+            correction_db: 0.0,
+            gain: [1.0; 2],
+        };
+        trim.set_sample_rate(sample_rate);
+        trim
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.window_coeff = coeff_for_ms(WINDOW_MS, sample_rate);
+        self.ramp_coeff = coeff_for_ms(RAMP_MS, sample_rate);
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.in_ms = [0.0; 2];
+        self.out_ms = [0.0; 2];
+        self.correction_db = 0.0;
+        self.gain = [1.0; 2];
+    }
+
+    /// Current auto-balance correction in dB (+ = left raised).
+    #[cfg(test)]
+    pub fn correction_db(&self) -> f32 {
+        self.correction_db
+    }
+
+    /// Measure the compressor's input. Call before the compressor runs.
+    pub fn measure_input(&mut self, input: &[&mut [f32]]) {
+        if input.len() < 2 {
+            return;
+        }
+        for (ms, ch) in self.in_ms.iter_mut().zip(input) {
+            *ms = mean_square(*ms, ch, self.window_coeff);
+        }
+    }
+
+    /// Measure the compressor's output, then apply trim and (when `auto`)
+    /// the balance correction in place. Mono buffers only get the left trim.
+    pub fn process(&mut self, output: &mut [&mut [f32]], trim_db: [f32; 2], auto: bool) {
+        if output.len() >= 2 {
+            for (ms, ch) in self.out_ms.iter_mut().zip(output.iter()) {
+                *ms = mean_square(*ms, ch, self.window_coeff);
+            }
+        }
+        if !auto || output.len() < 2 {
+            self.correction_db = 0.0;
+        } else if self
+            .in_ms
+            .iter()
+            .chain(&self.out_ms)
+            .all(|&ms| ms > LEVEL_FLOOR)
+        {
+            // Output L/R ratio over input L/R ratio, as a power ratio.
+            let shift = (self.out_ms[0] * self.in_ms[1]) / (self.out_ms[1] * self.in_ms[0]);
+            self.correction_db =
+                (-10.0 * shift.log10()).clamp(-MAX_CORRECTION_DB, MAX_CORRECTION_DB);
+        }
+
+        let half = 0.5 * self.correction_db;
+        let target = [db_to_gain(trim_db[0] + half), db_to_gain(trim_db[1] - half)];
+        for ((ch, gain), target) in output.iter_mut().zip(&mut self.gain).zip(target) {
+            for s in ch.iter_mut() {
+                *gain = target + (*gain - target) * self.ramp_coeff;
+                *s *= *gain;
+            }
+        }
+    }
+}
+
+fn mean_square(mut ms: f32, samples: &[f32], coeff: f32) -> f32 {
+    for &x in samples {
+        ms = x * x + (ms - x * x) * coeff;
+    }
+    if ms < 1e-20 {
+        0.0
+    } else {
+        ms
+    }
+}
+
+fn db_to_gain(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    fn sine(amp: f32, n: usize) -> Vec<f32> {
+        (0..n)
+            .map(|i| amp * (std::f32::consts::TAU * 440.0 * i as f32 / SR).sin())
+            .collect()
+    }
+
+    fn rms(x: &[f32]) -> f32 {
+        (x.iter().map(|s| s * s).sum::<f32>() / x.len() as f32).sqrt()
+    }
+
+    /// Run 200 512-sample blocks of sines at the `input` levels through a
+    /// "compressor" that scales each side by `comp`; return the last block.
+    fn run(
+        trim: &mut BalanceTrim,
+        input: [f32; 2],
+        comp: [f32; 2],
+        trim_db: [f32; 2],
+        auto: bool,
+    ) -> (Vec<f32>, Vec<f32>) {
+        let mut last = (Vec::new(), Vec::new());
+        for _ in 0..200 {
+            let (mut l, mut r) = (sine(input[0], 512), sine(input[1], 512));
+            trim.measure_input(&[&mut l[..], &mut r[..]]);
+            l.iter_mut().for_each(|s| *s *= comp[0]);
+            r.iter_mut().for_each(|s| *s *= comp[1]);
+            trim.process(&mut [&mut l[..], &mut r[..]], trim_db, auto);
+            last = (l, r);
+        }
+        last
+    }
+
+    #[test]
+    fn trim_applies_per_channel() {
+        let mut trim = BalanceTrim::new(SR);
+        let (l, r) = run(&mut trim, [0.5, 0.5], [1.0, 1.0], [-6.0, 2.0], false);
+        assert!((rms(&l) / rms(&sine(0.5, 512)) - db_to_gain(-6.0)).abs() < 1e-3);
+        assert!((rms(&r) / rms(&sine(0.5, 512)) - db_to_gain(2.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn auto_balance_restores_the_input_ratio() {
+        // Louder-left input; the compressor pulls the left 1 dB further down.
+        let mut trim = BalanceTrim::new(SR);
+        let input = [0.6, 0.3];
+        let (l, r) = run(
+            &mut trim,
+            input,
+            [db_to_gain(-3.0), db_to_gain(-2.0)],
+            [0.0; 2],
+            true,
+        );
+        let in_ratio = 20.0 * (input[0] / input[1]).log10();
+        let out_ratio = 20.0 * (rms(&l) / rms(&r)).log10();
+        assert!(
+            (out_ratio - in_ratio).abs() < 0.05,
+            "{out_ratio} vs {in_ratio}"
+        );
+        assert!((trim.correction_db() - 1.0).abs() < 0.05);
+
+        // Off, the shift stays.
+        let mut plain = BalanceTrim::new(SR);
+        let (l, r) = run(
+            &mut plain,
+            input,
+            [db_to_gain(-3.0), db_to_gain(-2.0)],
+            [0.0; 2],
+            false,
+        );
+        let out_ratio = 20.0 * (rms(&l) / rms(&r)).log10();
+        assert!((out_ratio - (in_ratio - 1.0)).abs() < 0.05);
+    }
+
+    #[test]
+    fn correction_is_bounded_and_holds_in_silence() {
+        let mut trim = BalanceTrim::new(SR);
+        run(
+            &mut trim,
+            [0.5, 0.5],
+            [db_to_gain(-12.0), 1.0],
+            [0.0; 2],
+            true,
+        );
+        assert_eq!(trim.correction_db(), MAX_CORRECTION_DB);
+        run(&mut trim, [0.0, 0.0], [1.0, 1.0], [0.0; 2], true);
+        assert_eq!(trim.correction_db(), MAX_CORRECTION_DB);
+    }
+}
@@ -0,0 +1,246 @@
+// src/bench.rs
+// Benchmark and golden-test support (the `bench` feature, and every test
+// build with all modules): every DSP module at a fixed, engaged setting, so
+// `benches/dsp.rs` measures each module's `process()` and the full chain
+// without a host, and `golden_tests` pins their output. `property_tests`
+// drives the same modules with randomized parameters instead, and
+// `unity_tests` with every control at neutral. Settings are chosen to make
+// each module do real work — EQ bands cut or boosted, compressors pulling
+// gain, Punch oversampling at 4× — not the bypass-off defaults.
+//
+// Not part of the plugin; nothing here is reachable from `process()`.
+
+use crate::api5500::{Api5500, EqFirShared};
+use crate::buttercomp2::{ButterComp2, FetCompressor, FetRatio, OpticalCompressor, VcaCompressor};
+use crate::dynamic_eq::{DynamicBandParams, DynamicEQ, DynamicMode};
+use crate::haas::{CombMode, HaasModule};
+use crate::pultec::PultecEQ;
+use crate::punch::{ClipMode, OversamplingFactor, PunchModule};
+use crate::sheen::SheenModule;
+use crate::transformer::{TransformerModel, TransformerModule};
+use nih_plug::buffer::Buffer;
+
+/// One benchmarked processing stage. The compressor slot is split by model
+/// since the four share nothing but the slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Api5500,
+    CompClassic,
+    CompVca,
+    CompOptical,
+    CompFet,
+    Pultec,
+    DynamicEq,
+    Transformer,
+    Haas,
+    Punch,
+    Sheen,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 11] = [
+        Stage::Api5500,
+        Stage::CompClassic,
+        Stage::CompVca,
+        Stage::CompOptical,
+        Stage::CompFet,
+        Stage::Pultec,
+        Stage::DynamicEq,
+        Stage::Transformer,
+        Stage::Haas,
+        Stage::Punch,
+        Stage::Sheen,
+    ];
+
+    /// The default slot order (Classic compressor, Dynamic EQ in the empty
+    /// slot), then Sheen, which is pinned last.
+    pub const CHAIN: [Stage; 8] = [
+        Stage::Api5500,
+        Stage::CompClassic,
+        Stage::Pultec,
+        Stage::Transformer,
+        Stage::Haas,
+        Stage::Punch,
+        Stage::DynamicEq,
+        Stage::Sheen,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Stage::Api5500 => "api5500",
+            Stage::CompClassic => "comp_classic",
+            Stage::CompVca => "comp_vca",
+            Stage::CompOptical => "comp_optical",
+            Stage::CompFet => "comp_fet",
+            Stage::Pultec => "pultec",
+            Stage::DynamicEq => "dynamic_eq",
+            Stage::Transformer => "transformer",
+            Stage::Haas => "haas",
+            Stage::Punch => "punch",
+            Stage::Sheen => "sheen",
+        }
+    }
+}
+
+const OPTICAL_THRESH_DB: f32 = -24.0;
+
+fn dyneq_band(freq: f32, mode: DynamicMode) -> DynamicBandParams {
+    DynamicBandParams {
+        mode,
+        detector_freq: freq,
+        freq,
+        q: 1.5,
+        threshold_db: -30.0,
+        ratio: 4.0,
+        attack_ms: 5.0,
+        release_ms: 80.0,
+        gain_db: 0.0,
+        enabled: true,
+        solo: false,
+    }
+}
+
+/// Every module at one sample rate, parameters set once at construction.
+pub struct BenchStrip {
+    api5500: Api5500,
+    eq_fir: EqFirShared,
+    classic: ButterComp2,
+    vca: VcaCompressor,
+    optical: OpticalCompressor,
+    fet: FetCompressor,
+    pultec: PultecEQ,
+    dynamic_eq: DynamicEQ,
+    transformer: TransformerModule,
+    haas: HaasModule,
+    punch: PunchModule,
+    sheen: SheenModule,
+}
+
+impl BenchStrip {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut strip = Self {
+            api5500: Api5500::new(sample_rate),
+            eq_fir: EqFirShared::new(),
+            classic: ButterComp2::new(sample_rate),
+            vca: VcaCompressor::new(sample_rate),
+            optical: OpticalCompressor::new(sample_rate),
+            fet: FetCompressor::new(sample_rate),
+            pultec: PultecEQ::new(sample_rate),
+            dynamic_eq: DynamicEQ::new(sample_rate),
+            transformer: TransformerModule::new(sample_rate),
+            haas: HaasModule::new(sample_rate),
+            punch: PunchModule::new(sample_rate),
+            sheen: SheenModule::new(sample_rate),
+        };
+        strip.api5500.update_parameters(
+            80.0, 3.0, 250.0, -2.0, 1.0, 1200.0, 2.0, 1.5, 4000.0, -3.0, 2.0, 12000.0, 2.5,
+        );
+        strip.classic.update_parameters(0.6, 0.5, 1.0, false);
+        strip.vca.update_parameters(-24.0, 4.0, 10.0, 100.0, 80.0);
+        strip.optical.update_parameters(OPTICAL_THRESH_DB, 0.5, 0.5);
+        strip
+            .fet
+            .update_parameters(12.0, 0.0, 0.2, 200.0, FetRatio::R8, false, 80.0);
+        strip.pultec.update_parameters(
+            60.0, 6.0, 0.5, 60.0, 3.0, 0.5, 8000.0, 5.0, 0.5, 10000.0, 2.0, 0.4,
+        );
+        strip.dynamic_eq.update_parameters(&[
+            dyneq_band(120.0, DynamicMode::CompressDownward),
+            dyneq_band(600.0, DynamicMode::CompressDownward),
+            dyneq_band(3000.0, DynamicMode::ExpandUpward),
+            dyneq_band(8000.0, DynamicMode::Gate),
+        ]);
+        strip.transformer.update_parameters(
+            TransformerModel::Vintage,
+            0.5,
+            0.5,
+            0.5,
+            0.5,
+            0.3,
+            -0.2,
+            0.4,
+        );
+        strip
+            .haas
+            .update_parameters(1.0, 1.4, 0.5, 8.0, CombMode::SideComb, 1.0);
+        strip.punch.update_parameters(
+            -3.0,
+            ClipMode::Soft,
+            0.5,
+            OversamplingFactor::X4,
+            0.5,
+            -0.3,
+            5.0,
+            100.0,
+            0.5,
+            3.0,
+            0.0,
+            1.0,
+            80.0,
+        );
+        strip.sheen.update_parameters(
+            false, 1.0, false, 1.0, false, 1.5, false, 0.5, false, 0.5, false,
+        );
+        strip
+    }
+
+    pub fn process(&mut self, stage: Stage, buffer: &mut Buffer) {
+        match stage {
+            Stage::Api5500 => self.api5500.process(buffer, &self.eq_fir),
+            Stage::CompClassic => self.classic.process(buffer),
+            Stage::CompVca => self.vca.process(buffer),
+            Stage::CompOptical => self.optical.process(buffer, OPTICAL_THRESH_DB),
+            Stage::CompFet => self.fet.process(buffer),
+            Stage::Pultec => self.pultec.process(buffer),
+            Stage::DynamicEq => self.dynamic_eq.process(buffer),
+            Stage::Transformer => self.transformer.process(buffer),
+            Stage::Haas => self.haas.process(buffer),
+            Stage::Punch => self.punch.process(buffer),
+            Stage::Sheen => self.sheen.process(buffer),
+        }
+    }
+
+    /// Every module in chain order.
+    pub fn process_chain(&mut self, buffer: &mut Buffer) {
+        for stage in Stage::CHAIN {
+            self.process(stage, buffer);
+        }
+    }
+}
+
+/// Host-sized blocks for the test suites, so per-buffer parameter and
+/// metering paths run too.
+#[cfg(test)]
+const BLOCK: usize = 512;
+
+/// Run `left`/`right` through `process` in `BLOCK`-sized buffers (the last
+/// may be shorter) and return the result. Every test suite below feeds its
+/// modules through this.
+#[cfg(test)]
+fn render_blocks(
+    left: &[f32],
+    right: &[f32],
+    mut process: impl FnMut(&mut Buffer),
+) -> (Vec<f32>, Vec<f32>) {
+    let (mut left, mut right) = (left.to_vec(), right.to_vec());
+    for (l, r) in left.chunks_mut(BLOCK).zip(right.chunks_mut(BLOCK)) {
+        let mut buffer = Buffer::default();
+        // SAFETY: the slices borrow `left`/`right`, which outlive `buffer`.
+        unsafe {
+            buffer.set_slices(l.len(), |slices| {
+                slices.clear();
+                slices.push(l);
+                slices.push(r);
+            });
+        }
+        process(&mut buffer);
+    }
+    (left, right)
+}
+
+#[cfg(test)]
+mod golden_tests;
+#[cfg(test)]
+mod property_tests;
+#[cfg(test)]
+mod unity_tests;
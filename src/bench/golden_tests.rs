@@ -0,0 +1,148 @@
+//! Golden-file regression tests — every module, and the full chain, run over
+//! the WAV fixtures in `tests/golden/input/` at the fixed settings in
+//! `bench.rs`, compared against the references in `tests/golden/expected/`.
+//!
+//! These catch what the unit tests can't: a change anywhere in a module (or
+//! in the ButterComp2 C++ behind the FFI, or a native port replacing it)
+//! that keeps the output finite and plausible but changes the sound.
+//!
+//! A missing reference is recorded on first run, except under CI, where it
+//! fails. After an intentional sound change, re-record with
+//! `BLESS_GOLDEN=1 cargo test golden` and commit the new files.
+
+use super::{render_blocks, BenchStrip, Stage};
+use nih_plug::buffer::Buffer;
+use std::path::{Path, PathBuf};
+
+const SAMPLE_RATE: f32 = 48_000.0;
+/// -80 dBFS: well below audibility, well above cross-platform float noise
+/// (SSE2 vs NEON vs scalar lanes, compiler differences in the C++).
+const TOLERANCE: f32 = 1.0e-4;
+const INPUTS: [&str; 2] = ["program", "transients"];
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn read_wav(path: &Path) -> [Vec<f32>; 2] {
+    let mut reader =
+        hound::WavReader::open(path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    let spec = reader.spec();
+    assert_eq!(spec.channels, 2, "{}: fixtures are stereo", path.display());
+    assert_eq!(spec.sample_rate, SAMPLE_RATE as u32, "{}", path.display());
+    let interleaved: Vec<f32> = reader
+        .samples::<f32>()
+        .collect::<Result<_, _>>()
+        .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    let left = interleaved.iter().step_by(2).copied().collect();
+    let right = interleaved.iter().skip(1).step_by(2).copied().collect();
+    [left, right]
+}
+
+fn write_wav(path: &Path, channels: &[Vec<f32>; 2]) {
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: SAMPLE_RATE as u32,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(path, spec).expect("create reference");
+    for (l, r) in channels[0].iter().zip(&channels[1]) {
+        writer.write_sample(*l).expect("write reference");
+        writer.write_sample(*r).expect("write reference");
+    }
+    writer.finalize().expect("finalize reference");
+}
+
+/// Run `input` through a fresh strip.
+fn render(input: &[Vec<f32>; 2], run: impl Fn(&mut BenchStrip, &mut Buffer)) -> [Vec<f32>; 2] {
+    let mut strip = BenchStrip::new(SAMPLE_RATE);
+    let (left, right) = render_blocks(&input[0], &input[1], |buffer| run(&mut strip, buffer));
+    [left, right]
+}
+
+/// Compare against the stored reference, recording it if it's missing (or
+/// when blessing). Returns a description of the mismatch, if any.
+fn check(name: &str, output: &[Vec<f32>; 2]) -> Option<String> {
+    let path = golden_dir().join("expected").join(format!("{name}.wav"));
+    let bless = std::env::var_os("BLESS_GOLDEN").is_some();
+    if bless || !path.exists() {
+        if !bless && std::env::var_os("CI").is_some() {
+            return Some(format!("{name}: no reference at {}", path.display()));
+        }
+        std::fs::create_dir_all(path.parent().unwrap()).expect("create expected/");
+        write_wav(&path, output);
+        eprintln!("recorded {}", path.display());
+        return None;
+    }
+    let expected = read_wav(&path);
+    for (ch, (got, want)) in output.iter().zip(&expected).enumerate() {
+        if got.len() != want.len() {
+            return Some(format!(
+                "{name}: length {} vs reference {}",
+                got.len(),
+                want.len()
+            ));
+        }
+        let (i, diff) = got
+            .iter()
+            .zip(want)
+            .map(|(a, b)| (a - b).abs())
+            .enumerate()
+            .fold((0, 0.0_f32), |worst, (i, d)| {
+                // NaN compares false, so catch it explicitly.
+                if d.is_nan() || d > worst.1 {
+                    (i, if d.is_nan() { f32::INFINITY } else { d })
+                } else {
+                    worst
+                }
+            });
+        if diff > TOLERANCE {
+            return Some(format!(
+                "{name}: channel {ch} sample {i} differs by {diff:e} ({:.1} dB)",
+                20.0 * diff.log10()
+            ));
+        }
+    }
+    None
+}
+
+/// Every input through `run`, collecting mismatches so one run reports all
+/// of them.
+fn check_all(label: &str, run: impl Fn(&mut BenchStrip, &mut Buffer) + Copy) -> Vec<String> {
+    INPUTS
+        .iter()
+        .filter_map(|input| {
+            let path = golden_dir().join("input").join(format!("{input}.wav"));
+            let output = render(&read_wav(&path), run);
+            check(&format!("{label}_{input}"), &output)
+        })
+        .collect()
+}
+
+#[test]
+fn golden_modules_match_references() {
+    let failures: Vec<String> = Stage::ALL
+        .iter()
+        .flat_map(|&stage| {
+            check_all(stage.name(), move |strip, buffer| {
+                strip.process(stage, buffer)
+            })
+        })
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "golden mismatches:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn golden_chain_matches_reference() {
+    let failures = check_all("chain", BenchStrip::process_chain);
+    assert!(
+        failures.is_empty(),
+        "golden mismatches:\n{}",
+        failures.join("\n")
+    );
+}
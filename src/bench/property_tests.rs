@@ -0,0 +1,359 @@
+//! Property tests — every module, with randomized parameters across its full
+//! host range and a random sample rate, fed the inputs that break DSP code:
+//! silence, DC, full-scale square waves, alternating ±1 (Nyquist) and
+//! denormal-range values. Every run ends on a block of silence so decaying
+//! state passes through the subnormal range too.
+//!
+//! The output must stay finite and below a ceiling derived from the
+//! parameters (the most gain the settings can legitimately apply, plus
+//! 12 dB). Catches NaN/inf from `log10(0)`-style hazards, runaway
+//! feedback, and gain computers that leave their range.
+
+use super::{render_blocks, BLOCK};
+use crate::api5500::{Api5500, EqFirShared};
+use crate::buttercomp2::{ButterComp2, FetCompressor, FetRatio, OpticalCompressor, VcaCompressor};
+use crate::dynamic_eq::{DynamicBandParams, DynamicEQ, DynamicMode};
+use crate::haas::{CombMode, HaasModule};
+use crate::pultec::PultecEQ;
+use crate::punch::{ClipMode, OversamplingFactor, PunchModule};
+use crate::sheen::SheenModule;
+use crate::transformer::{TransformerModel, TransformerModule};
+use nih_plug::buffer::Buffer;
+use proptest::prelude::*;
+
+const BLOCKS: usize = 4;
+/// Headroom above the settings' own worst-case gain.
+const MARGIN_DB: f32 = 12.0;
+/// Ceiling for modules whose gain isn't a simple sum of their parameters.
+const FIXED_CEILING_DB: f32 = 24.0;
+
+#[derive(Debug, Clone, Copy)]
+enum Signal {
+    Silence,
+    Dc(f32),
+    /// Full-scale square; `half_period` samples per level.
+    Square {
+        half_period: usize,
+    },
+    /// ±1 on alternate samples — a full-scale tone at Nyquist.
+    Alternating,
+    /// Alternating ±x with x in the f32 subnormal/denormal range.
+    Denormal(f32),
+}
+
+impl Signal {
+    fn sample(self, i: usize) -> f32 {
+        let alternate = |x: f32| if i % 2 == 0 { x } else { -x };
+        match self {
+            Signal::Silence => 0.0,
+            Signal::Dc(level) => level,
+            Signal::Square { half_period } => {
+                if (i / half_period) % 2 == 0 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Signal::Alternating => alternate(1.0),
+            Signal::Denormal(x) => alternate(x),
+        }
+    }
+}
+
+fn signal() -> impl Strategy<Value = Signal> {
+    prop_oneof![
+        Just(Signal::Silence),
+        (-1.0_f32..=1.0).prop_map(Signal::Dc),
+        (1_usize..400).prop_map(|half_period| Signal::Square { half_period }),
+        Just(Signal::Alternating),
+        (-45_i32..-30).prop_map(|exp| Signal::Denormal(10.0_f32.powi(exp))),
+    ]
+}
+
+fn sample_rate() -> impl Strategy<Value = f32> {
+    prop::sample::select(vec![44_100.0_f32, 48_000.0, 96_000.0, 192_000.0])
+}
+
+/// Run `signal` through `process` in host-sized blocks, the right channel
+/// polarity-flipped so mid/side paths see a side signal, then one block of
+/// silence. Returns both channels, concatenated.
+fn render(signal: Signal, process: impl FnMut(&mut Buffer)) -> Vec<f32> {
+    let n = BLOCK * (BLOCKS + 1);
+    let left: Vec<f32> = (0..n)
+        .map(|i| {
+            if i < BLOCK * BLOCKS {
+                signal.sample(i)
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let right: Vec<f32> = left.iter().map(|x| -x).collect();
+    let (mut left, right) = render_blocks(&left, &right, process);
+    left.extend(right);
+    left
+}
+
+fn check(output: &[f32], ceiling_db: f32) -> Result<(), TestCaseError> {
+    let ceiling = 10.0_f32.powf((ceiling_db + MARGIN_DB) / 20.0);
+    for (i, &x) in output.iter().enumerate() {
+        prop_assert!(x.is_finite(), "non-finite output {} at sample {}", x, i);
+        prop_assert!(
+            x.abs() <= ceiling,
+            "output {} at sample {} exceeds {} dB",
+            x,
+            i,
+            ceiling_db + MARGIN_DB
+        );
+    }
+    Ok(())
+}
+
+fn boost_db(gains: &[f32]) -> f32 {
+    gains.iter().map(|g| g.max(0.0)).sum()
+}
+
+fn dyneq_band() -> impl Strategy<Value = DynamicBandParams> {
+    (
+        prop::sample::select(vec![
+            DynamicMode::CompressDownward,
+            DynamicMode::ExpandUpward,
+            DynamicMode::Gate,
+            DynamicMode::SpectralDuck,
+        ]),
+        20.0_f32..20_000.0,
+        20.0_f32..20_000.0,
+        0.3_f32..8.0,
+        -60.0_f32..0.0,
+        1.0_f32..20.0,
+        (0.1_f32..200.0, 1.0_f32..2000.0),
+        -18.0_f32..18.0,
+        any::<bool>(),
+        any::<bool>(),
+    )
+        .prop_map(
+            |(
+                mode,
+                detector_freq,
+                freq,
+                q,
+                threshold_db,
+                ratio,
+                (attack_ms, release_ms),
+                gain_db,
+                enabled,
+                solo,
+            )| {
+                DynamicBandParams {
+                    mode,
+                    detector_freq,
+                    freq,
+                    q,
+                    threshold_db,
+                    ratio,
+                    attack_ms,
+                    release_ms,
+                    gain_db,
+                    enabled,
+                    solo,
+                }
+            },
+        )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn api5500_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        freqs in (20.0_f32..400.0, 50.0_f32..2000.0, 200.0_f32..8000.0, 1000.0_f32..15_000.0, 3000.0_f32..20_000.0),
+        gains in prop::array::uniform5(-15.0_f32..15.0),
+        qs in prop::array::uniform3(0.1_f32..10.0),
+    ) {
+        let mut eq = Api5500::new(sr);
+        let shared = EqFirShared::new();
+        eq.update_parameters(
+            freqs.0, gains[0], freqs.1, gains[1], qs[0], freqs.2, gains[2], qs[1],
+            freqs.3, gains[3], qs[2], freqs.4, gains[4],
+        );
+        check(&render(signal, |b| eq.process(b, &shared)), boost_db(&gains))?;
+    }
+
+    #[test]
+    fn classic_comp_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        compress in 0.0_f32..=1.0,
+        output in 0.0_f32..=1.0,
+        dry_wet in 0.0_f32..=1.0,
+        faithful in any::<bool>(),
+    ) {
+        let mut comp = ButterComp2::new(sr);
+        comp.update_parameters(compress, output, dry_wet, faithful);
+        check(&render(signal, |b| comp.process(b)), FIXED_CEILING_DB)?;
+    }
+
+    #[test]
+    fn vca_comp_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        thresh in -60.0_f32..0.0,
+        ratio in 1.0_f32..20.0,
+        atk in 0.1_f32..100.0,
+        rel in 10.0_f32..1000.0,
+        sc_hp in 20.0_f32..400.0,
+    ) {
+        let mut comp = VcaCompressor::new(sr);
+        comp.update_parameters(thresh, ratio, atk, rel, sc_hp);
+        check(&render(signal, |b| comp.process(b)), FIXED_CEILING_DB)?;
+    }
+
+    #[test]
+    fn optical_comp_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        thresh in -60.0_f32..0.0,
+        speed in 0.0_f32..=1.0,
+        character in 0.0_f32..=1.0,
+    ) {
+        let mut comp = OpticalCompressor::new(sr);
+        comp.update_parameters(thresh, speed, character);
+        check(&render(signal, |b| comp.process(b, thresh)), FIXED_CEILING_DB)?;
+    }
+
+    #[test]
+    fn fet_comp_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        input_db in -20.0_f32..40.0,
+        output_db in -20.0_f32..20.0,
+        attack_ms in 0.02_f32..0.8,
+        release_ms in 50.0_f32..1100.0,
+        ratio in prop::sample::select(vec![FetRatio::R4, FetRatio::R8, FetRatio::R12, FetRatio::R20, FetRatio::All]),
+        auto_release in any::<bool>(),
+        sc_hp in 20.0_f32..400.0,
+    ) {
+        let mut comp = FetCompressor::new(sr);
+        comp.update_parameters(input_db, output_db, attack_ms, release_ms, ratio, auto_release, sc_hp);
+        check(&render(signal, |b| comp.process(b)), boost_db(&[input_db, output_db]))?;
+    }
+
+    #[test]
+    fn pultec_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        lf in (20.0_f32..300.0, 0.0_f32..18.0, 0.0_f32..=1.0, 20.0_f32..400.0, 0.0_f32..18.0, 0.0_f32..=1.0),
+        hf in (5000.0_f32..20_000.0, 0.0_f32..10.0, 0.0_f32..=1.0, 5000.0_f32..20_000.0, 0.0_f32..8.0),
+        tube_drive in 0.0_f32..=1.0,
+    ) {
+        let mut eq = PultecEQ::new(sr);
+        eq.update_parameters(lf.0, lf.1, lf.2, lf.3, lf.4, lf.5, hf.0, hf.1, hf.2, hf.3, hf.4, tube_drive);
+        check(&render(signal, |b| eq.process(b)), boost_db(&[lf.1, hf.1]))?;
+    }
+
+    #[test]
+    fn dynamic_eq_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        bands in prop::array::uniform4(dyneq_band()),
+    ) {
+        let mut eq = DynamicEQ::new(sr);
+        eq.update_parameters(&bands);
+        // Cascaded make-up gains, plus the most any band's upward
+        // expansion can add, plus the solo bandpass's peak gain (its Q, at
+        // most 8 ≈ 18 dB).
+        let make_up: Vec<f32> = bands.iter().map(|b| b.gain_db).collect();
+        let ceiling = boost_db(&make_up) + 4.0 * crate::dynamic_eq::MAX_EXPANSION_DB + 18.0;
+        check(&render(signal, |b| eq.process(b)), ceiling)?;
+        prop_assert!(eq.get_gain_reduction_db().iter().all(|gr| gr.is_finite()));
+    }
+
+    #[test]
+    fn transformer_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        model in prop::sample::select(vec![
+            TransformerModel::Vintage,
+            TransformerModel::Modern,
+            TransformerModel::British,
+            TransformerModel::American,
+        ]),
+        drive in prop::array::uniform4(0.0_f32..=1.0),
+        response in prop::array::uniform2(-1.0_f32..=1.0),
+        compression in 0.0_f32..=1.0,
+    ) {
+        let mut transformer = TransformerModule::new(sr);
+        transformer.update_parameters(
+            model, drive[0], drive[1], drive[2], drive[3], response[0], response[1], compression,
+        );
+        check(&render(signal, |b| transformer.process(b)), FIXED_CEILING_DB)?;
+        prop_assert!(transformer.saturation_activity().is_finite());
+    }
+
+    #[test]
+    fn punch_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        clip in (
+            -12.0_f32..0.0,
+            prop::sample::select(vec![ClipMode::Hard, ClipMode::Soft, ClipMode::Cubic]),
+            0.0_f32..=1.0,
+            prop::sample::select(vec![
+                OversamplingFactor::X1,
+                OversamplingFactor::X4,
+                OversamplingFactor::X8,
+                OversamplingFactor::X16,
+            ]),
+        ),
+        shaper in (-1.0_f32..=1.0, -1.0_f32..=1.0, 0.1_f32..30.0, 10.0_f32..500.0, 0.0_f32..=1.0),
+        global in (-12.0_f32..12.0, -12.0_f32..12.0, 0.0_f32..=1.0, 20.0_f32..1000.0),
+    ) {
+        let mut punch = PunchModule::new(sr);
+        punch.update_parameters(
+            clip.0, clip.1, clip.2, clip.3,
+            shaper.0, shaper.1, shaper.2, shaper.3, shaper.4,
+            global.0, global.1, global.2, global.3,
+        );
+        // The transient shaper can add up to its own boost ahead of the
+        // clipper; the dry path carries the input gain.
+        let ceiling = boost_db(&[global.0, global.1]) + FIXED_CEILING_DB;
+        check(&render(signal, |b| punch.process(b)), ceiling)?;
+        prop_assert!(punch.get_gain_reduction().is_finite());
+    }
+
+    #[test]
+    fn haas_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        mid_db in -12.0_f32..6.0,
+        side_db in -6.0_f32..6.0,
+        depth in 0.0_f32..=1.0,
+        time_ms in 1.0_f32..20.0,
+        mode in prop::sample::select(vec![CombMode::SideComb, CombMode::WideComb]),
+        mix in 0.0_f32..=1.0,
+    ) {
+        let mut haas = HaasModule::new(sr);
+        let gain = |db: f32| 10.0_f32.powf(db / 20.0);
+        haas.update_parameters(gain(mid_db), gain(side_db), depth, time_ms, mode, mix);
+        check(&render(signal, |b| haas.process(b)), FIXED_CEILING_DB)?;
+    }
+
+    #[test]
+    fn sheen_stays_finite(
+        signal in signal(),
+        sr in sample_rate(),
+        bands in (-2.0_f32..3.0, -3.0_f32..3.0, 0.0_f32..4.0),
+        warmth in 0.0_f32..=1.0,
+        width in 0.0_f32..=1.0,
+        bypass in prop::array::uniform6(any::<bool>()),
+    ) {
+        let mut sheen = SheenModule::new(sr);
+        sheen.update_parameters(
+            bypass[0], bands.0, bypass[1], bands.1, bypass[2], bands.2, bypass[3],
+            warmth, bypass[4], width, bypass[5],
+        );
+        check(&render(signal, |b| sheen.process(b)), FIXED_CEILING_DB)?;
+    }
+}
@@ -0,0 +1,320 @@
+//! Unity tests — every module with its controls at neutral (gains at 0 dB,
+//! drives and depths at 0, ratios at 1:1) must hand its input back
+//! unchanged. A module set to do nothing that still moves the audio is a
+//! bug, unless the deviation is part of the design; those are listed here
+//! with the tolerance they're held to instead:
+//!
+//! - Punch always runs the oversampling filters and the wet path's 20 Hz
+//!   DC block: the signal comes out about 20 samples late at 8× (not
+//!   reported to the host), a few tenths of a dB down in the top octave
+//!   and 0.1 dB down at 50 Hz. It's held to its level between 100 Hz and
+//!   10 kHz.
+//! - Sheen's width stage high-passes the side at 150 Hz whatever its
+//!   sliders say (mono lows). It's held to unity on a mono signal.
+//!
+//! Nothing between the modules clips either: the linear stages pass a
+//! +6 dBFS signal untouched, and the only level protection is the output
+//! Safety stage, which can be switched off.
+//!
+//! The classic ButterComp2 used to fail here: its Output default sat at
+//! +1.6 dB. It now defaults to `comp_scale::UNITY_OUTPUT`.
+
+use super::render_blocks;
+use crate::api5500::{Api5500, EqFirShared};
+use crate::buttercomp2::{ButterComp2, FetCompressor, FetRatio, OpticalCompressor, VcaCompressor};
+use crate::dynamic_eq::{DynamicBandParams, DynamicEQ, DynamicMode};
+use crate::haas::{CombMode, HaasModule};
+use crate::pultec::PultecEQ;
+use crate::punch::{ClipMode, OversamplingFactor, PunchModule};
+use crate::shaping::{Filter, FilterType};
+use crate::sheen::SheenModule;
+use crate::transformer::{TransformerModel, TransformerModule};
+use nih_plug::buffer::Buffer;
+
+const SAMPLE_RATE: f32 = 48_000.0;
+const LEN: usize = 48_000;
+/// -100 dBFS: float rounding through a filter cascade, and no more.
+const TOLERANCE: f32 = 1.0e-5;
+/// Level tolerance for the modules that can't be sample-exact.
+const LEVEL_TOLERANCE_DB: f32 = 0.01;
+
+/// Uncorrelated noise on each channel, peaking at -12 dBFS: below every
+/// threshold and ceiling the neutral settings leave in place.
+fn noise(seed: u32) -> Vec<f32> {
+    let mut state = seed;
+    (0..LEN)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            ((state >> 8) as f32 / (1u32 << 24) as f32 - 0.5) * 0.5
+        })
+        .collect()
+}
+
+fn sine(freq: f32) -> Vec<f32> {
+    (0..LEN)
+        .map(|i| 0.25 * (std::f32::consts::TAU * freq * i as f32 / SAMPLE_RATE).sin())
+        .collect()
+}
+
+/// Stereo noise through `process` must come back within `TOLERANCE`.
+fn assert_unity(name: &str, process: impl FnMut(&mut Buffer)) {
+    assert_unity_at(name, 1.0, process);
+}
+
+/// `assert_unity` with the noise scaled by `gain`; the tolerance scales
+/// with it.
+fn assert_unity_at(name: &str, gain: f32, process: impl FnMut(&mut Buffer)) {
+    let scaled = |seed| {
+        noise(seed)
+            .into_iter()
+            .map(|x| x * gain)
+            .collect::<Vec<_>>()
+    };
+    let (left, right) = (scaled(1), scaled(7));
+    let (out_l, out_r) = render_blocks(&left, &right, process);
+    for (ch, (got, want)) in [(&out_l, &left), (&out_r, &right)].into_iter().enumerate() {
+        let (i, diff) = got
+            .iter()
+            .zip(want.iter())
+            .map(|(a, b)| (a - b).abs())
+            .enumerate()
+            .fold((0, 0.0_f32), |worst, (i, d)| {
+                if d.is_nan() || d > worst.1 {
+                    (i, if d.is_nan() { f32::INFINITY } else { d })
+                } else {
+                    worst
+                }
+            });
+        assert!(
+            diff <= TOLERANCE * gain,
+            "{name}: channel {ch} sample {i} differs by {diff:e} ({:.1} dB)",
+            20.0 * diff.log10()
+        );
+    }
+}
+
+fn rms(signal: &[f32]) -> f32 {
+    (signal.iter().map(|x| x * x).sum::<f32>() / signal.len() as f32).sqrt()
+}
+
+fn neutral_band(freq: f32, enabled: bool) -> DynamicBandParams {
+    DynamicBandParams {
+        mode: DynamicMode::CompressDownward,
+        detector_freq: freq,
+        freq,
+        q: 1.0,
+        threshold_db: -18.0,
+        ratio: 1.0,
+        attack_ms: 10.0,
+        release_ms: 100.0,
+        gain_db: 0.0,
+        enabled,
+        solo: false,
+    }
+}
+
+#[test]
+fn api5500_flat_is_unity() {
+    let mut eq = Api5500::new(SAMPLE_RATE);
+    let shared = EqFirShared::new();
+    eq.update_parameters(
+        80.0, 0.0, 250.0, 0.0, 1.0, 1200.0, 0.0, 1.0, 4000.0, 0.0, 1.0, 12000.0, 0.0,
+    );
+    assert_unity("api5500", |b| eq.process(b, &shared));
+}
+
+#[test]
+fn classic_comp_at_rest_is_unity() {
+    let mut comp = ButterComp2::new(SAMPLE_RATE);
+    comp.update_parameters(0.0, crate::comp_scale::UNITY_OUTPUT, 1.0, false);
+    assert_unity("comp_classic", |b| comp.process(b));
+}
+
+#[test]
+fn classic_comp_output_defaults_to_unity() {
+    use nih_plug::prelude::Param;
+    let strip = crate::BusChannelStrip::default();
+    let default = strip.params.comp_output.default_plain_value();
+    let db = crate::comp_scale::output_to_db(default, false);
+    assert!(db.abs() < 1e-4, "Comp Output defaults to {db:+.2} dB");
+}
+
+#[test]
+fn vca_comp_at_one_to_one_is_unity() {
+    let mut comp = VcaCompressor::new(SAMPLE_RATE);
+    comp.update_parameters(-24.0, 1.0, 10.0, 100.0, 20.0);
+    assert_unity("comp_vca", |b| comp.process(b));
+}
+
+#[test]
+fn optical_comp_under_threshold_is_unity() {
+    let mut comp = OpticalCompressor::new(SAMPLE_RATE);
+    comp.update_parameters(0.0, 0.5, 0.0);
+    assert_unity("comp_optical", |b| comp.process(b, 0.0));
+}
+
+#[test]
+fn fet_comp_without_drive_is_unity() {
+    let mut comp = FetCompressor::new(SAMPLE_RATE);
+    comp.update_parameters(0.0, 0.0, 0.2, 200.0, FetRatio::R4, false, 20.0);
+    assert_unity("comp_fet", |b| comp.process(b));
+}
+
+#[test]
+fn pultec_flat_is_unity() {
+    let mut eq = PultecEQ::new(SAMPLE_RATE);
+    eq.update_parameters(
+        60.0, 0.0, 0.5, 60.0, 0.0, 0.5, 8000.0, 0.0, 0.5, 10000.0, 0.0, 0.0,
+    );
+    assert_unity("pultec", |b| eq.process(b));
+}
+
+#[test]
+fn dynamic_eq_at_one_to_one_is_unity() {
+    let mut eq = DynamicEQ::new(SAMPLE_RATE);
+    eq.update_parameters(&[
+        neutral_band(200.0, true),
+        neutral_band(1000.0, true),
+        neutral_band(3000.0, true),
+        neutral_band(8000.0, true),
+    ]);
+    assert_unity("dynamic_eq", |b| eq.process(b));
+
+    let mut eq = DynamicEQ::new(SAMPLE_RATE);
+    eq.update_parameters(&[
+        neutral_band(200.0, false),
+        neutral_band(1000.0, false),
+        neutral_band(3000.0, false),
+        neutral_band(8000.0, false),
+    ]);
+    assert_unity("dynamic_eq (bands off)", |b| eq.process(b));
+}
+
+#[test]
+fn transformer_without_drive_is_unity() {
+    for model in [
+        TransformerModel::Vintage,
+        TransformerModel::Modern,
+        TransformerModel::British,
+        TransformerModel::American,
+    ] {
+        let mut transformer = TransformerModule::new(SAMPLE_RATE);
+        transformer.update_parameters(model, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        assert_unity(&format!("transformer {model:?}"), |b| {
+            transformer.process(b)
+        });
+    }
+}
+
+#[test]
+fn hot_signal_passes_the_linear_stages_unclipped() {
+    // Peaks at +6 dBFS. Nothing between the modules limits the level; the
+    // only protection is the output Safety stage, which the user can turn off.
+    const HOT: f32 = 8.0;
+    let mut eq = Api5500::new(SAMPLE_RATE);
+    let shared = EqFirShared::new();
+    eq.update_parameters(
+        80.0, 0.0, 250.0, 0.0, 1.0, 1200.0, 0.0, 1.0, 4000.0, 0.0, 1.0, 12000.0, 0.0,
+    );
+    assert_unity_at("api5500 (hot)", HOT, |b| eq.process(b, &shared));
+
+    let mut eq = PultecEQ::new(SAMPLE_RATE);
+    eq.update_parameters(
+        60.0, 0.0, 0.5, 60.0, 0.0, 0.5, 8000.0, 0.0, 0.5, 10000.0, 0.0, 0.0,
+    );
+    assert_unity_at("pultec (hot)", HOT, |b| eq.process(b));
+
+    let mut eq = DynamicEQ::new(SAMPLE_RATE);
+    eq.update_parameters(&[
+        neutral_band(200.0, true),
+        neutral_band(1000.0, true),
+        neutral_band(3000.0, true),
+        neutral_band(8000.0, true),
+    ]);
+    assert_unity_at("dynamic_eq (hot)", HOT, |b| eq.process(b));
+
+    let mut filter = Filter::new(SAMPLE_RATE, FilterType::Bell, 1000.0, 1.0, 0.0);
+    assert_unity_at("shaping::Filter (hot)", HOT, |b| {
+        for (ch, samples) in b.as_slice().iter_mut().enumerate() {
+            for x in samples.iter_mut() {
+                *x = filter.run_ch(*x, ch);
+            }
+        }
+    });
+}
+
+#[test]
+fn haas_without_comb_is_unity() {
+    let mut haas = HaasModule::new(SAMPLE_RATE);
+    haas.update_parameters(1.0, 1.0, 0.0, 8.0, CombMode::SideComb, 1.0);
+    assert_unity("haas", |b| haas.process(b));
+}
+
+#[test]
+fn punch_below_the_ceiling_keeps_its_level() {
+    // Sample-exact is out: see the module docs. The level has to hold.
+    for freq in [100.0, 1000.0, 10_000.0] {
+        let mut punch = PunchModule::new(SAMPLE_RATE);
+        punch.update_parameters(
+            -0.1,
+            ClipMode::Soft,
+            0.3,
+            OversamplingFactor::X8,
+            0.0,
+            0.0,
+            5.0,
+            100.0,
+            0.5,
+            0.0,
+            0.0,
+            1.0,
+            20.0,
+        );
+        let input = sine(freq);
+        let (out, _) = render_blocks(&input, &input, |b| punch.process(b));
+        let settled = LEN / 2;
+        let gain_db = 20.0 * (rms(&out[settled..]) / rms(&input[settled..])).log10();
+        assert!(
+            gain_db.abs() < LEVEL_TOLERANCE_DB,
+            "punch at {freq} Hz: {gain_db:+.4} dB"
+        );
+    }
+}
+
+#[test]
+fn sheen_flat_is_unity_on_mono() {
+    let mut sheen = SheenModule::new(SAMPLE_RATE);
+    sheen.update_parameters(
+        false, 0.0, false, 0.0, false, 0.0, false, 0.0, false, 0.0, false,
+    );
+    // The width stage's side HPF is by design; a mono signal has no side.
+    let input = noise(3);
+    let (out_l, out_r) = render_blocks(&input, &input, |b| sheen.process(b));
+    for (ch, out) in [out_l, out_r].iter().enumerate() {
+        let diff = out
+            .iter()
+            .zip(&input)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(diff <= TOLERANCE, "sheen: channel {ch} differs by {diff:e}");
+    }
+}
+
+#[cfg(feature = "conditioning")]
+#[test]
+fn conditioning_with_filters_off_is_unity() {
+    use crate::conditioning::{InputConditioning, Polarity, Slope};
+    let mut conditioning = InputConditioning::new(SAMPLE_RATE);
+    conditioning.update_parameters(
+        false,
+        20.0,
+        Slope::Off,
+        20_000.0,
+        Slope::Off,
+        0.0,
+        Polarity::Normal,
+    );
+    assert_unity("conditioning", |b| conditioning.process(b));
+}
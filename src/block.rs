@@ -0,0 +1,278 @@
+// src/block.rs
+// Fixed internal block size.
+//
+// Hosts hand `process()` whatever buffer length they like, and nih-plug
+// splits it further at every automation point (sample-accurate automation).
+// With a block size selected, the scheduler collects host samples into a
+// FIFO and runs the chain only on whole blocks of that size, so every
+// module, meter and FFT hand-off sees the same block length in every host.
+// The cost is one block of latency, which `lib.rs` adds to what it reports.
+//
+// Per host buffer the scheduler alternates two copies: host → input FIFO and
+// output FIFO → host. When the input FIFO fills, the chain runs on it in
+// place and the two FIFOs swap, so processed audio leaves exactly one block
+// after it arrived.
+//
+// All storage is allocated in `new()`. The block `Buffer` keeps its slice
+// list's capacity between blocks, so pointing it at the FIFO allocates
+// nothing on the audio thread.
+
+use nih_plug::prelude::*;
+
+/// Largest selectable block; the FIFOs are sized for it up front.
+pub const MAX_BLOCK: usize = 512;
+/// Main input: stereo or 5.1.
+const CHANNELS: usize = 6;
+/// The sidechain is stereo.
+const SIDECHAIN_CHANNELS: usize = 2;
+
+#[derive(Default)]
+pub struct BlockScheduler {
+    /// Active block length; 0 passes host buffers straight through.
+    size: usize,
+    /// Samples collected into `input` so far.
+    pos: usize,
+    input: [Vec<f32>; CHANNELS],
+    output: [Vec<f32>; CHANNELS],
+    sidechain: [Vec<f32>; SIDECHAIN_CHANNELS],
+    /// Points at `input` only while the chain runs on it; empty otherwise.
+    block: Buffer<'static>,
+}
+
+impl BlockScheduler {
+    pub fn new() -> Self {
+        fn fifo<const N: usize>() -> [Vec<f32>; N] {
+            std::array::from_fn(|_| vec![0.0; MAX_BLOCK])
+        }
+        let mut block = Buffer::default();
+        // SAFETY: no slices are handed over; this only reserves the slice
+        // list so later `set_slices` calls don't allocate.
+        unsafe {
+            block.set_slices(0, |slices| {
+                slices.clear();
+                slices.reserve(CHANNELS);
+            });
+        }
+        Self {
+            size: 0,
+            pos: 0,
+            input: fifo(),
+            output: fifo(),
+            sidechain: fifo(),
+            block,
+        }
+    }
+
+    /// Switch block length (0 = off). Returns true when it changed; the
+    /// FIFOs restart from silence.
+    pub fn set_size(&mut self, size: usize) -> bool {
+        let size = size.min(MAX_BLOCK);
+        if size == self.size {
+            return false;
+        }
+        self.size = size;
+        self.reset();
+        true
+    }
+
+    /// Latency the FIFO adds, in samples.
+    pub fn latency(&self) -> u32 {
+        self.size as u32
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.size > 0
+    }
+
+    pub fn reset(&mut self) {
+        self.pos = 0;
+        for fifo in self
+            .input
+            .iter_mut()
+            .chain(&mut self.output)
+            .chain(&mut self.sidechain)
+        {
+            fifo.fill(0.0);
+        }
+    }
+
+    /// Run `process` on whole blocks of the host audio in `main` (and the
+    /// sidechain, when there is one), writing the delayed result back into
+    /// `main`. `process` may run several times or not at all per call.
+    pub fn run(
+        &mut self,
+        main: &mut Buffer,
+        sidechain: Option<&[&mut [f32]]>,
+        mut process: impl FnMut(&mut Buffer, Option<&[&mut [f32]]>),
+    ) {
+        let n = self.size;
+        if n == 0 {
+            return;
+        }
+        let frames = main.samples();
+        let host = main.as_slice();
+        let channels = host.len().min(CHANNELS);
+        let mut done = 0;
+        while done < frames {
+            let k = (n - self.pos).min(frames - done);
+            let fifo = self.pos..self.pos + k;
+            let span = done..done + k;
+            for (ch, samples) in host.iter_mut().take(channels).enumerate() {
+                self.input[ch][fifo.clone()].copy_from_slice(&samples[span.clone()]);
+                samples[span.clone()].copy_from_slice(&self.output[ch][fifo.clone()]);
+            }
+            if let Some(sc) = sidechain {
+                for (ch, samples) in sc.iter().take(SIDECHAIN_CHANNELS).enumerate() {
+                    self.sidechain[ch][fifo.clone()].copy_from_slice(&samples[span.clone()]);
+                }
+            }
+            self.pos += k;
+            done += k;
+
+            if self.pos == n {
+                self.pos = 0;
+                let input = &mut self.input[..channels];
+                // SAFETY: the slices point into `input`, whose storage is
+                // never resized after `new()`. `block` is emptied again
+                // below, before `input` is touched through any other path.
+                unsafe {
+                    self.block.set_slices(n, |slices| {
+                        slices.clear();
+                        for fifo in input {
+                            slices.push(std::slice::from_raw_parts_mut(fifo.as_mut_ptr(), n));
+                        }
+                    });
+                }
+                let [sc_left, sc_right] = &mut self.sidechain;
+                let sc_block: [&mut [f32]; SIDECHAIN_CHANNELS] =
+                    [&mut sc_left[..n], &mut sc_right[..n]];
+                let sc = sidechain.map(|sc| &sc_block[..sc.len().min(SIDECHAIN_CHANNELS)]);
+                process(&mut self.block, sc);
+                // SAFETY: clearing hands nothing over and keeps capacity.
+                unsafe {
+                    self.block.set_slices(0, |slices| slices.clear());
+                }
+                std::mem::swap(&mut self.input, &mut self.output);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed `left`/`right` through `scheduler` in host buffers of the given
+    /// lengths (cycled), with an optional sidechain, and return the output.
+    fn run_chunked(
+        scheduler: &mut BlockScheduler,
+        left: &[f32],
+        right: &[f32],
+        sidechain: Option<&[f32]>,
+        chunks: &[usize],
+        mut process: impl FnMut(&mut Buffer, Option<&[&mut [f32]]>),
+    ) -> (Vec<f32>, Vec<f32>) {
+        let (mut out_l, mut out_r) = (left.to_vec(), right.to_vec());
+        let mut sc = sidechain.map(<[f32]>::to_vec);
+        let mut start = 0;
+        for &chunk in chunks.iter().cycle() {
+            if start == left.len() {
+                break;
+            }
+            let end = (start + chunk).min(left.len());
+            let mut buffer = Buffer::default();
+            // SAFETY: the slices borrow the output Vecs, which outlive
+            // `buffer`.
+            unsafe {
+                let (l, r) = (&mut out_l[start..end], &mut out_r[start..end]);
+                buffer.set_slices(end - start, |slices| {
+                    slices.clear();
+                    slices.push(l);
+                    slices.push(r);
+                });
+            }
+            let mut sc_chunk = sc.as_mut().map(|sc| [&mut sc[start..end]]);
+            let sc_slices = sc_chunk.as_mut().map(|sc| &sc[..]);
+            scheduler.run(&mut buffer, sc_slices, &mut process);
+            start = end;
+        }
+        (out_l, out_r)
+    }
+
+    fn ramp(len: usize, offset: f32) -> Vec<f32> {
+        (0..len).map(|i| i as f32 + offset).collect()
+    }
+
+    #[test]
+    fn identity_chain_delays_by_one_block_for_any_host_size() {
+        let (left, right) = (ramp(1000, 1.0), ramp(1000, -5000.0));
+        for chunks in [&[64][..], &[1], &[7, 100, 3], &[511, 2]] {
+            let mut scheduler = BlockScheduler::new();
+            scheduler.set_size(64);
+            let mut blocks = 0;
+            let (out_l, out_r) =
+                run_chunked(&mut scheduler, &left, &right, None, chunks, |block, _| {
+                    assert_eq!(block.samples(), 64);
+                    blocks += 1;
+                });
+            assert_eq!(blocks, 1000 / 64, "host chunks {chunks:?}");
+            for i in 0..1000 {
+                let (l, r) = if i < 64 {
+                    (0.0, 0.0)
+                } else {
+                    (left[i - 64], right[i - 64])
+                };
+                assert_eq!(
+                    (out_l[i], out_r[i]),
+                    (l, r),
+                    "sample {i}, chunks {chunks:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn chain_sees_whole_blocks_of_main_and_sidechain() {
+        let (left, right) = (ramp(256, 0.0), ramp(256, 0.0));
+        let sidechain = ramp(256, 1000.0);
+        let mut scheduler = BlockScheduler::new();
+        scheduler.set_size(32);
+        let mut next = 0.0;
+        let (out_l, _) = run_chunked(
+            &mut scheduler,
+            &left,
+            &right,
+            Some(&sidechain),
+            &[13, 50],
+            |block, sc| {
+                let sc = sc.expect("sidechain is connected");
+                assert_eq!(sc.len(), 1);
+                for (i, (s, &key)) in block.as_slice()[0].iter_mut().zip(sc[0].iter()).enumerate() {
+                    assert_eq!(*s, next + i as f32);
+                    assert_eq!(key, next + i as f32 + 1000.0);
+                    *s = -*s;
+                }
+                next += 32.0;
+            },
+        );
+        assert_eq!(
+            out_l[32..],
+            left[..224].iter().map(|s| -s).collect::<Vec<_>>()[..]
+        );
+    }
+
+    #[test]
+    fn changing_size_restarts_from_silence() {
+        let mut scheduler = BlockScheduler::new();
+        assert!(!scheduler.is_active());
+        assert!(scheduler.set_size(128));
+        assert!(!scheduler.set_size(128));
+        assert_eq!(scheduler.latency(), 128);
+        let ones = vec![1.0; 100];
+        run_chunked(&mut scheduler, &ones, &ones, None, &[100], |_, _| {});
+        scheduler.set_size(64);
+        let (out, _) = run_chunked(&mut scheduler, &ones, &ones, None, &[100], |_, _| {});
+        assert!(out[..64].iter().all(|&s| s == 0.0));
+        assert!(out[64..].iter().all(|&s| s == 1.0));
+    }
+}
@@ -1,8 +1,12 @@
-use crate::oversampler::Oversampler;
+use crate::comp_scale;
+use crate::envelope::{coeff_for_ms, Detector, EnvelopeFollower, Release, TransientDetector};
+use crate::oversampler::{factor_for_rate, CascadeDelay, Oversampler};
 use crate::shaping::biquad_coeffs;
 use biquad::{Biquad, DirectForm1, Type};
 use nih_plug::buffer::Buffer;
+use nih_plug::nih_log;
 use nih_plug::prelude::Enum;
+use std::ptr::NonNull;
 
 /// Oversampling factor for the FET All-Buttons second-harmonic saturation.
 /// 4× is enough to keep 2nd/3rd-harmonic of full-bandwidth content out of the
@@ -24,6 +28,15 @@ const SC_HP_OFF_HZ: f32 = 20.0;
 /// predictable; higher Q would ring and make attack timing unpredictable.
 const SC_HP_Q: f32 = 0.707;
 
+/// Sample `i` of an external key as an (L, R) pair for the detectors. A mono
+/// key feeds both sides; samples past the key's end read as silence.
+#[inline]
+fn key_frame(key: &[&mut [f32]], i: usize) -> (f32, f32) {
+    let sample = |ch: usize| key.get(ch).and_then(|k| k.get(i)).copied();
+    let l = sample(0).unwrap_or(0.0);
+    (l, sample(1).unwrap_or(l))
+}
+
 // ============================================================================
 // ButterComp2 Model Enum
 // ============================================================================
@@ -98,21 +111,23 @@ const FET_AUTO_RELEASE_MIN_MS: f32 = 40.0;
 /// Auto-release: longest release time (ms) — active during transients.
 const FET_AUTO_RELEASE_MAX_MS: f32 = 1100.0;
 
-/// 1176-style peak-detecting FET compressor with linked stereo detection.
+/// 1176-style peak-detecting FET compressor with linked stereo detection,
+/// or two independent detectors when unlinked (see `set_linked`).
 ///
 /// All mutable state is pre-allocated in struct fields — no heap allocation in
 /// `process_sample()`. This struct intentionally does NOT implement `Copy`.
 pub struct FetCompressor {
     sample_rate: f32,
-    // Linked stereo detection — single shared gain-reduction envelope.
+    // Linked stereo detection — single shared gain-reduction envelope,
+    // following the GR depth in dB (positive = more reduction).
+    gr_env: EnvelopeFollower,
+    // The envelope as applied: -depth, clamped to [FET_ENVELOPE_MIN_DB, 0].
     envelope_db: f32,
-    fast_env_db: f32,
-    envelope_fast_db: f32, // All-Buttons dual-release secondary arm.
-    // Cached ballistic coefficients — recomputed only on parameter change.
-    coeff_attack: f32,
-    coeff_release: f32,
-    coeff_fast_attack: f32,
-    coeff_fast_release: f32,
+    // Unlinked, the shared envelope above follows the left channel and
+    // these follow the right. Linked, `envelope_db_r` mirrors `envelope_db`.
+    linked: bool,
+    gr_env_r: EnvelopeFollower,
+    envelope_db_r: f32,
     // Parameter dirty-check cache — avoids pow() on every buffer.
     cached_input_db: f32,
     cached_output_db: f32,
@@ -140,15 +155,13 @@ impl FetCompressor {
         // will reconfigure them before first use.
         let flat_hp = biquad_coeffs(Type::HighPass, sample_rate, SC_HP_OFF_HZ, SC_HP_Q)
             .expect("20 Hz HP at any sample rate is always valid");
-        let mut s = Self {
+        Self {
             sample_rate,
+            gr_env: EnvelopeFollower::new(sample_rate, 0.2, 250.0),
             envelope_db: 0.0,
-            fast_env_db: 0.0,
-            envelope_fast_db: 0.0,
-            coeff_attack: 0.0,
-            coeff_release: 0.0,
-            coeff_fast_attack: 0.0,
-            coeff_fast_release: 0.0,
+            linked: true,
+            gr_env_r: EnvelopeFollower::new(sample_rate, 0.2, 250.0),
+            envelope_db_r: 0.0,
             // NaN sentinel forces coefficient computation on first update_parameters() call.
             cached_input_db: f32::NAN,
             cached_output_db: f32::NAN,
@@ -163,25 +176,63 @@ impl FetCompressor {
             cached_sc_hp_hz: f32::NAN,
             sat_os_l: {
                 let mut os = Oversampler::new(FET_ALL_BUTTONS_OS_FACTOR, 1);
-                os.set_factor(FET_ALL_BUTTONS_OS_FACTOR);
+                os.set_factor(factor_for_rate(FET_ALL_BUTTONS_OS_FACTOR, sample_rate));
                 os
             },
             sat_os_r: {
                 let mut os = Oversampler::new(FET_ALL_BUTTONS_OS_FACTOR, 1);
-                os.set_factor(FET_ALL_BUTTONS_OS_FACTOR);
+                os.set_factor(factor_for_rate(FET_ALL_BUTTONS_OS_FACTOR, sample_rate));
                 os
             },
+        }
+    }
+
+    /// Point the GR envelope at the front-panel ballistics. All-Buttons
+    /// forces a 0.02 ms attack and blends 30% of a 50 ms release into the
+    /// slow one; auto release slides between 40 ms and 1.1 s with depth.
+    fn configure_ballistics(
+        &mut self,
+        attack_ms: f32,
+        release_ms: f32,
+        ratio: FetRatio,
+        auto_release: bool,
+    ) {
+        let (attack_ms, release) = if ratio == FetRatio::All {
+            (
+                0.02,
+                Release::Dual {
+                    fast_ms: 50.0,
+                    fast_share: 0.3,
+                },
+            )
+        } else if auto_release {
+            (
+                attack_ms,
+                Release::ProgramDependent {
+                    min_ms: FET_AUTO_RELEASE_MIN_MS,
+                    max_ms: FET_AUTO_RELEASE_MAX_MS,
+                    full_scale: 20.0,
+                },
+            )
+        } else {
+            (attack_ms, Release::Fixed)
         };
-        s.recompute_coefficients(0.2, 250.0, false);
-        s
+        self.gr_env.set_times(attack_ms, release_ms);
+        self.gr_env.set_release(release);
+        self.gr_env_r.set_times(attack_ms, release_ms);
+        self.gr_env_r.set_release(release);
     }
 
-    fn recompute_coefficients(&mut self, attack_ms: f32, release_ms: f32, _auto_release: bool) {
-        self.coeff_attack = (-1.0 / (attack_ms * 0.001 * self.sample_rate)).exp();
-        self.coeff_release = (-1.0 / (release_ms * 0.001 * self.sample_rate)).exp();
-        // Fixed fast coefficients used by the All-Buttons secondary envelope and auto-release.
-        self.coeff_fast_attack = (-1.0 / (5.0 * 0.001 * self.sample_rate)).exp();
-        self.coeff_fast_release = (-1.0 / (50.0 * 0.001 * self.sample_rate)).exp();
+    /// Link the channels' detection (the default) or give each channel its
+    /// own, for two unrelated mono sources on one stereo track. Unlinking
+    /// starts the right channel's envelope from the shared one, so the
+    /// switch doesn't step.
+    pub fn set_linked(&mut self, linked: bool) {
+        if self.linked && !linked {
+            self.gr_env_r = self.gr_env.clone();
+            self.envelope_db_r = self.envelope_db;
+        }
+        self.linked = linked;
     }
 
     /// Update parameters — call once per buffer, not per sample.
@@ -209,13 +260,14 @@ impl FetCompressor {
         let rel_changed =
             self.cached_release_ms.is_nan() || (release_ms - self.cached_release_ms).abs() > 0.5;
         let auto_changed = auto_release != self.cached_auto_release;
-        if atk_changed || rel_changed || auto_changed {
+        let ratio_changed = ratio != self.cached_ratio;
+        self.cached_ratio = ratio;
+        if atk_changed || rel_changed || auto_changed || ratio_changed {
             self.cached_attack_ms = attack_ms;
             self.cached_release_ms = release_ms;
             self.cached_auto_release = auto_release;
-            self.recompute_coefficients(attack_ms, release_ms, auto_release);
+            self.configure_ballistics(attack_ms, release_ms, ratio, auto_release);
         }
-        self.cached_ratio = ratio;
 
         // SC HP coefficient update — only on frequency change. update_coefficients()
         // preserves filter state so knob moves don't click.
@@ -231,11 +283,35 @@ impl FetCompressor {
         }
     }
 
+    /// Current gain reduction in dB (positive = attenuation), for metering.
+    /// Unlinked, the deeper of the two channels.
+    pub fn gain_reduction_db(&self) -> f32 {
+        -self.envelope_db.min(self.envelope_db_r)
+    }
+
+    /// Gain reduction per channel in dB; linked, both read the same.
+    pub fn channel_gain_reduction_db(&self) -> [f32; 2] {
+        [-self.envelope_db, -self.envelope_db_r]
+    }
+
+    /// All-Buttons saturates through the oversamplers; the other ratios
+    /// bypass them.
+    pub fn cascade_delay(&self) -> CascadeDelay {
+        if self.cached_ratio != FetRatio::All {
+            return CascadeDelay::NONE;
+        }
+        CascadeDelay {
+            factor: self.sat_os_l.factor(),
+            passes: 1,
+        }
+    }
+
     /// Reset all envelope state. May be called from the audio thread (no allocation).
     pub fn reset(&mut self) {
+        self.gr_env.reset();
         self.envelope_db = 0.0;
-        self.fast_env_db = 0.0;
-        self.envelope_fast_db = 0.0;
+        self.gr_env_r.reset();
+        self.envelope_db_r = 0.0;
         self.sat_os_l.reset();
         self.sat_os_r.reset();
     }
@@ -245,6 +321,20 @@ impl FetCompressor {
     /// No allocation, no locking, no panics — safe for the audio thread.
     #[inline]
     pub fn process_sample(&mut self, in_l: f32, in_r: f32) -> (f32, f32) {
+        self.process_sample_keyed(in_l, in_r, in_l, in_r)
+    }
+
+    /// `process_sample` with the detector listening to `key_l`/`key_r`
+    /// instead of the input. The key goes through the same input drive and
+    /// SC HP, so threshold and filter settings mean the same for both.
+    #[inline]
+    pub fn process_sample_keyed(
+        &mut self,
+        in_l: f32,
+        in_r: f32,
+        key_l: f32,
+        key_r: f32,
+    ) -> (f32, f32) {
         let is_all_buttons = self.cached_ratio == FetRatio::All;
 
         // Stage 1 — Input drive (applied equally to both channels and sidechain).
@@ -252,85 +342,34 @@ impl FetCompressor {
         let driven_r = in_r * self.input_gain_linear;
 
         // Stage 1.5 — Sidechain HP filter. Runs on a *copy* of the driven
-        // signal; the main audio path below uses the unfiltered driven_l/r.
+        // key (the input itself unless an external key is routed in); the
+        // main audio path below uses the unfiltered driven_l/r.
         // At SC_HP_OFF_HZ the filter still updates state but has ~flat
         // response above ~30 Hz, so the detector behaviour matches legacy
         // sessions when sc_hp is left at default.
-        let det_l = self.sc_hp_l.run(driven_l);
-        let det_r = self.sc_hp_r.run(driven_r);
-
-        // Stage 2 — Linked peak detection (max of absolute values, stereo-linked).
-        let x_abs = det_l.abs().max(det_r.abs());
-
-        // Stage 3 — Gain computer in log domain.
-        let x_db = (20.0 * x_abs.max(FET_LEVEL_FLOOR).log10()).max(FET_DB_FLOOR);
-        // Threshold shifts with input drive: louder input → earlier engagement.
-        let effective_threshold = -self.cached_input_db;
-        let over_db = (x_db - effective_threshold).max(0.0);
-        let ratio_val = self.cached_ratio.value();
-        let mut gr_target = if over_db > 0.0 {
-            -over_db * (1.0 - 1.0 / ratio_val)
-        } else {
-            0.0
-        };
-
-        // All-Buttons hard GR cap — prevents extreme pumping artefacts.
-        if is_all_buttons {
-            gr_target = gr_target.max(FET_ALL_BUTTONS_GR_CAP);
-        }
-
-        // Stage 4 — Attack/Release ballistics.
-        let coeff_attack = if is_all_buttons {
-            // All-Buttons mode enforces minimum 0.02 ms attack for characteristic aggression.
-            (-1.0 / (0.02_f32 * 0.001 * self.sample_rate)).exp()
+        let det_l = self.sc_hp_l.run(key_l * self.input_gain_linear);
+        let det_r = self.sc_hp_r.run(key_r * self.input_gain_linear);
+
+        // Stage 2 — Peak detection: the louder channel when linked, each
+        // channel on its own when not.
+        // Stages 3–4 — Gain computer and ballistics, in `follow_gr`.
+        let (gr_l, gr_r) = if self.linked {
+            self.envelope_db = self.follow_gr(true, det_l.abs().max(det_r.abs()));
+            self.envelope_db_r = self.envelope_db;
+            let gr_linear = 10.0_f32.powf(self.envelope_db / 20.0);
+            (gr_linear, gr_linear)
         } else {
-            self.coeff_attack
+            self.envelope_db = self.follow_gr(true, det_l.abs());
+            self.envelope_db_r = self.follow_gr(false, det_r.abs());
+            (
+                10.0_f32.powf(self.envelope_db / 20.0),
+                10.0_f32.powf(self.envelope_db_r / 20.0),
+            )
         };
 
-        if gr_target < self.envelope_db {
-            // More GR needed — follow attack coefficient.
-            self.envelope_db = coeff_attack * self.envelope_db + (1.0 - coeff_attack) * gr_target;
-        } else if is_all_buttons {
-            // All-Buttons: blend slow and fast release envelopes for the characteristic sound.
-            let coeff_r_fast = (-1.0 / (50.0_f32 * 0.001 * self.sample_rate)).exp();
-            self.envelope_fast_db =
-                coeff_r_fast * self.envelope_fast_db + (1.0 - coeff_r_fast) * gr_target;
-            self.envelope_db = 0.7
-                * (self.coeff_release * self.envelope_db + (1.0 - self.coeff_release) * gr_target)
-                + 0.3 * self.envelope_fast_db;
-        } else if self.cached_auto_release {
-            // Auto-release: release time scales dynamically with current GR depth.
-            // Update the fast-tracking secondary envelope.
-            if gr_target < self.fast_env_db {
-                self.fast_env_db = self.coeff_fast_attack * self.fast_env_db
-                    + (1.0 - self.coeff_fast_attack) * gr_target;
-            } else {
-                self.fast_env_db = self.coeff_fast_release * self.fast_env_db
-                    + (1.0 - self.coeff_fast_release) * gr_target;
-            }
-            let gr_magnitude = self.fast_env_db.abs();
-            let t_auto_ms = (FET_AUTO_RELEASE_MIN_MS
-                + (gr_magnitude / 20.0) * (FET_AUTO_RELEASE_MAX_MS - FET_AUTO_RELEASE_MIN_MS))
-                .clamp(FET_AUTO_RELEASE_MIN_MS, FET_AUTO_RELEASE_MAX_MS);
-            let coeff_auto_rel = (-1.0 / (t_auto_ms * 0.001 * self.sample_rate)).exp();
-            self.envelope_db =
-                coeff_auto_rel * self.envelope_db + (1.0 - coeff_auto_rel) * gr_target;
-        } else {
-            self.envelope_db =
-                self.coeff_release * self.envelope_db + (1.0 - self.coeff_release) * gr_target;
-        }
-
-        // Clamp envelopes and prevent denormals.
-        self.envelope_db = self.envelope_db.clamp(FET_ENVELOPE_MIN_DB, 0.0);
-        self.fast_env_db = self.fast_env_db.clamp(FET_ENVELOPE_MIN_DB, 0.0);
-        self.envelope_fast_db = self.envelope_fast_db.clamp(FET_ENVELOPE_MIN_DB, 0.0);
-
-        // Convert GR from dB to linear.
-        let gr_linear = 10.0_f32.powf(self.envelope_db / 20.0);
-
         // Apply GR to the driven signal.
-        let mut out_l = driven_l * gr_linear;
-        let mut out_r = driven_r * gr_linear;
+        let mut out_l = driven_l * gr_l;
+        let mut out_r = driven_r * gr_r;
 
         // Stage 5 — All-Buttons second-harmonic injection (odd-order
         // saturation, asymmetric). Run through a 4× halfband oversampler so
@@ -339,26 +378,23 @@ impl FetCompressor {
         if is_all_buttons {
             let mut scratch_l = [0.0_f32; FET_ALL_BUTTONS_OS_FACTOR];
             let mut scratch_r = [0.0_f32; FET_ALL_BUTTONS_OS_FACTOR];
-            {
+            // The upsampled slices hold the factor chosen for the sample
+            // rate, at most FET_ALL_BUTTONS_OS_FACTOR.
+            let factor = {
                 let up_l = self.sat_os_l.upsample(out_l, 0);
-                for i in 0..FET_ALL_BUTTONS_OS_FACTOR {
-                    let x = up_l[i];
-                    scratch_l[i] = x + FET_ALL_BUTTONS_SAT * x * x * x.signum();
+                for (y, &x) in scratch_l.iter_mut().zip(up_l) {
+                    *y = x + FET_ALL_BUTTONS_SAT * x * x * x.signum();
                 }
-            }
-            out_l = self
-                .sat_os_l
-                .downsample(&scratch_l[..FET_ALL_BUTTONS_OS_FACTOR], 0);
+                up_l.len()
+            };
+            out_l = self.sat_os_l.downsample(&scratch_l[..factor], 0);
             {
                 let up_r = self.sat_os_r.upsample(out_r, 0);
-                for i in 0..FET_ALL_BUTTONS_OS_FACTOR {
-                    let x = up_r[i];
-                    scratch_r[i] = x + FET_ALL_BUTTONS_SAT * x * x * x.signum();
+                for (y, &x) in scratch_r.iter_mut().zip(up_r) {
+                    *y = x + FET_ALL_BUTTONS_SAT * x * x * x.signum();
                 }
             }
-            out_r = self
-                .sat_os_r
-                .downsample(&scratch_r[..FET_ALL_BUTTONS_OS_FACTOR], 0);
+            out_r = self.sat_os_r.downsample(&scratch_r[..factor], 0);
         }
 
         // Stage 6 — Output makeup gain.
@@ -368,6 +404,39 @@ impl FetCompressor {
         (out_l, out_r)
     }
 
+    /// Stages 3–4 for one peak level `x_abs` on the left (shared) envelope
+    /// or the right one: the gain computer in the log domain, then the
+    /// attack/release ballistics on the GR depth. Returns the envelope as
+    /// applied, in dB (≤ 0).
+    #[inline]
+    fn follow_gr(&mut self, left: bool, x_abs: f32) -> f32 {
+        let x_db = (20.0 * x_abs.max(FET_LEVEL_FLOOR).log10()).max(FET_DB_FLOOR);
+        // Threshold shifts with input drive: louder input → earlier engagement.
+        let effective_threshold = -self.cached_input_db;
+        let over_db = (x_db - effective_threshold).max(0.0);
+        let ratio_val = self.cached_ratio.value();
+        let mut gr_target = if over_db > 0.0 {
+            -over_db * (1.0 - 1.0 / ratio_val)
+        } else {
+            0.0
+        };
+
+        // All-Buttons hard GR cap — prevents extreme pumping artefacts.
+        if self.cached_ratio == FetRatio::All {
+            gr_target = gr_target.max(FET_ALL_BUTTONS_GR_CAP);
+        }
+
+        // The ballistics mode (All-Buttons dual release, auto release,
+        // fixed) is set up in `configure_ballistics`.
+        let gr_env = if left {
+            &mut self.gr_env
+        } else {
+            &mut self.gr_env_r
+        };
+        let depth = gr_env.follow(-gr_target);
+        (-depth).clamp(FET_ENVELOPE_MIN_DB, 0.0)
+    }
+
     /// Process a full stereo buffer in place.
     ///
     /// # Safety invariant
@@ -384,6 +453,94 @@ impl FetCompressor {
             }
         }
     }
+
+    /// `process` with detection on an external key (see [`key_frame`]).
+    pub fn process_keyed(&mut self, buffer: &mut Buffer, key: &[&mut [f32]]) {
+        let [left, right, ..] = buffer.as_slice() else {
+            return;
+        };
+        for (i, (l, r)) in left.iter_mut().zip(right.iter_mut()).enumerate() {
+            let (key_l, key_r) = key_frame(key, i);
+            (*l, *r) = self.process_sample_keyed(*l, *r, key_l, key_r);
+        }
+    }
+}
+
+// ============================================================================
+// TransientProtect — ducks the keyed models' detector on transients
+// ============================================================================
+
+/// Key level left on a full transient at full protect (−12 dB).
+const PROTECT_KEY_FLOOR: f32 = 0.25;
+/// Detector readings below this are a sustained low note's ripple (the
+/// fast follower sags between cycles), not an attack.
+const PROTECT_DEAD_ZONE: f32 = 0.3;
+
+/// Transient protect for the VCA, Optical and FET models. While the shared
+/// transient detector (the one Punch shapes with) sees an attack, the key is
+/// turned down, so the compressor reads the hit as quieter and lets its
+/// front edge through before clamping on the body. Only the key moves; the
+/// audio path is untouched. Linked, both key channels take one gain, driven
+/// by the louder, so the stereo image holds; unlinked, each channel has its
+/// own detector.
+pub struct TransientProtect {
+    /// One per key channel; linked, only the first runs.
+    detectors: [TransientDetector; 2],
+    linked: bool,
+}
+
+impl TransientProtect {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            detectors: [
+                TransientDetector::new(sample_rate),
+                TransientDetector::new(sample_rate),
+            ],
+            linked: true,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for detector in &mut self.detectors {
+            detector.reset();
+        }
+    }
+
+    /// Link the key channels (the default) or protect each on its own.
+    pub fn set_linked(&mut self, linked: bool) {
+        self.linked = linked;
+    }
+
+    /// Duck `key` in place. `depth` 0..=1; 0 leaves the key alone.
+    pub fn process(&mut self, key: &mut [&mut [f32]], depth: f32) {
+        let n = key.iter().map(|ch| ch.len()).min().unwrap_or(0);
+        let duck = depth.clamp(0.0, 1.0) * (1.0 - PROTECT_KEY_FLOOR);
+        if !self.linked {
+            for (ch, detector) in key.iter_mut().zip(&mut self.detectors) {
+                for s in ch[..n].iter_mut() {
+                    *s *= protect_gain(detector, s.abs(), duck);
+                }
+            }
+            return;
+        }
+        let detector = &mut self.detectors[0];
+        for i in 0..n {
+            let level = key.iter().fold(0.0_f32, |m, ch| m.max(ch[i].abs()));
+            let gain = protect_gain(detector, level, duck);
+            for ch in key.iter_mut() {
+                ch[i] *= gain;
+            }
+        }
+    }
+}
+
+/// Key gain for one detector sample at `level`, `duck` being the most the
+/// key is turned down.
+#[inline]
+fn protect_gain(detector: &mut TransientDetector, level: f32, duck: f32) -> f32 {
+    let transient =
+        ((detector.process(level) - PROTECT_DEAD_ZONE) / (1.0 - PROTECT_DEAD_ZONE)).clamp(0.0, 1.0);
+    1.0 - duck * transient
 }
 
 // ============================================================================
@@ -392,11 +549,9 @@ impl FetCompressor {
 // ============================================================================
 
 /// RMS window used for level detection (10 ms is a standard ballistic choice).
-const VCA_RMS_WINDOW_S: f32 = 0.010;
+const VCA_RMS_WINDOW_MS: f32 = 10.0;
 /// Soft-knee width in dB — transitions from linear to compressed region over 6 dB.
 const VCA_KNEE_WIDTH_DB: f32 = 6.0;
-/// Denormal guard for the squared RMS accumulator — prevents CPU load spikes.
-const VCA_DENORMAL_FLOOR: f32 = 1e-15;
 /// Minimum RMS level before log conversion — avoids -inf dB.
 const VCA_MIN_RMS_LINEAR: f32 = 1e-6;
 /// Minimum linear GR multiplier — prevents complete signal extinction.
@@ -405,17 +560,22 @@ const VCA_GR_MIN_LINEAR: f32 = 0.001;
 /// RMS-detecting, soft-knee, feed-forward VCA bus compressor.
 ///
 /// All mutable state is pre-allocated in struct fields — no heap allocation in
-/// `process_sample()`. Linked stereo detection produces a single shared envelope.
+/// `process_sample()`. Linked stereo detection produces a single shared envelope;
+/// unlinked (see `set_linked`), each channel has its own.
 pub struct VcaCompressor {
     sample_rate: f32,
-    /// Linked stereo RMS accumulator (mean-square, pre-sqrt).
-    rms_sq: f32,
-    coeff_rms: f32,
+    /// Linked stereo RMS level detector (10 ms window, no ballistics).
+    rms: EnvelopeFollower,
+    /// Attack/release on the GR depth, 1 − the linear multiplier.
+    gr_env: EnvelopeFollower,
     /// Shared gain-reduction envelope, linear multiplier (init 1.0 = no GR).
     env_gr: f32,
-    /// Cached ballistic coefficients — recomputed only on parameter change.
-    coeff_atk: f32,
-    coeff_rel: f32,
+    /// Unlinked, the detector above follows the left channel and these the
+    /// right. Linked, `env_gr_r` mirrors `env_gr`.
+    linked: bool,
+    rms_r: EnvelopeFollower,
+    gr_env_r: EnvelopeFollower,
+    env_gr_r: f32,
     /// Dirty-check cache — avoids exp() on every buffer call.
     cached_thresh: f32,
     cached_ratio: f32,
@@ -432,13 +592,19 @@ impl VcaCompressor {
     pub fn new(sample_rate: f32) -> Self {
         let flat_hp = biquad_coeffs(Type::HighPass, sample_rate, SC_HP_OFF_HZ, SC_HP_Q)
             .expect("20 Hz HP at any sample rate is always valid");
-        let mut s = Self {
+        let rms = EnvelopeFollower::new(sample_rate, 0.0, 0.0).with_detector(Detector::Rms {
+            window_ms: VCA_RMS_WINDOW_MS,
+        });
+        let gr_env = EnvelopeFollower::new(sample_rate, 10.0, 100.0);
+        Self {
             sample_rate,
-            rms_sq: 0.0,
-            coeff_rms: 0.0,
+            rms: rms.clone(),
+            gr_env: gr_env.clone(),
             env_gr: 1.0,
-            coeff_atk: 0.0,
-            coeff_rel: 0.0,
+            linked: true,
+            rms_r: rms,
+            gr_env_r: gr_env,
+            env_gr_r: 1.0,
             // NaN sentinel forces coefficient computation on first update_parameters() call.
             cached_thresh: f32::NAN,
             cached_ratio: f32::NAN,
@@ -447,15 +613,7 @@ impl VcaCompressor {
             sc_hp_l: DirectForm1::<f32>::new(flat_hp),
             sc_hp_r: DirectForm1::<f32>::new(flat_hp),
             cached_sc_hp_hz: f32::NAN,
-        };
-        s.recompute_coefficients(10.0, 100.0);
-        s
-    }
-
-    fn recompute_coefficients(&mut self, atk_ms: f32, rel_ms: f32) {
-        self.coeff_rms = (-1.0 / (VCA_RMS_WINDOW_S * self.sample_rate)).exp();
-        self.coeff_atk = (-1.0 / (atk_ms * 0.001 * self.sample_rate)).exp();
-        self.coeff_rel = (-1.0 / (rel_ms * 0.001 * self.sample_rate)).exp();
+        }
     }
 
     /// Update parameters — call once per buffer, not per sample.
@@ -482,7 +640,8 @@ impl VcaCompressor {
         if atk_changed || rel_changed {
             self.cached_atk_ms = atk_ms;
             self.cached_rel_ms = rel_ms;
-            self.recompute_coefficients(atk_ms, rel_ms);
+            self.gr_env.set_times(atk_ms, rel_ms);
+            self.gr_env_r.set_times(atk_ms, rel_ms);
         }
 
         let hp_changed =
@@ -497,22 +656,66 @@ impl VcaCompressor {
         }
     }
 
+    /// Link the channels' detection (the default) or give each channel its
+    /// own. Unlinking starts the right channel's detector from the shared
+    /// one, so the switch doesn't step.
+    pub fn set_linked(&mut self, linked: bool) {
+        if self.linked && !linked {
+            self.rms_r = self.rms.clone();
+            self.gr_env_r = self.gr_env.clone();
+            self.env_gr_r = self.env_gr;
+        }
+        self.linked = linked;
+    }
+
     /// Process one stereo sample pair with linked RMS detection.
     ///
     /// No allocation, no locking, no panics — safe for the audio thread.
     #[inline]
     pub fn process_sample(&mut self, in_l: f32, in_r: f32) -> (f32, f32) {
+        self.process_sample_keyed(in_l, in_r, in_l, in_r)
+    }
+
+    /// `process_sample` with the RMS detector listening to `key_l`/`key_r`
+    /// instead of the input.
+    #[inline]
+    pub fn process_sample_keyed(
+        &mut self,
+        in_l: f32,
+        in_r: f32,
+        key_l: f32,
+        key_r: f32,
+    ) -> (f32, f32) {
         // Stage 0 — Detection-path HP. Audio path below uses raw in_l/in_r,
         // only the RMS detector sees the high-passed copy.
-        let det_l = self.sc_hp_l.run(in_l);
-        let det_r = self.sc_hp_r.run(in_r);
+        let det_l = self.sc_hp_l.run(key_l);
+        let det_r = self.sc_hp_r.run(key_r);
+
+        // Stage 1 — RMS detection (max-abs side-chain, mean-square IIR) of
+        // the louder channel when linked, each channel on its own when not.
+        // Stages 2–4 are in `follow_gr`.
+        if self.linked {
+            self.env_gr = self.follow_gr(true, det_l.abs().max(det_r.abs()));
+            self.env_gr_r = self.env_gr;
+        } else {
+            self.env_gr = self.follow_gr(true, det_l.abs());
+            self.env_gr_r = self.follow_gr(false, det_r.abs());
+        }
+
+        // Stage 5 — Apply the GR.
+        (in_l * self.env_gr, in_r * self.env_gr_r)
+    }
 
-        // Stage 1 — Linked RMS accumulation (max-abs side-chain, mean-square IIR).
-        let x_sq = det_l.abs().max(det_r.abs()).powi(2);
-        self.rms_sq = self.coeff_rms * self.rms_sq + (1.0 - self.coeff_rms) * x_sq;
-        // Denormal guard: clamp to a small positive floor before sqrt.
-        self.rms_sq = self.rms_sq.max(VCA_DENORMAL_FLOOR);
-        let rms = self.rms_sq.sqrt();
+    /// Stages 1–4 for one rectified detector sample on the left (shared)
+    /// detector or the right one. Returns the linear GR multiplier.
+    #[inline]
+    fn follow_gr(&mut self, left: bool, level: f32) -> f32 {
+        let (rms, gr_env) = if left {
+            (&mut self.rms, &mut self.gr_env)
+        } else {
+            (&mut self.rms_r, &mut self.gr_env_r)
+        };
+        let rms = rms.process(level);
 
         // Stage 2 — Level to dB.
         let x_db = if rms < VCA_MIN_RMS_LINEAR {
@@ -537,18 +740,10 @@ impl VcaCompressor {
         // gr_db ≤ 0; convert to linear and clamp to prevent full extinction.
         let gr_linear_target = 10.0_f32.powf(gr_db / 20.0).clamp(VCA_GR_MIN_LINEAR, 1.0);
 
-        // Stage 4 — Attack/release envelope on the linear GR multiplier.
-        if gr_linear_target < self.env_gr {
-            // More GR needed — attack phase.
-            self.env_gr = self.coeff_atk * self.env_gr + (1.0 - self.coeff_atk) * gr_linear_target;
-        } else {
-            // Less GR needed — release phase.
-            self.env_gr = self.coeff_rel * self.env_gr + (1.0 - self.coeff_rel) * gr_linear_target;
-        }
-        self.env_gr = self.env_gr.clamp(VCA_GR_MIN_LINEAR, 1.0);
-
-        // Stage 5 — Apply shared GR to both channels.
-        (in_l * self.env_gr, in_r * self.env_gr)
+        // Stage 4 — Attack/release envelope on the linear GR multiplier:
+        // more depth (more GR) attacks, less releases.
+        let depth = gr_env.follow(1.0 - gr_linear_target);
+        (1.0 - depth).clamp(VCA_GR_MIN_LINEAR, 1.0)
     }
 
     /// Process a full stereo buffer in place.
@@ -568,10 +763,36 @@ impl VcaCompressor {
         }
     }
 
+    /// `process` with detection on an external key (see [`key_frame`]).
+    pub fn process_keyed(&mut self, buffer: &mut Buffer, key: &[&mut [f32]]) {
+        let [left, right, ..] = buffer.as_slice() else {
+            return;
+        };
+        for (i, (l, r)) in left.iter_mut().zip(right.iter_mut()).enumerate() {
+            let (key_l, key_r) = key_frame(key, i);
+            (*l, *r) = self.process_sample_keyed(*l, *r, key_l, key_r);
+        }
+    }
+
+    /// Current gain reduction in dB (positive = attenuation), for metering.
+    /// Unlinked, the deeper of the two channels.
+    pub fn gain_reduction_db(&self) -> f32 {
+        -20.0 * self.env_gr.min(self.env_gr_r).log10()
+    }
+
+    /// Gain reduction per channel in dB; linked, both read the same.
+    pub fn channel_gain_reduction_db(&self) -> [f32; 2] {
+        [self.env_gr, self.env_gr_r].map(|gr| -20.0 * gr.log10())
+    }
+
     /// Reset all envelope and accumulator state. Safe to call from audio thread.
     pub fn reset(&mut self) {
         self.env_gr = 1.0;
-        self.rms_sq = 0.0;
+        self.rms.reset();
+        self.gr_env.reset();
+        self.env_gr_r = 1.0;
+        self.rms_r.reset();
+        self.gr_env_r.reset();
     }
 }
 
@@ -665,14 +886,14 @@ impl OpticalCompressor {
         let release_ms = (3000.0_f32.ln() * (1.0 - speed) + 80.0_f32.ln() * speed).exp();
         let memory_ms = (6000.0_f32.ln() * (1.0 - speed) + 500.0_f32.ln() * speed).exp();
 
-        self.attack_coeff = (-1.0 / (attack_ms * 0.001 * self.sample_rate)).exp();
-        self.release_coeff = (-1.0 / (release_ms * 0.001 * self.sample_rate)).exp();
-        self.memory_coeff = (-1.0 / (memory_ms * 0.001 * self.sample_rate)).exp();
-        self.memory_decay_coeff =
-            (-1.0 / (memory_ms * OPT_MEMORY_DECAY_FACTOR * 0.001 * self.sample_rate)).exp();
+        let sr = self.sample_rate;
+        self.attack_coeff = coeff_for_ms(attack_ms, sr);
+        self.release_coeff = coeff_for_ms(release_ms, sr);
+        self.memory_coeff = coeff_for_ms(memory_ms, sr);
+        self.memory_decay_coeff = coeff_for_ms(memory_ms * OPT_MEMORY_DECAY_FACTOR, sr);
 
-        self.peak_atk_coeff = (-1.0 / (OPT_PEAK_HOLD_ATK_MS * 0.001 * self.sample_rate)).exp();
-        self.peak_rel_coeff = (-1.0 / (OPT_PEAK_HOLD_REL_MS * 0.001 * self.sample_rate)).exp();
+        self.peak_atk_coeff = coeff_for_ms(OPT_PEAK_HOLD_ATK_MS, sr);
+        self.peak_rel_coeff = coeff_for_ms(OPT_PEAK_HOLD_REL_MS, sr);
 
         // character sweeps memory_weight 0.3..0.9 and knee 12..3 dB.
         self.memory_weight = 0.3 + char_val * 0.6;
@@ -730,13 +951,14 @@ impl OpticalCompressor {
     fn process_sample_channel(
         &self,
         x: f32,
+        key: f32,
         env_fast: f32,
         env_slow: f32,
         peak_hold: f32,
         thresh_db: f32,
     ) -> (f32, f32, f32, f32) {
         // Stage 1 — Peak pre-filter (smooth peak tracking to reduce inter-sample clicks).
-        let x_abs = x.abs() + OPT_DENORM_GUARD;
+        let x_abs = key.abs() + OPT_DENORM_GUARD;
         let x_db_raw = 20.0 * x_abs.log10();
         let x_db = x_db_raw.max(OPT_MIN_LEVEL_DB);
         let new_peak = if x_db > peak_hold {
@@ -783,8 +1005,23 @@ impl OpticalCompressor {
     /// No allocation, no locking, no panics — safe for the audio thread.
     #[inline]
     pub fn process_sample(&mut self, in_l: f32, in_r: f32, thresh_db: f32) -> (f32, f32) {
+        self.process_sample_keyed(in_l, in_r, in_l, in_r, thresh_db)
+    }
+
+    /// `process_sample` with each channel's opto cell lit by the matching
+    /// key channel instead of its own input.
+    #[inline]
+    pub fn process_sample_keyed(
+        &mut self,
+        in_l: f32,
+        in_r: f32,
+        key_l: f32,
+        key_r: f32,
+        thresh_db: f32,
+    ) -> (f32, f32) {
         let (out_l, ef_l, es_l, ph_l) = self.process_sample_channel(
             in_l,
+            key_l,
             self.env_fast_l,
             self.env_slow_l,
             self.peak_hold_l,
@@ -792,6 +1029,7 @@ impl OpticalCompressor {
         );
         let (out_r, ef_r, es_r, ph_r) = self.process_sample_channel(
             in_r,
+            key_r,
             self.env_fast_r,
             self.env_slow_r,
             self.peak_hold_r,
@@ -823,6 +1061,28 @@ impl OpticalCompressor {
         }
     }
 
+    /// `process` with detection on an external key (see [`key_frame`]).
+    pub fn process_keyed(&mut self, buffer: &mut Buffer, key: &[&mut [f32]], thresh_db: f32) {
+        let [left, right, ..] = buffer.as_slice() else {
+            return;
+        };
+        for (i, (l, r)) in left.iter_mut().zip(right.iter_mut()).enumerate() {
+            let (key_l, key_r) = key_frame(key, i);
+            (*l, *r) = self.process_sample_keyed(*l, *r, key_l, key_r, thresh_db);
+        }
+    }
+
+    /// Current gain reduction in dB (positive = attenuation), for metering.
+    /// Channels are detected independently, so report the deeper of the two.
+    pub fn gain_reduction_db(&self) -> f32 {
+        self.env_fast_l.max(self.env_fast_r)
+    }
+
+    /// Gain reduction per channel in dB.
+    pub fn channel_gain_reduction_db(&self) -> [f32; 2] {
+        [self.env_fast_l, self.env_fast_r]
+    }
+
     /// Reset all envelope and pre-filter state. Safe to call from audio thread.
     pub fn reset(&mut self) {
         self.env_fast_l = 0.0;
@@ -861,34 +1121,65 @@ extern "C" {
 ///
 /// Airwindows ButterComp2: "The single richest, lushest 'glue' compressor"
 /// Features 4 independent compressors per channel in bipolar, interleaved configuration
+///
+/// If the C++ side can't allocate its state the wrapper runs degraded: every
+/// call becomes a no-op, so the Classic model is a clean bypass instead of
+/// taking the host down with it.
 pub struct ButterComp2 {
-    state: *mut ButterComp2State,
+    /// `None` in degraded mode.
+    state: Option<NonNull<ButterComp2State>>,
 }
 
 impl ButterComp2 {
-    /// Create a new ButterComp2 instance
-    pub fn new(sample_rate: f32) -> Self {
+    /// Create a new ButterComp2 instance, or `None` if the C++ state
+    /// couldn't be allocated.
+    pub fn try_new(sample_rate: f32) -> Option<Self> {
+        // SAFETY: plain constructor call; a null return is handled below.
         let state = unsafe { buttercomp2_create(sample_rate as f64) };
-        assert!(!state.is_null(), "Failed to create ButterComp2 state");
-        Self { state }
+        NonNull::new(state).map(|state| Self { state: Some(state) })
+    }
+
+    /// `try_new`, falling back to a degraded (pass-through) instance and
+    /// logging why. Check `is_degraded` to tell the user.
+    pub fn new(sample_rate: f32) -> Self {
+        Self::try_new(sample_rate).unwrap_or_else(|| {
+            nih_log!("ButterComp2: couldn't allocate the C++ core; Classic model bypassed");
+            Self { state: None }
+        })
+    }
+
+    /// True when the C++ core is missing and the Classic model passes audio
+    /// through untouched.
+    pub fn is_degraded(&self) -> bool {
+        self.state.is_none()
     }
 
     /// Update compressor parameters
     ///
     /// # Arguments
-    /// * `compress` - Compression amount (0.0 to 1.0, maps to 0-14dB)
-    /// * `output` - Output gain (0.0 to 1.0, maps to 0-2x gain)
+    /// * `compress` - Compression amount (0.0 to 1.0, up to ~7 dB of GR)
+    /// * `output` - Output gain (0.0 to 1.0, −8 dB to +6 dB)
     /// * `dry_wet` - Dry/wet mix (0.0 = dry, 1.0 = wet)
-    pub fn update_parameters(&mut self, compress: f32, output: f32, dry_wet: f32) {
-        // Scale parameters to prevent over-compression and distortion
-        let safe_compress = (compress * 0.5).clamp(0.0, 0.5); // Reduce max compression
-        let safe_output = (output * 0.8 + 0.2).clamp(0.2, 1.0); // Keep output in reasonable range
+    /// * `faithful` - Pass compress and output to the core unscaled (up to
+    ///   ~14 dB of GR, output from silence to +6 dB)
+    ///
+    /// The knob-to-core mapping lives in `comp_scale`, which the params'
+    /// dB readouts are computed from.
+    pub fn update_parameters(&mut self, compress: f32, output: f32, dry_wet: f32, faithful: bool) {
+        // Scaled down from the core's full range to prevent over-compression
+        // and distortion, unless the user asked for the whole range.
+        let safe_compress = comp_scale::core_compress(compress, faithful);
+        let safe_output = comp_scale::core_output(output, faithful);
         let safe_dry_wet = dry_wet.clamp(0.0, 1.0);
 
+        let Some(state) = self.state else {
+            return;
+        };
+        // SAFETY: `state` came from `buttercomp2_create` and lives until drop.
         unsafe {
-            buttercomp2_set_compress(self.state, safe_compress as f64);
-            buttercomp2_set_output(self.state, safe_output as f64);
-            buttercomp2_set_dry_wet(self.state, safe_dry_wet as f64);
+            buttercomp2_set_compress(state.as_ptr(), safe_compress as f64);
+            buttercomp2_set_output(state.as_ptr(), safe_output as f64);
+            buttercomp2_set_dry_wet(state.as_ptr(), safe_dry_wet as f64);
         }
     }
 
@@ -899,6 +1190,9 @@ impl ButterComp2 {
     /// over `num_samples` internally — see buttercomp2_process_stereo in cpp/.
     pub fn process(&mut self, buffer: &mut Buffer) {
         let num_samples = buffer.samples();
+        let Some(state) = self.state else {
+            return;
+        };
         if num_samples == 0 {
             return;
         }
@@ -922,24 +1216,28 @@ impl ButterComp2 {
             // element of non-overlapping, contiguous channel slices of length
             // num_samples. buttercomp2_process_stereo iterates [0..num_samples).
             unsafe {
-                buttercomp2_process_stereo(self.state, ch[0], ch[1], num_samples as i32);
+                buttercomp2_process_stereo(state.as_ptr(), ch[0], ch[1], num_samples as i32);
             }
         }
     }
 
     /// Reset internal state
     pub fn reset(&mut self) {
-        unsafe {
-            buttercomp2_reset(self.state);
+        if let Some(state) = self.state {
+            // SAFETY: `state` came from `buttercomp2_create` and lives until drop.
+            unsafe {
+                buttercomp2_reset(state.as_ptr());
+            }
         }
     }
 }
 
 impl Drop for ButterComp2 {
     fn drop(&mut self) {
-        if !self.state.is_null() {
+        if let Some(state) = self.state.take() {
+            // SAFETY: created by `buttercomp2_create`, destroyed exactly once.
             unsafe {
-                buttercomp2_destroy(self.state);
+                buttercomp2_destroy(state.as_ptr());
             }
         }
     }
@@ -970,11 +1268,10 @@ mod tests {
     fn test_fet_compressor_new_initial_state() {
         let fet = FetCompressor::new(44100.0);
         assert!((fet.envelope_db - 0.0).abs() < 1e-5);
-        assert!((fet.fast_env_db - 0.0).abs() < 1e-5);
+        assert!((fet.gr_env.value() - 0.0).abs() < 1e-5);
         assert!((fet.input_gain_linear - 1.0).abs() < 1e-5);
         assert!((fet.output_gain_linear - 1.0).abs() < 1e-5);
-        assert!(fet.coeff_attack > 0.0 && fet.coeff_attack < 1.0);
-        assert!(fet.coeff_release > 0.0 && fet.coeff_release < 1.0);
+        assert!(fet.gr_env.attack_coeff() > 0.0 && fet.gr_env.attack_coeff() < 1.0);
     }
 
     #[test]
@@ -990,14 +1287,9 @@ mod tests {
             fet.envelope_db
         );
         assert!(
-            (fet.fast_env_db - 0.0).abs() < 1e-5,
-            "fast_env_db after reset: {}",
-            fet.fast_env_db
-        );
-        assert!(
-            (fet.envelope_fast_db - 0.0).abs() < 1e-5,
-            "envelope_fast_db after reset: {}",
-            fet.envelope_fast_db
+            (fet.gr_env.value() - 0.0).abs() < 1e-5,
+            "GR depth after reset: {}",
+            fet.gr_env.value()
         );
     }
 
@@ -1121,11 +1413,11 @@ mod tests {
         let mut fet = FetCompressor::new(44100.0);
         // First call seeds the cache (NaN → real value)
         fet.update_parameters(0.0, 0.0, 1.0, 200.0, FetRatio::R4, false, 20.0);
-        let coeff_before = fet.coeff_attack;
+        let coeff_before = fet.gr_env.attack_coeff();
         // Same parameters — dirty check should skip recompute
         fet.update_parameters(0.0, 0.0, 1.0, 200.0, FetRatio::R4, false, 20.0);
         assert!(
-            (fet.coeff_attack - coeff_before).abs() < 1e-9,
+            (fet.gr_env.attack_coeff() - coeff_before).abs() < 1e-9,
             "Dirty-check bypass: coeff should not change"
         );
     }
@@ -1135,12 +1427,12 @@ mod tests {
         let mut fet = FetCompressor::new(44100.0);
         // First call seeds the cache
         fet.update_parameters(0.0, 0.0, 1.0, 200.0, FetRatio::R4, false, 20.0);
-        let coeff_before = fet.coeff_attack;
+        let coeff_before = fet.gr_env.attack_coeff();
         // Different attack time — dirty check fires, coefficients recomputed
         fet.update_parameters(0.0, 0.0, 10.0, 200.0, FetRatio::R4, false, 20.0);
         assert!(
-            (fet.coeff_attack - coeff_before).abs() > 1e-5,
-            "New attack time should change coeff_attack"
+            (fet.gr_env.attack_coeff() - coeff_before).abs() > 1e-5,
+            "New attack time should change the attack coefficient"
         );
     }
 
@@ -1153,12 +1445,89 @@ mod tests {
         let mut fet = FetCompressor::new(sr);
         fet.update_parameters(0.0, 0.0, ms, 200.0, FetRatio::R4, false, 20.0);
         assert!(
-            (fet.coeff_attack - expected).abs() < 1e-7,
+            (fet.gr_env.attack_coeff() - expected).abs() < 1e-7,
             "coeff mismatch: {} vs {expected}",
-            fet.coeff_attack
+            fet.gr_env.attack_coeff()
         );
     }
 
+    // ── TransientProtect ──────────────────────────────────────────────────────
+
+    /// A 100 Hz tone that starts from silence at sample 2205 (50 ms).
+    fn hit(len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| {
+                let t = i.saturating_sub(2205) as f32 / 44100.0;
+                if i < 2205 {
+                    0.0
+                } else {
+                    0.8 * (std::f32::consts::TAU * 100.0 * t).sin()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_transient_protect_ducks_the_attack_not_the_body() {
+        let input = hit(44100);
+        let mut key = input.clone();
+        TransientProtect::new(44100.0).process(&mut [&mut key[..]], 1.0);
+        let gain = |i: usize| key[i] / input[i];
+        let attack = (2210..2646).filter(|&i| input[i].abs() > 0.1).map(gain);
+        assert!(attack.fold(1.0_f32, f32::min) < 0.6, "attack not ducked");
+        // Half a second into the note the detector has let go.
+        let body = (30000..31000).filter(|&i| input[i].abs() > 0.1).map(gain);
+        assert!(body.fold(0.0_f32, f32::max) > 0.99);
+    }
+
+    #[test]
+    fn test_transient_protect_lets_the_hit_through_the_vca() {
+        let input = hit(4410);
+        let run = |depth: f32| {
+            let mut vca = VcaCompressor::new(44100.0);
+            vca.update_parameters(-24.0, 8.0, 1.0, 100.0, 20.0);
+            let mut protect = TransientProtect::new(44100.0);
+            let (mut key_l, mut key_r) = (input.clone(), input.clone());
+            protect.process(&mut [&mut key_l[..], &mut key_r[..]], depth);
+            // Peak output over the first 10 ms of the hit.
+            (0..input.len())
+                .map(|i| {
+                    vca.process_sample_keyed(input[i], input[i], key_l[i], key_r[i])
+                        .0
+                })
+                .skip(2205)
+                .take(441)
+                .fold(0.0_f32, |m, x| m.max(x.abs()))
+        };
+        let (plain, protected) = (run(0.0), run(1.0));
+        assert!(protected > plain * 1.2, "{protected} vs {plain}");
+    }
+
+    #[test]
+    fn test_transient_protect_unlinked_ducks_only_the_hit_channel() {
+        let input = hit(4410);
+        let steady: Vec<f32> = (0..4410)
+            .map(|i| 0.5 * (std::f32::consts::TAU * 100.0 * i as f32 / 44100.0).sin())
+            .collect();
+        let mut protect = TransientProtect::new(44100.0);
+        let (mut key_l, mut key_r) = (input.clone(), steady.clone());
+        protect.set_linked(false);
+        // Settle the right channel's detector on the steady tone first.
+        protect.process(
+            &mut [&mut vec![0.0; 4410][..], &mut steady.clone()[..]],
+            1.0,
+        );
+        protect.process(&mut [&mut key_l[..], &mut key_r[..]], 1.0);
+        let ducked = |key: &[f32], dry: &[f32]| {
+            (2210..2646)
+                .filter(|&i| dry[i].abs() > 0.1)
+                .map(|i| key[i] / dry[i])
+                .fold(1.0_f32, f32::min)
+        };
+        assert!(ducked(&key_l, &input) < 0.6, "hit not ducked");
+        assert!(ducked(&key_r, &steady) > 0.99, "steady channel ducked");
+    }
+
     // ── VcaCompressor ─────────────────────────────────────────────────────────
 
     #[test]
@@ -1188,6 +1557,112 @@ mod tests {
         assert!(out_r.is_finite());
     }
 
+    #[test]
+    fn test_vca_gain_reduction_db_tracks_envelope() {
+        let mut vca = VcaCompressor::new(44100.0);
+        vca.update_parameters(-18.0, 4.0, 5.0, 100.0, 20.0);
+        assert!(
+            vca.gain_reduction_db().abs() < 1e-5,
+            "idle VCA must report 0 dB GR"
+        );
+        for _ in 0..4410 {
+            vca.process_sample(0.9, 0.9);
+        }
+        assert!(
+            vca.gain_reduction_db() > 1.0,
+            "loud signal should report positive GR, got {}",
+            vca.gain_reduction_db()
+        );
+        vca.reset();
+        assert!(vca.gain_reduction_db().abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_unlinked_detectors_leave_the_quiet_channel_alone() {
+        // Loud left, quiet right: linked, the right is pulled down with the
+        // left; unlinked, it passes at the model's makeup gain alone.
+        let mut fet = FetCompressor::new(44100.0);
+        let mut vca = VcaCompressor::new(44100.0);
+        for linked in [true, false] {
+            fet.reset();
+            vca.reset();
+            fet.update_parameters(20.0, 0.0, 0.5, 100.0, FetRatio::R8, false, 20.0);
+            vca.update_parameters(-24.0, 8.0, 1.0, 100.0, 20.0);
+            fet.set_linked(linked);
+            vca.set_linked(linked);
+            let (mut fet_r, mut vca_r) = (0.0, 0.0);
+            for i in 0..4410 {
+                let loud = 0.9 * (std::f32::consts::TAU * 1000.0 * i as f32 / 44100.0).sin();
+                fet_r = fet.process_sample(loud, 0.001).1;
+                vca_r = vca.process_sample(loud, 0.001).1;
+            }
+            // The FET's input drive is +20 dB, so unity through it is 0.01.
+            let (fet_gain, vca_gain) = (fet_r / 0.01, vca_r / 0.001);
+            if linked {
+                assert!(fet_gain < 0.5 && vca_gain < 0.5, "{fet_gain} {vca_gain}");
+            } else {
+                assert!((fet_gain - 1.0).abs() < 1e-3, "FET R gain {fet_gain}");
+                assert!((vca_gain - 1.0).abs() < 1e-3, "VCA R gain {vca_gain}");
+            }
+            // The meters follow the channel doing the work either way.
+            assert!(fet.gain_reduction_db() > 6.0 && vca.gain_reduction_db() > 6.0);
+            // Per channel, the quiet side reports its own reduction.
+            for [left, right] in [
+                fet.channel_gain_reduction_db(),
+                vca.channel_gain_reduction_db(),
+            ] {
+                assert!(left > 6.0, "L {left}");
+                if linked {
+                    assert_eq!(left, right);
+                } else {
+                    assert!(right.abs() < 0.01, "R {right}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_keyed_detectors_follow_the_key_not_the_input() {
+        // Quiet program, loud key: every keyed model compresses the program;
+        // a silent key leaves it alone however loud the program is.
+        let sr = 44100.0_f32;
+        let mut vca = VcaCompressor::new(sr);
+        vca.update_parameters(-18.0, 4.0, 5.0, 100.0, 20.0);
+        let mut fet = FetCompressor::new(sr);
+        fet.update_parameters(12.0, 0.0, 1.0, 200.0, FetRatio::R8, false, 20.0);
+        let mut opt = OpticalCompressor::new(sr);
+        opt.update_parameters(-18.0, 0.5, 0.5);
+        let (mut v, mut f, mut o) = ((0.0, 0.0), (0.0, 0.0), (0.0, 0.0));
+        for _ in 0..4410 {
+            v = vca.process_sample_keyed(0.05, 0.05, 0.9, 0.9);
+            f = fet.process_sample_keyed(0.05, 0.05, 0.9, 0.9);
+            o = opt.process_sample_keyed(0.05, 0.05, 0.9, 0.9, -18.0);
+        }
+        assert!(v.0 < 0.04, "VCA should follow the key, got {}", v.0);
+        // +12 dB of drive puts the uncompressed program near 0.2.
+        assert!(f.0 < 0.1, "FET should follow the key, got {}", f.0);
+        assert!(o.0 < 0.04, "optical should follow the key, got {}", o.0);
+
+        let mut vca = VcaCompressor::new(sr);
+        vca.update_parameters(-18.0, 4.0, 5.0, 100.0, 20.0);
+        for _ in 0..4410 {
+            vca.process_sample_keyed(0.9, 0.9, 0.0, 0.0);
+        }
+        assert!(
+            vca.gain_reduction_db() < 0.01,
+            "silent key must not compress"
+        );
+    }
+
+    #[test]
+    fn test_key_frame_spreads_mono_and_pads_with_silence() {
+        let mut mono = [0.5_f32, 0.25];
+        let key = [&mut mono[..]];
+        assert_eq!(key_frame(&key, 1), (0.25, 0.25));
+        assert_eq!(key_frame(&key, 2), (0.0, 0.0));
+        assert_eq!(key_frame(&[], 0), (0.0, 0.0));
+    }
+
     #[test]
     fn test_vca_sc_hp_reduces_gr_for_low_frequency_content() {
         // A loud 30 Hz sine produces full GR with SC HP off, but should
@@ -1278,4 +1753,71 @@ mod tests {
             "Optical compressor should reduce loud signal, got {out_l}"
         );
     }
+
+    // ── Audio-thread allocation guard ─────────────────────────────────────────
+
+    /// Every compressor model processes a host buffer without touching the
+    /// heap (the ButterComp2 FFI path included).
+    #[test]
+    fn test_compressor_process_does_not_allocate() {
+        use crate::alloc_guard::assert_no_alloc;
+        use nih_plug::buffer::Buffer;
+
+        let sr = 44_100.0_f32;
+        let n = 512;
+        let omega = 2.0 * core::f32::consts::PI * 200.0 / sr;
+        let mut data_l: Vec<f32> = (0..n).map(|i| (omega * i as f32).sin()).collect();
+        let mut data_r = data_l.clone();
+        let mut buffer = Buffer::default();
+        unsafe {
+            buffer.set_slices(n, |slices| {
+                slices.clear();
+                slices.push(&mut data_l);
+                slices.push(&mut data_r);
+            });
+        }
+
+        let mut butter = ButterComp2::new(sr);
+        butter.update_parameters(0.8, 0.5, 1.0, false);
+        let mut fet = FetCompressor::new(sr);
+        fet.update_parameters(12.0, 0.0, 0.2, 100.0, FetRatio::All, true, 100.0);
+        let mut vca = VcaCompressor::new(sr);
+        vca.update_parameters(-18.0, 4.0, 10.0, 100.0, 60.0);
+        let mut opt = OpticalCompressor::new(sr);
+        opt.update_parameters(-18.0, 0.5, 0.5);
+
+        assert_no_alloc(|| {
+            butter.process(&mut buffer);
+            fet.process(&mut buffer);
+            vca.process(&mut buffer);
+            opt.process(&mut buffer, -18.0);
+        });
+    }
+
+    #[test]
+    fn test_degraded_buttercomp2_is_a_clean_bypass() {
+        let sr = 44_100.0_f32;
+        assert!(!ButterComp2::new(sr).is_degraded());
+
+        // What `new` falls back to when the C++ core can't be allocated.
+        let mut degraded = ButterComp2 { state: None };
+        assert!(degraded.is_degraded());
+        let mut data_l: Vec<f32> = (0..64).map(|i| (i as f32 * 0.1).sin()).collect();
+        let mut data_r = data_l.clone();
+        let expected = data_l.clone();
+        let mut buffer = Buffer::default();
+        unsafe {
+            buffer.set_slices(64, |slices| {
+                slices.clear();
+                slices.push(&mut data_l);
+                slices.push(&mut data_r);
+            });
+        }
+        degraded.update_parameters(1.0, 1.0, 1.0);
+        degraded.process(&mut buffer);
+        degraded.reset();
+        drop(degraded);
+        assert_eq!(data_l, expected);
+        assert_eq!(data_r, expected);
+    }
 }
@@ -0,0 +1,166 @@
+// src/chain.rs
+// The slot modules as one unit.
+//
+// Everything the seven reorderable slots run lives here: the modules, the
+// compressor's key EQ and transient protect, Punch's link follower and the
+// bypass bookkeeping. The pinned stages (input conditioning, Sheen) and all
+// metering stay on the strip.
+//
+// The strip keeps two chains, the live one and a spare, so a reorder can
+// fade from the old order on one module set to the new order on the other
+// without either running twice in a block; see reorder.rs. Each chain is
+// boxed, so trading them is a pointer swap on the audio thread.
+
+#[cfg(feature = "api5500")]
+use crate::api5500::Api5500;
+#[cfg(feature = "buttercomp2")]
+use crate::balance::BalanceTrim;
+#[cfg(feature = "buttercomp2")]
+use crate::buttercomp2::{
+    ButterComp2, FetCompressor, OpticalCompressor, TransientProtect, VcaCompressor,
+};
+#[cfg(feature = "dynamic_eq")]
+use crate::dynamic_eq::DynamicEQ;
+#[cfg(feature = "haas")]
+use crate::haas::HaasModule;
+#[cfg(feature = "buttercomp2")]
+use crate::key_eq::KeyEq;
+#[cfg(feature = "pultec")]
+use crate::pultec::PultecEQ;
+#[cfg(feature = "punch")]
+use crate::punch::PunchModule;
+#[cfg(feature = "punch")]
+use crate::punch_link::PunchLink;
+#[cfg(feature = "transformer")]
+use crate::transformer::TransformerModule;
+
+pub struct Chain {
+    /// API 5500–style input EQ module
+    #[cfg(feature = "api5500")]
+    pub eq_api5500: Api5500,
+    /// ButterComp2 compressor module
+    #[cfg(feature = "buttercomp2")]
+    pub compressor: ButterComp2,
+    /// 1176-style FET compressor — pure Rust, no FFI
+    #[cfg(feature = "buttercomp2")]
+    pub fet_compressor: FetCompressor,
+    /// VCA bus compressor — SSL G-Bus style, pure Rust, no FFI
+    #[cfg(feature = "buttercomp2")]
+    pub vca_compressor: VcaCompressor,
+    /// Optical compressor — LA-2A style, pure Rust, no FFI
+    #[cfg(feature = "buttercomp2")]
+    pub optical_compressor: OpticalCompressor,
+    /// Per-channel trim and auto balance after whichever model ran.
+    #[cfg(feature = "buttercomp2")]
+    pub comp_balance: BalanceTrim,
+    /// Key EQ ahead of the VCA, Optical and FET detectors.
+    #[cfg(feature = "buttercomp2")]
+    pub comp_key_eq: KeyEq,
+    /// Ducks the key on transients for `comp_transient_protect`.
+    #[cfg(feature = "buttercomp2")]
+    pub comp_protect: TransientProtect,
+    /// Pultec-style EQ module
+    #[cfg(feature = "pultec")]
+    pub pultec: PultecEQ,
+    /// Dynamic EQ module
+    #[cfg(feature = "dynamic_eq")]
+    pub dynamic_eq: DynamicEQ,
+    /// Transformer coloration module
+    #[cfg(feature = "transformer")]
+    pub transformer: TransformerModule,
+    /// Punch module (Clipper + Transient Shaper)
+    #[cfg(feature = "punch")]
+    pub punch: PunchModule,
+    /// Compressor gain reduction, smoothed for Punch's transient link.
+    #[cfg(feature = "punch")]
+    pub punch_link: PunchLink,
+    /// Haas psychoacoustic stereo widener
+    #[cfg(feature = "haas")]
+    pub haas: HaasModule,
+    /// Each module's bypass switch as of its last dispatch, indexed by
+    /// `module_type_index`; the first bypassed buffer clears its state.
+    pub module_bypassed: [bool; 8],
+}
+
+impl Chain {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            #[cfg(feature = "api5500")]
+            eq_api5500: Api5500::new(sample_rate),
+            #[cfg(feature = "buttercomp2")]
+            compressor: ButterComp2::new(sample_rate),
+            #[cfg(feature = "buttercomp2")]
+            fet_compressor: FetCompressor::new(sample_rate),
+            #[cfg(feature = "buttercomp2")]
+            vca_compressor: VcaCompressor::new(sample_rate),
+            #[cfg(feature = "buttercomp2")]
+            optical_compressor: OpticalCompressor::new(sample_rate),
+            #[cfg(feature = "buttercomp2")]
+            comp_balance: BalanceTrim::new(sample_rate),
+            #[cfg(feature = "buttercomp2")]
+            comp_key_eq: KeyEq::new(sample_rate),
+            #[cfg(feature = "buttercomp2")]
+            comp_protect: TransientProtect::new(sample_rate),
+            #[cfg(feature = "pultec")]
+            pultec: PultecEQ::new(sample_rate),
+            #[cfg(feature = "dynamic_eq")]
+            dynamic_eq: DynamicEQ::new(sample_rate),
+            #[cfg(feature = "transformer")]
+            transformer: TransformerModule::new(sample_rate),
+            #[cfg(feature = "punch")]
+            punch: PunchModule::new(sample_rate),
+            #[cfg(feature = "punch")]
+            punch_link: PunchLink::new(sample_rate),
+            #[cfg(feature = "haas")]
+            haas: HaasModule::new(sample_rate),
+            module_bypassed: [false; 8],
+        }
+    }
+
+    /// Clear every module's state, as if the chain had just been built.
+    /// Allocation-free; safe to call on the audio thread.
+    pub fn reset(&mut self) {
+        #[cfg(feature = "api5500")]
+        self.eq_api5500.reset();
+        #[cfg(feature = "buttercomp2")]
+        {
+            self.compressor.reset();
+            self.fet_compressor.reset();
+            self.vca_compressor.reset();
+            self.optical_compressor.reset();
+            self.comp_balance.reset();
+            self.comp_key_eq.reset();
+            self.comp_protect.reset();
+        }
+        #[cfg(feature = "pultec")]
+        self.pultec.reset();
+        #[cfg(feature = "dynamic_eq")]
+        self.dynamic_eq.reset();
+        #[cfg(feature = "transformer")]
+        self.transformer.reset();
+        #[cfg(feature = "punch")]
+        {
+            self.punch.reset();
+            self.punch_link.reset();
+        }
+        #[cfg(feature = "haas")]
+        self.haas.reset();
+        self.module_bypassed = [false; 8];
+    }
+
+    /// Clear the detector envelopes only; the static EQs and delay lines
+    /// keep running. See the strip's `reset_envelopes`.
+    pub fn reset_envelopes(&mut self) {
+        #[cfg(feature = "buttercomp2")]
+        {
+            self.compressor.reset();
+            self.fet_compressor.reset();
+            self.vca_compressor.reset();
+            self.optical_compressor.reset();
+        }
+        #[cfg(feature = "dynamic_eq")]
+        self.dynamic_eq.reset();
+        #[cfg(feature = "transformer")]
+        self.transformer.reset();
+    }
+}
@@ -0,0 +1,157 @@
+// src/character.rs
+// Clean vs Vintage character for the EQ modules (API 5500, Pultec).
+//
+// Clean is the digital filters and nothing else. Vintage runs a parallel
+// path that voices the hardware the filters came from:
+//
+// - Band interaction, owned by each module since it depends on the
+//   circuit: the API's bands share one summing amp, so a band pulls its
+//   neighbours a little and its bells narrow as they boost (proportional
+//   Q); the Pultec's passive sections load each other, so a cut eats into
+//   the boost on the same side.
+// - `VintageStage` below, shared: a first-order allpass that lags the top
+//   octave the way an amplifier's finite bandwidth does, magnitude
+//   untouched, then a white noise floor per channel.
+//
+// Each channel's noise comes from its own generator, seeded apart, so the
+// floor doesn't image in the centre. Nothing allocates after `new()`.
+
+#[cfg(feature = "plugin")]
+use nih_plug::prelude::Enum;
+
+/// Character switch shared by the EQ modules.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "plugin", derive(Enum))]
+pub enum EqCharacter {
+    #[cfg_attr(feature = "plugin", name = "Clean")]
+    Clean,
+    #[cfg_attr(feature = "plugin", name = "Vintage")]
+    Vintage,
+}
+
+const CHANNELS: usize = 2;
+/// Seeds for the per-channel noise generators; never zero.
+const SEEDS: [u32; CHANNELS] = [0x2545_F491, 0x6C07_8965];
+
+/// Allpass phase lag and noise floor of a vintage EQ's electronics.
+pub struct VintageStage {
+    /// First-order allpass coefficient for the corner.
+    coeff: f32,
+    /// Allpass state per channel: last input, last output.
+    state: [(f32, f32); CHANNELS],
+    /// xorshift32 state per channel.
+    rng: [u32; CHANNELS],
+    /// Scale from a uniform draw in [−0.5, 0.5) to the noise floor's RMS.
+    noise_scale: f32,
+    /// Level of the noise floor relative to `noise_db`, set by the analog
+    /// amount macro.
+    noise_gain: f32,
+}
+
+impl VintageStage {
+    /// `corner_hz` is where the allpass lags 90°; `noise_db` is the noise
+    /// floor's RMS level in dBFS.
+    pub fn new(sample_rate: f32, corner_hz: f32, noise_db: f32) -> Self {
+        let t = (std::f32::consts::PI * corner_hz.min(0.45 * sample_rate) / sample_rate).tan();
+        Self {
+            coeff: (t - 1.0) / (t + 1.0),
+            state: [(0.0, 0.0); CHANNELS],
+            rng: SEEDS,
+            // A uniform draw has an RMS of 1/√12.
+            noise_scale: 12.0_f32.sqrt() * 10.0_f32.powf(noise_db / 20.0),
+            noise_gain: 1.0,
+        }
+    }
+
+    /// Scale the noise floor by `gain` (linear); 1.0 is the level given
+    /// to `new()`.
+    pub fn set_noise_gain(&mut self, gain: f32) {
+        self.noise_gain = gain.max(0.0);
+    }
+
+    /// Run the stage in place; `phase` false adds only the noise, for a
+    /// linear-phase path. Channels past the second pass through.
+    pub fn process(&mut self, channels: &mut [&mut [f32]], phase: bool) {
+        let coeff = self.coeff;
+        let noise_scale = self.noise_scale * self.noise_gain;
+        let channels = channels
+            .iter_mut()
+            .zip(self.state.iter_mut().zip(&mut self.rng));
+        for (channel, ((x1, y1), rng)) in channels {
+            for sample in channel.iter_mut() {
+                let x = *sample;
+                let y = if phase {
+                    let y = coeff * x + *x1 - coeff * *y1;
+                    *x1 = x;
+                    *y1 = y;
+                    y
+                } else {
+                    x
+                };
+                *rng ^= *rng << 13;
+                *rng ^= *rng >> 17;
+                *rng ^= *rng << 5;
+                let uniform = (*rng >> 8) as f32 / (1u32 << 24) as f32 - 0.5;
+                *sample = y + uniform * noise_scale;
+            }
+        }
+    }
+
+    /// Clear the allpass history. The generators keep running so a reset
+    /// doesn't replay the same noise.
+    pub fn reset(&mut self) {
+        self.state = [(0.0, 0.0); CHANNELS];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    fn rms(x: &[f32]) -> f32 {
+        (x.iter().map(|s| s * s).sum::<f32>() / x.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_allpass_keeps_level_and_lags_the_top() {
+        // Noise far below the sine's level, so only the allpass shows.
+        let mut stage = VintageStage::new(SR, 18_000.0, -200.0);
+        // (frequency, least and most correlation with the dry sine): the
+        // lag is negligible through the mids and clear near the top.
+        for (freq, low, high) in [
+            (100.0, 0.999, 1.0),
+            (1000.0, 0.99, 1.0),
+            (10_000.0, 0.7, 0.9),
+        ] {
+            let sine: Vec<f32> = (0..9600)
+                .map(|i| (std::f32::consts::TAU * freq * i as f32 / SR).sin())
+                .collect();
+            let mut out = sine.clone();
+            stage.process(&mut [&mut out[..]], true);
+            let (out, dry) = (&out[4800..], &sine[4800..]);
+            let ratio = rms(out) / rms(dry);
+            assert!((ratio - 1.0).abs() < 0.01, "{freq} Hz level {ratio}");
+            let corr = out.iter().zip(dry).map(|(a, b)| a * b).sum::<f32>()
+                / (rms(out) * rms(dry) * out.len() as f32);
+            assert!(
+                corr >= low && corr <= high + 1e-4,
+                "{freq} Hz correlation {corr}"
+            );
+            stage.reset();
+        }
+    }
+
+    #[test]
+    fn test_noise_floor_sits_at_its_level_and_decorrelates() {
+        let mut stage = VintageStage::new(SR, 18_000.0, -100.0);
+        let (mut l, mut r) = (vec![0.0; 48_000], vec![0.0; 48_000]);
+        stage.process(&mut [&mut l[..], &mut r[..]], true);
+        let level = 20.0 * rms(&l).log10();
+        assert!((level + 100.0).abs() < 0.5, "noise {level} dBFS");
+        let corr = l.iter().zip(&r).map(|(a, b)| a * b).sum::<f32>()
+            / (rms(&l) * rms(&r) * l.len() as f32);
+        assert!(corr.abs() < 0.02, "L/R correlation {corr}");
+    }
+}
@@ -0,0 +1,191 @@
+// src/comp_scale.rs
+// Calibrated display for the classic ButterComp2 core's knobs.
+//
+// `comp_compress` and `comp_output` stay 0–1 params so saved sessions and
+// automation keep their values, but a bare 0.42 tells nobody anything. The
+// mapping from knob to core lives here, and the params' formatters read it
+// back out in dB:
+//
+// - Compress: the wrapper halves the knob and the core scales that to 14 dB,
+//   so full travel asks for 7 dB. The core is program-dependent, so this is
+//   the most it will pull down on a hot bus, not a threshold-and-ratio
+//   figure; the display marks it as approximate.
+// - Output: the wrapper lifts the knob into 0.2–1.0 and the core doubles
+//   it, a 0.4–2.0× gain: −8.0 dB to +6.0 dB, unity at 0.375 (the default).
+//   The default used to be 0.5, +1.6 dB of make-up on a module set to do
+//   nothing; the factory presets that use the compressor keep that.
+//
+// Typed values go the other way, so "4 dB" on Compress or "-2" on Output
+// lands on the knob position that produces it.
+//
+// `comp_faithful` skips both mappings and hands the knobs to the core as
+// they are, the full Airwindows range: up to 14 dB of compress, and an
+// output from silence to +6 dB. The formatters read the mode through a
+// flag the param's callback keeps current.
+
+use nih_plug::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Core compress amount at full knob travel (the wrapper feeds it 0–0.5).
+const CORE_COMPRESS_DB: f32 = 14.0;
+const WRAPPER_COMPRESS_SCALE: f32 = 0.5;
+/// Wrapper output range fed to the core, which doubles it.
+const WRAPPER_OUTPUT_MIN: f32 = 0.2;
+const WRAPPER_OUTPUT_SPAN: f32 = 0.8;
+const CORE_OUTPUT_GAIN: f32 = 2.0;
+
+/// Output knob position for unity gain through the wrapper's mapping.
+pub const UNITY_OUTPUT: f32 = (1.0 / CORE_OUTPUT_GAIN - WRAPPER_OUTPUT_MIN) / WRAPPER_OUTPUT_SPAN;
+
+/// Core-side compress amount for a knob position: 0–0.5, or 0–1 when
+/// `faithful`.
+pub fn core_compress(compress: f32, faithful: bool) -> f32 {
+    let scale = compress_scale(faithful);
+    (compress * scale).clamp(0.0, scale)
+}
+
+/// Core-side output amount for a knob position: 0.2–1.0, or 0–1 when
+/// `faithful`.
+pub fn core_output(output: f32, faithful: bool) -> f32 {
+    if faithful {
+        output.clamp(0.0, 1.0)
+    } else {
+        (output * WRAPPER_OUTPUT_SPAN + WRAPPER_OUTPUT_MIN)
+            .clamp(WRAPPER_OUTPUT_MIN, WRAPPER_OUTPUT_MIN + WRAPPER_OUTPUT_SPAN)
+    }
+}
+
+fn compress_scale(faithful: bool) -> f32 {
+    if faithful {
+        1.0
+    } else {
+        WRAPPER_COMPRESS_SCALE
+    }
+}
+
+/// Approximate most gain reduction, in dB, a compress position asks for.
+pub fn compress_to_db(compress: f32, faithful: bool) -> f32 {
+    core_compress(compress, faithful) * CORE_COMPRESS_DB
+}
+
+/// Knob position for `db` of gain reduction, clamped to the knob's range.
+pub fn db_to_compress(db: f32, faithful: bool) -> f32 {
+    (db / (compress_scale(faithful) * CORE_COMPRESS_DB)).clamp(0.0, 1.0)
+}
+
+/// Output gain in dB for a knob position.
+pub fn output_to_db(output: f32, faithful: bool) -> f32 {
+    util::gain_to_db(core_output(output, faithful) * CORE_OUTPUT_GAIN)
+}
+
+/// Knob position for `db` of output gain, clamped to the knob's range.
+pub fn db_to_output(db: f32, faithful: bool) -> f32 {
+    let core = util::db_to_gain(db) / CORE_OUTPUT_GAIN;
+    if faithful {
+        core.clamp(0.0, 1.0)
+    } else {
+        ((core - WRAPPER_OUTPUT_MIN) / WRAPPER_OUTPUT_SPAN).clamp(0.0, 1.0)
+    }
+}
+
+pub fn v2s_compress(faithful: Arc<AtomicBool>) -> Arc<dyn Fn(f32) -> String + Send + Sync> {
+    Arc::new(move |value| {
+        format!(
+            "≈{:.1}",
+            compress_to_db(value, faithful.load(Ordering::Relaxed))
+        )
+    })
+}
+
+pub fn s2v_compress(faithful: Arc<AtomicBool>) -> Arc<dyn Fn(&str) -> Option<f32> + Send + Sync> {
+    Arc::new(move |string| {
+        parse_db(string).map(|db| db_to_compress(db, faithful.load(Ordering::Relaxed)))
+    })
+}
+
+pub fn v2s_output(faithful: Arc<AtomicBool>) -> Arc<dyn Fn(f32) -> String + Send + Sync> {
+    Arc::new(move |value| {
+        format!(
+            "{:+.1}",
+            output_to_db(value, faithful.load(Ordering::Relaxed))
+        )
+    })
+}
+
+pub fn s2v_output(faithful: Arc<AtomicBool>) -> Arc<dyn Fn(&str) -> Option<f32> + Send + Sync> {
+    Arc::new(move |string| {
+        parse_db(string).map(|db| db_to_output(db, faithful.load(Ordering::Relaxed)))
+    })
+}
+
+/// Callback for `comp_faithful` that keeps the formatters' flag current.
+pub fn faithful_callback(faithful: Arc<AtomicBool>) -> Arc<dyn Fn(bool) + Send + Sync> {
+    Arc::new(move |value| faithful.store(value, Ordering::Relaxed))
+}
+
+/// A dB figure as typed: an optional `≈`, the number, an optional unit.
+fn parse_db(string: &str) -> Option<f32> {
+    let string = string.trim().trim_start_matches('≈');
+    let string = string
+        .strip_suffix("dB")
+        .or_else(|| string.strip_suffix("db"))
+        .unwrap_or(string);
+    string.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress_spans_zero_to_seven_db() {
+        assert_eq!(compress_to_db(0.0, false), 0.0);
+        assert!((compress_to_db(1.0, false) - 7.0).abs() < 1e-5);
+        assert!((compress_to_db(0.5, false) - 3.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_output_range_and_default() {
+        assert!((output_to_db(0.0, false) + 7.96).abs() < 0.01);
+        assert!((output_to_db(0.5, false) - 1.58).abs() < 0.01);
+        assert!(output_to_db(UNITY_OUTPUT, false).abs() < 1e-4);
+        assert!((output_to_db(1.0, false) - 6.02).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_faithful_passes_the_knobs_through() {
+        assert_eq!(core_compress(0.8, true), 0.8);
+        assert_eq!(core_output(0.1, true), 0.1);
+        assert!((compress_to_db(1.0, true) - 14.0).abs() < 1e-5);
+        // Unity at half travel, +6 dB at the top.
+        assert!(output_to_db(0.5, true).abs() < 0.01);
+        assert!((output_to_db(1.0, true) - 6.02).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_typed_values_round_trip() {
+        for faithful in [false, true] {
+            let flag = Arc::new(AtomicBool::new(faithful));
+            let (v2s_c, s2v_c) = (v2s_compress(flag.clone()), s2v_compress(flag.clone()));
+            let (v2s_o, s2v_o) = (v2s_output(flag.clone()), s2v_output(flag));
+            for knob in [0.0, 0.13, 0.5, 0.87, 1.0] {
+                let compress = s2v_c(&v2s_c(knob)).unwrap();
+                assert!((compress - knob).abs() < 0.01, "{knob} -> {compress}");
+                let output = s2v_o(&v2s_o(knob)).unwrap();
+                assert!((output - knob).abs() < 0.01, "{knob} -> {output}");
+            }
+        }
+        let flag = Arc::new(AtomicBool::new(false));
+        assert_eq!(
+            s2v_output(flag.clone())("0 dB"),
+            Some(db_to_output(0.0, false))
+        );
+        assert_eq!(s2v_compress(flag.clone())("99"), Some(1.0));
+        assert_eq!(s2v_compress(flag.clone())("loud"), None);
+        // The readout follows the mode.
+        let v2s = v2s_compress(flag.clone());
+        faithful_callback(flag)(true);
+        assert_eq!(v2s(1.0), "≈14.0");
+    }
+}
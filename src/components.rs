@@ -2,11 +2,16 @@
 // Reusable UI components for the Bus Channel Strip editor
 
 use nih_plug::prelude::*;
-use std::sync::Arc;
+use std::cell::{Cell, RefCell};
+use std::sync::{Arc, Mutex};
 use vizia_plug::vizia::prelude::*;
 use vizia_plug::widgets::*;
 
-use crate::BusChannelStripParams;
+use crate::editor::{tr, HistoryEvent};
+use crate::i18n;
+use crate::meter_scale::{MeterReading, MeterScale, MeterZone, READING_FLOOR_DB};
+use crate::spectral;
+use crate::{BusChannelStripParams, EditorSkin};
 
 // ── Layout constants ──────────────────────────────────────────────────────────
 // In morphorm, height(Auto) on a leaf node (no children) resolves to 0, not
@@ -29,7 +34,7 @@ pub fn module_row(cx: &mut Context, builder: impl FnOnce(&mut Context)) -> Handl
 /// Single point of failure for all labeled sections across all modules.
 pub fn module_section(cx: &mut Context, title: &str, builder: impl FnOnce(&mut Context)) {
     VStack::new(cx, |cx| {
-        Label::new(cx, title)
+        Label::new(cx, tr(title))
             .class("section-label")
             .height(Pixels(PARAM_LABEL_H))
             .width(Stretch(1.0));
@@ -40,6 +45,52 @@ pub fn module_section(cx: &mut Context, title: &str, builder: impl FnOnce(&mut C
     .gap(Pixels(4.0));
 }
 
+/// Collapsible "ADVANCED" drawer at the foot of a module card. The basic
+/// view stays the hardware faceplate; oversampling, sidechain keying, ADAA
+/// and the like live in here. `open` drives the chevron and the body, which
+/// is only built while open; `toggle` fires on the header.
+pub fn advanced_drawer<L>(
+    cx: &mut Context,
+    open: L,
+    toggle: impl Fn(&mut EventContext) + 'static,
+    builder: impl Fn(&mut Context) + 'static,
+) where
+    L: Lens<Target = bool> + Copy,
+{
+    VStack::new(cx, |cx| {
+        Button::new(cx, |cx| {
+            HStack::new(cx, |cx| {
+                Label::new(cx, tr("ADVANCED"))
+                    .class("section-label")
+                    .width(Stretch(1.0));
+                Label::new(cx, open.map(|o| if *o { "▼" } else { "▶" }))
+                    .class("advanced-chevron")
+                    .width(Pixels(16.0));
+            })
+            .height(Auto)
+            .width(Stretch(1.0))
+        })
+        .class("advanced-toggle")
+        .toggle_class("advanced-toggle-open", open)
+        .on_press(toggle)
+        .cursor(CursorIcon::Hand)
+        .height(Pixels(PARAM_LABEL_H + 4.0))
+        .width(Stretch(1.0));
+        Binding::new(cx, open, move |cx, open| {
+            if open.get(cx) {
+                VStack::new(cx, |cx| builder(cx))
+                    .class("advanced-drawer")
+                    .height(Auto)
+                    .width(Stretch(1.0))
+                    .gap(Pixels(4.0));
+            }
+        });
+    })
+    .height(Auto)
+    .width(Stretch(1.0))
+    .gap(Pixels(4.0));
+}
+
 // Theme configuration for modules. `Empty` is the unoccupied-slot variant —
 // rendered as a muted dashed placeholder with the library picker inside.
 #[derive(Clone, Copy)]
@@ -68,16 +119,54 @@ impl ModuleTheme {
         }
     }
 
-    pub fn accent_color(self) -> Color {
-        match self {
-            Self::Api5500 => Color::rgb(64, 160, 208),     // #40a0d0
-            Self::ButterComp2 => Color::rgb(255, 150, 64), // #ff9640
-            Self::Pultec => Color::rgb(255, 215, 0),       // #ffd700
-            Self::DynamicEq => Color::rgb(102, 204, 102),  // #66cc66
-            Self::Transformer => Color::rgb(204, 102, 51), // #cc6633
-            Self::Punch => Color::rgb(255, 51, 68),        // #ff3344 (red/orange per spec)
-            Self::Haas => Color::rgb(140, 160, 210),       // #8ca0d2 (muted blue-lavender)
-            Self::Empty => Color::rgb(110, 116, 128),      // #6e7480 (neutral steel)
+    /// Accent for this module under the given skin. Dark is the reference
+    /// palette; Light deepens each hue so it reads on pale faceplates, and
+    /// Classic pulls everything toward the muted paint of vintage hardware.
+    pub fn accent_color(self, skin: EditorSkin) -> Color {
+        let (r, g, b) = match skin {
+            EditorSkin::Dark => match self {
+                Self::Api5500 => (64, 160, 208),     // #40a0d0
+                Self::ButterComp2 => (255, 150, 64), // #ff9640
+                Self::Pultec => (255, 215, 0),       // #ffd700
+                Self::DynamicEq => (102, 204, 102),  // #66cc66
+                Self::Transformer => (204, 102, 51), // #cc6633
+                Self::Punch => (255, 51, 68),        // #ff3344 (red/orange per spec)
+                Self::Haas => (140, 160, 210),       // #8ca0d2 (muted blue-lavender)
+                Self::Empty => (110, 116, 128),      // #6e7480 (neutral steel)
+            },
+            EditorSkin::Light => match self {
+                Self::Api5500 => (24, 110, 160),    // #186ea0
+                Self::ButterComp2 => (208, 96, 16), // #d06010
+                Self::Pultec => (176, 136, 0),      // #b08800
+                Self::DynamicEq => (40, 140, 56),   // #288c38
+                Self::Transformer => (168, 72, 28), // #a8481c
+                Self::Punch => (200, 24, 44),       // #c8182c
+                Self::Haas => (80, 96, 168),        // #5060a8
+                Self::Empty => (150, 156, 166),     // #969ca6
+            },
+            EditorSkin::Classic => match self {
+                Self::Api5500 => (120, 170, 196),    // #78aac4
+                Self::ButterComp2 => (222, 160, 96), // #dea060
+                Self::Pultec => (226, 200, 110),     // #e2c86e
+                Self::DynamicEq => (140, 188, 128),  // #8cbc80
+                Self::Transformer => (196, 124, 84), // #c47c54
+                Self::Punch => (214, 84, 80),        // #d65450
+                Self::Haas => (164, 174, 204),       // #a4aecc
+                Self::Empty => (96, 100, 104),       // #606468
+            },
+        };
+        Color::rgb(r, g, b)
+    }
+
+    /// Base fill behind a slot's theme gradient.
+    pub fn slot_background(self, skin: EditorSkin) -> Color {
+        match (skin, self) {
+            (EditorSkin::Dark, Self::Empty) => Color::rgb(36, 36, 38),
+            (EditorSkin::Dark, _) => Color::rgb(42, 42, 42),
+            (EditorSkin::Light, Self::Empty) => Color::rgb(214, 217, 222),
+            (EditorSkin::Light, _) => Color::rgb(236, 238, 241),
+            (EditorSkin::Classic, Self::Empty) => Color::rgb(22, 23, 24),
+            (EditorSkin::Classic, _) => Color::rgb(98, 112, 124),
         }
     }
 }
@@ -89,17 +178,23 @@ where
     L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
     F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &P,
 {
+    let tooltip_lens = lens.clone();
+    let ptr = param_map(&lens.get(cx)).as_ptr();
     VStack::new(cx, |cx| {
-        Label::new(cx, label)
+        Label::new(cx, tr(label))
             .class("param-label")
             .height(Pixels(PARAM_LABEL_H))
             .width(Stretch(1.0));
 
         ParamSlider::new(cx, lens, param_map)
+            .param_entry(ptr)
             .height(Pixels(20.0))
             .width(Stretch(1.0));
     })
     .class("param-control")
+    .tooltip(move |cx| build_param_tooltip(cx, tooltip_lens.clone(), param_map))
+    .param_menu(ptr)
+    .param_access(ptr)
     .width(Stretch(1.0))
     .height(Auto)
     .top(Pixels(0.0))
@@ -108,14 +203,520 @@ where
 
 // Removed problematic raw param slider function for now
 
+/// Hover tooltip shared by every param control: full parameter name, the
+/// live value with units, and the default. The value line is bound to the
+/// param, so an open tooltip tracks automation and drags.
+fn build_param_tooltip<P, L, F>(cx: &mut Context, lens: L, param_map: F) -> Handle<'_, Tooltip>
+where
+    P: Param + 'static,
+    L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
+    F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &P,
+{
+    let params = lens.get(cx);
+    let param = param_map(&params);
+    let name = param.name().to_owned();
+    let default = param.normalized_value_to_string(param.default_normalized_value(), true);
+    Tooltip::new(cx, move |cx| {
+        Label::new(cx, name.as_str()).class("param-tooltip-name");
+        Label::new(
+            cx,
+            lens.clone().map(move |p| {
+                let param = param_map(p);
+                param.normalized_value_to_string(param.unmodulated_normalized_value(), true)
+            }),
+        )
+        .class("param-tooltip-value");
+        let default = default.clone();
+        Label::new(
+            cx,
+            crate::editor::Data::language
+                .map(move |l| i18n::translate(*l, "Default {}").replace("{}", &default)),
+        )
+        .class("param-tooltip-default");
+    })
+    .class("param-tooltip")
+}
+
+// ── Param context menu ────────────────────────────────────────────────────────
+//
+// Right-click menu shared by every param control: reset, typed entry,
+// copy/paste of the plain value, and a held automation "touch". Controls opt
+// in with `.param_menu(ptr)`; the request bubbles up to the editor model,
+// which owns one `ParamMenuState` and forwards `ParamMenuEvent`s to it. The
+// overlay itself is built once at the chassis root by `build_param_menu`.
+//
+// Double-clicking a control opens the same overlay straight in typed entry.
+// That box is the editor's only numeric entry: it parses with the
+// parameter's own string_to_value, so "2.5k", "-6 dB" and "3:1" work where
+// the parameter's formatter writes them (see `parse_entry`).
+//
+// Every write goes through the gesture helpers below so hosts see a normal
+// begin/set/end automation gesture and the undo history records one step.
+
+/// Begin a host automation gesture on `ptr`.
+pub fn begin_gesture(cx: &mut EventContext, ptr: ParamPtr) {
+    cx.emit(RawParamEvent::BeginSetParameter(ptr));
+}
+
+/// End a host automation gesture on `ptr`.
+pub fn end_gesture(cx: &mut EventContext, ptr: ParamPtr) {
+    cx.emit(RawParamEvent::EndSetParameter(ptr));
+}
+
+/// One-shot begin/set/end write — resets, text entry, pastes and steps,
+/// where there is no surrounding drag gesture.
+pub fn write_param(cx: &mut EventContext, ptr: ParamPtr, normalized: f32) {
+    begin_gesture(cx, ptr);
+    cx.emit(RawParamEvent::SetParameterNormalized(
+        ptr,
+        normalized.clamp(0.0, 1.0),
+    ));
+    end_gesture(cx, ptr);
+}
+
+#[derive(Clone)]
+pub enum ParamMenuEvent {
+    /// Open the menu for `ptr` at window position (`x`, `y`).
+    Open {
+        ptr: ParamPtr,
+        x: f32,
+        y: f32,
+    },
+    /// Open straight into typed entry (double-click). `restore` is the
+    /// value before the click, put back if the entry is cancelled:
+    /// vizia-plug's ParamSlider resets on double-click, and that reset is
+    /// already queued by the time the box opens.
+    OpenEntry {
+        ptr: ParamPtr,
+        x: f32,
+        y: f32,
+        restore: f32,
+    },
+    Close,
+    Reset,
+    BeginEntry,
+    SubmitEntry(String),
+    Copy,
+    Paste,
+    /// Begin a gesture and hold it open (host touch/latch writes), or
+    /// release the one already held on this param.
+    ToggleTouch,
+}
+
+/// Menu state owned by the editor model. `generation` bumps on every change
+/// so the overlay can rebuild through a single Binding.
+#[derive(Clone, Default)]
+pub struct ParamMenuState {
+    target: Option<ParamPtr>,
+    x: f32,
+    y: f32,
+    entry_active: bool,
+    entry_text: String,
+    /// Value a cancelled double-click entry restores.
+    restore: Option<f32>,
+    /// Plain value from "Copy value"; pasting clamps it to the target's range.
+    clipboard: Option<f32>,
+    /// Param whose gesture is held open by "Hold touch".
+    touching: Option<ParamPtr>,
+    generation: u32,
+}
+
+impl ParamMenuState {
+    pub fn is_open(&self) -> bool {
+        self.target.is_some()
+    }
+
+    pub fn event(&mut self, cx: &mut EventContext, event: &ParamMenuEvent) {
+        // SAFETY (all unsafe ParamPtr calls below): every ParamPtr in the
+        // editor comes from its Arc'd params, which outlive every view.
+        match event {
+            ParamMenuEvent::Open { ptr, x, y } => {
+                self.target = Some(*ptr);
+                self.x = *x;
+                self.y = *y;
+                self.entry_active = false;
+                self.restore = None;
+            }
+            ParamMenuEvent::OpenEntry { ptr, x, y, restore } => {
+                self.target = Some(*ptr);
+                self.x = *x;
+                self.y = *y;
+                self.entry_text = unsafe { ptr.normalized_value_to_string(*restore, false) };
+                self.entry_active = true;
+                self.restore = Some(*restore);
+            }
+            ParamMenuEvent::Close => {
+                if let Some(ptr) = self.target.take() {
+                    self.undo_double_click(cx, ptr);
+                }
+            }
+            ParamMenuEvent::Reset => {
+                if let Some(ptr) = self.target.take() {
+                    write_param(cx, ptr, unsafe { ptr.default_normalized_value() });
+                }
+            }
+            ParamMenuEvent::BeginEntry => {
+                if let Some(ptr) = self.target {
+                    self.entry_text = unsafe {
+                        ptr.normalized_value_to_string(ptr.unmodulated_normalized_value(), false)
+                    };
+                    self.entry_active = true;
+                }
+            }
+            ParamMenuEvent::SubmitEntry(text) => {
+                if let Some(ptr) = self.target.take() {
+                    match parse_entry(ptr, text) {
+                        Some(norm) => write_param(cx, ptr, norm),
+                        // Unparseable text leaves the value as it was.
+                        None => self.undo_double_click(cx, ptr),
+                    }
+                }
+                self.restore = None;
+            }
+            ParamMenuEvent::Copy => {
+                if let Some(ptr) = self.target.take() {
+                    self.clipboard = Some(unsafe { ptr.unmodulated_plain_value() });
+                }
+            }
+            ParamMenuEvent::Paste => {
+                if let (Some(ptr), Some(plain)) = (self.target.take(), self.clipboard) {
+                    write_param(cx, ptr, unsafe { ptr.preview_normalized(plain) });
+                }
+            }
+            ParamMenuEvent::ToggleTouch => {
+                if let Some(ptr) = self.target.take() {
+                    let held = self.touching;
+                    self.release_touch(cx);
+                    if held != Some(ptr) {
+                        begin_gesture(cx, ptr);
+                        self.touching = Some(ptr);
+                    }
+                }
+            }
+        }
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// Put back the value from before the double-click that opened entry,
+    /// if a slider's double-click reset has moved it since.
+    fn undo_double_click(&mut self, cx: &mut EventContext, ptr: ParamPtr) {
+        if let Some(restore) = self.restore.take() {
+            // SAFETY: see `event`.
+            if unsafe { ptr.unmodulated_normalized_value() } != restore {
+                write_param(cx, ptr, restore);
+            }
+        }
+    }
+
+    /// End the held touch gesture, if any. A held touch outlives the menu
+    /// closing; only "Release touch" or holding another param ends it.
+    fn release_touch(&mut self, cx: &mut EventContext) {
+        if let Some(held) = self.touching.take() {
+            end_gesture(cx, held);
+        }
+    }
+}
+
+/// Normalized value for typed entry text. The parameter's own
+/// string_to_value gets the first go; failing that, the leading number (with
+/// a `k` multiplier, units and a ratio's `:1` dropped) goes back through it,
+/// so "-6dB" reaches a " dB" parameter and "3:1" a bare ratio.
+pub fn parse_entry(ptr: ParamPtr, text: &str) -> Option<f32> {
+    // SAFETY: see `ParamMenuState::event`.
+    unsafe { ptr.string_to_normalized_value(text) }.or_else(|| {
+        let number = entry_number(text)?;
+        unsafe { ptr.string_to_normalized_value(&number.to_string()) }
+    })
+}
+
+/// The leading number of `text`, times 1000 when a `k` follows it.
+fn entry_number(text: &str) -> Option<f32> {
+    let text = text.trim();
+    let end = text
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || c == '.' || (i == 0 && (c == '-' || c == '+'))))
+        .map_or(text.len(), |(i, _)| i);
+    let value: f32 = text[..end].parse().ok()?;
+    let unit = text[end..].trim_start();
+    Some(if unit.starts_with(['k', 'K']) {
+        value * 1000.0
+    } else {
+        value
+    })
+}
+
+/// `ParamPtr` for an editor param, for controls built outside the helpers
+/// here.
+pub fn param_ptr<P, F>(cx: &mut Context, param_map: F) -> ParamPtr
+where
+    P: Param + 'static,
+    F: Fn(&Arc<BusChannelStripParams>) -> &P,
+{
+    param_map(&crate::editor::Data::params.get(cx)).as_ptr()
+}
+
+/// Open typed entry on `ptr` at the cursor.
+fn open_entry(cx: &mut EventContext, ptr: ParamPtr) {
+    let (x, y) = (cx.mouse().cursor_x, cx.mouse().cursor_y);
+    // SAFETY: see `ParamMenuState::event`.
+    let restore = unsafe { ptr.unmodulated_normalized_value() };
+    cx.emit(ParamMenuEvent::OpenEntry { ptr, x, y, restore });
+}
+
+/// Opt a control in to the param context menu (`param_menu`) and, for
+/// continuous controls, double-click entry (`param_entry`). Buttons skip the
+/// latter: a double-click there is two toggles.
+pub trait ParamMenuExt {
+    fn param_menu(self, ptr: ParamPtr) -> Self;
+    fn param_entry(self, ptr: ParamPtr) -> Self;
+}
+
+impl<V: View> ParamMenuExt for Handle<'_, V> {
+    fn param_menu(self, ptr: ParamPtr) -> Self {
+        self.on_mouse_down(move |cx, button| {
+            if button == MouseButton::Right {
+                let (x, y) = (cx.mouse().cursor_x, cx.mouse().cursor_y);
+                cx.emit(ParamMenuEvent::Open { ptr, x, y });
+            }
+        })
+    }
+
+    fn param_entry(self, ptr: ParamPtr) -> Self {
+        self.on_double_click(move |cx, button| {
+            if button == MouseButton::Left {
+                open_entry(cx, ptr);
+            }
+        })
+    }
+}
+
+// ── Keyboard and screen-reader access ───────────────────────────────────────
+//
+// Every param control opts in with `.param_access(ptr)`: it becomes a Tab
+// stop (Tab / shift+Tab walk the controls in layout order, slot by slot)
+// and reports its role, the parameter's name and its current value to
+// assistive technology. Focusing one makes it the keyboard target:
+//   • arrows            — nudge, shift for fine steps (one notch for
+//                         switches and choices)
+//   • Page Up / Down    — ten nudges
+//   • Home / End        — minimum / maximum
+//   • Delete, Backspace — reset to the default
+//   • Enter             — type a value in the shared entry box; on a
+//                         switch, Enter and Space toggle it instead
+// The editor model receives the keys (a focused control doesn't consume
+// them) and hands them to `keyboard_adjust`; writes are ordinary gestures,
+// so hosts record them and undo covers them.
+
+/// Keyboard focus entering or leaving a param control.
+#[derive(Clone, Copy)]
+pub enum ParamFocusEvent {
+    /// (`x`, `y`) is where the control's typed-entry box opens.
+    Focused {
+        ptr: ParamPtr,
+        x: f32,
+        y: f32,
+    },
+    Blurred(ParamPtr),
+}
+
+/// What a key does to a focused control.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum KeyAction {
+    /// Write this normalized value (clamped on the way out).
+    Set(f32),
+    /// Open typed entry.
+    Entry,
+}
+
+/// The action bound to `code` on a control at `current` (normalized), with
+/// `step` per nudge. `switch` marks two-state params. None when unbound.
+fn key_action(
+    code: Code,
+    current: f32,
+    default: f32,
+    step: f32,
+    switch: bool,
+) -> Option<KeyAction> {
+    Some(match code {
+        Code::ArrowUp | Code::ArrowRight => KeyAction::Set(current + step),
+        Code::ArrowDown | Code::ArrowLeft => KeyAction::Set(current - step),
+        Code::PageUp => KeyAction::Set(current + 10.0 * step),
+        Code::PageDown => KeyAction::Set(current - 10.0 * step),
+        Code::Home => KeyAction::Set(0.0),
+        Code::End => KeyAction::Set(1.0),
+        Code::Delete | Code::Backspace => KeyAction::Set(default),
+        Code::Enter | Code::NumpadEnter | Code::Space if switch => {
+            KeyAction::Set(1.0 - current.round())
+        }
+        Code::Enter | Code::NumpadEnter => KeyAction::Entry,
+        _ => return None,
+    })
+}
+
+/// Apply the adjustment bound to `code` to the focused control `ptr`, whose
+/// entry box opens at (`x`, `y`). False for keys that aren't bound.
+pub fn keyboard_adjust(cx: &mut EventContext, ptr: ParamPtr, code: Code, x: f32, y: f32) -> bool {
+    // SAFETY: see `nudge_step`.
+    let (current, default, switch) = unsafe {
+        (
+            ptr.unmodulated_normalized_value(),
+            ptr.default_normalized_value(),
+            ptr.step_count() == Some(1),
+        )
+    };
+    let step = nudge_step(ptr, cx.modifiers().shift());
+    match key_action(code, current, default, step, switch) {
+        Some(KeyAction::Set(target)) => write_param(cx, ptr, target),
+        Some(KeyAction::Entry) => cx.emit(ParamMenuEvent::OpenEntry {
+            ptr,
+            x,
+            y,
+            restore: current,
+        }),
+        None => return false,
+    }
+    true
+}
+
+pub trait ParamAccessExt {
+    fn param_access(self, ptr: ParamPtr) -> Self;
+}
+
+impl<V: View> ParamAccessExt for Handle<'_, V> {
+    fn param_access(self, ptr: ParamPtr) -> Self {
+        // SAFETY: see `nudge_step`.
+        let (name, switch) = unsafe { (ptr.name().to_owned(), ptr.step_count() == Some(1)) };
+        // Same text the value readouts show, units included.
+        let value = crate::editor::Data::params.map(move |_| unsafe {
+            ptr.normalized_value_to_string(ptr.unmodulated_normalized_value(), true)
+        });
+        let handle = self
+            .class("param-access")
+            .navigable(true)
+            .name(name)
+            .text_value(value)
+            .on_focus_in(move |cx| {
+                let bounds = cx.bounds();
+                cx.emit(ParamFocusEvent::Focused {
+                    ptr,
+                    x: bounds.x,
+                    y: bounds.y + bounds.h,
+                });
+            })
+            .on_focus_out(move |cx| cx.emit(ParamFocusEvent::Blurred(ptr)));
+        if switch {
+            handle.role(Role::CheckBox).checked(
+                crate::editor::Data::params
+                    .map(move |_| unsafe { ptr.unmodulated_normalized_value() } > 0.5),
+            )
+        } else {
+            handle.role(Role::Slider)
+        }
+    }
+}
+
+fn build_param_menu_item(cx: &mut Context, label: &str, event: ParamMenuEvent, enabled: bool) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr(label)).class("param-menu-item-label");
+    })
+    .class("param-menu-item")
+    .toggle_class("param-menu-item-disabled", !enabled)
+    .on_press(move |cx| {
+        if enabled {
+            cx.emit(event.clone());
+        }
+    })
+    .cursor(CursorIcon::Hand)
+    .height(Pixels(22.0))
+    .width(Stretch(1.0));
+}
+
+/// Context-menu overlay. Build once, after the rest of the editor, so it
+/// draws on top; clicking outside the menu dismisses it.
+pub fn build_param_menu<L>(cx: &mut Context, lens: L)
+where
+    L: Lens<Target = ParamMenuState> + Clone + 'static,
+{
+    let generation = lens.clone().map(|m| m.generation);
+    Binding::new(cx, generation, move |cx, _| {
+        let menu = lens.get(cx);
+        let Some(ptr) = menu.target else {
+            return;
+        };
+        // SAFETY: see `ParamMenuState::event`.
+        let (name, holding) = (unsafe { ptr.name() }.to_owned(), menu.touching == Some(ptr));
+
+        Element::new(cx)
+            .class("param-menu-backdrop")
+            .position_type(PositionType::Absolute)
+            .left(Pixels(0.0))
+            .top(Pixels(0.0))
+            .width(Stretch(1.0))
+            .height(Stretch(1.0))
+            .on_press(|cx| cx.emit(ParamMenuEvent::Close));
+
+        VStack::new(cx, |cx| {
+            Label::new(cx, name.as_str()).class("param-menu-title");
+            if menu.entry_active {
+                Textbox::new(cx, lens.clone().map(|m| m.entry_text.clone()))
+                    .class("param-menu-entry")
+                    .on_submit(|cx, text, success| {
+                        cx.emit(if success {
+                            ParamMenuEvent::SubmitEntry(text)
+                        } else {
+                            ParamMenuEvent::Close
+                        });
+                    })
+                    .on_build(|cx| {
+                        cx.emit(TextEvent::StartEdit);
+                        cx.emit(TextEvent::SelectAll);
+                    })
+                    .height(Pixels(22.0))
+                    .width(Stretch(1.0));
+                return;
+            }
+            build_param_menu_item(cx, "Reset to default", ParamMenuEvent::Reset, true);
+            build_param_menu_item(cx, "Enter value…", ParamMenuEvent::BeginEntry, true);
+            build_param_menu_item(cx, "Copy value", ParamMenuEvent::Copy, true);
+            build_param_menu_item(
+                cx,
+                "Paste value",
+                ParamMenuEvent::Paste,
+                menu.clipboard.is_some(),
+            );
+            Element::new(cx).class("param-menu-separator");
+            build_param_menu_item(
+                cx,
+                if holding {
+                    "Release touch"
+                } else {
+                    "Hold touch (automation)"
+                },
+                ParamMenuEvent::ToggleTouch,
+                true,
+            );
+        })
+        .class("param-menu")
+        .position_type(PositionType::Absolute)
+        .left(Pixels(menu.x))
+        .top(Pixels(menu.y))
+        .width(Pixels(180.0))
+        .height(Auto);
+    });
+}
+
 // Reusable bypass button component
 pub fn create_bypass_button<F>(cx: &mut Context, _label: &str, param_map: F)
 where
     F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &BoolParam,
 {
     // Create the button with proper lens binding
+    let ptr = param_map(&crate::editor::Data::params.get(cx)).as_ptr();
     ParamButton::new(cx, crate::editor::Data::params, param_map)
         .class("bypass-button")
+        .tooltip(move |cx| build_param_tooltip(cx, crate::editor::Data::params, param_map))
+        .param_menu(ptr)
+        .param_access(ptr)
         .height(Pixels(28.0))
         .width(Stretch(1.0))
         .top(Pixels(0.0))
@@ -131,9 +732,13 @@ pub fn create_active_led_button<F>(cx: &mut Context, param_map: F)
 where
     F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &BoolParam,
 {
+    let ptr = param_map(&crate::editor::Data::params.get(cx)).as_ptr();
     ParamButton::new(cx, crate::editor::Data::params, param_map)
         .with_label("ACTIVE")
         .class("active-led-button")
+        .tooltip(move |cx| build_param_tooltip(cx, crate::editor::Data::params, param_map))
+        .param_menu(ptr)
+        .param_access(ptr)
         .height(Pixels(28.0))
         .width(Stretch(1.0))
         .top(Pixels(0.0))
@@ -148,8 +753,12 @@ pub fn create_on_button<F>(cx: &mut Context, param_map: F)
 where
     F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &BoolParam,
 {
+    let ptr = param_map(&crate::editor::Data::params.get(cx)).as_ptr();
     ParamButton::new(cx, crate::editor::Data::params, param_map)
         .class("on-button")
+        .tooltip(move |cx| build_param_tooltip(cx, crate::editor::Data::params, param_map))
+        .param_menu(ptr)
+        .param_access(ptr)
         .height(Pixels(28.0))
         .width(Stretch(1.0))
         .top(Pixels(0.0))
@@ -164,8 +773,10 @@ where
     L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
     F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &BoolParam,
 {
+    let tooltip_lens = lens.clone();
+    let ptr = param_map(&lens.get(cx)).as_ptr();
     VStack::new(cx, |cx| {
-        Label::new(cx, label)
+        Label::new(cx, tr(label))
             .class("param-label")
             .height(Pixels(PARAM_LABEL_H))
             .width(Stretch(1.0));
@@ -175,6 +786,9 @@ where
             .width(Stretch(1.0));
     })
     .class("param-control")
+    .tooltip(move |cx| build_param_tooltip(cx, tooltip_lens.clone(), param_map))
+    .param_menu(ptr)
+    .param_access(ptr)
     .width(Stretch(1.0))
     .height(Auto)
     .top(Pixels(0.0))
@@ -183,74 +797,1398 @@ where
 
 // Specialized components for common parameter types
 
-pub fn create_frequency_slider<L, F>(cx: &mut Context, label: &str, lens: L, param_map: F)
+pub fn create_gain_slider<L, F>(cx: &mut Context, label: &str, lens: L, param_map: F)
 where
     L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
     F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &FloatParam,
 {
+    let tooltip_lens = lens.clone();
+    let ptr = param_map(&lens.get(cx)).as_ptr();
     VStack::new(cx, |cx| {
-        Label::new(cx, label)
+        Label::new(cx, tr(label))
             .class("param-label")
             .height(Pixels(PARAM_LABEL_H))
             .width(Stretch(1.0));
 
         ParamSlider::new(cx, lens, param_map)
+            .param_entry(ptr)
             .height(Pixels(20.0))
             .width(Stretch(1.0))
-            .class("frequency-slider");
+            .class("gain-slider");
     })
     .class("param-control")
-    .class("frequency-control")
+    .class("gain-control")
+    .tooltip(move |cx| build_param_tooltip(cx, tooltip_lens.clone(), param_map))
+    .param_menu(ptr)
+    .param_access(ptr)
     .width(Stretch(1.0))
     .height(Auto)
     .top(Pixels(0.0))
     .bottom(Pixels(0.0));
 }
 
-pub fn create_gain_slider<L, F>(cx: &mut Context, label: &str, lens: L, param_map: F)
-where
+// ── Rotary knob ───────────────────────────────────────────────────────────────
+//
+// 500-series style rotary control used on every module faceplate. Drawn with
+// Skia (same path as the DynEQ SpectrumCanvas) so the arc, modulation ring and
+// pointer stay crisp at every zoom level. Interaction:
+//   • vertical drag          — coarse adjust (KNOB_DRAG_PX px = full travel)
+//   • shift + drag           — fine adjust; re-anchors when shift toggles so
+//                              the value never jumps mid-gesture
+//   • alt + drag             — on linked knobs, move the partner param by
+//                              the same amount, keeping their offset
+//   • ctrl/cmd-click         — reset to the parameter default (same as
+//                              vizia-plug's ParamSlider)
+//   • double-click, or click — type a value in the shared entry box (see
+//     the value text           `parse_entry`)
+//   • scroll wheel           — step, shift for fine steps
+//   • arrow keys (focused)   — nudge, shift for fine steps
+// Writes go through RawParamEvent begin/set/end like the editor's batch
+// writes, so hosts record a single automation gesture per drag.
+
+/// Knob face diameter (logical px). The value readout sits below it.
+const KNOB_SIZE: f32 = 40.0;
+/// Height of the value readout / text-entry row under the knob face.
+const KNOB_VALUE_H: f32 = 14.0;
+/// Vertical drag distance (logical px) that sweeps the full normalized range.
+const KNOB_DRAG_PX: f32 = 200.0;
+/// Shift-drag sensitivity relative to a normal drag.
+const KNOB_FINE_FACTOR: f32 = 0.1;
+/// Normalized step per scroll notch (coarse / fine).
+const KNOB_SCROLL_STEP: f32 = 0.02;
+const KNOB_SCROLL_STEP_FINE: f32 = 0.002;
+/// Normalized step per arrow-key nudge of a continuous param (coarse / fine).
+const KNOB_KEY_STEP: f32 = 0.01;
+const KNOB_KEY_STEP_FINE: f32 = 0.001;
+/// Arc geometry in Skia's convention (0° = 3 o'clock, clockwise): the travel
+/// runs from 7:30 through 12:00 to 4:30, like a stepped hardware pot.
+const KNOB_START_DEG: f32 = 135.0;
+const KNOB_SWEEP_DEG: f32 = 270.0;
+
+/// Arc color family for a knob. Mirrors the label tints used by the
+/// `.frequency-control` / `.gain-control` / `.ratio-control` classes.
+#[derive(Clone, Copy)]
+pub enum KnobAccent {
+    Neutral,
+    Frequency,
+    Gain,
+    Ratio,
+}
+
+impl KnobAccent {
+    fn rgb(self, skin: EditorSkin) -> (u8, u8, u8) {
+        match (skin, self) {
+            (EditorSkin::Light, Self::Neutral) => (58, 62, 70), // #3a3e46 (ink)
+            (EditorSkin::Light, Self::Frequency) => (24, 110, 160),
+            (EditorSkin::Light, Self::Gain) => (208, 96, 16),
+            (EditorSkin::Light, Self::Ratio) => (40, 140, 56),
+            (EditorSkin::Classic, Self::Neutral) => (242, 234, 210), // #f2ead2
+            (_, Self::Neutral) => (216, 210, 192), // #d8d2c0 (cream pointer paint)
+            (_, Self::Frequency) => (64, 160, 208), // #40a0d0
+            (_, Self::Gain) => (255, 150, 64),     // #ff9640
+            (_, Self::Ratio) => (102, 204, 102),   // #66cc66
+        }
+    }
+
+    fn control_class(self) -> &'static str {
+        match self {
+            Self::Neutral => "knob-control",
+            Self::Frequency => "frequency-control",
+            Self::Gain => "gain-control",
+            Self::Ratio => "ratio-control",
+        }
+    }
+}
+
+/// Snapshot of the values a knob face draws. Rebuilt through a Binding when
+/// either the host value or the modulation offset changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct KnobValues {
+    value: f32,
+    modulated: f32,
+}
+
+impl Data for KnobValues {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// Anchor for an in-flight drag. `fine` records whether shift was held when
+/// the anchor was taken so a modifier change can re-anchor cleanly.
+/// `origin_value` and `link_start` survive re-anchoring: the linked partner
+/// always follows the total movement since the press.
+#[derive(Clone, Copy)]
+struct KnobDrag {
+    start_y: f32,
+    start_value: f32,
+    fine: bool,
+    origin_value: f32,
+    link_start: Option<f32>,
+}
+
+pub struct ParamKnob {
+    param_ptr: ParamPtr,
+    /// Partner param moved along with this one on alt-drag.
+    link: Option<ParamPtr>,
+    drag: Option<KnobDrag>,
+}
+
+/// Normalized step for one arrow-key nudge of `ptr`: one notch for stepped
+/// params (enums, integers), otherwise 1% of travel — 0.1% with shift.
+pub fn nudge_step(ptr: ParamPtr, fine: bool) -> f32 {
+    // SAFETY: every ParamPtr in the editor comes from its Arc'd params,
+    // which outlive every view.
+    match unsafe { ptr.step_count() } {
+        Some(steps) if steps > 0 => 1.0 / steps as f32,
+        _ if fine => KNOB_KEY_STEP_FINE,
+        _ => KNOB_KEY_STEP,
+    }
+}
+
+impl ParamKnob {
+    pub fn new<P, L, F>(
+        cx: &mut Context,
+        lens: L,
+        param_map: F,
+        accent: KnobAccent,
+        link: Option<ParamPtr>,
+    ) -> Handle<'_, Self>
+    where
+        P: Param + 'static,
+        L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
+        F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &P,
+    {
+        let params = lens.get(cx);
+        let param = param_map(&params);
+        let param_ptr = param.as_ptr();
+        // The editor rebuilds module slots when the skin changes, so reading
+        // the persisted skin once at build time is enough.
+        let rgb = accent.rgb(params.editor_skin.read().map(|s| *s).unwrap_or_default());
+        // Bipolar params (gain, tilt, response) centre their default; draw
+        // their value arc outward from 12 o'clock instead of from zero.
+        let origin = if (param.default_normalized_value() - 0.5).abs() < 0.01 {
+            0.5
+        } else {
+            0.0
+        };
+
+        Self {
+            param_ptr,
+            link,
+            drag: None,
+        }
+        .build(cx, move |cx| {
+            let values = lens.clone().map(move |p| {
+                let param = param_map(p);
+                KnobValues {
+                    value: param.unmodulated_normalized_value(),
+                    modulated: param.modulated_normalized_value(),
+                }
+            });
+            Binding::new(cx, values, move |cx, v| {
+                KnobFace::new(cx, v.get(cx), origin, rgb)
+                    .height(Pixels(KNOB_SIZE))
+                    .width(Stretch(1.0));
+            });
+
+            let display = lens.clone().map(move |p| {
+                let param = param_map(p);
+                param.normalized_value_to_string(param.unmodulated_normalized_value(), true)
+            });
+            Label::new(cx, display)
+                .class("param-knob-value")
+                .hoverable(false)
+                .height(Pixels(KNOB_VALUE_H))
+                .width(Stretch(1.0));
+        })
+    }
+
+    fn current_normalized(&self) -> f32 {
+        // SAFETY: the ParamPtr comes from the editor's Arc'd params, which
+        // outlive every view in the editor.
+        unsafe { self.param_ptr.unmodulated_normalized_value() }
+    }
+
+    fn set_gesture(&self, cx: &mut EventContext, normalized: f32) {
+        write_param(cx, self.param_ptr, normalized);
+    }
+}
+
+impl View for ParamKnob {
+    fn element(&self) -> Option<&'static str> {
+        Some("param-knob")
+    }
+
+    fn event(&mut self, cx: &mut EventContext, event: &mut Event) {
+        event.map(|window_event: &WindowEvent, meta| match window_event {
+            WindowEvent::MouseDown(MouseButton::Left)
+            | WindowEvent::MouseTripleClick(MouseButton::Left) => {
+                // Ctrl/Cmd-click resets, matching vizia-plug's ParamSlider.
+                if cx.modifiers().command() {
+                    // SAFETY: see `current_normalized`.
+                    let default = unsafe { self.param_ptr.default_normalized_value() };
+                    self.set_gesture(cx, default);
+                    meta.consume();
+                    return;
+                }
+                // A press on the value readout row opens text entry instead
+                // of starting a drag. The readout is always the bottom
+                // KNOB_VALUE_H of this view, so a bounds test is enough.
+                let bounds = cx.bounds();
+                let readout_top = bounds.y + bounds.h - KNOB_VALUE_H * cx.scale_factor();
+                if cx.mouse().cursor_y >= readout_top {
+                    open_entry(cx, self.param_ptr);
+                    meta.consume();
+                    return;
+                }
+                cx.capture();
+                cx.focus();
+                cx.set_active(true);
+                let start_value = self.current_normalized();
+                // Alt-drag on a linked knob moves both params; the pair is
+                // one gesture for the editor's undo history.
+                let link_start = match self.link {
+                    // SAFETY: see `current_normalized`.
+                    Some(link) if cx.modifiers().alt() => {
+                        Some(unsafe { link.unmodulated_normalized_value() })
+                    }
+                    _ => None,
+                };
+                self.drag = Some(KnobDrag {
+                    start_y: cx.mouse().cursor_y,
+                    start_value,
+                    fine: cx.modifiers().shift(),
+                    origin_value: start_value,
+                    link_start,
+                });
+                if let (Some(link), Some(_)) = (self.link, link_start) {
+                    cx.emit(HistoryEvent::GroupBegin);
+                    cx.emit(RawParamEvent::BeginSetParameter(link));
+                }
+                cx.emit(RawParamEvent::BeginSetParameter(self.param_ptr));
+                meta.consume();
+            }
+            WindowEvent::MouseDoubleClick(MouseButton::Left) => {
+                open_entry(cx, self.param_ptr);
+                meta.consume();
+            }
+            WindowEvent::MouseMove(_x, y) => {
+                let fine = cx.modifiers().shift();
+                let current = self.current_normalized();
+                if let Some(drag) = self.drag.as_mut() {
+                    if fine != drag.fine {
+                        *drag = KnobDrag {
+                            start_y: *y,
+                            start_value: current,
+                            fine,
+                            ..*drag
+                        };
+                    }
+                    let travel_px = if fine {
+                        KNOB_DRAG_PX / KNOB_FINE_FACTOR
+                    } else {
+                        KNOB_DRAG_PX
+                    } * cx.scale_factor();
+                    // Up = increase, matching hardware pots and every DAW.
+                    let norm = (drag.start_value + (drag.start_y - *y) / travel_px).clamp(0.0, 1.0);
+                    cx.emit(RawParamEvent::SetParameterNormalized(self.param_ptr, norm));
+                    if let (Some(link), Some(link_start)) = (self.link, drag.link_start) {
+                        let partner = (link_start + norm - drag.origin_value).clamp(0.0, 1.0);
+                        cx.emit(RawParamEvent::SetParameterNormalized(link, partner));
+                    }
+                    meta.consume();
+                }
+            }
+            WindowEvent::MouseUp(MouseButton::Left) => {
+                if let Some(drag) = self.drag.take() {
+                    cx.release();
+                    cx.set_active(false);
+                    cx.emit(RawParamEvent::EndSetParameter(self.param_ptr));
+                    if let (Some(link), Some(_)) = (self.link, drag.link_start) {
+                        cx.emit(RawParamEvent::EndSetParameter(link));
+                        cx.emit(HistoryEvent::GroupEnd);
+                    }
+                    meta.consume();
+                }
+            }
+            WindowEvent::MouseScroll(_x, y) => {
+                if *y != 0.0 && self.drag.is_none() {
+                    let step = if cx.modifiers().shift() {
+                        KNOB_SCROLL_STEP_FINE
+                    } else {
+                        KNOB_SCROLL_STEP
+                    };
+                    self.set_gesture(cx, self.current_normalized() + y.signum() * step);
+                    meta.consume();
+                }
+            }
+            WindowEvent::KeyDown(code, _) if self.drag.is_none() => {
+                let direction = match code {
+                    Code::ArrowUp | Code::ArrowRight => 1.0,
+                    Code::ArrowDown | Code::ArrowLeft => -1.0,
+                    _ => return,
+                };
+                let step = nudge_step(self.param_ptr, cx.modifiers().shift());
+                self.set_gesture(cx, self.current_normalized() + direction * step);
+                meta.consume();
+            }
+            _ => {}
+        });
+    }
+}
+
+/// Pure drawing child of `ParamKnob`: track, value arc, modulation ring,
+/// body and pointer. Holds no interaction state — it is rebuilt by the
+/// parent's Binding whenever the value changes, so mouse capture stays on
+/// the (stable) parent.
+struct KnobFace {
+    values: KnobValues,
+    origin: f32,
+    arc_rgb: (u8, u8, u8),
+}
+
+impl KnobFace {
+    fn new(
+        cx: &mut Context,
+        values: KnobValues,
+        origin: f32,
+        arc_rgb: (u8, u8, u8),
+    ) -> Handle<'_, Self> {
+        Self {
+            values,
+            origin,
+            arc_rgb,
+        }
+        .build(cx, |_cx| {})
+        .hoverable(false)
+    }
+}
+
+impl View for KnobFace {
+    fn element(&self) -> Option<&'static str> {
+        Some("knob-face")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        use vizia_plug::vizia::vg;
+
+        let bounds = cx.bounds();
+        if bounds.w < 1.0 || bounds.h < 1.0 {
+            return;
+        }
+
+        let size = bounds.w.min(bounds.h);
+        let (center_x, center_y) = (bounds.x + bounds.w * 0.5, bounds.y + bounds.h * 0.5);
+        let ring_w = (size * 0.09).max(1.5);
+        let radius = size * 0.5 - ring_w * 1.5;
+        let oval = vg::Rect::from_xywh(
+            center_x - radius,
+            center_y - radius,
+            radius * 2.0,
+            radius * 2.0,
+        );
+        let (r, g, b) = self.arc_rgb;
+
+        let stroke = |argb: (u8, u8, u8, u8), width: f32| {
+            let mut paint = vg::Paint::default();
+            paint.set_color(vg::Color::from_argb(argb.0, argb.1, argb.2, argb.3));
+            paint.set_style(vg::PaintStyle::Stroke);
+            paint.set_stroke_width(width);
+            paint.set_stroke_cap(vg::PaintCap::Round);
+            paint.set_anti_alias(true);
+            paint
+        };
+
+        // ── Track: full 270° travel, dim ────────────────────────────────
+        canvas.draw_arc(
+            oval,
+            KNOB_START_DEG,
+            KNOB_SWEEP_DEG,
+            false,
+            &stroke((255, 52, 56, 64), ring_w),
+        );
+
+        // ── Value arc: origin → value (bipolar params grow from 12:00) ─
+        let value = self.values.value.clamp(0.0, 1.0);
+        let (lo, hi) = if value < self.origin {
+            (value, self.origin)
+        } else {
+            (self.origin, value)
+        };
+        if hi - lo > 1e-4 {
+            canvas.draw_arc(
+                oval,
+                KNOB_START_DEG + lo * KNOB_SWEEP_DEG,
+                (hi - lo) * KNOB_SWEEP_DEG,
+                false,
+                &stroke((255, r, g, b), ring_w),
+            );
+        }
+
+        // ── Modulation ring: thin outer arc spanning value → modulated ─
+        let modulated = self.values.modulated.clamp(0.0, 1.0);
+        if (modulated - value).abs() > 1e-4 {
+            let mod_r = radius + ring_w * 1.2;
+            let mod_oval =
+                vg::Rect::from_xywh(center_x - mod_r, center_y - mod_r, mod_r * 2.0, mod_r * 2.0);
+            let (m_lo, m_hi) = if modulated < value {
+                (modulated, value)
+            } else {
+                (value, modulated)
+            };
+            canvas.draw_arc(
+                mod_oval,
+                KNOB_START_DEG + m_lo * KNOB_SWEEP_DEG,
+                (m_hi - m_lo) * KNOB_SWEEP_DEG,
+                false,
+                &stroke((170, 235, 235, 235), (ring_w * 0.45).max(1.0)),
+            );
+        }
+
+        // ── Body: recessed cap inside the ring ──────────────────────────
+        let body_r = radius - ring_w * 1.1;
+        let mut body = vg::Paint::default();
+        body.set_color(vg::Color::from_argb(255, 30, 33, 39));
+        body.set_style(vg::PaintStyle::Fill);
+        body.set_anti_alias(true);
+        canvas.draw_circle((center_x, center_y), body_r, &body);
+        canvas.draw_circle(
+            (center_x, center_y),
+            body_r,
+            &stroke((255, 70, 76, 88), 1.0),
+        );
+
+        // ── Pointer: cream line from near the centre to the cap edge ────
+        let angle = (KNOB_START_DEG + value * KNOB_SWEEP_DEG).to_radians();
+        let (dx, dy) = (angle.cos(), angle.sin());
+        canvas.draw_line(
+            (center_x + dx * body_r * 0.3, center_y + dy * body_r * 0.3),
+            (center_x + dx * body_r * 0.9, center_y + dy * body_r * 0.9),
+            &stroke((255, 235, 230, 215), (ring_w * 0.7).max(1.0)),
+        );
+    }
+}
+
+/// Label + knob + value readout, sized to sit in a `module_row` alongside
+/// other controls. Private — callers pick one of the typed wrappers below.
+fn create_knob_control<P, L, F>(
+    cx: &mut Context,
+    label: &str,
+    lens: L,
+    param_map: F,
+    accent: KnobAccent,
+    link: Option<ParamPtr>,
+) where
+    P: Param + 'static,
     L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
-    F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &FloatParam,
+    F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &P,
 {
+    let tooltip_lens = lens.clone();
+    let ptr = param_map(&lens.get(cx)).as_ptr();
     VStack::new(cx, |cx| {
-        Label::new(cx, label)
+        Label::new(cx, tr(label))
             .class("param-label")
             .height(Pixels(PARAM_LABEL_H))
             .width(Stretch(1.0));
 
-        ParamSlider::new(cx, lens, param_map)
-            .height(Pixels(20.0))
-            .width(Stretch(1.0))
-            .class("gain-slider");
+        ParamKnob::new(cx, lens, param_map, accent, link)
+            .height(Pixels(KNOB_SIZE + KNOB_VALUE_H))
+            .width(Stretch(1.0));
     })
     .class("param-control")
-    .class("gain-control")
+    .class(accent.control_class())
+    .tooltip(move |cx| build_param_tooltip(cx, tooltip_lens.clone(), param_map))
+    .param_menu(ptr)
+    .param_access(ptr)
     .width(Stretch(1.0))
     .height(Auto)
     .top(Pixels(0.0))
     .bottom(Pixels(0.0));
 }
 
-pub fn create_ratio_slider<L, F>(cx: &mut Context, label: &str, lens: L, param_map: F)
+/// Generic rotary control (neutral arc) for continuous parameters.
+pub fn create_param_knob<P, L, F>(cx: &mut Context, label: &str, lens: L, param_map: F)
+where
+    P: Param + 'static,
+    L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
+    F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &P,
+{
+    create_knob_control(cx, label, lens, param_map, KnobAccent::Neutral, None);
+}
+
+pub fn create_frequency_knob<L, F>(cx: &mut Context, label: &str, lens: L, param_map: F)
 where
     L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
     F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &FloatParam,
 {
-    VStack::new(cx, |cx| {
-        Label::new(cx, label)
+    create_knob_control(cx, label, lens, param_map, KnobAccent::Frequency, None);
+}
+
+pub fn create_gain_knob<L, F>(cx: &mut Context, label: &str, lens: L, param_map: F)
+where
+    L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
+    F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &FloatParam,
+{
+    create_knob_control(cx, label, lens, param_map, KnobAccent::Gain, None);
+}
+
+/// Gain knob with an alt-drag partner: alt + drag moves `link_map`'s param by
+/// the same normalized amount, so a pair can be trimmed without losing its
+/// offset (mid/side today; per-channel L/R trims use the same helper).
+pub fn create_linked_gain_knob<L, F, G>(
+    cx: &mut Context,
+    label: &str,
+    lens: L,
+    param_map: F,
+    link_map: G,
+) where
+    L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
+    F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &FloatParam,
+    G: Fn(&Arc<BusChannelStripParams>) -> &FloatParam,
+{
+    let link = link_map(&lens.get(cx)).as_ptr();
+    create_knob_control(cx, label, lens, param_map, KnobAccent::Gain, Some(link));
+}
+
+pub fn create_ratio_knob<L, F>(cx: &mut Context, label: &str, lens: L, param_map: F)
+where
+    L: Lens<Target = Arc<BusChannelStripParams>> + Clone + 'static,
+    F: 'static + Clone + Copy + Fn(&Arc<BusChannelStripParams>) -> &FloatParam,
+{
+    create_knob_control(cx, label, lens, param_map, KnobAccent::Ratio, None);
+}
+
+// ── Drive meter ───────────────────────────────────────────────────────────────
+//
+// Horizontal LED ladder showing how hard a saturation stage is working. The
+// audio thread publishes an already-ballistic activity ratio; the meter maps
+// it to dB (harmonic level relative to the signal) and redraws every frame
+// while visible, like the DynEQ spectrum canvas.
+
+const DRIVE_METER_SEGMENTS: usize = 12;
+/// Activity (dB re. signal) at the first and last segment.
+const DRIVE_METER_FLOOR_DB: f32 = -48.0;
+const DRIVE_METER_CEIL_DB: f32 = -6.0;
+
+struct DriveMeter {
+    source: Arc<spectral::SaturationMeterData>,
+}
+
+impl View for DriveMeter {
+    fn element(&self) -> Option<&'static str> {
+        Some("drive-meter")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        use vizia_plug::vizia::vg;
+
+        let bounds = cx.bounds();
+        if bounds.w < 1.0 || bounds.h < 1.0 {
+            return;
+        }
+
+        let activity_db = util::gain_to_db(self.source.load());
+        let lit = ((activity_db - DRIVE_METER_FLOOR_DB)
+            / (DRIVE_METER_CEIL_DB - DRIVE_METER_FLOOR_DB)
+            * DRIVE_METER_SEGMENTS as f32)
+            .ceil()
+            .clamp(0.0, DRIVE_METER_SEGMENTS as f32) as usize;
+
+        let gap = 2.0 * cx.scale_factor();
+        let seg_w =
+            (bounds.w - gap * (DRIVE_METER_SEGMENTS - 1) as f32) / DRIVE_METER_SEGMENTS as f32;
+        for i in 0..DRIVE_METER_SEGMENTS {
+            // Green for gentle colour, amber once harmonics are obvious,
+            // red for the last two segments (audible grit).
+            let (r, g, b) = match i {
+                i if i + 2 >= DRIVE_METER_SEGMENTS => (240, 72, 64),
+                i if i * 2 >= DRIVE_METER_SEGMENTS => (240, 176, 64),
+                _ => (96, 208, 96),
+            };
+            let alpha = if i < lit { 255 } else { 40 };
+            let mut paint = vg::Paint::default();
+            paint.set_color(vg::Color::from_argb(alpha, r, g, b));
+            paint.set_style(vg::PaintStyle::Fill);
+            paint.set_anti_alias(true);
+            let x = bounds.x + i as f32 * (seg_w + gap);
+            canvas.draw_rect(vg::Rect::from_xywh(x, bounds.y, seg_w, bounds.h), &paint);
+        }
+
+        cx.needs_redraw();
+    }
+}
+
+/// Labelled drive meter fed by a saturation-activity tap.
+pub fn create_drive_meter(
+    cx: &mut Context,
+    label: &str,
+    source: Arc<spectral::SaturationMeterData>,
+) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr(label))
             .class("param-label")
-            .height(Pixels(PARAM_LABEL_H))
-            .width(Stretch(1.0));
+            .width(Pixels(44.0))
+            .height(Pixels(PARAM_LABEL_H));
+        DriveMeter { source }
+            .build(cx, |_| {})
+            .class("drive-meter")
+            .height(Pixels(8.0))
+            .width(Stretch(1.0))
+            .top(Stretch(1.0))
+            .bottom(Stretch(1.0));
+    })
+    .class("drive-meter-row")
+    .height(Auto)
+    .width(Stretch(1.0))
+    .gap(Pixels(6.0));
+}
 
-        ParamSlider::new(cx, lens, param_map)
-            .height(Pixels(20.0))
+/// Read-only measurement: a label and a value that follows `value`.
+pub fn create_readout(cx: &mut Context, label: &str, value: impl Lens<Target = String>) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr(label))
+            .class("param-label")
+            .width(Pixels(44.0))
+            .height(Pixels(PARAM_LABEL_H));
+        Label::new(cx, value)
+            .class("readout-value")
             .width(Stretch(1.0))
-            .class("ratio-slider");
+            .height(Pixels(PARAM_LABEL_H));
     })
-    .class("param-control")
-    .class("ratio-control")
+    .height(Auto)
     .width(Stretch(1.0))
+    .gap(Pixels(6.0));
+}
+
+// ── Status LED ────────────────────────────────────────────────────────────────
+//
+// A dot lit while an audio-thread flag is set. Polled every frame while
+// visible, like the drive meter.
+
+struct StatusLed {
+    source: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl View for StatusLed {
+    fn element(&self) -> Option<&'static str> {
+        Some("status-led")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        use vizia_plug::vizia::vg;
+
+        let bounds = cx.bounds();
+        if bounds.w < 1.0 || bounds.h < 1.0 {
+            return;
+        }
+        let lit = self.source.load(std::sync::atomic::Ordering::Relaxed);
+        let mut paint = vg::Paint::default();
+        paint.set_color(vg::Color::from_argb(
+            if lit { 255 } else { 40 },
+            240,
+            176,
+            64,
+        ));
+        paint.set_style(vg::PaintStyle::Fill);
+        paint.set_anti_alias(true);
+        let r = bounds.w.min(bounds.h) * 0.5;
+        canvas.draw_circle(
+            (bounds.x + bounds.w * 0.5, bounds.y + bounds.h * 0.5),
+            r,
+            &paint,
+        );
+
+        cx.needs_redraw();
+    }
+}
+
+/// Labelled status LED fed by an audio-thread flag.
+pub fn create_status_led(
+    cx: &mut Context,
+    label: &str,
+    source: Arc<std::sync::atomic::AtomicBool>,
+) {
+    HStack::new(cx, |cx| {
+        StatusLed { source }
+            .build(cx, |_| {})
+            .class("status-led")
+            .width(Pixels(8.0))
+            .height(Pixels(8.0))
+            .top(Stretch(1.0))
+            .bottom(Stretch(1.0));
+        Label::new(cx, tr(label))
+            .class("param-label")
+            .height(Pixels(PARAM_LABEL_H));
+    })
     .height(Auto)
-    .top(Pixels(0.0))
-    .bottom(Pixels(0.0));
+    .width(Stretch(1.0))
+    .gap(Pixels(6.0));
+}
+
+// ── Stereo scope ──────────────────────────────────────────────────────────────
+//
+// Goniometer (Lissajous trace rotated 45° so mono is vertical and out-of-phase
+// content is horizontal) with a correlation bar underneath. Reads the master
+// tap every frame while visible.
+
+/// Goniometer trace gain: full-scale L/R lands this far out from the centre.
+const SCOPE_TRACE_GAIN: f32 = 0.9;
+/// Height of the correlation bar under the goniometer (logical px).
+const SCOPE_CORRELATION_H: f32 = 6.0;
+
+struct StereoScope {
+    source: Arc<spectral::StereoScopeData>,
+    points: RefCell<Vec<(f32, f32)>>,
+}
+
+impl View for StereoScope {
+    fn element(&self) -> Option<&'static str> {
+        Some("stereo-scope")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        use vizia_plug::vizia::vg;
+
+        let bounds = cx.bounds();
+        if bounds.w < 1.0 || bounds.h < 1.0 {
+            return;
+        }
+
+        let fill = |argb: (u8, u8, u8, u8)| {
+            let mut paint = vg::Paint::default();
+            paint.set_color(vg::Color::from_argb(argb.0, argb.1, argb.2, argb.3));
+            paint.set_style(vg::PaintStyle::Fill);
+            paint.set_anti_alias(true);
+            paint
+        };
+
+        // ── Goniometer square ───────────────────────────────────────────
+        let bar_h = SCOPE_CORRELATION_H * cx.scale_factor();
+        let gap = 3.0 * cx.scale_factor();
+        let size = bounds.w.min(bounds.h - bar_h - gap).max(1.0);
+        let left = bounds.x + (bounds.w - size) * 0.5;
+        canvas.draw_rect(
+            vg::Rect::from_xywh(left, bounds.y, size, size),
+            &fill((255, 12, 16, 20)),
+        );
+        let (cx0, cy0) = (left + size * 0.5, bounds.y + size * 0.5);
+        let mut guide = fill((60, 200, 200, 200));
+        guide.set_style(vg::PaintStyle::Stroke);
+        guide.set_stroke_width(1.0);
+        canvas.draw_line((cx0, bounds.y), (cx0, bounds.y + size), &guide);
+        canvas.draw_line((left, cy0), (left + size, cy0), &guide);
+
+        {
+            let mut points = self.points.borrow_mut();
+            self.source.read_points(&mut points);
+            let half = size * 0.5 * SCOPE_TRACE_GAIN;
+            let dot = fill((150, 110, 220, 190));
+            for &(l, r) in points.iter() {
+                // M/S rotation: mono (L == R) plots straight up.
+                let x = ((r - l) * std::f32::consts::FRAC_1_SQRT_2).clamp(-1.0, 1.0);
+                let y = ((l + r) * std::f32::consts::FRAC_1_SQRT_2).clamp(-1.0, 1.0);
+                canvas.draw_rect(
+                    vg::Rect::from_xywh(cx0 + x * half, cy0 - y * half, 1.5, 1.5),
+                    &dot,
+                );
+            }
+        }
+
+        // ── Correlation bar: -1 (left edge) … +1 (right edge) ───────────
+        let bar_y = bounds.y + size + gap;
+        canvas.draw_rect(
+            vg::Rect::from_xywh(left, bar_y, size, bar_h),
+            &fill((255, 24, 28, 34)),
+        );
+        let correlation = self.source.load_correlation();
+        let centre = left + size * 0.5;
+        let tip = centre + correlation * size * 0.5;
+        let colour = if correlation < 0.0 {
+            (255, 240, 72, 64)
+        } else {
+            (255, 96, 208, 96)
+        };
+        canvas.draw_rect(
+            vg::Rect::from_xywh(centre.min(tip), bar_y, (tip - centre).abs().max(1.0), bar_h),
+            &fill(colour),
+        );
+
+        cx.needs_redraw();
+    }
+}
+
+/// Goniometer + correlation meter fed by the master stereo tap. `width` and
+/// `height` are logical px; the goniometer stays square inside them.
+pub fn create_stereo_scope(
+    cx: &mut Context,
+    source: Arc<spectral::StereoScopeData>,
+    width: f32,
+    height: f32,
+) {
+    StereoScope {
+        source,
+        points: RefCell::new(vec![(0.0, 0.0); spectral::SCOPE_POINTS]),
+    }
+    .build(cx, |_| {})
+    .class("stereo-scope")
+    .width(Pixels(width))
+    .height(Pixels(height));
+}
+
+// ── Stage level pip ───────────────────────────────────────────────────────────
+//
+// Small arrow-shaped level meter drawn between stages of the signal-flow
+// strip. Reads one index of the per-stage RMS/peak tap and shows it on the
+// chosen meter scale, with that scale's ballistics; see meter_scale.rs. A
+// hairline under the shaft shows the stage's side level on the same scale,
+// so a module that widens or narrows the image moves it between pips.
+
+struct StageLevelPip {
+    source: Arc<spectral::StageLevelData>,
+    stage: usize,
+    scale: MeterScale,
+    /// Per-frame smoothing of the displayed level (0 = frozen, 1 = raw);
+    /// see `MeterBallistics::smoothing`.
+    smoothing: f32,
+    reading: Cell<MeterReading>,
+    /// Displayed side level (dBFS), smoothed like a K-scale reading.
+    side_shown: Cell<f32>,
+    /// When the last frame was drawn, for the VU's timed integration.
+    last_frame: Cell<Option<std::time::Instant>>,
+}
+
+impl View for StageLevelPip {
+    fn element(&self) -> Option<&'static str> {
+        Some("stage-level-pip")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        use vizia_plug::vizia::vg;
+
+        let bounds = cx.bounds();
+        if bounds.w < 1.0 || bounds.h < 1.0 {
+            return;
+        }
+
+        let now = std::time::Instant::now();
+        let dt = self
+            .last_frame
+            .replace(Some(now))
+            .map_or(0.0, |last| (now - last).as_secs_f32());
+        let mut reading = self.reading.get();
+        let shown = reading.update(
+            self.scale,
+            self.source.load_db(self.stage),
+            self.source.load_peak_db(self.stage),
+            self.smoothing,
+            dt,
+        );
+        self.reading.set(reading);
+
+        let fill = |argb: (u8, u8, u8, u8)| {
+            let mut paint = vg::Paint::default();
+            paint.set_color(vg::Color::from_argb(argb.0, argb.1, argb.2, argb.3));
+            paint.set_style(vg::PaintStyle::Fill);
+            paint.set_anti_alias(true);
+            paint
+        };
+
+        // Shaft (meter track + fill) with a triangular head pointing
+        // downstream.
+        let head_w = bounds.h * 0.6;
+        let shaft_w = (bounds.w - head_w).max(1.0);
+        let shaft_h = bounds.h * 0.4;
+        let shaft_y = bounds.y + (bounds.h - shaft_h) * 0.5;
+        canvas.draw_rect(
+            vg::Rect::from_xywh(bounds.x, shaft_y, shaft_w, shaft_h),
+            &fill((255, 24, 28, 34)),
+        );
+        let fraction = self.scale.fraction(shown);
+        // Green in the working range, amber in the headroom, red at the top.
+        let colour = match self.scale.zone(shown) {
+            MeterZone::Over => (255, 240, 72, 64),
+            MeterZone::Hot => (255, 240, 176, 64),
+            MeterZone::Normal => (255, 96, 208, 96),
+        };
+        canvas.draw_rect(
+            vg::Rect::from_xywh(bounds.x, shaft_y, shaft_w * fraction, shaft_h),
+            &fill(colour),
+        );
+        let (_, side_db) = self.source.load_mid_side_db(self.stage);
+        let side = self.side_shown.get();
+        let side = side + (side_db - side) * self.smoothing;
+        self.side_shown.set(side);
+        canvas.draw_rect(
+            vg::Rect::from_xywh(
+                bounds.x,
+                shaft_y + shaft_h + 1.0,
+                shaft_w * self.scale.fraction(side),
+                1.0,
+            ),
+            &fill((255, 96, 176, 224)),
+        );
+        // Tick at the scale's 0 when it isn't the top of the shaft.
+        let zero = self.scale.fraction(self.scale.reference_dbfs());
+        if zero < 1.0 {
+            canvas.draw_rect(
+                vg::Rect::from_xywh(bounds.x + shaft_w * zero, shaft_y, 1.0, shaft_h),
+                &fill((200, 230, 232, 236)),
+            );
+        }
+        let mut head = vg::Path::new();
+        head.move_to((bounds.x + shaft_w, bounds.y));
+        head.line_to((bounds.x + bounds.w, bounds.y + bounds.h * 0.5));
+        head.line_to((bounds.x + shaft_w, bounds.y + bounds.h));
+        head.close();
+        canvas.draw_path(&head, &fill((200, 140, 152, 168)));
+
+        cx.needs_redraw();
+    }
+}
+
+/// Level arrow between two stages of the signal-flow strip. `stage` indexes
+/// `spectral::StageLevelData` (0 = input, N + 1 = after slot N).
+pub fn create_stage_level_pip(
+    cx: &mut Context,
+    source: Arc<spectral::StageLevelData>,
+    stage: usize,
+    scale: MeterScale,
+    smoothing: f32,
+) {
+    StageLevelPip {
+        source,
+        stage,
+        scale,
+        smoothing,
+        reading: Cell::new(MeterReading::new()),
+        side_shown: Cell::new(READING_FLOOR_DB),
+        last_frame: Cell::new(None),
+    }
+    .build(cx, |_| {})
+    .class("stage-level-pip")
+    .width(Pixels(22.0))
+    .height(Pixels(10.0))
+    .top(Stretch(1.0))
+    .bottom(Stretch(1.0));
+}
+
+// ── Mid/side meter ────────────────────────────────────────────────────────────
+//
+// Two stacked bars for one index of the per-stage tap: mid on top, side
+// underneath, both RMS on the Digital scale's span. A tick on the side bar
+// marks the mid level, so the gap between them reads as width at a glance;
+// put the strip's IN and OUT meters side by side and a stage that widens or
+// narrows the image shows as the gap changing.
+
+/// Height of each of the two bars (logical px).
+const MID_SIDE_BAR_H: f32 = 4.0;
+
+struct MidSideMeter {
+    source: Arc<spectral::StageLevelData>,
+    stage: usize,
+    /// Per-frame smoothing, as for the stage pips.
+    smoothing: f32,
+    /// Displayed (mid, side) levels in dBFS.
+    shown: Cell<(f32, f32)>,
+}
+
+impl View for MidSideMeter {
+    fn element(&self) -> Option<&'static str> {
+        Some("mid-side-meter")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        use vizia_plug::vizia::vg;
+
+        let bounds = cx.bounds();
+        if bounds.w < 1.0 || bounds.h < 1.0 {
+            return;
+        }
+
+        let (mid_db, side_db) = self.source.load_mid_side_db(self.stage);
+        let (mid, side) = self.shown.get();
+        let shown = (
+            mid + (mid_db - mid) * self.smoothing,
+            side + (side_db - side) * self.smoothing,
+        );
+        self.shown.set(shown);
+
+        let fill = |argb: (u8, u8, u8, u8)| {
+            let mut paint = vg::Paint::default();
+            paint.set_color(vg::Color::from_argb(argb.0, argb.1, argb.2, argb.3));
+            paint.set_style(vg::PaintStyle::Fill);
+            paint.set_anti_alias(true);
+            paint
+        };
+        let scale = MeterScale::Digital;
+        let gap = (bounds.h - 2.0 * MID_SIDE_BAR_H).max(0.0);
+        let side_y = bounds.y + MID_SIDE_BAR_H + gap;
+        for (y, db, colour) in [
+            (bounds.y, shown.0, (255, 96, 208, 96)),
+            (side_y, shown.1, (255, 96, 176, 224)),
+        ] {
+            canvas.draw_rect(
+                vg::Rect::from_xywh(bounds.x, y, bounds.w, MID_SIDE_BAR_H),
+                &fill((255, 24, 28, 34)),
+            );
+            canvas.draw_rect(
+                vg::Rect::from_xywh(bounds.x, y, bounds.w * scale.fraction(db), MID_SIDE_BAR_H),
+                &fill(colour),
+            );
+        }
+        let mid_x = bounds.x + bounds.w * scale.fraction(shown.0);
+        canvas.draw_rect(
+            vg::Rect::from_xywh(mid_x - 0.5, side_y, 1.0, MID_SIDE_BAR_H),
+            &fill((200, 230, 232, 236)),
+        );
+
+        cx.needs_redraw();
+    }
+}
+
+/// Labelled mid-over-side bars for one stage of `source`.
+pub fn create_mid_side_meter(
+    cx: &mut Context,
+    label: &str,
+    source: Arc<spectral::StageLevelData>,
+    stage: usize,
+    smoothing: f32,
+) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr(label))
+            .class("param-label")
+            .width(Pixels(28.0))
+            .height(Pixels(PARAM_LABEL_H));
+        MidSideMeter {
+            source,
+            stage,
+            smoothing,
+            shown: Cell::new((READING_FLOOR_DB, READING_FLOOR_DB)),
+        }
+        .build(cx, |_| {})
+        .class("mid-side-meter")
+        .width(Stretch(1.0))
+        .height(Pixels(2.0 * MID_SIDE_BAR_H + 2.0))
+        .top(Stretch(1.0))
+        .bottom(Stretch(1.0));
+    })
+    .height(Auto)
+    .width(Stretch(1.0))
+    .gap(Pixels(4.0));
+}
+
+// ── Dynamic gain overlay ──────────────────────────────────────────────────────
+//
+// Drawn on top of a DynEQ band's GAIN slider: a span from the static gain to
+// the gain the band is applying right now (static + dynamic offset from the
+// per-band GR tap), ending in a marker line. Orange when the band is cutting,
+// green when it is boosting. Not hoverable, so the slider underneath keeps
+// every mouse interaction.
+
+/// Offsets smaller than this (dB) are not drawn.
+const DYN_GAIN_MIN_OFFSET_DB: f32 = 0.05;
+
+struct DynamicGainOverlay {
+    source: Arc<spectral::GainReductionData>,
+    band: usize,
+    gain: ParamPtr,
+}
+
+impl View for DynamicGainOverlay {
+    fn element(&self) -> Option<&'static str> {
+        Some("dyn-gain-overlay")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        use std::sync::atomic::Ordering;
+        use vizia_plug::vizia::vg;
+
+        let bounds = cx.bounds();
+        if bounds.w < 1.0 || bounds.h < 1.0 {
+            return;
+        }
+
+        // GR is positive for a cut, so the applied offset is its negation.
+        let offset_db = -f32::from_bits(self.source.bands[self.band].load(Ordering::Relaxed));
+        if offset_db.abs() >= DYN_GAIN_MIN_OFFSET_DB {
+            // SAFETY: the ParamPtr comes from the editor's Arc'd params, which
+            // outlive every view in the editor.
+            let static_db = unsafe { self.gain.modulated_plain_value() };
+            let x_of = |db: f32| bounds.x + unsafe { self.gain.preview_normalized(db) } * bounds.w;
+            let (x_static, x_live) = (x_of(static_db), x_of(static_db + offset_db));
+            let (r, g, b) = if offset_db < 0.0 {
+                (240, 160, 64)
+            } else {
+                (96, 208, 96)
+            };
+
+            let mut span = vg::Paint::default();
+            span.set_color(vg::Color::from_argb(90, r, g, b));
+            span.set_style(vg::PaintStyle::Fill);
+            canvas.draw_rect(
+                vg::Rect::from_xywh(
+                    x_static.min(x_live),
+                    bounds.y,
+                    (x_live - x_static).abs(),
+                    bounds.h,
+                ),
+                &span,
+            );
+
+            let mut marker = vg::Paint::default();
+            marker.set_color(vg::Color::from_argb(255, r, g, b));
+            marker.set_style(vg::PaintStyle::Stroke);
+            marker.set_stroke_width(2.0 * cx.scale_factor());
+            marker.set_anti_alias(true);
+            canvas.draw_line((x_live, bounds.y), (x_live, bounds.y + bounds.h), &marker);
+        }
+
+        cx.needs_redraw();
+    }
+}
+
+/// Live dynamic-gain indicator for DynEQ band `band`, laid over the band's
+/// GAIN slider. The caller positions it (absolute, filling the slider).
+pub fn create_dynamic_gain_overlay(
+    cx: &mut Context,
+    source: Arc<spectral::GainReductionData>,
+    band: usize,
+    gain: ParamPtr,
+) -> Handle<'_, impl View> {
+    DynamicGainOverlay { source, band, gain }
+        .build(cx, |_| {})
+        .class("dyn-gain-overlay")
+        .hoverable(false)
+}
+
+// ── Gain-reduction bar ────────────────────────────────────────────────────────
+//
+// Vertical GR meter for the metering bridge, growing down from the top like
+// a console GR needle. The reader closure pulls the current GR (dB, positive
+// = attenuation) from whichever atomic tap the module publishes.
+
+/// Full-scale GR shown by a GR bar (dB).
+const GR_BAR_RANGE_DB: f32 = 24.0;
+
+struct GrBar {
+    read: Box<dyn Fn() -> f32>,
+}
+
+impl View for GrBar {
+    fn element(&self) -> Option<&'static str> {
+        Some("gr-bar")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        use vizia_plug::vizia::vg;
+
+        let bounds = cx.bounds();
+        if bounds.w < 1.0 || bounds.h < 1.0 {
+            return;
+        }
+
+        let mut bg = vg::Paint::default();
+        bg.set_color(vg::Color::from_argb(255, 12, 16, 20));
+        bg.set_style(vg::PaintStyle::Fill);
+        canvas.draw_rect(
+            vg::Rect::from_xywh(bounds.x, bounds.y, bounds.w, bounds.h),
+            &bg,
+        );
+
+        let fraction = ((self.read)() / GR_BAR_RANGE_DB).clamp(0.0, 1.0);
+        let mut bar = vg::Paint::default();
+        bar.set_color(vg::Color::from_argb(230, 240, 160, 64));
+        bar.set_style(vg::PaintStyle::Fill);
+        canvas.draw_rect(
+            vg::Rect::from_xywh(bounds.x, bounds.y, bounds.w, fraction * bounds.h),
+            &bar,
+        );
+
+        cx.needs_redraw();
+    }
+}
+
+/// Labelled vertical GR meter. `read` returns the current GR in dB.
+pub fn create_gr_bar(cx: &mut Context, label: &str, read: impl Fn() -> f32 + 'static) {
+    VStack::new(cx, |cx| {
+        GrBar {
+            read: Box::new(read),
+        }
+        .build(cx, |_| {})
+        .class("gr-bar")
+        .width(Pixels(14.0))
+        .height(Stretch(1.0))
+        .left(Stretch(1.0))
+        .right(Stretch(1.0));
+        Label::new(cx, tr(label))
+            .class("param-label")
+            .height(Pixels(PARAM_LABEL_H));
+    })
+    .class("gr-bar-col")
+    .width(Pixels(40.0))
+    .height(Stretch(1.0))
+    .gap(Pixels(4.0));
+}
+
+// ── Gain-reduction history ────────────────────────────────────────────────────
+//
+// Scrolling GR trace for the compressor and DynEQ over the last ~10 s
+// (spectral::GR_HISTORY_LEN columns). Newest column on the right; GR grows
+// downward from the top edge like a console GR meter.
+
+/// Full-scale GR shown by the history graph (dB).
+const GR_HISTORY_RANGE_DB: f32 = 24.0;
+
+struct GrHistoryGraph {
+    source: Arc<Mutex<spectral::GrHistoryData>>,
+    columns: RefCell<Vec<(f32, f32)>>,
+}
+
+impl View for GrHistoryGraph {
+    fn element(&self) -> Option<&'static str> {
+        Some("gr-history")
+    }
+
+    fn draw(&self, cx: &mut DrawContext, canvas: &Canvas) {
+        use vizia_plug::vizia::vg;
+
+        let bounds = cx.bounds();
+        if bounds.w < 1.0 || bounds.h < 1.0 {
+            return;
+        }
+
+        let mut bg = vg::Paint::default();
+        bg.set_color(vg::Color::from_argb(255, 12, 16, 20));
+        bg.set_style(vg::PaintStyle::Fill);
+        canvas.draw_rect(
+            vg::Rect::from_xywh(bounds.x, bounds.y, bounds.w, bounds.h),
+            &bg,
+        );
+
+        // 6 dB grid lines.
+        let mut grid = vg::Paint::default();
+        grid.set_color(vg::Color::from_argb(40, 200, 200, 200));
+        grid.set_style(vg::PaintStyle::Stroke);
+        grid.set_stroke_width(1.0);
+        let mut db = 6.0;
+        while db < GR_HISTORY_RANGE_DB {
+            let y = bounds.y + db / GR_HISTORY_RANGE_DB * bounds.h;
+            canvas.draw_line((bounds.x, y), (bounds.x + bounds.w, y), &grid);
+            db += 6.0;
+        }
+
+        let mut columns = self.columns.borrow_mut();
+        if let Ok(mut history) = self.source.lock() {
+            history.update();
+            history.read_columns(&mut columns);
+        }
+        let step = bounds.w / (spectral::GR_HISTORY_LEN - 1) as f32;
+        let y_of = |db: f32| bounds.y + (db / GR_HISTORY_RANGE_DB).clamp(0.0, 1.0) * bounds.h;
+
+        // Compressor (orange) then DynEQ (green) on top.
+        for (pick, (r, g, b)) in [
+            (0_usize, (240_u8, 160_u8, 64_u8)),
+            (1_usize, (96_u8, 208_u8, 96_u8)),
+        ] {
+            let mut path = vg::Path::new();
+            for (i, &(comp, dyneq)) in columns.iter().enumerate() {
+                let value = if pick == 0 { comp } else { dyneq };
+                let point = (bounds.x + i as f32 * step, y_of(value));
+                if i == 0 {
+                    path.move_to(point);
+                } else {
+                    path.line_to(point);
+                }
+            }
+            let mut stroke = vg::Paint::default();
+            stroke.set_color(vg::Color::from_argb(230, r, g, b));
+            stroke.set_style(vg::PaintStyle::Stroke);
+            stroke.set_stroke_width(1.5 * cx.scale_factor());
+            stroke.set_anti_alias(true);
+            canvas.draw_path(&path, &stroke);
+        }
+
+        cx.needs_redraw();
+    }
+}
+
+/// Scrolling gain-reduction history fed by the compressor / DynEQ tap.
+pub fn create_gr_history(cx: &mut Context, source: Arc<Mutex<spectral::GrHistoryData>>) {
+    VStack::new(cx, |cx| {
+        HStack::new(cx, |cx| {
+            Label::new(cx, tr("GR HISTORY")).class("param-label");
+            Label::new(cx, tr("COMP")).class("gr-history-key-comp");
+            Label::new(cx, tr("DYN EQ")).class("gr-history-key-dyneq");
+        })
+        .class("gr-history-header")
+        .height(Pixels(PARAM_LABEL_H))
+        .gap(Pixels(8.0));
+        GrHistoryGraph {
+            source,
+            columns: RefCell::new(vec![(0.0, 0.0); spectral::GR_HISTORY_LEN]),
+        }
+        .build(cx, |_| {})
+        .class("gr-history")
+        .width(Stretch(1.0))
+        .height(Pixels(44.0));
+    })
+    .class("gr-history-panel")
+    .height(Auto)
+    .width(Stretch(1.0))
+    .gap(Pixels(2.0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{entry_number, key_action, KeyAction};
+    use vizia_plug::vizia::prelude::Code;
+
+    #[test]
+    fn entry_number_reads_units_and_multipliers() {
+        assert_eq!(entry_number("2.5k"), Some(2500.0));
+        assert_eq!(entry_number("2.5 kHz"), Some(2500.0));
+        assert_eq!(entry_number("-6 dB"), Some(-6.0));
+        assert_eq!(entry_number("+3dB"), Some(3.0));
+        assert_eq!(entry_number("3:1"), Some(3.0));
+        assert_eq!(entry_number(" 100ms "), Some(100.0));
+        assert_eq!(entry_number("abc"), None);
+        assert_eq!(entry_number("-"), None);
+    }
+
+    #[test]
+    fn keys_adjust_a_focused_control() {
+        let act = |code, current, switch| key_action(code, current, 0.25, 0.03125, switch);
+        assert_eq!(
+            act(Code::ArrowUp, 0.5, false),
+            Some(KeyAction::Set(0.53125))
+        );
+        assert_eq!(
+            act(Code::ArrowLeft, 0.5, false),
+            Some(KeyAction::Set(0.46875))
+        );
+        assert_eq!(
+            act(Code::PageDown, 0.5, false),
+            Some(KeyAction::Set(0.1875))
+        );
+        assert_eq!(act(Code::Home, 0.5, false), Some(KeyAction::Set(0.0)));
+        assert_eq!(act(Code::End, 0.5, false), Some(KeyAction::Set(1.0)));
+        assert_eq!(act(Code::Delete, 0.5, false), Some(KeyAction::Set(0.25)));
+        assert_eq!(act(Code::Enter, 0.5, false), Some(KeyAction::Entry));
+        // Space is left to the host on continuous controls.
+        assert_eq!(act(Code::Space, 0.5, false), None);
+        assert_eq!(act(Code::KeyA, 0.5, false), None);
+    }
+
+    #[test]
+    fn enter_and_space_toggle_a_switch() {
+        assert_eq!(
+            key_action(Code::Enter, 0.0, 0.0, 1.0, true),
+            Some(KeyAction::Set(1.0))
+        );
+        assert_eq!(
+            key_action(Code::Space, 1.0, 0.0, 1.0, true),
+            Some(KeyAction::Set(0.0))
+        );
+    }
 }
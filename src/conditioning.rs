@@ -0,0 +1,556 @@
+// src/conditioning.rs
+// Input conditioning: the clean-up stage pinned ahead of slot 1.
+//
+// Signal flow, in processing order:
+//
+//   [in] -> HPF (Butterworth, 6-24 dB/oct) -> LPF (Butterworth, 6-24 dB/oct)
+//        -> phase rotation (Hilbert all-pass pair) -> polarity -> [out]
+//
+// Nothing here colours the sound: the filters take out rumble and hash the
+// rack shouldn't react to, and the rotator redistributes an asymmetric
+// waveform (voice, bass, brass) so its positive and negative peaks even out
+// before the compressor and clipper see them, without changing its
+// spectrum. Like Sheen at the other end it isn't a rack slot and can't be
+// reordered; unlike Sheen it's off until switched in.
+//
+// Phase rotation runs the signal through two all-pass chains whose phase
+// responses stay 90° apart across the band (Olli Niemitalo's 4+4 polyphase
+// Hilbert pair) and mixes them by the cosine and sine of the angle. Both
+// chains share a common phase response, so the rotator is never a plain
+// wire: at 0° the stage leaves it out of circuit entirely. The pair is
+// designed relative to the sample rate; at 48 kHz the 90° split holds
+// from about 20 Hz up.
+
+use crate::shaping::biquad_coeffs_f64;
+use biquad::{Biquad, Coefficients, DirectForm2Transposed, Type};
+#[cfg(feature = "plugin")]
+use nih_plug::buffer::Buffer;
+#[cfg(feature = "plugin")]
+use nih_plug::prelude::Enum;
+
+/// Rotations closer to 0° than this take the rotator out of circuit.
+const ROTATION_OFF_DEG: f32 = 0.5;
+
+/// Squared all-pass coefficients of the Hilbert pair. Each stage is
+/// `y[n] = a²·(x[n] + y[n-2]) - x[n-2]`; the first chain is followed by a
+/// one-sample delay.
+const HILBERT_A: [f64; 4] = [
+    0.692_387_8 * 0.692_387_8,
+    0.936_065_432_295_9 * 0.936_065_432_295_9,
+    0.988_229_522_686 * 0.988_229_522_686,
+    0.998_748_845_273_7 * 0.998_748_845_273_7,
+];
+const HILBERT_B: [f64; 4] = [
+    0.402_192_116_242_6 * 0.402_192_116_242_6,
+    0.856_171_088_242 * 0.856_171_088_242,
+    0.972_290_954_565_1 * 0.972_290_954_565_1,
+    0.995_288_479_127_8 * 0.995_288_479_127_8,
+];
+
+/// Filter slope for the HPF and LPF. Odd orders add a first-order section.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "plugin", derive(Enum))]
+pub enum Slope {
+    #[cfg_attr(feature = "plugin", name = "Off")]
+    Off,
+    #[cfg_attr(feature = "plugin", name = "6 dB/oct")]
+    Db6,
+    #[cfg_attr(feature = "plugin", name = "12 dB/oct")]
+    Db12,
+    #[cfg_attr(feature = "plugin", name = "18 dB/oct")]
+    Db18,
+    #[cfg_attr(feature = "plugin", name = "24 dB/oct")]
+    Db24,
+}
+
+impl Slope {
+    /// Butterworth section Qs for this order; `None` is the first-order
+    /// section.
+    fn sections(self) -> &'static [Option<f32>] {
+        match self {
+            Slope::Off => &[],
+            Slope::Db6 => &[None],
+            Slope::Db12 => &[Some(std::f32::consts::FRAC_1_SQRT_2)],
+            Slope::Db18 => &[None, Some(1.0)],
+            Slope::Db24 => &[Some(0.541_196_1), Some(1.306_563)],
+        }
+    }
+}
+
+/// Which channels get their polarity flipped.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "plugin", derive(Enum))]
+pub enum Polarity {
+    #[cfg_attr(feature = "plugin", name = "Normal")]
+    Normal,
+    #[cfg_attr(feature = "plugin", name = "Invert L")]
+    InvertLeft,
+    #[cfg_attr(feature = "plugin", name = "Invert R")]
+    InvertRight,
+    #[cfg_attr(feature = "plugin", name = "Invert L+R")]
+    InvertBoth,
+}
+
+impl Polarity {
+    fn sign(self, ch: usize) -> f32 {
+        let flipped = match self {
+            Polarity::Normal => false,
+            Polarity::InvertLeft => ch == 0,
+            Polarity::InvertRight => ch == 1,
+            Polarity::InvertBoth => true,
+        };
+        if flipped {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Pass {
+    High,
+    Low,
+}
+
+/// First-order Butterworth (bilinear) section as biquad coefficients.
+fn first_order(pass: Pass, sample_rate: f32, freq: f32) -> Coefficients<f64> {
+    let nyquist_guard = sample_rate as f64 * 0.49;
+    let k = (std::f64::consts::PI * (freq as f64).min(nyquist_guard) / sample_rate as f64).tan();
+    let a1 = (k - 1.0) / (k + 1.0);
+    let (b0, b1) = match pass {
+        Pass::High => (1.0 / (1.0 + k), -1.0 / (1.0 + k)),
+        Pass::Low => (k / (1.0 + k), k / (1.0 + k)),
+    };
+    Coefficients {
+        a1,
+        a2: 0.0,
+        b0,
+        b1,
+        b2: 0.0,
+    }
+}
+
+/// Up to two cascaded sections per channel.
+struct Cascade {
+    pass: Pass,
+    sections: [[DirectForm2Transposed<f64>; 2]; 2],
+    active: usize,
+}
+
+impl Cascade {
+    fn new(pass: Pass, sample_rate: f32, freq: f32, slope: Slope) -> Self {
+        let flat = Coefficients {
+            a1: 0.0,
+            a2: 0.0,
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+        };
+        let mut cascade = Self {
+            pass,
+            sections: std::array::from_fn(|_| {
+                std::array::from_fn(|_| DirectForm2Transposed::<f64>::new(flat))
+            }),
+            active: 0,
+        };
+        cascade.configure(sample_rate, freq, slope);
+        cascade
+    }
+
+    /// New corner or slope. Filter memory is kept so a sweep doesn't click;
+    /// a section that wasn't running starts from silence.
+    fn configure(&mut self, sample_rate: f32, freq: f32, slope: Slope) {
+        let sections = slope.sections();
+        for (k, q) in sections.iter().enumerate() {
+            let coeffs = match (q, self.pass) {
+                (None, pass) => first_order(pass, sample_rate, freq),
+                (Some(q), Pass::High) => biquad_coeffs_f64(Type::HighPass, sample_rate, freq, *q)
+                    .expect("HPF coefficients"),
+                (Some(q), Pass::Low) => biquad_coeffs_f64(Type::LowPass, sample_rate, freq, *q)
+                    .expect("LPF coefficients"),
+            };
+            for channel in &mut self.sections {
+                if k >= self.active {
+                    channel[k].reset_state();
+                }
+                channel[k].update_coefficients(coeffs);
+            }
+        }
+        self.active = sections.len();
+    }
+
+    #[inline]
+    fn run(&mut self, sample: f64, ch: usize) -> f64 {
+        self.sections[ch][..self.active]
+            .iter_mut()
+            .fold(sample, |s, section| section.run(s))
+    }
+
+    fn reset(&mut self) {
+        self.sections
+            .iter_mut()
+            .flatten()
+            .for_each(|s| s.reset_state());
+    }
+}
+
+/// One chain of the Hilbert pair: four second-order all-passes in `z⁻²`.
+#[derive(Clone, Copy, Default)]
+struct AllpassChain {
+    /// Per stage: x[n-1], x[n-2], y[n-1], y[n-2].
+    state: [[f64; 4]; 4],
+}
+
+impl AllpassChain {
+    #[inline]
+    fn run(&mut self, coeffs: &[f64; 4], mut x: f64) -> f64 {
+        for (a, s) in coeffs.iter().zip(&mut self.state) {
+            let y = a * (x + s[3]) - s[1];
+            *s = [x, s[0], y, s[2]];
+            x = y;
+        }
+        x
+    }
+}
+
+/// Constant-angle phase rotator for one channel.
+#[derive(Clone, Copy, Default)]
+struct Rotator {
+    real: AllpassChain,
+    imag: AllpassChain,
+    /// The real chain's one-sample delay.
+    real_delay: f64,
+}
+
+impl Rotator {
+    #[inline]
+    fn run(&mut self, x: f64, cos: f64, sin: f64) -> f64 {
+        let real = self.real_delay;
+        self.real_delay = self.real.run(&HILBERT_A, x);
+        let imag = self.imag.run(&HILBERT_B, x);
+        real * cos - imag * sin
+    }
+}
+
+/// The input conditioning stage.
+pub struct InputConditioning {
+    sample_rate: f32,
+    bypassed: bool,
+    hpf: Cascade,
+    lpf: Cascade,
+    hpf_freq: f32,
+    hpf_slope: Slope,
+    lpf_freq: f32,
+    lpf_slope: Slope,
+    rotators: [Rotator; 2],
+    rotation_deg: f32,
+    rotation: (f64, f64),
+    polarity: Polarity,
+}
+
+impl InputConditioning {
+    /// Defaults match the plugin's: 20 Hz HPF at 12 dB/oct, LPF off, no
+    /// rotation, normal polarity, bypassed.
+    pub fn new(sample_rate: f32) -> Self {
+        let (hpf_freq, hpf_slope) = (20.0, Slope::Db12);
+        let (lpf_freq, lpf_slope) = (20000.0, Slope::Off);
+        Self {
+            sample_rate,
+            bypassed: true,
+            hpf: Cascade::new(Pass::High, sample_rate, hpf_freq, hpf_slope),
+            lpf: Cascade::new(Pass::Low, sample_rate, lpf_freq, lpf_slope),
+            hpf_freq,
+            hpf_slope,
+            lpf_freq,
+            lpf_slope,
+            rotators: [Rotator::default(); 2],
+            rotation_deg: 0.0,
+            rotation: (1.0, 0.0),
+            polarity: Polarity::Normal,
+        }
+    }
+
+    /// Called once per buffer. Coefficients are only rebuilt when a corner
+    /// or slope moved. Coming out of bypass clears the filter memory, which
+    /// holds whatever was playing when the stage was switched out.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_parameters(
+        &mut self,
+        bypassed: bool,
+        hpf_freq: f32,
+        hpf_slope: Slope,
+        lpf_freq: f32,
+        lpf_slope: Slope,
+        rotation_deg: f32,
+        polarity: Polarity,
+    ) {
+        if self.bypassed && !bypassed {
+            self.reset();
+        }
+        self.bypassed = bypassed;
+
+        if (hpf_freq - self.hpf_freq).abs() > 1.0e-3 || hpf_slope != self.hpf_slope {
+            self.hpf.configure(self.sample_rate, hpf_freq, hpf_slope);
+            self.hpf_freq = hpf_freq;
+            self.hpf_slope = hpf_slope;
+        }
+        if (lpf_freq - self.lpf_freq).abs() > 1.0e-3 || lpf_slope != self.lpf_slope {
+            self.lpf.configure(self.sample_rate, lpf_freq, lpf_slope);
+            self.lpf_freq = lpf_freq;
+            self.lpf_slope = lpf_slope;
+        }
+        if rotation_deg != self.rotation_deg {
+            // Entering the rotator from 0°: start its chains from silence.
+            if self.rotation_deg.abs() < ROTATION_OFF_DEG {
+                self.rotators = [Rotator::default(); 2];
+            }
+            self.rotation_deg = rotation_deg;
+            let (sin, cos) = (rotation_deg as f64).to_radians().sin_cos();
+            self.rotation = (cos, sin);
+        }
+        self.polarity = polarity;
+    }
+
+    /// Process audio buffer through the stage.
+    #[cfg(feature = "plugin")]
+    pub fn process(&mut self, buffer: &mut Buffer) {
+        self.process_channels(buffer.as_slice());
+    }
+
+    /// Process one block given as per-channel slices. Channels past the
+    /// second share the right channel's state.
+    pub fn process_channels(&mut self, channels: &mut [&mut [f32]]) {
+        if self.bypassed {
+            return;
+        }
+        let rotate = self.rotation_deg.abs() >= ROTATION_OFF_DEG;
+        let (cos, sin) = self.rotation;
+        for (ch, channel) in channels.iter_mut().enumerate() {
+            let ch = ch.min(1);
+            let sign = self.polarity.sign(ch) as f64;
+            for sample in channel.iter_mut() {
+                let mut s = self.hpf.run(*sample as f64, ch);
+                s = self.lpf.run(s, ch);
+                if rotate {
+                    s = self.rotators[ch].run(s, cos, sin);
+                }
+                *sample = (s * sign) as f32;
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.hpf.reset();
+        self.lpf.reset();
+        self.rotators = [Rotator::default(); 2];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    fn sine(freq: f32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (std::f32::consts::TAU * freq * i as f32 / SR).sin())
+            .collect()
+    }
+
+    fn rms(x: &[f32]) -> f32 {
+        (x.iter().map(|s| s * s).sum::<f32>() / x.len() as f32).sqrt()
+    }
+
+    /// Steady-state RMS gain of `stage` at `freq`, left channel.
+    fn gain_at(stage: &mut InputConditioning, freq: f32) -> f32 {
+        stage.reset();
+        let input = sine(freq, 48000);
+        let mut left = input.clone();
+        stage.process_channels(&mut [&mut left[..]]);
+        rms(&left[24000..]) / rms(&input[24000..])
+    }
+
+    fn active() -> InputConditioning {
+        let mut stage = InputConditioning::new(SR);
+        stage.update_parameters(
+            false,
+            20.0,
+            Slope::Off,
+            20000.0,
+            Slope::Off,
+            0.0,
+            Polarity::Normal,
+        );
+        stage
+    }
+
+    #[test]
+    fn bypassed_by_default() {
+        let mut stage = InputConditioning::new(SR);
+        let input = sine(50.0, 512);
+        let mut left = input.clone();
+        stage.process_channels(&mut [&mut left[..]]);
+        assert_eq!(left, input);
+    }
+
+    #[test]
+    fn slopes_fall_at_their_rate() {
+        for (slope, db_per_oct) in [
+            (Slope::Db6, 6.0),
+            (Slope::Db12, 12.0),
+            (Slope::Db18, 18.0),
+            (Slope::Db24, 24.0),
+        ] {
+            let mut stage = active();
+            stage.update_parameters(
+                false,
+                400.0,
+                slope,
+                20000.0,
+                Slope::Off,
+                0.0,
+                Polarity::Normal,
+            );
+            // Butterworth: -3 dB at the corner, then the full slope two
+            // octaves below it.
+            let corner = 20.0 * gain_at(&mut stage, 400.0).log10();
+            assert!((corner + 3.01).abs() < 0.1, "{slope:?}: {corner} dB at fc");
+            let octave_2 = 20.0 * gain_at(&mut stage, 100.0).log10();
+            let octave_3 = 20.0 * gain_at(&mut stage, 50.0).log10();
+            let rate = octave_2 - octave_3;
+            assert!((rate - db_per_oct).abs() < 0.6, "{slope:?}: {rate} dB/oct");
+
+            stage.update_parameters(
+                false,
+                20.0,
+                Slope::Off,
+                2000.0,
+                slope,
+                0.0,
+                Polarity::Normal,
+            );
+            let corner = 20.0 * gain_at(&mut stage, 2000.0).log10();
+            assert!(
+                (corner + 3.01).abs() < 0.1,
+                "LPF {slope:?}: {corner} dB at fc"
+            );
+            let passband = 20.0 * gain_at(&mut stage, 100.0).log10();
+            assert!(
+                passband.abs() < 0.05,
+                "LPF {slope:?}: {passband} dB in band"
+            );
+        }
+    }
+
+    #[test]
+    fn rotation_turns_phase_without_changing_level() {
+        let mut stage = active();
+        for (freq, deg) in [(1000.0, 90.0), (60.0, 90.0), (10000.0, 45.0)] {
+            let input = sine(freq, 48000);
+            let run = |stage: &mut InputConditioning, deg: f32| {
+                stage.update_parameters(
+                    false,
+                    20.0,
+                    Slope::Off,
+                    20000.0,
+                    Slope::Off,
+                    deg,
+                    Polarity::Normal,
+                );
+                stage.reset();
+                let mut out = input.clone();
+                stage.process_channels(&mut [&mut out[..]]);
+                out
+            };
+            let base = run(&mut stage, 1.0e-3 + ROTATION_OFF_DEG);
+            let turned = run(&mut stage, deg);
+            let tail = 24000..;
+            assert!(
+                (rms(&turned[tail.clone()]) / rms(&input[tail.clone()]) - 1.0).abs() < 0.01,
+                "{freq} Hz: level changed"
+            );
+            // The angle between the two outputs, from their correlation.
+            let dot: f32 = base[tail.clone()]
+                .iter()
+                .zip(&turned[tail.clone()])
+                .map(|(a, b)| a * b)
+                .sum();
+            let cos = dot
+                / (rms(&base[tail.clone()]) * rms(&turned[tail.clone()]))
+                / (turned.len() - 24000) as f32;
+            let measured = cos.clamp(-1.0, 1.0).acos().to_degrees();
+            assert!(
+                (measured - deg).abs() < 2.0,
+                "{freq} Hz: rotated {measured}° for {deg}°"
+            );
+        }
+    }
+
+    #[test]
+    fn rotation_evens_out_an_asymmetric_wave() {
+        // Fundamental plus an in-phase second harmonic: the positive peak
+        // is well above the negative one.
+        let input: Vec<f32> = (0..48000)
+            .map(|i| {
+                let t = std::f32::consts::TAU * 200.0 * i as f32 / SR;
+                0.5 * t.cos() + 0.3 * (2.0 * t).cos()
+            })
+            .collect();
+        let asymmetry = |x: &[f32]| {
+            let max = x.iter().cloned().fold(f32::MIN, f32::max);
+            let min = x.iter().cloned().fold(f32::MAX, f32::min);
+            max / -min
+        };
+        // The angle that evens a waveform out depends on the source (and on
+        // the rotator's own dispersion), which is why it's a knob: some
+        // setting on the dial must get it close.
+        let mut stage = active();
+        let best = (1..24)
+            .map(|step| {
+                stage.update_parameters(
+                    false,
+                    20.0,
+                    Slope::Off,
+                    20000.0,
+                    Slope::Off,
+                    step as f32 * 15.0 - 180.0,
+                    Polarity::Normal,
+                );
+                stage.reset();
+                let mut out = input.clone();
+                stage.process_channels(&mut [&mut out[..]]);
+                asymmetry(&out[24000..])
+            })
+            .fold(f32::MAX, |best, a| {
+                if (a - 1.0).abs() < (best - 1.0).abs() {
+                    a
+                } else {
+                    best
+                }
+            });
+        let before = asymmetry(&input[24000..]);
+        assert!(before > 1.3, "{before}");
+        assert!((best - 1.0).abs() < 0.1, "best {best}");
+    }
+
+    #[test]
+    fn polarity_flips_the_chosen_channels() {
+        let mut stage = active();
+        stage.update_parameters(
+            false,
+            20.0,
+            Slope::Off,
+            20000.0,
+            Slope::Off,
+            0.0,
+            Polarity::InvertRight,
+        );
+        let mut left = [0.5_f32; 8];
+        let mut right = [0.5_f32; 8];
+        stage.process_channels(&mut [&mut left[..], &mut right[..]]);
+        assert!(left.iter().all(|&s| s == 0.5));
+        assert!(right.iter().all(|&s| s == -0.5));
+    }
+}
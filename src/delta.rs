@@ -0,0 +1,314 @@
+// src/delta.rs
+// Delta listen: hear what one module adds or removes.
+//
+// Around the selected module's slot the chain copies the slot's input into
+// `DeltaTap` before the module runs and subtracts it from the module's
+// output afterwards. The difference waits in the tap until the end of the
+// block, where it replaces what leaves the strip; the rest of the chain,
+// and every meter, keeps running on the program as usual.
+//
+// A module with latency (the API5500 in linear-phase mode) delays its output
+// against its input, so the captured input goes through a matching delay
+// first. Without that, the difference would be mostly the program itself.
+// The oversampled modules delay their output too, by a fraction of a sample
+// above 2×; the captured input takes the same trips through a halfband
+// cascade instead (see `CascadeDelay`).
+//
+// The delay ring only moves while a module is listened to, so it holds
+// whatever the last listen left behind. `select` clears it whenever the
+// listened module changes, including the off → on edge, so the first block
+// doesn't subtract stale audio.
+//
+// All storage is allocated in `new()`.
+
+use crate::oversampler::{CascadeDelay, Oversampler, MAX_OS_STAGES};
+use crate::ModuleType;
+
+/// Channels captured: the chain's stereo pair. On a 5.1 bus that is the
+/// front group, the one the listen taps follow.
+const CHANNELS: usize = 2;
+
+/// Most trips through a cascade one module makes: the Transformer's input
+/// and output stages.
+const MAX_PASSES: usize = 2;
+
+pub struct DeltaTap {
+    /// The slot's (delayed) input, then the difference.
+    delta: [Vec<f32>; CHANNELS],
+    /// Ring buffers that delay the captured input.
+    delay: [Vec<f32>; CHANNELS],
+    delay_pos: usize,
+    /// Cascades the captured input runs through to match the module's.
+    align: [[Oversampler; MAX_PASSES]; CHANNELS],
+    cascade: CascadeDelay,
+    /// Samples and channels in `delta` this block; 0 when nothing was
+    /// captured (the module isn't in the rack, or the block didn't fit).
+    len: usize,
+    channels: usize,
+    /// Module the tap was last selected for.
+    listened: Option<ModuleType>,
+}
+
+impl DeltaTap {
+    /// Room for blocks of up to `max_block` samples and module latency of
+    /// up to `max_delay` samples.
+    pub fn new(max_block: usize, max_delay: usize) -> Self {
+        Self {
+            delta: [vec![0.0; max_block], vec![0.0; max_block]],
+            delay: [vec![0.0; max_delay + 1], vec![0.0; max_delay + 1]],
+            delay_pos: 0,
+            align: std::array::from_fn(|_| {
+                std::array::from_fn(|_| Oversampler::new(1 << MAX_OS_STAGES, 1))
+            }),
+            cascade: CascadeDelay::NONE,
+            len: 0,
+            channels: 0,
+            listened: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for ring in &mut self.delay {
+            ring.fill(0.0);
+        }
+        for os in self.align.iter_mut().flatten() {
+            os.reset();
+        }
+        self.delay_pos = 0;
+        self.len = 0;
+    }
+
+    /// Call once per block with the module delta listen is set to; starts
+    /// from a clean ring whenever that changes.
+    pub fn select(&mut self, module: Option<ModuleType>) {
+        if module != self.listened {
+            self.listened = module;
+            self.reset();
+        }
+    }
+
+    /// Copy the slot's input, delayed by the module's latency in samples.
+    pub fn capture(&mut self, input: &[&mut [f32]], latency: usize) {
+        let samples = input.first().map_or(0, |ch| ch.len());
+        let ring_len = self.delay[0].len();
+        if samples > self.delta[0].len() || latency >= ring_len {
+            self.len = 0;
+            return;
+        }
+        self.channels = input.len().min(CHANNELS);
+        self.len = samples;
+        for (ch, samples) in input.iter().take(self.channels).enumerate() {
+            let mut pos = self.delay_pos;
+            for (out, &x) in self.delta[ch].iter_mut().zip(samples.iter()) {
+                self.delay[ch][pos] = x;
+                *out = self.delay[ch][(pos + ring_len - latency) % ring_len];
+                pos = (pos + 1) % ring_len;
+            }
+        }
+        self.delay_pos = (self.delay_pos + samples) % ring_len;
+    }
+
+    /// Turn the captured input into `output − input` once the module ran,
+    /// the input first taking the module's current `cascade` trips.
+    pub fn subtract_from(&mut self, output: &[&mut [f32]], cascade: CascadeDelay) {
+        if cascade != self.cascade {
+            self.cascade = cascade;
+            for os in self.align.iter_mut().flatten() {
+                os.set_factor(cascade.factor);
+                os.reset();
+            }
+        }
+        let passes = cascade.passes.min(MAX_PASSES);
+        let channels = self.delta.iter_mut().zip(&mut self.align).zip(output);
+        for ((delta, align), out) in channels.take(self.channels) {
+            for (d, &y) in delta[..self.len].iter_mut().zip(out.iter()) {
+                let x = align[..passes]
+                    .iter_mut()
+                    .fold(*d, |x, os| os.round_trip(x));
+                *d = y - x;
+            }
+        }
+    }
+
+    /// Replace `output` with the difference, or silence when the module
+    /// didn't run this block. A mono capture feeds every channel; channels
+    /// past a stereo capture (the rest of a 5.1 bus) fall silent. Clears
+    /// the tap for the next block.
+    pub fn replace(&mut self, output: &mut [&mut [f32]]) {
+        for (ch, out) in output.iter_mut().enumerate() {
+            let source = if self.channels == 1 { 0 } else { ch };
+            if self.len > 0 && self.len == out.len() && source < self.channels {
+                out.copy_from_slice(&self.delta[source][..self.len]);
+            } else {
+                out.fill(0.0);
+            }
+        }
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `module` on `left`/`right` with the tap around it; return what
+    /// delta listen would output.
+    fn listen(
+        tap: &mut DeltaTap,
+        left: &mut [f32],
+        right: &mut [f32],
+        latency: usize,
+        module: impl Fn(&mut [f32]),
+    ) -> Vec<Vec<f32>> {
+        let mut channels = [left, right];
+        tap.capture(&channels, latency);
+        for ch in channels.iter_mut() {
+            module(ch);
+        }
+        tap.subtract_from(&channels, CascadeDelay::NONE);
+        tap.replace(&mut channels);
+        channels.iter().map(|ch| ch.to_vec()).collect()
+    }
+
+    #[test]
+    fn delta_is_what_the_module_changed() {
+        let mut tap = DeltaTap::new(64, 0);
+        let mut l: Vec<f32> = (0..64).map(|i| (i as f32 * 0.1).sin()).collect();
+        let mut r = l.clone();
+        let input = l.clone();
+        let out = listen(&mut tap, &mut l, &mut r, 0, |ch| {
+            ch.iter_mut().for_each(|s| *s = s.clamp(-0.5, 0.5))
+        });
+        for (d, x) in out[0].iter().zip(&input) {
+            assert!((d - (x.clamp(-0.5, 0.5) - x)).abs() < 1e-6);
+        }
+        assert_eq!(out[0], out[1]);
+    }
+
+    #[test]
+    fn latency_is_compensated() {
+        // A pure delay module changes nothing once aligned.
+        const LATENCY: usize = 5;
+        let mut tap = DeltaTap::new(16, LATENCY);
+        let mut history = vec![0.0_f32; LATENCY];
+        for block in 0..4 {
+            let mut l: Vec<f32> = (0..16).map(|i| (block * 16 + i) as f32 + 1.0).collect();
+            let mut r = l.clone();
+            let delayed: Vec<f32> = history.iter().chain(&l).take(16).copied().collect();
+            history = l[16 - LATENCY..].to_vec();
+            let out = listen(&mut tap, &mut l, &mut r, LATENCY, |ch| {
+                ch.copy_from_slice(&delayed)
+            });
+            assert!(out.iter().flatten().all(|&d| d == 0.0), "block {block}");
+        }
+    }
+
+    #[test]
+    fn a_stereo_capture_plays_on_the_front_pair_only() {
+        let mut tap = DeltaTap::new(8, 0);
+        let (mut l, mut r) = (vec![1.0; 8], vec![1.0; 8]);
+        tap.capture(&[&mut l[..], &mut r[..]], 0);
+        tap.subtract_from(&[&mut [0.5; 8][..], &mut [0.5; 8][..]], CascadeDelay::NONE);
+        let mut bus = vec![vec![9.0; 8]; 6];
+        let mut out: Vec<&mut [f32]> = bus.iter_mut().map(|ch| &mut ch[..]).collect();
+        tap.replace(&mut out);
+        assert_eq!(
+            bus.iter().map(|ch| ch[0]).collect::<Vec<_>>(),
+            [-0.5, -0.5, 0.0, 0.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn nothing_captured_monitors_silence() {
+        let mut tap = DeltaTap::new(8, 0);
+        let (mut l, mut r) = (vec![1.0; 8], vec![1.0; 8]);
+        tap.replace(&mut [&mut l[..], &mut r[..]]);
+        assert!(l.iter().chain(&r).all(|&s| s == 0.0));
+
+        // Too long for the tap: skipped rather than truncated.
+        let (mut l, mut r) = (vec![1.0; 16], vec![1.0; 16]);
+        tap.capture(&[&mut l[..], &mut r[..]], 0);
+        tap.replace(&mut [&mut l[..], &mut r[..]]);
+        assert!(l.iter().chain(&r).all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn turning_listen_back_on_forgets_the_last_listen() {
+        const LATENCY: usize = 4;
+        let mut tap = DeltaTap::new(8, LATENCY);
+        tap.select(Some(ModuleType::Api5500EQ));
+        let (mut l, mut r) = (vec![1.0; 8], vec![1.0; 8]);
+        listen(&mut tap, &mut l, &mut r, LATENCY, |_| {});
+
+        // Off for a while, then on again with silence going in: the delayed
+        // input is silence too, not what was left in the ring.
+        tap.select(None);
+        tap.select(Some(ModuleType::Api5500EQ));
+        let (mut l, mut r) = (vec![0.0; 8], vec![0.0; 8]);
+        let out = listen(&mut tap, &mut l, &mut r, LATENCY, |_| {});
+        assert!(out.iter().flatten().all(|&d| d == 0.0));
+    }
+
+    /// Punch at 4× with nothing to do changes nothing once its 16.5-sample
+    /// halfband delay is matched; the 20 Hz wet HPF's phase shift is all
+    /// that's left up here.
+    #[cfg(feature = "punch")]
+    #[test]
+    fn oversampled_module_is_aligned() {
+        use crate::punch::{ClipMode, OversamplingFactor, PunchModule};
+
+        const SR: f32 = 48000.0;
+        const BLOCK: usize = 256;
+        let residual_db = |cascade: bool| {
+            let mut punch = PunchModule::new(SR);
+            punch.update_parameters(
+                0.0,
+                ClipMode::Hard,
+                0.0,
+                OversamplingFactor::X4,
+                0.0,
+                0.0,
+                5.0,
+                100.0,
+                0.5,
+                0.0,
+                0.0,
+                1.0,
+                20.0,
+            );
+            let mut tap = DeltaTap::new(BLOCK, 0);
+            let (mut input_sq, mut delta_sq) = (0.0, 0.0);
+            for block in 0..(SR as usize / BLOCK) {
+                let mut l: Vec<f32> = (0..BLOCK)
+                    .map(|i| {
+                        let t = (block * BLOCK + i) as f32 / SR;
+                        let tau = std::f32::consts::TAU;
+                        0.25 * ((tau * 5000.0 * t).sin() + (tau * 10000.0 * t).sin())
+                    })
+                    .collect();
+                let mut r = l.clone();
+                let input = l.clone();
+                let mut channels = [&mut l[..], &mut r[..]];
+                tap.capture(&channels, 0);
+                punch.process_channels(&mut channels);
+                let delay = if cascade {
+                    punch.cascade_delay()
+                } else {
+                    CascadeDelay::NONE
+                };
+                tap.subtract_from(&channels, delay);
+                tap.replace(&mut channels);
+                // Past the HPF's settling.
+                if block * BLOCK >= SR as usize / 2 {
+                    input_sq += input.iter().map(|x| x * x).sum::<f32>();
+                    delta_sq += l.iter().map(|d| d * d).sum::<f32>();
+                }
+            }
+            10.0 * (delta_sq / input_sq).log10()
+        };
+        let aligned = residual_db(true);
+        assert!(aligned < -40.0, "aligned residual {aligned:.1} dB");
+        let unaligned = residual_db(false);
+        assert!(unaligned > -10.0, "unaligned residual {unaligned:.1} dB");
+    }
+}
@@ -0,0 +1,209 @@
+// src/dither.rs
+// Output dither for printing stems straight out of the strip.
+//
+// The strip runs in float, but a stem printed at 16 or 24 bits gets
+// truncated by the host on the way to the file, and truncation error on
+// quiet fades and reverb tails is correlated with the signal: it sounds
+// like grit, not hiss. This stage quantises to the target word length
+// itself, with TPDF dither (two uniform draws, ±1 LSB peak) added first,
+// so the error becomes plain white noise. The host's own conversion then
+// lands on values already on its grid and changes nothing.
+//
+// Noise shaping feeds the quantisation error back through a 3-tap
+// E-weighted filter (Wannamaker's, tuned for 44.1/48 kHz), moving the
+// noise out of the 2–5 kHz region the ear is keenest on and up toward
+// Nyquist. Total noise power rises, perceived noise falls; leave it off
+// for stems that will be processed further.
+//
+// Each channel draws from its own generator, seeded apart, so the noise
+// is uncorrelated between L and R and doesn't image in the centre. Nothing
+// allocates after `new()`.
+
+use nih_plug::prelude::*;
+
+/// Word length the output is dithered and quantised to.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum DitherDepth {
+    #[name = "Off"]
+    Off,
+    #[name = "16-bit"]
+    Bits16,
+    #[name = "24-bit"]
+    Bits24,
+}
+
+impl DitherDepth {
+    /// Quantisation step at full scale ±1.0; `None` when off.
+    pub fn lsb(self) -> Option<f32> {
+        match self {
+            DitherDepth::Off => None,
+            DitherDepth::Bits16 => Some(1.0 / 32_768.0),
+            DitherDepth::Bits24 => Some(1.0 / 8_388_608.0),
+        }
+    }
+}
+
+/// Error-feedback taps: the shaped noise spectrum is |1 − H(z)|².
+const SHAPING: [f32; 3] = [1.623, -0.982, 0.109];
+/// Channels with their own generator and error history, enough for 5.1.
+const CHANNELS: usize = 6;
+/// Generator seeds, one per channel, so no two channels share noise.
+const SEEDS: [u32; CHANNELS] = [
+    0x9E37_79B9,
+    0x85EB_CA6B,
+    0xC2B2_AE35,
+    0x27D4_EB2F,
+    0x1656_67B1,
+    0xD3A2_646C,
+];
+
+struct ChannelDither {
+    /// xorshift32 state; never zero.
+    rng: u32,
+    /// Last three total errors (output − shaped input), newest first.
+    error: [f32; 3],
+}
+
+impl ChannelDither {
+    /// Uniform draw in [−0.5, 0.5).
+    #[inline]
+    fn uniform(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng >> 8) as f32 / (1u32 << 24) as f32 - 0.5
+    }
+
+    #[inline]
+    fn process(&mut self, x: f32, lsb: f32, shaped: bool) -> f32 {
+        let target = if shaped {
+            x - SHAPING
+                .iter()
+                .zip(self.error.iter())
+                .map(|(h, e)| h * e)
+                .sum::<f32>()
+        } else {
+            x
+        };
+        let tpdf = (self.uniform() + self.uniform()) * lsb;
+        let y = ((target + tpdf) / lsb).round() * lsb;
+        if shaped {
+            self.error = [y - target, self.error[0], self.error[1]];
+        }
+        y
+    }
+}
+
+pub struct Dither {
+    channels: [ChannelDither; CHANNELS],
+}
+
+impl Dither {
+    pub fn new() -> Self {
+        Self {
+            channels: SEEDS.map(|rng| ChannelDither {
+                rng,
+                error: [0.0; 3],
+            }),
+        }
+    }
+
+    /// Dither and quantise in place to `depth`. Channels past the sixth
+    /// pass through.
+    pub fn process(&mut self, channels: &mut [&mut [f32]], depth: DitherDepth, shaped: bool) {
+        let Some(lsb) = depth.lsb() else {
+            return;
+        };
+        for (state, channel) in self.channels.iter_mut().zip(channels.iter_mut()) {
+            for sample in channel.iter_mut() {
+                *sample = state.process(*sample, lsb, shaped);
+            }
+        }
+    }
+
+    /// Clear the error history. The generators keep running: restarting
+    /// them would repeat the same noise after every reset.
+    pub fn reset(&mut self) {
+        for channel in &mut self.channels {
+            channel.error = [0.0; 3];
+        }
+    }
+}
+
+impl Default for Dither {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LSB16: f32 = 1.0 / 32_768.0;
+
+    /// Dither `len` samples of `level` on both channels.
+    fn run(level: f32, len: usize, shaped: bool) -> [Vec<f32>; 2] {
+        let mut dither = Dither::new();
+        let (mut l, mut r) = (vec![level; len], vec![level; len]);
+        dither.process(&mut [&mut l[..], &mut r[..]], DitherDepth::Bits16, shaped);
+        [l, r]
+    }
+
+    fn noise(out: &[f32], level: f32) -> Vec<f32> {
+        out.iter().map(|y| (y - level) / LSB16).collect()
+    }
+
+    #[test]
+    fn test_output_lands_on_the_grid() {
+        for shaped in [false, true] {
+            for ch in run(0.123_456, 4096, shaped) {
+                for y in ch {
+                    let steps = y / LSB16;
+                    assert!((steps - steps.round()).abs() < 1e-3, "{y} off grid");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tpdf_noise_is_unbiased_white_and_bounded() {
+        let level = 0.3 * LSB16;
+        let [l, _] = run(level, 65_536, false);
+        let n = noise(&l, level);
+        let mean = n.iter().sum::<f32>() / n.len() as f32;
+        // TPDF removes the quantiser's bias: the mean tracks the input.
+        assert!(mean.abs() < 0.02, "mean {mean}");
+        // Rounding plus ±1 LSB of dither never lands further than 1.5 away.
+        assert!(n.iter().all(|e| e.abs() <= 1.5 + 1e-3));
+        let lag1 = n.windows(2).map(|w| w[0] * w[1]).sum::<f32>() / n.len() as f32;
+        assert!(lag1.abs() < 0.02, "lag-1 {lag1}");
+    }
+
+    #[test]
+    fn test_channels_draw_independent_noise() {
+        let [l, r] = run(0.0, 65_536, false);
+        let corr = l.iter().zip(&r).map(|(a, b)| a * b).sum::<f32>()
+            / (l.iter().map(|a| a * a).sum::<f32>() * r.iter().map(|b| b * b).sum::<f32>()).sqrt();
+        assert!(corr.abs() < 0.02, "L/R correlation {corr}");
+    }
+
+    #[test]
+    fn test_shaping_pushes_noise_up_the_spectrum() {
+        let level = 0.3 * LSB16;
+        let [l, _] = run(level, 65_536, true);
+        let n = noise(&l, level);
+        // Negative lag-1 correlation: the noise alternates, i.e. sits high.
+        let power = n.iter().map(|e| e * e).sum::<f32>();
+        let lag1 = n.windows(2).map(|w| w[0] * w[1]).sum::<f32>();
+        assert!(lag1 / power < -0.3, "lag-1 {}", lag1 / power);
+    }
+
+    #[test]
+    fn test_off_leaves_audio_alone() {
+        let mut dither = Dither::new();
+        let mut block = vec![0.123_456_7; 64];
+        dither.process(&mut [&mut block[..]], DitherDepth::Off, true);
+        assert!(block.iter().all(|&x| x == 0.123_456_7));
+    }
+}
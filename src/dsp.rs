@@ -0,0 +1,61 @@
+//! The strip's DSP modules as a plain library, free of the plugin wrapper.
+//!
+//! Each module is a struct built for one sample rate, configured through
+//! `update_parameters` and run in place over a block given as per-channel
+//! slices (`&mut [&mut [f32]]`, left then right; a lone channel takes the
+//! mono path). Nothing here allocates or locks while processing, so the
+//! same calls that run inside the plugin can run inside another plugin, an
+//! offline renderer or a wasm demo.
+//!
+//! Each module sits behind its cargo feature (`api5500`, `pultec`,
+//! `transformer`, `punch`, `dynamic_eq`, `conditioning`); the shaping
+//! primitives are always there. None of them needs the `plugin` feature:
+//!
+//! ```toml
+//! bus_channel_strip = { version = "1", default-features = false, features = ["pultec"] }
+//! ```
+//!
+//! ```ignore
+//! use bus_channel_strip::dsp::PultecEQ;
+//!
+//! let mut eq = PultecEQ::new(48_000.0);
+//! eq.update_parameters(
+//!     60.0, 6.0, 0.5, 60.0, 3.0, 0.5, 8000.0, 5.0, 0.5, 10000.0, 2.0, 0.4,
+//! );
+//! eq.process_channels(&mut [&mut left[..], &mut right[..]]);
+//! ```
+//!
+//! Two modes hand heavy work to a second thread, as the plugin does from its
+//! background task executor: the API 5500's linear-phase mode needs its FIR
+//! designed by an [`EqFirWorker`], and the Pultec's print mode needs its tube
+//! curve printed by a [`PrintWorker`]. Both modes are off until switched on
+//! (`set_linear_phase`, `set_printed`), so a caller without a spare thread
+//! can ignore them.
+
+#[cfg(feature = "api5500")]
+pub use crate::api5500::{Api5500, EqFirShared, EqFirWorker};
+#[cfg(any(feature = "api5500", feature = "pultec"))]
+pub use crate::character::EqCharacter;
+
+#[cfg(feature = "pultec")]
+pub use crate::print::{
+    print_channel, CurveReader, LoadMonitor, PrintShared, PrintWorker, StaticCurve,
+};
+#[cfg(feature = "pultec")]
+pub use crate::pultec::{tube_transfer, PultecEQ};
+
+#[cfg(feature = "conditioning")]
+pub use crate::conditioning::{InputConditioning, Polarity, Slope};
+
+#[cfg(feature = "transformer")]
+pub use crate::transformer::{TransformerModel, TransformerModule};
+
+#[cfg(feature = "punch")]
+pub use crate::punch::{ClipMode, OversamplingFactor, PunchModule};
+
+#[cfg(feature = "dynamic_eq")]
+pub use crate::dynamic_eq::{DynamicBandParams, DynamicEQ, DynamicMode};
+#[cfg(feature = "dynamic_eq")]
+pub use crate::key_eq::{KeyEq, KeyEqSettings};
+
+pub use crate::shaping::{biquad_coeffs, biquad_coeffs_f64, shaping_fns, Filter, FilterType, Svf};
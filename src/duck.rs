@@ -0,0 +1,244 @@
+// src/duck.rs
+// MIDI-triggered ducking.
+//
+// Some hosts can't route an audio sidechain into a bus insert at all. A
+// MIDI note can usually get there: a note-on matching the chosen channel
+// and note fires a gain envelope (attack down to the set depth, hold,
+// release back to unity) at a chosen slot boundary, so a kick pattern on a
+// MIDI track can duck a bass or pad bus with nothing else routed.
+//
+// Triggers land on the sample their note-on carries when the chain runs
+// on host buffers. With a fixed block size the chain runs a block behind
+// the host buffer, so a trigger fires at the start of the next block the
+// chain runs, within one block of its note.
+
+use nih_plug::prelude::*;
+
+/// Slot boundary the envelope applies at. Input sits after the input
+/// conditioning, so every slot sees the ducked signal; Output is after
+/// Sheen and master gain.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum DuckPoint {
+    #[name = "Input"]
+    Input,
+    #[name = "After Slot 1"]
+    Slot1,
+    #[name = "After Slot 2"]
+    Slot2,
+    #[name = "After Slot 3"]
+    Slot3,
+    #[name = "After Slot 4"]
+    Slot4,
+    #[name = "After Slot 5"]
+    Slot5,
+    #[name = "After Slot 6"]
+    Slot6,
+    #[name = "After Slot 7"]
+    Slot7,
+    #[name = "Output"]
+    Output,
+}
+
+/// Boundary after Sheen and master gain.
+pub const OUTPUT_BOUNDARY: usize = 8;
+
+impl DuckPoint {
+    /// Boundary index, counted like the analyzer tap's: 0 is the input, N
+    /// follows slot N, then `OUTPUT_BOUNDARY`.
+    pub fn boundary(self) -> usize {
+        match self {
+            DuckPoint::Input => 0,
+            DuckPoint::Slot1 => 1,
+            DuckPoint::Slot2 => 2,
+            DuckPoint::Slot3 => 3,
+            DuckPoint::Slot4 => 4,
+            DuckPoint::Slot5 => 5,
+            DuckPoint::Slot6 => 6,
+            DuckPoint::Slot7 => 7,
+            DuckPoint::Output => OUTPUT_BOUNDARY,
+        }
+    }
+}
+
+/// True when a note-on on `channel` (0–15) with `note` should fire the
+/// envelope. `want_channel` is 1–16, or 0 for any channel.
+pub fn matches(want_channel: i32, want_note: i32, channel: u8, note: u8) -> bool {
+    (want_channel == 0 || want_channel == channel as i32 + 1) && want_note == note as i32
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DuckSettings {
+    /// Gain at full duck, dB (≤ 0).
+    pub depth_db: f32,
+    pub attack_ms: f32,
+    pub hold_ms: f32,
+    pub release_ms: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Stage {
+    Idle,
+    Attack,
+    Hold,
+    Release,
+}
+
+pub struct DuckEnvelope {
+    sample_rate: f32,
+    stage: Stage,
+    /// How far down the envelope is: 0 at unity, 1 at full depth.
+    amount: f32,
+    /// Samples of hold left.
+    hold_left: usize,
+    /// Offset into the next block of a trigger waiting to fire.
+    pending: Option<usize>,
+}
+
+impl DuckEnvelope {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            stage: Stage::Idle,
+            amount: 0.0,
+            hold_left: 0,
+            pending: None,
+        }
+    }
+
+    /// Fire at `offset` samples into the next block processed. A second
+    /// trigger before then keeps the earlier one.
+    pub fn trigger(&mut self, offset: usize) {
+        self.pending = Some(self.pending.map_or(offset, |p| p.min(offset)));
+    }
+
+    /// Apply the envelope to one block in place. A retrigger mid-envelope
+    /// attacks from wherever the envelope is, so it never jumps.
+    pub fn process(&mut self, channels: &mut [&mut [f32]], settings: DuckSettings) {
+        let samples = channels.first().map_or(0, |ch| ch.len());
+        let start = self
+            .pending
+            .take()
+            .map(|offset| offset.min(samples.saturating_sub(1)));
+        if self.stage == Stage::Idle && start.is_none() {
+            return;
+        }
+        let depth = 1.0 - util::db_to_gain(settings.depth_db.min(0.0));
+        let attack_step = self.step(settings.attack_ms);
+        let release_step = self.step(settings.release_ms);
+        let hold = (settings.hold_ms.max(0.0) * 0.001 * self.sample_rate) as usize;
+        for i in 0..samples {
+            if start == Some(i) {
+                self.stage = Stage::Attack;
+            }
+            match self.stage {
+                Stage::Idle => {}
+                Stage::Attack => {
+                    self.amount = (self.amount + attack_step).min(1.0);
+                    if self.amount >= 1.0 {
+                        self.stage = Stage::Hold;
+                        self.hold_left = hold;
+                    }
+                }
+                Stage::Hold => {
+                    if self.hold_left == 0 {
+                        self.stage = Stage::Release;
+                    } else {
+                        self.hold_left -= 1;
+                    }
+                }
+                Stage::Release => {
+                    self.amount = (self.amount - release_step).max(0.0);
+                    if self.amount <= 0.0 {
+                        self.stage = Stage::Idle;
+                    }
+                }
+            }
+            let gain = 1.0 - self.amount * depth;
+            for channel in channels.iter_mut() {
+                channel[i] *= gain;
+            }
+        }
+    }
+
+    /// False while idle with no trigger waiting: the next block would
+    /// leave the audio alone.
+    pub fn is_active(&self) -> bool {
+        self.stage != Stage::Idle || self.pending.is_some()
+    }
+
+    /// Per-sample change of `amount` for a linear ramp of `ms`.
+    fn step(&self, ms: f32) -> f32 {
+        1.0 / (ms * 0.001 * self.sample_rate).max(1.0)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sample_rate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+    const SETTINGS: DuckSettings = DuckSettings {
+        depth_db: -12.0,
+        attack_ms: 1.0,
+        hold_ms: 10.0,
+        release_ms: 20.0,
+    };
+
+    /// Gain the envelope applies over `blocks` blocks of `len` samples, with
+    /// a trigger `offset` samples into the first.
+    fn run(offset: usize, blocks: usize, len: usize) -> Vec<f32> {
+        let mut duck = DuckEnvelope::new(SR);
+        duck.trigger(offset);
+        let mut out = Vec::new();
+        for _ in 0..blocks {
+            let mut block = vec![1.0; len];
+            duck.process(&mut [&mut block[..]], SETTINGS);
+            out.extend(block);
+        }
+        out
+    }
+
+    #[test]
+    fn test_envelope_attacks_holds_and_releases() {
+        let gain = run(0, 40, 64);
+        let floor = util::db_to_gain(-12.0);
+        // 1 ms attack (48 samples), 10 ms hold, 20 ms release.
+        assert!((gain[47] - floor).abs() < 1e-4, "attack end {}", gain[47]);
+        assert!((gain[47 + 480] - floor).abs() < 1e-4, "hold {}", gain[527]);
+        assert!(gain[48 + 480 + 480] > floor && gain[48 + 480 + 480] < 1.0);
+        assert_eq!(gain[48 + 480 + 960 + 2], 1.0);
+        assert_eq!(*gain.last().unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_trigger_lands_on_its_sample() {
+        let gain = run(100, 1, 256);
+        assert!(gain[..100].iter().all(|&g| g == 1.0));
+        assert!(gain[100] < 1.0);
+    }
+
+    #[test]
+    fn test_idle_envelope_leaves_audio_alone() {
+        let mut duck = DuckEnvelope::new(SR);
+        assert!(!duck.is_active());
+        let mut block = vec![0.5; 64];
+        duck.process(&mut [&mut block[..]], SETTINGS);
+        assert!(block.iter().all(|&x| x == 0.5));
+        duck.trigger(0);
+        assert!(duck.is_active());
+    }
+
+    #[test]
+    fn test_note_filter() {
+        // Any channel, C1 (36).
+        assert!(matches(0, 36, 9, 36));
+        assert!(!matches(0, 36, 9, 38));
+        // Channel 10 only: MIDI channel index 9.
+        assert!(matches(10, 36, 9, 36));
+        assert!(!matches(10, 36, 0, 36));
+    }
+}
@@ -1,28 +1,42 @@
 // src/dynamic_eq.rs — 4-band dynamic equalizer
 //
 // Key design decisions:
-//   - BiquadPeak replaces biquad::DirectForm1 everywhere so filter state
-//     is never reset when coefficients change (DirectForm1::new() zeroed state).
-//   - The sidechain detection filter is a BiquadPeak running in
+//   - Every filter is a `shaping::Svf` (TPT state-variable filter). Its state
+//     is integrator memory, so the EQ bell's gain follows the gain computer
+//     every sample with no zipper and no state glitch; coefficient updates
+//     never reset state.
+//   - The sidechain detection filter is an Svf running in
 //     constant-0-dB-peak bandpass mode so out-of-band energy is rejected
 //     rather than leaking through at unity gain (a +6 dB peaking EQ used
 //     previously passed all out-of-band content, biasing detection toward
 //     low-frequency broadband energy).
 //   - Envelope detection uses a denormal guard (max with f32::MIN_POSITIVE)
 //     before log10() to prevent -inf / NaN when the signal is silent.
-//   - Solo mode routes only the soloed band(s) through a RBJ bandpass filter
+//   - Solo mode routes only the soloed band(s) through a bandpass filter
 //     so the user can isolate exactly the frequency range being processed.
-
+//   - Each band can put a key EQ (HP, bell, LP; see key_eq.rs) ahead of its
+//     detection filter. It works on the signed mono key rather than the
+//     rectified linked level, so it shapes the key's real spectrum.
+//   - Detection is stereo-linked by default. Unlinked (dual mono), each
+//     channel detects on its own input or key channel through its own BPF,
+//     key EQ channel and envelope, and sets its own bell gain.
+//   - Detection steps once per sample at the rate the EQ was built for, and
+//     every time constant (RMS window, attack, release) goes through
+//     envelope.rs against that rate. The EQ never oversamples, and the block
+//     scheduler only changes how many samples a call carries, so ballistics
+//     set in ms hold at any rate and any block size.
+//   - The Range/Sensitivity control law derives each band's threshold and
+//     ratio from a range in dB and a 0–1 sensitivity, and caps the band's
+//     gain change at the range, so a band never moves further than asked.
+
+use crate::envelope::{Detector, EnvelopeFollower};
+use crate::key_eq::{KeyEq, KeyEqSettings, HP_OFF_HZ};
+use crate::shaping::Svf;
+#[cfg(feature = "plugin")]
 use nih_plug::buffer::Buffer;
+#[cfg(feature = "plugin")]
 use nih_plug::prelude::Enum;
 
-// Denormal flush threshold. IIR filters and envelope followers asymptote to
-// zero through the subnormal range (|x| < ~1.18e-38 on f32), which on x86
-// without FTZ costs ~100x the normal multiply latency. Flushing any state
-// below this threshold to zero eliminates the stall while introducing an
-// error well below any audible level.
-const DENORMAL_FLUSH: f32 = 1.0e-20;
-
 // RMS integration window for sidechain detection. 10 ms is a conventional
 // trade-off: long enough to smooth out transient spikes that would cause
 // peak-style pumping, short enough that the envelope's attack/release can
@@ -36,6 +50,12 @@ const RMS_WINDOW_MS: f32 = 10.0;
 // dynamic processing.
 const KNEE_WIDTH_DB: f32 = 6.0;
 
+// Most boost upward expansion can apply. The slope (ratio - 1) reaches 19
+// at 20:1, so a loud band 60 dB over threshold would otherwise ask for
+// +1140 dB — past f32 range once the bell squares its amplitude. The Gate's
+// -96 dB floor is the same guard on the other side.
+pub(crate) const MAX_EXPANSION_DB: f32 = 24.0;
+
 /// Soft-knee gain computer (Reiss 2012). Given the detector's dB-over-threshold
 /// value, the mode, and the compression ratio, returns the gain change in dB
 /// (negative = attenuation, positive = upward expansion). The transition region
@@ -44,7 +64,7 @@ const KNEE_WIDTH_DB: f32 = 6.0;
 fn compute_gain_change_db(over_db: f32, mode: DynamicMode, ratio: f32) -> f32 {
     let half_knee = KNEE_WIDTH_DB * 0.5;
     match mode {
-        DynamicMode::CompressDownward => {
+        DynamicMode::CompressDownward | DynamicMode::SpectralDuck => {
             let slope = 1.0 - 1.0 / ratio;
             if over_db <= -half_knee {
                 0.0
@@ -60,10 +80,10 @@ fn compute_gain_change_db(over_db: f32, mode: DynamicMode, ratio: f32) -> f32 {
             if over_db <= -half_knee {
                 0.0
             } else if over_db >= half_knee {
-                slope * over_db
+                (slope * over_db).min(MAX_EXPANSION_DB)
             } else {
                 let x = over_db + half_knee;
-                slope * x * x / (2.0 * KNEE_WIDTH_DB)
+                (slope * x * x / (2.0 * KNEE_WIDTH_DB)).min(MAX_EXPANSION_DB)
             }
         }
         DynamicMode::Gate => {
@@ -80,123 +100,6 @@ fn compute_gain_change_db(over_db: f32, mode: DynamicMode, ratio: f32) -> f32 {
     }
 }
 
-#[inline(always)]
-fn flush_denormal(x: f32) -> f32 {
-    if x.abs() < DENORMAL_FLUSH {
-        0.0
-    } else {
-        x
-    }
-}
-
-// ── Stateful biquad ──────────────────────────────────────────────────────────
-//
-// Both the EQ and sidechain filters use this struct. Coefficient fields
-// (b0‥a2) are updated in-place without touching the state fields (x1,x2,y1,y2).
-
-struct BiquadPeak {
-    b0: f32,
-    b1: f32,
-    b2: f32,
-    a1: f32,
-    a2: f32,
-    x1: f32,
-    x2: f32,
-    y1: f32,
-    y2: f32,
-}
-
-impl BiquadPeak {
-    fn new() -> Self {
-        // Identity (flat): b0=1, all others 0.
-        Self {
-            b0: 1.0,
-            b1: 0.0,
-            b2: 0.0,
-            a1: 0.0,
-            a2: 0.0,
-            x1: 0.0,
-            x2: 0.0,
-            y1: 0.0,
-            y2: 0.0,
-        }
-    }
-
-    /// RBJ Cookbook peaking EQ — updates coefficients, preserves state.
-    fn update_peaking(&mut self, freq_hz: f32, q: f32, gain_db: f32, sample_rate: f32) {
-        let freq_hz = freq_hz.clamp(20.0, sample_rate * 0.49);
-        let q = q.max(0.1);
-        let a = 10.0f32.powf(gain_db / 40.0); // sqrt of linear gain
-        let w0 = std::f32::consts::TAU * freq_hz / sample_rate;
-        let cos_w0 = w0.cos();
-        let alpha = w0.sin() / (2.0 * q);
-        let inv_a0 = 1.0 / (1.0 + alpha / a);
-        self.b0 = (1.0 + alpha * a) * inv_a0;
-        self.b1 = (-2.0 * cos_w0) * inv_a0;
-        self.b2 = (1.0 - alpha * a) * inv_a0;
-        self.a1 = (-2.0 * cos_w0) * inv_a0;
-        self.a2 = (1.0 - alpha / a) * inv_a0;
-    }
-
-    /// RBJ Cookbook constant-0-dB-peak bandpass — updates coefficients, preserves state.
-    /// Peak gain is exactly 1.0 at `freq_hz` regardless of Q, so the detected level
-    /// equals the actual signal energy in the band. Out-of-band content falls off
-    /// at ~6 dB/octave * Q. Used for sidechain detection so the envelope follower
-    /// is not contaminated by broadband low-frequency energy.
-    fn update_bandpass_unity(&mut self, freq_hz: f32, q: f32, sample_rate: f32) {
-        let freq_hz = freq_hz.clamp(20.0, sample_rate * 0.49);
-        let q = q.max(0.1);
-        let w0 = std::f32::consts::TAU * freq_hz / sample_rate;
-        let cos_w0 = w0.cos();
-        let sin_w0 = w0.sin();
-        let alpha = sin_w0 / (2.0 * q);
-        let inv_a0 = 1.0 / (1.0 + alpha);
-        self.b0 = alpha * inv_a0;
-        self.b1 = 0.0;
-        self.b2 = -alpha * inv_a0;
-        self.a1 = (-2.0 * cos_w0) * inv_a0;
-        self.a2 = (1.0 - alpha) * inv_a0;
-    }
-
-    /// RBJ Cookbook constant-skirt-gain bandpass — updates coefficients, preserves state.
-    /// Used for solo band-isolation mode.
-    fn update_bandpass(&mut self, freq_hz: f32, q: f32, sample_rate: f32) {
-        let freq_hz = freq_hz.clamp(20.0, sample_rate * 0.49);
-        let q = q.max(0.1);
-        let w0 = std::f32::consts::TAU * freq_hz / sample_rate;
-        let cos_w0 = w0.cos();
-        let sin_w0 = w0.sin();
-        let alpha = sin_w0 / (2.0 * q);
-        let inv_a0 = 1.0 / (1.0 + alpha);
-        self.b0 = (sin_w0 / 2.0) * inv_a0;
-        self.b1 = 0.0;
-        self.b2 = -(sin_w0 / 2.0) * inv_a0;
-        self.a1 = (-2.0 * cos_w0) * inv_a0;
-        self.a2 = (1.0 - alpha) * inv_a0;
-    }
-
-    /// Direct Form 1 — processes one sample.
-    #[inline]
-    fn process(&mut self, x0: f32) -> f32 {
-        let mut y0 = self.b0 * x0 + self.b1 * self.x1 + self.b2 * self.x2
-            - self.a1 * self.y1
-            - self.a2 * self.y2;
-        y0 = flush_denormal(y0);
-        self.x2 = self.x1;
-        self.x1 = x0;
-        self.y2 = self.y1;
-        self.y1 = y0;
-        y0
-    }
-
-    fn reset(&mut self) {
-        self.x1 = 0.0;
-        self.x2 = 0.0;
-        self.y1 = 0.0;
-        self.y2 = 0.0;
-    }
-}
-
 // ── DynamicMode ───────────────────────────────────────────────────────────────
 
 /// Dynamic processing mode for a single band. The display labels are chosen
@@ -206,7 +109,8 @@ impl BiquadPeak {
 /// - `CompressDownward` — reduce gain of signal *above* threshold.
 ///   Like a classic compressor, but applied per-band via the peaking EQ.
 /// - `ExpandUpward` — boost gain of signal *above* threshold.
-///   Accentuates the band when the band's content is loud.
+///   Accentuates the band when the band's content is loud. The boost is
+///   capped at +24 dB.
 /// - `Gate` — reduce gain of signal *below* threshold (downward expansion).
 ///   Despite the name, this is a ratio-controlled downward expander, not a
 ///   hard-mute gate: with ratio=1.5 it is a subtle expander, with ratio=20
@@ -214,14 +118,22 @@ impl BiquadPeak {
 ///   -96 dB so extreme silence doesn't push the peaking EQ into numerical
 ///   corner cases. No hold or hysteresis — it responds purely to the
 ///   instantaneous envelope level through the attack/release smoother.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+/// - `SpectralDuck` — compress downward on the *external sidechain's*
+///   energy in the band rather than the band's own. The detection filter
+///   picks the key's range (say 2–5 kHz of a voiceover) and the bell carves
+///   the same range out of the program while the key is loud. Independent of
+///   the module's KEY choice; with no sidechain connected the band stays flat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "plugin", derive(Enum))]
 pub enum DynamicMode {
-    #[name = "Compress Down"]
+    #[cfg_attr(feature = "plugin", name = "Compress Down")]
     CompressDownward,
-    #[name = "Expand Up"]
+    #[cfg_attr(feature = "plugin", name = "Expand Up")]
     ExpandUpward,
-    #[name = "Gate"]
+    #[cfg_attr(feature = "plugin", name = "Gate")]
     Gate,
+    #[cfg_attr(feature = "plugin", name = "Spectral Duck")]
+    SpectralDuck,
 }
 
 impl Default for DynamicMode {
@@ -230,27 +142,68 @@ impl Default for DynamicMode {
     }
 }
 
+// ── ControlLaw ────────────────────────────────────────────────────────────────
+
+// Sensitivity maps linearly onto a threshold from -6 dBFS (0 %) down to
+// -54 dBFS (100 %): the upper end leaves only peaks over it, the lower end
+// catches a band sitting well under typical program level.
+const SENSITIVITY_TOP_DB: f32 = -6.0;
+const SENSITIVITY_SPAN_DB: f32 = 48.0;
+
+// Level over (or, for the Gate, under) threshold at which the derived slope
+// alone reaches the full range. The clamp then holds the band there.
+const RANGE_SPAN_DB: f32 = 12.0;
+
+// Steepest derived downward slope, 20:1 — the top of the RATIO control.
+const MAX_RANGE_SLOPE: f32 = 0.95;
+
+/// How the bands' gain computers are set. `ThresholdRatio` is the classic
+/// compressor-style pair; `RangeSensitivity` asks for the most a band may
+/// move and how readily it starts, and works out threshold and ratio itself
+/// (see `RangeLaw`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "plugin", derive(Enum))]
+pub enum ControlLaw {
+    #[cfg_attr(feature = "plugin", name = "Threshold/Ratio")]
+    ThresholdRatio,
+    #[cfg_attr(feature = "plugin", name = "Range/Sensitivity")]
+    RangeSensitivity,
+}
+
+impl Default for ControlLaw {
+    fn default() -> Self {
+        ControlLaw::ThresholdRatio
+    }
+}
+
 // ── DynamicBand ───────────────────────────────────────────────────────────────
 
 struct DynamicBand {
-    // Filters (all BiquadPeak — state persists across buffer boundaries).
+    // Filters (all Svf — state persists across buffer boundaries).
     // Detection is mono (one BPF fed a linked-from-stereo signal); EQ and solo
     // filters are duplicated per channel so left and right maintain independent
-    // biquad state while receiving identical coefficients. Without the per-
+    // filter state while receiving identical coefficients. Without the per-
     // channel split the same struct would see interleaved L/R samples and its
     // state would corrupt both channels' outputs.
-    sidechain_filter: BiquadPeak, // mono detection: unity-peak BPF
-    eq_filter_l: BiquadPeak,
-    eq_filter_r: BiquadPeak,
-    solo_filter_l: BiquadPeak,
-    solo_filter_r: BiquadPeak,
-
-    // Detection (mono, shared across channels for linked GR)
-    rms_state: f32, // one-pole lowpass state on squared bandpass output
-    rms_coeff: f32, // smoothing coefficient for the RMS integrator
-    envelope: f32,  // peak-follower state driven by sqrt(rms_state)
+    sidechain_filter: Svf,   // mono detection: unity-peak BPF
+    sidechain_filter_r: Svf, // right channel's, when unlinked
+    key_eq: KeyEq,           // mono, ahead of the detection BPF when active
+    listen_filter: Svf,      // key listen: the detection BPF on the signed key
+    eq_filter_l: Svf,
+    eq_filter_r: Svf,
+    solo_filter_l: Svf,
+    solo_filter_r: Svf,
+
+    // Detection (mono, shared across channels for linked GR): RMS of the
+    // bandpass output through attack/release ballistics.
+    envelope: EnvelopeFollower,
     pub gain_reduction_db: f32,
-    last_gain_change_db: f32, // hysteresis cache — avoids per-sample trig recompute
+    eq_gain_db: f32, // bell gain the EQ filters are currently set to
+    // Unlinked, the detection above follows the left channel and these the
+    // right; each channel's bell takes its own gain.
+    linked: bool,
+    envelope_r: EnvelopeFollower,
+    eq_gain_db_r: f32,
 
     // Cached parameter values (updated per-buffer, used per-sample)
     sample_rate: f32,
@@ -260,36 +213,49 @@ struct DynamicBand {
     q: f32,
     threshold_db: f32, // stored directly in dB (no round-trip conversion)
     ratio: f32,
-    attack_coeff: f32,
-    release_coeff: f32,
+    // Set under the Range/Sensitivity law; replaces threshold and ratio above.
+    range_law: Option<RangeLaw>,
     make_up_gain: f32, // linear gain
     enabled: bool,
     solo: bool,
+    // Attack ms, release ms and make-up dB behind the coefficients above;
+    // NaN until the first update so it always computes.
+    cached_timing: [f32; 3],
 }
 
 impl DynamicBand {
     fn new(sample_rate: f32) -> Self {
-        let mut sidechain_filter = BiquadPeak::new();
-        sidechain_filter.update_bandpass_unity(1000.0, 1.0, sample_rate);
+        let mut sidechain_filter = Svf::new();
+        sidechain_filter.set_band_pass_unity(sample_rate, 1000.0, 1.0);
+        let listen_filter = sidechain_filter.clone();
+
+        let mut eq_filter_l = Svf::new();
+        eq_filter_l.set_bell(sample_rate, 1000.0, 1.0, 0.0);
+        let eq_filter_r = eq_filter_l.clone();
 
-        let mut solo_filter_l = BiquadPeak::new();
-        let mut solo_filter_r = BiquadPeak::new();
-        solo_filter_l.update_bandpass(1000.0, 1.0, sample_rate);
-        solo_filter_r.update_bandpass(1000.0, 1.0, sample_rate);
+        let mut solo_filter_l = Svf::new();
+        solo_filter_l.set_band_pass(sample_rate, 1000.0, 1.0);
+        let solo_filter_r = solo_filter_l.clone();
 
-        let rms_coeff = (-1.0 / (RMS_WINDOW_MS * 0.001 * sample_rate)).exp();
+        let envelope = EnvelopeFollower::new(sample_rate, 0.0, 0.0).with_detector(Detector::Rms {
+            window_ms: RMS_WINDOW_MS,
+        });
 
         Self {
+            sidechain_filter_r: sidechain_filter.clone(),
             sidechain_filter,
-            eq_filter_l: BiquadPeak::new(),
-            eq_filter_r: BiquadPeak::new(),
+            key_eq: KeyEq::new(sample_rate),
+            listen_filter,
+            eq_filter_l,
+            eq_filter_r,
             solo_filter_l,
             solo_filter_r,
-            rms_state: 0.0,
-            rms_coeff,
-            envelope: 0.0,
+            envelope_r: envelope.clone(),
+            envelope,
             gain_reduction_db: 0.0,
-            last_gain_change_db: 0.0,
+            eq_gain_db: 0.0,
+            linked: true,
+            eq_gain_db_r: 0.0,
             sample_rate,
             mode: DynamicMode::default(),
             detector_freq: 1000.0,
@@ -297,11 +263,11 @@ impl DynamicBand {
             q: 1.0,
             threshold_db: -18.0,
             ratio: 4.0,
-            attack_coeff: 0.0,
-            release_coeff: 0.0,
+            range_law: None,
             make_up_gain: 1.0,
             enabled: true,
             solo: false,
+            cached_timing: [f32::NAN; 3],
         }
     }
 
@@ -320,67 +286,131 @@ impl DynamicBand {
         solo: bool,
     ) {
         self.mode = mode;
-        self.detector_freq = detector_freq;
-        self.frequency = frequency;
-        self.q = q;
         self.threshold_db = threshold_db; // direct dB — no mapping needed
         self.ratio = ratio;
-        let sr = self.sample_rate;
-        // Standard exponential-decay IIR attack/release coefficients.
-        self.attack_coeff = (-1.0 / (attack_ms.max(0.01) * 0.001 * sr)).exp();
-        self.release_coeff = (-1.0 / (release_ms.max(0.01) * 0.001 * sr)).exp();
-        // RMS coefficient is derived from a fixed 10 ms window; recomputed here
-        // in case sample_rate changes between calls (cheap, runs once per buffer).
-        self.rms_coeff = (-1.0 / (RMS_WINDOW_MS * 0.001 * sr)).exp();
-        self.make_up_gain = 10.0f32.powf(make_up_gain_db / 20.0);
         self.enabled = enabled;
         self.solo = solo;
+        let sr = self.sample_rate;
 
-        // Update sidechain detection filter — state preserved, no reset.
-        // Unity-peak bandpass: detection level == actual in-band signal level,
-        // without pollution from out-of-band content like a peaking EQ would leak.
-        self.sidechain_filter
-            .update_bandpass_unity(detector_freq, q, sr);
-
-        // Update solo bandpass filters (L and R) for this band's center
-        // frequency. Both channels receive identical coefficients — only state
-        // diverges with input.
-        self.solo_filter_l.update_bandpass(frequency, q, sr);
-        self.solo_filter_r.update_bandpass(frequency, q, sr);
+        // Called every buffer: the exp()/powf()/trig below only rerun when
+        // their inputs changed. Exact comparison is fine — these are the
+        // host's parameter values, not computed results. The RMS window
+        // depends on the sample rate alone and is fixed in `new()`.
+        let timing = [attack_ms, release_ms, make_up_gain_db];
+        if timing != self.cached_timing {
+            self.cached_timing = timing;
+            self.envelope
+                .set_times(attack_ms.max(0.01), release_ms.max(0.01));
+            self.envelope_r
+                .set_times(attack_ms.max(0.01), release_ms.max(0.01));
+            self.make_up_gain = 10.0f32.powf(make_up_gain_db / 20.0);
+        }
+
+        // `new()` designed the filters for the initial detector_freq /
+        // frequency / q, so the stored values double as the cache.
+        let q_changed = q != self.q;
+        self.q = q;
+        if q_changed || detector_freq != self.detector_freq {
+            self.detector_freq = detector_freq;
+            // Update sidechain detection filter — state preserved, no reset.
+            // Unity-peak bandpass: detection level == actual in-band signal level,
+            // without pollution from out-of-band content like a peaking EQ would leak.
+            self.sidechain_filter
+                .set_band_pass_unity(sr, detector_freq, q);
+            self.sidechain_filter_r
+                .set_band_pass_unity(sr, detector_freq, q);
+            self.listen_filter.set_band_pass_unity(sr, detector_freq, q);
+        }
+        if q_changed || frequency != self.frequency {
+            self.frequency = frequency;
+            // Update the EQ bells and solo bandpass filters (L and R) for this
+            // band's center frequency. Both channels receive identical
+            // coefficients — only state diverges with input. The bells keep
+            // whatever gain the gain computer last set.
+            self.eq_filter_l.set_bell(sr, frequency, q, self.eq_gain_db);
+            self.eq_filter_r
+                .set_bell(sr, frequency, q, self.eq_gain_db_r);
+            self.solo_filter_l.set_band_pass(sr, frequency, q);
+            self.solo_filter_r.set_band_pass(sr, frequency, q);
+        }
     }
 
     /// Update the sidechain envelope from a detection input. This is called
     /// with the **module input** (not the inter-band cascade signal) so that
     /// band N's detection is not contaminated by EQ applied in bands 0..N-1.
+    /// `ch` 0 is the linked (or left) detector, 1 the right one.
     ///
     /// Detection chain:
     ///   BPF → square → RMS lowpass (10 ms) → sqrt → attack/release smoother.
     /// RMS integration replaces peak-style abs() to avoid the harsh transient
     /// pumping that peak detectors produce on program material.
-    fn update_envelope(&mut self, detection_input: f32) {
+    fn update_envelope(&mut self, detection_input: f32, ch: usize) {
         if !self.enabled {
             return;
         }
-        let sc = self.sidechain_filter.process(detection_input);
-        let sc_sq = sc * sc;
-        self.rms_state = sc_sq + (self.rms_state - sc_sq) * self.rms_coeff;
-        self.rms_state = flush_denormal(self.rms_state);
-        let det = self.rms_state.max(0.0).sqrt();
-
-        if det > self.envelope {
-            self.envelope = det + (self.envelope - det) * self.attack_coeff;
+        if ch == 0 {
+            let sc = self.sidechain_filter.process(detection_input);
+            self.envelope.process(sc);
+        } else {
+            let sc = self.sidechain_filter_r.process(detection_input);
+            self.envelope_r.process(sc);
+        }
+    }
+
+    /// Feed detector `ch` one sample and return what it hears ahead of the
+    /// BPF. `signed` is the mono key (the sidechain for Spectral Duck) and
+    /// `rectified` the stereo-linked level the other modes detect on; an
+    /// unlinked detector gets its own channel for both. With the key EQ in
+    /// circuit those modes detect on the rectified key EQ output instead.
+    fn detect(&mut self, signed: f32, rectified: f32, ch: usize) -> f32 {
+        if !self.key_eq.is_active() {
+            if self.mode == DynamicMode::SpectralDuck {
+                self.update_envelope(signed, ch);
+            } else {
+                self.update_envelope(rectified, ch);
+            }
+            return signed;
+        }
+        let keyed = self.key_eq.run(signed, ch);
+        if self.mode == DynamicMode::SpectralDuck {
+            self.update_envelope(keyed, ch);
         } else {
-            self.envelope = det + (self.envelope - det) * self.release_coeff;
+            self.update_envelope(keyed.abs(), ch);
+        }
+        keyed
+    }
+
+    /// Link detection across the channels (the default) or split it. The
+    /// right detector starts from the shared one, so unlinking doesn't step.
+    fn set_linked(&mut self, linked: bool) {
+        if self.linked && !linked {
+            self.sidechain_filter_r = self.sidechain_filter.clone();
+            self.envelope_r = self.envelope.clone();
+        }
+        self.linked = linked;
+    }
+
+    /// Bell gain, in dB, the gain computer asks for at `envelope`'s level.
+    fn gain_change_db(&self, envelope: &EnvelopeFollower) -> f32 {
+        // Guard: max with MIN_POSITIVE prevents log10(0) = -inf → NaN / Gate explosion.
+        let envelope_db = 20.0 * envelope.value().max(f32::MIN_POSITIVE).log10();
+        match self.range_law {
+            None => compute_gain_change_db(envelope_db - self.threshold_db, self.mode, self.ratio),
+            Some(law) => {
+                let over_db = envelope_db - law.threshold_db();
+                compute_gain_change_db(over_db, self.mode, law.ratio(self.mode))
+                    .clamp(-law.range_db, law.range_db)
+            }
         }
-        self.envelope = flush_denormal(self.envelope);
     }
 
     /// Compute the dynamic gain from the current envelope and apply the peaking
-    /// EQ + makeup gain to both L and R channels. The same gain change is used
-    /// for both channels so stereo image is preserved — hence the shared
-    /// envelope state that lives on `self`. Coefficients are recomputed once
-    /// per hysteresis trip and written to both L and R biquad instances; state
-    /// remains per-channel so the filters don't corrupt each other.
+    /// EQ + makeup gain to both L and R channels. Linked, the same gain change
+    /// is used for both channels so stereo image is preserved — hence the
+    /// shared envelope state that lives on `self`; unlinked, each channel's
+    /// bell follows its own envelope. The bell gain is written to the filters
+    /// every sample it changes; state remains per-channel so the filters don't
+    /// corrupt each other.
     ///
     /// `l`/`r` are the **cascade signals** from the previous band's apply_eq
     /// (or the dry module input for band 0).
@@ -390,34 +420,31 @@ impl DynamicBand {
         }
 
         // Gain computation in dB.
-        // Guard: max with MIN_POSITIVE prevents log10(0) = -inf → NaN / Gate explosion.
-        let envelope_db = 20.0 * self.envelope.max(f32::MIN_POSITIVE).log10();
-        let over_db = envelope_db - self.threshold_db;
-
-        let gain_change_db = compute_gain_change_db(over_db, self.mode, self.ratio);
+        let gain_l = self.gain_change_db(&self.envelope);
+        let gain_r = if self.linked {
+            gain_l
+        } else {
+            self.gain_change_db(&self.envelope_r)
+        };
+        // Unlinked, the meter shows whichever channel moves further.
+        let gain_change_db = if gain_r.abs() > gain_l.abs() {
+            gain_r
+        } else {
+            gain_l
+        };
         self.gain_reduction_db = -gain_change_db;
 
-        // Update EQ coefficients only when gain changes significantly.
-        // update_peaking() runs cos()/sin()/powf() — expensive transcendental math.
-        // With typical attack/release times, the envelope changes <0.025 dB/sample,
-        // so a 0.05 dB hysteresis threshold means we recompute every ~2 samples
-        // during active compression and never during silence — substantial savings
-        // with at most 0.05 dB of GR tracking error (inaudible).
-        const GR_HYSTERESIS_DB: f32 = 0.05;
-        if (gain_change_db - self.last_gain_change_db).abs() > GR_HYSTERESIS_DB {
-            self.eq_filter_l.update_peaking(
-                self.frequency,
-                self.q,
-                gain_change_db,
-                self.sample_rate,
-            );
-            self.eq_filter_r.update_peaking(
-                self.frequency,
-                self.q,
-                gain_change_db,
-                self.sample_rate,
-            );
-            self.last_gain_change_db = gain_change_db;
+        // Track the gain computer exactly. A gain-only update on the SVF is
+        // one exp() and no trig, and its integrator state carries straight
+        // across the change, so there is no hysteresis step to hide. A steady
+        // envelope (including silence) skips the update.
+        if gain_l != self.eq_gain_db {
+            self.eq_filter_l.set_bell_gain(gain_l);
+            self.eq_gain_db = gain_l;
+        }
+        if gain_r != self.eq_gain_db_r {
+            self.eq_filter_r.set_bell_gain(gain_r);
+            self.eq_gain_db_r = gain_r;
         }
 
         (
@@ -433,17 +460,21 @@ impl DynamicBand {
     /// directly with a linked detection input.
     #[cfg(test)]
     fn process_sample(&mut self, input: f32) -> f32 {
-        self.update_envelope(input);
+        self.update_envelope(input, 0);
         self.apply_eq_stereo(input, input).0
     }
 
     fn reset(&mut self) {
-        self.rms_state = 0.0;
-        self.envelope = 0.0;
+        self.envelope.reset();
+        self.envelope_r.reset();
         self.gain_reduction_db = 0.0;
-        self.last_gain_change_db = 0.0;
+        self.eq_gain_db = 0.0;
+        self.eq_gain_db_r = 0.0;
+        self.eq_filter_l.set_bell_gain(0.0);
+        self.eq_filter_r.set_bell_gain(0.0);
         self.eq_filter_l.reset();
         self.eq_filter_r.reset();
+        self.key_eq.reset();
         // Intentionally keep sidechain_filter and solo_filter state to avoid clicks.
     }
 }
@@ -451,7 +482,7 @@ impl DynamicBand {
 // ── Public API types ──────────────────────────────────────────────────────────
 
 /// Parameters for a single dynamic band, passed from lib.rs each buffer.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct DynamicBandParams {
     pub mode: DynamicMode,
     pub detector_freq: f32,
@@ -466,6 +497,32 @@ pub struct DynamicBandParams {
     pub solo: bool,
 }
 
+/// A band's settings under `ControlLaw::RangeSensitivity`. `range_db` is
+/// the most the band's bell may cut (or, expanding, boost); `sensitivity`
+/// runs 0–1 and sets how far below full scale it starts to act.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RangeLaw {
+    pub range_db: f32,
+    pub sensitivity: f32,
+}
+
+impl RangeLaw {
+    /// Threshold the sensitivity stands for, in dBFS.
+    pub fn threshold_db(&self) -> f32 {
+        SENSITIVITY_TOP_DB - SENSITIVITY_SPAN_DB * self.sensitivity.clamp(0.0, 1.0)
+    }
+
+    /// Ratio that reaches the full range `RANGE_SPAN_DB` past the threshold
+    /// in `mode`'s direction. A zero range is 1:1, so the band stays flat.
+    pub fn ratio(&self, mode: DynamicMode) -> f32 {
+        let slope = self.range_db.max(0.0) / RANGE_SPAN_DB;
+        match mode {
+            DynamicMode::ExpandUpward => 1.0 + slope,
+            _ => 1.0 / (1.0 - slope.min(MAX_RANGE_SLOPE)),
+        }
+    }
+}
+
 // ── DynamicEQ ─────────────────────────────────────────────────────────────────
 
 pub struct DynamicEQ {
@@ -502,7 +559,100 @@ impl DynamicEQ {
         }
     }
 
+    /// Link the bands' detection across the channels (the default) or give
+    /// each channel its own, for two unrelated mono sources on one track.
+    pub fn set_linked(&mut self, linked: bool) {
+        for band in &mut self.bands {
+            band.set_linked(linked);
+        }
+    }
+
+    /// Key EQ settings per band (see `key_eq.rs`); every band starts with
+    /// it out. A band with its key EQ disabled leaves the HP out as well:
+    /// unlike the compressor, the bands have no standalone SC HP.
+    pub fn set_key_eq(&mut self, settings: &[KeyEqSettings; 4]) {
+        for (band, s) in self.bands.iter_mut().zip(settings) {
+            let mut s = *s;
+            if !s.enabled {
+                s.hp_hz = HP_OFF_HZ;
+            }
+            band.key_eq.update_parameters(s);
+        }
+    }
+
+    /// Range/Sensitivity settings per band, or `None` throughout for the
+    /// threshold and ratio from `update_parameters`.
+    pub fn set_range_law(&mut self, laws: &[Option<RangeLaw>; 4]) {
+        for (band, law) in self.bands.iter_mut().zip(laws) {
+            band.range_law = *law;
+        }
+    }
+
+    #[cfg(feature = "plugin")]
     pub fn process(&mut self, buffer: &mut Buffer) {
+        self.process_channels(buffer.as_slice());
+    }
+
+    /// `process` with the bands detecting on `key` (see
+    /// `process_channels_keyed`) and Spectral Duck bands on `sidechain`.
+    #[cfg(feature = "plugin")]
+    pub fn process_sidechained(
+        &mut self,
+        buffer: &mut Buffer,
+        key: Option<&[&mut [f32]]>,
+        sidechain: Option<&[&mut [f32]]>,
+    ) {
+        self.process_channels_sidechained(buffer.as_slice(), key, sidechain);
+    }
+
+    /// Process one block given as per-channel slices (L, R, or mono).
+    pub fn process_channels(&mut self, channels: &mut [&mut [f32]]) {
+        self.process_channels_keyed(channels, None);
+    }
+
+    /// Process one block, detecting on `key` when given (any channel count;
+    /// samples past its end read as silence) and on the module input
+    /// otherwise. Only detection changes: the EQ still filters `channels`.
+    pub fn process_channels_keyed(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        key: Option<&[&mut [f32]]>,
+    ) {
+        self.process_channels_sidechained(channels, key, None);
+    }
+
+    /// `process_channels_keyed`, with `SpectralDuck` bands always detecting
+    /// on `sidechain` (silence when `None`) whatever the other bands use.
+    pub fn process_channels_sidechained(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        key: Option<&[&mut [f32]]>,
+        sidechain: Option<&[&mut [f32]]>,
+    ) {
+        self.run(channels, key, sidechain, None);
+    }
+
+    /// `process_channels_sidechained`, also writing what band `band`'s
+    /// detector hears (its key EQ and detection BPF on the signed key) to
+    /// `listen` for key listen. Samples past `listen`'s end aren't written.
+    pub fn process_channels_listening(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        key: Option<&[&mut [f32]]>,
+        sidechain: Option<&[&mut [f32]]>,
+        band: usize,
+        listen: &mut [f32],
+    ) {
+        self.run(channels, key, sidechain, Some((band, listen)));
+    }
+
+    fn run(
+        &mut self,
+        channels: &mut [&mut [f32]],
+        key: Option<&[&mut [f32]]>,
+        sidechain: Option<&[&mut [f32]]>,
+        mut listen: Option<(usize, &mut [f32])>,
+    ) {
         let any_solo = self.bands.iter().any(|b| b.solo && b.enabled);
         // Normalise solo level: sum of N band-limited signals ÷ N to avoid clipping.
         let solo_count = self
@@ -512,7 +662,6 @@ impl DynamicEQ {
             .count()
             .max(1) as f32;
 
-        let channels = buffer.as_slice();
         let num_channels = channels.len();
         if num_channels == 0 {
             return;
@@ -535,9 +684,63 @@ impl DynamicEQ {
             // triggers symmetrical gain reduction on both, preserving stereo
             // image. Detection always taps the dry module input so the cascade
             // of bands 0..N-1 can't starve or pump band N's detection.
-            let det_input = l_in.abs().max(r_in.abs());
-            for band in &mut self.bands {
-                band.update_envelope(det_input);
+            let linked = |sc: &[&mut [f32]]| {
+                sc.iter()
+                    .map(|ch| ch.get(i).map_or(0.0, |s| s.abs()))
+                    .fold(0.0, f32::max)
+            };
+            let det_input = match key {
+                Some(key) => linked(key),
+                None => l_in.abs().max(r_in.abs()),
+            };
+            // Spectral Duck measures the key's energy *in the band*, so its
+            // BPF gets the signed mono sum: rectifying first would fold the
+            // key's spectrum down to DC and up to its harmonics. The key EQ
+            // works on the same signed sum of the detection key.
+            let mono = |sc: &[&mut [f32]]| {
+                sc.iter()
+                    .map(|ch| ch.get(i).copied().unwrap_or(0.0))
+                    .sum::<f32>()
+                    / sc.len() as f32
+            };
+            let duck_input = sidechain.filter(|sc| !sc.is_empty()).map_or(0.0, mono);
+            let signed = match key {
+                Some(key) if !key.is_empty() => mono(key),
+                Some(_) => 0.0,
+                None => 0.5 * (l_in + r_in),
+            };
+            // Unlinked, each channel detects on its own side of the key (a
+            // mono key feeds both) or of the input.
+            let split = |sc: &[&mut [f32]]| {
+                let sample = |ch: usize| sc.get(ch).and_then(|k| k.get(i)).copied();
+                let l = sample(0).unwrap_or(0.0);
+                (l, sample(1).unwrap_or(l))
+            };
+            let (key_l, key_r) = key.map_or((l_in, r_in), split);
+            let (duck_l, duck_r) = sidechain.map_or((0.0, 0.0), split);
+            for (b, band) in self.bands.iter_mut().enumerate() {
+                let duck = band.mode == DynamicMode::SpectralDuck;
+                let heard = if band.linked {
+                    if duck {
+                        band.detect(duck_input, 0.0, 0)
+                    } else {
+                        band.detect(signed, det_input, 0)
+                    }
+                } else {
+                    let (l, r) = if duck {
+                        (duck_l, duck_r)
+                    } else {
+                        (key_l, key_r)
+                    };
+                    // Key listen plays the two detectors' mix.
+                    0.5 * (band.detect(l, l.abs(), 0) + band.detect(r, r.abs(), 1))
+                };
+                if let Some((_, out)) = listen.as_mut().filter(|(lb, _)| *lb == b) {
+                    let monitored = band.listen_filter.process(heard);
+                    if let Some(s) = out.get_mut(i) {
+                        *s = monitored;
+                    }
+                }
             }
 
             let (l_out, r_out) = if any_solo {
@@ -559,8 +762,8 @@ impl DynamicEQ {
                 }
                 (ol / solo_count, or_ / solo_count)
             } else {
-                // Normal mode: cascade EQs in series on each channel. Every
-                // band applies identical gain to L and R (single shared
+                // Normal mode: cascade EQs in series on each channel. Linked,
+                // every band applies identical gain to L and R (single shared
                 // envelope), so stereo image is preserved across the cascade.
                 let mut sl = l_in;
                 let mut sr = r_in;
@@ -599,162 +802,8 @@ impl DynamicEQ {
 mod tests {
     use super::*;
 
-    // ── BiquadPeak ────────────────────────────────────────────────────────────
-
-    #[test]
-    fn test_biquad_peak_identity_passthrough() {
-        let mut bq = BiquadPeak::new();
-        // Identity filter (b0=1, all others 0) should pass signal unchanged
-        for &input in &[0.0, 0.5, -0.5, 1.0, -1.0] {
-            let out = bq.process(input);
-            assert!(
-                (out - input).abs() < 1e-6,
-                "Identity passthrough: input={input}, out={out}"
-            );
-        }
-    }
-
-    #[test]
-    fn test_biquad_peak_reset_clears_state() {
-        let mut bq = BiquadPeak::new();
-        bq.update_peaking(1000.0, 1.0, 6.0, 44100.0);
-        for _ in 0..100 {
-            bq.process(1.0);
-        }
-        bq.reset();
-        assert!((bq.x1 - 0.0).abs() < 1e-9);
-        assert!((bq.x2 - 0.0).abs() < 1e-9);
-        assert!((bq.y1 - 0.0).abs() < 1e-9);
-        assert!((bq.y2 - 0.0).abs() < 1e-9);
-    }
-
-    #[test]
-    fn test_biquad_peak_update_peaking_does_not_clear_state() {
-        // State fields must survive a coefficient update (key design invariant)
-        let mut bq = BiquadPeak::new();
-        bq.update_peaking(1000.0, 1.0, 6.0, 44100.0);
-        for _ in 0..100 {
-            bq.process(0.7);
-        }
-        let y1_before = bq.y1;
-        bq.update_peaking(2000.0, 1.5, -3.0, 44100.0);
-        assert!(
-            (bq.y1 - y1_before).abs() < 1e-9,
-            "y1 state should survive coeff update"
-        );
-    }
-
-    #[test]
-    fn test_biquad_peak_nonzero_gain_changes_amplitude() {
-        let mut flat = BiquadPeak::new();
-        flat.update_peaking(1000.0, 1.0, 0.0, 44100.0);
-
-        let mut boosted = BiquadPeak::new();
-        boosted.update_peaking(1000.0, 1.0, 6.0, 44100.0);
-
-        // Warm up both with a DC signal
-        for _ in 0..1000 {
-            flat.process(0.5);
-            boosted.process(0.5);
-        }
-        let flat_out = flat.process(0.5);
-        let boosted_out = boosted.process(0.5);
-        // 6 dB boost at center freq — boosted should produce higher output
-        assert!(
-            boosted_out.abs() > flat_out.abs(),
-            "6 dB boost should increase amplitude"
-        );
-    }
-
-    #[test]
-    fn test_biquad_peak_produces_finite_output() {
-        let mut bq = BiquadPeak::new();
-        bq.update_peaking(20.0, 0.1, -60.0, 44100.0); // extreme params
-        for i in 0..200 {
-            let out = bq.process(if i % 2 == 0 { 1.0 } else { -1.0 });
-            assert!(
-                out.is_finite(),
-                "BiquadPeak output must be finite at sample {i}: {out}"
-            );
-        }
-    }
-
-    #[test]
-    fn test_biquad_bandpass_unity_rejects_out_of_band() {
-        // Verifies the detector shape fix: out-of-band content must be
-        // significantly attenuated relative to in-band content. The old
-        // +6 dB peaking detector passed out-of-band energy at 0 dB, which
-        // biased envelope detection toward broadband LF content.
-        let sr = 44100.0;
-        let detector_fc = 4000.0_f32;
-
-        let mut bp = BiquadPeak::new();
-        bp.update_bandpass_unity(detector_fc, 1.5, sr);
-
-        // Measure energy of a 100 Hz sine (8 kHz away from center) after detector
-        let mut low_peak = 0.0_f32;
-        for n in 0..8192 {
-            let phase = std::f32::consts::TAU * 100.0 * (n as f32) / sr;
-            let out = bp.process(phase.sin()).abs();
-            if n > 2048 && out > low_peak {
-                low_peak = out;
-            }
-        }
-
-        // Measure energy of a 4 kHz sine (at center) after detector
-        let mut bp2 = BiquadPeak::new();
-        bp2.update_bandpass_unity(detector_fc, 1.5, sr);
-        let mut center_peak = 0.0_f32;
-        for n in 0..8192 {
-            let phase = std::f32::consts::TAU * detector_fc * (n as f32) / sr;
-            let out = bp2.process(phase.sin()).abs();
-            if n > 2048 && out > center_peak {
-                center_peak = out;
-            }
-        }
-
-        assert!(
-            center_peak > 0.9,
-            "Center-frequency output should be ~unity, got {center_peak}"
-        );
-        assert!(
-            low_peak < 0.1 * center_peak,
-            "Out-of-band (100 Hz vs 4 kHz detector) must be <10% of center level, \
-             got low={low_peak}, center={center_peak}"
-        );
-    }
-
-    #[test]
-    fn test_biquad_bandpass_update_does_not_panic() {
-        let mut bq = BiquadPeak::new();
-        bq.update_bandpass(1000.0, 1.0, 44100.0);
-        bq.update_bandpass(500.0, 2.0, 48000.0);
-    }
-
-    #[test]
-    fn test_biquad_freq_clamping_to_nyquist() {
-        let sr = 44100.0;
-        let nyquist = sr * 0.49;
-        let mut bq = BiquadPeak::new();
-        // freq above Nyquist should be clamped — should not panic or produce NaN
-        bq.update_peaking(nyquist + 10000.0, 1.0, 3.0, sr);
-        let out = bq.process(0.5);
-        assert!(out.is_finite(), "Output after freq clamping: {out}");
-    }
-
     // ── DynamicBand ───────────────────────────────────────────────────────────
 
-    #[test]
-    fn test_flush_denormal_zeros_subthreshold() {
-        assert_eq!(flush_denormal(0.0), 0.0);
-        assert_eq!(flush_denormal(1e-25), 0.0);
-        assert_eq!(flush_denormal(-1e-25), 0.0);
-        // Values above threshold pass through unchanged
-        assert_eq!(flush_denormal(1.0e-15), 1.0e-15);
-        assert_eq!(flush_denormal(1.0), 1.0);
-        assert_eq!(flush_denormal(-0.5), -0.5);
-    }
-
     #[test]
     fn test_dynamic_band_envelope_flushes_to_zero_on_silence() {
         let sr = 44100.0_f32;
@@ -779,9 +828,9 @@ mod tests {
             band.process_sample(phase.sin());
         }
         assert!(
-            band.envelope > 0.1,
+            band.envelope.value() > 0.1,
             "Envelope should build up under sustained in-band excitation, got {}",
-            band.envelope
+            band.envelope.value()
         );
         // At 10 ms release, envelope reaches ~e^-500 after ~220k silent samples —
         // guaranteed to cross the DENORMAL_FLUSH threshold well before the end.
@@ -789,16 +838,17 @@ mod tests {
             band.process_sample(0.0);
         }
         assert_eq!(
-            band.envelope, 0.0,
+            band.envelope.value(),
+            0.0,
             "Envelope must flush to exactly zero under sustained silence, got {}",
-            band.envelope
+            band.envelope.value()
         );
     }
 
     #[test]
     fn test_dynamic_band_new_default_values() {
         let band = DynamicBand::new(44100.0);
-        assert!((band.envelope - 0.0).abs() < 1e-9);
+        assert!((band.envelope.value() - 0.0).abs() < 1e-9);
         assert!((band.gain_reduction_db - 0.0).abs() < 1e-9);
         assert!(band.enabled);
         assert!(!band.solo);
@@ -853,7 +903,7 @@ mod tests {
         }
         band.reset();
         assert!(
-            (band.envelope - 0.0).abs() < 1e-9,
+            (band.envelope.value() - 0.0).abs() < 1e-9,
             "Envelope should be 0 after reset"
         );
         assert!(
@@ -978,6 +1028,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_expand_upward_boost_is_capped() {
+        // 20:1 at 60 dB over would be +1140 dB uncapped.
+        let boost = compute_gain_change_db(60.0, DynamicMode::ExpandUpward, 20.0);
+        assert_eq!(boost, MAX_EXPANSION_DB);
+        // Below the cap the curve is untouched.
+        let mild = compute_gain_change_db(10.0, DynamicMode::ExpandUpward, 2.0);
+        assert!((mild - 10.0).abs() < 1e-4, "mild: {mild}");
+
+        let mut band = DynamicBand::new(44100.0);
+        band.update_parameters(
+            DynamicMode::ExpandUpward,
+            1000.0,
+            1000.0,
+            1.0,
+            -60.0,
+            20.0,
+            0.1,
+            100.0,
+            0.0,
+            true,
+            false,
+        );
+        for i in 0..4410 {
+            let x = (std::f32::consts::TAU * 1000.0 * i as f32 / 44100.0).sin();
+            let out = band.process_sample(x);
+            assert!(out.is_finite(), "sample {i}: {out}");
+        }
+    }
+
     #[test]
     fn test_soft_knee_gate_continuity() {
         // Gate: above threshold+knee → 0. Below threshold-knee → -slope * over_db.
@@ -1026,29 +1106,29 @@ mod tests {
         // Let the detector settle: >> attack, release, and RMS window combined.
         for n in 0..50_000 {
             let phase = std::f32::consts::TAU * 1000.0 * (n as f32) / sr;
-            band.update_envelope(phase.sin() * amp);
+            band.update_envelope(phase.sin() * amp, 0);
         }
         let expected_rms = amp / std::f32::consts::SQRT_2;
-        let relative_error = (band.envelope - expected_rms).abs() / expected_rms;
+        let relative_error = (band.envelope.value() - expected_rms).abs() / expected_rms;
         assert!(
             relative_error < 0.05,
             "Envelope should settle near A/sqrt(2) = {expected_rms:.4}, got {:.4} \
              (relative error {:.3})",
-            band.envelope,
+            band.envelope.value(),
             relative_error
         );
         // And it must NOT be near the peak amplitude (which is what a peak
         // detector would produce).
         assert!(
-            band.envelope < amp * 0.85,
+            band.envelope.value() < amp * 0.85,
             "Envelope {:.4} is too close to peak {amp}; detector looks peak-style",
-            band.envelope
+            band.envelope.value()
         );
     }
 
     #[test]
     fn test_dynamic_band_attack_coeff_formula() {
-        // attack_coeff = exp(-1 / (attack_ms * 0.001 * sr))
+        // attack coefficient = exp(-1 / (attack_ms * 0.001 * sr))
         let sr = 44100.0_f32;
         let attack_ms = 5.0_f32;
         let expected = (-1.0_f32 / (attack_ms * 0.001 * sr)).exp();
@@ -1067,13 +1147,129 @@ mod tests {
             false,
         );
         assert!(
-            (band.attack_coeff - expected).abs() < 1e-7,
+            (band.envelope.attack_coeff() - expected).abs() < 1e-7,
             "Attack coeff: {} vs expected {}",
-            band.attack_coeff,
+            band.envelope.attack_coeff(),
             expected
         );
     }
 
+    #[test]
+    fn test_dynamic_band_time_constants_hold_across_sample_rates() {
+        // 5 ms of samples decays the attack smoother to 1/e at any rate.
+        for sr in [44100.0_f32, 88200.0, 96000.0, 192000.0] {
+            let mut band = DynamicBand::new(sr);
+            band.update_parameters(
+                DynamicMode::CompressDownward,
+                1000.0,
+                1000.0,
+                1.0,
+                -18.0,
+                4.0,
+                5.0,
+                100.0,
+                0.0,
+                true,
+                false,
+            );
+            let decay = band.envelope.attack_coeff().powf(0.005 * sr);
+            assert!(
+                (decay - (-1.0_f32).exp()).abs() < 1e-3,
+                "attack decay {decay} at {sr}"
+            );
+        }
+    }
+
+    /// Band gain reduction once per millisecond over a 1 kHz burst: on for
+    /// 150 ms, then off for 150 ms.
+    fn burst_gr_per_ms(sr: f32) -> Vec<f32> {
+        let mut band = DynamicBand::new(sr);
+        band.update_parameters(
+            DynamicMode::CompressDownward,
+            1000.0,
+            1000.0,
+            1.0,
+            -24.0,
+            4.0,
+            10.0,
+            80.0,
+            0.0,
+            true,
+            false,
+        );
+        let burst = (0.15 * sr).round() as usize;
+        let mut gr = Vec::new();
+        let mut next_ms = 1;
+        for n in 0..2 * burst {
+            let x = if n < burst {
+                0.5 * (std::f32::consts::TAU * 1000.0 * n as f32 / sr).sin()
+            } else {
+                0.0
+            };
+            band.process_sample(x);
+            // The sample closest to the end of each millisecond.
+            if n + 1 == (next_ms as f32 * sr / 1000.0).round() as usize {
+                gr.push(band.gain_reduction_db);
+                next_ms += 1;
+            }
+        }
+        gr
+    }
+
+    #[test]
+    fn test_dynamic_band_ballistics_match_at_44k_and_96k() {
+        // Detection BPF, RMS window and attack/release all scale with the
+        // rate, so the gain reduction follows the same curve in time.
+        let gr_44 = burst_gr_per_ms(44100.0);
+        let gr_96 = burst_gr_per_ms(96000.0);
+        assert_eq!(gr_44.len(), gr_96.len());
+        let peak = gr_44.iter().copied().fold(0.0_f32, f32::max);
+        assert!(peak > 6.0, "burst should compress, peak GR {peak} dB");
+        for (ms, (a, b)) in gr_44.iter().zip(&gr_96).enumerate() {
+            assert!(
+                (a - b).abs() < 0.05 * peak,
+                "{ms} ms: {a} dB at 44.1k vs {b} dB at 96k"
+            );
+        }
+    }
+
+    #[test]
+    fn test_dynamic_band_update_recomputes_only_on_change() {
+        let mut band = DynamicBand::new(44100.0);
+        assert!(band.cached_timing.iter().all(|v| v.is_nan()));
+        let update = |band: &mut DynamicBand, freq: f32, attack_ms: f32| {
+            band.update_parameters(
+                DynamicMode::CompressDownward,
+                freq,
+                freq,
+                1.0,
+                -18.0,
+                4.0,
+                attack_ms,
+                100.0,
+                6.0,
+                true,
+                false,
+            );
+        };
+        update(&mut band, 1000.0, 5.0);
+        assert_eq!(band.cached_timing, [5.0, 100.0, 6.0]);
+        let solo = band.solo_filter_l.clone();
+
+        // Same values: coefficients stay as they were (a 0 ms follower
+        // stands in for a stale one).
+        band.envelope = EnvelopeFollower::new(44100.0, 0.0, 0.0);
+        update(&mut band, 1000.0, 5.0);
+        assert_eq!(band.envelope.attack_coeff(), 0.0);
+        assert_eq!(band.solo_filter_l, solo);
+
+        // A change recomputes.
+        update(&mut band, 4000.0, 10.0);
+        assert!(band.envelope.attack_coeff() > 0.0);
+        assert_ne!(band.solo_filter_l, solo);
+        assert_eq!(band.detector_freq, 4000.0);
+    }
+
     #[test]
     fn test_dynamic_band_process_produces_finite_output() {
         let mut band = DynamicBand::new(44100.0);
@@ -1128,6 +1324,45 @@ mod tests {
         deq.update_parameters(&params);
     }
 
+    #[test]
+    fn test_dynamic_eq_output_independent_of_block_size() {
+        // Detection steps per sample, so how the host or the block
+        // scheduler splits the signal must not change a single sample.
+        let sr = 48000.0_f32;
+        let params = [DynamicBandParams {
+            mode: DynamicMode::CompressDownward,
+            detector_freq: 1000.0,
+            freq: 1000.0,
+            q: 1.0,
+            threshold_db: -24.0,
+            ratio: 4.0,
+            attack_ms: 5.0,
+            release_ms: 60.0,
+            gain_db: 0.0,
+            enabled: true,
+            solo: false,
+        }; 4];
+        let input: Vec<f32> = (0..4800)
+            .map(|i| {
+                let env = if i < 2400 { 0.5 } else { 0.05 };
+                env * (std::f32::consts::TAU * 1000.0 * i as f32 / sr).sin()
+            })
+            .collect();
+        let run = |block: usize| {
+            let mut deq = DynamicEQ::new(sr);
+            let (mut l, mut r) = (input.clone(), input.clone());
+            for (l, r) in l.chunks_mut(block).zip(r.chunks_mut(block)) {
+                deq.update_parameters(&params);
+                deq.process_channels(&mut [l, r]);
+            }
+            l
+        };
+        let whole = run(input.len());
+        for block in [1, 64, 441, 512] {
+            assert_eq!(run(block), whole, "block size {block}");
+        }
+    }
+
     #[test]
     fn test_dynamic_eq_get_gain_reduction_db_initial() {
         let deq = DynamicEQ::new(44100.0);
@@ -1147,8 +1382,6 @@ mod tests {
         // band 2's detected level should be independent of whether band 1 is
         // heavily cutting or not. Verifies the invariant.
         let sr = 44100.0_f32;
-        use nih_plug::buffer::Buffer;
-
         let make_sine = |n: usize| {
             let l: Vec<f32> = (0..n)
                 .map(|i| (std::f32::consts::TAU * 1000.0 * (i as f32) / sr).sin() * 0.5)
@@ -1159,14 +1392,6 @@ mod tests {
 
         // Scenario A: band 0 disabled (no LF cut), band 1 detecting 1 kHz.
         let (mut l_a, mut r_a) = make_sine(512);
-        let mut buf_a = Buffer::default();
-        unsafe {
-            buf_a.set_slices(512, |ss| {
-                ss.clear();
-                ss.push(&mut l_a);
-                ss.push(&mut r_a);
-            });
-        }
         let mut deq_a = DynamicEQ::new(sr);
         let params_a = [
             DynamicBandParams {
@@ -1223,27 +1448,19 @@ mod tests {
             },
         ];
         deq_a.update_parameters(&params_a);
-        deq_a.process(&mut buf_a);
+        deq_a.process_channels(&mut [&mut l_a[..], &mut r_a[..]]);
         let gr_a = deq_a.get_gain_reduction_db()[1];
 
         // Scenario B: band 0 cutting hard at 100 Hz. Band 1 still sees the
         // full 1 kHz input because detection taps the dry signal.
         let (mut l_b, mut r_b) = make_sine(512);
-        let mut buf_b = Buffer::default();
-        unsafe {
-            buf_b.set_slices(512, |ss| {
-                ss.clear();
-                ss.push(&mut l_b);
-                ss.push(&mut r_b);
-            });
-        }
         let mut deq_b = DynamicEQ::new(sr);
         let mut params_b = params_a;
         params_b[0].enabled = true;
         params_b[0].threshold_db = -60.0; // cut aggressively at 100 Hz
         params_b[0].ratio = 20.0;
         deq_b.update_parameters(&params_b);
-        deq_b.process(&mut buf_b);
+        deq_b.process_channels(&mut [&mut l_b[..], &mut r_b[..]]);
         let gr_b = deq_b.get_gain_reduction_db()[1];
 
         // Band 1's gain reduction must be (essentially) identical in both
@@ -1264,8 +1481,6 @@ mod tests {
         // same coefficient trajectory over time. We verify this by running two
         // buffers side by side and comparing band GR + per-sample ratios.
         let sr = 44100.0_f32;
-        use nih_plug::buffer::Buffer;
-
         let n = 1024_usize;
         // L channel gets a 1 kHz sine at -6 dBFS; R channel is silent.
         // Without stereo linking, the detector would see nothing on R's frame
@@ -1275,15 +1490,6 @@ mod tests {
             .collect();
         let mut r: Vec<f32> = vec![0.0; n];
 
-        let mut buf = Buffer::default();
-        unsafe {
-            buf.set_slices(n, |ss| {
-                ss.clear();
-                ss.push(&mut l);
-                ss.push(&mut r);
-            });
-        }
-
         let mut deq = DynamicEQ::new(sr);
         let params = [
             DynamicBandParams {
@@ -1341,7 +1547,7 @@ mod tests {
             },
         ];
         deq.update_parameters(&params);
-        deq.process(&mut buf);
+        deq.process_channels(&mut [&mut l[..], &mut r[..]]);
 
         // Gain reduction is a single state shared between L and R — by
         // construction it's identical. What we want to verify is that the
@@ -1356,7 +1562,7 @@ mod tests {
 
         // R must stay near-silent (filter state shouldn't leak anything
         // because R input is zero). Per-channel EQ state means the L and R
-        // biquads are independent even with identical coefficients.
+        // filters are independent even with identical coefficients.
         let r_peak = r.iter().map(|s| s.abs()).fold(0.0_f32, f32::max);
         assert!(
             r_peak < 1e-3,
@@ -1369,17 +1575,9 @@ mod tests {
         let mut r2: Vec<f32> = (0..n)
             .map(|i| (std::f32::consts::TAU * 1000.0 * (i as f32) / sr).sin() * 0.5)
             .collect();
-        let mut buf2 = Buffer::default();
-        unsafe {
-            buf2.set_slices(n, |ss| {
-                ss.clear();
-                ss.push(&mut l2);
-                ss.push(&mut r2);
-            });
-        }
         let mut deq2 = DynamicEQ::new(sr);
         deq2.update_parameters(&params);
-        deq2.process(&mut buf2);
+        deq2.process_channels(&mut [&mut l2[..], &mut r2[..]]);
         let gr_swapped = deq2.get_gain_reduction_db()[0];
         assert!(
             (gr - gr_swapped).abs() < 0.1,
@@ -1387,17 +1585,341 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dynamic_eq_unlinked_channels_detect_on_their_own() {
+        // Hot 1 kHz on L, a quiet 1 kHz on R well under the threshold.
+        // Linked, L's level pulls R's bell down too; unlinked, R's bell
+        // stays flat while L's still compresses.
+        let sr = 44100.0_f32;
+        let n = 8192_usize;
+        let tone = |amp: f32| -> Vec<f32> {
+            (0..n)
+                .map(|i| (std::f32::consts::TAU * 1000.0 * (i as f32) / sr).sin() * amp)
+                .collect()
+        };
+        let band = |enabled: bool| DynamicBandParams {
+            mode: DynamicMode::CompressDownward,
+            detector_freq: 1000.0,
+            freq: 1000.0,
+            q: 1.0,
+            threshold_db: -30.0,
+            ratio: 4.0,
+            attack_ms: 1.0,
+            release_ms: 100.0,
+            gain_db: 0.0,
+            enabled,
+            solo: false,
+        };
+        let params = [band(true), band(false), band(false), band(false)];
+        let tail_gain = |out: &[f32], dry: &[f32]| {
+            let rms = |x: &[f32]| (x.iter().map(|s| s * s).sum::<f32>() / x.len() as f32).sqrt();
+            rms(&out[n / 2..]) / rms(&dry[n / 2..])
+        };
+        for linked in [true, false] {
+            let (dry_l, dry_r) = (tone(0.5), tone(0.005));
+            let (mut l, mut r) = (dry_l.clone(), dry_r.clone());
+            let mut deq = DynamicEQ::new(sr);
+            deq.update_parameters(&params);
+            deq.set_linked(linked);
+            deq.process_channels(&mut [&mut l[..], &mut r[..]]);
+            let (gain_l, gain_r) = (tail_gain(&l, &dry_l), tail_gain(&r, &dry_r));
+            assert!(gain_l < 0.5, "L not compressed: {gain_l}");
+            if linked {
+                assert!(gain_r < 0.5, "linked R should follow L: {gain_r}");
+            } else {
+                assert!((gain_r - 1.0).abs() < 0.01, "unlinked R moved: {gain_r}");
+            }
+            assert!(deq.get_gain_reduction_db()[0] > 6.0);
+        }
+    }
+
+    #[test]
+    fn external_key_drives_detection_instead_of_the_input() {
+        // A quiet 1 kHz program under a loud 1 kHz key: keyed, the band
+        // compresses; detecting on the program alone, it stays open.
+        let sr = 44100.0_f32;
+        let n = 4096_usize;
+        let sine = |amp: f32| -> Vec<f32> {
+            (0..n)
+                .map(|i| (std::f32::consts::TAU * 1000.0 * (i as f32) / sr).sin() * amp)
+                .collect()
+        };
+        let mut params = [DynamicBandParams {
+            mode: DynamicMode::CompressDownward,
+            detector_freq: 1000.0,
+            freq: 1000.0,
+            q: 1.0,
+            threshold_db: -24.0,
+            ratio: 4.0,
+            attack_ms: 1.0,
+            release_ms: 100.0,
+            gain_db: 0.0,
+            enabled: false,
+            solo: false,
+        }; 4];
+        params[0].enabled = true;
+
+        let mut key = sine(0.9);
+        let (mut l, mut r) = (sine(0.01), sine(0.01));
+        let mut keyed = DynamicEQ::new(sr);
+        keyed.update_parameters(&params);
+        keyed.process_channels_keyed(&mut [&mut l[..], &mut r[..]], Some(&[&mut key[..]]));
+
+        let (mut l, mut r) = (sine(0.01), sine(0.01));
+        let mut internal = DynamicEQ::new(sr);
+        internal.update_parameters(&params);
+        internal.process_channels(&mut [&mut l[..], &mut r[..]]);
+
+        let keyed_gr = keyed.get_gain_reduction_db()[0];
+        let internal_gr = internal.get_gain_reduction_db()[0];
+        assert!(
+            keyed_gr > 3.0,
+            "keyed band should compress; got {keyed_gr} dB"
+        );
+        assert!(
+            internal_gr < 0.1,
+            "program alone is under threshold; got {internal_gr} dB"
+        );
+    }
+
+    #[test]
+    fn key_eq_reshapes_what_the_detector_hears() {
+        // A 1 kHz program over threshold. Cutting 1 kHz out of the key with
+        // the key EQ's bell keeps the band from compressing.
+        let sr = 44100.0_f32;
+        let n = 8192_usize;
+        let sine = || -> Vec<f32> {
+            (0..n)
+                .map(|i| (std::f32::consts::TAU * 1000.0 * (i as f32) / sr).sin() * 0.5)
+                .collect()
+        };
+        let mut params = [DynamicBandParams {
+            mode: DynamicMode::CompressDownward,
+            detector_freq: 1000.0,
+            freq: 1000.0,
+            q: 1.0,
+            threshold_db: -40.0,
+            ratio: 4.0,
+            attack_ms: 1.0,
+            release_ms: 100.0,
+            gain_db: 0.0,
+            enabled: false,
+            solo: false,
+        }; 4];
+        params[0].enabled = true;
+
+        let gr_with = |key_eq: KeyEqSettings| {
+            let mut deq = DynamicEQ::new(sr);
+            deq.update_parameters(&params);
+            deq.set_key_eq(&[key_eq; 4]);
+            let (mut l, mut r) = (sine(), sine());
+            deq.process_channels(&mut [&mut l[..], &mut r[..]]);
+            deq.get_gain_reduction_db()[0]
+        };
+        let open = gr_with(KeyEqSettings::default());
+        let cut = gr_with(KeyEqSettings {
+            enabled: true,
+            bell_hz: 1000.0,
+            bell_gain_db: -30.0,
+            bell_q: 0.5,
+            ..KeyEqSettings::default()
+        });
+        assert!(open > 3.0, "plain key should compress; got {open} dB");
+        assert!(
+            cut < open - 3.0,
+            "key EQ cut should back off; {cut} vs {open} dB"
+        );
+    }
+
+    #[test]
+    fn range_law_derives_threshold_and_ratio() {
+        let law = |range_db, sensitivity| RangeLaw {
+            range_db,
+            sensitivity,
+        };
+        assert_eq!(law(6.0, 0.0).threshold_db(), -6.0);
+        assert_eq!(law(6.0, 1.0).threshold_db(), -54.0);
+        assert_eq!(law(6.0, 0.5).threshold_db(), -30.0);
+        assert!((law(6.0, 0.5).ratio(DynamicMode::CompressDownward) - 2.0).abs() < 1e-6);
+        assert!((law(6.0, 0.5).ratio(DynamicMode::ExpandUpward) - 1.5).abs() < 1e-6);
+        assert_eq!(law(0.0, 0.5).ratio(DynamicMode::Gate), 1.0);
+        assert!((law(24.0, 0.5).ratio(DynamicMode::CompressDownward) - 20.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn range_law_caps_the_band_at_its_range() {
+        // A 1 kHz sine far over the most sensitive threshold: the derived
+        // ratio alone would cut well past the range, the law holds it there.
+        let sr = 44100.0_f32;
+        let n = 8192_usize;
+        let mut params = [DynamicBandParams {
+            mode: DynamicMode::CompressDownward,
+            detector_freq: 1000.0,
+            freq: 1000.0,
+            q: 1.0,
+            threshold_db: -18.0,
+            ratio: 4.0,
+            attack_ms: 1.0,
+            release_ms: 100.0,
+            gain_db: 0.0,
+            enabled: false,
+            solo: false,
+        }; 4];
+        params[0].enabled = true;
+
+        for (mode, range_db) in [
+            (DynamicMode::CompressDownward, 4.0_f32),
+            (DynamicMode::ExpandUpward, 3.0),
+        ] {
+            params[0].mode = mode;
+            let mut deq = DynamicEQ::new(sr);
+            deq.update_parameters(&params);
+            let law = Some(RangeLaw {
+                range_db,
+                sensitivity: 1.0,
+            });
+            deq.set_range_law(&[law; 4]);
+            let mut l: Vec<f32> = (0..n)
+                .map(|i| (std::f32::consts::TAU * 1000.0 * (i as f32) / sr).sin() * 0.5)
+                .collect();
+            let mut r = l.clone();
+            deq.process_channels(&mut [&mut l[..], &mut r[..]]);
+            let moved = deq.get_gain_reduction_db()[0].abs();
+            assert!(
+                (moved - range_db).abs() < 1e-4,
+                "{mode:?} should sit at its {range_db} dB range; got {moved} dB"
+            );
+        }
+    }
+
+    #[test]
+    fn key_listen_taps_the_band_without_changing_the_output() {
+        let sr = 44100.0_f32;
+        let n = 2048_usize;
+        let noise = |seed: u32| -> Vec<f32> {
+            let mut x = seed;
+            (0..n)
+                .map(|_| {
+                    x ^= x << 13;
+                    x ^= x >> 17;
+                    x ^= x << 5;
+                    x as f32 / u32::MAX as f32 - 0.5
+                })
+                .collect()
+        };
+        let params = [DynamicBandParams {
+            mode: DynamicMode::CompressDownward,
+            detector_freq: 2000.0,
+            freq: 2000.0,
+            q: 2.0,
+            threshold_db: -30.0,
+            ratio: 4.0,
+            attack_ms: 1.0,
+            release_ms: 100.0,
+            gain_db: 0.0,
+            enabled: true,
+            solo: false,
+        }; 4];
+
+        let (mut l, mut r) = (noise(1), noise(2));
+        let mut plain = DynamicEQ::new(sr);
+        plain.update_parameters(&params);
+        plain.process_channels(&mut [&mut l[..], &mut r[..]]);
+
+        let (mut ll, mut lr) = (noise(1), noise(2));
+        let mut listen = vec![0.0; n];
+        let mut tapped = DynamicEQ::new(sr);
+        tapped.update_parameters(&params);
+        tapped.process_channels_listening(
+            &mut [&mut ll[..], &mut lr[..]],
+            None,
+            None,
+            2,
+            &mut listen,
+        );
+
+        assert_eq!(l, ll);
+        assert_eq!(r, lr);
+        // The band-passed key is quieter than the broadband program.
+        let rms = |x: &[f32]| (x.iter().map(|s| s * s).sum::<f32>() / x.len() as f32).sqrt();
+        let heard = rms(&listen);
+        assert!(heard > 0.0 && heard < rms(&l), "listen rms {heard}");
+    }
+
+    #[test]
+    fn spectral_duck_follows_the_sidechain_in_its_band_only() {
+        // A loud 3 kHz program. Band 1 ducks 3 kHz on the sidechain; band 2
+        // compresses 3 kHz on the program as usual.
+        let sr = 44100.0_f32;
+        let n = 4096_usize;
+        let sine = |freq: f32, amp: f32| -> Vec<f32> {
+            (0..n)
+                .map(|i| (std::f32::consts::TAU * freq * (i as f32) / sr).sin() * amp)
+                .collect()
+        };
+        let mut params = [DynamicBandParams {
+            mode: DynamicMode::CompressDownward,
+            detector_freq: 3000.0,
+            freq: 3000.0,
+            q: 1.0,
+            threshold_db: -24.0,
+            ratio: 4.0,
+            attack_ms: 1.0,
+            release_ms: 100.0,
+            gain_db: 0.0,
+            enabled: false,
+            solo: false,
+        }; 4];
+        params[0].mode = DynamicMode::SpectralDuck;
+        params[0].enabled = true;
+        params[1].enabled = true;
+
+        let run = |key: &mut Vec<f32>, connected: bool| -> [f32; 4] {
+            let (mut l, mut r) = (sine(3000.0, 0.5), sine(3000.0, 0.5));
+            let mut deq = DynamicEQ::new(sr);
+            deq.update_parameters(&params);
+            let sc: [&mut [f32]; 1] = [&mut key[..]];
+            let sidechain = if connected { Some(&sc[..]) } else { None };
+            deq.process_channels_sidechained(&mut [&mut l[..], &mut r[..]], None, sidechain);
+            deq.get_gain_reduction_db()
+        };
+
+        let voice = run(&mut sine(3000.0, 0.5), true);
+        assert!(
+            voice[0] > 3.0,
+            "key in band should duck; got {} dB",
+            voice[0]
+        );
+        assert!(
+            voice[1] > 0.5,
+            "program band still detects on the program; got {} dB",
+            voice[1]
+        );
+
+        let out_of_band = run(&mut sine(200.0, 0.5), true);
+        assert!(
+            out_of_band[0] < 0.1,
+            "key outside the band must not duck; got {} dB",
+            out_of_band[0]
+        );
+
+        let unconnected = run(&mut sine(3000.0, 0.5), false);
+        assert!(
+            unconnected[0] == 0.0,
+            "no sidechain, no ducking — the loud program doesn't count; got {} dB",
+            unconnected[0]
+        );
+    }
+
     #[test]
     fn test_dynamic_eq_stereo_channels_independent_filter_state() {
-        // Per-channel biquad state invariant: feeding DC + sine to L and R
+        // Per-channel filter state invariant: feeding DC + sine to L and R
         // channels through the same (bypassed) dynamic EQ must preserve both
-        // channels independently. This would fail if the same biquad struct
+        // channels independently. This would fail if the same filter struct
         // were shared across channels — interleaved L/R samples would
         // corrupt each other's state. With eq_filter_l / eq_filter_r split,
         // the channels are effectively two independent filter chains.
         let sr = 44100.0_f32;
-        use nih_plug::buffer::Buffer;
-
         let n = 256_usize;
         // L: 500 Hz sine at 0.25 amplitude. R: 2 kHz sine at 0.25 amplitude.
         let mut l: Vec<f32> = (0..n)
@@ -1411,15 +1933,6 @@ mod tests {
         let l_orig = l.clone();
         let r_orig = r.clone();
 
-        let mut buf = Buffer::default();
-        unsafe {
-            buf.set_slices(n, |ss| {
-                ss.clear();
-                ss.push(&mut l);
-                ss.push(&mut r);
-            });
-        }
-
         let mut deq = DynamicEQ::new(sr);
         // All bands disabled → no EQ applied, just a pass-through that still
         // exercises update_envelope and the channel read/write path.
@@ -1437,7 +1950,7 @@ mod tests {
             solo: false,
         };
         deq.update_parameters(&[disabled, disabled, disabled, disabled]);
-        deq.process(&mut buf);
+        deq.process_channels(&mut [&mut l[..], &mut r[..]]);
 
         // With every band disabled, apply_eq_stereo returns (l, r) unchanged.
         // Both channels must be bit-identical to their input.
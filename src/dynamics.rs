@@ -0,0 +1,241 @@
+// src/dynamics.rs
+// Dynamics statistics for the metering bridge, fed from the same taps as
+// the loudness meter so the numbers show how much the chain flattens the mix.
+//
+//   • crest factor — sample peak over RMS across the last 3 s
+//   • PSR          — 3 s sample peak over short-term loudness
+//   • PLR          — sample peak since reset over integrated loudness
+//   • spread       — loudest minus quietest 100 ms RMS block in the last
+//                    3 s (blocks under the floor are ignored)
+//
+// Peaks are sample peaks, not true peaks. Like the loudness meter, the
+// window is a fixed ring of 100 ms blocks, so nothing allocates after `new()`.
+
+use crate::loudness::LOUDNESS_FLOOR_LUFS;
+
+/// Blocks quieter than this (dBFS RMS) carry no dynamics reading.
+pub const DYNAMICS_FLOOR_DB: f32 = -70.0;
+
+const BLOCK_S: f32 = 0.1;
+/// 3 s, the short-term loudness window.
+const WINDOW_BLOCKS: usize = 30;
+
+#[derive(Clone, Copy, Default)]
+struct Block {
+    peak: f32,
+    mean_square: f32,
+}
+
+fn to_db(amplitude: f32) -> f32 {
+    20.0 * amplitude.max(1e-9).log10()
+}
+
+pub struct DynamicsMeter {
+    block_len: usize,
+    block_filled: usize,
+    block_peak: f32,
+    /// Sum of squares over every channel in the current block.
+    block_sum: f64,
+    block_values: usize,
+    blocks: [Block; WINDOW_BLOCKS],
+    block_pos: usize,
+    blocks_seen: usize,
+    /// Highest sample peak since the last reset, for PLR.
+    peak_hold: f32,
+}
+
+impl DynamicsMeter {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut meter = Self {
+            block_len: 1,
+            block_filled: 0,
+            block_peak: 0.0,
+            block_sum: 0.0,
+            block_values: 0,
+            blocks: [Block::default(); WINDOW_BLOCKS],
+            block_pos: 0,
+            blocks_seen: 0,
+            peak_hold: 0.0,
+        };
+        meter.set_sample_rate(sample_rate);
+        meter
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.block_len = ((sample_rate * BLOCK_S) as usize).max(1);
+        self.reset();
+    }
+
+    /// Clear the window and the peak hold.
+    pub fn reset(&mut self) {
+        self.block_filled = 0;
+        self.block_peak = 0.0;
+        self.block_sum = 0.0;
+        self.block_values = 0;
+        self.blocks = [Block::default(); WINDOW_BLOCKS];
+        self.block_pos = 0;
+        self.blocks_seen = 0;
+        self.reset_peak_hold();
+    }
+
+    /// Restart PLR's peak only; shares the bridge's integrated reset.
+    pub fn reset_peak_hold(&mut self) {
+        self.peak_hold = 0.0;
+    }
+
+    /// Feed one buffer. Channels beyond the first two are ignored.
+    pub fn process(&mut self, channels: &[&mut [f32]]) {
+        let num_channels = channels.len().min(2);
+        let Some(samples) = channels.first().map(|ch| ch.len()) else {
+            return;
+        };
+        for i in 0..samples {
+            for channel in &channels[..num_channels] {
+                let x = channel[i];
+                self.block_peak = self.block_peak.max(x.abs());
+                self.block_sum += (x as f64) * (x as f64);
+            }
+            self.block_values += num_channels;
+            self.block_filled += 1;
+            if self.block_filled >= self.block_len {
+                self.finish_block();
+            }
+        }
+    }
+
+    fn finish_block(&mut self) {
+        self.blocks[self.block_pos] = Block {
+            peak: self.block_peak,
+            mean_square: (self.block_sum / self.block_values.max(1) as f64) as f32,
+        };
+        self.block_pos = (self.block_pos + 1) % WINDOW_BLOCKS;
+        self.blocks_seen = self.blocks_seen.saturating_add(1);
+        self.peak_hold = self.peak_hold.max(self.block_peak);
+        self.block_filled = 0;
+        self.block_peak = 0.0;
+        self.block_sum = 0.0;
+        self.block_values = 0;
+    }
+
+    /// Completed blocks in the window, newest first.
+    fn window(&self) -> impl Iterator<Item = &Block> {
+        (1..=self.blocks_seen.min(WINDOW_BLOCKS))
+            .map(move |back| &self.blocks[(self.block_pos + WINDOW_BLOCKS - back) % WINDOW_BLOCKS])
+    }
+
+    /// Window peak and RMS in dBFS, or `None` while silent or warming up.
+    fn window_levels_db(&self) -> Option<(f32, f32)> {
+        let (count, peak, sum) = self
+            .window()
+            .fold((0_usize, 0.0_f32, 0.0_f32), |(n, peak, sum), block| {
+                (n + 1, peak.max(block.peak), sum + block.mean_square)
+            });
+        if count == 0 {
+            return None;
+        }
+        let rms_db = to_db((sum / count as f32).sqrt());
+        (rms_db > DYNAMICS_FLOOR_DB).then_some((to_db(peak), rms_db))
+    }
+
+    /// Peak-to-RMS ratio over the window (dB).
+    pub fn crest_factor_db(&self) -> Option<f32> {
+        self.window_levels_db().map(|(peak, rms)| peak - rms)
+    }
+
+    /// Peak-to-short-term-loudness ratio (dB).
+    pub fn psr_db(&self, short_term_lufs: f32) -> Option<f32> {
+        if short_term_lufs <= LOUDNESS_FLOOR_LUFS {
+            return None;
+        }
+        self.window_levels_db()
+            .map(|(peak, _)| peak - short_term_lufs)
+    }
+
+    /// Peak-to-loudness ratio since the last reset (dB).
+    pub fn plr_db(&self, integrated_lufs: f32) -> Option<f32> {
+        if integrated_lufs <= LOUDNESS_FLOOR_LUFS || self.peak_hold <= 0.0 {
+            return None;
+        }
+        Some(to_db(self.peak_hold) - integrated_lufs)
+    }
+
+    /// Loudest minus quietest block RMS over the window (dB); needs at
+    /// least two blocks above the floor.
+    pub fn spread_db(&self) -> Option<f32> {
+        let (count, loudest, quietest) = self
+            .window()
+            .map(|block| to_db(block.mean_square.sqrt()))
+            .filter(|db| *db > DYNAMICS_FLOOR_DB)
+            .fold((0_usize, f32::MIN, f32::MAX), |(n, hi, lo), db| {
+                (n + 1, hi.max(db), lo.min(db))
+            });
+        (count >= 2).then_some(loudest - quietest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    fn feed(meter: &mut DynamicsMeter, seconds: f32, signal: impl Fn(usize) -> f32) {
+        let n = (SR * seconds) as usize;
+        let mut l: Vec<f32> = (0..n).map(signal).collect();
+        let mut r = l.clone();
+        for (cl, cr) in l.chunks_mut(480).zip(r.chunks_mut(480)) {
+            meter.process(&[cl, cr]);
+        }
+    }
+
+    fn sine(amplitude: f32) -> impl Fn(usize) -> f32 {
+        let omega = 2.0 * std::f32::consts::PI * 997.0 / SR;
+        move |i| amplitude * (omega * i as f32).sin()
+    }
+
+    #[test]
+    fn sine_crest_factor_is_three_db() {
+        let mut meter = DynamicsMeter::new(SR);
+        feed(&mut meter, 3.0, sine(0.5));
+        let crest = meter.crest_factor_db().unwrap();
+        assert!((crest - 3.01).abs() < 0.05, "crest: {crest}");
+        let spread = meter.spread_db().unwrap();
+        assert!(spread < 0.05, "steady tone should have no spread: {spread}");
+    }
+
+    #[test]
+    fn silence_has_no_readings() {
+        let mut meter = DynamicsMeter::new(SR);
+        assert_eq!(meter.crest_factor_db(), None);
+        feed(&mut meter, 1.0, |_| 0.0);
+        assert_eq!(meter.crest_factor_db(), None);
+        assert_eq!(meter.spread_db(), None);
+        assert_eq!(meter.psr_db(LOUDNESS_FLOOR_LUFS), None);
+        assert_eq!(meter.plr_db(-20.0), None);
+    }
+
+    #[test]
+    fn psr_and_plr_measure_peak_over_loudness() {
+        let mut meter = DynamicsMeter::new(SR);
+        // 0.5 peak ≈ -6.02 dBFS.
+        feed(&mut meter, 1.0, sine(0.5));
+        let psr = meter.psr_db(-14.0).unwrap();
+        assert!((psr - 7.98).abs() < 0.05, "psr: {psr}");
+        let plr = meter.plr_db(-12.0).unwrap();
+        assert!((plr - 5.98).abs() < 0.05, "plr: {plr}");
+    }
+
+    #[test]
+    fn spread_follows_level_changes_and_peak_hold_resets() {
+        let mut meter = DynamicsMeter::new(SR);
+        feed(&mut meter, 1.0, sine(0.5));
+        feed(&mut meter, 1.0, sine(0.05));
+        let spread = meter.spread_db().unwrap();
+        assert!((spread - 20.0).abs() < 0.1, "spread: {spread}");
+
+        meter.reset_peak_hold();
+        feed(&mut meter, 0.2, sine(0.05));
+        let plr = meter.plr_db(-30.0).unwrap();
+        assert!((plr - 4.0).abs() < 0.05, "plr after reset: {plr}");
+    }
+}
@@ -3,16 +3,40 @@
 
 use nih_plug::prelude::*;
 use std::cell::RefCell;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use vizia_plug::vizia::prelude::*;
 use vizia_plug::widgets::{ParamButton, ParamButtonExt, ParamSlider, RawParamEvent};
 use vizia_plug::{create_vizia_editor, ViziaState, ViziaTheming};
 
-use crate::components::{self, ModuleTheme};
+use crate::components::{
+    self, ModuleTheme, ParamAccessExt, ParamFocusEvent, ParamMenuEvent, ParamMenuExt,
+    ParamMenuState,
+};
+#[cfg(feature = "dynamic_eq")]
+use crate::dynamic_eq::ControlLaw;
+use crate::history::UndoHistory;
+use crate::host_context::{self, ContextPreset};
+use crate::i18n;
+#[cfg(feature = "punch")]
+use crate::loudness_target::{self, LearnedLevels, LoudnessSuggestion, PunchSettings};
+use crate::matching;
+use crate::meter_scale::MeterScale;
+use crate::presets::{self, Preset, PresetLibrary, PresetTask};
+#[cfg(any(feature = "api5500", feature = "pultec"))]
+use crate::response::{self, ResponseFormat};
+use crate::snapshots::{AbCompare, AbSlot};
 use crate::spectral;
-use crate::styles::COMPONENT_STYLES;
-use crate::{BusChannelStripParams, ModuleType};
+use crate::styles::{self, COMPONENT_STYLES};
+use crate::variation;
+use crate::view_state::{EditorView, Panel, ViewInput};
+use crate::{
+    BusChannelStrip, BusChannelStripParams, EditorLanguage, EditorSettings, EditorSkin,
+    MeterBallistics, MeterRate, ModuleType,
+};
 
 // ============================================================================
 // App Events
@@ -38,6 +62,36 @@ impl vizia_plug::vizia::binding::Data for DropPos {
     }
 }
 
+impl vizia_plug::vizia::binding::Data for EditorView {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl vizia_plug::vizia::binding::Data for EditorSettings {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl vizia_plug::vizia::binding::Data for MeterBallistics {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl vizia_plug::vizia::binding::Data for MeterScale {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl vizia_plug::vizia::binding::Data for EditorSkin {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum AppEvent {
     /// Emitted from a slot's `on_drag` callback the moment vizia detects
@@ -70,6 +124,9 @@ pub enum AppEvent {
     /// slot; otherwise add it to the first empty slot. No-op if the rack
     /// is full of other modules and there's no empty slot.
     AddOrFocusModule(ModuleType),
+    /// Module header click: open that slot's focus view, or return to the
+    /// rack when it is already focused.
+    ToggleFocus(usize),
     /// Exit focus mode entirely — every slot returns to its hide-flag-driven
     /// presentation. Wired to the chassis-header EXIT FOCUS button.
     ClearFocus,
@@ -89,9 +146,57 @@ pub enum AppEvent {
     RestoreSheenFactory,
     /// Toggle the expand/collapse state of a DynEQ band (0–3). GUI-only state.
     ToggleDynEQBand(usize),
+    /// Open or close a module's ADVANCED drawer. GUI-only state, keyed by
+    /// module type so the drawer follows the module through reorders.
+    ToggleAdvanced(ModuleType),
+    /// Make A or B the live snapshot. The state being left is stored into
+    /// its slot; the other slot's state (if any) is written to the params.
+    AbSelect(AbSlot),
+    /// Overwrite the inactive snapshot with the live state.
+    AbCopy,
+    /// Trim the master gain so the live snapshot's output level matches the
+    /// level measured for the other snapshot.
+    AbMatchGain,
+    /// Turn A/B loudness matching on or off. Persisted in the settings blob.
+    ToggleAbLoudnessMatch,
     /// Set the chassis zoom level (percentage: 75, 100, 125, 150, 200).
     /// Applied via toggle_class on the chassis root; CSS scales content widths.
     SetZoom(u8),
+    /// Switch the editor skin. Persisted with the plugin state; applied via
+    /// a `skin-*` class on the chassis root plus a slot rebuild for accents.
+    SetSkin(EditorSkin),
+    /// Switch the editor language. Persisted with the plugin state; every
+    /// translated label is bound to `Data::language`, so nothing rebuilds.
+    SetLanguage(EditorLanguage),
+    /// Change how often the audio thread publishes meters and spectra.
+    /// Persisted with the plugin state and mirrored to the audio thread.
+    SetMeterRate(MeterRate),
+    /// Show or hide the spectrum analyzers. Persisted in the settings blob.
+    ToggleAnalyzer,
+    /// Show or hide param tooltips. Persisted in the settings blob.
+    ToggleTooltips,
+    /// Freeze the analyzer's current curve into overlay slot N, or clear
+    /// the slot if it already holds one. Persisted with the session.
+    ToggleSpectrumSnapshot(usize),
+    /// Change how quickly the level meters follow. Persisted in the
+    /// settings blob.
+    SetMeterBallistics(MeterBallistics),
+    /// Change the level meters' calibration. Persisted in the settings
+    /// blob.
+    SetMeterScale(MeterScale),
+    /// Emitted by a slot each time it (re)builds, with the module index it
+    /// rendered. Only a change of module starts the insert animation, so
+    /// rebuilds for skin/focus/hide changes stay still.
+    SlotBuilt { slot: usize, module: usize },
+    /// Settle timer expired — every animating slot returns to rest.
+    SlotsSettled,
+    /// Show or hide the metering bridge in place of the module slots.
+    /// Opening it closes the DynEQ / Sheen back views.
+    ToggleMeterBridge,
+    /// Poll tick: copy the loudness readings into the bridge's labels.
+    MeterTick,
+    /// Restart integrated loudness on the audio thread.
+    ResetLoudness,
     /// Request a one-shot sidechain masking analysis from the audio thread.
     #[cfg(feature = "dynamic_eq")]
     RequestAnalysis,
@@ -104,6 +209,121 @@ pub enum AppEvent {
     },
 }
 
+/// Undo/redo events. Group and replay markers are emitted around batches of
+/// `RawParamEvent`s; because events are processed in emission order, the
+/// markers bracket exactly those writes.
+#[derive(Debug, Clone, Copy)]
+pub enum HistoryEvent {
+    Undo,
+    Redo,
+    /// Following gestures collapse into one undo step until `GroupEnd`.
+    GroupBegin,
+    GroupEnd,
+    /// Following gestures are undo/redo playback and must not be recorded.
+    ReplayBegin,
+    ReplayEnd,
+}
+
+/// Parameter changes that arrived over OSC (see osc.rs), handed from the
+/// poll timer to the model.
+#[cfg(feature = "osc")]
+#[derive(Debug, Clone)]
+pub struct RemoteChanges(Vec<(String, crate::osc::OscValue)>);
+
+/// Identifies a preset in the browser. User presets are keyed by path rather
+/// than list index so a rescan landing between render and click can never
+/// load the wrong file.
+#[derive(Debug, Clone)]
+pub enum PresetId {
+    Factory(usize),
+    User(PathBuf),
+}
+
+/// Preset browser events. Kept apart from `AppEvent` because they carry
+/// owned strings/paths and `AppEvent` is `Copy`.
+#[derive(Debug, Clone)]
+pub enum PresetEvent {
+    /// Show/hide the browser panel. Opening also queues a rescan so files
+    /// added outside the plugin show up.
+    ToggleBrowser,
+    /// Live search text from the browser's filter box.
+    SetSearch(String),
+    /// Apply a preset: every preset param is written in one event frame;
+    /// params the preset omits return to their defaults.
+    Load(PresetId),
+    /// Open the save-as dialog, pre-filled with the current preset name.
+    OpenSave,
+    SetSaveName(String),
+    /// Capture the current state and hand it to the background thread.
+    ConfirmSave,
+    CancelSave,
+    /// Delete a user preset file (background thread).
+    Delete(PathBuf),
+    /// Flip the first-instantiation preset for every instance (background
+    /// thread); see host_context.rs.
+    ToggleContextPresets,
+    /// Write the whole chain to a file picked in a native dialog.
+    ExportChain,
+    /// Read a chain picked in a native dialog; applied on the next poll.
+    ImportChain,
+    /// Timer tick — picks up completed background tasks via the library's
+    /// generation counter.
+    Poll,
+}
+
+/// Spectrum-match events, in the metering bridge. Kept apart from
+/// `AppEvent` for the same reason as `PresetEvent`: the path is owned.
+#[derive(Debug, Clone)]
+pub enum MatchEvent {
+    /// Live text of the reference-path box.
+    SetReferencePath(String),
+    /// Decode the WAV at `match_reference_path` on the background thread.
+    LoadReference,
+    /// Drop the WAV reference; the sidechain becomes the reference again.
+    UseSidechain,
+    /// Ask the audio thread to capture the program (and sidechain).
+    Capture,
+    /// Write the last suggestion into the API5500 as one undo step.
+    Apply,
+}
+
+/// EQ response export events, in the metering bridge. Kept apart from
+/// `AppEvent` for the same reason as `PresetEvent`: the path is owned.
+#[cfg(any(feature = "api5500", feature = "pultec"))]
+#[derive(Debug, Clone)]
+pub enum ResponseEvent {
+    /// Live text of the export-path box.
+    SetPath(String),
+    /// Render the EQ response and write it to `response_path`.
+    Export(ResponseFormat),
+}
+
+/// Loudness-target assistant events, in the metering bridge.
+#[cfg(feature = "punch")]
+#[derive(Debug, Clone, Copy)]
+pub enum LoudnessEvent {
+    /// Move the target by this many LU.
+    StepTarget(i8),
+    /// Ask the audio thread to run a learn pass.
+    Learn,
+    /// Write the last suggestion into Punch as one undo step.
+    Apply,
+    /// Flip applying each suggestion as soon as its pass finishes.
+    ToggleAutoApply,
+}
+
+/// VARY button events. Kept apart from `AppEvent` because the seed field
+/// carries an owned string.
+#[derive(Debug, Clone)]
+pub enum VariationEvent {
+    /// Live text of the seed box.
+    SetSeed(String),
+    /// Step to the next seed and vary one module (`None`: the whole strip).
+    Vary(Option<ModuleType>),
+    /// Re-apply the typed seed to the last scope varied.
+    ApplySeed,
+}
+
 // ============================================================================
 // Editor Data Model
 // ============================================================================
@@ -124,12 +344,10 @@ pub struct Data {
     /// user always sees what they're dragging next to the cursor.
     pub cursor_x: f32,
     pub cursor_y: f32,
-    /// When true, the DynEQ back view is shown instead of the strip.
-    pub dyneq_open: bool,
-    /// When true, the Sheen back view is shown instead of the strip.
-    /// Mutually exclusive with `dyneq_open` — handlers for either Open*
-    /// event clear the other so the model never has two back views true.
-    pub sheen_open: bool,
+    /// Rack, focus view, or the panel (DynEQ / Sheen back view, metering
+    /// bridge) covering the rack. Only changed through `ViewInput`s; see
+    /// `src/view_state.rs`.
+    pub view: EditorView,
     /// GUI-only expand state for each of the 4 DynEQ bands. Never accessed from audio thread.
     pub dyneq_band_expand: Arc<[AtomicBool; 4]>,
     /// Incremented on every ToggleDynEQBand — used as lens target to trigger .display() re-evaluation.
@@ -138,14 +356,142 @@ pub struct Data {
     pub analysis_requested: Arc<AtomicBool>,
     /// Shared with the audio thread — read after analysis completes.
     pub analysis_result: Arc<spectral::AnalysisResult>,
+    /// Analyzer feed, drawn over the focused module.
+    pub spectrum_data: Arc<spectral::SpectrumData>,
+    /// Mirror of `params.analyzer_snapshots.held_mask()` for the FREEZE
+    /// buttons.
+    pub spectrum_snapshot_mask: u8,
     /// Current chassis zoom level as integer percentage. Valid: 75, 100, 125, 150, 200.
     /// Applied via toggle_class to the chassis root; CSS scales slot width + padding.
     pub zoom_level: u8,
-    /// When `Some(slot)`, the rack is in focus mode: that slot renders full
-    /// and every other slot collapses to its narrow tab regardless of its
-    /// per-module hide flag. Set only via keyboard `1..7`; click-to-focus
-    /// was removed when the slot body became the drag source.
-    pub focused_slot: Option<usize>,
+    /// Mirrors `params.editor_settings`; `zoom_level` is its zoom.
+    pub settings: EditorSettings,
+    /// Active skin. Mirrors `params.editor_skin` so views can bind to it.
+    pub skin: EditorSkin,
+    /// Active language. Mirrors `params.editor_language`; see `tr`.
+    pub language: EditorLanguage,
+    /// Meter refresh rate. Mirrors `params.meter_rate`.
+    pub meter_rate: MeterRate,
+    /// The audio thread's copy of the rate, in Hz.
+    pub meter_rate_hz: Arc<AtomicU32>,
+    /// Bit per slot: set while that slot plays its insert/remove animation.
+    pub slot_settling: u8,
+    /// Bit per module type (`module_type_to_usize`): set while that
+    /// module's ADVANCED drawer is open.
+    pub advanced_open: u8,
+    /// Module index each slot last rendered; `usize::MAX` until first build,
+    /// so opening the editor plays the power-on animation across the rack.
+    slot_built: [usize; 7],
+    /// One-shot timer that clears `slot_settling`.
+    settle_timer: Timer,
+    /// Runs preset file IO on nih-plug's background thread.
+    executor: AsyncExecutor<BusChannelStrip>,
+    /// User presets published by the background thread.
+    pub preset_library: Arc<PresetLibrary>,
+    /// Compiled-in factory bank, built once per editor instance.
+    pub factory_presets: Arc<Vec<Preset>>,
+    /// Mirror of `preset_library.generation()`. Updated by the poll timer;
+    /// the browser list rebuilds when it changes.
+    pub preset_generation: u32,
+    pub preset_browser_open: bool,
+    /// Mirror of `preset_library.context_presets()`, refreshed by the poll
+    /// timer; drives the browser's AUTO switch.
+    pub context_presets: bool,
+    pub preset_search: String,
+    pub preset_save_open: bool,
+    pub preset_save_name: String,
+    /// Name of the last loaded/saved preset, shown on the header button.
+    /// Empty until the user picks one.
+    pub current_preset_name: String,
+    /// A/B snapshot pair. GUI-only state — not persisted with the session.
+    pub ab: AbCompare,
+    /// Editor-local undo/redo of parameter gestures. GUI-only, like `ab`.
+    pub history: UndoHistory<ParamPtr>,
+    /// Text of the bottom readout strip: the last parameter the user touched.
+    pub param_readout: String,
+    /// Right-click context menu shared by every param control.
+    pub param_menu: ParamMenuState,
+    /// Last parameter the user touched. Arrow keys that no focused knob
+    /// consumed nudge this one, so sliders and buttons get nudging too.
+    pub last_touched: Option<ParamPtr>,
+    /// Param control holding keyboard focus, with where its entry box
+    /// opens. It takes the adjustment keys; see components.rs.
+    pub keyboard_focus: Option<(ParamPtr, f32, f32)>,
+    /// Smoothed post-master output level from the audio thread.
+    pub output_level: Arc<spectral::OutputLevelData>,
+    /// Transformer saturation activity, drawn by the module's drive meter.
+    pub transformer_sat: Arc<spectral::SaturationMeterData>,
+    /// Master stereo tap for the goniometer and correlation meter.
+    pub scope_data: Arc<spectral::StereoScopeData>,
+    /// Scrolling compressor / DynEQ gain-reduction history.
+    pub gr_history: Arc<Mutex<spectral::GrHistoryData>>,
+    /// Punch clipper gain-reduction fraction for the metering bridge.
+    pub punch_clip: Arc<spectral::SaturationMeterData>,
+    /// BS.1770 loudness of the final output.
+    pub loudness: Arc<spectral::LoudnessData>,
+    /// Crest factor / PSR / PLR / spread for the bridge's dynamics panel.
+    pub dynamics: Arc<spectral::DynamicsData>,
+    /// How often the tube, transformer and clipper bend the signal.
+    pub engagement: Arc<spectral::EngagementData>,
+    /// Transformer harmonic probe results.
+    pub harmonics: Arc<spectral::HarmonicData>,
+    /// Mirror of `harmonics.generation()`, advanced by `MeterTick`.
+    pub harmonic_generation: u32,
+    /// Transformer THD and even-harmonic share, as last probed.
+    pub transformer_thd: f32,
+    pub transformer_even_share: f32,
+    /// RMS between stages, drawn by the signal-flow strip.
+    pub stage_levels: Arc<spectral::StageLevelData>,
+    /// Per-band DynEQ gain reduction, drawn on each band's GAIN slider.
+    pub gr_data: Arc<spectral::GainReductionData>,
+    /// Lit while the Pultec's printed tube stage is in the signal path.
+    pub print_active: Arc<AtomicBool>,
+    /// Lit when the Classic compressor's core failed to load (bypassed).
+    pub comp_degraded: Arc<AtomicBool>,
+    /// Lit for a second after the output safety stage caught a NaN/Inf.
+    pub safety_fault: Arc<AtomicBool>,
+    /// Loudness readouts, refreshed by `MeterTick` while the bridge is open.
+    pub lufs_momentary: f32,
+    pub lufs_short_term: f32,
+    pub lufs_integrated: f32,
+    /// Dynamics readouts indexed by `DynamicsStat`, refreshed with the LUFS.
+    pub dynamics_readings: [Option<f32>; spectral::DYNAMICS_STATS],
+    /// Engagement shares indexed by `EngagementStage`, refreshed with the
+    /// LUFS.
+    pub engagement_readings: [f32; spectral::ENGAGEMENT_STAGES],
+    /// Program / reference levels for the spectrum-match panel.
+    pub match_data: Arc<spectral::MatchData>,
+    /// WAV path typed into the match panel.
+    pub match_reference_path: String,
+    /// What the suggestion is matched against: "SIDECHAIN" or a file name.
+    pub match_reference_name: String,
+    /// Status or suggestion text. English; translated where it is shown.
+    pub match_status: String,
+    /// Result of the last capture; `MatchEvent::Apply` writes it to the EQ.
+    match_suggestion: Option<matching::MatchSuggestion>,
+    /// Mirror of `match_data.file_generation()`, advanced by `MeterTick`.
+    match_file_generation: u32,
+    /// Outcome of the last EQ response export.
+    response_export: Arc<spectral::ResponseExportData>,
+    /// Mirror of `response_export.generation()`, advanced by `MeterTick`.
+    response_generation: u32,
+    /// File path typed into the response-export box.
+    pub response_path: String,
+    /// Status of the export. English; translated where it is shown.
+    pub response_status: String,
+    /// Learn-pass handshake and readings for the loudness-target assistant.
+    #[cfg(feature = "punch")]
+    loudness_learn: Arc<spectral::LoudnessLearnData>,
+    /// Result of the last learn pass; `LoudnessEvent::Apply` writes it to
+    /// Punch.
+    #[cfg(feature = "punch")]
+    loudness_suggestion: Option<LoudnessSuggestion>,
+    /// Status or suggestion text. English; translated where it is shown.
+    pub loudness_status: String,
+    /// Seed box text; VARY writes the seed it used back here.
+    pub variation_seed: String,
+    /// What the last VARY covered, reused when a typed seed is submitted.
+    variation_scope: Option<ModuleType>,
 }
 
 impl Model for Data {
@@ -161,14 +507,35 @@ impl Model for Data {
         event.map(|win: &WindowEvent, _| match win {
             WindowEvent::KeyDown(code, _) => match code {
                 Code::Escape => {
-                    self.focused_slot = None;
-                    self.drag_source = None;
-                    self.drop_target = None;
                     // Esc also closes any open back view so users have a
                     // single universal "get me back to the strip" key.
-                    self.dyneq_open = false;
-                    self.sheen_open = false;
+                    self.view = self.view.next(ViewInput::Exit);
+                    self.drag_source = None;
+                    self.drop_target = None;
+                    self.preset_browser_open = false;
+                    self.preset_save_open = false;
+                    if self.param_menu.is_open() {
+                        self.param_menu.event(cx, &ParamMenuEvent::Close);
+                    }
                 }
+                // Digits are text while the browser's search / save-name
+                // boxes are up — don't steal them for slot focus.
+                _ if self.preset_browser_open => {}
+                // Ctrl/Cmd+Z undo, Ctrl/Cmd+Shift+Z or Ctrl/Cmd+Y redo.
+                Code::KeyZ if cx.modifiers().command() => {
+                    cx.emit(if cx.modifiers().shift() {
+                        HistoryEvent::Redo
+                    } else {
+                        HistoryEvent::Undo
+                    });
+                }
+                Code::KeyY if cx.modifiers().command() => cx.emit(HistoryEvent::Redo),
+                // A control reached with Tab owns the adjustment keys.
+                _ if self.adjust_focused(cx, *code) => {}
+                // Arrow keys only get here when no focused knob consumed
+                // them — nudge the last touched control instead.
+                Code::ArrowUp | Code::ArrowRight => self.nudge_last_touched(cx, 1.0),
+                Code::ArrowDown | Code::ArrowLeft => self.nudge_last_touched(cx, -1.0),
                 Code::Digit1 => self.focus_if_real(0),
                 Code::Digit2 => self.focus_if_real(1),
                 Code::Digit3 => self.focus_if_real(2),
@@ -199,21 +566,18 @@ impl Model for Data {
         });
 
         event.map(|e: &AppEvent, _| match e {
+            // One panel at a time; the state machine swaps them.
             AppEvent::OpenDynEq => {
-                self.dyneq_open = true;
-                // Mutual exclusion with Sheen back view.
-                self.sheen_open = false;
+                self.view = self.view.next(ViewInput::Open(Panel::DynEq));
             }
             AppEvent::CloseDynEq => {
-                self.dyneq_open = false;
+                self.view = self.view.next(ViewInput::Close(Panel::DynEq));
             }
             AppEvent::OpenSheen => {
-                self.sheen_open = true;
-                // Mutual exclusion with DynEQ back view.
-                self.dyneq_open = false;
+                self.view = self.view.next(ViewInput::Open(Panel::Sheen));
             }
             AppEvent::CloseSheen => {
-                self.sheen_open = false;
+                self.view = self.view.next(ViewInput::Close(Panel::Sheen));
             }
             AppEvent::RestoreSheenFactory => {
                 // Re-write every Sheen param to the factory default in one
@@ -235,6 +599,7 @@ impl Model for Data {
                     cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
                     cx.emit(RawParamEvent::EndSetParameter(ptr));
                 };
+                cx.emit(HistoryEvent::GroupBegin);
                 restore_bool(cx, self.params.sheen_bypass.as_ptr(), false);
                 restore(cx, self.params.sheen_body_db.as_ptr(), 1.0);
                 restore_bool(cx, self.params.sheen_body_bypass.as_ptr(), false);
@@ -246,6 +611,7 @@ impl Model for Data {
                 restore_bool(cx, self.params.sheen_warmth_bypass.as_ptr(), false);
                 restore(cx, self.params.sheen_width.as_ptr(), 0.50);
                 restore_bool(cx, self.params.sheen_width_bypass.as_ptr(), false);
+                cx.emit(HistoryEvent::GroupEnd);
             }
 
             AppEvent::ToggleDynEQBand(band) => {
@@ -257,6 +623,49 @@ impl Model for Data {
                 }
             }
 
+            AppEvent::ToggleAdvanced(mt) => {
+                self.advanced_open ^= 1 << module_type_to_usize(*mt);
+            }
+
+            AppEvent::AbSelect(slot) => {
+                let switching = self.ab.active() != *slot;
+                let match_loudness = self.settings.ab_match_loudness;
+                if switching && match_loudness {
+                    self.ab.record_loudness(self.loudness.integrated());
+                }
+                let live = presets::capture(self.params.as_ref());
+                let level = self.output_level.load_db();
+                if let Some(mut state) = self.ab.select(*slot, live, level) {
+                    if match_loudness {
+                        self.ab.match_loudness(&mut state);
+                    }
+                    self.apply_param_values(cx, &state);
+                }
+                if switching && match_loudness {
+                    // Measure the incoming side on its own.
+                    self.loudness.request_reset();
+                    self.lufs_integrated = crate::loudness::LOUDNESS_FLOOR_LUFS;
+                }
+            }
+
+            AppEvent::AbCopy => {
+                let live = presets::capture(self.params.as_ref());
+                self.ab.copy_to_other(live, self.output_level.load_db());
+            }
+
+            AppEvent::AbMatchGain => {
+                if let Some(offset_db) = self.ab.match_gain_offset_db(self.output_level.load_db()) {
+                    // Master gain is stored linear; the range clamp in
+                    // preview_normalized keeps extreme offsets in bounds.
+                    let ptr = self.params.gain.as_ptr();
+                    let target = self.params.gain.value() * util::db_to_gain(offset_db);
+                    let norm = self.params.gain.preview_normalized(target);
+                    cx.emit(RawParamEvent::BeginSetParameter(ptr));
+                    cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
+                    cx.emit(RawParamEvent::EndSetParameter(ptr));
+                }
+            }
+
             AppEvent::SetZoom(level) => {
                 // Clamp to supported discrete levels. Unknown values fall back to 100.
                 // NOTE: vizia-plug does not support runtime host-window resize
@@ -268,6 +677,94 @@ impl Model for Data {
                     75 | 100 | 125 | 150 | 200 => *level,
                     _ => 100,
                 };
+                let zoom = self.zoom_level;
+                self.update_settings(|s| s.zoom = zoom);
+            }
+
+            AppEvent::ToggleAnalyzer => self.update_settings(|s| s.analyzer = !s.analyzer),
+            AppEvent::ToggleTooltips => self.update_settings(|s| s.tooltips = !s.tooltips),
+            AppEvent::ToggleSpectrumSnapshot(slot) => {
+                if let Ok(mut snapshots) = self.params.analyzer_snapshots.write() {
+                    if snapshots.get(*slot).is_some() {
+                        snapshots.clear(*slot);
+                    } else {
+                        snapshots.capture(*slot, &self.spectrum_data);
+                    }
+                    self.spectrum_snapshot_mask = snapshots.held_mask();
+                }
+            }
+            AppEvent::ToggleAbLoudnessMatch => {
+                self.update_settings(|s| s.ab_match_loudness = !s.ab_match_loudness)
+            }
+            AppEvent::SetMeterBallistics(ballistics) => {
+                let ballistics = *ballistics;
+                self.update_settings(|s| s.meter_ballistics = ballistics);
+            }
+            AppEvent::SetMeterScale(scale) => {
+                let scale = *scale;
+                self.update_settings(|s| s.meter_scale = scale);
+            }
+
+            AppEvent::SetSkin(skin) => {
+                // Write the persisted copy first: knobs read it while the
+                // slots rebuild in response to `skin` changing.
+                if let Ok(mut persisted) = self.params.editor_skin.write() {
+                    *persisted = *skin;
+                }
+                self.skin = *skin;
+            }
+
+            AppEvent::SetLanguage(language) => {
+                if let Ok(mut persisted) = self.params.editor_language.write() {
+                    *persisted = *language;
+                }
+                self.language = *language;
+            }
+
+            AppEvent::SetMeterRate(rate) => {
+                if let Ok(mut persisted) = self.params.meter_rate.write() {
+                    *persisted = *rate;
+                }
+                self.meter_rate_hz.store(rate.hz(), Ordering::Relaxed);
+                self.meter_rate = *rate;
+            }
+
+            AppEvent::SlotBuilt { slot, module } => {
+                if self.slot_built[*slot] != *module {
+                    self.slot_built[*slot] = *module;
+                    self.slot_settling |= 1 << *slot;
+                    cx.start_timer(self.settle_timer);
+                }
+            }
+
+            AppEvent::SlotsSettled => {
+                self.slot_settling = 0;
+            }
+
+            AppEvent::ToggleMeterBridge => {
+                self.view = self.view.next(ViewInput::Toggle(Panel::MeterBridge));
+            }
+
+            AppEvent::MeterTick => {
+                // Labels only re-render when the values change, and they
+                // only change while someone is looking at them.
+                if self.view.is_open(Panel::MeterBridge) {
+                    self.lufs_momentary = self.loudness.momentary();
+                    self.lufs_short_term = self.loudness.short_term();
+                    self.lufs_integrated = self.loudness.integrated();
+                    self.poll_dynamics();
+                    self.poll_engagement();
+                    self.poll_match();
+                    self.poll_response_export();
+                    #[cfg(feature = "punch")]
+                    self.poll_loudness_learn(cx);
+                }
+                self.poll_harmonics();
+            }
+
+            AppEvent::ResetLoudness => {
+                self.loudness.request_reset();
+                self.lufs_integrated = crate::loudness::LOUDNESS_FLOOR_LUFS;
             }
 
             #[cfg(feature = "dynamic_eq")]
@@ -322,7 +819,7 @@ impl Model for Data {
             AppEvent::AddOrFocusModule(mt) => {
                 if let Some(slot) = slot_containing(&self.params, *mt) {
                     // Module is already in the rack — focus that slot.
-                    self.focused_slot = Some(slot);
+                    self.view = self.view.next(ViewInput::Focus(slot));
                 } else if let Some(slot) = first_empty_slot(&self.params) {
                     // Add to the leftmost empty slot, then focus it so
                     // the user can immediately tweak the new module.
@@ -331,7 +828,7 @@ impl Model for Data {
                     cx.emit(RawParamEvent::BeginSetParameter(ptr));
                     cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
                     cx.emit(RawParamEvent::EndSetParameter(ptr));
-                    self.focused_slot = Some(slot);
+                    self.view = self.view.next(ViewInput::Focus(slot));
                 } else {
                     // If no empty slot exists, silently no-op (the user
                     // would have to eject something first; baseview lacks
@@ -339,12 +836,19 @@ impl Model for Data {
                 }
             }
 
+            AppEvent::ToggleFocus(slot) => {
+                // Empty slots have no focus view (see `focus_if_real`).
+                if self.view.focused_slot() == Some(*slot)
+                    || slot_module_type(&self.params, *slot) != ModuleType::Empty
+                {
+                    self.view = self.view.next(ViewInput::ToggleFocus(*slot));
+                }
+            }
+
             AppEvent::ClearFocus => {
-                self.focused_slot = None;
+                self.view = self.view.next(ViewInput::Exit);
                 self.drag_source = None;
                 self.drop_target = None;
-                self.dyneq_open = false;
-                self.sheen_open = false;
             }
 
             AppEvent::LoadChain(idx) => {
@@ -352,6 +856,7 @@ impl Model for Data {
                     // Write all seven slots in one batch so the host sees a
                     // coherent state change. Bypasses are intentionally not
                     // touched: presets define routing, not levels.
+                    cx.emit(HistoryEvent::GroupBegin);
                     for slot in 0..7 {
                         let mt = preset.chain[slot];
                         let ptr = slot_param_ptr(&self.params, slot);
@@ -360,12 +865,13 @@ impl Model for Data {
                         cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
                         cx.emit(RawParamEvent::EndSetParameter(ptr));
                     }
+                    cx.emit(HistoryEvent::GroupEnd);
                     // Reset transient view state so the loaded chain shows
                     // as the overview instead of focused on whatever was
                     // there before.
                     self.drag_source = None;
                     self.drop_target = None;
-                    self.focused_slot = None;
+                    self.view = self.view.next(ViewInput::Unfocus);
                 }
             }
 
@@ -384,6 +890,10 @@ impl Model for Data {
                 // while a swap is staged would be ambiguous.
                 self.drag_source = None;
                 self.drop_target = None;
+                // An ejected slot has nothing left to focus on.
+                if *mt == ModuleType::Empty && self.view.focused_slot() == Some(*slot) {
+                    self.view = self.view.next(ViewInput::Unfocus);
+                }
             }
 
             AppEvent::DragStarted(idx) => {
@@ -419,110 +929,662 @@ impl Model for Data {
                 self.drop_target = None;
             }
         });
-    }
-}
 
-impl Data {
-    /// Focus a slot ONLY if it holds a real module. Empty slots silently
-    /// stay unfocused — focusing one would collapse every real slot via
-    /// the "any-other-focused → collapsed" render rule, leaving nothing
-    /// to inspect.
-    fn focus_if_real(&mut self, idx: usize) {
-        if slot_module_type(&self.params, idx) != ModuleType::Empty {
-            self.focused_slot = Some(idx);
-            self.drag_source = None;
-            self.drop_target = None;
-        }
-    }
+        // ── Gesture recording ───────────────────────────────────────────
+        // Every widget write (knobs, buttons, batch actions) passes through
+        // here on its way to vizia-plug's param model. Begin sees the value
+        // before the widget changes it.
+        event.map(|e: &RawParamEvent, _| match e {
+            RawParamEvent::BeginSetParameter(ptr) => {
+                // SAFETY: every ParamPtr in this editor comes from
+                // `self.params`, which outlives the editor.
+                let before = unsafe { ptr.unmodulated_normalized_value() };
+                self.history.begin(*ptr, before);
+                if !self.history.in_batch() {
+                    self.param_readout = param_readout_text(*ptr, before);
+                    self.last_touched = Some(*ptr);
+                }
+            }
+            RawParamEvent::SetParameterNormalized(ptr, norm) => {
+                self.history.set(*ptr, *norm);
+                if !self.history.in_batch() {
+                    self.param_readout = param_readout_text(*ptr, *norm);
+                }
+            }
+            RawParamEvent::EndSetParameter(ptr) => {
+                self.history.end(*ptr);
+            }
+            _ => {}
+        });
 
-    /// Apply a reorder operation against the seven `module_order_*` params
-    /// in one event frame. `position` decides semantics:
-    ///   • `Onto`   → swap src ↔ tgt (two slots change)
-    ///   • `Before` → remove src, insert before tgt (rotation; up to 7 change)
-    ///   • `After`  → remove src, insert after tgt (rotation; up to 7 change)
-    ///
-    /// Reads the current order, computes the new order in-memory, then
-    /// writes back any slot that actually changed. This minimises host
-    /// preset diff size and avoids spurious automation events.
-    fn reorder(&self, cx: &mut EventContext, src: usize, tgt: usize, position: DropPos) {
-        if src == tgt && position == DropPos::Onto {
-            return; // self-drop, nothing to do
-        }
+        event.map(|e: &ParamMenuEvent, _| self.param_menu.event(cx, e));
+
+        event.map(|e: &ParamFocusEvent, _| match *e {
+            ParamFocusEvent::Focused { ptr, x, y } => {
+                self.keyboard_focus = Some((ptr, x, y));
+                // Pinned in the readout too, which screen readers announce.
+                self.last_touched = Some(ptr);
+                // SAFETY: every ParamPtr in this editor comes from
+                // `self.params`, which outlives the editor.
+                let value = unsafe { ptr.unmodulated_normalized_value() };
+                self.param_readout = param_readout_text(ptr, value);
+            }
+            ParamFocusEvent::Blurred(ptr) => {
+                if self
+                    .keyboard_focus
+                    .is_some_and(|(focused, ..)| focused == ptr)
+                {
+                    self.keyboard_focus = None;
+                }
+            }
+        });
 
-        let mut order: [ModuleType; 7] = [
-            self.params.module_order_1.value(),
-            self.params.module_order_2.value(),
-            self.params.module_order_3.value(),
-            self.params.module_order_4.value(),
-            self.params.module_order_5.value(),
-            self.params.module_order_6.value(),
-            self.params.module_order_7.value(),
-        ];
+        #[cfg(feature = "osc")]
+        event.map(|e: &RemoteChanges, _| self.apply_remote_values(cx, &e.0));
 
-        match position {
-            DropPos::Onto => {
-                order.swap(src, tgt);
+        event.map(|e: &HistoryEvent, _| match e {
+            HistoryEvent::Undo => {
+                if let Some(values) = self.history.undo() {
+                    replay_history(cx, values);
+                }
             }
-            DropPos::Before | DropPos::After => {
-                let moving = order[src];
-                // Remove src, then insert at the resolved index.
-                // After removal, indices > src shift down by one — the
-                // insert index must account for this.
-                let raw_insert = match position {
-                    DropPos::Before => tgt,
-                    DropPos::After => tgt + 1,
-                    DropPos::Onto => unreachable!(),
+            HistoryEvent::Redo => {
+                if let Some(values) = self.history.redo() {
+                    replay_history(cx, values);
+                }
+            }
+            HistoryEvent::GroupBegin => self.history.begin_group(),
+            HistoryEvent::GroupEnd => self.history.end_group(),
+            HistoryEvent::ReplayBegin => self.history.set_replaying(true),
+            HistoryEvent::ReplayEnd => self.history.set_replaying(false),
+        });
+
+        event.map(|e: &MatchEvent, _| match e {
+            MatchEvent::SetReferencePath(path) => {
+                self.match_reference_path = path.clone();
+            }
+            MatchEvent::LoadReference => {
+                let path = self.match_reference_path.trim();
+                if !path.is_empty() {
+                    self.executor
+                        .execute_background(crate::BackgroundTask::LoadMatchReference(
+                            PathBuf::from(path),
+                        ));
+                    self.match_status = "Loading reference\u{2026}".to_owned();
+                }
+            }
+            MatchEvent::UseSidechain => {
+                self.match_data.clear_file_reference();
+                self.match_reference_name = "SIDECHAIN".to_owned();
+            }
+            MatchEvent::Capture => {
+                self.match_data.request_capture();
+                self.match_suggestion = None;
+                self.match_status = "Capturing\u{2026}".to_owned();
+            }
+            MatchEvent::Apply => {
+                if let Some(suggestion) = self.match_suggestion {
+                    self.apply_match(cx, &suggestion);
+                }
+            }
+        });
+
+        #[cfg(any(feature = "api5500", feature = "pultec"))]
+        event.map(|e: &ResponseEvent, _| match e {
+            ResponseEvent::SetPath(path) => {
+                self.response_path = path.clone();
+            }
+            ResponseEvent::Export(format) => {
+                if !self.response_path.trim().is_empty() {
+                    let path = response::export_path(&self.response_path, *format);
+                    self.response_path = path.to_string_lossy().into_owned();
+                    self.executor
+                        .execute_background(crate::BackgroundTask::ExportResponse(path, *format));
+                    self.response_status = "Exporting\u{2026}".to_owned();
+                }
+            }
+        });
+
+        #[cfg(feature = "punch")]
+        event.map(|e: &LoudnessEvent, _| match e {
+            LoudnessEvent::StepTarget(step) => {
+                let target = (self.settings.loudness_target + step).clamp(
+                    loudness_target::MIN_TARGET_LUFS,
+                    loudness_target::MAX_TARGET_LUFS,
+                );
+                self.update_settings(|s| s.loudness_target = target);
+                // The suggestion was worked out for the old target.
+                if self.loudness_suggestion.take().is_some() {
+                    self.loudness_status = "Target changed \u{2014} LEARN again".to_owned();
+                }
+            }
+            LoudnessEvent::Learn => {
+                self.loudness_learn.request_learn();
+                self.loudness_suggestion = None;
+                self.loudness_status = "Learning\u{2026} play the program".to_owned();
+            }
+            LoudnessEvent::Apply => {
+                if let Some(suggestion) = self.loudness_suggestion {
+                    self.apply_loudness(cx, &suggestion);
+                }
+            }
+            LoudnessEvent::ToggleAutoApply => {
+                self.update_settings(|s| s.loudness_auto_apply = !s.loudness_auto_apply)
+            }
+        });
+
+        event.map(|e: &VariationEvent, _| match e {
+            VariationEvent::SetSeed(text) => {
+                self.variation_seed = text.clone();
+            }
+            VariationEvent::Vary(scope) => {
+                let seed = self.variation_seed.trim().parse().unwrap_or(0);
+                let seed = variation::next_seed(seed);
+                self.variation_seed = seed.to_string();
+                self.variation_scope = *scope;
+                self.apply_variation(cx, seed, *scope);
+            }
+            VariationEvent::ApplySeed => {
+                if let Ok(seed) = self.variation_seed.trim().parse() {
+                    self.apply_variation(cx, seed, self.variation_scope);
+                }
+            }
+        });
+
+        event.map(|e: &PresetEvent, _| match e {
+            PresetEvent::ToggleBrowser => {
+                self.preset_browser_open = !self.preset_browser_open;
+                if self.preset_browser_open {
+                    self.executor.execute_background(PresetTask::Rescan.into());
+                } else {
+                    self.preset_save_open = false;
+                }
+            }
+            PresetEvent::SetSearch(text) => {
+                self.preset_search = text.clone();
+            }
+            PresetEvent::Load(id) => {
+                let preset = match id {
+                    PresetId::Factory(idx) => self.factory_presets.get(*idx).cloned(),
+                    PresetId::User(path) => self
+                        .preset_library
+                        .user_presets()
+                        .into_iter()
+                        .find(|p| p.path.as_ref() == Some(path)),
                 };
-                let mut tail: [ModuleType; 6] = [ModuleType::Empty; 6];
-                let mut j = 0;
-                for (i, &mt) in order.iter().enumerate() {
-                    if i == src {
-                        continue;
-                    }
-                    tail[j] = mt;
-                    j += 1;
+                if let Some(preset) = preset {
+                    self.apply_param_values(cx, &preset.values);
+                    self.current_preset_name = preset.name;
+                    // Same reasoning as LoadChain: show the new state as the
+                    // rack overview, not whatever was focused before.
+                    self.drag_source = None;
+                    self.drop_target = None;
+                    self.view = self.view.next(ViewInput::Unfocus);
                 }
-                let insert = raw_insert
-                    .min(6)
-                    .saturating_sub(if raw_insert > src { 1 } else { 0 });
-                let mut rebuilt = [ModuleType::Empty; 7];
-                let mut k = 0;
-                for (i, &mt) in tail.iter().enumerate() {
-                    if i == insert {
-                        rebuilt[k] = moving;
-                        k += 1;
-                    }
-                    rebuilt[k] = mt;
-                    k += 1;
+            }
+            PresetEvent::OpenSave => {
+                self.preset_save_name = self.current_preset_name.clone();
+                self.preset_save_open = true;
+            }
+            PresetEvent::SetSaveName(name) => {
+                self.preset_save_name = name.clone();
+            }
+            PresetEvent::ConfirmSave => {
+                let name = self.preset_save_name.trim().to_owned();
+                if !name.is_empty() {
+                    self.executor.execute_background(
+                        PresetTask::Save {
+                            name: name.clone(),
+                            category: "User".to_owned(),
+                            values: presets::capture(self.params.as_ref()),
+                        }
+                        .into(),
+                    );
+                    self.current_preset_name = name;
+                    self.preset_save_open = false;
                 }
-                if k < 7 {
-                    rebuilt[k] = moving;
+            }
+            PresetEvent::CancelSave => {
+                self.preset_save_open = false;
+            }
+            PresetEvent::Delete(path) => {
+                self.executor
+                    .execute_background(PresetTask::Delete(path.clone()).into());
+            }
+            PresetEvent::ToggleContextPresets => {
+                let enabled = !self.preset_library.context_presets();
+                self.executor
+                    .execute_background(PresetTask::SetContextPresets(enabled).into());
+            }
+            PresetEvent::ExportChain => {
+                let name = if self.current_preset_name.is_empty() {
+                    "Chain".to_owned()
+                } else {
+                    self.current_preset_name.clone()
+                };
+                // Captured now: the dialog may stay open while the
+                // params move.
+                self.executor.execute_background(
+                    PresetTask::ExportChain {
+                        name,
+                        values: presets::capture(self.params.as_ref()),
+                    }
+                    .into(),
+                );
+            }
+            PresetEvent::ImportChain => {
+                self.executor
+                    .execute_background(PresetTask::ImportChain.into());
+            }
+            PresetEvent::Poll => {
+                let generation = self.preset_library.generation();
+                if generation != self.preset_generation {
+                    self.preset_generation = generation;
+                    self.context_presets = self.preset_library.context_presets();
+                    if let Some(chain) = self.preset_library.take_imported() {
+                        self.apply_param_values(cx, &chain.values);
+                        self.current_preset_name = chain.name;
+                        // Same as Load: show the imported chain as the rack.
+                        self.drag_source = None;
+                        self.drop_target = None;
+                        self.view = self.view.next(ViewInput::Unfocus);
+                    }
                 }
-                order = rebuilt;
             }
-        }
+        });
+    }
+}
 
-        // Write back only the slots that actually changed.
-        let before: [ModuleType; 7] = [
-            self.params.module_order_1.value(),
-            self.params.module_order_2.value(),
-            self.params.module_order_3.value(),
-            self.params.module_order_4.value(),
-            self.params.module_order_5.value(),
-            self.params.module_order_6.value(),
-            self.params.module_order_7.value(),
-        ];
-        for slot in 0..7usize {
-            if before[slot] == order[slot] {
+impl Data {
+    /// Write a preset or A/B snapshot into the host-visible params. Every
+    /// preset-relevant param is written (missing entries fall back to their
+    /// default) so the result doesn't depend on what was loaded before.
+    fn apply_param_values(&self, cx: &mut EventContext, values: &BTreeMap<String, f32>) {
+        cx.emit(HistoryEvent::GroupBegin);
+        for (id, ptr, _group) in self.params.param_map() {
+            if !presets::is_preset_param(&id) {
                 continue;
             }
-            let ptr = slot_param_ptr(&self.params, slot);
-            let norm = slot_preview_normalized(&self.params, slot, order[slot]);
+            // SAFETY: ParamPtr is taken from `self.params` (Arc'd, outlives
+            // the editor).
+            let norm = unsafe {
+                match values.get(&id) {
+                    Some(&plain) => ptr.preview_normalized(plain),
+                    None => ptr.default_normalized_value(),
+                }
+            };
             cx.emit(RawParamEvent::BeginSetParameter(ptr));
             cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
             cx.emit(RawParamEvent::EndSetParameter(ptr));
         }
+        cx.emit(HistoryEvent::GroupEnd);
     }
+
+    /// Draw a variation and write it as one undo step. Only the drawn params
+    /// are touched; everything else keeps its current value.
+    fn apply_variation(&self, cx: &mut EventContext, seed: u32, scope: Option<ModuleType>) {
+        let map = self.params.param_map();
+        let values = variation::generate(seed, scope, map.iter().map(|(id, _, _)| id.as_str()));
+        cx.emit(HistoryEvent::GroupBegin);
+        for (id, ptr, _group) in &map {
+            let Some(&plain) = values.get(id) else {
+                continue;
+            };
+            // SAFETY: ParamPtr is taken from `self.params` (Arc'd, outlives
+            // the editor).
+            let norm = unsafe { ptr.preview_normalized(plain) };
+            cx.emit(RawParamEvent::BeginSetParameter(*ptr));
+            cx.emit(RawParamEvent::SetParameterNormalized(*ptr, norm));
+            cx.emit(RawParamEvent::EndSetParameter(*ptr));
+        }
+        cx.emit(HistoryEvent::GroupEnd);
+    }
+
+    /// Write parameter changes that came over OSC. They go through the same
+    /// host-notifying path as a knob, but stay out of the undo history: the
+    /// surface that sent them is its own undo.
+    #[cfg(feature = "osc")]
+    fn apply_remote_values(
+        &self,
+        cx: &mut EventContext,
+        changes: &[(String, crate::osc::OscValue)],
+    ) {
+        use crate::osc::OscValue;
+
+        cx.emit(HistoryEvent::ReplayBegin);
+        for (id, ptr, _group) in self.params.param_map() {
+            let Some((_, value)) = changes.iter().find(|(changed, _)| *changed == id) else {
+                continue;
+            };
+            let norm = match *value {
+                OscValue::Normalized(norm) => norm,
+                // SAFETY: ParamPtr is taken from `self.params` (Arc'd,
+                // outlives the editor).
+                OscValue::Plain(plain) => unsafe { ptr.preview_normalized(plain) },
+            };
+            cx.emit(RawParamEvent::BeginSetParameter(ptr));
+            cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
+            cx.emit(RawParamEvent::EndSetParameter(ptr));
+        }
+        cx.emit(HistoryEvent::ReplayEnd);
+    }
+
+    /// Copy the dynamics statistics into the bridge's readouts.
+    fn poll_dynamics(&mut self) {
+        use spectral::DynamicsStat;
+        for stat in [
+            DynamicsStat::InputCrest,
+            DynamicsStat::OutputCrest,
+            DynamicsStat::Psr,
+            DynamicsStat::Plr,
+            DynamicsStat::Spread,
+        ] {
+            self.dynamics_readings[stat as usize] = self.dynamics.load(stat);
+        }
+    }
+
+    fn poll_engagement(&mut self) {
+        use spectral::EngagementStage;
+        for stage in [
+            EngagementStage::PultecTube,
+            EngagementStage::Transformer,
+            EngagementStage::PunchClip,
+        ] {
+            self.engagement_readings[stage as usize] = self.engagement.load(stage);
+        }
+    }
+
+    /// Pick up a finished transformer probe. Probes only run when the
+    /// settings change, so this is one atomic load on most ticks.
+    fn poll_harmonics(&mut self) {
+        let generation = self.harmonics.generation();
+        if generation != self.harmonic_generation {
+            self.harmonic_generation = generation;
+            (self.transformer_thd, self.transformer_even_share) = self.harmonics.load();
+        }
+    }
+
+    /// Pick up a finished match capture or WAV reference load.
+    fn poll_match(&mut self) {
+        let generation = self.match_data.file_generation();
+        if generation != self.match_file_generation {
+            self.match_file_generation = generation;
+            match matching::ReferenceError::from_code(self.match_data.file_status()) {
+                Some(err) => self.match_status = err.message().to_owned(),
+                None => {
+                    self.match_reference_name = Path::new(self.match_reference_path.trim())
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    self.match_status = "Reference loaded \u{2014} CAPTURE the program".to_owned();
+                }
+            }
+        }
+        if self.match_data.take_capture_done() {
+            self.match_suggestion =
+                matching::suggest_api5500(&self.match_data.reference(), &self.match_data.program());
+            self.match_status = match &self.match_suggestion {
+                Some(suggestion) => format_match_suggestion(suggestion),
+                None => "No signal to match".to_owned(),
+            };
+        }
+    }
+
+    /// Pick up a finished EQ response export.
+    fn poll_response_export(&mut self) {
+        let generation = self.response_export.generation();
+        if generation != self.response_generation {
+            self.response_generation = generation;
+            self.response_status = if self.response_export.written() {
+                "Response written".to_owned()
+            } else {
+                "Couldn't write the file".to_owned()
+            };
+        }
+    }
+
+    /// Pick up a finished learn pass and work out Punch's settings for the
+    /// target. With AUTO lit they go straight in.
+    #[cfg(feature = "punch")]
+    fn poll_loudness_learn(&mut self, cx: &mut EventContext) {
+        let Some((input_lufs, input_peak_db, output_lufs)) = self.loudness_learn.take_learned()
+        else {
+            return;
+        };
+        let levels = LearnedLevels {
+            input_lufs,
+            input_peak_db,
+            output_lufs,
+        };
+        let p = &self.params;
+        let current = PunchSettings {
+            engaged: slot_containing(p, ModuleType::Punch).is_some() && !p.punch_bypass.value(),
+            input_gain_db: p.punch_input_gain.value(),
+            ceiling_db: p.punch_threshold.value(),
+            output_gain_db: p.punch_output_gain.value(),
+        };
+        let target = self.settings.loudness_target as f32;
+        self.loudness_suggestion = loudness_target::suggest(&levels, &current, target);
+        self.loudness_status = match &self.loudness_suggestion {
+            Some(suggestion) => format_loudness_suggestion(suggestion),
+            None => {
+                "No signal at Punch \u{2014} put it in the rack and play the program".to_owned()
+            }
+        };
+        if let (Some(suggestion), true) =
+            (self.loudness_suggestion, self.settings.loudness_auto_apply)
+        {
+            self.apply_loudness(cx, &suggestion);
+        }
+    }
+
+    /// Write a loudness suggestion into Punch and un-bypass it, as one undo
+    /// step.
+    #[cfg(feature = "punch")]
+    fn apply_loudness(&self, cx: &mut EventContext, suggestion: &LoudnessSuggestion) {
+        let p = &self.params;
+        cx.emit(HistoryEvent::GroupBegin);
+        for (ptr, plain) in [
+            (p.punch_bypass.as_ptr(), 0.0),
+            (p.punch_input_gain.as_ptr(), suggestion.input_gain_db),
+            (p.punch_threshold.as_ptr(), suggestion.ceiling_db),
+        ] {
+            // SAFETY: ParamPtr is taken from `self.params` (Arc'd, outlives
+            // the editor).
+            let norm = unsafe { ptr.preview_normalized(plain) };
+            cx.emit(RawParamEvent::BeginSetParameter(ptr));
+            cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
+            cx.emit(RawParamEvent::EndSetParameter(ptr));
+        }
+        cx.emit(HistoryEvent::GroupEnd);
+    }
+
+    /// Write a match suggestion into the API5500 and un-bypass it. The EQ
+    /// also drops into the first empty slot when it isn't in the rack.
+    fn apply_match(&self, cx: &mut EventContext, suggestion: &matching::MatchSuggestion) {
+        let set = |cx: &mut EventContext, ptr: ParamPtr, plain: f32| {
+            // SAFETY: ParamPtr is taken from `self.params` (Arc'd, outlives
+            // the editor).
+            let norm = unsafe { ptr.preview_normalized(plain) };
+            cx.emit(RawParamEvent::BeginSetParameter(ptr));
+            cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
+            cx.emit(RawParamEvent::EndSetParameter(ptr));
+        };
+        let p = &self.params;
+        cx.emit(HistoryEvent::GroupBegin);
+        if slot_containing(p, ModuleType::Api5500EQ).is_none() {
+            if let Some(slot) = first_empty_slot(p) {
+                let ptr = slot_param_ptr(p, slot);
+                let norm = slot_preview_normalized(p, slot, ModuleType::Api5500EQ);
+                cx.emit(RawParamEvent::BeginSetParameter(ptr));
+                cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
+                cx.emit(RawParamEvent::EndSetParameter(ptr));
+            }
+        }
+        set(cx, p.eq_bypass.as_ptr(), 0.0);
+        set(cx, p.lf_freq.as_ptr(), suggestion.lf_freq);
+        set(cx, p.lf_gain.as_ptr(), suggestion.lf_gain_db);
+        set(cx, p.lmf_freq.as_ptr(), suggestion.lmf.freq);
+        set(cx, p.lmf_gain.as_ptr(), suggestion.lmf.gain_db);
+        set(cx, p.lmf_q.as_ptr(), suggestion.q);
+        set(cx, p.mf_freq.as_ptr(), suggestion.mf.freq);
+        set(cx, p.mf_gain.as_ptr(), suggestion.mf.gain_db);
+        set(cx, p.mf_q.as_ptr(), suggestion.q);
+        set(cx, p.hmf_freq.as_ptr(), suggestion.hmf.freq);
+        set(cx, p.hmf_gain.as_ptr(), suggestion.hmf.gain_db);
+        set(cx, p.hmf_q.as_ptr(), suggestion.q);
+        set(cx, p.hf_freq.as_ptr(), suggestion.hf_freq);
+        set(cx, p.hf_gain.as_ptr(), suggestion.hf_gain_db);
+        cx.emit(HistoryEvent::GroupEnd);
+    }
+
+    /// Change the editor settings and write them to the persisted blob.
+    fn update_settings(&mut self, change: impl FnOnce(&mut EditorSettings)) {
+        change(&mut self.settings);
+        if let Ok(mut persisted) = self.params.editor_settings.write() {
+            *persisted = self.settings;
+        }
+    }
+
+    /// Focus a slot ONLY if it holds a real module. Empty slots silently
+    /// stay unfocused — focusing one would collapse every real slot via
+    /// the "any-other-focused → collapsed" render rule, leaving nothing
+    /// to inspect.
+    fn focus_if_real(&mut self, idx: usize) {
+        if slot_module_type(&self.params, idx) != ModuleType::Empty {
+            self.view = self.view.next(ViewInput::Focus(idx));
+            self.drag_source = None;
+            self.drop_target = None;
+        }
+    }
+
+    /// Arrow-key nudge of the last touched param, one step in `direction`.
+    /// Knobs handle their own arrows while focused; this covers the rest.
+    /// Hand `code` to the control holding keyboard focus, if any. False when
+    /// nothing is focused or the key isn't one of its adjustment keys.
+    fn adjust_focused(&self, cx: &mut EventContext, code: Code) -> bool {
+        self.keyboard_focus
+            .is_some_and(|(ptr, x, y)| components::keyboard_adjust(cx, ptr, code, x, y))
+    }
+
+    fn nudge_last_touched(&self, cx: &mut EventContext, direction: f32) {
+        let Some(ptr) = self.last_touched else {
+            return;
+        };
+        let step = components::nudge_step(ptr, cx.modifiers().shift());
+        // SAFETY: every ParamPtr in this editor comes from `self.params`,
+        // which outlives the editor.
+        let current = unsafe { ptr.unmodulated_normalized_value() };
+        cx.emit(RawParamEvent::BeginSetParameter(ptr));
+        cx.emit(RawParamEvent::SetParameterNormalized(
+            ptr,
+            (current + direction * step).clamp(0.0, 1.0),
+        ));
+        cx.emit(RawParamEvent::EndSetParameter(ptr));
+    }
+
+    /// Apply a reorder operation against the seven `module_order_*` params
+    /// in one event frame. `position` decides semantics:
+    ///   • `Onto`   → swap src ↔ tgt (two slots change)
+    ///   • `Before` → remove src, insert before tgt (rotation; up to 7 change)
+    ///   • `After`  → remove src, insert after tgt (rotation; up to 7 change)
+    ///
+    /// Reads the current order, computes the new order in-memory, then
+    /// writes back any slot that actually changed. This minimises host
+    /// preset diff size and avoids spurious automation events.
+    fn reorder(&self, cx: &mut EventContext, src: usize, tgt: usize, position: DropPos) {
+        if src == tgt && position == DropPos::Onto {
+            return; // self-drop, nothing to do
+        }
+
+        let mut order: [ModuleType; 7] = [
+            self.params.module_order_1.value(),
+            self.params.module_order_2.value(),
+            self.params.module_order_3.value(),
+            self.params.module_order_4.value(),
+            self.params.module_order_5.value(),
+            self.params.module_order_6.value(),
+            self.params.module_order_7.value(),
+        ];
+
+        match position {
+            DropPos::Onto => {
+                order.swap(src, tgt);
+            }
+            DropPos::Before | DropPos::After => {
+                let moving = order[src];
+                // Remove src, then insert at the resolved index.
+                // After removal, indices > src shift down by one — the
+                // insert index must account for this.
+                let raw_insert = match position {
+                    DropPos::Before => tgt,
+                    DropPos::After => tgt + 1,
+                    DropPos::Onto => unreachable!(),
+                };
+                let mut tail: [ModuleType; 6] = [ModuleType::Empty; 6];
+                let mut j = 0;
+                for (i, &mt) in order.iter().enumerate() {
+                    if i == src {
+                        continue;
+                    }
+                    tail[j] = mt;
+                    j += 1;
+                }
+                let insert = raw_insert
+                    .min(6)
+                    .saturating_sub(if raw_insert > src { 1 } else { 0 });
+                let mut rebuilt = [ModuleType::Empty; 7];
+                let mut k = 0;
+                for (i, &mt) in tail.iter().enumerate() {
+                    if i == insert {
+                        rebuilt[k] = moving;
+                        k += 1;
+                    }
+                    rebuilt[k] = mt;
+                    k += 1;
+                }
+                if k < 7 {
+                    rebuilt[k] = moving;
+                }
+                order = rebuilt;
+            }
+        }
+
+        // Write back only the slots that actually changed — as one undo step.
+        let before: [ModuleType; 7] = [
+            self.params.module_order_1.value(),
+            self.params.module_order_2.value(),
+            self.params.module_order_3.value(),
+            self.params.module_order_4.value(),
+            self.params.module_order_5.value(),
+            self.params.module_order_6.value(),
+            self.params.module_order_7.value(),
+        ];
+        cx.emit(HistoryEvent::GroupBegin);
+        for slot in 0..7usize {
+            if before[slot] == order[slot] {
+                continue;
+            }
+            let ptr = slot_param_ptr(&self.params, slot);
+            let norm = slot_preview_normalized(&self.params, slot, order[slot]);
+            cx.emit(RawParamEvent::BeginSetParameter(ptr));
+            cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
+            cx.emit(RawParamEvent::EndSetParameter(ptr));
+        }
+        cx.emit(HistoryEvent::GroupEnd);
+    }
+}
+
+/// Write undo/redo values back as gestures, bracketed by replay markers so
+/// the history doesn't record its own playback.
+fn replay_history(cx: &mut EventContext, values: Vec<(ParamPtr, f32)>) {
+    cx.emit(HistoryEvent::ReplayBegin);
+    for (ptr, norm) in values {
+        cx.emit(RawParamEvent::BeginSetParameter(ptr));
+        cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
+        cx.emit(RawParamEvent::EndSetParameter(ptr));
+    }
+    cx.emit(HistoryEvent::ReplayEnd);
 }
 
 /// Hit-test cursor X within a slot's bounds → DropPos.
@@ -541,6 +1603,14 @@ fn hit_test_drop_pos(cursor_x: f32, bounds: BoundingBox) -> DropPos {
     }
 }
 
+/// Label text in the editor language. English strings are the keys (see
+/// `i18n.rs`); the lens follows `Data::language`, so a language switch
+/// relabels the open editor in place.
+pub(crate) fn tr(text: &str) -> impl Lens<Target = String> {
+    let text = text.to_owned();
+    Data::language.map(move |language| i18n::translate(*language, &text).to_owned())
+}
+
 // ============================================================================
 // Module Order Helpers
 // ============================================================================
@@ -789,6 +1859,24 @@ fn is_module_hidden(params: &Arc<BusChannelStripParams>, mt: ModuleType) -> bool
     }
 }
 
+/// Per-module bypass flag, as the audio thread reads it. Modules compiled
+/// out by their feature flag count as bypassed.
+fn is_module_bypassed(params: &Arc<BusChannelStripParams>, mt: ModuleType) -> bool {
+    match mt {
+        ModuleType::Api5500EQ => params.eq_bypass.value(),
+        ModuleType::ButterComp2 => params.comp_bypass.value(),
+        ModuleType::PultecEQ => params.pultec_bypass.value(),
+        #[cfg(feature = "dynamic_eq")]
+        ModuleType::DynamicEQ => params.dyneq_bypass.value(),
+        ModuleType::Transformer => params.transformer_bypass.value(),
+        #[cfg(feature = "punch")]
+        ModuleType::Punch => params.punch_bypass.value(),
+        #[cfg(feature = "haas")]
+        ModuleType::Haas => params.haas_bypass.value(),
+        _ => true,
+    }
+}
+
 /// Short 3-char tag for the collapsed tab. Keeps the narrow strip legible
 /// without overflowing the tab width.
 fn module_type_short_name(mt: ModuleType) -> &'static str {
@@ -905,13 +1993,24 @@ fn build_expand_button_for_type(cx: &mut Context, mt: ModuleType) {
 fn build_eject_button(cx: &mut Context, slot_idx: usize) {
     HStack::new(cx, |cx| {
         Label::new(cx, "\u{2715}").class("eject-btn-glyph"); // ✕
-        Label::new(cx, "REMOVE").class("eject-btn-label");
+        Label::new(cx, tr("REMOVE")).class("eject-btn-label");
     })
     .class("eject-btn")
     .on_press(move |cx| cx.emit(AppEvent::SetSlotModule(slot_idx, ModuleType::Empty)))
     .cursor(CursorIcon::Hand);
 }
 
+/// Module-header VARY: redraws this module's continuous controls from the
+/// next seed (see `variation.rs`).
+fn build_vary_button(cx: &mut Context, mt: ModuleType) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr("VARY")).class("eject-btn-label");
+    })
+    .class("eject-btn")
+    .on_press(move |cx| cx.emit(VariationEvent::Vary(Some(mt))))
+    .cursor(CursorIcon::Hand);
+}
+
 /// One-shot repair for saved sessions whose `module_order_*` values now collide
 /// on a real module (e.g. older schema migrations). Walks slots 0..7, finds
 /// duplicated *real* module types, and rewrites later duplicates to
@@ -956,6 +2055,42 @@ fn repair_module_order(cx: &mut Context, params: &Arc<BusChannelStripParams>) {
     }
 }
 
+/// Apply the first-instantiation preset picked in `initialize()` and return
+/// its name. Runs at most once per instance: the state settles whether or
+/// not the strip was still untouched. See host_context.rs.
+fn apply_context_preset(
+    cx: &mut Context,
+    params: &Arc<BusChannelStripParams>,
+    factory: &[Preset],
+) -> Option<String> {
+    let flavor = {
+        let mut state = params.context_preset.write().ok()?;
+        let ContextPreset::Pending(flavor) = *state else {
+            return None;
+        };
+        *state = ContextPreset::Settled;
+        flavor
+    };
+    if !host_context::is_untouched(params.as_ref()) {
+        return None;
+    }
+    let preset = factory.iter().find(|p| p.name == flavor.preset_name())?;
+    // Everything else is still at its default, so only the listed values
+    // need writing.
+    for (id, ptr, _group) in params.param_map() {
+        let Some(&plain) = preset.values.get(&id) else {
+            continue;
+        };
+        // SAFETY: ParamPtr is taken from `params` (Arc'd, outlives the
+        // editor).
+        let norm = unsafe { ptr.preview_normalized(plain) };
+        cx.emit(RawParamEvent::BeginSetParameter(ptr));
+        cx.emit(RawParamEvent::SetParameterNormalized(ptr, norm));
+        cx.emit(RawParamEvent::EndSetParameter(ptr));
+    }
+    Some(preset.name.clone())
+}
+
 fn module_type_subtitle(mt: ModuleType) -> &'static str {
     match mt {
         ModuleType::Api5500EQ => "3-BAND EQ",
@@ -1007,26 +2142,85 @@ pub(crate) fn default_state() -> Arc<ViziaState> {
     ViziaState::new_with_default_scale_factor(|| (DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT), 1.0)
 }
 
+/// How often the editor checks the preset library for finished background
+/// tasks. Only an atomic load per tick; the list rebuilds only on change.
+const PRESET_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long a slot holds its "settling" class after a module is inserted or
+/// removed. The CSS transition back to rest is the visible animation.
+const SLOT_SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// Refresh rate of the metering bridge's numeric loudness readouts. The
+/// graphical meters redraw every frame; text faster than this is unreadable.
+const METER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often OSC changes are picked up: fast enough for a fader on a
+/// control surface, and an uncontended lock when nothing arrived.
+#[cfg(feature = "osc")]
+const OSC_POLL_INTERVAL: Duration = Duration::from_millis(15);
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn create(
     params: Arc<BusChannelStripParams>,
     editor_state: Arc<ViziaState>,
+    executor: AsyncExecutor<BusChannelStrip>,
+    preset_library: Arc<PresetLibrary>,
+    output_level: Arc<spectral::OutputLevelData>,
+    transformer_sat: Arc<spectral::SaturationMeterData>,
+    scope_data: Arc<spectral::StereoScopeData>,
+    gr_history: Arc<Mutex<spectral::GrHistoryData>>,
+    punch_clip: Arc<spectral::SaturationMeterData>,
+    loudness: Arc<spectral::LoudnessData>,
+    dynamics: Arc<spectral::DynamicsData>,
+    engagement: Arc<spectral::EngagementData>,
+    harmonics: Arc<spectral::HarmonicData>,
+    response_export: Arc<spectral::ResponseExportData>,
+    #[cfg(feature = "punch")] loudness_learn: Arc<spectral::LoudnessLearnData>,
+    stage_levels: Arc<spectral::StageLevelData>,
+    match_data: Arc<spectral::MatchData>,
     spectrum_data: Arc<spectral::SpectrumData>,
     analysis_requested: Arc<AtomicBool>,
     analysis_result: Arc<spectral::AnalysisResult>,
     gr_data: Arc<spectral::GainReductionData>,
+    print_active: Arc<AtomicBool>,
+    comp_degraded: Arc<AtomicBool>,
+    safety_fault: Arc<AtomicBool>,
+    meter_rate_hz: Arc<AtomicU32>,
+    #[cfg(feature = "osc")] osc_inbox: Arc<crate::osc::OscInbox>,
 ) -> Option<Box<dyn Editor>> {
     create_vizia_editor(editor_state, ViziaTheming::Custom, move |cx, _| {
         cx.add_stylesheet(COMPONENT_STYLES)
             .expect("Failed to add stylesheet");
+        // Every skin's overrides are loaded up front; they only apply under
+        // their root class, so switching skins is a class toggle.
+        for info in styles::SKINS
+            .iter()
+            .filter(|info| !info.stylesheet.is_empty())
+        {
+            cx.add_stylesheet(info.stylesheet)
+                .expect("Failed to add skin stylesheet");
+        }
 
+        let settle_timer = cx.add_timer(SLOT_SETTLE_TIME, Some(SLOT_SETTLE_TIME), |cx, action| {
+            if let TimerAction::Stop = action {
+                cx.emit(AppEvent::SlotsSettled);
+            }
+        });
+
+        let settings = params
+            .editor_settings
+            .read()
+            .map(|s| *s)
+            .unwrap_or_default();
+        let factory_presets = presets::factory_presets();
+        let context_preset = apply_context_preset(cx, &params, &factory_presets);
         Data {
             params: params.clone(),
             drag_source: None,
             drop_target: None,
             cursor_x: 0.0,
             cursor_y: 0.0,
-            dyneq_open: false,
-            sheen_open: false,
+            view: EditorView::Rack,
             dyneq_band_expand: Arc::new([
                 AtomicBool::new(false),
                 AtomicBool::new(false),
@@ -1036,20 +2230,129 @@ pub(crate) fn create(
             dyneq_expand_gen: 0,
             analysis_requested: analysis_requested.clone(),
             analysis_result: analysis_result.clone(),
-            zoom_level: 100,
-            focused_slot: None,
+            spectrum_data: spectrum_data.clone(),
+            spectrum_snapshot_mask: params
+                .analyzer_snapshots
+                .read()
+                .map(|s| s.held_mask())
+                .unwrap_or(0),
+            zoom_level: match settings.zoom {
+                level @ (75 | 100 | 125 | 150 | 200) => level,
+                _ => 100,
+            },
+            settings,
+            skin: params.editor_skin.read().map(|s| *s).unwrap_or_default(),
+            language: params
+                .editor_language
+                .read()
+                .map(|l| *l)
+                .unwrap_or_default(),
+            meter_rate: params.meter_rate.read().map(|r| *r).unwrap_or_default(),
+            meter_rate_hz: meter_rate_hz.clone(),
+            slot_settling: 0,
+            advanced_open: 0,
+            slot_built: [usize::MAX; 7],
+            settle_timer,
+            executor: executor.clone(),
+            preset_library: preset_library.clone(),
+            factory_presets: Arc::new(factory_presets),
+            preset_generation: preset_library.generation(),
+            preset_browser_open: false,
+            context_presets: preset_library.context_presets(),
+            preset_search: String::new(),
+            preset_save_open: false,
+            preset_save_name: String::new(),
+            current_preset_name: context_preset.unwrap_or_default(),
+            ab: AbCompare::new(),
+            history: UndoHistory::new(),
+            param_readout: String::new(),
+            param_menu: ParamMenuState::default(),
+            last_touched: None,
+            keyboard_focus: None,
+            output_level: output_level.clone(),
+            transformer_sat: transformer_sat.clone(),
+            scope_data: scope_data.clone(),
+            gr_history: gr_history.clone(),
+            punch_clip: punch_clip.clone(),
+            loudness: loudness.clone(),
+            stage_levels: stage_levels.clone(),
+            gr_data: gr_data.clone(),
+            print_active: print_active.clone(),
+            comp_degraded: comp_degraded.clone(),
+            safety_fault: safety_fault.clone(),
+            lufs_momentary: loudness.momentary(),
+            lufs_short_term: loudness.short_term(),
+            lufs_integrated: loudness.integrated(),
+            dynamics: dynamics.clone(),
+            dynamics_readings: [None; spectral::DYNAMICS_STATS],
+            engagement: engagement.clone(),
+            engagement_readings: [0.0; spectral::ENGAGEMENT_STAGES],
+            harmonic_generation: harmonics.generation(),
+            transformer_thd: harmonics.load().0,
+            transformer_even_share: harmonics.load().1,
+            harmonics: harmonics.clone(),
+            match_data: match_data.clone(),
+            match_reference_path: String::new(),
+            match_reference_name: if match_data.has_file_reference() {
+                "FILE".to_owned()
+            } else {
+                "SIDECHAIN".to_owned()
+            },
+            match_status: String::new(),
+            match_suggestion: None,
+            match_file_generation: match_data.file_generation(),
+            response_generation: response_export.generation(),
+            response_export: response_export.clone(),
+            response_path: String::new(),
+            response_status: String::new(),
+            #[cfg(feature = "punch")]
+            loudness_learn: loudness_learn.clone(),
+            #[cfg(feature = "punch")]
+            loudness_suggestion: None,
+            loudness_status: String::new(),
+            variation_seed: "1".to_owned(),
+            variation_scope: None,
         }
         .build(cx);
 
+        // Populate the user bank once up front, then poll for completed
+        // background tasks (scan/save/delete) so the browser stays current.
+        executor.execute_background(PresetTask::Rescan.into());
+        let preset_poll = cx.add_timer(PRESET_POLL_INTERVAL, None, |cx, action| {
+            if let TimerAction::Tick(_) = action {
+                cx.emit(PresetEvent::Poll);
+            }
+        });
+        cx.start_timer(preset_poll);
+        let meter_poll = cx.add_timer(METER_POLL_INTERVAL, None, |cx, action| {
+            if let TimerAction::Tick(_) = action {
+                cx.emit(AppEvent::MeterTick);
+            }
+        });
+        cx.start_timer(meter_poll);
+        #[cfg(feature = "osc")]
+        {
+            let osc_inbox = osc_inbox.clone();
+            let osc_poll = cx.add_timer(OSC_POLL_INTERVAL, None, move |cx, action| {
+                if let TimerAction::Tick(_) = action {
+                    let changes = osc_inbox.take_fresh();
+                    if !changes.is_empty() {
+                        cx.emit(RemoteChanges(changes));
+                    }
+                }
+            });
+            cx.start_timer(osc_poll);
+        }
+
         // Heal duplicate module_order_* assignments left over from sessions
         // saved under an older schema (fewer slots). When slot N defaults to
         // a type already occupied by an earlier slot, overwrite it with the
         // missing module type so every slot shows a unique module.
         repair_module_order(cx, &params);
 
-        VStack::new(cx, |cx| {
+        let mut chassis = VStack::new(cx, |cx| {
             // ── Chassis header ──────────────────────────────────────────────
-            // Three-zone band: brand title (left) | signal-flow hint (center,
+            // Three-zone band: brand title (left) | chain presets (center,
             // flexible) | zoom + master (right). The inner pills share the
             // same translucent fill so the whole header reads as one gradient
             // surface rather than a row of clunky boxes.
@@ -1069,17 +2372,20 @@ pub(crate) fn create(
                 // parent — wherever the click lands inside the plate it
                 // emits OpenSheen.
                 HStack::new(cx, |cx| {
-                    Label::new(cx, "API")
+                    Label::new(cx, tr("API"))
                         .class("chassis-brand")
                         .on_press(|cx| cx.emit(AppEvent::OpenSheen))
                         .cursor(CursorIcon::Hand);
-                    Label::new(cx, "Bus Channel Strip")
+                    Label::new(cx, tr("Bus Channel Strip"))
                         .class("chassis-title")
                         .on_press(|cx| cx.emit(AppEvent::OpenSheen))
                         .cursor(CursorIcon::Hand);
                 })
                 .class("brand-plate-brass")
-                .toggle_class("brand-plate-active", Data::sheen_open.map(|s| *s))
+                .toggle_class(
+                    "brand-plate-active",
+                    Data::view.map(|v| v.is_open(Panel::Sheen)),
+                )
                 .on_press(|cx| cx.emit(AppEvent::OpenSheen))
                 .cursor(CursorIcon::Hand)
                 .width(Auto)
@@ -1092,11 +2398,11 @@ pub(crate) fn create(
                 // because the rest of the header is already busy; positioned
                 // next to the brand so users always know where to look.
                 HStack::new(cx, |cx| {
-                    Label::new(cx, "\u{2715} EXIT FOCUS").class("exit-focus-label");
+                    Label::new(cx, tr("\u{2715} EXIT FOCUS")).class("exit-focus-label");
                 })
                 .class("exit-focus-btn")
-                .display(Data::focused_slot.map(|f| {
-                    if f.is_some() {
+                .display(Data::view.map(|v| {
+                    if v.focused_slot().is_some() {
                         Display::Flex
                     } else {
                         Display::None
@@ -1109,11 +2415,26 @@ pub(crate) fn create(
                 .top(Pixels(0.0))
                 .bottom(Pixels(0.0));
 
+                // Preset browser toggle — shows the current preset name
+                // once one has been loaded or saved.
+                build_preset_button(cx);
+
+                // A/B compare — A, B, copy-to-other and match-gain.
+                build_ab_controls(cx);
+
+                // Undo / redo of editor gestures (Ctrl/Cmd+Z, Ctrl/Cmd+Y).
+                build_history_controls(cx);
+
+                // Whole-strip VARY and the seed it used.
+                build_variation_controls(cx);
+
+                // Metering bridge toggle — swaps the rack for the meters.
+                build_meter_bridge_button(cx);
+
                 // Chain preset selector — centered, takes remaining space.
                 // One button per stock chain; clicking writes all 7
-                // module_order_* params atomically. Replaces the old
-                // signal-flow hint text (the rack itself now teaches the
-                // routing model better than a hint sentence could).
+                // module_order_* params atomically. The live signal
+                // flow itself sits in the readout strip at the bottom.
                 build_chain_preset_selector(cx)
                     .left(Stretch(1.0))
                     .right(Stretch(1.0));
@@ -1121,6 +2442,15 @@ pub(crate) fn create(
                 // Zoom control band — discrete 75/100/125/150/200 buttons.
                 create_zoom_controls(cx);
 
+                // Skin selector — one button per registered skin.
+                create_skin_controls(cx);
+
+                // Language selector.
+                create_language_controls(cx);
+
+                // Analyzer and tooltip switches.
+                create_view_controls(cx);
+
                 create_master_section(cx);
             })
             .class("chassis-header")
@@ -1136,6 +2466,10 @@ pub(crate) fn create(
             HStack::new(cx, |cx| {
                 build_library_sidebar(cx);
 
+                // Input conditioning, pinned ahead of slot 1.
+                #[cfg(feature = "conditioning")]
+                build_input_panel(cx);
+
                 ScrollView::new(cx, |cx| {
                     HStack::new(cx, |cx| {
                         for slot_idx in 0..7_usize {
@@ -1157,11 +2491,10 @@ pub(crate) fn create(
             .height(Stretch(1.0))
             .width(Stretch(1.0))
             .gap(Pixels(4.0))
-            // Strip view hides whenever EITHER back view (DynEQ or Sheen)
-            // is open. `OrLens` short-circuits — no need for nested
-            // Bindings or a derived state field.
-            .display(Data::dyneq_open.or(Data::sheen_open).map(|open| {
-                if *open {
+            // Strip view hides whenever a panel (DynEQ or Sheen back view,
+            // meter bridge) covers it.
+            .display(Data::view.map(|v| {
+                if v.panel().is_some() {
                     Display::None
                 } else {
                     Display::Flex
@@ -1181,6 +2514,27 @@ pub(crate) fn create(
             // header opens this; mutually exclusive with the DynEQ back view.
             build_sheen_back_view(cx);
 
+            // ── Metering bridge ─────────────────────────────────────────────
+            // Every meter in one panel for loudness checks on the master
+            // bus. Replaces the rack only while toggled; DSP is unaffected.
+            build_meter_bridge(
+                cx,
+                spectrum_data.clone(),
+                analysis_result.clone(),
+                gr_data.clone(),
+            );
+
+            // ── Readout strip ───────────────────────────────────────────────
+            // Persistent footer naming the last touched parameter, so the
+            // value stays readable after the pointer leaves the control, with
+            // the live signal flow on its right.
+            build_readout_strip(cx);
+
+            // ── Preset browser ──────────────────────────────────────────────
+            // Floating panel over the strip's right edge. Absolute so opening
+            // it never reflows the rack underneath.
+            build_preset_browser(cx);
+
             // ── Floating drag ghost ─────────────────────────────────────────
             // While a drag is in flight, render a small pill next to the
             // cursor showing the dragged module's tag. Position-type Absolute
@@ -1200,6 +2554,10 @@ pub(crate) fn create(
                     .left(Data::cursor_x.map(|x| Pixels(*x + 14.0)))
                     .top(Data::cursor_y.map(|y| Pixels(*y + 14.0)));
             });
+
+            // ── Param context menu ──────────────────────────────────────────
+            // Right-click on any control. Built last so it draws on top.
+            components::build_param_menu(cx, Data::param_menu);
         })
         .class("lunchbox-chassis")
         // Keyboard shortcuts (Esc, 1..7) are routed through
@@ -1217,9 +2575,16 @@ pub(crate) fn create(
         .toggle_class("zoom-125", Data::zoom_level.map(|z| *z == 125))
         .toggle_class("zoom-150", Data::zoom_level.map(|z| *z == 150))
         .toggle_class("zoom-200", Data::zoom_level.map(|z| *z == 200))
+        .toggle_class("tooltips-off", Data::settings.map(|s| !s.tooltips))
         .width(Stretch(1.0))
         .height(Stretch(1.0))
         .padding(Data::zoom_level.map(|z| Pixels(14.0 * (*z as f32) / 100.0)));
+        // Skins are applied the same way as zoom: a root class per skin,
+        // with each skin's stylesheet scoped under it.
+        for info in styles::SKINS {
+            let skin = info.skin;
+            chassis = chassis.toggle_class(info.class, Data::skin.map(move |s| *s == skin));
+        }
         // vizia-plug doesn't support runtime host-window resize
         // (set_user_scale_factor / WindowEvent::SetSize aren't wired into
         // baseview). Zoom rescales content within the fixed window: slot
@@ -1241,7 +2606,7 @@ pub(crate) fn create(
 // inventory-oriented.
 fn build_library_sidebar(cx: &mut Context) {
     VStack::new(cx, |cx| {
-        Label::new(cx, "LIBRARY").class("library-sidebar-header");
+        Label::new(cx, tr("LIBRARY")).class("library-sidebar-header");
 
         // Reactive bitset of which module types are currently in the rack.
         // Rebuilds the row list whenever any slot's contents change.
@@ -1268,18 +2633,22 @@ fn build_library_sidebar(cx: &mut Context) {
                     // Status dot — accent-colored if in rack, dim otherwise.
                     Label::new(cx, if present { "\u{25CF}" } else { "\u{25CB}" })
                         .class("library-row-dot")
-                        .color(if present {
-                            theme.accent_color()
-                        } else {
-                            Color::rgb(90, 96, 108)
-                        });
+                        .color(Data::skin.map(move |skin| {
+                            if present {
+                                theme.accent_color(*skin)
+                            } else {
+                                Color::rgb(90, 96, 108)
+                            }
+                        }));
                     Label::new(cx, tag)
                         .class("library-row-tag")
-                        .color(if present {
-                            theme.accent_color()
-                        } else {
-                            Color::rgb(140, 146, 158)
-                        });
+                        .color(Data::skin.map(move |skin| {
+                            if present {
+                                theme.accent_color(*skin)
+                            } else {
+                                Color::rgb(140, 146, 158)
+                            }
+                        }));
                 })
                 .class("library-row")
                 .toggle_class("library-row-in-rack", present)
@@ -1298,19 +2667,40 @@ fn build_library_sidebar(cx: &mut Context) {
     .gap(Pixels(2.0));
 }
 
-// Chain preset selector — horizontal row of compact buttons in the chassis
-// header. Each button shows a 3-char tag and the full preset name; clicking
-// emits AppEvent::LoadChain(idx) which rewrites module_order_*. Returns the
+// Input conditioning panel: a fixed column between the library and slot 1,
+// mirroring where the stage sits in the signal path. Not a rack slot, so it
+// can't be dragged, hidden or ejected.
+#[cfg(feature = "conditioning")]
+fn build_input_panel(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        Label::new(cx, tr("INPUT")).class("library-sidebar-header");
+        components::create_active_led_button(cx, |p| &p.input_bypass);
+        components::create_param_slider(cx, "HPF", Data::params, |p| &p.input_hpf_freq);
+        components::create_param_slider(cx, "SLOPE", Data::params, |p| &p.input_hpf_slope);
+        components::create_param_slider(cx, "LPF", Data::params, |p| &p.input_lpf_freq);
+        components::create_param_slider(cx, "SLOPE", Data::params, |p| &p.input_lpf_slope);
+        components::create_param_slider(cx, "ROTATE", Data::params, |p| &p.input_rotation);
+        components::create_param_slider(cx, "POLARITY", Data::params, |p| &p.input_polarity);
+    })
+    .class("library-sidebar")
+    .height(Stretch(1.0))
+    .width(Pixels(96.0))
+    .gap(Pixels(6.0));
+}
+
+// Chain preset selector — horizontal row of compact buttons in the chassis
+// header. Each button shows a 3-char tag and the full preset name; clicking
+// emits AppEvent::LoadChain(idx) which rewrites module_order_*. Returns the
 // outer Handle so the caller can attach layout modifiers (Stretch margins,
 // etc.) at the call site.
 fn build_chain_preset_selector(cx: &mut Context) -> Handle<'_, VStack> {
     VStack::new(cx, |cx| {
-        Label::new(cx, "CHAIN PRESETS").class("signal-flow-label");
+        Label::new(cx, tr("CHAIN PRESETS")).class("signal-flow-label");
         HStack::new(cx, |cx| {
             for (i, preset) in CHAIN_PRESETS.iter().enumerate() {
                 VStack::new(cx, |cx| {
                     Label::new(cx, preset.tag).class("chain-preset-tag");
-                    Label::new(cx, preset.name).class("chain-preset-name");
+                    Label::new(cx, tr(preset.name)).class("chain-preset-name");
                 })
                 .class("chain-preset-btn")
                 .on_press(move |cx| cx.emit(AppEvent::LoadChain(i)))
@@ -1333,12 +2723,538 @@ fn build_chain_preset_selector(cx: &mut Context) -> Handle<'_, VStack> {
     .gap(Pixels(4.0))
 }
 
+// Preset browser toggle pill for the chassis header. The label follows the
+// last loaded/saved preset so users can always see what they started from.
+fn build_preset_button(cx: &mut Context) {
+    HStack::new(cx, |cx| {
+        Label::new(
+            cx,
+            Data::current_preset_name.map(|name| {
+                if name.is_empty() {
+                    "PRESETS".to_owned()
+                } else {
+                    format!("\u{25BE} {name}") // ▾
+                }
+            }),
+        )
+        .class("preset-btn-label");
+    })
+    .class("preset-btn")
+    .toggle_class("preset-btn-active", Data::preset_browser_open)
+    .on_press(|cx| cx.emit(PresetEvent::ToggleBrowser))
+    .cursor(CursorIcon::Hand)
+    .height(Pixels(28.0))
+    .width(Auto)
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0));
+}
+
+// Header toggle for the metering bridge. Lit while the bridge replaces the
+// rack, like the preset button while its browser is open.
+fn build_meter_bridge_button(cx: &mut Context) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr("METERS")).class("preset-btn-label");
+    })
+    .class("preset-btn")
+    .toggle_class(
+        "preset-btn-active",
+        Data::view.map(|v| v.is_open(Panel::MeterBridge)),
+    )
+    .on_press(|cx| cx.emit(AppEvent::ToggleMeterBridge))
+    .cursor(CursorIcon::Hand)
+    .height(Pixels(28.0))
+    .width(Auto)
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0));
+}
+
+// A/B compare block for the chassis header. The active side is lit via a
+// reactive class; the copy button names its direction so users know which
+// side gets overwritten. LUFS lights while switches level on loudness.
+fn build_ab_controls(cx: &mut Context) {
+    HStack::new(cx, |cx| {
+        for slot in [AbSlot::A, AbSlot::B] {
+            HStack::new(cx, |cx| {
+                Label::new(cx, if slot == AbSlot::A { "A" } else { "B" }).class("ab-btn-label");
+            })
+            .class("ab-btn")
+            .toggle_class("ab-btn-active", Data::ab.map(move |ab| ab.active() == slot))
+            .on_press(move |cx| cx.emit(AppEvent::AbSelect(slot)))
+            .cursor(CursorIcon::Hand)
+            .width(Pixels(26.0))
+            .height(Pixels(24.0));
+        }
+        HStack::new(cx, |cx| {
+            Label::new(
+                cx,
+                Data::ab.map(|ab| {
+                    match ab.active() {
+                        AbSlot::A => "A\u{2192}B",
+                        AbSlot::B => "B\u{2192}A",
+                    }
+                    .to_owned()
+                }),
+            )
+            .class("ab-btn-label");
+        })
+        .class("ab-btn")
+        .on_press(|cx| cx.emit(AppEvent::AbCopy))
+        .cursor(CursorIcon::Hand)
+        .width(Pixels(40.0))
+        .height(Pixels(24.0));
+        HStack::new(cx, |cx| {
+            Label::new(cx, tr("MATCH")).class("ab-btn-label");
+        })
+        .class("ab-btn")
+        .on_press(|cx| cx.emit(AppEvent::AbMatchGain))
+        .cursor(CursorIcon::Hand)
+        .width(Pixels(48.0))
+        .height(Pixels(24.0));
+        HStack::new(cx, |cx| {
+            Label::new(cx, "LUFS").class("ab-btn-label");
+        })
+        .class("ab-btn")
+        .toggle_class("ab-btn-active", Data::settings.map(|s| s.ab_match_loudness))
+        .on_press(|cx| cx.emit(AppEvent::ToggleAbLoudnessMatch))
+        .cursor(CursorIcon::Hand)
+        .width(Pixels(40.0))
+        .height(Pixels(24.0));
+    })
+    .class("ab-controls")
+    .width(Auto)
+    .height(Auto)
+    .gap(Pixels(2.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0))
+    .alignment(Alignment::Center);
+}
+
+// Undo/redo buttons. Dimmed via a reactive class when the respective stack
+// is empty; clicks on a dimmed button are harmless no-ops.
+fn build_history_controls(cx: &mut Context) {
+    HStack::new(cx, |cx| {
+        HStack::new(cx, |cx| {
+            Label::new(cx, "\u{21B6}").class("ab-btn-label"); // ↶
+        })
+        .class("ab-btn")
+        .toggle_class("history-btn-disabled", Data::history.map(|h| !h.can_undo()))
+        .on_press(|cx| cx.emit(HistoryEvent::Undo))
+        .cursor(CursorIcon::Hand)
+        .width(Pixels(26.0))
+        .height(Pixels(24.0));
+        HStack::new(cx, |cx| {
+            Label::new(cx, "\u{21B7}").class("ab-btn-label"); // ↷
+        })
+        .class("ab-btn")
+        .toggle_class("history-btn-disabled", Data::history.map(|h| !h.can_redo()))
+        .on_press(|cx| cx.emit(HistoryEvent::Redo))
+        .cursor(CursorIcon::Hand)
+        .width(Pixels(26.0))
+        .height(Pixels(24.0));
+    })
+    .class("ab-controls")
+    .width(Auto)
+    .height(Auto)
+    .gap(Pixels(2.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0))
+    .alignment(Alignment::Center);
+}
+
+// Whole-strip VARY plus the seed box. VARY steps to a fresh seed and shows
+// it; typing a seed and pressing Enter brings that variation back.
+fn build_variation_controls(cx: &mut Context) {
+    HStack::new(cx, |cx| {
+        HStack::new(cx, |cx| {
+            Label::new(cx, tr("VARY")).class("ab-btn-label");
+        })
+        .class("ab-btn")
+        .on_press(|cx| cx.emit(VariationEvent::Vary(None)))
+        .cursor(CursorIcon::Hand)
+        .width(Pixels(44.0))
+        .height(Pixels(24.0));
+        Textbox::new(cx, Data::variation_seed)
+            .class("preset-textbox")
+            .on_edit(|cx, text| cx.emit(VariationEvent::SetSeed(text)))
+            .on_submit(|cx, _text, success| {
+                if success {
+                    cx.emit(VariationEvent::ApplySeed);
+                }
+            })
+            .width(Pixels(84.0))
+            .height(Pixels(24.0));
+    })
+    .class("ab-controls")
+    .width(Auto)
+    .height(Auto)
+    .gap(Pixels(2.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0))
+    .alignment(Alignment::Center);
+}
+
+/// Case-insensitive match on name or category. Empty query matches all.
+fn preset_matches(preset: &Preset, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty()
+        || preset.name.to_lowercase().contains(&query)
+        || preset.category.to_lowercase().contains(&query)
+}
+
+// Preset browser panel: search box, FACTORY and USER sections, save-as
+// dialog and an error footer. File IO never happens here — every disk
+// operation is a PresetTask on the background thread, and the list
+// rebuilds when the poll timer sees the library's generation change.
+fn build_preset_browser(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        // ── Title row ───────────────────────────────────────────────────
+        HStack::new(cx, |cx| {
+            Label::new(cx, tr("PRESETS"))
+                .class("preset-browser-title")
+                .width(Stretch(1.0));
+            // New instances start from a preset matching their bus; see
+            // host_context.rs. Lit while on.
+            HStack::new(cx, |cx| {
+                Label::new(cx, tr("AUTO")).class("preset-action-label");
+            })
+            .class("preset-action-btn")
+            .toggle_class("preset-action-primary", Data::context_presets)
+            .on_press(|cx| cx.emit(PresetEvent::ToggleContextPresets))
+            .cursor(CursorIcon::Hand)
+            .width(Auto)
+            .height(Pixels(24.0));
+            HStack::new(cx, |cx| {
+                Label::new(cx, tr("SAVE AS\u{2026}")).class("preset-action-label");
+            })
+            .class("preset-action-btn")
+            .on_press(|cx| cx.emit(PresetEvent::OpenSave))
+            .cursor(CursorIcon::Hand)
+            .width(Auto)
+            .height(Pixels(24.0));
+            HStack::new(cx, |cx| {
+                Label::new(cx, "\u{2715}").class("preset-action-label");
+            })
+            .class("preset-action-btn")
+            .on_press(|cx| cx.emit(PresetEvent::ToggleBrowser))
+            .cursor(CursorIcon::Hand)
+            .width(Pixels(24.0))
+            .height(Pixels(24.0));
+        })
+        .height(Pixels(28.0))
+        .gap(Pixels(6.0))
+        .alignment(Alignment::Center);
+
+        // ── Save-as dialog ──────────────────────────────────────────────
+        // Inline rather than modal: baseview has no native dialog
+        // primitive, and an inline row keeps the list visible for
+        // checking name collisions (same name overwrites).
+        HStack::new(cx, |cx| {
+            Textbox::new(cx, Data::preset_save_name)
+                .class("preset-textbox")
+                .on_edit(|cx, text| cx.emit(PresetEvent::SetSaveName(text)))
+                .on_submit(|cx, _text, success| {
+                    if success {
+                        cx.emit(PresetEvent::ConfirmSave);
+                    }
+                })
+                .width(Stretch(1.0))
+                .height(Pixels(24.0));
+            HStack::new(cx, |cx| {
+                Label::new(cx, tr("SAVE")).class("preset-action-label");
+            })
+            .class("preset-action-btn")
+            .class("preset-action-primary")
+            .on_press(|cx| cx.emit(PresetEvent::ConfirmSave))
+            .cursor(CursorIcon::Hand)
+            .width(Auto)
+            .height(Pixels(24.0));
+            HStack::new(cx, |cx| {
+                Label::new(cx, tr("CANCEL")).class("preset-action-label");
+            })
+            .class("preset-action-btn")
+            .on_press(|cx| cx.emit(PresetEvent::CancelSave))
+            .cursor(CursorIcon::Hand)
+            .width(Auto)
+            .height(Pixels(24.0));
+        })
+        .class("preset-save-dialog")
+        .display(Data::preset_save_open.map(
+            |open| {
+                if *open {
+                    Display::Flex
+                } else {
+                    Display::None
+                }
+            },
+        ))
+        .height(Pixels(32.0))
+        .gap(Pixels(4.0))
+        .alignment(Alignment::Center);
+
+        // ── Search ──────────────────────────────────────────────────────
+        Textbox::new(cx, Data::preset_search)
+            .class("preset-textbox")
+            .class("preset-search")
+            .on_edit(|cx, text| cx.emit(PresetEvent::SetSearch(text)))
+            .width(Stretch(1.0))
+            .height(Pixels(24.0));
+
+        // ── List ────────────────────────────────────────────────────────
+        ScrollView::new(cx, |cx| {
+            Binding::new(cx, Data::preset_generation, |cx, _| {
+                Binding::new(cx, Data::preset_search, |cx, search| {
+                    let query = search.get(cx);
+                    let factory = Data::factory_presets.get(cx);
+                    let user = Data::preset_library.get(cx).user_presets();
+
+                    Label::new(cx, tr("FACTORY")).class("preset-section-label");
+                    for (idx, preset) in factory.iter().enumerate() {
+                        if preset_matches(preset, &query) {
+                            build_preset_row(cx, preset, PresetId::Factory(idx));
+                        }
+                    }
+
+                    Label::new(cx, tr("USER")).class("preset-section-label");
+                    let mut any_user = false;
+                    for preset in user.iter().filter(|p| preset_matches(p, &query)) {
+                        if let Some(path) = preset.path.clone() {
+                            build_preset_row(cx, preset, PresetId::User(path));
+                            any_user = true;
+                        }
+                    }
+                    if !any_user {
+                        Label::new(
+                            cx,
+                            tr(if user.is_empty() {
+                                "No user presets yet \u{2014} use SAVE AS\u{2026}"
+                            } else {
+                                "No matches"
+                            }),
+                        )
+                        .class("preset-empty-hint");
+                    }
+                });
+            });
+        })
+        .class("preset-list")
+        .width(Stretch(1.0))
+        .height(Stretch(1.0));
+
+        // ── Chain file ──────────────────────────────────────────────────
+        // The whole strip as one .bcsp file, for moving a bus between
+        // machines without the host's preset format. Each button opens a
+        // native file dialog on the background thread (see `PresetTask`).
+        HStack::new(cx, |cx| {
+            HStack::new(cx, |cx| {
+                Label::new(cx, tr("EXPORT CHAIN\u{2026}")).class("preset-action-label");
+            })
+            .class("preset-action-btn")
+            .on_press(|cx| cx.emit(PresetEvent::ExportChain))
+            .cursor(CursorIcon::Hand)
+            .width(Stretch(1.0))
+            .height(Pixels(24.0));
+            HStack::new(cx, |cx| {
+                Label::new(cx, tr("IMPORT CHAIN\u{2026}")).class("preset-action-label");
+            })
+            .class("preset-action-btn")
+            .on_press(|cx| cx.emit(PresetEvent::ImportChain))
+            .cursor(CursorIcon::Hand)
+            .width(Stretch(1.0))
+            .height(Pixels(24.0));
+        })
+        .height(Pixels(28.0))
+        .gap(Pixels(4.0))
+        .alignment(Alignment::Center);
+
+        // ── Error footer ────────────────────────────────────────────────
+        Binding::new(cx, Data::preset_generation, |cx, _| {
+            if let Some(err) = Data::preset_library.get(cx).last_error() {
+                Label::new(cx, err.as_str()).class("preset-error");
+            }
+        });
+    })
+    .class("preset-browser")
+    .display(Data::preset_browser_open.map(
+        |open| {
+            if *open {
+                Display::Flex
+            } else {
+                Display::None
+            }
+        },
+    ))
+    .position_type(PositionType::Absolute)
+    .top(Pixels(96.0))
+    .right(Pixels(24.0))
+    .width(Pixels(320.0))
+    .height(Pixels(520.0))
+    .gap(Pixels(6.0));
+}
+
+// One row in the preset list: name + category, with a delete affordance on
+// user presets. Same HStack-with-on_press shape as the library sidebar rows.
+fn build_preset_row(cx: &mut Context, preset: &Preset, id: PresetId) {
+    let delete_path = match &id {
+        PresetId::User(path) => Some(path.clone()),
+        PresetId::Factory(_) => None,
+    };
+    HStack::new(cx, move |cx| {
+        Label::new(cx, preset.name.as_str())
+            .class("preset-row-name")
+            .width(Stretch(1.0));
+        Label::new(cx, preset.category.as_str()).class("preset-row-category");
+        if let Some(path) = delete_path {
+            HStack::new(cx, |cx| {
+                Label::new(cx, "\u{2715}").class("preset-row-delete-glyph");
+            })
+            .class("preset-row-delete")
+            .on_press(move |cx| cx.emit(PresetEvent::Delete(path.clone())))
+            .cursor(CursorIcon::Hand)
+            .width(Pixels(18.0))
+            .height(Pixels(18.0));
+        }
+    })
+    .class("preset-row")
+    .on_press(move |cx| cx.emit(PresetEvent::Load(id.clone())))
+    .cursor(CursorIcon::Hand)
+    .height(Pixels(26.0))
+    .width(Stretch(1.0))
+    .gap(Pixels(6.0))
+    .alignment(Alignment::Center);
+}
+
+/// Readout line for a parameter at `normalized`: name, value with units and
+/// default. Batch writes (presets, A/B, undo) don't update the readout.
+fn param_readout_text(ptr: ParamPtr, normalized: f32) -> String {
+    // SAFETY: every ParamPtr in this editor comes from `Data::params`, which
+    // outlives the editor.
+    unsafe {
+        format!(
+            "{}   {}   (default {})",
+            ptr.name(),
+            ptr.normalized_value_to_string(normalized, true),
+            ptr.normalized_value_to_string(ptr.default_normalized_value(), true),
+        )
+    }
+}
+
+fn build_readout_strip(cx: &mut Context) {
+    HStack::new(cx, |cx| {
+        // The placeholder follows the language; pinned readouts are
+        // parameter names and values, which stay as the host shows them.
+        Binding::new(cx, Data::language, |cx, language| {
+            let language = language.get(cx);
+            Label::new(
+                cx,
+                Data::param_readout.map(move |text| {
+                    if text.is_empty() {
+                        i18n::translate(
+                            language,
+                            "Hover a control for details \u{00b7} touch one to pin it here",
+                        )
+                        .to_owned()
+                    } else {
+                        text.clone()
+                    }
+                }),
+            )
+            .class("param-readout-text")
+            .live(Live::Polite)
+            .width(Stretch(1.0));
+        });
+
+        // Rebuilt when the ballistics or scale change; the pips take them
+        // at build.
+        Binding::new(cx, Data::settings.map(|s| s.meter_ballistics), |cx, _| {
+            Binding::new(cx, Data::settings.map(|s| s.meter_scale), |cx, _| {
+                build_signal_flow(cx)
+            });
+        });
+    })
+    .class("param-readout-strip")
+    .height(Pixels(22.0))
+    .width(Stretch(1.0));
+}
+
+// Live signal flow: IN → every occupied slot in processing order → Sheen →
+// OUT, with a level arrow after each stage. Generated from module_order_*
+// and the bypass flags, so it follows reorders, chain loads and bypass
+// clicks without any extra plumbing. Empty slots drop out of the chain.
+fn build_signal_flow(cx: &mut Context) {
+    let settings = Data::settings.get(cx);
+    let (scale, smoothing) = (settings.meter_scale, settings.meter_ballistics.smoothing());
+    HStack::new(cx, |cx| {
+        let levels = Data::stage_levels.get(cx);
+        Label::new(cx, tr("IN")).class("signal-flow-endpoint");
+        components::create_stage_level_pip(cx, levels.clone(), 0, scale, smoothing);
+        #[cfg(feature = "conditioning")]
+        Label::new(cx, tr("INP"))
+            .class("signal-flow-chip")
+            .toggle_class(
+                "signal-flow-chip-bypassed",
+                Data::params.map(|p| p.input_bypass.value()),
+            );
+        for slot in 0..7_usize {
+            let levels = levels.clone();
+            Binding::new(
+                cx,
+                Data::params.map(move |p| module_type_to_usize(slot_module_type(p, slot))),
+                move |cx, mt_lens| {
+                    let mt = usize_to_module_type(mt_lens.get(cx));
+                    if mt == ModuleType::Empty {
+                        return;
+                    }
+                    Label::new(cx, module_type_short_name(mt))
+                        .class("signal-flow-chip")
+                        .toggle_class(
+                            "signal-flow-chip-bypassed",
+                            Data::params.map(move |p| is_module_bypassed(p, mt)),
+                        )
+                        .on_press(move |cx| cx.emit(AppEvent::AddOrFocusModule(mt)))
+                        .cursor(CursorIcon::Hand);
+                    components::create_stage_level_pip(
+                        cx,
+                        levels.clone(),
+                        slot + 1,
+                        scale,
+                        smoothing,
+                    );
+                },
+            );
+        }
+        #[cfg(feature = "sheen")]
+        Label::new(cx, tr("SHN"))
+            .class("signal-flow-chip")
+            .toggle_class(
+                "signal-flow-chip-bypassed",
+                Data::params.map(|p| p.sheen_bypass.value()),
+            );
+        components::create_stage_level_pip(
+            cx,
+            levels,
+            spectral::STAGE_LEVELS - 1,
+            scale,
+            smoothing,
+        );
+        Label::new(cx, tr("OUT")).class("signal-flow-endpoint");
+    })
+    .class("signal-flow")
+    .toggle_class(
+        "signal-flow-bypassed",
+        Data::params.map(|p| p.global_bypass.value()),
+    )
+    .width(Auto)
+    .height(Stretch(1.0))
+    .gap(Pixels(3.0))
+    .alignment(Alignment::Center);
+}
+
 // Discrete zoom buttons (75/100/125/150/200%). Each button emits SetZoom on
 // press; the active level is styled via a reactive `zoom-btn-active` class so
 // users can see which step is current.
 fn create_zoom_controls(cx: &mut Context) {
     VStack::new(cx, |cx| {
-        Label::new(cx, "ZOOM").class("zoom-label");
+        Label::new(cx, tr("ZOOM")).class("zoom-label");
         HStack::new(cx, |cx| {
             for &level in &[75_u8, 100, 125, 150, 200] {
                 VStack::new(cx, |cx| {
@@ -1381,14 +3297,137 @@ fn create_zoom_controls(cx: &mut Context) {
     .bottom(Pixels(0.0));
 }
 
+// Skin selector — one button per entry in the skin registry, styled like the
+// zoom band so the header reads as one row of segmented controls.
+fn create_skin_controls(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        Label::new(cx, tr("SKIN")).class("zoom-label");
+        HStack::new(cx, |cx| {
+            for info in styles::SKINS {
+                let skin = info.skin;
+                VStack::new(cx, |cx| {
+                    Label::new(cx, info.tag).class("zoom-btn-label");
+                })
+                .class("zoom-btn")
+                .toggle_class("zoom-btn-active", Data::skin.map(move |s| *s == skin))
+                .on_press(move |cx| cx.emit(AppEvent::SetSkin(skin)))
+                .cursor(CursorIcon::Hand)
+                .width(Pixels(36.0))
+                .height(Pixels(24.0))
+                .top(Pixels(0.0))
+                .bottom(Pixels(0.0));
+            }
+        })
+        .gap(Pixels(2.0))
+        .height(Pixels(24.0))
+        .width(Auto)
+        .top(Pixels(0.0))
+        .bottom(Pixels(0.0));
+    })
+    .class("zoom-controls")
+    .height(Auto)
+    .width(Auto)
+    .gap(Pixels(4.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0));
+}
+
+// Analyzer (FFT) and tooltip (TIPS) on/off switches, styled like the zoom
+// band. Both are editor settings, saved with the session.
+fn create_view_controls(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        Label::new(cx, tr("VIEW")).class("zoom-label");
+        HStack::new(cx, |cx| {
+            let switches: [(&str, fn(&EditorSettings) -> bool, AppEvent); 2] = [
+                ("FFT", |s| s.analyzer, AppEvent::ToggleAnalyzer),
+                ("TIPS", |s| s.tooltips, AppEvent::ToggleTooltips),
+            ];
+            for (tag, is_on, event) in switches {
+                VStack::new(cx, |cx| {
+                    Label::new(cx, tr(tag)).class("zoom-btn-label");
+                })
+                .class("zoom-btn")
+                .toggle_class("zoom-btn-active", Data::settings.map(is_on))
+                .on_press(move |cx| cx.emit(event))
+                .cursor(CursorIcon::Hand)
+                .width(Pixels(36.0))
+                .height(Pixels(24.0))
+                .top(Pixels(0.0))
+                .bottom(Pixels(0.0));
+            }
+        })
+        .gap(Pixels(2.0))
+        .height(Pixels(24.0))
+        .width(Auto)
+        .top(Pixels(0.0))
+        .bottom(Pixels(0.0));
+    })
+    .class("zoom-controls")
+    .height(Auto)
+    .width(Auto)
+    .gap(Pixels(4.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0));
+}
+
+// Language buttons, styled like the zoom and skin bands.
+fn create_language_controls(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        Label::new(cx, tr("LANG")).class("zoom-label");
+        HStack::new(cx, |cx| {
+            for (language, tag) in [
+                (EditorLanguage::English, "EN"),
+                (EditorLanguage::Spanish, "ES"),
+            ] {
+                VStack::new(cx, |cx| {
+                    Label::new(cx, tag).class("zoom-btn-label");
+                })
+                .class("zoom-btn")
+                .toggle_class(
+                    "zoom-btn-active",
+                    Data::language.map(move |l| *l == language),
+                )
+                .on_press(move |cx| cx.emit(AppEvent::SetLanguage(language)))
+                .cursor(CursorIcon::Hand)
+                .width(Pixels(30.0))
+                .height(Pixels(24.0))
+                .top(Pixels(0.0))
+                .bottom(Pixels(0.0));
+            }
+        })
+        .gap(Pixels(2.0))
+        .height(Pixels(24.0))
+        .width(Auto)
+        .top(Pixels(0.0))
+        .bottom(Pixels(0.0));
+    })
+    .class("zoom-controls")
+    .height(Auto)
+    .width(Auto)
+    .gap(Pixels(4.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0));
+}
+
 fn create_master_section(cx: &mut Context) {
     HStack::new(cx, |cx| {
         // Global bypass — prominently placed so it's always reachable.
         VStack::new(cx, |cx| {
-            Label::new(cx, "BYPASS")
-                .class("param-label")
-                .height(Pixels(16.0))
-                .width(Stretch(1.0));
+            HStack::new(cx, |cx| {
+                Label::new(cx, tr("BYPASS"))
+                    .class("param-label")
+                    .height(Pixels(16.0))
+                    .width(Stretch(1.0));
+                // Strip-level status LED: lit while the strip processes,
+                // dark while global bypass passes audio straight through.
+                Element::new(cx).class("module-led-indicator").toggle_class(
+                    "module-led-lit",
+                    Data::params.map(|p| !p.global_bypass.value()),
+                );
+            })
+            .height(Pixels(16.0))
+            .width(Stretch(1.0))
+            .alignment(Alignment::Center);
             components::create_bypass_button(cx, "BYPASS", |p| &p.global_bypass);
         })
         .height(Auto)
@@ -1397,10 +3436,101 @@ fn create_master_section(cx: &mut Context) {
         .top(Pixels(0.0))
         .bottom(Pixels(0.0));
 
-        // Auto-gain compensation toggle.
-        components::create_bool_button(cx, "AUTO GAIN", Data::params, |p| &p.global_auto_gain);
+        // Auto-gain compensation toggle.
+        components::create_bool_button(cx, "AUTO GAIN", Data::params, |p| &p.global_auto_gain);
+
+        // Analog Amount macro: every coloration stage, 0–150 %.
+        components::create_param_slider(cx, "ANALOG", Data::params, |p| &p.global_analog_amount);
+
+        // Stereo, or the two channels as independent mono strips.
+        components::create_param_slider(cx, "CHANNELS", Data::params, |p| &p.global_channel_mode);
+
+        // 5.1 buses: whether the saturation stages run on the LFE.
+        components::create_bool_button(cx, "LFE SAT", Data::params, |p| &p.global_lfe_saturation);
+
+        // Internal block size; a fixed size adds that much latency.
+        components::create_param_slider(cx, "BLOCK", Data::params, |p| &p.block_size);
+
+        // Audition the sidechain key in place of the output.
+        components::create_bool_button(cx, "SC LISTEN", Data::params, |p| &p.global_sc_listen);
+
+        // Hear only what one module changes (its output minus its input).
+        components::create_param_slider(cx, "DELTA", Data::params, |p| &p.global_delta_listen);
+
+        // Hear what one detector hears through its key EQ.
+        components::create_param_slider(cx, "KEY LISTEN", Data::params, |p| &p.global_key_listen);
+
+        // Output safety stage; the light shows it replaced a NaN/Inf.
+        components::create_bool_button(cx, "SAFETY", Data::params, |p| &p.global_safety);
+        let fault = Data::safety_fault.get(cx);
+        components::create_status_led(cx, "NaN/INF", fault);
+
+        // Clear detector envelopes when playback starts or loops.
+        components::create_bool_button(cx, "LOOP RESET", Data::params, |p| {
+            &p.global_transport_reset
+        });
+
+        // Dither for printing stems at 16 or 24 bits.
+        components::create_param_slider(cx, "DITHER", Data::params, |p| &p.global_dither);
+        components::create_bool_button(cx, "SHAPE", Data::params, |p| &p.global_dither_shaping);
+
+        // MIDI duck: a note-on ducks the strip at the chosen boundary, for
+        // hosts that can't route an audio sidechain.
+        VStack::new(cx, |cx| {
+            components::module_row(cx, |cx| {
+                components::create_bool_button(cx, "MIDI DUCK", Data::params, |p| &p.duck_enable);
+                components::create_param_slider(cx, "NOTE", Data::params, |p| &p.duck_note);
+                components::create_param_slider(cx, "CH", Data::params, |p| &p.duck_channel);
+                components::create_param_slider(cx, "DUCK AT", Data::params, |p| &p.duck_point);
+            });
+            components::module_row(cx, |cx| {
+                components::create_param_slider(cx, "DEPTH", Data::params, |p| &p.duck_depth);
+                components::create_param_slider(cx, "ATTACK", Data::params, |p| &p.duck_attack);
+                components::create_param_slider(cx, "HOLD", Data::params, |p| &p.duck_hold);
+                components::create_param_slider(cx, "RELEASE", Data::params, |p| &p.duck_release);
+            });
+        })
+        .height(Auto)
+        .width(Auto)
+        .gap(Pixels(4.0))
+        .top(Pixels(0.0))
+        .bottom(Pixels(0.0));
+
+        // Goniometer + correlation: watch mono compatibility while pushing
+        // width (Haas, Sheen) or saturation.
+        let scope = Data::scope_data.get(cx);
+        components::create_stereo_scope(cx, scope, 56.0, 66.0);
+
+        // Mid over side at the strip's input and output: the width the
+        // chain adds or takes away. Rebuilt when the ballistics change.
+        Binding::new(
+            cx,
+            Data::settings.map(|s| s.meter_ballistics),
+            |cx, ballistics| {
+                let smoothing = ballistics.get(cx).smoothing();
+                VStack::new(cx, |cx| {
+                    Label::new(cx, tr("MID / SIDE"))
+                        .class("param-label")
+                        .height(Pixels(16.0));
+                    let levels = Data::stage_levels.get(cx);
+                    components::create_mid_side_meter(cx, "IN", levels.clone(), 0, smoothing);
+                    components::create_mid_side_meter(
+                        cx,
+                        "OUT",
+                        levels,
+                        spectral::STAGE_LEVELS - 1,
+                        smoothing,
+                    );
+                })
+                .height(Auto)
+                .width(Pixels(96.0))
+                .gap(Pixels(4.0))
+                .top(Pixels(0.0))
+                .bottom(Pixels(0.0));
+            },
+        );
 
-        Label::new(cx, "MASTER").class("master-label");
+        Label::new(cx, tr("MASTER")).class("master-label");
         components::create_gain_slider(cx, "Gain", Data::params, |p| &p.gain);
     })
     .class("master-controls")
@@ -1412,65 +3542,87 @@ fn create_master_section(cx: &mut Context) {
 // ============================================================================
 
 /// Creates one 500-series slot that reactively renders whatever module is
-/// currently assigned to `module_order_{slot_idx+1}`. Three layers of
+/// currently assigned to `module_order_{slot_idx+1}`. Four layers of
 /// `Binding` track independent inputs that affect what gets rendered:
-///   1. `Data::focused_slot` — focus mode collapses every non-focused slot
+///   0. `Data::skin` — accents and knob faces are baked in at build time
+///   1. `Data::view`'s focused slot — the focused slot opens its focus
+///      view and every other slot collapses to the mini strip
 ///   2. `Data::params` (module type) — rebuild when a swap or library pick
 ///      changes which module lives here
 ///   3. `Data::params` (hide flag for that module) — collapse when hidden
 ///
 /// The drag-source highlight is toggled separately via `toggle_class`
-/// which reacts to `Data::drag_source` without a full rebuild.
+/// which reacts to `Data::drag_source` without a full rebuild. Each build
+/// reports its module via `AppEvent::SlotBuilt`; a changed module sets the
+/// slot's `slot-settling` class briefly, and the CSS transition back to rest
+/// is the insert/remove animation.
 fn create_dynamic_module_slot(cx: &mut Context, slot_idx: usize) {
-    Binding::new(cx, Data::focused_slot, move |cx, focus_b| {
-        let focus = focus_b.get(cx);
-        let this_focused = focus == Some(slot_idx);
-        let any_focused = focus.is_some();
+    Binding::new(cx, Data::skin, move |cx, _| {
+        Binding::new(cx, focused_slot_lens(), move |cx, focus_b| {
+            let focus = focus_b.get(cx);
+            let this_focused = focus == Some(slot_idx);
+            let any_focused = focus.is_some();
+
+            // Use usize as the Binding target because vizia requires `Target: Data`,
+            // and usize satisfies that bound whereas our ModuleType enum does not.
+            Binding::new(
+                cx,
+                Data::params.map(move |p| module_type_to_usize(slot_module_type(p, slot_idx))),
+                move |cx, mt_lens| {
+                    let module = mt_lens.get(cx);
+                    cx.emit(AppEvent::SlotBuilt {
+                        slot: slot_idx,
+                        module,
+                    });
+                    let mt = usize_to_module_type(module);
+                    let theme = module_type_to_theme(mt);
+
+                    // Inner binding watches the hide flag for this module type.
+                    // Render rule:
+                    //   • this_focused                       → full (focus wins)
+                    //   • any other slot is focused          → collapsed
+                    //   • nothing focused, hide flag set     → collapsed
+                    //   • nothing focused, not hidden        → full
+                    // Empty slots are ALWAYS rendered as a slim placeholder tab
+                    // regardless of focus or hide flags. There is no body to
+                    // expand — adding a module is now done via the global
+                    // library sidebar (which auto-targets the focused empty
+                    // slot when one exists, falling back to first-empty).
+                    if mt == ModuleType::Empty {
+                        build_empty_slot(cx, slot_idx);
+                        return;
+                    }
 
-        // Use usize as the Binding target because vizia requires `Target: Data`,
-        // and usize satisfies that bound whereas our ModuleType enum does not.
-        Binding::new(
-            cx,
-            Data::params.map(move |p| module_type_to_usize(slot_module_type(p, slot_idx))),
-            move |cx, mt_lens| {
-                let mt = usize_to_module_type(mt_lens.get(cx));
-                let theme = module_type_to_theme(mt);
+                    let hide_lens = Data::params.map(move |p| is_module_hidden(p, mt));
+                    Binding::new(cx, hide_lens, move |cx, hide_binding| {
+                        let hidden = hide_binding.get(cx);
+                        let render_full = if this_focused {
+                            true
+                        } else if any_focused {
+                            false
+                        } else {
+                            !hidden
+                        };
+                        if render_full {
+                            build_full_slot(cx, slot_idx, mt, theme, this_focused);
+                        } else {
+                            build_collapsed_slot(cx, slot_idx, mt, theme);
+                        }
+                    });
+                },
+            );
+        });
+    });
+}
 
-                // Inner binding watches the hide flag for this module type.
-                // Render rule:
-                //   • this_focused                       → full (focus wins)
-                //   • any other slot is focused          → collapsed
-                //   • nothing focused, hide flag set     → collapsed
-                //   • nothing focused, not hidden        → full
-                // Empty slots are ALWAYS rendered as a slim placeholder tab
-                // regardless of focus or hide flags. There is no body to
-                // expand — adding a module is now done via the global
-                // library sidebar (which auto-targets the focused empty
-                // slot when one exists, falling back to first-empty).
-                if mt == ModuleType::Empty {
-                    build_empty_slot(cx, slot_idx);
-                    return;
-                }
+/// Slot the rack is focused on, if any.
+fn focused_slot_lens() -> impl Lens<Target = Option<usize>> {
+    Data::view.map(|v| v.focused_slot())
+}
 
-                let hide_lens = Data::params.map(move |p| is_module_hidden(p, mt));
-                Binding::new(cx, hide_lens, move |cx, hide_binding| {
-                    let hidden = hide_binding.get(cx);
-                    let render_full = if this_focused {
-                        true
-                    } else if any_focused {
-                        false
-                    } else {
-                        !hidden
-                    };
-                    if render_full {
-                        build_full_slot(cx, slot_idx, mt, theme);
-                    } else {
-                        build_collapsed_slot(cx, slot_idx, mt, theme);
-                    }
-                });
-            },
-        );
-    });
+/// True while `slot_idx` is playing its insert/remove animation.
+fn slot_settling_lens(slot_idx: usize) -> impl Lens<Target = bool> {
+    Data::slot_settling.map(move |bits| bits & (1 << slot_idx) != 0)
 }
 
 /// Full expanded slot — module header, bypass LED, parameter controls.
@@ -1478,25 +3630,46 @@ fn create_dynamic_module_slot(cx: &mut Context, slot_idx: usize) {
 /// convention — no separate `≡` handle). Vizia's `on_drag` fires when
 /// the cursor leaves this view with LMB held; `on_drop` fires on a sibling
 /// when MouseUp lands there with active `drop_data`.
-fn build_full_slot(cx: &mut Context, slot_idx: usize, mt: ModuleType, theme: ModuleTheme) {
+///
+/// `focused` builds the focus view instead: FOCUS_WIDTH_FACTOR times as
+/// wide, the analyzer across the top, and the ADVANCED drawer laid open
+/// (see `build_advanced_drawer`). Clicking the module name toggles it.
+fn build_full_slot(
+    cx: &mut Context,
+    slot_idx: usize,
+    mt: ModuleType,
+    theme: ModuleTheme,
+    focused: bool,
+) {
+    let skin = Data::skin.get(cx);
+    let width_factor = if focused { FOCUS_WIDTH_FACTOR } else { 1.0 };
     VStack::new(cx, |cx| {
         // ── Module header (name + eject + hide + LED) ────────────────
         HStack::new(cx, |cx| {
+            // on_press only fires on the press target, which is whichever
+            // label the click lands on — so both labels and their parent
+            // carry it (same as the brand plate).
             VStack::new(cx, |cx| {
-                Label::new(cx, module_type_name(mt))
+                Label::new(cx, tr(module_type_name(mt)))
                     .class("module-name")
-                    .color(theme.accent_color());
-                Label::new(cx, module_type_subtitle(mt)).class("module-type");
+                    .color(theme.accent_color(skin))
+                    .on_press(move |cx| cx.emit(AppEvent::ToggleFocus(slot_idx)));
+                Label::new(cx, tr(module_type_subtitle(mt)))
+                    .class("module-type")
+                    .on_press(move |cx| cx.emit(AppEvent::ToggleFocus(slot_idx)));
             })
             .class("module-name-target")
+            .on_press(move |cx| cx.emit(AppEvent::ToggleFocus(slot_idx)))
+            .cursor(CursorIcon::Hand)
             .toggle_class(
                 "module-name-target-focused",
-                Data::focused_slot.map(move |fs| *fs == Some(slot_idx)),
+                focused_slot_lens().map(move |fs| *fs == Some(slot_idx)),
             )
             .height(Auto)
             .width(Stretch(1.0));
 
             if mt != ModuleType::Empty {
+                build_vary_button(cx, mt);
                 build_eject_button(cx, slot_idx);
             }
             build_hide_button_for_type(cx, mt);
@@ -1510,12 +3683,25 @@ fn build_full_slot(cx: &mut Context, slot_idx: usize, mt: ModuleType, theme: Mod
         .gap(Pixels(6.0));
 
         build_bypass_button_for_type(cx, mt);
+        if focused {
+            let spectrum = Data::spectrum_data.get(cx);
+            let analysis = Data::analysis_result.get(cx);
+            let gr = Data::gr_data.get(cx);
+            SpectrumCanvas::new(cx, spectrum, analysis, gr)
+                .class("dyneq-spectrum")
+                .class("focus-spectrum")
+                .display(analyzer_display())
+                .height(Pixels(160.0))
+                .width(Stretch(1.0));
+        }
         build_controls_for_type(cx, mt, slot_idx);
     })
     .alignment(Alignment::TopLeft)
     .gap(Pixels(4.0))
     .class("module-slot")
+    .toggle_class("slot-focused", focused)
     .class(theme.class_name())
+    .toggle_class("slot-settling", slot_settling_lens(slot_idx))
     // Eligible-target class: lit on every slot OTHER than the drag source
     // while a drag is in flight. CSS pairs this with `:hover` to show the
     // active drop-target outline only on the slot the cursor is over.
@@ -1573,11 +3759,14 @@ fn build_full_slot(cx: &mut Context, slot_idx: usize, mt: ModuleType, theme: Mod
         });
         ex.emit(WindowEvent::SetCursor(CursorIcon::Default));
     })
-    .border_color(theme.accent_color())
-    .width(Data::zoom_level.map(|z| Pixels(BASE_SLOT_WIDTH_PX * (*z as f32) / 100.0)))
+    .border_color(theme.accent_color(skin))
+    .width(
+        Data::zoom_level
+            .map(move |z| Pixels(BASE_SLOT_WIDTH_PX * width_factor * (*z as f32) / 100.0)),
+    )
     .height(Stretch(1.0))
     .border_width(Pixels(3.0))
-    .background_color(Color::rgb(42, 42, 42))
+    .background_color(theme.slot_background(skin))
     .padding(Pixels(12.0));
 }
 
@@ -1585,10 +3774,15 @@ fn build_full_slot(cx: &mut Context, slot_idx: usize, mt: ModuleType, theme: Mod
 /// that toggles the hide flag back to false. Width is fixed regardless of
 /// zoom so several collapsed tabs stack neatly next to full slots.
 fn build_collapsed_slot(cx: &mut Context, slot_idx: usize, mt: ModuleType, theme: ModuleTheme) {
+    let skin = Data::skin.get(cx);
     VStack::new(cx, |cx| {
+        // In the focus view's mini strip, the tag moves the focus here.
         Label::new(cx, module_type_short_name(mt))
             .class("collapsed-name")
-            .color(theme.accent_color());
+            .color(theme.accent_color(skin))
+            .on_press(move |cx| cx.emit(AppEvent::ToggleFocus(slot_idx)))
+            .cursor(CursorIcon::Hand);
+        build_led_indicator_for_type(cx, mt);
         build_expand_button_for_type(cx, mt);
     })
     .alignment(Alignment::Center)
@@ -1596,6 +3790,7 @@ fn build_collapsed_slot(cx: &mut Context, slot_idx: usize, mt: ModuleType, theme
     .class("module-slot")
     .class("slot-collapsed")
     .class(theme.class_name())
+    .toggle_class("slot-settling", slot_settling_lens(slot_idx))
     .toggle_class(
         "slot-eligible-target",
         Data::drag_source.map(move |ds| ds.is_some() && *ds != Some(slot_idx)),
@@ -1638,11 +3833,11 @@ fn build_collapsed_slot(cx: &mut Context, slot_idx: usize, mt: ModuleType, theme
         });
         ex.emit(WindowEvent::SetCursor(CursorIcon::Default));
     })
-    .border_color(theme.accent_color())
+    .border_color(theme.accent_color(skin))
     .width(Pixels(56.0))
     .height(Stretch(1.0))
     .border_width(Pixels(3.0))
-    .background_color(Color::rgb(42, 42, 42))
+    .background_color(theme.slot_background(skin))
     .padding(Pixels(6.0));
 }
 
@@ -1650,53 +3845,35 @@ fn build_collapsed_slot(cx: &mut Context, slot_idx: usize, mt: ModuleType, theme
 /// derived as `BASE_SLOT_WIDTH_PX * zoom_level / 100` via a reactive lens.
 pub const BASE_SLOT_WIDTH_PX: f32 = 280.0;
 
+/// How many base slot widths the focus view spans.
+const FOCUS_WIDTH_FACTOR: f32 = 2.0;
+
 // ============================================================================
 // Bypass Buttons — dispatched by module type
 // ============================================================================
 
+/// Status LED in the module header. Purely an indicator — bound through a
+/// lens to the module's bypass param AND the global bypass, so it reflects
+/// what the audio thread actually does:
+///   • lit (green)   — module is processing
+///   • held (amber)  — module enabled, but the whole strip is globally bypassed
+///   • dark          — module bypassed
+/// Toggling stays on the ACTIVE button below the header.
 fn build_led_indicator_for_type(cx: &mut Context, mt: ModuleType) {
-    match mt {
-        ModuleType::Api5500EQ => {
-            ParamButton::new(cx, Data::params, |p| &p.eq_bypass)
-                .with_label("")
-                .class("module-led-indicator");
-        }
-        ModuleType::ButterComp2 => {
-            ParamButton::new(cx, Data::params, |p| &p.comp_bypass)
-                .with_label("")
-                .class("module-led-indicator");
-        }
-        ModuleType::PultecEQ => {
-            ParamButton::new(cx, Data::params, |p| &p.pultec_bypass)
-                .with_label("")
-                .class("module-led-indicator");
-        }
-        ModuleType::DynamicEQ => {
-            #[cfg(feature = "dynamic_eq")]
-            ParamButton::new(cx, Data::params, |p| &p.dyneq_bypass)
-                .with_label("")
-                .class("module-led-indicator");
-        }
-        ModuleType::Transformer => {
-            ParamButton::new(cx, Data::params, |p| &p.transformer_bypass)
-                .with_label("")
-                .class("module-led-indicator");
-        }
-        ModuleType::Punch => {
-            #[cfg(feature = "punch")]
-            ParamButton::new(cx, Data::params, |p| &p.punch_bypass)
-                .with_label("")
-                .class("module-led-indicator");
-        }
-        ModuleType::Haas => {
-            #[cfg(feature = "haas")]
-            ParamButton::new(cx, Data::params, |p| &p.haas_bypass)
-                .with_label("")
-                .class("module-led-indicator");
-        }
-        // No LED for empty slots — there is nothing to indicate.
-        ModuleType::Empty => {}
+    // No LED for empty slots — there is nothing to indicate.
+    if mt == ModuleType::Empty {
+        return;
     }
+    Element::new(cx)
+        .class("module-led-indicator")
+        .toggle_class(
+            "module-led-lit",
+            Data::params.map(move |p| !is_module_bypassed(p, mt) && !p.global_bypass.value()),
+        )
+        .toggle_class(
+            "module-led-held",
+            Data::params.map(move |p| !is_module_bypassed(p, mt) && p.global_bypass.value()),
+        );
 }
 
 fn build_bypass_button_for_type(cx: &mut Context, mt: ModuleType) {
@@ -1751,6 +3928,31 @@ fn build_controls_for_type(cx: &mut Context, mt: ModuleType, slot_idx: usize) {
     }
 }
 
+/// A module's ADVANCED drawer, open state keyed by module type. The focus
+/// view shows the full parameter set, so there it is a plain open section.
+fn build_advanced_drawer(
+    cx: &mut Context,
+    mt: ModuleType,
+    builder: impl Fn(&mut Context) + 'static,
+) {
+    let params = Data::params.get(cx);
+    let focused = Data::view
+        .get(cx)
+        .focused_slot()
+        .is_some_and(|slot| slot_module_type(&params, slot) == mt);
+    if focused {
+        components::module_section(cx, "ADVANCED", builder);
+        return;
+    }
+    let bit = 1 << module_type_to_usize(mt);
+    components::advanced_drawer(
+        cx,
+        Data::advanced_open.map(move |open| open & bit != 0),
+        move |cx| cx.emit(AppEvent::ToggleAdvanced(mt)),
+        builder,
+    );
+}
+
 // ============================================================================
 // Empty slot placeholder — slim dashed tab, sole add path is the sidebar
 // ============================================================================
@@ -1761,16 +3963,31 @@ fn build_controls_for_type(cx: &mut Context, mt: ModuleType, slot_idx: usize) {
 /// is the same as inserting between the surrounding slots.
 fn build_empty_slot(cx: &mut Context, slot_idx: usize) {
     let theme = ModuleTheme::Empty;
+    let skin = Data::skin.get(cx);
+    // Unpopulated 500-series bay: rail screws top and bottom, the slot
+    // number, and the vacant card-edge connector a module would plug into.
     VStack::new(cx, |cx| {
+        Element::new(cx).class("faceplate-screw");
         Label::new(cx, "+").class("empty-slot-glyph");
-        Label::new(cx, format!("SLOT {}", slot_idx + 1).as_str()).class("empty-slot-label");
+        Label::new(
+            cx,
+            Data::language
+                .map(move |l| format!("{} {}", i18n::translate(*l, "SLOT"), slot_idx + 1)),
+        )
+        .class("empty-slot-label");
+        Element::new(cx)
+            .class("empty-slot-connector")
+            .top(Stretch(1.0))
+            .bottom(Stretch(1.0));
+        Element::new(cx).class("faceplate-screw");
     })
-    .alignment(Alignment::Center)
-    .gap(Pixels(2.0))
+    .alignment(Alignment::TopCenter)
+    .gap(Pixels(6.0))
     .class("module-slot")
     .class("slot-collapsed")
     .class("slot-empty")
     .class(theme.class_name())
+    .toggle_class("slot-settling", slot_settling_lens(slot_idx))
     .toggle_class(
         "slot-eligible-target",
         Data::drag_source.map(move |ds| ds.is_some() && *ds != Some(slot_idx)),
@@ -1804,11 +4021,11 @@ fn build_empty_slot(cx: &mut Context, slot_idx: usize) {
         });
         ex.emit(WindowEvent::SetCursor(CursorIcon::Default));
     })
-    .border_color(theme.accent_color())
+    .border_color(theme.accent_color(skin))
     .width(Pixels(56.0))
     .height(Stretch(1.0))
     .border_width(Pixels(2.0))
-    .background_color(Color::rgb(36, 36, 38))
+    .background_color(theme.slot_background(skin))
     .padding(Pixels(6.0));
 }
 
@@ -1818,12 +4035,12 @@ fn build_api5500_controls(cx: &mut Context) {
         HStack::new(cx, |cx| {
             // Left: LF low shelf
             VStack::new(cx, |cx| {
-                Label::new(cx, "LF SHELF")
+                Label::new(cx, tr("LF SHELF"))
                     .class("section-label")
                     .height(Pixels(16.0))
                     .width(Stretch(1.0));
-                components::create_frequency_slider(cx, "FREQ", Data::params, |p| &p.lf_freq);
-                components::create_gain_slider(cx, "GAIN", Data::params, |p| &p.lf_gain);
+                components::create_frequency_knob(cx, "FREQ", Data::params, |p| &p.lf_freq);
+                components::create_gain_knob(cx, "GAIN", Data::params, |p| &p.lf_gain);
             })
             .gap(Pixels(4.0))
             .height(Auto)
@@ -1833,12 +4050,12 @@ fn build_api5500_controls(cx: &mut Context) {
 
             // Right: HF high shelf
             VStack::new(cx, |cx| {
-                Label::new(cx, "HF SHELF")
+                Label::new(cx, tr("HF SHELF"))
                     .class("section-label")
                     .height(Pixels(16.0))
                     .width(Stretch(1.0));
-                components::create_frequency_slider(cx, "FREQ", Data::params, |p| &p.hf_freq);
-                components::create_gain_slider(cx, "GAIN", Data::params, |p| &p.hf_gain);
+                components::create_frequency_knob(cx, "FREQ", Data::params, |p| &p.hf_freq);
+                components::create_gain_knob(cx, "GAIN", Data::params, |p| &p.hf_gain);
             })
             .gap(Pixels(4.0))
             .height(Auto)
@@ -1854,19 +4071,38 @@ fn build_api5500_controls(cx: &mut Context) {
 
         // ── Parametric bands: LMF → MF → HMF (low to high) ──────────────────
         components::module_row(cx, |cx| {
-            components::create_frequency_slider(cx, "LMF", Data::params, |p| &p.lmf_freq);
-            components::create_gain_slider(cx, "GAIN", Data::params, |p| &p.lmf_gain);
-            components::create_param_slider(cx, "Q", Data::params, |p| &p.lmf_q);
+            components::create_frequency_knob(cx, "LMF", Data::params, |p| &p.lmf_freq);
+            components::create_gain_knob(cx, "GAIN", Data::params, |p| &p.lmf_gain);
+            components::create_param_knob(cx, "Q", Data::params, |p| &p.lmf_q);
         });
         components::module_row(cx, |cx| {
-            components::create_frequency_slider(cx, "MF", Data::params, |p| &p.mf_freq);
-            components::create_gain_slider(cx, "GAIN", Data::params, |p| &p.mf_gain);
-            components::create_param_slider(cx, "Q", Data::params, |p| &p.mf_q);
+            components::create_frequency_knob(cx, "MF", Data::params, |p| &p.mf_freq);
+            components::create_gain_knob(cx, "GAIN", Data::params, |p| &p.mf_gain);
+            components::create_param_knob(cx, "Q", Data::params, |p| &p.mf_q);
         });
         components::module_row(cx, |cx| {
-            components::create_frequency_slider(cx, "HMF", Data::params, |p| &p.hmf_freq);
-            components::create_gain_slider(cx, "GAIN", Data::params, |p| &p.hmf_gain);
-            components::create_param_slider(cx, "Q", Data::params, |p| &p.hmf_q);
+            components::create_frequency_knob(cx, "HMF", Data::params, |p| &p.hmf_freq);
+            components::create_gain_knob(cx, "GAIN", Data::params, |p| &p.hmf_gain);
+            components::create_param_knob(cx, "Q", Data::params, |p| &p.hmf_q);
+        });
+        build_advanced_drawer(cx, ModuleType::Api5500EQ, |cx| {
+            // Vintage: bands pull on each other, proportional Q, hiss.
+            components::create_param_slider(cx, "CHARACTER", Data::params, |p| &p.eq_character);
+            // Mastering mode: same curve, no phase shift, reported latency.
+            components::create_bool_button(cx, "LINEAR PHASE", Data::params, |p| {
+                &p.eq_linear_phase
+            });
+            // Per-band L/R trims for rebalancing a stereo bus; they take
+            // effect in the Dual Mono channel mode.
+            components::module_section(cx, "L/R OFFSET", |cx| {
+                components::module_row(cx, |cx| {
+                    components::create_param_knob(cx, "LF", Data::params, |p| &p.eq_lf_offset);
+                    components::create_param_knob(cx, "LMF", Data::params, |p| &p.eq_lmf_offset);
+                    components::create_param_knob(cx, "MF", Data::params, |p| &p.eq_mf_offset);
+                    components::create_param_knob(cx, "HMF", Data::params, |p| &p.eq_hmf_offset);
+                    components::create_param_knob(cx, "HF", Data::params, |p| &p.eq_hf_offset);
+                });
+            });
         });
     })
     .gap(Pixels(6.0))
@@ -1902,6 +4138,49 @@ fn build_buttercomp2_controls(cx: &mut Context) {
         // Fallback when buttercomp2 feature is disabled — render classic controls directly.
         #[cfg(not(feature = "buttercomp2"))]
         build_classic_controls(cx);
+
+        // Last ~10 s of gain reduction, for dialing attack/release by eye.
+        let history = Data::gr_history.get(cx);
+        components::create_gr_history(cx, history);
+
+        #[cfg(feature = "buttercomp2")]
+        build_advanced_drawer(cx, ModuleType::ButterComp2, |cx| {
+            // Detector key: own input or the aux sidechain.
+            components::create_param_slider(cx, "KEY", Data::params, |p| &p.comp_key);
+            // Key EQ on the detector (VCA, Optical and FET); KEY LISTEN in
+            // the master section plays what it leaves.
+            components::module_section(cx, "KEY EQ", |cx| {
+                components::module_row(cx, |cx| {
+                    components::create_bool_button(cx, "KEY EQ", Data::params, |p| &p.comp_key_eq);
+                    components::create_frequency_knob(cx, "KEY LP", Data::params, |p| {
+                        &p.comp_key_lp
+                    });
+                });
+                components::module_row(cx, |cx| {
+                    components::create_frequency_knob(cx, "KEY BELL", Data::params, |p| {
+                        &p.comp_key_bell_freq
+                    });
+                    components::create_gain_knob(cx, "BELL dB", Data::params, |p| {
+                        &p.comp_key_bell_gain
+                    });
+                    components::create_param_knob(cx, "BELL Q", Data::params, |p| {
+                        &p.comp_key_bell_q
+                    });
+                });
+            });
+            // Transient protect: the key ducks on attacks (not Classic).
+            components::create_param_knob(cx, "PROTECT", Data::params, |p| {
+                &p.comp_transient_protect
+            });
+            // Post-compressor trim and auto balance, shared by every model.
+            components::module_row(cx, |cx| {
+                components::create_gain_knob(cx, "TRIM L", Data::params, |p| &p.comp_trim_l);
+                components::create_gain_knob(cx, "TRIM R", Data::params, |p| &p.comp_trim_r);
+                components::create_bool_button(cx, "AUTO BAL", Data::params, |p| {
+                    &p.comp_auto_balance
+                });
+            });
+        });
     })
     .gap(Pixels(6.0))
     .height(Auto)
@@ -1913,12 +4192,17 @@ fn build_buttercomp2_controls(cx: &mut Context) {
 /// Classic ButterComp2 control surface — Compress, Output, SC HP, Dry/Wet.
 fn build_classic_controls(cx: &mut Context) {
     VStack::new(cx, |cx| {
-        components::create_ratio_slider(cx, "COMPRESS", Data::params, |p| &p.comp_compress);
-        components::create_gain_slider(cx, "OUTPUT", Data::params, |p| &p.comp_output);
+        components::create_ratio_knob(cx, "COMPRESS", Data::params, |p| &p.comp_compress);
+        components::create_gain_knob(cx, "OUTPUT", Data::params, |p| &p.comp_output);
         components::module_row(cx, |cx| {
-            components::create_frequency_slider(cx, "SC HP", Data::params, |p| &p.comp_sc_hp_freq);
-            components::create_param_slider(cx, "DRY/WET", Data::params, |p| &p.comp_dry_wet);
+            components::create_frequency_knob(cx, "SC HP", Data::params, |p| &p.comp_sc_hp_freq);
+            components::create_param_knob(cx, "DRY/WET", Data::params, |p| &p.comp_dry_wet);
         });
+        // Full Airwindows range instead of the safe mapping; see comp_scale.rs.
+        components::create_bool_button(cx, "FAITHFUL", Data::params, |p| &p.comp_faithful);
+        // The C++ core failed to allocate; Classic is passing audio through.
+        let degraded = Data::comp_degraded.get(cx);
+        components::create_status_led(cx, "CORE OFFLINE", degraded);
     })
     .gap(Pixels(6.0))
     .height(Auto)
@@ -1931,16 +4215,16 @@ fn build_classic_controls(cx: &mut Context) {
 fn build_vca_controls(cx: &mut Context) {
     VStack::new(cx, |cx| {
         components::module_row(cx, |cx| {
-            components::create_param_slider(cx, "THRESH", Data::params, |p| &p.vca_thresh);
-            components::create_ratio_slider(cx, "RATIO", Data::params, |p| &p.vca_ratio);
+            components::create_param_knob(cx, "THRESH", Data::params, |p| &p.vca_thresh);
+            components::create_ratio_knob(cx, "RATIO", Data::params, |p| &p.vca_ratio);
         });
         components::module_row(cx, |cx| {
-            components::create_param_slider(cx, "ATTACK", Data::params, |p| &p.vca_atk);
-            components::create_param_slider(cx, "RELEASE", Data::params, |p| &p.vca_rel);
+            components::create_param_knob(cx, "ATTACK", Data::params, |p| &p.vca_atk);
+            components::create_param_knob(cx, "RELEASE", Data::params, |p| &p.vca_rel);
         });
         components::module_row(cx, |cx| {
-            components::create_frequency_slider(cx, "SC HP", Data::params, |p| &p.comp_sc_hp_freq);
-            components::create_param_slider(cx, "MIX", Data::params, |p| &p.comp_dry_wet);
+            components::create_frequency_knob(cx, "SC HP", Data::params, |p| &p.comp_sc_hp_freq);
+            components::create_param_knob(cx, "MIX", Data::params, |p| &p.comp_dry_wet);
         });
     })
     .gap(Pixels(6.0))
@@ -1954,13 +4238,13 @@ fn build_vca_controls(cx: &mut Context) {
 fn build_optical_controls(cx: &mut Context) {
     VStack::new(cx, |cx| {
         components::module_row(cx, |cx| {
-            components::create_param_slider(cx, "THRESH", Data::params, |p| &p.opt_thresh);
-            components::create_param_slider(cx, "CHAR %", Data::params, |p| &p.opt_char);
+            components::create_param_knob(cx, "THRESH", Data::params, |p| &p.opt_thresh);
+            components::create_param_knob(cx, "CHAR %", Data::params, |p| &p.opt_char);
         });
-        components::create_param_slider(cx, "SPEED", Data::params, |p| &p.opt_speed);
+        components::create_param_knob(cx, "SPEED", Data::params, |p| &p.opt_speed);
         components::module_row(cx, |cx| {
-            components::create_frequency_slider(cx, "SC HP", Data::params, |p| &p.comp_sc_hp_freq);
-            components::create_param_slider(cx, "MIX", Data::params, |p| &p.comp_dry_wet);
+            components::create_frequency_knob(cx, "SC HP", Data::params, |p| &p.comp_sc_hp_freq);
+            components::create_param_knob(cx, "MIX", Data::params, |p| &p.comp_dry_wet);
         });
     })
     .gap(Pixels(6.0))
@@ -1975,20 +4259,20 @@ fn build_optical_controls(cx: &mut Context) {
 fn build_fet_controls(cx: &mut Context) {
     VStack::new(cx, |cx| {
         components::module_row(cx, |cx| {
-            components::create_gain_slider(cx, "INPUT", Data::params, |p| &p.fet_input_db);
-            components::create_gain_slider(cx, "OUTPUT", Data::params, |p| &p.fet_output_db);
+            components::create_gain_knob(cx, "INPUT", Data::params, |p| &p.fet_input_db);
+            components::create_gain_knob(cx, "OUTPUT", Data::params, |p| &p.fet_output_db);
         });
         components::module_row(cx, |cx| {
-            components::create_param_slider(cx, "ATTACK", Data::params, |p| &p.fet_attack_ms);
-            components::create_param_slider(cx, "RELEASE", Data::params, |p| &p.fet_release_ms);
+            components::create_param_knob(cx, "ATTACK", Data::params, |p| &p.fet_attack_ms);
+            components::create_param_knob(cx, "RELEASE", Data::params, |p| &p.fet_release_ms);
         });
         components::module_row(cx, |cx| {
             components::create_param_slider(cx, "RATIO", Data::params, |p| &p.fet_ratio);
             components::create_bool_button(cx, "AUTO REL", Data::params, |p| &p.fet_auto_release);
         });
         components::module_row(cx, |cx| {
-            components::create_frequency_slider(cx, "SC HP", Data::params, |p| &p.comp_sc_hp_freq);
-            components::create_param_slider(cx, "MIX", Data::params, |p| &p.comp_dry_wet);
+            components::create_frequency_knob(cx, "SC HP", Data::params, |p| &p.comp_sc_hp_freq);
+            components::create_param_knob(cx, "MIX", Data::params, |p| &p.comp_dry_wet);
         });
     })
     .gap(Pixels(6.0))
@@ -2002,57 +4286,72 @@ fn build_pultec_controls(cx: &mut Context) {
     VStack::new(cx, |cx| {
         // LOW FREQUENCY: boost freq/gain on top row, independent cut
         // freq/gain on bottom row. Independent cut freq enables the classic
-        // EQP-1A boost+cut trick (boost at 60 Hz, cut at 200 Hz → tight lows).
+        // EQP-1A boost+cut trick (boost at 60 Hz, cut at 200 Hz → tight lows);
+        // LINK ties it back to the boost like the stock unit's one selector.
         components::module_section(cx, "LOW FREQUENCY", |cx| {
             components::module_row(cx, |cx| {
-                components::create_frequency_slider(cx, "FREQ", Data::params, |p| {
+                components::create_frequency_knob(cx, "FREQ", Data::params, |p| {
                     &p.pultec_lf_boost_freq
                 });
-                components::create_gain_slider(cx, "BOOST", Data::params, |p| {
+                components::create_gain_knob(cx, "BOOST", Data::params, |p| {
                     &p.pultec_lf_boost_gain
                 });
-                components::create_param_slider(cx, "BW", Data::params, |p| {
+                components::create_param_knob(cx, "BW", Data::params, |p| {
                     &p.pultec_lf_boost_bandwidth
                 });
             });
             components::module_row(cx, |cx| {
-                components::create_frequency_slider(cx, "ATTEN", Data::params, |p| {
+                components::create_frequency_knob(cx, "ATTEN", Data::params, |p| {
                     &p.pultec_lf_cut_freq
                 });
-                components::create_gain_slider(cx, "ATTEN", Data::params, |p| {
-                    &p.pultec_lf_cut_gain
-                });
-                components::create_param_slider(cx, "BW", Data::params, |p| {
+                components::create_gain_knob(cx, "ATTEN", Data::params, |p| &p.pultec_lf_cut_gain);
+                components::create_param_knob(cx, "BW", Data::params, |p| {
                     &p.pultec_lf_cut_bandwidth
                 });
+                components::create_bool_button(cx, "LINK", Data::params, |p| &p.pultec_lf_cut_link);
             });
         });
         // HIGH FREQUENCY: boost and cut each on their own row (freq + gain/bw)
         components::module_section(cx, "HIGH FREQUENCY", |cx| {
             components::module_row(cx, |cx| {
-                components::create_frequency_slider(cx, "FREQ", Data::params, |p| {
+                components::create_frequency_knob(cx, "FREQ", Data::params, |p| {
                     &p.pultec_hf_boost_freq
                 });
-                components::create_gain_slider(cx, "BOOST", Data::params, |p| {
+                components::create_gain_knob(cx, "BOOST", Data::params, |p| {
                     &p.pultec_hf_boost_gain
                 });
-                components::create_param_slider(cx, "BW", Data::params, |p| {
+                components::create_param_knob(cx, "BW", Data::params, |p| {
                     &p.pultec_hf_boost_bandwidth
                 });
             });
             components::module_row(cx, |cx| {
-                components::create_frequency_slider(cx, "ATTEN", Data::params, |p| {
+                components::create_frequency_knob(cx, "ATTEN", Data::params, |p| {
                     &p.pultec_hf_cut_freq
                 });
-                components::create_gain_slider(cx, "ATTEN", Data::params, |p| {
-                    &p.pultec_hf_cut_gain
-                });
+                components::create_gain_knob(cx, "ATTEN", Data::params, |p| &p.pultec_hf_cut_gain);
             });
         });
         // OUTPUT: tube drive separate from the EQ bands
         components::module_section(cx, "OUTPUT", |cx| {
-            components::create_param_slider(cx, "TUBE DRIVE", Data::params, |p| {
-                &p.pultec_tube_drive
+            components::create_param_knob(cx, "TUBE DRIVE", Data::params, |p| &p.pultec_tube_drive);
+        });
+        build_advanced_drawer(cx, ModuleType::PultecEQ, |cx| {
+            // Component tolerance and thermal drift, reproducible per render.
+            components::create_bool_button(cx, "VARIANCE", Data::params, |p| &p.pultec_variance);
+            // Native-rate anti-aliasing instead of the 4× oversampler.
+            components::create_bool_button(cx, "ADAA", Data::params, |p| &p.pultec_adaa);
+            // Vintage: the passive sections load each other, plus hiss.
+            components::create_param_slider(cx, "CHARACTER", Data::params, |p| &p.pultec_character);
+            // Print mode trades the tube stage's oversampling for CPU while
+            // the host is struggling; the switch is the user's consent to that.
+            components::module_section(cx, "PRINT UNDER LOAD", |cx| {
+                components::create_bool_button(cx, "PRINT", Data::params, |p| &p.pultec_print);
+                Label::new(cx, tr("Drops tube oversampling; may alias at high drive"))
+                    .class("dyneq-card-hint")
+                    .height(Pixels(16.0))
+                    .width(Stretch(1.0));
+                let active = Data::print_active.get(cx);
+                components::create_status_led(cx, "PRINTED", active);
             });
         });
     })
@@ -2063,11 +4362,18 @@ fn build_pultec_controls(cx: &mut Context) {
     .bottom(Pixels(0.0));
 }
 
+/// Whether the DynEQ bands are set by range and sensitivity rather than
+/// threshold and ratio.
+#[cfg(feature = "dynamic_eq")]
+fn dyneq_range_law() -> impl Lens<Target = bool> {
+    Data::params.map(|p| p.dyneq_control_law.value() == ControlLaw::RangeSensitivity)
+}
+
 /// Compact DynEQ card shown inside the strip slot.
 /// All 4 bands are edited in the full back view — click OPEN to flip.
 fn build_dynamic_eq_controls(cx: &mut Context) {
     VStack::new(cx, |cx| {
-        Label::new(cx, "4-band dynamic equalizer")
+        Label::new(cx, tr("4-band dynamic equalizer"))
             .class("dyneq-card-hint")
             .height(Pixels(16.0))
             .width(Stretch(1.0));
@@ -2082,7 +4388,7 @@ fn build_dynamic_eq_controls(cx: &mut Context) {
         // Uses Button::new (not VStack) so the full 40px hit area is reliably clickable;
         // VStack + on_press can have dead zones where child labels shadow parent events.
         Button::new(cx, |cx| {
-            Label::new(cx, "OPEN EDITOR  \u{25B6}")
+            Label::new(cx, tr("OPEN EDITOR  \u{25B6}"))
                 .class("dyneq-open-label")
                 .width(Stretch(1.0))
                 .top(Pixels(0.0))
@@ -2095,6 +4401,10 @@ fn build_dynamic_eq_controls(cx: &mut Context) {
         .width(Stretch(1.0))
         .top(Pixels(0.0))
         .bottom(Pixels(0.0));
+        #[cfg(feature = "dynamic_eq")]
+        build_advanced_drawer(cx, ModuleType::DynamicEQ, |cx| {
+            components::create_param_slider(cx, "KEY", Data::params, |p| &p.dyneq_key);
+        });
     })
     .gap(Pixels(10.0))
     .height(Auto)
@@ -2107,12 +4417,203 @@ fn build_dynamic_eq_controls(cx: &mut Context) {
 // Spectrum Canvas — real-time lock-free spectrum display
 // ============================================================================
 
-/// Reads magnitude bins from the audio thread's lock-free `SpectrumData` and
+/// Display of every spectrum canvas, from the FFT settings toggle. A hidden
+/// canvas has zero bounds, which its `draw` treats as "skip this frame".
+fn analyzer_display() -> impl Lens<Target = Display> {
+    Data::settings.map(|s| {
+        if s.analyzer {
+            Display::Flex
+        } else {
+            Display::None
+        }
+    })
+}
+
+/// Analyzer tap / channels / window / overlap / averaging / floor, shown
+/// under each spectrum. The params are shared, so both views stay in step.
+fn build_analyzer_options(cx: &mut Context) {
+    HStack::new(cx, |cx| {
+        components::create_param_slider(cx, "TAP", Data::params, |p| &p.analyzer_tap);
+        components::create_param_slider(cx, "CHANNELS", Data::params, |p| &p.analyzer_channels);
+        components::create_param_slider(cx, "WINDOW", Data::params, |p| &p.analyzer_window);
+        components::create_param_slider(cx, "OVERLAP", Data::params, |p| &p.analyzer_overlap);
+        components::create_param_slider(cx, "AVERAGING", Data::params, |p| &p.analyzer_averaging);
+        components::create_param_slider(cx, "FLOOR", Data::params, |p| &p.analyzer_floor);
+        create_meter_rate_controls(cx);
+        create_meter_ballistics_controls(cx);
+        create_meter_scale_controls(cx);
+        create_spectrum_snapshot_controls(cx);
+    })
+    .height(Auto)
+    .width(Stretch(1.0))
+    .gap(Pixels(8.0));
+}
+
+// Meter ballistics buttons, next to the rate band. Display-side only: the
+// meters smooth what the audio thread publishes.
+fn create_meter_ballistics_controls(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        Label::new(cx, tr("METERS")).class("zoom-label");
+        HStack::new(cx, |cx| {
+            for (ballistics, tag) in [
+                (MeterBallistics::Fast, "F"),
+                (MeterBallistics::Normal, "N"),
+                (MeterBallistics::Slow, "S"),
+            ] {
+                VStack::new(cx, |cx| {
+                    Label::new(cx, tag).class("zoom-btn-label");
+                })
+                .class("zoom-btn")
+                .toggle_class(
+                    "zoom-btn-active",
+                    Data::settings.map(move |s| s.meter_ballistics == ballistics),
+                )
+                .on_press(move |cx| cx.emit(AppEvent::SetMeterBallistics(ballistics)))
+                .cursor(CursorIcon::Hand)
+                .width(Pixels(24.0))
+                .height(Pixels(24.0))
+                .top(Pixels(0.0))
+                .bottom(Pixels(0.0));
+            }
+        })
+        .gap(Pixels(2.0))
+        .height(Pixels(24.0))
+        .width(Auto)
+        .top(Pixels(0.0))
+        .bottom(Pixels(0.0));
+    })
+    .class("zoom-controls")
+    .height(Auto)
+    .width(Auto)
+    .gap(Pixels(4.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0));
+}
+
+// Analyzer freeze buttons: each captures the current curve as an overlay
+// (amber for 1, pink for 2) and clears it when pressed again.
+fn create_spectrum_snapshot_controls(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        Label::new(cx, tr("FREEZE")).class("zoom-label");
+        HStack::new(cx, |cx| {
+            for slot in 0..spectral::SPECTRUM_SNAPSHOTS {
+                VStack::new(cx, |cx| {
+                    Label::new(cx, (slot + 1).to_string()).class("zoom-btn-label");
+                })
+                .class("zoom-btn")
+                .toggle_class(
+                    "zoom-btn-active",
+                    Data::spectrum_snapshot_mask.map(move |mask| mask & (1 << slot) != 0),
+                )
+                .on_press(move |cx| cx.emit(AppEvent::ToggleSpectrumSnapshot(slot)))
+                .cursor(CursorIcon::Hand)
+                .width(Pixels(24.0))
+                .height(Pixels(24.0))
+                .top(Pixels(0.0))
+                .bottom(Pixels(0.0));
+            }
+        })
+        .gap(Pixels(2.0))
+        .height(Pixels(24.0))
+        .width(Auto)
+        .top(Pixels(0.0))
+        .bottom(Pixels(0.0));
+    })
+    .class("zoom-controls")
+    .height(Auto)
+    .width(Auto)
+    .gap(Pixels(4.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0));
+}
+
+// Meter scale buttons: digital peak, K-System or VU. Display-side only,
+// like the ballistics; see meter_scale.rs.
+fn create_meter_scale_controls(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        Label::new(cx, tr("SCALE")).class("zoom-label");
+        HStack::new(cx, |cx| {
+            for scale in MeterScale::ALL {
+                VStack::new(cx, |cx| {
+                    Label::new(cx, scale.label()).class("zoom-btn-label");
+                })
+                .class("zoom-btn")
+                .toggle_class(
+                    "zoom-btn-active",
+                    Data::settings.map(move |s| s.meter_scale == scale),
+                )
+                .on_press(move |cx| cx.emit(AppEvent::SetMeterScale(scale)))
+                .cursor(CursorIcon::Hand)
+                .width(Pixels(28.0))
+                .height(Pixels(24.0))
+                .top(Pixels(0.0))
+                .bottom(Pixels(0.0));
+            }
+        })
+        .gap(Pixels(2.0))
+        .height(Pixels(24.0))
+        .width(Auto)
+        .top(Pixels(0.0))
+        .bottom(Pixels(0.0));
+    })
+    .class("zoom-controls")
+    .height(Auto)
+    .width(Auto)
+    .gap(Pixels(4.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0));
+}
+
+// Meter refresh-rate buttons, styled like the language band. The rate is
+// applied on the audio side (see `spectral::MeterClock`), so a slower
+// setting saves DSP work as well as redraws.
+fn create_meter_rate_controls(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        Label::new(cx, tr("RATE")).class("zoom-label");
+        HStack::new(cx, |cx| {
+            for (rate, tag) in [
+                (MeterRate::Hz15, "15"),
+                (MeterRate::Hz30, "30"),
+                (MeterRate::Hz60, "60"),
+            ] {
+                VStack::new(cx, |cx| {
+                    Label::new(cx, tag).class("zoom-btn-label");
+                })
+                .class("zoom-btn")
+                .toggle_class("zoom-btn-active", Data::meter_rate.map(move |r| *r == rate))
+                .on_press(move |cx| cx.emit(AppEvent::SetMeterRate(rate)))
+                .cursor(CursorIcon::Hand)
+                .width(Pixels(30.0))
+                .height(Pixels(24.0))
+                .top(Pixels(0.0))
+                .bottom(Pixels(0.0));
+            }
+        })
+        .gap(Pixels(2.0))
+        .height(Pixels(24.0))
+        .width(Auto)
+        .top(Pixels(0.0))
+        .bottom(Pixels(0.0));
+    })
+    .class("zoom-controls")
+    .height(Auto)
+    .width(Auto)
+    .gap(Pixels(4.0))
+    .top(Pixels(0.0))
+    .bottom(Pixels(0.0));
+}
+
+/// Reads dB bins from the audio thread's lock-free `SpectrumData` and
 /// redraws each frame. Also overlays the sidechain masking analysis when available.
-/// Both `display_bins` and `display_overlap` are GUI-thread-only RefCells.
+/// When the analyzer splits channels the right side is drawn as a second line.
+/// Frozen snapshots are drawn behind the live curve.
+/// The `display_*` buffers are GUI-thread-only RefCells.
 struct SpectrumCanvas {
     spectrum_data: Arc<spectral::SpectrumData>,
+    /// Read each frame for `analyzer_snapshots`.
+    params: Arc<BusChannelStripParams>,
     display_bins: RefCell<Vec<f32>>,
+    display_right: RefCell<Vec<f32>>,
     analysis_result: Arc<spectral::AnalysisResult>,
     display_overlap: RefCell<Vec<f32>>,
     gr_data: Arc<spectral::GainReductionData>,
@@ -2126,8 +4627,10 @@ impl SpectrumCanvas {
         gr_data: Arc<spectral::GainReductionData>,
     ) -> Handle<'_, Self> {
         Self {
+            params: Data::params.get(cx),
+            display_bins: RefCell::new(vec![spectrum_data.floor_db(); spectral::SPECTRUM_BINS]),
+            display_right: RefCell::new(vec![spectrum_data.floor_db(); spectral::SPECTRUM_BINS]),
             spectrum_data,
-            display_bins: RefCell::new(vec![0.0_f32; spectral::SPECTRUM_BINS]),
             analysis_result,
             display_overlap: RefCell::new(vec![0.0_f32; spectral::SPECTRUM_BINS]),
             gr_data,
@@ -2158,6 +4661,11 @@ impl View for SpectrumCanvas {
             let mut bins = self.display_bins.borrow_mut();
             self.spectrum_data.read_into_slice(&mut bins)
         };
+        let split = self.spectrum_data.is_split();
+        if has_new_data && split {
+            self.spectrum_data
+                .read_right_into_slice(&mut self.display_right.borrow_mut());
+        }
         // Pull overlap bins from the last analysis (Relaxed — display-only, staleness is fine).
         {
             let mut overlap = self.display_overlap.borrow_mut();
@@ -2296,12 +4804,41 @@ impl View for SpectrumCanvas {
             }
         }
 
-        // ── Spectrum filled area (dBFS: −90 dB → bottom, 0 dB → top) ─────
+        let floor_db = self.spectrum_data.floor_db().min(-1.0);
+
+        // ── Frozen snapshots: lines only, behind the live curve ─────────────
+        if let Ok(snapshots) = self.params.analyzer_snapshots.try_read() {
+            const SNAPSHOT_ARGB: [(u8, u8, u8, u8); spectral::SPECTRUM_SNAPSHOTS] =
+                [(170, 240, 190, 90), (170, 240, 120, 170)];
+            for (slot, argb) in SNAPSHOT_ARGB.iter().enumerate() {
+                let Some(frozen) = snapshots.get(slot) else {
+                    continue;
+                };
+                let mut line = vg::Path::new();
+                for (i, &db) in frozen.iter().enumerate() {
+                    let norm = ((db - floor_db) / -floor_db).clamp(0.0, 1.0);
+                    let x = bounds.x + i as f32 * x_step;
+                    let y = bounds.y + bounds.h - norm * bounds.h;
+                    if i == 0 {
+                        line.move_to((x, y));
+                    } else {
+                        line.line_to((x, y));
+                    }
+                }
+                let mut paint = vg::Paint::default();
+                paint.set_color(vg::Color::from_argb(argb.0, argb.1, argb.2, argb.3));
+                paint.set_style(vg::PaintStyle::Stroke);
+                paint.set_stroke_width(1.0);
+                paint.set_anti_alias(true);
+                canvas.draw_path(&line, &paint);
+            }
+        }
+
+        // ── Spectrum filled area (dBFS: analyzer floor → bottom, 0 dB → top) ─
         let mut fill = vg::Path::new();
         let mut started = false;
-        for (i, &mag) in bins.iter().enumerate() {
-            let db = 20.0 * mag.max(1e-9_f32).log10();
-            let norm = ((db + 90.0) / 90.0).clamp(0.0, 1.0);
+        for (i, &db) in bins.iter().enumerate() {
+            let norm = ((db - floor_db) / -floor_db).clamp(0.0, 1.0);
             let x = bounds.x + i as f32 * x_step;
             let y = bounds.y + bounds.h - norm * bounds.h;
             if !started {
@@ -2323,9 +4860,8 @@ impl View for SpectrumCanvas {
         // ── Stroke line ──────────────────────────────────────────────────────
         let mut line = vg::Path::new();
         let mut started = false;
-        for (i, &mag) in bins.iter().enumerate() {
-            let db = 20.0 * mag.max(1e-9_f32).log10();
-            let norm = ((db + 90.0) / 90.0).clamp(0.0, 1.0);
+        for (i, &db) in bins.iter().enumerate() {
+            let norm = ((db - floor_db) / -floor_db).clamp(0.0, 1.0);
             let x = bounds.x + i as f32 * x_step;
             let y = bounds.y + bounds.h - norm * bounds.h;
             if !started {
@@ -2342,6 +4878,28 @@ impl View for SpectrumCanvas {
         stroke_paint.set_anti_alias(true);
         canvas.draw_path(&line, &stroke_paint);
 
+        // ── Right channel (split mode): line only, over the left fill ───────
+        if split {
+            let right = self.display_right.borrow();
+            let mut line = vg::Path::new();
+            for (i, &db) in right.iter().enumerate() {
+                let norm = ((db - floor_db) / -floor_db).clamp(0.0, 1.0);
+                let x = bounds.x + i as f32 * x_step;
+                let y = bounds.y + bounds.h - norm * bounds.h;
+                if i == 0 {
+                    line.move_to((x, y));
+                } else {
+                    line.line_to((x, y));
+                }
+            }
+            let mut right_paint = vg::Paint::default();
+            right_paint.set_color(vg::Color::from_argb(200, 190, 150, 255));
+            right_paint.set_style(vg::PaintStyle::Stroke);
+            right_paint.set_stroke_width(1.5);
+            right_paint.set_anti_alias(true);
+            canvas.draw_path(&line, &right_paint);
+        }
+
         // Always request the next frame when visible. The bounds guard above prevents
         // redraws when hidden. The has_new_data flag only tells us if the audio thread
         // wrote this frame — but skipping redraws on false would permanently stall the
@@ -2379,14 +4937,20 @@ impl View for SpectrumCanvas {
 //       band_N_enabled, band_N_solo,
 //       band_N_freq, band_N_threshold, band_N_ratio,
 //       band_N_q, band_N_mode, band_N_attack, band_N_release, band_N_gain);
+//
+// Under the Range/Sensitivity control law THRESH gives way to RANGE and
+// SENS, and RATIO drops out of tier 2.
 macro_rules! dyneq_slider {
     ($cx:expr, $label:literal, $pf:expr) => {{
         VStack::new($cx, |cx| {
-            Label::new(cx, $label)
+            Label::new(cx, tr($label))
                 .class("dyneq-param-label")
                 .height(Pixels(13.0))
                 .width(Stretch(1.0));
+            let ptr = components::param_ptr(cx, $pf);
             ParamSlider::new(cx, Data::params, $pf)
+                .param_entry(ptr)
+                .param_access(ptr)
                 .height(Pixels(16.0))
                 .width(Stretch(1.0));
         })
@@ -2404,12 +4968,15 @@ macro_rules! dyneq_band_col {
     ($cx:expr, $title:literal,
      $enabled:ident, $solo:ident,
      $freq:ident, $thresh:ident, $ratio:ident,
+     $range:ident, $sensitivity:ident,
      $q:ident, $mode:ident, $atk:ident, $rel:ident, $gain:ident,
+     $key_eq:ident, $key_hp:ident, $key_bell:ident, $key_gain:ident,
+     $key_q:ident, $key_lp:ident,
      $band_idx:literal) => {
         VStack::new($cx, |cx| {
             // Band header: title + ON/SOLO buttons + chevron expand toggle
             HStack::new(cx, |cx| {
-                Label::new(cx, $title)
+                Label::new(cx, tr($title))
                     .class("dyneq-band-title")
                     .height(Pixels(14.0))
                     .width(Stretch(1.0))
@@ -2445,11 +5012,48 @@ macro_rules! dyneq_band_col {
             .width(Stretch(1.0))
             .height(Auto);
 
-            // Tier 1 — always visible: MODE, FREQ, THRESH, GAIN
+            // Tier 1 — always visible: MODE, FREQ, THRESH (or RANGE, SENS), GAIN
             dyneq_slider!(cx, "MODE", |p| &p.$mode);
             dyneq_slider!(cx, "FREQ", |p| &p.$freq);
-            dyneq_slider!(cx, "THRESH", |p| &p.$thresh);
-            dyneq_slider!(cx, "GAIN", |p| &p.$gain);
+            Binding::new(cx, dyneq_range_law(), |cx, law| {
+                if law.get(cx) {
+                    dyneq_slider!(cx, "RANGE", |p| &p.$range);
+                    dyneq_slider!(cx, "SENS", |p| &p.$sensitivity);
+                } else {
+                    dyneq_slider!(cx, "THRESH", |p| &p.$thresh);
+                }
+            });
+            // GAIN carries a live overlay of the band's dynamic offset, so
+            // the band can be watched working while its gain is set.
+            VStack::new(cx, |cx| {
+                Label::new(cx, tr("GAIN"))
+                    .class("dyneq-param-label")
+                    .height(Pixels(13.0))
+                    .width(Stretch(1.0));
+                VStack::new(cx, |cx| {
+                    let ptr = components::param_ptr(cx, |p| &p.$gain);
+                    ParamSlider::new(cx, Data::params, |p| &p.$gain)
+                        .param_entry(ptr)
+                        .param_access(ptr)
+                        .height(Pixels(16.0))
+                        .width(Stretch(1.0));
+                    let gr_data = Data::gr_data.get(cx);
+                    let gain_ptr = Data::params.get(cx).$gain.as_ptr();
+                    components::create_dynamic_gain_overlay(cx, gr_data, $band_idx, gain_ptr)
+                        .position_type(PositionType::Absolute)
+                        .left(Pixels(0.0))
+                        .top(Pixels(0.0))
+                        .width(Stretch(1.0))
+                        .height(Stretch(1.0));
+                })
+                .height(Pixels(16.0))
+                .width(Stretch(1.0));
+            })
+            .class("param-control")
+            .width(Stretch(1.0))
+            .height(Auto)
+            .top(Stretch(1.0))
+            .bottom(Pixels(0.0));
 
             // Tier 2 — conditionally built when band is expanded.
             // Uses Binding::new rather than .display() because .display(lens.map(...))
@@ -2461,10 +5065,24 @@ macro_rules! dyneq_band_col {
                 Binding::new(cx, Data::dyneq_expand_gen, move |cx, _gen| {
                     if expand_arc_tier2[$band_idx].load(Ordering::Relaxed) {
                         VStack::new(cx, |cx| {
-                            dyneq_slider!(cx, "RATIO", |p| &p.$ratio);
+                            Binding::new(cx, dyneq_range_law(), |cx, law| {
+                                if !law.get(cx) {
+                                    dyneq_slider!(cx, "RATIO", |p| &p.$ratio);
+                                }
+                            });
                             dyneq_slider!(cx, "Q", |p| &p.$q);
                             dyneq_slider!(cx, "ATK ms", |p| &p.$atk);
                             dyneq_slider!(cx, "REL ms", |p| &p.$rel);
+                            // Key EQ: what the band's detector hears. Key
+                            // listen (master section) makes it audible.
+                            components::create_bool_button(cx, "KEY EQ", Data::params, |p| {
+                                &p.$key_eq
+                            });
+                            dyneq_slider!(cx, "KEY HP", |p| &p.$key_hp);
+                            dyneq_slider!(cx, "KEY BELL", |p| &p.$key_bell);
+                            dyneq_slider!(cx, "BELL dB", |p| &p.$key_gain);
+                            dyneq_slider!(cx, "BELL Q", |p| &p.$key_q);
+                            dyneq_slider!(cx, "KEY LP", |p| &p.$key_lp);
                         })
                         .width(Stretch(1.0))
                         .height(Auto)
@@ -2499,7 +5117,7 @@ fn build_dyneq_back_view(
         HStack::new(cx, |cx| {
             // Back button
             VStack::new(cx, |cx| {
-                Label::new(cx, "\u{25C0} STRIP VIEW")
+                Label::new(cx, tr("\u{25C0} STRIP VIEW"))
                     .class("dyneq-back-btn-label")
                     .height(Pixels(16.0))
                     .width(Stretch(1.0));
@@ -2512,7 +5130,7 @@ fn build_dyneq_back_view(
             .top(Pixels(0.0))
             .bottom(Pixels(0.0));
 
-            Label::new(cx, "DYNAMIC EQ")
+            Label::new(cx, tr("DYNAMIC EQ"))
                 .class("dyneq-back-title")
                 .height(Pixels(28.0))
                 .top(Pixels(0.0))
@@ -2520,6 +5138,10 @@ fn build_dyneq_back_view(
 
             #[cfg(feature = "dynamic_eq")]
             components::create_bypass_button(cx, "BYPASS", |p| &p.dyneq_bypass);
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_slider!(cx, "LAW", |p| &p.dyneq_control_law)
+                .width(Pixels(150.0))
+                .top(Pixels(0.0));
 
             // ── Sidechain masking analysis controls ──────────────────────────
             // ANALYZE: arms the audio thread to run one analysis on the next FFT frame.
@@ -2529,7 +5151,7 @@ fn build_dyneq_back_view(
             {
                 let ar_clone = analysis_result.clone();
                 VStack::new(cx, |cx| {
-                    Label::new(cx, "ANALYZE SC")
+                    Label::new(cx, tr("ANALYZE SC"))
                         .class("dyneq-auto-btn-label")
                         .height(Pixels(14.0))
                         .width(Stretch(1.0));
@@ -2543,7 +5165,7 @@ fn build_dyneq_back_view(
                 .bottom(Pixels(0.0));
 
                 VStack::new(cx, |cx| {
-                    Label::new(cx, "APPLY RESULT")
+                    Label::new(cx, tr("APPLY RESULT"))
                         .class("dyneq-apply-btn-label")
                         .height(Pixels(14.0))
                         .width(Stretch(1.0));
@@ -2583,11 +5205,17 @@ fn build_dyneq_back_view(
         // windows.
         SpectrumCanvas::new(cx, spectrum_data, analysis_result, gr_data)
             .class("dyneq-spectrum")
+            .display(analyzer_display())
             .height(Stretch(2.0))
             .min_height(Pixels(180.0))
             .width(Stretch(1.0))
             .top(Pixels(0.0))
             .bottom(Pixels(0.0));
+        build_analyzer_options(cx);
+
+        // ── Gain-reduction history (comp + DynEQ, last ~10 s) ────────────────
+        let history = Data::gr_history.get(cx);
+        components::create_gr_history(cx, history);
 
         // ── 4-band horizontal editor ──────────────────────────────────────────
         #[cfg(feature = "dynamic_eq")]
@@ -2603,11 +5231,19 @@ fn build_dyneq_back_view(
                 dyneq_band1_freq,
                 dyneq_band1_threshold,
                 dyneq_band1_ratio,
+                dyneq_band1_range,
+                dyneq_band1_sensitivity,
                 dyneq_band1_q,
                 dyneq_band1_mode,
                 dyneq_band1_attack,
                 dyneq_band1_release,
                 dyneq_band1_gain,
+                dyneq_band1_key_eq,
+                dyneq_band1_key_hp,
+                dyneq_band1_key_bell_freq,
+                dyneq_band1_key_bell_gain,
+                dyneq_band1_key_bell_q,
+                dyneq_band1_key_lp,
                 0
             );
 
@@ -2619,11 +5255,19 @@ fn build_dyneq_back_view(
                 dyneq_band2_freq,
                 dyneq_band2_threshold,
                 dyneq_band2_ratio,
+                dyneq_band2_range,
+                dyneq_band2_sensitivity,
                 dyneq_band2_q,
                 dyneq_band2_mode,
                 dyneq_band2_attack,
                 dyneq_band2_release,
                 dyneq_band2_gain,
+                dyneq_band2_key_eq,
+                dyneq_band2_key_hp,
+                dyneq_band2_key_bell_freq,
+                dyneq_band2_key_bell_gain,
+                dyneq_band2_key_bell_q,
+                dyneq_band2_key_lp,
                 1
             );
 
@@ -2635,11 +5279,19 @@ fn build_dyneq_back_view(
                 dyneq_band3_freq,
                 dyneq_band3_threshold,
                 dyneq_band3_ratio,
+                dyneq_band3_range,
+                dyneq_band3_sensitivity,
                 dyneq_band3_q,
                 dyneq_band3_mode,
                 dyneq_band3_attack,
                 dyneq_band3_release,
                 dyneq_band3_gain,
+                dyneq_band3_key_eq,
+                dyneq_band3_key_hp,
+                dyneq_band3_key_bell_freq,
+                dyneq_band3_key_bell_gain,
+                dyneq_band3_key_bell_q,
+                dyneq_band3_key_lp,
                 2
             );
 
@@ -2651,11 +5303,19 @@ fn build_dyneq_back_view(
                 dyneq_band4_freq,
                 dyneq_band4_threshold,
                 dyneq_band4_ratio,
+                dyneq_band4_range,
+                dyneq_band4_sensitivity,
                 dyneq_band4_q,
                 dyneq_band4_mode,
                 dyneq_band4_attack,
                 dyneq_band4_release,
                 dyneq_band4_gain,
+                dyneq_band4_key_eq,
+                dyneq_band4_key_hp,
+                dyneq_band4_key_bell_freq,
+                dyneq_band4_key_bell_gain,
+                dyneq_band4_key_bell_q,
+                dyneq_band4_key_lp,
                 3
             );
         })
@@ -2666,14 +5326,20 @@ fn build_dyneq_back_view(
         .bottom(Pixels(0.0));
 
         #[cfg(not(feature = "dynamic_eq"))]
-        Label::new(cx, "enable dynamic_eq feature to use this module").class("module-type");
+        Label::new(cx, tr("enable dynamic_eq feature to use this module")).class("module-type");
     })
     .class("dyneq-back-view")
     .height(Stretch(1.0))
     .width(Stretch(1.0))
     .gap(Pixels(12.0))
     .padding(Pixels(16.0))
-    .display(Data::dyneq_open.map(|o| if *o { Display::Flex } else { Display::None }));
+    .display(Data::view.map(|v| {
+        if v.is_open(Panel::DynEq) {
+            Display::Flex
+        } else {
+            Display::None
+        }
+    }));
 }
 
 // ============================================================================
@@ -2689,7 +5355,7 @@ fn build_sheen_back_view(cx: &mut Context) {
         // ── Header row: back button + wordmark ─────────────────────────
         HStack::new(cx, |cx| {
             VStack::new(cx, |cx| {
-                Label::new(cx, "\u{25C0} STRIP VIEW")
+                Label::new(cx, tr("\u{25C0} STRIP VIEW"))
                     .class("sheen-back-btn-label")
                     .height(Pixels(16.0))
                     .width(Stretch(1.0));
@@ -2702,7 +5368,7 @@ fn build_sheen_back_view(cx: &mut Context) {
             .top(Pixels(0.0))
             .bottom(Pixels(0.0));
 
-            Label::new(cx, "SHEEN")
+            Label::new(cx, tr("SHEEN"))
                 .class("sheen-back-title")
                 .height(Pixels(28.0))
                 .top(Pixels(0.0))
@@ -2719,11 +5385,13 @@ fn build_sheen_back_view(cx: &mut Context) {
         // ── Master strip: bypass + restore ─────────────────────────────
         HStack::new(cx, |cx| {
             VStack::new(cx, |cx| {
-                Label::new(cx, "MASTER BYPASS")
+                Label::new(cx, tr("MASTER BYPASS"))
                     .class("param-label")
                     .height(Pixels(14.0))
                     .width(Stretch(1.0));
+                let ptr = components::param_ptr(cx, |p| &p.sheen_bypass);
                 ParamButton::new(cx, Data::params, |p| &p.sheen_bypass)
+                    .param_access(ptr)
                     .class("sheen-master-bypass")
                     .height(Pixels(32.0))
                     .width(Stretch(1.0));
@@ -2742,7 +5410,7 @@ fn build_sheen_back_view(cx: &mut Context) {
                     .class("param-label")
                     .height(Pixels(14.0))
                     .width(Stretch(1.0));
-                Label::new(cx, "\u{21BA} RESTORE FACTORY")
+                Label::new(cx, tr("\u{21BA} RESTORE FACTORY"))
                     .class("sheen-restore-btn")
                     .height(Pixels(32.0))
                     .width(Stretch(1.0));
@@ -2784,7 +5452,535 @@ fn build_sheen_back_view(cx: &mut Context) {
     .width(Stretch(1.0))
     .gap(Pixels(12.0))
     .padding(Pixels(16.0))
-    .display(Data::sheen_open.map(|o| if *o { Display::Flex } else { Display::None }));
+    .display(Data::view.map(|v| {
+        if v.is_open(Panel::Sheen) {
+            Display::Flex
+        } else {
+            Display::None
+        }
+    }));
+}
+
+// ============================================================================
+// Metering Bridge — every meter in one panel
+// ============================================================================
+
+/// Loudness readout text: one decimal, floor shown as "-inf".
+fn format_lufs(lufs: f32) -> String {
+    if lufs <= crate::loudness::LOUDNESS_FLOOR_LUFS {
+        "-inf".to_owned()
+    } else {
+        format!("{lufs:.1}")
+    }
+}
+
+/// Dynamics readout text: one decimal dB, "-" without a reading.
+fn format_dynamics(db: Option<f32>) -> String {
+    db.map_or_else(|| "-".to_owned(), |db| format!("{db:.1}"))
+}
+
+fn build_dynamics_row(cx: &mut Context, name: &str, stat: spectral::DynamicsStat) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr(name))
+            .class("meter-bridge-lufs-name")
+            .width(Stretch(1.0));
+        Label::new(
+            cx,
+            Data::dynamics_readings.map(move |readings| format_dynamics(readings[stat as usize])),
+        )
+        .class("meter-bridge-lufs-value")
+        .width(Auto);
+    })
+    .class("meter-bridge-lufs-row")
+    .height(Pixels(28.0))
+    .alignment(Alignment::Center);
+}
+
+fn build_engagement_row(cx: &mut Context, name: &str, stage: spectral::EngagementStage) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr(name))
+            .class("meter-bridge-lufs-name")
+            .width(Stretch(1.0));
+        Label::new(
+            cx,
+            Data::engagement_readings
+                .map(move |readings| format!("{:.0}%", readings[stage as usize] * 100.0)),
+        )
+        .class("meter-bridge-lufs-value")
+        .width(Auto);
+    })
+    .class("meter-bridge-lufs-row")
+    .height(Pixels(28.0))
+    .alignment(Alignment::Center);
+}
+
+fn build_lufs_row(cx: &mut Context, name: &str, lens: impl Lens<Target = f32>) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr(name))
+            .class("meter-bridge-lufs-name")
+            .width(Stretch(1.0));
+        Label::new(cx, lens.map(|v| format_lufs(*v)))
+            .class("meter-bridge-lufs-value")
+            .width(Auto);
+    })
+    .class("meter-bridge-lufs-row")
+    .height(Pixels(28.0))
+    .alignment(Alignment::Center);
+}
+
+/// Suggestion readout, e.g. "IN +6.3 · CEILING -1.0 · CLIP 1.3 dB → -14.0 LUFS".
+#[cfg(feature = "punch")]
+fn format_loudness_suggestion(suggestion: &LoudnessSuggestion) -> String {
+    format!(
+        "{}IN {:+.1} \u{00b7} CEILING {:.1} \u{00b7} CLIP {:.1} dB \u{2192} {:.1} LUFS",
+        if suggestion.reachable {
+            ""
+        } else {
+            "OUT OF REACH \u{00b7} "
+        },
+        suggestion.input_gain_db,
+        suggestion.ceiling_db,
+        suggestion.clip_db,
+        suggestion.expected_lufs,
+    )
+}
+
+/// Suggestion readout, e.g. "LF +2 · 160 Hz -3 · 1 kHz 0 · 5 kHz +1 · HF +3" (dB).
+fn format_match_suggestion(suggestion: &matching::MatchSuggestion) -> String {
+    let hz = |freq: f32| {
+        if freq >= 1000.0 {
+            format!("{} kHz", freq / 1000.0)
+        } else {
+            format!("{freq} Hz")
+        }
+    };
+    format!(
+        "LF {:+} \u{00b7} {} {:+} \u{00b7} {} {:+} \u{00b7} {} {:+} \u{00b7} HF {:+} dB",
+        suggestion.lf_gain_db,
+        hz(suggestion.lmf.freq),
+        suggestion.lmf.gain_db,
+        hz(suggestion.mf.freq),
+        suggestion.mf.gain_db,
+        hz(suggestion.hmf.freq),
+        suggestion.hmf.gain_db,
+        suggestion.hf_gain_db,
+    )
+}
+
+/// Bridge button in the preset-browser action style.
+#[cfg(feature = "api5500")]
+fn build_match_action(cx: &mut Context, label: &str, primary: bool, event: MatchEvent) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, tr(label)).class("preset-action-label");
+    })
+    .class("preset-action-btn")
+    .toggle_class("preset-action-primary", primary)
+    .on_press(move |cx| cx.emit(event))
+    .cursor(CursorIcon::Hand)
+    .width(Auto)
+    .height(Pixels(24.0));
+}
+
+/// Spectrum-match EQ: capture the program against the sidechain or a WAV
+/// reference, then write the suggested API5500 curve with one click.
+#[cfg(feature = "api5500")]
+fn build_match_panel(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        HStack::new(cx, |cx| {
+            Label::new(cx, tr("EQ MATCH"))
+                .class("param-label")
+                .width(Auto);
+            Binding::new(cx, Data::language, |cx, language| {
+                let language = language.get(cx);
+                Label::new(
+                    cx,
+                    Data::match_reference_name.map(move |name| {
+                        format!(
+                            "{} {}",
+                            i18n::translate(language, "REFERENCE:"),
+                            i18n::translate(language, name)
+                        )
+                    }),
+                )
+                .class("meter-bridge-match-ref")
+                .width(Auto);
+            });
+            Textbox::new(cx, Data::match_reference_path)
+                .class("preset-textbox")
+                .on_edit(|cx, text| cx.emit(MatchEvent::SetReferencePath(text)))
+                .on_submit(|cx, _text, success| {
+                    if success {
+                        cx.emit(MatchEvent::LoadReference);
+                    }
+                })
+                .width(Stretch(1.0))
+                .height(Pixels(24.0));
+            build_match_action(cx, "LOAD WAV", false, MatchEvent::LoadReference);
+            build_match_action(cx, "USE SIDECHAIN", false, MatchEvent::UseSidechain);
+            build_match_action(cx, "CAPTURE", false, MatchEvent::Capture);
+            build_match_action(cx, "APPLY TO EQ", true, MatchEvent::Apply);
+        })
+        .height(Pixels(28.0))
+        .gap(Pixels(8.0))
+        .alignment(Alignment::Center);
+
+        Binding::new(cx, Data::language, |cx, language| {
+            let language = language.get(cx);
+            Label::new(
+                cx,
+                Data::match_status.map(move |status| {
+                    if status.is_empty() {
+                        i18n::translate(
+                            language,
+                            "Play the program, then CAPTURE to compare it with the reference",
+                        )
+                        .to_owned()
+                    } else {
+                        i18n::translate(language, status).to_owned()
+                    }
+                }),
+            )
+            .class("meter-bridge-match-status")
+            .width(Stretch(1.0));
+        });
+    })
+    .class("meter-bridge-panel")
+    .height(Auto)
+    .width(Stretch(1.0))
+    .gap(Pixels(6.0));
+}
+
+/// EQ response export: the static curve of the EQ modules in the rack as
+/// CSV, or as an impulse-response WAV for a convolver.
+#[cfg(any(feature = "api5500", feature = "pultec"))]
+fn build_response_panel(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        HStack::new(cx, |cx| {
+            Label::new(cx, tr("EQ RESPONSE"))
+                .class("param-label")
+                .width(Auto);
+            Textbox::new(cx, Data::response_path)
+                .class("preset-textbox")
+                .on_edit(|cx, text| cx.emit(ResponseEvent::SetPath(text)))
+                .width(Stretch(1.0))
+                .height(Pixels(24.0));
+            for (label, format) in [
+                ("EXPORT CSV", ResponseFormat::Csv),
+                ("EXPORT WAV", ResponseFormat::Wav),
+            ] {
+                HStack::new(cx, |cx| {
+                    Label::new(cx, tr(label)).class("preset-action-label");
+                })
+                .class("preset-action-btn")
+                .on_press(move |cx| cx.emit(ResponseEvent::Export(format)))
+                .cursor(CursorIcon::Hand)
+                .width(Auto)
+                .height(Pixels(24.0));
+            }
+        })
+        .height(Pixels(28.0))
+        .gap(Pixels(8.0))
+        .alignment(Alignment::Center);
+
+        Binding::new(cx, Data::language, |cx, language| {
+            let language = language.get(cx);
+            Label::new(
+                cx,
+                Data::response_status.map(move |status| {
+                    let status = if status.is_empty() {
+                        "Type a path, then export the EQ curve as CSV or an impulse-response WAV"
+                    } else {
+                        status.as_str()
+                    };
+                    i18n::translate(language, status).to_owned()
+                }),
+            )
+            .class("meter-bridge-match-status")
+            .width(Stretch(1.0));
+        });
+    })
+    .class("meter-bridge-panel")
+    .height(Auto)
+    .width(Stretch(1.0))
+    .gap(Pixels(6.0));
+}
+
+/// Expanded metering view for loudness checks on the master bus: spectrum,
+/// LUFS, a full-size goniometer/correlation meter, per-module gain
+/// reduction and the spectrum-match EQ. Takes the rack's place while open;
+/// the header stays live so master gain and A/B remain in reach.
+fn build_meter_bridge(
+    cx: &mut Context,
+    spectrum_data: Arc<spectral::SpectrumData>,
+    analysis_result: Arc<spectral::AnalysisResult>,
+    gr_data: Arc<spectral::GainReductionData>,
+) {
+    VStack::new(cx, |cx| {
+        // ── Header row: back button + title ────────────────────────────
+        HStack::new(cx, |cx| {
+            VStack::new(cx, |cx| {
+                Label::new(cx, tr("\u{25C0} STRIP VIEW"))
+                    .class("meter-bridge-back-btn-label")
+                    .height(Pixels(16.0))
+                    .width(Stretch(1.0));
+            })
+            .class("meter-bridge-back-btn")
+            .on_press(|cx| cx.emit(AppEvent::ToggleMeterBridge))
+            .cursor(CursorIcon::Hand)
+            .height(Pixels(32.0))
+            .width(Pixels(140.0))
+            .top(Pixels(0.0))
+            .bottom(Pixels(0.0));
+
+            Label::new(cx, tr("METERING"))
+                .class("meter-bridge-title")
+                .height(Pixels(28.0))
+                .top(Pixels(0.0))
+                .bottom(Pixels(0.0));
+        })
+        .height(Pixels(40.0))
+        .width(Stretch(1.0))
+        .gap(Pixels(12.0))
+        .alignment(Alignment::Center);
+
+        // ── Top row: spectrum | loudness | stereo image ────────────────
+        HStack::new(cx, |cx| {
+            VStack::new(cx, |cx| {
+                // Tap point and channel mode are picked in the options row.
+                Label::new(cx, tr("SPECTRUM"))
+                    .class("param-label")
+                    .height(Pixels(16.0));
+                SpectrumCanvas::new(cx, spectrum_data, analysis_result, gr_data.clone())
+                    .class("dyneq-spectrum")
+                    .display(analyzer_display())
+                    .height(Stretch(1.0))
+                    .min_height(Pixels(140.0))
+                    .width(Stretch(1.0));
+                build_analyzer_options(cx);
+            })
+            .width(Stretch(1.0))
+            .height(Stretch(1.0))
+            .gap(Pixels(4.0));
+
+            VStack::new(cx, |cx| {
+                Label::new(cx, tr("LOUDNESS (LUFS)"))
+                    .class("param-label")
+                    .height(Pixels(16.0));
+                build_lufs_row(cx, "MOMENTARY", Data::lufs_momentary);
+                build_lufs_row(cx, "SHORT-TERM", Data::lufs_short_term);
+                build_lufs_row(cx, "INTEGRATED", Data::lufs_integrated);
+                Label::new(cx, tr("\u{21BA} RESET INTEGRATED"))
+                    .class("meter-bridge-reset-btn")
+                    .on_press(|cx| cx.emit(AppEvent::ResetLoudness))
+                    .cursor(CursorIcon::Hand)
+                    .height(Pixels(26.0))
+                    .width(Stretch(1.0));
+            })
+            .class("meter-bridge-panel")
+            .width(Pixels(220.0))
+            .height(Auto)
+            .gap(Pixels(6.0));
+
+            // Input vs output crest shows how much the chain flattens the
+            // mix; PSR / PLR / spread describe what goes out.
+            VStack::new(cx, |cx| {
+                use spectral::DynamicsStat;
+                Label::new(cx, tr("DYNAMICS (dB)"))
+                    .class("param-label")
+                    .height(Pixels(16.0));
+                build_dynamics_row(cx, "CREST IN", DynamicsStat::InputCrest);
+                build_dynamics_row(cx, "CREST OUT", DynamicsStat::OutputCrest);
+                build_dynamics_row(cx, "PSR", DynamicsStat::Psr);
+                build_dynamics_row(cx, "PLR", DynamicsStat::Plr);
+                build_dynamics_row(cx, "SPREAD", DynamicsStat::Spread);
+            })
+            .class("meter-bridge-panel")
+            .width(Pixels(200.0))
+            .height(Auto)
+            .gap(Pixels(6.0));
+
+            VStack::new(cx, |cx| {
+                Label::new(cx, tr("STEREO / CORRELATION"))
+                    .class("param-label")
+                    .height(Pixels(16.0));
+                let scope = Data::scope_data.get(cx);
+                components::create_stereo_scope(cx, scope, 160.0, 176.0);
+            })
+            .class("meter-bridge-panel")
+            .width(Auto)
+            .height(Auto)
+            .gap(Pixels(6.0));
+        })
+        .height(Stretch(1.0))
+        .width(Stretch(1.0))
+        .gap(Pixels(12.0));
+
+        // ── Bottom row: per-module gain reduction + GR history ─────────
+        HStack::new(cx, |cx| {
+            HStack::new(cx, |cx| {
+                let history = Data::gr_history.get(cx);
+                components::create_gr_bar(cx, "COMP", move || {
+                    history.lock().map_or(0.0, |mut history| {
+                        history.update();
+                        history.latest().0
+                    })
+                });
+                for (band, name) in ["EQ 1", "EQ 2", "EQ 3", "EQ 4"].into_iter().enumerate() {
+                    let gr_data = gr_data.clone();
+                    components::create_gr_bar(cx, name, move || {
+                        f32::from_bits(gr_data.bands[band].load(Ordering::Relaxed))
+                    });
+                }
+                // Clipper reports the fraction of the peak removed.
+                let clip = Data::punch_clip.get(cx);
+                components::create_gr_bar(cx, "CLIP", move || {
+                    -util::gain_to_db((1.0 - clip.load()).max(1e-6))
+                });
+            })
+            .class("meter-bridge-panel")
+            .width(Auto)
+            .height(Stretch(1.0))
+            .gap(Pixels(8.0));
+
+            // Share of samples each nonlinear stage bends: where the level
+            // is being eaten, as opposed to how hard (the bars beside it).
+            VStack::new(cx, |cx| {
+                use spectral::EngagementStage;
+                Label::new(cx, tr("ENGAGED"))
+                    .class("param-label")
+                    .height(Pixels(16.0));
+                build_engagement_row(cx, "TUBE", EngagementStage::PultecTube);
+                build_engagement_row(cx, "XFMR", EngagementStage::Transformer);
+                build_engagement_row(cx, "CLIP", EngagementStage::PunchClip);
+            })
+            .class("meter-bridge-panel")
+            .width(Pixels(160.0))
+            .height(Stretch(1.0))
+            .gap(Pixels(4.0));
+
+            VStack::new(cx, |cx| {
+                let sat = Data::transformer_sat.get(cx);
+                components::create_drive_meter(cx, "XFMR", sat);
+                let history = Data::gr_history.get(cx);
+                components::create_gr_history(cx, history);
+            })
+            .class("meter-bridge-panel")
+            .width(Stretch(1.0))
+            .height(Auto)
+            .gap(Pixels(8.0));
+        })
+        .height(Pixels(150.0))
+        .width(Stretch(1.0))
+        .gap(Pixels(12.0));
+
+        // ── Loudness target ─────────────────────────────────────────────
+        #[cfg(feature = "punch")]
+        build_loudness_target_panel(cx);
+
+        // ── Spectrum-match EQ ───────────────────────────────────────────
+        #[cfg(feature = "api5500")]
+        build_match_panel(cx);
+
+        // ── EQ response export ──────────────────────────────────────────
+        #[cfg(any(feature = "api5500", feature = "pultec"))]
+        build_response_panel(cx);
+    })
+    .class("meter-bridge")
+    .height(Stretch(1.0))
+    .width(Stretch(1.0))
+    .gap(Pixels(12.0))
+    .padding(Pixels(16.0))
+    .display(Data::view.map(|v| {
+        if v.is_open(Panel::MeterBridge) {
+            Display::Flex
+        } else {
+            Display::None
+        }
+    }));
+}
+
+/// Target step button in the A/B style.
+#[cfg(feature = "punch")]
+fn build_target_step(cx: &mut Context, label: &'static str, step: i8) {
+    HStack::new(cx, |cx| {
+        Label::new(cx, label).class("ab-btn-label");
+    })
+    .class("ab-btn")
+    .on_press(move |cx| cx.emit(LoudnessEvent::StepTarget(step)))
+    .cursor(CursorIcon::Hand)
+    .width(Pixels(24.0))
+    .height(Pixels(24.0));
+}
+
+/// Loudness-target assistant: a learn pass measures the program at Punch
+/// and at the output, then Punch's drive and ceiling are set for the target.
+#[cfg(feature = "punch")]
+fn build_loudness_target_panel(cx: &mut Context) {
+    VStack::new(cx, |cx| {
+        HStack::new(cx, |cx| {
+            Label::new(cx, tr("LOUDNESS TARGET"))
+                .class("param-label")
+                .width(Auto);
+            build_target_step(cx, "\u{2212}", -1);
+            Label::new(
+                cx,
+                Data::settings.map(|s| format!("{} LUFS", s.loudness_target)),
+            )
+            .class("meter-bridge-match-ref")
+            .width(Pixels(64.0));
+            build_target_step(cx, "+", 1);
+            for (label, primary, event) in [
+                ("LEARN", false, LoudnessEvent::Learn),
+                ("APPLY TO PUNCH", true, LoudnessEvent::Apply),
+            ] {
+                HStack::new(cx, |cx| {
+                    Label::new(cx, tr(label)).class("preset-action-label");
+                })
+                .class("preset-action-btn")
+                .toggle_class("preset-action-primary", primary)
+                .on_press(move |cx| cx.emit(event))
+                .cursor(CursorIcon::Hand)
+                .width(Auto)
+                .height(Pixels(24.0));
+            }
+            HStack::new(cx, |cx| {
+                Label::new(cx, tr("AUTO")).class("ab-btn-label");
+            })
+            .class("ab-btn")
+            .toggle_class(
+                "ab-btn-active",
+                Data::settings.map(|s| s.loudness_auto_apply),
+            )
+            .on_press(|cx| cx.emit(LoudnessEvent::ToggleAutoApply))
+            .cursor(CursorIcon::Hand)
+            .width(Pixels(48.0))
+            .height(Pixels(24.0));
+        })
+        .height(Pixels(28.0))
+        .gap(Pixels(8.0))
+        .alignment(Alignment::Center);
+
+        Binding::new(cx, Data::language, |cx, language| {
+            let language = language.get(cx);
+            Label::new(
+                cx,
+                Data::loudness_status.map(move |status| {
+                    let status = if status.is_empty() {
+                        "Set a target, play the program, then LEARN"
+                    } else {
+                        status.as_str()
+                    };
+                    i18n::translate(language, status).to_owned()
+                }),
+            )
+            .class("meter-bridge-match-status")
+            .width(Stretch(1.0));
+        });
+    })
+    .class("meter-bridge-panel")
+    .height(Auto)
+    .width(Stretch(1.0))
+    .gap(Pixels(6.0));
 }
 
 /// One vertical column for a Sheen stage. The `is_first` flag decides which
@@ -2794,7 +5990,7 @@ fn build_sheen_back_view(cx: &mut Context) {
 fn sheen_stage_column(cx: &mut Context, name: &'static str, sub: &'static str, _is_first: bool) {
     VStack::new(cx, |cx| {
         // Stage header: large brass-typography name + small unit hint.
-        Label::new(cx, name)
+        Label::new(cx, tr(name))
             .class("sheen-stage-name")
             .height(Pixels(22.0))
             .width(Stretch(1.0));
@@ -2808,51 +6004,76 @@ fn sheen_stage_column(cx: &mut Context, name: &'static str, sub: &'static str, _
         // param so the two sliders below stay in sync visually.
         match name {
             "BODY" => {
+                let ptr = components::param_ptr(cx, |p| &p.sheen_body_db);
                 ParamSlider::new(cx, Data::params, |p| &p.sheen_body_db)
+                    .param_entry(ptr)
+                    .param_access(ptr)
                     .class("sheen-slider")
                     .height(Pixels(22.0))
                     .width(Stretch(1.0));
+                let ptr = components::param_ptr(cx, |p| &p.sheen_body_bypass);
                 ParamButton::new(cx, Data::params, |p| &p.sheen_body_bypass)
+                    .param_access(ptr)
                     .class("sheen-stage-bypass")
                     .height(Pixels(24.0))
                     .width(Stretch(1.0));
             }
             "PRESENCE" => {
+                let ptr = components::param_ptr(cx, |p| &p.sheen_presence_db);
                 ParamSlider::new(cx, Data::params, |p| &p.sheen_presence_db)
+                    .param_entry(ptr)
+                    .param_access(ptr)
                     .class("sheen-slider")
                     .height(Pixels(22.0))
                     .width(Stretch(1.0));
+                let ptr = components::param_ptr(cx, |p| &p.sheen_presence_bypass);
                 ParamButton::new(cx, Data::params, |p| &p.sheen_presence_bypass)
+                    .param_access(ptr)
                     .class("sheen-stage-bypass")
                     .height(Pixels(24.0))
                     .width(Stretch(1.0));
             }
             "AIR" => {
+                let ptr = components::param_ptr(cx, |p| &p.sheen_air_db);
                 ParamSlider::new(cx, Data::params, |p| &p.sheen_air_db)
+                    .param_entry(ptr)
+                    .param_access(ptr)
                     .class("sheen-slider")
                     .height(Pixels(22.0))
                     .width(Stretch(1.0));
+                let ptr = components::param_ptr(cx, |p| &p.sheen_air_bypass);
                 ParamButton::new(cx, Data::params, |p| &p.sheen_air_bypass)
+                    .param_access(ptr)
                     .class("sheen-stage-bypass")
                     .height(Pixels(24.0))
                     .width(Stretch(1.0));
             }
             "WARMTH" => {
+                let ptr = components::param_ptr(cx, |p| &p.sheen_warmth);
                 ParamSlider::new(cx, Data::params, |p| &p.sheen_warmth)
+                    .param_entry(ptr)
+                    .param_access(ptr)
                     .class("sheen-slider")
                     .height(Pixels(22.0))
                     .width(Stretch(1.0));
+                let ptr = components::param_ptr(cx, |p| &p.sheen_warmth_bypass);
                 ParamButton::new(cx, Data::params, |p| &p.sheen_warmth_bypass)
+                    .param_access(ptr)
                     .class("sheen-stage-bypass")
                     .height(Pixels(24.0))
                     .width(Stretch(1.0));
             }
             "WIDTH" => {
+                let ptr = components::param_ptr(cx, |p| &p.sheen_width);
                 ParamSlider::new(cx, Data::params, |p| &p.sheen_width)
+                    .param_entry(ptr)
+                    .param_access(ptr)
                     .class("sheen-slider")
                     .height(Pixels(22.0))
                     .width(Stretch(1.0));
+                let ptr = components::param_ptr(cx, |p| &p.sheen_width_bypass);
                 ParamButton::new(cx, Data::params, |p| &p.sheen_width_bypass)
+                    .param_access(ptr)
                     .class("sheen-stage-bypass")
                     .height(Pixels(24.0))
                     .width(Stretch(1.0));
@@ -2870,20 +6091,19 @@ fn sheen_stage_column(cx: &mut Context, name: &'static str, sub: &'static str, _
 
 fn build_transformer_controls(cx: &mut Context) {
     VStack::new(cx, |cx| {
-        // Model + compression on one row
+        // Model, compression and core age on one row
         components::module_row(cx, |cx| {
             components::create_param_slider(cx, "MODEL", Data::params, |p| &p.transformer_model);
-            components::create_ratio_slider(cx, "COMP", Data::params, |p| {
-                &p.transformer_compression
-            });
+            components::create_ratio_knob(cx, "COMP", Data::params, |p| &p.transformer_compression);
+            components::create_param_knob(cx, "AGE", Data::params, |p| &p.transformer_age);
         });
         // Input stage: drive + saturation paired
         components::module_section(cx, "INPUT", |cx| {
             components::module_row(cx, |cx| {
-                components::create_param_slider(cx, "DRIVE", Data::params, |p| {
+                components::create_param_knob(cx, "DRIVE", Data::params, |p| {
                     &p.transformer_input_drive
                 });
-                components::create_param_slider(cx, "SAT", Data::params, |p| {
+                components::create_param_knob(cx, "SAT", Data::params, |p| {
                     &p.transformer_input_saturation
                 });
             });
@@ -2891,10 +6111,10 @@ fn build_transformer_controls(cx: &mut Context) {
         // Output stage: drive + saturation paired
         components::module_section(cx, "OUTPUT", |cx| {
             components::module_row(cx, |cx| {
-                components::create_param_slider(cx, "DRIVE", Data::params, |p| {
+                components::create_param_knob(cx, "DRIVE", Data::params, |p| {
                     &p.transformer_output_drive
                 });
-                components::create_param_slider(cx, "SAT", Data::params, |p| {
+                components::create_param_knob(cx, "SAT", Data::params, |p| {
                     &p.transformer_output_saturation
                 });
             });
@@ -2902,14 +6122,31 @@ fn build_transformer_controls(cx: &mut Context) {
         // Tone shaping: low/high response
         components::module_section(cx, "TONE", |cx| {
             components::module_row(cx, |cx| {
-                components::create_param_slider(cx, "LOW", Data::params, |p| {
+                components::create_param_knob(cx, "LOW", Data::params, |p| {
                     &p.transformer_low_response
                 });
-                components::create_param_slider(cx, "HIGH", Data::params, |p| {
+                components::create_param_knob(cx, "HIGH", Data::params, |p| {
                     &p.transformer_high_response
                 });
             });
         });
+        // Drive feedback: how hard both saturation stages are working
+        let sat = Data::transformer_sat.get(cx);
+        components::create_drive_meter(cx, "DRIVE", sat);
+        // Character: what a 1 kHz probe comes out with at these settings
+        build_harmonic_readout(cx);
+        build_advanced_drawer(cx, ModuleType::Transformer, |cx| {
+            components::module_row(cx, |cx| {
+                components::create_bool_button(cx, "VARIANCE", Data::params, |p| {
+                    &p.transformer_variance
+                });
+                components::create_bool_button(cx, "ADAA", Data::params, |p| &p.transformer_adaa);
+                // Mid/side saturation: the distortion stays in the middle.
+                components::create_bool_button(cx, "MONO SAFE", Data::params, |p| {
+                    &p.transformer_mono_safe
+                });
+            });
+        });
     })
     .gap(Pixels(4.0))
     .height(Auto)
@@ -2918,39 +6155,74 @@ fn build_transformer_controls(cx: &mut Context) {
     .bottom(Pixels(0.0));
 }
 
+/// THD and even-harmonic share of the transformer's probe (harmonics.rs).
+fn build_harmonic_readout(cx: &mut Context) {
+    components::module_row(cx, |cx| {
+        components::create_readout(cx, "THD", Data::transformer_thd.map(|thd| format_thd(*thd)));
+        components::create_readout(
+            cx,
+            "EVEN",
+            Data::transformer_even_share.map(|share| format!("{:.0}%", share * 100.0)),
+        );
+    });
+}
+
+/// THD as a percentage, with more places while it's small.
+fn format_thd(thd: f32) -> String {
+    let percent = thd * 100.0;
+    if percent < 1.0 {
+        format!("{percent:.2}%")
+    } else {
+        format!("{percent:.1}%")
+    }
+}
+
 fn build_punch_controls(cx: &mut Context) {
     #[cfg(feature = "punch")]
     VStack::new(cx, |cx| {
         components::module_section(cx, "CLIPPER", |cx| {
             components::module_row(cx, |cx| {
-                components::create_gain_slider(cx, "THRESH", Data::params, |p| &p.punch_threshold);
+                components::create_gain_knob(cx, "THRESH", Data::params, |p| &p.punch_threshold);
                 components::create_param_slider(cx, "MODE", Data::params, |p| &p.punch_clip_mode);
             });
-            components::module_row(cx, |cx| {
-                components::create_param_slider(cx, "SOFT", Data::params, |p| &p.punch_softness);
-                components::create_param_slider(cx, "OVSMP", Data::params, |p| {
-                    &p.punch_oversampling
-                });
-            });
+            components::create_param_knob(cx, "SOFT", Data::params, |p| &p.punch_softness);
         });
         components::module_section(cx, "TRANSIENTS", |cx| {
             components::module_row(cx, |cx| {
-                components::create_param_slider(cx, "ATTACK", Data::params, |p| &p.punch_attack);
-                components::create_param_slider(cx, "SUSTAIN", Data::params, |p| &p.punch_sustain);
+                components::create_param_knob(cx, "ATTACK", Data::params, |p| &p.punch_attack);
+                components::create_param_knob(cx, "SUSTAIN", Data::params, |p| &p.punch_sustain);
+            });
+            // LINK: compressor gain reduction adds attack (see punch_link.rs).
+            components::module_row(cx, |cx| {
+                components::create_param_knob(cx, "SENS", Data::params, |p| &p.punch_sensitivity);
+                components::create_param_knob(cx, "LINK", Data::params, |p| &p.punch_link);
             });
-            components::create_param_slider(cx, "SENS", Data::params, |p| &p.punch_sensitivity);
         });
         components::module_section(cx, "OUTPUT", |cx| {
             components::module_row(cx, |cx| {
-                components::create_gain_slider(cx, "IN", Data::params, |p| &p.punch_input_gain);
-                components::create_gain_slider(cx, "OUT", Data::params, |p| &p.punch_output_gain);
+                components::create_gain_knob(cx, "IN", Data::params, |p| &p.punch_input_gain);
+                components::create_gain_knob(cx, "OUT", Data::params, |p| &p.punch_output_gain);
             });
+            components::create_param_knob(cx, "MIX", Data::params, |p| &p.punch_mix);
+        });
+        build_advanced_drawer(cx, ModuleType::Punch, |cx| {
             components::module_row(cx, |cx| {
-                components::create_param_slider(cx, "MIX", Data::params, |p| &p.punch_mix);
-                components::create_frequency_slider(cx, "WET HPF", Data::params, |p| {
+                components::create_param_slider(cx, "OVSMP", Data::params, |p| {
+                    &p.punch_oversampling
+                });
+                components::create_frequency_knob(cx, "WET HPF", Data::params, |p| {
                     &p.punch_wet_hpf_hz
                 });
             });
+            // Clipper tilt: lows down into the clipper, back up after it.
+            components::module_row(cx, |cx| {
+                components::create_gain_knob(cx, "TILT", Data::params, |p| &p.punch_tilt);
+                components::create_frequency_knob(cx, "PIVOT", Data::params, |p| {
+                    &p.punch_tilt_pivot
+                });
+            });
+            // Transient detector smoothing, in ms at the native rate.
+            components::create_param_knob(cx, "SMOOTH", Data::params, |p| &p.punch_smoothing);
         });
     })
     .gap(Pixels(4.0))
@@ -2960,24 +6232,37 @@ fn build_punch_controls(cx: &mut Context) {
     .bottom(Pixels(0.0));
 }
 
+// No ADVANCED drawer: every Haas control is on the faceplate already.
 fn build_haas_controls(cx: &mut Context) {
     #[cfg(feature = "haas")]
     VStack::new(cx, |cx| {
         components::module_section(cx, "M/S GAIN", |cx| {
             components::module_row(cx, |cx| {
-                components::create_gain_slider(cx, "MID", Data::params, |p| &p.haas_mid_gain);
-                components::create_gain_slider(cx, "SIDE", Data::params, |p| &p.haas_side_gain);
+                components::create_linked_gain_knob(
+                    cx,
+                    "MID",
+                    Data::params,
+                    |p| &p.haas_mid_gain,
+                    |p| &p.haas_side_gain,
+                );
+                components::create_linked_gain_knob(
+                    cx,
+                    "SIDE",
+                    Data::params,
+                    |p| &p.haas_side_gain,
+                    |p| &p.haas_mid_gain,
+                );
             });
         });
         components::module_section(cx, "COMB", |cx| {
             components::module_row(cx, |cx| {
-                components::create_param_slider(cx, "DEPTH", Data::params, |p| &p.haas_comb_depth);
-                components::create_param_slider(cx, "TIME", Data::params, |p| &p.haas_comb_time);
+                components::create_param_knob(cx, "DEPTH", Data::params, |p| &p.haas_comb_depth);
+                components::create_param_knob(cx, "TIME", Data::params, |p| &p.haas_comb_time);
             });
             components::create_param_slider(cx, "MODE", Data::params, |p| &p.haas_comb_mode);
         });
         components::module_section(cx, "OUTPUT", |cx| {
-            components::create_param_slider(cx, "MIX", Data::params, |p| &p.haas_mix);
+            components::create_param_knob(cx, "MIX", Data::params, |p| &p.haas_mix);
         });
     })
     .gap(Pixels(4.0))
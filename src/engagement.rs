@@ -0,0 +1,74 @@
+// src/engagement.rs
+// How often a nonlinear stage engages.
+//
+// The drive and clip meters show how HARD a stage is working; this counts
+// how OFTEN it works at all: the share of samples where the curve bent the
+// signal by more than `ENGAGE_DEVIATION` of its own level. A tube stage
+// that bends 2% of the samples is shaving peaks; one that bends 60% is
+// coloring everything, and the level going in is the thing to change.
+//
+// Each nonlinear module (Pultec tube, transformer, Punch clipper) tallies
+// one count per native sample per channel — a sample counts once however
+// many oversampled frames it spans — and the plugin drains the tally after
+// every block and smooths it into the percentage the meter bridge shows.
+
+/// Deviation from the linear path, relative to the sample, above which a
+/// stage counts as engaged: harmonics about 40 dB down.
+pub const ENGAGE_DEVIATION: f32 = 0.01;
+
+/// Engaged and total sample counts since the last `take`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Engagement {
+    pub engaged: u32,
+    pub total: u32,
+}
+
+impl Engagement {
+    /// Count one sample.
+    #[inline]
+    pub fn tally(&mut self, engaged: bool) {
+        self.total = self.total.saturating_add(1);
+        self.engaged = self.engaged.saturating_add(engaged as u32);
+    }
+
+    /// True when `output` strays more than `ENGAGE_DEVIATION` from `input`.
+    #[inline]
+    pub fn bent(input: f32, output: f32) -> bool {
+        (output - input).abs() > ENGAGE_DEVIATION * input.abs()
+    }
+
+    /// Return the counts so far and start over.
+    pub fn take(&mut self) -> Self {
+        std::mem::take(self)
+    }
+
+    /// Engaged share, 0..=1. `None` before any sample was counted.
+    pub fn fraction(self) -> Option<f32> {
+        (self.total > 0).then(|| self.engaged as f32 / self.total as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fraction_counts_engaged_samples() {
+        let mut e = Engagement::default();
+        assert_eq!(e.fraction(), None);
+        for i in 0..8 {
+            e.tally(i % 4 == 0);
+        }
+        assert_eq!(e.fraction(), Some(0.25));
+        assert_eq!(e.take().engaged, 2);
+        assert_eq!(e, Engagement::default());
+    }
+
+    #[test]
+    fn small_deviations_do_not_count() {
+        assert!(!Engagement::bent(0.5, 0.5));
+        assert!(!Engagement::bent(0.5, 0.499));
+        assert!(Engagement::bent(0.5, 0.45));
+        assert!(!Engagement::bent(0.0, 0.0));
+    }
+}
@@ -0,0 +1,482 @@
+// src/envelope.rs
+// Envelope followers shared by every dynamics stage.
+//
+// One configurable follower instead of a hand-rolled one per module:
+//
+//   • detection: peak (|x|) or RMS over a sliding window
+//   • one-pole attack/release ballistics, rising = attack
+//   • release: fixed, program-dependent (slower the deeper the recent
+//     level, like the 1176's auto release) or dual (a fixed share of a
+//     fast release blended into the slow one, the All-Buttons sound)
+//
+// Compressors that smooth gain reduction rather than level feed the
+// follower the *depth* (dB of reduction, or 1 − gain), so more reduction
+// still means "rising" and uses the attack time.
+//
+// `process()` takes a raw sample and runs the detector; `follow()` takes a
+// level that was already detected. State below ~1e-20 flushes to zero so a
+// decaying envelope never goes subnormal. The Optical model keeps its own
+// dual-integrator opto cell, which isn't an attack/release follower.
+//
+// `TransientDetector` pairs two followers, fast against slow, to say how
+// sharply the level is rising. Punch and the compressor's transient protect
+// share it, so "a transient" means the same thing to both.
+//
+// Every one-pole time constant in the strip goes through `coeff_for_ms` or
+// `coeff_for_block`, always against the rate the state actually steps at:
+// the host rate for per-sample state, the block rate for state updated
+// once per buffer. A time set in ms then means the same at 44.1 and
+// 96 kHz, at any host buffer size and under the fixed block scheduler.
+
+/// State below this flushes to zero.
+const DENORMAL_FLUSH: f32 = 1.0e-20;
+/// The program-dependent release's secondary follower, which measures how
+/// deep the recent program has been.
+const PROGRAM_FAST_ATTACK_MS: f32 = 5.0;
+const PROGRAM_FAST_RELEASE_MS: f32 = 50.0;
+
+/// One-pole coefficient for a time constant in ms: the state moves 1 − 1/e
+/// of the way to its target in `time_ms`. Zero or negative times are
+/// instant (coefficient 0).
+pub fn coeff_for_ms(time_ms: f32, sample_rate: f32) -> f32 {
+    coeff_for_block(time_ms, 1, sample_rate)
+}
+
+/// `coeff_for_ms` for state that steps once every `samples` samples — a
+/// meter or smoother updated per buffer. One step of the result equals
+/// `samples` steps of the per-sample coefficient, so the time constant
+/// holds whatever the buffer length.
+pub fn coeff_for_block(time_ms: f32, samples: usize, sample_rate: f32) -> f32 {
+    if time_ms <= 0.0 {
+        0.0
+    } else {
+        (-(samples as f32) / (time_ms * 0.001 * sample_rate)).exp()
+    }
+}
+
+/// What the follower measures.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Detector {
+    /// Absolute sample value.
+    Peak,
+    /// Root of the mean square over a one-pole window.
+    Rms { window_ms: f32 },
+}
+
+/// How the follower falls.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Release {
+    /// The release time as set.
+    Fixed,
+    /// Release time slides from `min_ms` to `max_ms` as a fast secondary
+    /// follower of the level approaches `full_scale`.
+    ProgramDependent {
+        min_ms: f32,
+        max_ms: f32,
+        full_scale: f32,
+    },
+    /// Blend `fast_share` of a `fast_ms` release into the set release.
+    Dual { fast_ms: f32, fast_share: f32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct EnvelopeFollower {
+    sample_rate: f32,
+    detector: Detector,
+    release: Release,
+    attack_ms: f32,
+    release_ms: f32,
+    rms_coeff: f32,
+    attack_coeff: f32,
+    release_coeff: f32,
+    /// Program-dependent: the secondary follower's ballistics. Dual: the
+    /// fast release (attack unused).
+    fast_attack_coeff: f32,
+    fast_release_coeff: f32,
+    mean_square: f32,
+    /// Program-dependent: recent level. Dual: the fast release arm.
+    fast: f32,
+    envelope: f32,
+}
+
+impl EnvelopeFollower {
+    /// Peak detector with a fixed release.
+    pub fn new(sample_rate: f32, attack_ms: f32, release_ms: f32) -> Self {
+        let mut env = Self {
+            sample_rate,
+            detector: Detector::Peak,
+            release: Release::Fixed,
+            attack_ms,
+            release_ms,
+            rms_coeff: 0.0,
+            attack_coeff: 0.0,
+            release_coeff: 0.0,
+            fast_attack_coeff: 0.0,
+            fast_release_coeff: 0.0,
+            mean_square: 0.0,
+            fast: 0.0,
+            envelope: 0.0,
+        };
+        env.recompute();
+        env
+    }
+
+    pub fn with_detector(mut self, detector: Detector) -> Self {
+        self.set_detector(detector);
+        self
+    }
+
+    pub fn with_release(mut self, release: Release) -> Self {
+        self.set_release(release);
+        self
+    }
+
+    /// Change attack and release; state carries over.
+    pub fn set_times(&mut self, attack_ms: f32, release_ms: f32) {
+        self.attack_ms = attack_ms;
+        self.release_ms = release_ms;
+        self.attack_coeff = coeff_for_ms(attack_ms, self.sample_rate);
+        self.release_coeff = coeff_for_ms(release_ms, self.sample_rate);
+    }
+
+    pub fn set_detector(&mut self, detector: Detector) {
+        if detector != self.detector {
+            self.detector = detector;
+            self.recompute();
+        }
+    }
+
+    /// Switch release modes; the envelope carries over.
+    pub fn set_release(&mut self, release: Release) {
+        if release != self.release {
+            self.release = release;
+            self.fast = 0.0;
+            self.recompute();
+        }
+    }
+
+    fn recompute(&mut self) {
+        self.set_times(self.attack_ms, self.release_ms);
+        self.rms_coeff = match self.detector {
+            Detector::Rms { window_ms } => coeff_for_ms(window_ms, self.sample_rate),
+            Detector::Peak => 0.0,
+        };
+        let (fast_attack_ms, fast_release_ms) = match self.release {
+            Release::Dual { fast_ms, .. } => (0.0, fast_ms),
+            _ => (PROGRAM_FAST_ATTACK_MS, PROGRAM_FAST_RELEASE_MS),
+        };
+        self.fast_attack_coeff = coeff_for_ms(fast_attack_ms, self.sample_rate);
+        self.fast_release_coeff = coeff_for_ms(fast_release_ms, self.sample_rate);
+    }
+
+    pub fn reset(&mut self) {
+        self.mean_square = 0.0;
+        self.fast = 0.0;
+        self.envelope = 0.0;
+    }
+
+    /// Current envelope.
+    #[inline]
+    pub fn value(&self) -> f32 {
+        self.envelope
+    }
+
+    /// Detect `input` and advance the envelope one sample.
+    #[inline]
+    pub fn process(&mut self, input: f32) -> f32 {
+        let level = match self.detector {
+            Detector::Peak => input.abs(),
+            Detector::Rms { .. } => {
+                let sq = input * input;
+                self.mean_square = flush(sq + (self.mean_square - sq) * self.rms_coeff);
+                self.mean_square.sqrt()
+            }
+        };
+        self.follow(level)
+    }
+
+    /// Advance the envelope one sample toward an already-detected `level`.
+    #[inline]
+    pub fn follow(&mut self, level: f32) -> f32 {
+        let rising = level > self.envelope;
+        self.envelope = match self.release {
+            _ if rising => smooth(self.envelope, level, self.attack_coeff),
+            Release::Fixed => smooth(self.envelope, level, self.release_coeff),
+            Release::ProgramDependent {
+                min_ms,
+                max_ms,
+                full_scale,
+            } => {
+                let depth = (self.fast / full_scale).clamp(0.0, 1.0);
+                let release_ms = min_ms + depth * (max_ms - min_ms);
+                smooth(
+                    self.envelope,
+                    level,
+                    coeff_for_ms(release_ms, self.sample_rate),
+                )
+            }
+            Release::Dual { fast_share, .. } => {
+                self.fast = flush(smooth(self.fast, level, self.fast_release_coeff));
+                (1.0 - fast_share) * smooth(self.envelope, level, self.release_coeff)
+                    + fast_share * self.fast
+            }
+        };
+        match self.release {
+            Release::ProgramDependent { .. } => {
+                let coeff = if level > self.fast {
+                    self.fast_attack_coeff
+                } else {
+                    self.fast_release_coeff
+                };
+                self.fast = flush(smooth(self.fast, level, coeff));
+            }
+            // The fast arm rides along on the way up, so the release starts
+            // from where the envelope is rather than dropping toward zero.
+            Release::Dual { .. } if rising => self.fast = self.envelope,
+            _ => {}
+        }
+        self.envelope = flush(self.envelope);
+        self.envelope
+    }
+
+    #[cfg(test)]
+    pub fn attack_coeff(&self) -> f32 {
+        self.attack_coeff
+    }
+}
+
+/// Differential envelope transient detector: a fast follower racing a slow
+/// one. Punch shapes with it; the bus compressor's transient protect ducks
+/// its key with it.
+pub struct TransientDetector {
+    fast_envelope: EnvelopeFollower,
+    slow_envelope: EnvelopeFollower,
+    sensitivity: f32,
+    smoothed_transient: f32,
+    smoothing_coeff: f32,
+}
+
+impl TransientDetector {
+    pub fn new(sample_rate: f32) -> Self {
+        // Fast envelope: 0.5ms attack, 5ms release (captures transient onset)
+        let fast_envelope = EnvelopeFollower::new(sample_rate, 0.5, 5.0);
+        // Slow envelope: 20ms attack, 100ms release (captures body/sustain)
+        let slow_envelope = EnvelopeFollower::new(sample_rate, 20.0, 100.0);
+
+        Self {
+            fast_envelope,
+            slow_envelope,
+            sensitivity: 0.5,
+            smoothed_transient: 0.0,
+            smoothing_coeff: coeff_for_ms(2.0, sample_rate),
+        }
+    }
+
+    pub fn update_parameters(
+        &mut self,
+        attack_time_ms: f32,
+        release_time_ms: f32,
+        sensitivity: f32,
+    ) {
+        // Fast envelope tracks transients
+        self.fast_envelope
+            .set_times(attack_time_ms * 0.1, attack_time_ms);
+        // Slow envelope tracks body
+        self.slow_envelope
+            .set_times(release_time_ms * 0.2, release_time_ms);
+        self.sensitivity = sensitivity;
+    }
+
+    /// Anti-click smoothing of the output, as a time constant at the rate
+    /// `process` runs at (2 ms from `new`). 0 ms is unsmoothed.
+    pub fn set_smoothing(&mut self, smoothing_ms: f32, sample_rate: f32) {
+        self.smoothing_coeff = coeff_for_ms(smoothing_ms, sample_rate);
+    }
+
+    /// Process a sample and return transient amount (0.0 to 1.0+)
+    #[inline]
+    pub fn process(&mut self, input: f32) -> f32 {
+        let fast_env = self.fast_envelope.process(input);
+        let slow_env = self.slow_envelope.process(input);
+
+        // Differential: transient = how much faster is rising than slow
+        let raw_transient = if slow_env > 0.0001 {
+            ((fast_env - slow_env) / slow_env).max(0.0)
+        } else {
+            0.0
+        };
+
+        // Apply sensitivity scaling
+        let scaled_transient = raw_transient * self.sensitivity * 4.0;
+
+        // Smooth to prevent clicks
+        self.smoothed_transient = self.smoothing_coeff * self.smoothed_transient
+            + (1.0 - self.smoothing_coeff) * scaled_transient;
+
+        self.smoothed_transient
+    }
+
+    pub fn reset(&mut self) {
+        self.fast_envelope.reset();
+        self.slow_envelope.reset();
+        self.smoothed_transient = 0.0;
+    }
+}
+
+#[inline(always)]
+fn smooth(state: f32, target: f32, coeff: f32) -> f32 {
+    target + (state - target) * coeff
+}
+
+#[inline(always)]
+fn flush(x: f32) -> f32 {
+    if x.abs() < DENORMAL_FLUSH {
+        0.0
+    } else {
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    fn sine(amp: f32, n: usize) -> impl Iterator<Item = f32> {
+        (0..n).map(move |i| amp * (std::f32::consts::TAU * 1000.0 * i as f32 / SR).sin())
+    }
+
+    #[test]
+    fn attack_and_release_reach_1_over_e_in_their_time() {
+        let mut env = EnvelopeFollower::new(SR, 5.0, 50.0);
+        for _ in 0..(0.005 * SR) as usize {
+            env.follow(1.0);
+        }
+        assert!((env.value() - (1.0 - (-1.0_f32).exp())).abs() < 1e-3);
+
+        let start = env.value();
+        for _ in 0..(0.05 * SR) as usize {
+            env.follow(0.0);
+        }
+        assert!((env.value() / start - (-1.0_f32).exp()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn block_coeff_matches_per_sample_steps() {
+        for samples in [1, 32, 480, 512] {
+            let per_sample = coeff_for_ms(20.0, SR).powi(samples as i32);
+            let per_block = coeff_for_block(20.0, samples, SR);
+            assert!(
+                (per_sample - per_block).abs() < 1e-4,
+                "{samples}: {per_sample} vs {per_block}"
+            );
+        }
+        assert_eq!(coeff_for_block(0.0, 512, SR), 0.0);
+    }
+
+    #[test]
+    fn time_constants_hold_across_sample_rates() {
+        // 50 ms of samples lands on 1/e at any rate.
+        for sr in [44_100.0_f32, 48_000.0, 96_000.0, 192_000.0] {
+            let mut env = EnvelopeFollower::new(sr, 0.0, 50.0);
+            env.follow(1.0);
+            for _ in 0..(0.05 * sr).round() as usize {
+                env.follow(0.0);
+            }
+            assert!(
+                (env.value() - (-1.0_f32).exp()).abs() < 1e-3,
+                "{} at {sr}",
+                env.value()
+            );
+        }
+    }
+
+    #[test]
+    fn zero_times_are_instant() {
+        let mut env = EnvelopeFollower::new(SR, 0.0, 0.0);
+        assert_eq!(env.process(-0.7), 0.7);
+        assert_eq!(env.process(0.2), 0.2);
+    }
+
+    #[test]
+    fn peak_and_rms_detectors_settle_where_expected() {
+        let mut peak = EnvelopeFollower::new(SR, 0.0, 200.0);
+        let mut rms =
+            EnvelopeFollower::new(SR, 0.1, 50.0).with_detector(Detector::Rms { window_ms: 10.0 });
+        for x in sine(0.5, SR as usize) {
+            peak.process(x);
+            rms.process(x);
+        }
+        assert!((peak.value() - 0.5).abs() < 0.01, "peak {}", peak.value());
+        let target = 0.5 / std::f32::consts::SQRT_2;
+        assert!(
+            (rms.value() - target).abs() / target < 0.05,
+            "rms {}",
+            rms.value()
+        );
+    }
+
+    #[test]
+    fn flush_zeros_only_subthreshold_values() {
+        assert_eq!(flush(1e-25), 0.0);
+        assert_eq!(flush(-1e-25), 0.0);
+        assert_eq!(flush(1.0e-15), 1.0e-15);
+        assert_eq!(flush(-0.5), -0.5);
+    }
+
+    #[test]
+    fn silence_flushes_to_exact_zero() {
+        let mut env =
+            EnvelopeFollower::new(SR, 1.0, 10.0).with_detector(Detector::Rms { window_ms: 10.0 });
+        for x in sine(1.0, 4800) {
+            env.process(x);
+        }
+        for _ in 0..500_000 {
+            env.process(0.0);
+        }
+        assert_eq!(env.value(), 0.0);
+    }
+
+    #[test]
+    fn program_dependent_release_slows_after_deep_program() {
+        let release = Release::ProgramDependent {
+            min_ms: 40.0,
+            max_ms: 1100.0,
+            full_scale: 20.0,
+        };
+        // Time for the envelope to fall from `level` to a tenth of it.
+        let fall_time = |level: f32| {
+            let mut env = EnvelopeFollower::new(SR, 0.1, 40.0).with_release(release);
+            for _ in 0..SR as usize {
+                env.follow(level);
+            }
+            (0..).take_while(|_| env.follow(0.0) > level * 0.1).count()
+        };
+        let shallow = fall_time(1.0);
+        let deep = fall_time(18.0);
+        assert!(
+            2 * deep > 3 * shallow,
+            "deep {deep} vs shallow {shallow} samples"
+        );
+    }
+
+    #[test]
+    fn dual_release_blends_in_the_fast_arm() {
+        let fall = |release: Release| {
+            let mut env = EnvelopeFollower::new(SR, 0.0, 500.0).with_release(release);
+            env.follow(1.0);
+            for _ in 0..(0.05 * SR) as usize {
+                env.follow(0.0);
+            }
+            env.value()
+        };
+        let fixed = fall(Release::Fixed);
+        let dual = fall(Release::Dual {
+            fast_ms: 50.0,
+            fast_share: 0.3,
+        });
+        assert!(dual < fixed, "dual {dual} should fall faster than {fixed}");
+        assert!(dual > 0.0);
+    }
+}
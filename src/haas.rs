@@ -18,6 +18,7 @@
 //! No EQ, no saturation, no bass enhancement. Those belong to API5500,
 //! Pultec, and Transformer respectively. Haas is a clean spatial tool.
 
+use crate::envelope::coeff_for_ms;
 use nih_plug::buffer::Buffer;
 use nih_plug::prelude::Enum;
 
@@ -347,7 +348,7 @@ fn delay_smooth_coeff(sample_rate: f32) -> f32 {
     if sample_rate <= 0.0 {
         return 1.0;
     }
-    1.0 - (-1.0 / (DELAY_SMOOTH_TAU_S * sample_rate)).exp()
+    1.0 - coeff_for_ms(DELAY_SMOOTH_TAU_S * 1000.0, sample_rate)
 }
 
 /// Clamp a raw delay-in-samples value to a safe Hermite-compatible range.
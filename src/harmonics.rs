@@ -0,0 +1,197 @@
+// src/harmonics.rs
+// Harmonic readout for the transformer module.
+//
+// The drive meter says how hard the transformer works; this says what it
+// sounds like doing it. A copy of the module, built from the current
+// settings, is fed a 1 kHz sine at a hot bus level on the background
+// thread, and the harmonics that come out are summed into two numbers:
+// THD, and how much of the harmonic energy sits in the even orders. The
+// four models differ mostly in that balance — the asymmetric Modern curve
+// throws 2nd and 4th, the others are symmetric and throw odd orders only.
+//
+// The probe runs on a private instance, so the audio path never sees it.
+// The audio thread only notices that the settings changed since the last
+// probe and queues `BackgroundTask::ProbeTransformer`; the worker reads the
+// params itself, probes, and publishes through `spectral::HarmonicData`.
+
+use crate::transformer::{TransformerModel, TransformerModule};
+use std::f64::consts::TAU;
+
+/// Rate the probe runs at, whatever the host's. A whole number of probe
+/// periods fits every window, so each harmonic lands exactly on a bin.
+const PROBE_RATE: f32 = 48_000.0;
+const PROBE_HZ: f32 = 1_000.0;
+/// Probe amplitude: −6 dBFS, a hot bus.
+const PROBE_LEVEL: f32 = 0.5;
+/// Samples run before measuring, so the shelves and the loading
+/// compression settle (100 ms).
+const SETTLE: usize = 4_800;
+/// Measured samples: 100 periods of the probe.
+const WINDOW: usize = 4_800;
+const WINDOW_PERIODS: usize = 100;
+/// Highest harmonic measured. 7 kHz stays clear of the 24 kHz Nyquist.
+const HIGHEST_HARMONIC: usize = 7;
+
+/// Transformer settings the probe copies; the ones that shape the curve.
+/// Variance is left out: it drifts by design, and the readout describes
+/// the model, not this unit's tolerances.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProbeSettings {
+    pub model: TransformerModel,
+    pub input_drive: f32,
+    pub input_saturation: f32,
+    pub output_drive: f32,
+    pub output_saturation: f32,
+    pub low_response: f32,
+    pub high_response: f32,
+    pub compression: f32,
+    pub age: f32,
+    pub adaa: bool,
+}
+
+/// What the probe measured.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HarmonicProfile {
+    /// RMS of harmonics 2..=7 over the fundamental, 0..
+    pub thd: f32,
+    /// Share of that harmonic energy in the even orders, 0..=1. Zero when
+    /// there are no harmonics to speak of.
+    pub even_share: f32,
+}
+
+/// Run the probe through a fresh module with `settings`. Allocates; call
+/// on the background thread.
+pub fn probe(settings: &ProbeSettings) -> HarmonicProfile {
+    let mut module = TransformerModule::new(PROBE_RATE);
+    module.set_adaa(settings.adaa);
+    module.set_age(settings.age);
+    module.update_parameters(
+        settings.model,
+        settings.input_drive,
+        settings.input_saturation,
+        settings.output_drive,
+        settings.output_saturation,
+        settings.low_response,
+        settings.high_response,
+        settings.compression,
+    );
+
+    let step = TAU * PROBE_HZ as f64 / PROBE_RATE as f64;
+    let mut signal: Vec<f32> = (0..SETTLE + WINDOW)
+        .map(|i| PROBE_LEVEL * (step * i as f64).sin() as f32)
+        .collect();
+    module.process_channels(&mut [&mut signal[..]]);
+    measure(&signal[SETTLE..])
+}
+
+/// THD and even share of a window holding exactly `WINDOW_PERIODS`
+/// periods of the probe.
+fn measure(window: &[f32]) -> HarmonicProfile {
+    let fundamental = bin_amplitude(window, WINDOW_PERIODS);
+    if fundamental < 1e-9 {
+        return HarmonicProfile::default();
+    }
+    let (mut even, mut odd) = (0.0, 0.0);
+    for order in 2..=HIGHEST_HARMONIC {
+        let power = bin_amplitude(window, order * WINDOW_PERIODS).powi(2);
+        if order % 2 == 0 {
+            even += power;
+        } else {
+            odd += power;
+        }
+    }
+    let harmonic = even + odd;
+    let thd = harmonic.sqrt() / fundamental;
+    // Below −100 dB the balance is rounding noise.
+    let even_share = if thd > 1e-5 { even / harmonic } else { 0.0 };
+    HarmonicProfile {
+        thd: thd as f32,
+        even_share: even_share as f32,
+    }
+}
+
+/// Peak amplitude of the component completing `cycles` periods over the
+/// window: a single DFT bin, exact for a periodic steady state.
+fn bin_amplitude(window: &[f32], cycles: usize) -> f64 {
+    let step = TAU * cycles as f64 / window.len() as f64;
+    let (re, im) = window
+        .iter()
+        .enumerate()
+        .fold((0.0, 0.0), |(re, im), (i, &x)| {
+            let phase = step * i as f64;
+            (re + x as f64 * phase.cos(), im - x as f64 * phase.sin())
+        });
+    2.0 * re.hypot(im) / window.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(model: TransformerModel, saturation: f32) -> ProbeSettings {
+        ProbeSettings {
+            model,
+            input_drive: 0.5,
+            input_saturation: saturation,
+            output_drive: 0.5,
+            output_saturation: saturation,
+            low_response: 0.0,
+            high_response: 0.0,
+            compression: 0.0,
+            age: 0.0,
+            adaa: false,
+        }
+    }
+
+    #[test]
+    fn test_measure_reads_a_known_mix() {
+        let step = TAU / (WINDOW / WINDOW_PERIODS) as f64;
+        let window: Vec<f32> = (0..WINDOW)
+            .map(|i| {
+                let t = step * i as f64;
+                (t.sin() + 0.03 * (2.0 * t).sin() + 0.04 * (3.0 * t).sin()) as f32
+            })
+            .collect();
+        let profile = measure(&window);
+        assert!((profile.thd - 0.05).abs() < 1e-4, "thd {}", profile.thd);
+        assert!((profile.even_share - 0.36).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_clean_settings_measure_no_distortion() {
+        let profile = probe(&settings(TransformerModel::Vintage, 0.0));
+        assert!(profile.thd < 1e-4, "thd {}", profile.thd);
+        assert_eq!(profile.even_share, 0.0);
+    }
+
+    #[test]
+    fn test_saturation_raises_thd() {
+        let light = probe(&settings(TransformerModel::British, 0.2));
+        let heavy = probe(&settings(TransformerModel::British, 1.0));
+        assert!(light.thd > 1e-4, "light thd {}", light.thd);
+        assert!(
+            heavy.thd > light.thd * 2.0,
+            "{} vs {}",
+            heavy.thd,
+            light.thd
+        );
+    }
+
+    #[test]
+    fn test_only_the_asymmetric_model_throws_even_harmonics() {
+        let modern = probe(&settings(TransformerModel::Modern, 1.0));
+        assert!(modern.even_share > 0.01, "modern {}", modern.even_share);
+        for model in [
+            TransformerModel::Vintage,
+            TransformerModel::British,
+            TransformerModel::American,
+        ] {
+            let profile = probe(&settings(model, 1.0));
+            assert!(
+                profile.even_share < 0.01,
+                "{model:?} even share {}",
+                profile.even_share
+            );
+        }
+    }
+}
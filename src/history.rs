@@ -0,0 +1,220 @@
+// src/history.rs
+// Editor-side undo/redo for parameter gestures.
+//
+// Many hosts either ignore plugin-GUI edits in their undo stack or lump them
+// together with unrelated track edits, so the editor keeps its own history.
+// It observes the same begin/set/end gesture events every widget already
+// emits and records one step per completed gesture:
+//   • begin — remember the value before the gesture
+//   • set   — track the latest value written during the gesture
+//   • end   — close the change; a no-op gesture (before == after) is dropped
+//
+// Batch actions (preset load, A/B switch, chain load, reorder) wrap their
+// writes in a group so a single undo reverts the whole batch. Undo/redo
+// playback is itself written through gestures, so it runs inside a replay
+// window where recording is suspended.
+//
+// Generic over the parameter key so the bookkeeping can be unit-tested
+// without real params; the editor uses `ParamPtr`.
+
+/// Maximum number of undo steps kept. Oldest steps are dropped first.
+pub const HISTORY_DEPTH: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Change<K> {
+    key: K,
+    before: f32,
+    after: f32,
+}
+
+pub struct UndoHistory<K> {
+    undo: Vec<Vec<Change<K>>>,
+    redo: Vec<Vec<Change<K>>>,
+    /// Gestures that have begun but not yet ended.
+    open: Vec<Change<K>>,
+    /// Changes finished inside the current group, committed on `end_group`.
+    group: Vec<Change<K>>,
+    group_depth: u32,
+    replaying: bool,
+}
+
+impl<K: Copy + PartialEq> Default for UndoHistory<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Copy + PartialEq> UndoHistory<K> {
+    pub fn new() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            open: Vec::new(),
+            group: Vec::new(),
+            group_depth: 0,
+            replaying: false,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn begin(&mut self, key: K, before: f32) {
+        if self.replaying || self.open.iter().any(|c| c.key == key) {
+            return;
+        }
+        self.open.push(Change {
+            key,
+            before,
+            after: before,
+        });
+    }
+
+    pub fn set(&mut self, key: K, value: f32) {
+        if let Some(change) = self.open.iter_mut().find(|c| c.key == key) {
+            change.after = value;
+        }
+    }
+
+    pub fn end(&mut self, key: K) {
+        let Some(idx) = self.open.iter().position(|c| c.key == key) else {
+            return;
+        };
+        let change = self.open.swap_remove(idx);
+        if change.before == change.after {
+            return;
+        }
+        if self.group_depth > 0 {
+            // A param written twice in one batch keeps its first `before`.
+            match self.group.iter_mut().find(|c| c.key == key) {
+                Some(existing) => existing.after = change.after,
+                None => self.group.push(change),
+            }
+        } else {
+            self.push_step(vec![change]);
+        }
+    }
+
+    pub fn begin_group(&mut self) {
+        self.group_depth += 1;
+    }
+
+    pub fn end_group(&mut self) {
+        self.group_depth = self.group_depth.saturating_sub(1);
+        if self.group_depth == 0 && !self.group.is_empty() {
+            let step = std::mem::take(&mut self.group);
+            self.push_step(step);
+        }
+    }
+
+    /// True while a batch write or an undo/redo replay is in progress, i.e.
+    /// when the writes going through aren't individual user gestures.
+    pub fn in_batch(&self) -> bool {
+        self.group_depth > 0 || self.replaying
+    }
+
+    /// Suspend recording while undo/redo values are being written back.
+    pub fn set_replaying(&mut self, replaying: bool) {
+        self.replaying = replaying;
+    }
+
+    /// Pop the latest step; returns the values to write to revert it.
+    pub fn undo(&mut self) -> Option<Vec<(K, f32)>> {
+        let step = self.undo.pop()?;
+        let values = step.iter().rev().map(|c| (c.key, c.before)).collect();
+        self.redo.push(step);
+        Some(values)
+    }
+
+    /// Re-apply the latest undone step; returns the values to write.
+    pub fn redo(&mut self) -> Option<Vec<(K, f32)>> {
+        let step = self.redo.pop()?;
+        let values = step.iter().map(|c| (c.key, c.after)).collect();
+        self.undo.push(step);
+        Some(values)
+    }
+
+    fn push_step(&mut self, step: Vec<Change<K>>) {
+        // A fresh edit invalidates the redo branch, like every editor.
+        self.redo.clear();
+        self.undo.push(step);
+        if self.undo.len() > HISTORY_DEPTH {
+            self.undo.remove(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gesture(h: &mut UndoHistory<u32>, key: u32, before: f32, after: f32) {
+        h.begin(key, before);
+        h.set(key, (before + after) * 0.5);
+        h.set(key, after);
+        h.end(key);
+    }
+
+    #[test]
+    fn gesture_undoes_to_value_before_begin() {
+        let mut h = UndoHistory::new();
+        gesture(&mut h, 1, 0.2, 0.8);
+        assert_eq!(h.undo(), Some(vec![(1, 0.2)]));
+        assert_eq!(h.redo(), Some(vec![(1, 0.8)]));
+    }
+
+    #[test]
+    fn no_op_gesture_is_not_recorded() {
+        let mut h = UndoHistory::new();
+        h.begin(1, 0.5);
+        h.end(1);
+        assert!(!h.can_undo());
+    }
+
+    #[test]
+    fn group_reverts_as_one_step() {
+        let mut h = UndoHistory::new();
+        h.begin_group();
+        assert!(h.in_batch());
+        gesture(&mut h, 1, 0.0, 1.0);
+        gesture(&mut h, 2, 0.3, 0.4);
+        gesture(&mut h, 1, 1.0, 0.6);
+        h.end_group();
+        assert!(!h.in_batch());
+        let values = h.undo().unwrap();
+        assert_eq!(values, vec![(2, 0.3), (1, 0.0)]);
+        assert!(!h.can_undo());
+    }
+
+    #[test]
+    fn replay_is_not_recorded_and_new_edit_clears_redo() {
+        let mut h = UndoHistory::new();
+        gesture(&mut h, 1, 0.0, 1.0);
+        h.undo();
+        h.set_replaying(true);
+        gesture(&mut h, 1, 1.0, 0.0);
+        h.set_replaying(false);
+        assert!(h.can_redo());
+        assert!(!h.can_undo());
+        gesture(&mut h, 2, 0.0, 0.5);
+        assert!(!h.can_redo());
+    }
+
+    #[test]
+    fn depth_is_bounded() {
+        let mut h = UndoHistory::new();
+        for i in 0..(HISTORY_DEPTH as u32 + 10) {
+            gesture(&mut h, i, 0.0, 1.0);
+        }
+        let mut steps = 0;
+        while h.undo().is_some() {
+            steps += 1;
+        }
+        assert_eq!(steps, HISTORY_DEPTH);
+    }
+}
@@ -0,0 +1,201 @@
+// src/host_context.rs
+// First-instantiation preset flavor.
+//
+// A freshly inserted strip starts from the factory preset that suits the
+// bus it sits on — drum bus, vocal bus or mix bus — instead of from Init.
+// The flavor comes from the host's track name when there is one and from
+// the audio IO layout otherwise. nih-plug's CLAP wrapper does not forward
+// the track-info extension yet, so `initialize()` currently passes no name
+// and the layout decides; the name classifier is ready for when it does.
+//
+// The choice is made once, in `initialize()`, and stored as the persisted
+// `ContextPreset` state so a reloaded session is never re-flavored. Presets
+// are written through ParamPtrs on the GUI thread (see presets.rs), so the
+// editor applies the pending flavor the first time it opens — and only if
+// every preset param is still at its default, which keeps sessions saved
+// before this state existed exactly as they were.
+//
+// Users who always want Init opt out with a marker file in the user preset
+// directory; the preset browser's AUTO switch creates and removes it.
+
+use nih_plug::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::presets::{self, PresetError};
+
+/// Marker file in `presets::user_preset_dir()` that disables the heuristic
+/// for every instance on this machine.
+const OPT_OUT_FILE: &str = "no-context-preset";
+
+/// Kind of bus the strip was inserted on.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum BusFlavor {
+    MixBus,
+    DrumBus,
+    VocalBus,
+}
+
+impl BusFlavor {
+    /// Factory preset (see `presets::factory_presets`) a new instance of
+    /// this flavor starts from.
+    pub fn preset_name(self) -> &'static str {
+        match self {
+            BusFlavor::MixBus => "Gentle Glue",
+            BusFlavor::DrumBus => "Drum Smack",
+            BusFlavor::VocalBus => "Vocal Air",
+        }
+    }
+}
+
+/// Persisted progress of the first-instantiation preset.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum ContextPreset {
+    /// Fresh instance, or a session saved before this state existed.
+    #[default]
+    Undecided,
+    /// Flavor picked in `initialize()`, waiting for the editor to apply it.
+    Pending(BusFlavor),
+    /// Applied, skipped or disabled. Never revisited.
+    Settled,
+}
+
+/// Track-name words, matched as prefixes of the name's words so "Drums",
+/// "KICK IN" and "Vox Bus" all classify.
+const DRUM_WORDS: &[&str] = &[
+    "drum", "kit", "kick", "snare", "tom", "hat", "overhead", "cymbal", "perc",
+];
+const VOCAL_WORDS: &[&str] = &["vox", "voc", "voice", "bgv", "choir"];
+
+/// Flavor named by a track, if any. Drum words win over vocal words, so
+/// "Drum Vox" (a talkback mic on the kit) stays a drum bus.
+pub fn flavor_from_track_name(name: &str) -> Option<BusFlavor> {
+    let words: Vec<String> = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let has_any = |list: &[&str]| {
+        words
+            .iter()
+            .any(|w| list.iter().any(|prefix| w.starts_with(prefix)))
+    };
+    if has_any(DRUM_WORDS) {
+        Some(BusFlavor::DrumBus)
+    } else if has_any(VOCAL_WORDS) {
+        Some(BusFlavor::VocalBus)
+    } else {
+        None
+    }
+}
+
+/// Pick the flavor for a new instance. The track name decides when it
+/// names a drum or vocal bus; otherwise a mono main input reads as a
+/// single vocal and anything else as a mix bus.
+pub fn pick_flavor(track_name: Option<&str>, layout: &AudioIOLayout) -> BusFlavor {
+    if let Some(flavor) = track_name.and_then(flavor_from_track_name) {
+        return flavor;
+    }
+    match layout.main_input_channels.map(|c| c.get()) {
+        Some(1) => BusFlavor::VocalBus,
+        _ => BusFlavor::MixBus,
+    }
+}
+
+/// True when every preset param still sits at its default — a strip
+/// nobody has touched, safe to flavor. GUI thread only.
+pub fn is_untouched(params: &dyn Params) -> bool {
+    params
+        .param_map()
+        .into_iter()
+        .filter(|(id, _, _)| presets::is_preset_param(id))
+        // SAFETY: the ParamPtrs come from `params`, which is borrowed for
+        // the duration of this call.
+        .all(|(_, ptr, _)| unsafe {
+            ptr.unmodulated_normalized_value() == ptr.default_normalized_value()
+        })
+}
+
+/// Whether new instances pick a flavor. A single stat of the opt-out file;
+/// called from `initialize()` and the background thread, never from audio.
+pub fn enabled() -> bool {
+    presets::user_preset_dir().map_or(true, |dir| !dir.join(OPT_OUT_FILE).exists())
+}
+
+/// Create or remove the opt-out file. Background thread only.
+pub fn set_enabled(enabled: bool) -> Result<(), PresetError> {
+    let dir = presets::user_preset_dir().ok_or(PresetError::NoPresetDirectory)?;
+    let marker = dir.join(OPT_OUT_FILE);
+    if enabled {
+        if marker.exists() {
+            std::fs::remove_file(marker)?;
+        }
+    } else {
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(marker, "")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(channels: u32) -> AudioIOLayout {
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(channels),
+            main_output_channels: NonZeroU32::new(channels),
+            ..AudioIOLayout::const_default()
+        }
+    }
+
+    #[test]
+    fn track_names_classify_by_word_prefix() {
+        assert_eq!(flavor_from_track_name("Drums"), Some(BusFlavor::DrumBus));
+        assert_eq!(flavor_from_track_name("KICK IN"), Some(BusFlavor::DrumBus));
+        assert_eq!(
+            flavor_from_track_name("Lead Vox"),
+            Some(BusFlavor::VocalBus)
+        );
+        assert_eq!(flavor_from_track_name("BGVs"), Some(BusFlavor::VocalBus));
+        assert_eq!(flavor_from_track_name("Drum Vox"), Some(BusFlavor::DrumBus));
+        assert_eq!(flavor_from_track_name("Mix Bus"), None);
+        // Prefixes only: "stomp" must not read as a tom.
+        assert_eq!(flavor_from_track_name("Stomp Box"), None);
+    }
+
+    #[test]
+    fn layout_decides_without_a_useful_name() {
+        assert_eq!(pick_flavor(None, &layout(2)), BusFlavor::MixBus);
+        assert_eq!(pick_flavor(None, &layout(1)), BusFlavor::VocalBus);
+        assert_eq!(pick_flavor(Some("Bus 3"), &layout(2)), BusFlavor::MixBus);
+        assert_eq!(
+            pick_flavor(Some("Snare Top"), &layout(1)),
+            BusFlavor::DrumBus
+        );
+    }
+
+    #[test]
+    fn every_flavor_names_a_factory_preset() {
+        let factory = presets::factory_presets();
+        for flavor in [BusFlavor::MixBus, BusFlavor::DrumBus, BusFlavor::VocalBus] {
+            assert!(
+                factory.iter().any(|p| p.name == flavor.preset_name()),
+                "{flavor:?} names a missing preset"
+            );
+        }
+    }
+
+    #[test]
+    fn fresh_params_are_untouched() {
+        let params = crate::BusChannelStripParams::default();
+        assert!(is_untouched(&params));
+    }
+
+    #[test]
+    fn state_round_trips_and_defaults_to_undecided() {
+        let state = ContextPreset::Pending(BusFlavor::DrumBus);
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<ContextPreset>(&json).unwrap(), state);
+        assert_eq!(ContextPreset::default(), ContextPreset::Undecided);
+    }
+}
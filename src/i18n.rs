@@ -0,0 +1,314 @@
+// src/i18n.rs
+// GUI string table.
+//
+// The English text of a label is its own key: views pass the English string
+// through `translate` and get back the active language's version, or the
+// English text itself when the table has no entry (brand names, units,
+// glyphs). Keeping English as the key means a missing translation degrades
+// to the current UI instead of to a placeholder id.
+//
+// Host-facing parameter names are not translated — automation lanes and
+// saved sessions must read the same in every language.
+
+use crate::EditorLanguage;
+
+/// Spanish strings, keyed by the English label.
+const ES: &[(&str, &str)] = &[
+    // ── Chassis header ──────────────────────────────────────────────────
+    ("\u{2715} EXIT FOCUS", "\u{2715} SALIR DE FOCO"),
+    ("CHAIN PRESETS", "CADENAS"),
+    ("METERS", "MEDIDORES"),
+    ("SCALE", "ESCALA"),
+    ("FREEZE", "CONGELAR"),
+    ("FAITHFUL", "FIEL"),
+    ("MATCH", "IGUALAR"),
+    ("VARY", "VARIAR"),
+    ("SKIN", "ASPECTO"),
+    ("LANG", "IDIOMA"),
+    ("RATE", "FRECUENCIA"),
+    ("VIEW", "VISTA"),
+    ("TIPS", "AYUDAS"),
+    ("AUTO GAIN", "GANANCIA AUTO"),
+    ("ANALOG", "ANALÓGICO"),
+    ("BLOCK", "BLOQUE"),
+    ("SC LISTEN", "ESCUCHAR SC"),
+    ("SAFETY", "SEGURIDAD"),
+    ("LOOP RESET", "REINICIO EN BUCLE"),
+    ("LFE SAT", "SAT. LFE"),
+    ("MIDI DUCK", "DUCKING MIDI"),
+    ("NOTE", "NOTA"),
+    ("CH", "CANAL"),
+    ("DUCK AT", "PUNTO"),
+    ("HOLD", "MANTENER"),
+    ("SHAPE", "MODELADO"),
+    ("CHARACTER", "CARÁCTER"),
+    ("LINK", "VINCULAR"),
+    ("Gain", "Ganancia"),
+    // ── Chain presets ───────────────────────────────────────────────────
+    ("Default", "Predeterminada"),
+    ("Drum Bus", "Bus de batería"),
+    ("Vocal Bus", "Bus de voces"),
+    ("Mix Glue", "Cohesión"),
+    ("Wide Bus", "Bus ancho"),
+    ("Empty", "Vacía"),
+    // ── Rack, library and slots ─────────────────────────────────────────
+    ("LIBRARY", "BIBLIOTECA"),
+    ("REMOVE", "QUITAR"),
+    ("EMPTY SLOT", "RANURA VACÍA"),
+    ("SLOT", "RANURA"),
+    ("3-BAND EQ", "EQ DE 3 BANDAS"),
+    ("COMPRESSOR", "COMPRESOR"),
+    ("TUBE EQ", "EQ VALVULAR"),
+    ("DYN EQ", "EQ DIN"),
+    ("TRANSFORMER", "TRANSFORMADOR"),
+    ("CLIP + TRANSIENT", "CLIP + TRANSITORIOS"),
+    ("STEREO WIDENER", "ENSANCHADOR ESTÉREO"),
+    ("PICK A MODULE", "ELIGE UN MÓDULO"),
+    // ── Preset browser ──────────────────────────────────────────────────
+    ("SAVE AS\u{2026}", "GUARDAR COMO\u{2026}"),
+    ("SAVE", "GUARDAR"),
+    ("CANCEL", "CANCELAR"),
+    ("EXPORT CHAIN\u{2026}", "EXPORTAR CADENA\u{2026}"),
+    ("IMPORT CHAIN\u{2026}", "IMPORTAR CADENA\u{2026}"),
+    ("FACTORY", "FÁBRICA"),
+    ("USER", "USUARIO"),
+    ("No matches", "Sin resultados"),
+    (
+        "No user presets yet \u{2014} use SAVE AS\u{2026}",
+        "Aún no hay presets de usuario \u{2014} usa GUARDAR COMO\u{2026}",
+    ),
+    // ── Readout, tooltips and context menu ──────────────────────────────
+    (
+        "Hover a control for details \u{00b7} touch one to pin it here",
+        "Pasa sobre un control para ver detalles \u{00b7} tócalo para fijarlo aquí",
+    ),
+    ("Default {}", "Por defecto {}"),
+    ("Reset to default", "Restablecer valor por defecto"),
+    ("Enter value\u{2026}", "Introducir valor\u{2026}"),
+    ("Copy value", "Copiar valor"),
+    ("Paste value", "Pegar valor"),
+    ("Release touch", "Soltar toque"),
+    ("Hold touch (automation)", "Mantener toque (automatización)"),
+    // ── Sections and controls ───────────────────────────────────────────
+    ("INPUT", "ENTRADA"),
+    ("OUTPUT", "SALIDA"),
+    ("IN", "ENT"),
+    ("OUT", "SAL"),
+    ("MID / SIDE", "MEDIO / LATERAL"),
+    ("GAIN", "GANANCIA"),
+    ("MIX", "MEZCLA"),
+    ("DRY/WET", "SECO/EFECTO"),
+    ("MODE", "MODO"),
+    ("MODEL", "MODELO"),
+    ("KEY", "CLAVE"),
+    ("KEY EQ", "EQ DE CLAVE"),
+    ("KEY LISTEN", "ESCUCHAR CLAVE"),
+    ("ADVANCED", "AVANZADO"),
+    ("KEY HP", "HP CLAVE"),
+    ("KEY BELL", "CAMPANA CLAVE"),
+    ("KEY LP", "LP CLAVE"),
+    ("BELL dB", "CAMPANA dB"),
+    ("BELL Q", "Q CAMPANA"),
+    ("CORE OFFLINE", "NÚCLEO INACTIVO"),
+    ("FREQ", "FREC"),
+    ("THRESH", "UMBRAL"),
+    ("RATIO", "RELACIÓN"),
+    ("RANGE", "RANGO"),
+    ("LAW", "LEY"),
+    ("ATTACK", "ATAQUE"),
+    ("RELEASE", "LIBERACIÓN"),
+    ("ATK ms", "ATQ ms"),
+    ("AUTO REL", "LIB AUTO"),
+    ("TRIM L", "AJUSTE I"),
+    ("TRIM R", "AJUSTE D"),
+    ("AUTO BAL", "BAL AUTO"),
+    ("PROTECT", "PROTEGER"),
+    ("ATTEN", "ATEN"),
+    ("BOOST", "REALCE"),
+    ("BW", "AB"),
+    ("LOW", "GRAVES"),
+    ("HIGH", "AGUDOS"),
+    ("LOW FREQUENCY", "BAJAS FRECUENCIAS"),
+    ("HIGH FREQUENCY", "ALTAS FRECUENCIAS"),
+    ("LF SHELF", "SHELF GRAVES"),
+    ("HF SHELF", "SHELF AGUDOS"),
+    ("LINEAR PHASE", "FASE LINEAL"),
+    ("SLOPE", "PENDIENTE"),
+    ("TILT", "INCLINACIÓN"),
+    ("PIVOT", "PIVOTE"),
+    ("ROTATE", "ROTACIÓN"),
+    ("POLARITY", "POLARIDAD"),
+    ("DUAL MONO", "MONO DUAL"),
+    ("MONO SAFE", "COMPATIBLE MONO"),
+    ("AGE", "EDAD"),
+    ("L/R OFFSET", "DESVÍO I/D"),
+    ("TONE", "TONO"),
+    ("DRIVE", "SATURACIÓN"),
+    ("VARIANCE", "VARIANZA"),
+    ("EVEN", "PARES"),
+    ("TUBE DRIVE", "SATURACIÓN VÁLVULA"),
+    ("PRINT UNDER LOAD", "IMPRESIÓN BAJO CARGA"),
+    ("PRINT", "IMPRIMIR"),
+    (
+        "Drops tube oversampling; may alias at high drive",
+        "Sin sobremuestreo de válvula; puede generar aliasing con saturación alta",
+    ),
+    ("PRINTED", "IMPRESO"),
+    ("CHAR %", "CARÁCTER %"),
+    ("CLIPPER", "RECORTADOR"),
+    ("SOFT", "SUAVE"),
+    ("SMOOTH", "SUAVIZADO"),
+    ("OVSMP", "SOBREM"),
+    ("TRANSIENTS", "TRANSITORIOS"),
+    ("SUSTAIN", "SOSTENIDO"),
+    ("WET HPF", "HPF EFECTO"),
+    ("COMPRESS", "COMPRIMIR"),
+    ("SPEED", "VELOCIDAD"),
+    ("DEPTH", "PROFUNDIDAD"),
+    ("TIME", "TIEMPO"),
+    ("COMB", "PEINE"),
+    ("M/S GAIN", "GANANCIA M/S"),
+    ("MID", "MEDIO"),
+    ("SIDE", "LATERAL"),
+    ("XFMR", "TRAFO"),
+    // ── DynEQ back view ─────────────────────────────────────────────────
+    ("\u{25C0} STRIP VIEW", "\u{25C0} VISTA DE CANAL"),
+    ("DYNAMIC EQ", "EQ DINÁMICO"),
+    (
+        "4-band dynamic equalizer",
+        "ecualizador dinámico de 4 bandas",
+    ),
+    ("OPEN EDITOR \u{25B6}", "ABRIR EDITOR \u{25B6}"),
+    ("ANALYZE SC", "ANALIZAR SC"),
+    ("APPLY RESULT", "APLICAR RESULTADO"),
+    ("BAND 1 \u{2014} LOW", "BANDA 1 \u{2014} GRAVES"),
+    ("BAND 2 \u{2014} LOW MID", "BANDA 2 \u{2014} MEDIOS GRAVES"),
+    ("BAND 3 \u{2014} HIGH MID", "BANDA 3 \u{2014} MEDIOS AGUDOS"),
+    ("BAND 4 \u{2014} HIGH", "BANDA 4 \u{2014} AGUDOS"),
+    // ── Sheen back view ─────────────────────────────────────────────────
+    ("MASTER BYPASS", "BYPASS GENERAL"),
+    ("\u{21BA} RESTORE FACTORY", "\u{21BA} RESTAURAR FÁBRICA"),
+    ("BODY", "CUERPO"),
+    ("PRESENCE", "PRESENCIA"),
+    ("AIR", "AIRE"),
+    ("WARMTH", "CALIDEZ"),
+    ("WIDTH", "AMPLITUD"),
+    // ── Metering ────────────────────────────────────────────────────────
+    ("METERING", "MEDICIÓN"),
+    ("SPECTRUM", "ESPECTRO"),
+    ("TAP", "PUNTO"),
+    ("CHANNELS", "CANALES"),
+    ("WINDOW", "VENTANA"),
+    ("OVERLAP", "SOLAPAMIENTO"),
+    ("AVERAGING", "PROMEDIADO"),
+    ("FLOOR", "SUELO"),
+    ("LOUDNESS (LUFS)", "SONORIDAD (LUFS)"),
+    ("MOMENTARY", "MOMENTÁNEA"),
+    ("SHORT-TERM", "CORTO PLAZO"),
+    ("INTEGRATED", "INTEGRADA"),
+    ("\u{21BA} RESET INTEGRATED", "\u{21BA} REINICIAR INTEGRADA"),
+    ("DYNAMICS (dB)", "DINÁMICA (dB)"),
+    ("CREST IN", "CRESTA ENT"),
+    ("CREST OUT", "CRESTA SAL"),
+    ("SPREAD", "MARGEN"),
+    ("STEREO / CORRELATION", "ESTÉREO / CORRELACIÓN"),
+    ("ENGAGED", "ACTIVIDAD"),
+    ("TUBE", "VÁLVULA"),
+    ("GR HISTORY", "HISTORIAL GR"),
+    // ── Loudness target ─────────────────────────────────────────────────
+    ("LOUDNESS TARGET", "OBJETIVO DE SONORIDAD"),
+    ("LEARN", "APRENDER"),
+    ("APPLY TO PUNCH", "APLICAR A PUNCH"),
+    (
+        "Set a target, play the program, then LEARN",
+        "Elige un objetivo, reproduce el programa y pulsa APRENDER",
+    ),
+    (
+        "Learning\u{2026} play the program",
+        "Aprendiendo\u{2026} reproduce el programa",
+    ),
+    (
+        "Target changed \u{2014} LEARN again",
+        "Objetivo cambiado \u{2014} vuelve a APRENDER",
+    ),
+    (
+        "No signal at Punch \u{2014} put it in the rack and play the program",
+        "No hay señal en Punch \u{2014} colócalo en el rack y reproduce el programa",
+    ),
+    // ── Spectrum-match EQ ───────────────────────────────────────────────
+    ("EQ MATCH", "IGUALACIÓN DE EQ"),
+    ("REFERENCE:", "REFERENCIA:"),
+    ("FILE", "ARCHIVO"),
+    ("LOAD WAV", "CARGAR WAV"),
+    ("USE SIDECHAIN", "USAR SIDECHAIN"),
+    ("CAPTURE", "CAPTURAR"),
+    ("APPLY TO EQ", "APLICAR AL EQ"),
+    (
+        "Play the program, then CAPTURE to compare it with the reference",
+        "Reproduce el programa y pulsa CAPTURAR para compararlo con la referencia",
+    ),
+    ("Capturing\u{2026}", "Capturando\u{2026}"),
+    ("Loading reference\u{2026}", "Cargando referencia\u{2026}"),
+    (
+        "Reference loaded \u{2014} CAPTURE the program",
+        "Referencia cargada \u{2014} CAPTURA el programa",
+    ),
+    ("No signal to match", "No hay señal que igualar"),
+    ("Couldn't read the file", "No se pudo leer el archivo"),
+    ("Not a WAV file", "No es un archivo WAV"),
+    ("Unsupported WAV format", "Formato WAV no compatible"),
+    ("Reference is too short", "La referencia es demasiado corta"),
+    // ── EQ response export ──────────────────────────────────────────────
+    ("EQ RESPONSE", "RESPUESTA DEL EQ"),
+    ("EXPORT CSV", "EXPORTAR CSV"),
+    ("EXPORT WAV", "EXPORTAR WAV"),
+    (
+        "Type a path, then export the EQ curve as CSV or an impulse-response WAV",
+        "Escribe una ruta y exporta la curva del EQ como CSV o como WAV de respuesta al impulso",
+    ),
+    ("Exporting\u{2026}", "Exportando\u{2026}"),
+    ("Response written", "Respuesta guardada"),
+    ("Couldn't write the file", "No se pudo escribir el archivo"),
+];
+
+/// `text` in `language`, falling back to `text` itself.
+pub fn translate(language: EditorLanguage, text: &str) -> &str {
+    let table = match language {
+        EditorLanguage::English => return text,
+        EditorLanguage::Spanish => ES,
+    };
+    table
+        .iter()
+        .find(|(en, _)| *en == text)
+        .map_or(text, |(_, translated)| translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_is_identity_and_unknown_text_falls_back() {
+        assert_eq!(translate(EditorLanguage::English, "OUTPUT"), "OUTPUT");
+        assert_eq!(translate(EditorLanguage::Spanish, "OUTPUT"), "SALIDA");
+        assert_eq!(translate(EditorLanguage::Spanish, "API 550A"), "API 550A");
+    }
+
+    #[test]
+    fn spanish_table_has_unique_non_empty_entries() {
+        for (i, (en, es)) in ES.iter().enumerate() {
+            assert!(!en.is_empty() && !es.is_empty(), "empty entry at {i}");
+            assert!(
+                ES[i + 1..].iter().all(|(other, _)| other != en),
+                "duplicate key {en:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn format_templates_keep_their_placeholder() {
+        for (en, es) in ES {
+            assert_eq!(en.matches("{}").count(), es.matches("{}").count(), "{en:?}");
+        }
+    }
+}
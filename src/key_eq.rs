@@ -0,0 +1,248 @@
+// src/key_eq.rs
+// Key EQ: the parametric filter in front of a dynamics detector.
+//
+//   [key] -> HP (12 dB/oct) -> bell -> LP (12 dB/oct) -> [detector]
+//
+// A plain sidechain HP only stops the low end from pumping the detector.
+// The bell and LP let it aim: lift 3-5 kHz so a bus compressor reacts to
+// vocal presence, dip the kick's fundamental out of a de-esser band, or cut
+// cymbal wash so a gate opens on the snare alone. The filters only shape
+// what the detector hears; the audio path never goes through them.
+//
+// The HP stands on its own corner (at `HP_OFF_HZ` it's out of circuit) so
+// it can double as the compressor's long-standing SC HP; `enabled` brings
+// the bell and LP in. Each stage is a `shaping::Filter`, so stereo keys
+// keep per-channel state.
+
+use crate::shaping::{Filter, FilterType};
+
+/// HP corners at or below this leave the HP out (20 Hz is "off" on the
+/// compressor's SC HP as well).
+pub const HP_OFF_HZ: f32 = 20.0;
+/// LP corners at or above this leave the LP out.
+pub const LP_OFF_HZ: f32 = 20_000.0;
+
+/// Butterworth Q for the HP and LP sections.
+const PASS_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// One detector's key EQ settings, passed in every buffer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyEqSettings {
+    /// Bell and LP in circuit.
+    pub enabled: bool,
+    pub hp_hz: f32,
+    pub bell_hz: f32,
+    pub bell_gain_db: f32,
+    pub bell_q: f32,
+    pub lp_hz: f32,
+}
+
+impl Default for KeyEqSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hp_hz: HP_OFF_HZ,
+            bell_hz: 1000.0,
+            bell_gain_db: 0.0,
+            bell_q: 1.0,
+            lp_hz: LP_OFF_HZ,
+        }
+    }
+}
+
+pub struct KeyEq {
+    sample_rate: f32,
+    settings: KeyEqSettings,
+    hp: Filter,
+    bell: Filter,
+    lp: Filter,
+}
+
+impl KeyEq {
+    pub fn new(sample_rate: f32) -> Self {
+        let settings = KeyEqSettings::default();
+        let (hp, bell, lp) = Self::design(sample_rate, &settings);
+        Self {
+            sample_rate,
+            settings,
+            hp,
+            bell,
+            lp,
+        }
+    }
+
+    fn design(sample_rate: f32, s: &KeyEqSettings) -> (Filter, Filter, Filter) {
+        (
+            Filter::new(sample_rate, FilterType::HighPass, s.hp_hz, PASS_Q, 0.0),
+            Filter::new(
+                sample_rate,
+                FilterType::Bell,
+                s.bell_hz,
+                s.bell_q,
+                s.bell_gain_db,
+            ),
+            Filter::new(
+                sample_rate,
+                FilterType::LowPass,
+                s.lp_hz.min(sample_rate * 0.45),
+                PASS_Q,
+                0.0,
+            ),
+        )
+    }
+
+    /// Update the filters — call once per buffer. Only the sections whose
+    /// settings changed are redesigned; filter state carries over.
+    pub fn update_parameters(&mut self, settings: KeyEqSettings) {
+        let old = self.settings;
+        self.settings = settings;
+        let sr = self.sample_rate;
+        if settings.hp_hz != old.hp_hz {
+            self.hp
+                .update_parameters(sr, FilterType::HighPass, settings.hp_hz, PASS_Q, 0.0);
+        }
+        if (settings.bell_hz, settings.bell_q, settings.bell_gain_db)
+            != (old.bell_hz, old.bell_q, old.bell_gain_db)
+        {
+            self.bell.update_parameters(
+                sr,
+                FilterType::Bell,
+                settings.bell_hz,
+                settings.bell_q,
+                settings.bell_gain_db,
+            );
+        }
+        if settings.lp_hz != old.lp_hz {
+            self.lp.update_parameters(
+                sr,
+                FilterType::LowPass,
+                settings.lp_hz.min(sr * 0.45),
+                PASS_Q,
+                0.0,
+            );
+        }
+    }
+
+    /// Whether any section is in circuit; when not, `run` is a wire.
+    pub fn is_active(&self) -> bool {
+        let s = &self.settings;
+        s.hp_hz > HP_OFF_HZ || (s.enabled && (s.bell_gain_db != 0.0 || s.lp_hz < LP_OFF_HZ))
+    }
+
+    /// Filter one key sample on channel `ch` (0 = L, 1 = R).
+    #[inline]
+    pub fn run(&mut self, x: f32, ch: usize) -> f32 {
+        let s = &self.settings;
+        let mut y = x;
+        if s.hp_hz > HP_OFF_HZ {
+            y = self.hp.run_ch(y, ch);
+        }
+        if s.enabled {
+            if s.bell_gain_db != 0.0 {
+                y = self.bell.run_ch(y, ch);
+            }
+            if s.lp_hz < LP_OFF_HZ {
+                y = self.lp.run_ch(y, ch);
+            }
+        }
+        y
+    }
+
+    /// Filter a key block in place, one slice per channel.
+    pub fn process_channels(&mut self, channels: &mut [&mut [f32]]) {
+        for (ch, samples) in channels.iter_mut().take(2).enumerate() {
+            for s in samples.iter_mut() {
+                *s = self.run(*s, ch);
+            }
+        }
+    }
+
+    /// Clear filter state.
+    pub fn reset(&mut self) {
+        (self.hp, self.bell, self.lp) = Self::design(self.sample_rate, &self.settings);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48000.0;
+
+    /// Steady-state RMS of a sine at `freq` through the key EQ.
+    fn gain_at(eq: &mut KeyEq, freq: f32) -> f32 {
+        eq.reset();
+        let n = SR as usize / 2;
+        let mut acc = 0.0;
+        for i in 0..n {
+            let x = (std::f32::consts::TAU * freq * i as f32 / SR).sin();
+            let y = eq.run(x, 0);
+            if i >= n / 2 {
+                acc += y * y;
+            }
+        }
+        (acc / (n / 2) as f32 * 2.0).sqrt()
+    }
+
+    #[test]
+    fn default_settings_are_a_wire() {
+        let mut eq = KeyEq::new(SR);
+        assert!(!eq.is_active());
+        for i in 0..64 {
+            let x = (i as f32 * 0.37).sin();
+            assert_eq!(eq.run(x, 0), x);
+        }
+    }
+
+    #[test]
+    fn hp_runs_without_the_bell_and_lp() {
+        let mut eq = KeyEq::new(SR);
+        eq.update_parameters(KeyEqSettings {
+            hp_hz: 200.0,
+            bell_gain_db: 12.0,
+            lp_hz: 1000.0,
+            ..KeyEqSettings::default()
+        });
+        assert!(eq.is_active());
+        assert!(gain_at(&mut eq, 40.0) < 0.1);
+        // Bell and LP are out while the EQ is disabled.
+        assert!((gain_at(&mut eq, 5000.0) - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn bell_and_lp_shape_the_key_when_enabled() {
+        let mut eq = KeyEq::new(SR);
+        eq.update_parameters(KeyEqSettings {
+            enabled: true,
+            bell_hz: 3000.0,
+            bell_gain_db: 12.0,
+            bell_q: 1.0,
+            lp_hz: 8000.0,
+            ..KeyEqSettings::default()
+        });
+        let boost = gain_at(&mut eq, 3000.0);
+        assert!((20.0 * boost.log10() - 12.0).abs() < 0.5, "{boost}");
+        assert!(gain_at(&mut eq, 18000.0) < 0.3);
+        assert!((gain_at(&mut eq, 100.0) - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn channels_keep_their_own_state() {
+        let mut eq = KeyEq::new(SR);
+        eq.update_parameters(KeyEqSettings {
+            enabled: true,
+            hp_hz: 100.0,
+            lp_hz: 5000.0,
+            ..KeyEqSettings::default()
+        });
+        let mut l: Vec<f32> = (0..256).map(|i| (i as f32 * 0.05).sin()).collect();
+        let mut r = vec![0.0; 256];
+        let mut alone = l.clone();
+        eq.process_channels(&mut [&mut l[..], &mut r[..]]);
+        assert!(r.iter().all(|&s| s == 0.0));
+        let mut mono = KeyEq::new(SR);
+        mono.update_parameters(eq.settings);
+        mono.process_channels(&mut [&mut alone[..]]);
+        assert_eq!(l, alone);
+    }
+}
@@ -1,55 +1,227 @@
+//! Bus Channel Strip: a mix-bus channel strip as a CLAP/VST3 plugin.
+//!
+//! The plugin wrapper (nih-plug, the editor, metering and presets) sits
+//! behind the default `plugin` feature. The DSP modules underneath don't
+//! need any of it: with `default-features = false` and the module features
+//! you want, [`dsp`] exposes them as a plain library that processes
+//! per-channel `f32` slices.
+
+#[cfg(feature = "plugin")]
 use nih_plug::prelude::*;
-use std::sync::Arc;
+#[cfg(feature = "plugin")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "plugin")]
+use std::sync::{Arc, Mutex, RwLock};
 #[cfg(feature = "gui")]
 use vizia_plug::ViziaState;
+// Counting allocator behind the DSP tests' `assert_no_alloc`.
 #[cfg(test)]
-mod biquad_sanity_test;
-mod oversampler;
+mod alloc_guard;
+// Module-level entry points for `benches/dsp.rs` and the golden tests;
+// both need every DSP module.
+#[cfg(any(
+    feature = "bench",
+    all(
+        test,
+        feature = "plugin",
+        feature = "api5500",
+        feature = "buttercomp2",
+        feature = "pultec",
+        feature = "transformer",
+        feature = "punch",
+        feature = "haas",
+        feature = "dynamic_eq",
+        feature = "sheen"
+    )
+))]
+#[doc(hidden)]
+pub mod bench;
 #[cfg(test)]
+mod biquad_sanity_test;
+pub mod dsp;
+// C-ABI exports for the browser demo; see wasm.rs.
+#[cfg(feature = "wasm")]
+mod wasm;
+
+// Everything from here to the DSP modules serves the plugin wrapper.
+#[cfg(feature = "plugin")]
+mod analog;
+#[cfg(all(
+    feature = "plugin",
+    any(
+        feature = "api5500",
+        feature = "pultec",
+        feature = "transformer",
+        feature = "sheen"
+    )
+))]
+use analog::AnalogTarget;
+#[cfg(feature = "plugin")]
+mod analyzer;
+#[cfg(feature = "plugin")]
+mod automation;
+#[cfg(feature = "plugin")]
+use automation::RarelyAutomated;
+#[cfg(feature = "plugin")]
+mod block;
+#[cfg(feature = "plugin")]
+mod chain;
+#[cfg(feature = "plugin")]
+mod comp_scale;
+#[cfg(feature = "plugin")]
+mod delta;
+#[cfg(feature = "plugin")]
+mod dither;
+#[cfg(feature = "plugin")]
+mod duck;
+#[cfg(feature = "plugin")]
+mod dynamics;
+// Undo/redo bookkeeping lives in the editor.
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod history;
+// First-instantiation preset flavor, applied by the editor.
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod host_context;
+// Translated labels are only looked up by the editor.
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod i18n;
+#[cfg(feature = "plugin")]
+mod loudness;
+// Suggestions are only worked out by the editor.
+#[cfg(all(feature = "plugin", feature = "punch"))]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod loudness_target;
+// Suggestions and reference-error text are only read by the editor.
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod matching;
+// Meter scales and ballistics are applied by the editor.
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod meter_scale;
+// Remote control; the listener only queues, the editor applies.
+#[cfg(feature = "osc")]
+mod osc;
+#[cfg(feature = "plugin")]
+mod param_groups;
+#[cfg(all(test, feature = "plugin"))]
 mod plugin_integration_tests;
-mod shaping;
+// Capture/apply helpers and the factory bank are only reached from the editor.
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod presets;
+#[cfg(all(feature = "plugin", feature = "punch"))]
+mod punch_link;
+#[cfg(feature = "plugin")]
+mod reorder;
+#[cfg(all(feature = "plugin", any(feature = "api5500", feature = "pultec")))]
+mod response;
+#[cfg(feature = "plugin")]
+mod safety;
+// A/B snapshot state lives in the editor.
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod snapshots;
+#[cfg(feature = "plugin")]
 mod spectral;
+#[cfg(feature = "plugin")]
+mod surround;
+#[cfg(feature = "plugin")]
+mod transport;
+// The VARY buttons are editor-only.
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod variation;
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+mod view_state;
+
+#[cfg(any(feature = "api5500", feature = "pultec"))]
+mod character;
+#[cfg(all(any(feature = "api5500", feature = "pultec"), feature = "plugin"))]
+use character::EqCharacter;
+#[cfg(any(feature = "pultec", feature = "punch", feature = "transformer"))]
+mod engagement;
+#[cfg_attr(
+    not(any(
+        feature = "buttercomp2",
+        feature = "dynamic_eq",
+        feature = "punch",
+        feature = "transformer"
+    )),
+    allow(dead_code)
+)]
+mod envelope;
+#[cfg(any(
+    all(feature = "buttercomp2", feature = "plugin"),
+    feature = "dynamic_eq"
+))]
+#[cfg_attr(not(feature = "dynamic_eq"), allow(dead_code))]
+mod key_eq;
+mod oversampler;
+mod shaping;
+#[cfg(any(feature = "api5500", feature = "pultec"))]
+mod stereo_biquad;
+#[cfg(any(feature = "pultec", feature = "transformer"))]
+mod variance;
+// Without the plugin only the Pultec print's triple buffer is in use.
+#[cfg_attr(not(feature = "plugin"), allow(dead_code))]
+mod telemetry;
 
 #[cfg(feature = "api5500")]
 mod api5500;
-#[cfg(feature = "api5500")]
+#[cfg(all(feature = "api5500", feature = "plugin"))]
 use api5500::Api5500;
+#[cfg(feature = "api5500")]
+mod linear_phase;
 
-#[cfg(feature = "buttercomp2")]
+#[cfg(all(feature = "buttercomp2", feature = "plugin"))]
+mod balance;
+#[cfg(all(feature = "buttercomp2", feature = "plugin"))]
 mod buttercomp2;
-#[cfg(feature = "buttercomp2")]
-use buttercomp2::{
-    ButterComp2, ButterComp2Model, FetCompressor, FetRatio, OpticalCompressor, VcaCompressor,
-};
+#[cfg(all(feature = "buttercomp2", feature = "plugin"))]
+use buttercomp2::{ButterComp2Model, FetRatio};
 
 #[cfg(feature = "pultec")]
-mod pultec;
+mod print;
 #[cfg(feature = "pultec")]
+mod pultec;
+#[cfg(all(feature = "pultec", feature = "plugin"))]
 use pultec::PultecEQ;
 
 #[cfg(feature = "dynamic_eq")]
 mod dynamic_eq;
-#[cfg(feature = "dynamic_eq")]
-use dynamic_eq::{DynamicBandParams, DynamicEQ, DynamicMode};
+#[cfg(all(feature = "dynamic_eq", feature = "plugin"))]
+use dynamic_eq::{ControlLaw, DynamicBandParams, DynamicMode, RangeLaw};
 
+#[cfg(all(feature = "transformer", feature = "plugin"))]
+mod harmonics;
 #[cfg(feature = "transformer")]
 mod transformer;
-#[cfg(feature = "transformer")]
-use transformer::{TransformerModel, TransformerModule};
+#[cfg(all(feature = "transformer", feature = "plugin"))]
+use transformer::TransformerModel;
 
 #[cfg(feature = "punch")]
 mod punch;
-#[cfg(feature = "punch")]
-use punch::{ClipMode, OversamplingFactor, PunchModule};
+#[cfg(all(feature = "punch", feature = "plugin"))]
+use punch::{ClipMode, OversamplingFactor};
 
-#[cfg(feature = "haas")]
+#[cfg(all(feature = "haas", feature = "plugin"))]
 mod haas;
-#[cfg(feature = "haas")]
-use haas::{CombMode, HaasModule};
+#[cfg(all(feature = "haas", feature = "plugin"))]
+use haas::CombMode;
+
+#[cfg(feature = "conditioning")]
+mod conditioning;
+#[cfg(all(feature = "conditioning", feature = "plugin"))]
+use conditioning::{InputConditioning, Polarity, Slope};
 
-#[cfg(feature = "sheen")]
+#[cfg(all(feature = "sheen", feature = "plugin"))]
 mod sheen;
-#[cfg(feature = "sheen")]
+#[cfg(all(feature = "sheen", feature = "plugin"))]
 use sheen::SheenModule;
 
 #[cfg(feature = "gui")]
@@ -61,6 +233,7 @@ mod styles;
 
 /// Compute RMS across all channels from a slice-of-slices buffer view.
 /// Allocation-free; safe to call on the audio thread.
+#[cfg(feature = "plugin")]
 fn rms_linear(channels: &[&mut [f32]]) -> f32 {
     let mut sum_sq = 0.0_f32;
     let mut n = 0_u32;
@@ -77,12 +250,76 @@ fn rms_linear(channels: &[&mut [f32]]) -> f32 {
     }
 }
 
-/// Smoothing coefficient for auto-gain: ~5-second time constant at 86 buffers/sec.
-const AUTO_GAIN_SMOOTH: f32 = 0.9975;
+/// `rms_linear` of the first and second channel apart; 0 for a channel the
+/// buffer doesn't have.
+#[cfg(feature = "plugin")]
+fn channel_rms(channels: &[&mut [f32]]) -> [f32; 2] {
+    std::array::from_fn(|ch| rms_linear(channels.get(ch..ch + 1).unwrap_or(&[])))
+}
+
+/// RMS of the mid ((L + R) / 2) and side ((L − R) / 2) signals. A mono
+/// buffer is all mid. Allocation-free; safe to call on the audio thread.
+#[cfg(feature = "plugin")]
+fn mid_side_rms(channels: &[&mut [f32]]) -> [f32; 2] {
+    match channels {
+        [left, right, ..] => {
+            let (mut mid_sq, mut side_sq) = (0.0_f32, 0.0_f32);
+            for (&l, &r) in left.iter().zip(right.iter()) {
+                mid_sq += (l + r) * (l + r) * 0.25;
+                side_sq += (l - r) * (l - r) * 0.25;
+            }
+            let n = left.len().min(right.len()).max(1) as f32;
+            [(mid_sq / n).sqrt(), (side_sq / n).sqrt()]
+        }
+        _ => [rms_linear(channels), 0.0],
+    }
+}
+
+/// Highest absolute sample across all channels. Allocation-free; safe to
+/// call on the audio thread.
+#[cfg(feature = "plugin")]
+fn peak_linear(channels: &[&mut [f32]]) -> f32 {
+    channels
+        .iter()
+        .flat_map(|ch| ch.iter())
+        .fold(0.0_f32, |peak, &s| peak.max(s.abs()))
+}
+
+/// Auto-gain smoothing time constant (seconds).
+#[cfg(feature = "plugin")]
+const AUTO_GAIN_TAU_S: f32 = 5.0;
 /// Maximum auto-gain correction: ±18 dB in linear.
+#[cfg(feature = "plugin")]
 const AUTO_GAIN_MAX: f32 = 8.0; // +18.06 dB
+#[cfg(feature = "plugin")]
 const AUTO_GAIN_MIN: f32 = 0.125; // −18.06 dB
 
+/// Smoothing time constant for the published output level (seconds). Slow
+/// on purpose — A/B gain matching wants an average loudness, not a meter.
+#[cfg(feature = "plugin")]
+const OUTPUT_LEVEL_TAU_S: f32 = 1.2;
+
+/// Correlation meter time constant (seconds), close to the integration
+/// time of hardware phase meters.
+#[cfg(feature = "plugin")]
+const CORRELATION_TAU_S: f32 = 0.12;
+/// Engagement share time constant (seconds). Long enough to read as "how
+/// much of this passage", short enough to follow a section change.
+#[cfg(feature = "plugin")]
+const ENGAGEMENT_TAU_S: f32 = 3.0;
+/// Goniometer points kept per second. The 512-point ring then spans ~85 ms
+/// at any sample rate — enough for a stable trace down to ~20 Hz.
+#[cfg(feature = "plugin")]
+const SCOPE_POINT_RATE_HZ: f32 = 6000.0;
+
+/// Per-buffer one-pole coefficient for a `tau_s` time constant. Derived
+/// from the buffer length so the smoothing speed holds at any sample rate
+/// and host buffer size.
+#[cfg(feature = "plugin")]
+fn block_smoothing(tau_s: f32, samples: usize, sample_rate: f32) -> f32 {
+    envelope::coeff_for_block(tau_s * 1000.0, samples, sample_rate)
+}
+
 /// Module identifiers for reordering.
 ///
 /// `Empty` is the sentinel for an unoccupied slot — the audio dispatcher
@@ -90,6 +327,7 @@ const AUTO_GAIN_MIN: f32 = 0.125; // −18.06 dB
 /// picker. It is intentionally the LAST variant so existing sessions
 /// (saved before Empty existed) still decode their non-Empty values
 /// against the same enum indices 0..6.
+#[cfg(feature = "plugin")]
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
 pub enum ModuleType {
     #[name = "API5500 EQ"]
@@ -110,110 +348,611 @@ pub enum ModuleType {
     Empty,
 }
 
+#[cfg(feature = "plugin")]
 impl Default for ModuleType {
     fn default() -> Self {
         Self::Api5500EQ
     }
 }
 
+/// Spectrum analyzer window. Blackman-Harris trades a wider main lobe for
+/// far lower leakage, so quiet content next to loud peaks stays visible.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum AnalyzerWindow {
+    #[name = "Hann"]
+    Hann,
+    #[name = "Blackman-Harris"]
+    BlackmanHarris,
+}
+
+/// Spectrum analyzer frame overlap. More overlap means more frames per
+/// second for the same resolution.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum AnalyzerOverlap {
+    #[name = "None"]
+    None,
+    #[name = "50%"]
+    Half,
+    #[name = "75%"]
+    ThreeQuarter,
+}
+
+/// Where in the chain the spectrum analyzer listens: the strip's input, the
+/// boundary after any of the seven slots, or the output after master gain.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum AnalyzerTap {
+    #[name = "Input"]
+    Input,
+    #[name = "After Slot 1"]
+    Slot1,
+    #[name = "After Slot 2"]
+    Slot2,
+    #[name = "After Slot 3"]
+    Slot3,
+    #[name = "After Slot 4"]
+    Slot4,
+    #[name = "After Slot 5"]
+    Slot5,
+    #[name = "After Slot 6"]
+    Slot6,
+    #[name = "After Slot 7"]
+    Slot7,
+    #[name = "Output"]
+    Output,
+}
+
+#[cfg(feature = "plugin")]
+impl AnalyzerTap {
+    /// Slot boundary the tap sits on: 0 is the input, N follows slot N and
+    /// `ANALYZER_TAP_OUTPUT` is after master gain.
+    fn boundary(self) -> usize {
+        match self {
+            AnalyzerTap::Input => 0,
+            AnalyzerTap::Slot1 => 1,
+            AnalyzerTap::Slot2 => 2,
+            AnalyzerTap::Slot3 => 3,
+            AnalyzerTap::Slot4 => 4,
+            AnalyzerTap::Slot5 => 5,
+            AnalyzerTap::Slot6 => 6,
+            AnalyzerTap::Slot7 => 7,
+            AnalyzerTap::Output => ANALYZER_TAP_OUTPUT,
+        }
+    }
+}
+
+/// Tap boundary past the last slot, Sheen and master gain.
+#[cfg(feature = "plugin")]
+const ANALYZER_TAP_OUTPUT: usize = 8;
+
+/// Internal processing block. `Host` runs the chain on whatever the host
+/// passes; a fixed size runs it on blocks of exactly that many samples and
+/// adds that many samples of latency. See `block.rs`.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum BlockSize {
+    #[name = "Host"]
+    Host,
+    #[name = "32"]
+    B32,
+    #[name = "64"]
+    B64,
+    #[name = "128"]
+    B128,
+    #[name = "256"]
+    B256,
+    #[name = "512"]
+    B512,
+}
+
+#[cfg(feature = "plugin")]
+impl BlockSize {
+    /// Block length in samples; 0 for `Host`.
+    fn samples(self) -> usize {
+        match self {
+            BlockSize::Host => 0,
+            BlockSize::B32 => 32,
+            BlockSize::B64 => 64,
+            BlockSize::B128 => 128,
+            BlockSize::B256 => 256,
+            BlockSize::B512 => block::MAX_BLOCK,
+        }
+    }
+}
+
+/// How the strip treats the two channels. `DualMono` runs them as two
+/// independent mono strips, for unrelated sources sharing a stereo track:
+/// every detector splits per channel, and the stages that only make sense
+/// across a pair (auto balance, mono-safe, Sheen's width, Haas) stand
+/// aside.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum ChannelMode {
+    #[name = "Stereo"]
+    Stereo,
+    #[name = "Dual Mono"]
+    DualMono,
+}
+
+/// Delta listen: the module whose difference (output − input) replaces
+/// the strip's output. See `delta.rs`.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum DeltaListen {
+    #[name = "Off"]
+    Off,
+    #[name = "API5500 EQ"]
+    Api5500EQ,
+    #[name = "ButterComp2"]
+    ButterComp2,
+    #[name = "Pultec EQ"]
+    PultecEQ,
+    #[name = "Dynamic EQ"]
+    DynamicEQ,
+    #[name = "Transformer"]
+    Transformer,
+    #[name = "Haas"]
+    Haas,
+    #[name = "Punch"]
+    Punch,
+}
+
+#[cfg(feature = "plugin")]
+impl DeltaListen {
+    fn module(self) -> Option<ModuleType> {
+        match self {
+            DeltaListen::Off => None,
+            DeltaListen::Api5500EQ => Some(ModuleType::Api5500EQ),
+            DeltaListen::ButterComp2 => Some(ModuleType::ButterComp2),
+            DeltaListen::PultecEQ => Some(ModuleType::PultecEQ),
+            DeltaListen::DynamicEQ => Some(ModuleType::DynamicEQ),
+            DeltaListen::Transformer => Some(ModuleType::Transformer),
+            DeltaListen::Haas => Some(ModuleType::Haas),
+            DeltaListen::Punch => Some(ModuleType::Punch),
+        }
+    }
+}
+
+/// Key listen: the detector whose key, as its key EQ and detection filter
+/// shape it, replaces the strip's output. See `key_eq.rs`.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum KeyListen {
+    #[name = "Off"]
+    Off,
+    #[name = "Compressor"]
+    Compressor,
+    #[name = "DynEQ Band 1"]
+    DynEqBand1,
+    #[name = "DynEQ Band 2"]
+    DynEqBand2,
+    #[name = "DynEQ Band 3"]
+    DynEqBand3,
+    #[name = "DynEQ Band 4"]
+    DynEqBand4,
+}
+
+#[cfg(feature = "plugin")]
+#[cfg_attr(not(feature = "dynamic_eq"), allow(dead_code))]
+impl KeyListen {
+    fn dyneq_band(self) -> Option<usize> {
+        match self {
+            KeyListen::DynEqBand1 => Some(0),
+            KeyListen::DynEqBand2 => Some(1),
+            KeyListen::DynEqBand3 => Some(2),
+            KeyListen::DynEqBand4 => Some(3),
+            KeyListen::Off | KeyListen::Compressor => None,
+        }
+    }
+}
+
+/// Longest whole-sample module latency delta listen has to align: the
+/// API5500's linear-phase FIR. The oversampled modules' halfband delay
+/// (11 to 20.6 samples, fractional above 2×) can't be met by a delay line
+/// this size or any other; `DeltaTap` runs the input through a matching
+/// cascade instead, so it needs no room here.
+#[cfg(all(feature = "plugin", feature = "api5500"))]
+const DELTA_MAX_LATENCY: usize = linear_phase::LATENCY_SAMPLES as usize;
+#[cfg(all(feature = "plugin", not(feature = "api5500")))]
+const DELTA_MAX_LATENCY: usize = 0;
+
+/// What a module's detector listens to: its own input, or the key on the
+/// aux sidechain input. See `route_key`.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum KeySource {
+    #[name = "Internal"]
+    Internal,
+    #[name = "External"]
+    External,
+}
+
+/// The sidechain routing matrix in one place: the key a module routed to
+/// `source` detects on, or `None` for its own input. Without the sidechain
+/// layout there is no key, and External falls back to internal detection.
+#[cfg(feature = "plugin")]
+#[cfg_attr(
+    not(any(feature = "buttercomp2", feature = "dynamic_eq")),
+    allow(dead_code)
+)]
+fn route_key<'a, 'b>(
+    source: KeySource,
+    sidechain: Option<&'a [&'b mut [f32]]>,
+) -> Option<&'a [&'b mut [f32]]> {
+    match source {
+        KeySource::External => sidechain.filter(|sc| !sc.is_empty()),
+        KeySource::Internal => None,
+    }
+}
+
+/// SC listen: replace the output with the key. A mono key feeds every
+/// channel, a stereo key only L/R of a 5.1 bus; an unconnected sidechain
+/// monitors as silence.
+#[cfg(feature = "plugin")]
+fn monitor_key(buffer: &mut Buffer, sidechain: Option<&[&mut [f32]]>) {
+    for (ch, out) in buffer.as_slice().iter_mut().enumerate() {
+        let key = sidechain.and_then(|sc| match sc {
+            [mono] => Some(mono),
+            _ => sc.get(ch),
+        });
+        match key {
+            Some(key) => out.copy_from_slice(&key[..out.len()]),
+            None => out.fill(0.0),
+        }
+    }
+}
+
+/// Whether the analyzer sums the channels into one trace or shows left and
+/// right separately.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+pub enum AnalyzerChannels {
+    #[name = "Sum"]
+    Sum,
+    #[name = "Split L/R"]
+    Split,
+}
+
+/// Editor skin. Lives here rather than in the GUI-gated modules because it is
+/// persisted with the plugin state (see `BusChannelStripParams::editor_skin`)
+/// and sessions must round-trip it even in headless builds.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum EditorSkin {
+    #[default]
+    Dark,
+    Light,
+    /// Gray-blue steel faceplates and cream legends, after the hardware racks.
+    Classic,
+}
+
+/// Editor language. Persisted next to the skin for the same reason; the
+/// string tables live in `i18n.rs`.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum EditorLanguage {
+    #[default]
+    English,
+    Spanish,
+}
+
+/// How often the audio thread publishes meter and analyzer updates for the
+/// editor. Persisted next to the skin; see `spectral::MeterClock`.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum MeterRate {
+    Hz15,
+    Hz30,
+    #[default]
+    Hz60,
+}
+
+#[cfg(feature = "plugin")]
+impl MeterRate {
+    pub fn hz(self) -> u32 {
+        match self {
+            MeterRate::Hz15 => 15,
+            MeterRate::Hz30 => 30,
+            MeterRate::Hz60 => 60,
+        }
+    }
+}
+
+/// How quickly the editor's level meters follow the signal.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum MeterBallistics {
+    Fast,
+    #[default]
+    Normal,
+    Slow,
+}
+
+#[cfg(feature = "plugin")]
+impl MeterBallistics {
+    /// Per-frame smoothing of a displayed level (0 = frozen, 1 = raw).
+    pub fn smoothing(self) -> f32 {
+        match self {
+            MeterBallistics::Fast => 0.6,
+            MeterBallistics::Normal => 0.25,
+            MeterBallistics::Slow => 0.08,
+        }
+    }
+}
+
+/// Editor preferences that aren't parameters, persisted together as one
+/// JSON blob (see `BusChannelStripParams::editor_settings`) so the host's
+/// automation list stays clean. The skin, language and meter rate predate
+/// it and keep their own keys, so older sessions load unchanged.
+/// `serde(default)` fills in any field a session was saved without.
+#[cfg(feature = "plugin")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorSettings {
+    /// Chassis zoom in percent: 75, 100, 125, 150 or 200.
+    pub zoom: u8,
+    /// Draw the spectrum analyzers.
+    pub analyzer: bool,
+    pub meter_ballistics: MeterBallistics,
+    /// Calibration of the level meters; see meter_scale.rs.
+    pub meter_scale: meter_scale::MeterScale,
+    /// Hover tooltips on parameter controls.
+    pub tooltips: bool,
+    /// Level A/B switches on integrated loudness; see snapshots.rs.
+    pub ab_match_loudness: bool,
+    /// Loudness-target assistant goal in whole LUFS; see loudness_target.rs.
+    pub loudness_target: i8,
+    /// Write the assistant's suggestion into Punch as soon as a learn pass
+    /// finishes.
+    pub loudness_auto_apply: bool,
+}
+
+#[cfg(feature = "plugin")]
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            zoom: 100,
+            analyzer: true,
+            meter_ballistics: MeterBallistics::default(),
+            meter_scale: meter_scale::MeterScale::default(),
+            tooltips: true,
+            ab_match_loudness: false,
+            loudness_target: -14,
+            loudness_auto_apply: false,
+        }
+    }
+}
+
+/// Work the editor and the audio thread hand to nih-plug's background
+/// thread.
+#[cfg(feature = "plugin")]
+#[derive(Debug)]
+pub enum BackgroundTask {
+    /// Preset scan / save / delete; see presets.rs.
+    Preset(presets::PresetTask),
+    /// Decode a WAV file and install it as the spectrum-match reference.
+    LoadMatchReference(std::path::PathBuf),
+    /// Drain the analyzer ring and publish spectra; see analyzer.rs.
+    AnalyzeSpectrum,
+    /// Design the API5500's linear-phase kernel for the posted settings.
+    #[cfg(feature = "api5500")]
+    DesignEqFir,
+    /// Print the Pultec's tube curve for the posted drive; see print.rs.
+    #[cfg(feature = "pultec")]
+    PrintTube,
+    /// Probe the transformer's harmonics at its current settings; see
+    /// harmonics.rs.
+    #[cfg(feature = "transformer")]
+    ProbeTransformer,
+    /// Render the EQ modules' static response and write it to a file; see
+    /// response.rs.
+    #[cfg(any(feature = "api5500", feature = "pultec"))]
+    ExportResponse(std::path::PathBuf, response::ResponseFormat),
+}
+
+#[cfg(feature = "plugin")]
+impl From<presets::PresetTask> for BackgroundTask {
+    fn from(task: presets::PresetTask) -> Self {
+        Self::Preset(task)
+    }
+}
+
 // This is a shortened version of the gain example with most comments removed, check out
 // https://github.com/robbert-vdh/nih-plug/blob/master/plugins/examples/gain/src/lib.rs to get
 // started
 
+#[cfg(feature = "plugin")]
 struct BusChannelStrip {
     params: Arc<BusChannelStripParams>,
-    /// API 5500–style input EQ module
-    #[cfg(feature = "api5500")]
-    eq_api5500: Api5500,
-    /// ButterComp2 compressor module
-    #[cfg(feature = "buttercomp2")]
-    compressor: ButterComp2,
-    /// 1176-style FET compressor — pure Rust, no FFI
-    #[cfg(feature = "buttercomp2")]
-    fet_compressor: FetCompressor,
-    /// VCA bus compressor — SSL G-Bus style, pure Rust, no FFI
-    #[cfg(feature = "buttercomp2")]
-    vca_compressor: VcaCompressor,
-    /// Optical compressor — LA-2A style, pure Rust, no FFI
-    #[cfg(feature = "buttercomp2")]
-    optical_compressor: OpticalCompressor,
-    /// Pultec-style EQ module
-    #[cfg(feature = "pultec")]
-    pultec: PultecEQ,
-    /// Dynamic EQ module
-    #[cfg(feature = "dynamic_eq")]
-    dynamic_eq: DynamicEQ,
-    /// Transformer coloration module
-    #[cfg(feature = "transformer")]
-    transformer: TransformerModule,
-    /// Punch module (Clipper + Transient Shaper)
-    #[cfg(feature = "punch")]
-    punch: PunchModule,
-    /// Haas psychoacoustic stereo widener
-    #[cfg(feature = "haas")]
-    haas: HaasModule,
+    /// Host sample rate, set in `initialize()`.
+    sample_rate: f32,
+    /// The slot modules the `process_module_*` helpers run.
+    chain: Box<chain::Chain>,
+    /// A second set of slot modules: the outgoing order's while a reorder
+    /// crossfades, idle otherwise; see reorder.rs.
+    spare_chain: Box<chain::Chain>,
     /// Sheen — pinned master-end "polish coat". Always last in the chain
     /// (post-Punch, pre-master-gain). Not user-reorderable; not in
     /// `module_order_*`. Default-on at factory tonality (see SHEEN_MODULE_SPEC.md).
     #[cfg(feature = "sheen")]
     sheen: SheenModule,
-
-    /// Buffers for module reordering
-    temp_buffer_1: Vec<Vec<f32>>,
-    temp_buffer_2: Vec<Vec<f32>>,
+    /// Input conditioning — pinned ahead of slot 1, the counterpart of
+    /// Sheen at the head of the chain. Default-off.
+    #[cfg(feature = "conditioning")]
+    input_conditioning: InputConditioning,
 
     /// Spectrum data shared lock-free with the GUI thread.
     spectrum_data: Arc<spectral::SpectrumData>,
 
-    /// Pre-allocated FFT ring buffer — no audio-thread allocation.
-    #[cfg(feature = "dynamic_eq")]
-    fft_ring: Vec<f32>,
-    #[cfg(feature = "dynamic_eq")]
-    fft_ring_pos: usize,
-    #[cfg(feature = "dynamic_eq")]
-    fft_engine: Option<Arc<dyn realfft::RealToComplex<f32>>>,
-    #[cfg(feature = "dynamic_eq")]
-    fft_input: Vec<f32>,
-    #[cfg(feature = "dynamic_eq")]
-    fft_output: Vec<realfft::num_complex::Complex<f32>>,
-    #[cfg(feature = "dynamic_eq")]
-    fft_scratch: Vec<realfft::num_complex::Complex<f32>>,
-    #[cfg(feature = "dynamic_eq")]
-    fft_window: Vec<f32>,
-    #[cfg(feature = "dynamic_eq")]
-    fft_magnitude_smooth: Vec<f32>,
-
-    // ── Sidechain masking analysis (Strategy A — one-shot, UI-triggered) ──────
-    /// Circular ring buffer for the sidechain mono mix-down.
-    #[cfg(feature = "dynamic_eq")]
-    sc_ring: Vec<f32>,
-    #[cfg(feature = "dynamic_eq")]
-    sc_ring_pos: usize,
-    /// Windowed sidechain snapshot for FFT (pre-allocated in initialize()).
-    #[cfg(feature = "dynamic_eq")]
-    sc_fft_input: Vec<f32>,
-    /// Sidechain FFT output (pre-allocated, same size as fft_output).
-    #[cfg(feature = "dynamic_eq")]
-    sc_fft_output: Vec<realfft::num_complex::Complex<f32>>,
-    /// Sample rate cached from initialize() for FFT bin → Hz conversion.
-    #[cfg(feature = "dynamic_eq")]
-    sample_rate: f32,
-    /// GUI → audio: GUI sets true to request an analysis on the next FFT frame.
+    /// Tapped samples for the analyzer worker, which runs the FFTs on the
+    /// background thread; see analyzer.rs. The sidechain rides along for
+    /// the masking analysis.
+    analyzer_ring: Arc<spectral::AnalyzerRing>,
+    /// Samples pushed since the last analysis task was queued.
+    analyzer_pending: usize,
+    /// Audio ↔ background state for the API5500's linear-phase FIR.
+    #[cfg(feature = "api5500")]
+    eq_fir: Arc<api5500::EqFirShared>,
+    /// Audio → background print requests for the Pultec's tube curve, the
+    /// worker that prints them, and the audio side's view of the result.
+    #[cfg(feature = "pultec")]
+    pultec_print: Arc<print::PrintShared>,
+    #[cfg(feature = "pultec")]
+    pultec_print_worker: Arc<Mutex<print::PrintWorker>>,
+    #[cfg(feature = "pultec")]
+    pultec_print_curve: print::CurveReader,
+    /// `process()` time against each buffer's real-time budget; decides
+    /// when the print swaps in.
+    #[cfg(feature = "pultec")]
+    load_monitor: print::LoadMonitor,
+    /// audio → GUI: some of the output currently comes from a print.
+    print_active: Arc<std::sync::atomic::AtomicBool>,
+    /// audio → GUI: the Classic compressor's C++ core couldn't be created
+    /// and the model passes audio through.
+    comp_degraded: Arc<std::sync::atomic::AtomicBool>,
+    /// Last stage before the host: NaN/Inf guard, DC blocker, +6 dBFS clip.
+    safety: safety::SafetyStage,
+    /// Output dither, after the safety stage.
+    dither: dither::Dither,
+    /// audio → GUI: the safety stage caught a NaN/Inf in the last second.
+    safety_fault: Arc<std::sync::atomic::AtomicBool>,
+    /// GUI → audio: `MeterRate` in Hz, mirrored from `params.meter_rate`.
+    meter_rate_hz: Arc<std::sync::atomic::AtomicU32>,
+    /// OSC listener → editor: resolved parameter changes.
+    #[cfg(feature = "osc")]
+    osc_inbox: Arc<osc::OscInbox>,
+    /// Started on first `initialize()`; stops when the plugin is dropped.
+    #[cfg(feature = "osc")]
+    osc_listener: Option<osc::OscListener>,
+    /// Decimates meter and analyzer publishing to `meter_rate_hz`.
+    meter_clock: spectral::MeterClock,
+    /// GUI → analyzer worker: GUI sets true to request an analysis on the next FFT frame.
     analysis_requested: Arc<std::sync::atomic::AtomicBool>,
-    /// audio → GUI: results of the last masking analysis.
+    /// analyzer worker → GUI: results of the last masking analysis.
     analysis_result: Arc<spectral::AnalysisResult>,
     /// audio → GUI: per-band gain reduction for the DynEQ spectrum display.
     gr_data: Arc<spectral::GainReductionData>,
 
+    /// audio → GUI: smoothed output RMS for A/B gain matching.
+    output_level: Arc<spectral::OutputLevelData>,
+    /// Smoothed mean-square of the output, feeding `output_level`.
+    output_mean_square: f32,
+    /// Latency last sent to the host, in samples.
+    reported_latency: u32,
+    /// Re-blocks host buffers when `block_size` isn't `Host`.
+    block_scheduler: block::BlockScheduler,
+    /// The scheduler's share of the reported latency.
+    block_latency: u32,
+    /// Holds the delta-listen module's input, then its difference.
+    delta_tap: delta::DeltaTap,
+    /// Fades the chain out and back in around a module reorder.
+    order_fade: reorder::OrderFade,
+    /// Gain envelope fired by `duck_note` note-ons.
+    duck: duck::DuckEnvelope,
+    /// The envelope over this block, rendered once so every pass through
+    /// the chain applies the same gains; `ducking` is false while it idles.
+    duck_gains: Vec<f32>,
+    ducking: bool,
+    /// False while the outgoing order runs during a reorder, or a surround
+    /// group behind the front pair: those passes feed no meter, tap or
+    /// readout.
+    metered: bool,
+    /// The linking group the chain is running on; `Front` on a stereo bus.
+    group: surround::LinkGroup,
+    /// The other groups of a 5.1 bus; `None` on the stereo layouts. See
+    /// surround.rs.
+    surround: Option<Box<surround::Surround>>,
+    /// Holds the key-listen detector's filtered key until the block ends.
+    key_tap: delta::DeltaTap,
+    /// The compressor's key after its key EQ, one block per channel.
+    #[cfg(feature = "buttercomp2")]
+    comp_key_scratch: [Vec<f32>; 2],
+    /// Band key listen's detector signal for one block.
+    #[cfg(feature = "dynamic_eq")]
+    dyneq_listen: Vec<f32>,
+    /// Spots loop seams and restarts for `global_transport_reset`.
+    transport_watch: transport::TransportWatch,
+    /// audio → GUI: transformer saturation activity for the drive meter.
+    transformer_sat: Arc<spectral::SaturationMeterData>,
+    /// audio → GUI: goniometer points and correlation for the master section.
+    scope_data: Arc<spectral::StereoScopeData>,
+    /// Smoothed L·R, L² and R² sums feeding the correlation meter.
+    scope_lr: f32,
+    scope_ll: f32,
+    scope_rr: f32,
+    /// Samples left until the next goniometer point is taken.
+    scope_countdown: usize,
+    /// Samples per goniometer point at the current sample rate.
+    scope_decimation: usize,
+    /// audio → GUI: scrolling compressor / DynEQ gain-reduction history.
+    /// The editor's half; only the GUI locks it.
+    gr_history: Arc<Mutex<spectral::GrHistoryData>>,
+    gr_history_tx: spectral::GrHistoryProducer,
+    /// Folds per-buffer GR readings into columns for `gr_history_tx`.
+    gr_history_acc: spectral::GrHistoryAccumulator,
+    /// Gain reduction (dB) reported by the compressor / DynEQ this buffer;
+    /// zeroed at the top of `process()` so a bypassed module reads 0.
+    comp_gr_db: f32,
+    dyneq_gr_db: f32,
+    /// The compressor's reduction per channel, for the punch link.
+    comp_channel_gr_db: [f32; 2],
+    /// audio → GUI: Punch clipper gain reduction (fraction of the peak
+    /// removed, 0..=1) for the metering bridge.
+    punch_clip: Arc<spectral::SaturationMeterData>,
+    /// BS.1770 meter on the final output (post master gain).
+    loudness: loudness::LoudnessMeter,
+    /// audio → GUI: momentary / short-term / integrated LUFS.
+    loudness_data: Arc<spectral::LoudnessData>,
+    /// Loudness-target learn pass at Punch's input and the final output.
+    #[cfg(feature = "punch")]
+    loudness_learn: loudness_target::LoudnessLearn,
+    /// GUI ↔ audio: learn-pass handshake and readings.
+    #[cfg(feature = "punch")]
+    loudness_learn_data: Arc<spectral::LoudnessLearnData>,
+    /// Crest factor of the strip input and of the final output; the output
+    /// meter also supplies PSR / PLR / spread.
+    input_dynamics: dynamics::DynamicsMeter,
+    output_dynamics: dynamics::DynamicsMeter,
+    /// audio → GUI: dynamics statistics for the metering bridge.
+    dynamics_data: Arc<spectral::DynamicsData>,
+    /// audio → GUI: how often each nonlinear stage engages.
+    engagement_data: Arc<spectral::EngagementData>,
+    /// Smoothed engagement shares behind `engagement_data`.
+    engagement_share: [f32; spectral::ENGAGEMENT_STAGES],
+    /// Transformer THD / even-harmonic share, probed on the background
+    /// thread.
+    harmonic_data: Arc<spectral::HarmonicData>,
+    /// background → GUI: outcome of the last EQ response export.
+    response_export: Arc<spectral::ResponseExportData>,
+    /// Settings the last queued transformer probe ran with.
+    #[cfg(feature = "transformer")]
+    transformer_probed: Option<harmonics::ProbeSettings>,
+    /// audio → GUI: RMS and peak between stages for the signal-flow strip.
+    stage_levels: Arc<spectral::StageLevelData>,
+    /// Highest sample per stage since the last meter publish (linear).
+    stage_peaks: [f32; spectral::STAGE_LEVELS],
+    /// Spectrum-match capture of the chain input and sidechain.
+    match_capture: matching::SpectrumCapture,
+    /// GUI ↔ audio ↔ background: match levels and capture handshake.
+    match_data: Arc<spectral::MatchData>,
+
+    /// User preset bank, filled by `PresetTask`s on the background thread
+    /// and read by the editor's preset browser.
+    preset_library: Arc<presets::PresetLibrary>,
+
     /// Smoothed auto-gain correction factor (linear, 1.0 = unity).
     /// Updated per buffer; reset to 1.0 when auto-gain is disabled.
-    auto_gain_correction: f32,
+    /// Per channel; the two only differ in dual mono.
+    auto_gain_correction: [f32; 2],
 
     /// GUI state
     #[cfg(feature = "gui")]
     editor_state: Arc<ViziaState>,
 }
 
+#[cfg(feature = "plugin")]
 #[derive(Params)]
 pub struct BusChannelStripParams {
     /// The parameter's ID is used to identify the parameter in the wrapped plugin API. As long as
@@ -231,9 +970,87 @@ pub struct BusChannelStripParams {
     #[id = "gain"]
     pub gain: FloatParam,
 
+    /// Master Analog Amount: every coloration stage at once, 0–150 %; see
+    /// `analog`.
+    #[id = "global_analog_amount"]
+    pub global_analog_amount: FloatParam,
+
+    /// Stereo, or two independent mono strips; see `ChannelMode`.
+    #[id = "global_channel_mode"]
+    pub global_channel_mode: EnumParam<ChannelMode>,
+
+    /// On a 5.1 bus, run the Transformer, Punch and Sheen on the LFE too.
+    /// Off by default; see surround.rs.
+    #[id = "global_lfe_saturation"]
+    pub global_lfe_saturation: BoolParam,
+
+    /// Internal processing block size. Changes latency, so it is not
+    /// automatable.
+    #[id = "block_size"]
+    pub block_size: EnumParam<BlockSize>,
+
+    /// Monitor the sidechain key instead of the strip's output.
+    #[id = "global_sc_listen"]
+    pub global_sc_listen: BoolParam,
+
+    /// Monitor what one module changes (its output minus its input).
+    #[id = "global_delta_listen"]
+    pub global_delta_listen: EnumParam<DeltaListen>,
+
+    /// Monitor what one detector hears through its key EQ.
+    #[id = "global_key_listen"]
+    pub global_key_listen: EnumParam<KeyListen>,
+
+    /// Output safety stage (NaN/Inf guard, DC blocker, +6 dBFS clip).
+    /// On by default; not automatable, so a session can't switch it off
+    /// mid-playback.
+    #[id = "global_safety"]
+    pub global_safety: BoolParam,
+
+    /// Clear compressor, DynEQ and transformer envelopes (and the analyzer
+    /// averages) when playback starts or jumps, e.g. at a loop seam.
+    #[id = "global_transport_reset"]
+    pub global_transport_reset: BoolParam,
+
+    /// TPDF dither and quantisation to 16 or 24 bits after the safety
+    /// stage, for printing stems from the strip; see dither.rs.
+    #[id = "global_dither"]
+    pub global_dither: EnumParam<dither::DitherDepth>,
+    /// Shape the dither noise toward Nyquist.
+    #[id = "global_dither_shaping"]
+    pub global_dither_shaping: BoolParam,
+
+    /// MIDI duck: a note-on matching `duck_channel` and `duck_note` fires a
+    /// gain envelope at `duck_point`, for hosts that can't route an audio
+    /// sidechain. See duck.rs.
+    #[id = "duck_enable"]
+    pub duck_enable: BoolParam,
+    /// 1–16, or 0 for any channel.
+    #[id = "duck_channel"]
+    pub duck_channel: IntParam,
+    #[id = "duck_note"]
+    pub duck_note: IntParam,
+    #[id = "duck_depth"]
+    pub duck_depth: FloatParam,
+    #[id = "duck_attack"]
+    pub duck_attack: FloatParam,
+    #[id = "duck_hold"]
+    pub duck_hold: FloatParam,
+    #[id = "duck_release"]
+    pub duck_release: FloatParam,
+    #[id = "duck_point"]
+    pub duck_point: EnumParam<duck::DuckPoint>,
+
     // API5500 EQ Parameters
     #[id = "eq_bypass"]
     pub eq_bypass: BoolParam,
+    /// Linear-phase FIR instead of the biquads. Adds latency, so it is not
+    /// automatable.
+    #[id = "eq_linear_phase"]
+    pub eq_linear_phase: BoolParam,
+    /// Clean filters, or vintage band interaction, phase lag and noise.
+    #[id = "eq_character"]
+    pub eq_character: EnumParam<EqCharacter>,
 
     // Low Frequency (LF) - Shelving
     #[id = "lf_freq"]
@@ -271,6 +1088,19 @@ pub struct BusChannelStripParams {
     #[id = "hf_gain"]
     pub hf_gain: FloatParam,
 
+    /// Per-band L/R gain offsets (±2 dB; + favours the left channel), in
+    /// the dual-mono channel mode only.
+    #[id = "eq_lf_offset"]
+    pub eq_lf_offset: FloatParam,
+    #[id = "eq_lmf_offset"]
+    pub eq_lmf_offset: FloatParam,
+    #[id = "eq_mf_offset"]
+    pub eq_mf_offset: FloatParam,
+    #[id = "eq_hmf_offset"]
+    pub eq_hmf_offset: FloatParam,
+    #[id = "eq_hf_offset"]
+    pub eq_hf_offset: FloatParam,
+
     // ButterComp2 Compressor Parameters
     #[id = "comp_bypass"]
     pub comp_bypass: BoolParam,
@@ -280,6 +1110,10 @@ pub struct BusChannelStripParams {
     pub comp_output: FloatParam,
     #[id = "comp_dry_wet"]
     pub comp_dry_wet: FloatParam,
+    /// Classic model: compress and output reach the core unscaled, the full
+    /// Airwindows range; see comp_scale.rs.
+    #[id = "comp_faithful"]
+    pub comp_faithful: BoolParam,
 
     /// Model selector — always visible; switches the active control surface.
     #[cfg(feature = "buttercomp2")]
@@ -293,6 +1127,49 @@ pub struct BusChannelStripParams {
     #[id = "comp_sc_hp"]
     pub comp_sc_hp_freq: FloatParam,
 
+    /// Detector key for the VCA, Optical and FET models. The Classic core
+    /// is FFI with no detector tap and always listens to its input.
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_key"]
+    pub comp_key: EnumParam<KeySource>,
+
+    /// Key EQ for the VCA, Optical and FET detectors: a bell and LP after
+    /// the SC HP (see `key_eq.rs`). With it on, the Optical takes the SC HP
+    /// as well.
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_key_eq"]
+    pub comp_key_eq: BoolParam,
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_key_bell_freq"]
+    pub comp_key_bell_freq: FloatParam,
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_key_bell_gain"]
+    pub comp_key_bell_gain: FloatParam,
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_key_bell_q"]
+    pub comp_key_bell_q: FloatParam,
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_key_lp"]
+    pub comp_key_lp: FloatParam,
+    /// Transient protect for the VCA, Optical and FET models: how far the
+    /// key drops while Punch's transient detector sees an attack, so hits
+    /// keep their front edge under heavy compression. 0 = off.
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_transient_protect"]
+    pub comp_transient_protect: FloatParam,
+
+    /// Post-compressor per-channel trim, all models.
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_trim_l"]
+    pub comp_trim_l: FloatParam,
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_trim_r"]
+    pub comp_trim_r: FloatParam,
+    /// Cancel any L/R balance shift the compressor introduces; see balance.rs.
+    #[cfg(feature = "buttercomp2")]
+    #[id = "comp_auto_balance"]
+    pub comp_auto_balance: BoolParam,
+
     // VCA model parameters
     #[id = "comp_vca_thresh"]
     pub vca_thresh: FloatParam,
@@ -347,6 +1224,10 @@ pub struct BusChannelStripParams {
     pub pultec_lf_boost_bandwidth: FloatParam,
     #[id = "pultec_lf_cut_freq"]
     pub pultec_lf_cut_freq: FloatParam,
+    /// Atten follows the boost frequency, as on a stock EQP-1A with its one
+    /// LF selector; off, `pultec_lf_cut_freq` sets it on its own.
+    #[id = "pultec_lf_cut_link"]
+    pub pultec_lf_cut_link: BoolParam,
     #[id = "pultec_lf_cut_gain"]
     pub pultec_lf_cut_gain: FloatParam,
     #[id = "pultec_lf_cut_bw"]
@@ -363,12 +1244,37 @@ pub struct BusChannelStripParams {
     pub pultec_hf_cut_gain: FloatParam,
     #[id = "pultec_tube_drive"]
     pub pultec_tube_drive: FloatParam,
+    /// Let the strip swap in a printed tube stage (no oversampling) while
+    /// the audio thread is short of time. Off by default: the print aliases
+    /// at high drive.
+    #[id = "pultec_print"]
+    pub pultec_print: BoolParam,
+    /// ±2% component tolerance and slow drift (seeded, so renders repeat).
+    #[id = "pultec_variance"]
+    pub pultec_variance: BoolParam,
+    /// Anti-alias the tube stage with ADAA instead of oversampling.
+    #[id = "pultec_adaa"]
+    pub pultec_adaa: BoolParam,
+    /// Clean filters, or vintage section loading, phase lag and noise.
+    #[id = "pultec_character"]
+    pub pultec_character: EnumParam<EqCharacter>,
 
     #[cfg(feature = "dynamic_eq")]
     // Dynamic EQ Parameters
     #[id = "dyneq_bypass"]
     pub dyneq_bypass: BoolParam,
 
+    /// Detector key for all four bands.
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_key"]
+    pub dyneq_key: EnumParam<KeySource>,
+
+    /// Threshold/ratio per band, or a range and sensitivity the bands work
+    /// their threshold and ratio out from (see `dynamic_eq::RangeLaw`).
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_control_law"]
+    pub dyneq_control_law: EnumParam<ControlLaw>,
+
     #[cfg(feature = "dynamic_eq")]
     // Band 1 (Low) - 200Hz default
     #[id = "dyneq_band1_freq"]
@@ -404,6 +1310,35 @@ pub struct BusChannelStripParams {
     #[id = "dyneq_band1_solo"]
     pub dyneq_band1_solo: BoolParam,
 
+    /// Range and sensitivity, read under the Range/Sensitivity law only.
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band1_range"]
+    pub dyneq_band1_range: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band1_sensitivity"]
+    pub dyneq_band1_sensitivity: FloatParam,
+
+    /// Band key EQ: HP, bell and LP ahead of the detection filter (see
+    /// `key_eq.rs`). Off, the band detects as before.
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band1_key_eq"]
+    pub dyneq_band1_key_eq: BoolParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band1_key_hp"]
+    pub dyneq_band1_key_hp: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band1_key_bell_freq"]
+    pub dyneq_band1_key_bell_freq: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band1_key_bell_gain"]
+    pub dyneq_band1_key_bell_gain: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band1_key_bell_q"]
+    pub dyneq_band1_key_bell_q: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band1_key_lp"]
+    pub dyneq_band1_key_lp: FloatParam,
+
     #[cfg(feature = "dynamic_eq")]
     // Band 2 (Low-Mid) - 800Hz default
     #[id = "dyneq_band2_freq"]
@@ -438,6 +1373,31 @@ pub struct BusChannelStripParams {
     #[cfg(feature = "dynamic_eq")]
     #[id = "dyneq_band2_solo"]
     pub dyneq_band2_solo: BoolParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band2_range"]
+    pub dyneq_band2_range: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band2_sensitivity"]
+    pub dyneq_band2_sensitivity: FloatParam,
+
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band2_key_eq"]
+    pub dyneq_band2_key_eq: BoolParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band2_key_hp"]
+    pub dyneq_band2_key_hp: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band2_key_bell_freq"]
+    pub dyneq_band2_key_bell_freq: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band2_key_bell_gain"]
+    pub dyneq_band2_key_bell_gain: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band2_key_bell_q"]
+    pub dyneq_band2_key_bell_q: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band2_key_lp"]
+    pub dyneq_band2_key_lp: FloatParam,
 
     #[cfg(feature = "dynamic_eq")]
     // Band 3 (High-Mid) - 3kHz default
@@ -473,6 +1433,31 @@ pub struct BusChannelStripParams {
     #[cfg(feature = "dynamic_eq")]
     #[id = "dyneq_band3_solo"]
     pub dyneq_band3_solo: BoolParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band3_range"]
+    pub dyneq_band3_range: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band3_sensitivity"]
+    pub dyneq_band3_sensitivity: FloatParam,
+
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band3_key_eq"]
+    pub dyneq_band3_key_eq: BoolParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band3_key_hp"]
+    pub dyneq_band3_key_hp: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band3_key_bell_freq"]
+    pub dyneq_band3_key_bell_freq: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band3_key_bell_gain"]
+    pub dyneq_band3_key_bell_gain: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band3_key_bell_q"]
+    pub dyneq_band3_key_bell_q: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band3_key_lp"]
+    pub dyneq_band3_key_lp: FloatParam,
 
     #[cfg(feature = "dynamic_eq")]
     // Band 4 (High) - 8kHz default
@@ -508,6 +1493,31 @@ pub struct BusChannelStripParams {
     #[cfg(feature = "dynamic_eq")]
     #[id = "dyneq_band4_solo"]
     pub dyneq_band4_solo: BoolParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band4_range"]
+    pub dyneq_band4_range: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band4_sensitivity"]
+    pub dyneq_band4_sensitivity: FloatParam,
+
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band4_key_eq"]
+    pub dyneq_band4_key_eq: BoolParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band4_key_hp"]
+    pub dyneq_band4_key_hp: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band4_key_bell_freq"]
+    pub dyneq_band4_key_bell_freq: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band4_key_bell_gain"]
+    pub dyneq_band4_key_bell_gain: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band4_key_bell_q"]
+    pub dyneq_band4_key_bell_q: FloatParam,
+    #[cfg(feature = "dynamic_eq")]
+    #[id = "dyneq_band4_key_lp"]
+    pub dyneq_band4_key_lp: FloatParam,
 
     // Transformer Module Parameters
     #[id = "transformer_bypass"]
@@ -528,6 +1538,20 @@ pub struct BusChannelStripParams {
     pub transformer_high_response: FloatParam,
     #[id = "transformer_compression"]
     pub transformer_compression: FloatParam,
+    /// Core wear: narrower band, more even harmonics, modulation noise.
+    #[id = "transformer_age"]
+    pub transformer_age: FloatParam,
+    /// ±2% component tolerance and slow drift (seeded, so renders repeat).
+    #[id = "transformer_variance"]
+    pub transformer_variance: BoolParam,
+    /// Anti-alias the saturators with ADAA instead of oversampling.
+    #[id = "transformer_adaa"]
+    pub transformer_adaa: BoolParam,
+    /// Saturate mid and side, the side gently, so the image doesn't widen.
+    /// Not automatable: switching mid-stream moves filter state across
+    /// domains.
+    #[id = "transformer_mono_safe"]
+    pub transformer_mono_safe: BoolParam,
 
     // Punch Module Parameters (Clipper + Transient Shaper)
     #[cfg(feature = "punch")]
@@ -578,6 +1602,24 @@ pub struct BusChannelStripParams {
     /// the low end. 20 Hz = effectively off; 120–400 Hz suits drum submix.
     #[id = "punch_wet_hpf"]
     pub punch_wet_hpf_hz: FloatParam,
+    /// Tilt into the clipper, undone after it, so the lows don't do all
+    /// the clipping. 0 dB = off.
+    #[cfg(feature = "punch")]
+    #[id = "punch_tilt"]
+    pub punch_tilt: FloatParam,
+    #[cfg(feature = "punch")]
+    #[id = "punch_tilt_pivot"]
+    pub punch_tilt_pivot: FloatParam,
+    /// Time constant of the transient detectors' output smoothing (see
+    /// `PunchModule::set_smoothing`).
+    #[cfg(feature = "punch")]
+    #[id = "punch_smoothing"]
+    pub punch_smoothing: FloatParam,
+    /// Punch link: compressor gain reduction adds transient attack, up to
+    /// `punch_link::MAX_BOOST` at 100 %. 0 = off.
+    #[cfg(feature = "punch")]
+    #[id = "punch_link"]
+    pub punch_link: FloatParam,
 
     // ── Haas Module Parameters ──────────────────────────────────────────
     #[cfg(feature = "haas")]
@@ -645,6 +1687,32 @@ pub struct BusChannelStripParams {
     #[id = "sheen_width_bypass"]
     pub sheen_width_bypass: BoolParam,
 
+    // ── Input Conditioning Parameters ────────────────────────────────────
+    // Pinned ahead of slot 1: HPF, LPF, phase rotation and polarity. Off
+    // until switched in; the panel sits at the head of the rack.
+    #[cfg(feature = "conditioning")]
+    #[id = "input_bypass"]
+    pub input_bypass: BoolParam,
+    #[cfg(feature = "conditioning")]
+    #[id = "input_hpf_freq"]
+    pub input_hpf_freq: FloatParam,
+    #[cfg(feature = "conditioning")]
+    #[id = "input_hpf_slope"]
+    pub input_hpf_slope: EnumParam<Slope>,
+    #[cfg(feature = "conditioning")]
+    #[id = "input_lpf_freq"]
+    pub input_lpf_freq: FloatParam,
+    #[cfg(feature = "conditioning")]
+    #[id = "input_lpf_slope"]
+    pub input_lpf_slope: EnumParam<Slope>,
+    /// Phase rotation angle. 0° takes the rotator out of circuit.
+    #[cfg(feature = "conditioning")]
+    #[id = "input_rotation"]
+    pub input_rotation: FloatParam,
+    #[cfg(feature = "conditioning")]
+    #[id = "input_polarity"]
+    pub input_polarity: EnumParam<Polarity>,
+
     // Module Ordering Parameters
     #[id = "module_order_1"]
     pub module_order_1: EnumParam<ModuleType>,
@@ -678,75 +1746,161 @@ pub struct BusChannelStripParams {
     pub hide_punch: BoolParam,
     #[id = "hide_haas"]
     pub hide_haas: BoolParam,
+
+    // Spectrum analyzer display options. View preferences like the hide
+    // flags: non-automatable and left out of presets.
+    #[id = "analyzer_tap"]
+    pub analyzer_tap: EnumParam<AnalyzerTap>,
+    #[id = "analyzer_channels"]
+    pub analyzer_channels: EnumParam<AnalyzerChannels>,
+    #[id = "analyzer_window"]
+    pub analyzer_window: EnumParam<AnalyzerWindow>,
+    #[id = "analyzer_overlap"]
+    pub analyzer_overlap: EnumParam<AnalyzerOverlap>,
+    /// Exponential averaging time constant; 0 shows raw frames.
+    #[id = "analyzer_averaging"]
+    pub analyzer_averaging: FloatParam,
+    /// Lowest level the analyzer shows.
+    #[id = "analyzer_floor"]
+    pub analyzer_floor: FloatParam,
+
+    /// Active editor skin. Persisted (not a parameter) so the host never
+    /// sees it as automatable, but it still travels with the session.
+    #[persist = "editor-skin"]
+    pub editor_skin: RwLock<EditorSkin>,
+
+    /// Editor language, persisted like the skin.
+    #[persist = "editor-language"]
+    pub editor_language: RwLock<EditorLanguage>,
+
+    /// Meter refresh rate, persisted like the skin. The audio thread reads
+    /// the `meter_rate_hz` mirror instead of taking this lock.
+    #[persist = "meter-rate"]
+    pub meter_rate: RwLock<MeterRate>,
+
+    /// Zoom, analyzer visibility, meter ballistics and tooltips; see
+    /// `EditorSettings`.
+    #[persist = "editor-settings"]
+    pub editor_settings: RwLock<EditorSettings>,
+
+    /// Frozen analyzer curves, kept with the session so a before/after
+    /// comparison survives closing the project; see spectral.rs.
+    #[persist = "analyzer-snapshots"]
+    pub analyzer_snapshots: RwLock<spectral::SpectrumSnapshots>,
+
+    /// Whether this instance has had its first-instantiation preset; see
+    /// host_context.rs.
+    #[persist = "context-preset"]
+    pub context_preset: RwLock<host_context::ContextPreset>,
 }
 
+#[cfg(feature = "plugin")]
 impl Default for BusChannelStrip {
     fn default() -> Self {
+        #[cfg(feature = "pultec")]
+        let (print_worker, print_curve) = print::print_channel();
+        let (gr_history_tx, gr_history) = spectral::gr_history_channel();
         Self {
             params: Arc::new(BusChannelStripParams::default()),
-            #[cfg(feature = "api5500")]
-            eq_api5500: Api5500::new(44100.0), // default sample rate; will be overwritten in initialize()
-            #[cfg(feature = "buttercomp2")]
-            compressor: ButterComp2::new(44100.0), // default sample rate; will be overwritten in initialize()
-            #[cfg(feature = "buttercomp2")]
-            fet_compressor: FetCompressor::new(44100.0), // default sample rate; will be overwritten in initialize()
-            #[cfg(feature = "buttercomp2")]
-            vca_compressor: VcaCompressor::new(44100.0), // default sample rate; will be overwritten in initialize()
-            #[cfg(feature = "buttercomp2")]
-            optical_compressor: OpticalCompressor::new(44100.0), // default sample rate; will be overwritten in initialize()
-            #[cfg(feature = "pultec")]
-            pultec: PultecEQ::new(44100.0), // default sample rate; will be overwritten in initialize()
-            #[cfg(feature = "dynamic_eq")]
-            dynamic_eq: DynamicEQ::new(44100.0), // default sample rate; will be overwritten in initialize()
-            #[cfg(feature = "transformer")]
-            transformer: TransformerModule::new(44100.0), // default sample rate; will be overwritten in initialize()
-            #[cfg(feature = "punch")]
-            punch: PunchModule::new(44100.0), // default sample rate; will be overwritten in initialize()
-            #[cfg(feature = "haas")]
-            haas: HaasModule::new(44100.0), // default sample rate; will be overwritten in initialize()
+            sample_rate: 44100.0,
+            chain: Box::new(chain::Chain::new(44100.0)),
+            spare_chain: Box::new(chain::Chain::new(44100.0)),
             #[cfg(feature = "sheen")]
             sheen: SheenModule::new(44100.0), // default sample rate; will be overwritten in initialize()
-            temp_buffer_1: Vec::new(),
-            temp_buffer_2: Vec::new(),
+            #[cfg(feature = "conditioning")]
+            input_conditioning: InputConditioning::new(44100.0), // default sample rate; will be overwritten in initialize()
             spectrum_data: Arc::new(spectral::SpectrumData::new()),
-            #[cfg(feature = "dynamic_eq")]
-            fft_ring: Vec::new(),
-            #[cfg(feature = "dynamic_eq")]
-            fft_ring_pos: 0,
-            #[cfg(feature = "dynamic_eq")]
-            fft_engine: None,
-            #[cfg(feature = "dynamic_eq")]
-            fft_input: Vec::new(),
-            #[cfg(feature = "dynamic_eq")]
-            fft_output: Vec::new(),
-            #[cfg(feature = "dynamic_eq")]
-            fft_scratch: Vec::new(),
-            #[cfg(feature = "dynamic_eq")]
-            fft_window: Vec::new(),
-            #[cfg(feature = "dynamic_eq")]
-            fft_magnitude_smooth: Vec::new(),
-            #[cfg(feature = "dynamic_eq")]
-            sc_ring: Vec::new(),
-            #[cfg(feature = "dynamic_eq")]
-            sc_ring_pos: 0,
-            #[cfg(feature = "dynamic_eq")]
-            sc_fft_input: Vec::new(),
-            #[cfg(feature = "dynamic_eq")]
-            sc_fft_output: Vec::new(),
-            #[cfg(feature = "dynamic_eq")]
-            sample_rate: 44100.0,
+            analyzer_ring: Arc::new(spectral::AnalyzerRing::new()),
+            analyzer_pending: 0,
+            #[cfg(feature = "api5500")]
+            eq_fir: Arc::new(api5500::EqFirShared::new()),
+            #[cfg(feature = "pultec")]
+            pultec_print: Arc::new(print::PrintShared::new()),
+            #[cfg(feature = "pultec")]
+            pultec_print_worker: Arc::new(Mutex::new(print_worker)),
+            #[cfg(feature = "pultec")]
+            pultec_print_curve: print_curve,
+            #[cfg(feature = "pultec")]
+            load_monitor: print::LoadMonitor::default(),
+            print_active: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            comp_degraded: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            safety: safety::SafetyStage::new(44100.0),
+            dither: dither::Dither::new(),
+            safety_fault: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            meter_rate_hz: Arc::new(std::sync::atomic::AtomicU32::new(MeterRate::default().hz())),
+            #[cfg(feature = "osc")]
+            osc_inbox: Arc::new(osc::OscInbox::default()),
+            #[cfg(feature = "osc")]
+            osc_listener: None,
+            meter_clock: spectral::MeterClock::new(),
             analysis_requested: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             analysis_result: Arc::new(spectral::AnalysisResult::new()),
             gr_data: Arc::new(spectral::GainReductionData::new()),
-            auto_gain_correction: 1.0,
-            #[cfg(feature = "gui")]
-            editor_state: editor::default_state(),
-        }
+            output_level: Arc::new(spectral::OutputLevelData::new()),
+            output_mean_square: 0.0,
+            reported_latency: 0,
+            block_scheduler: block::BlockScheduler::new(),
+            block_latency: 0,
+            delta_tap: delta::DeltaTap::new(block::MAX_BLOCK, DELTA_MAX_LATENCY),
+            order_fade: reorder::OrderFade::new(block::MAX_BLOCK, 44100.0),
+            duck: duck::DuckEnvelope::new(44100.0),
+            duck_gains: vec![1.0; block::MAX_BLOCK],
+            ducking: false,
+            metered: true,
+            group: surround::LinkGroup::Front,
+            surround: None,
+            key_tap: delta::DeltaTap::new(block::MAX_BLOCK, 0),
+            #[cfg(feature = "buttercomp2")]
+            comp_key_scratch: [vec![0.0; block::MAX_BLOCK], vec![0.0; block::MAX_BLOCK]],
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_listen: vec![0.0; block::MAX_BLOCK],
+            transport_watch: transport::TransportWatch::default(),
+            transformer_sat: Arc::new(spectral::SaturationMeterData::new()),
+            scope_data: Arc::new(spectral::StereoScopeData::new()),
+            scope_lr: 0.0,
+            scope_ll: 0.0,
+            scope_rr: 0.0,
+            scope_countdown: 0,
+            scope_decimation: 8,
+            gr_history: Arc::new(Mutex::new(gr_history)),
+            gr_history_tx,
+            gr_history_acc: spectral::GrHistoryAccumulator::new(44100.0),
+            comp_gr_db: 0.0,
+            comp_channel_gr_db: [0.0; 2],
+            dyneq_gr_db: 0.0,
+            punch_clip: Arc::new(spectral::SaturationMeterData::new()),
+            loudness: loudness::LoudnessMeter::new(44100.0),
+            loudness_data: Arc::new(spectral::LoudnessData::new()),
+            #[cfg(feature = "punch")]
+            loudness_learn: loudness_target::LoudnessLearn::new(44100.0),
+            #[cfg(feature = "punch")]
+            loudness_learn_data: Arc::new(spectral::LoudnessLearnData::new()),
+            input_dynamics: dynamics::DynamicsMeter::new(44100.0),
+            output_dynamics: dynamics::DynamicsMeter::new(44100.0),
+            dynamics_data: Arc::new(spectral::DynamicsData::new()),
+            engagement_data: Arc::new(spectral::EngagementData::new()),
+            engagement_share: [0.0; spectral::ENGAGEMENT_STAGES],
+            harmonic_data: Arc::new(spectral::HarmonicData::new()),
+            response_export: Arc::new(spectral::ResponseExportData::new()),
+            #[cfg(feature = "transformer")]
+            transformer_probed: None,
+            match_capture: matching::SpectrumCapture::new(),
+            match_data: Arc::new(spectral::MatchData::new()),
+            stage_levels: Arc::new(spectral::StageLevelData::new()),
+            stage_peaks: [0.0; spectral::STAGE_LEVELS],
+            preset_library: Arc::new(presets::PresetLibrary::new()),
+            auto_gain_correction: [1.0; 2],
+            #[cfg(feature = "gui")]
+            editor_state: editor::default_state(),
+        }
     }
 }
 
+#[cfg(feature = "plugin")]
 impl Default for BusChannelStripParams {
     fn default() -> Self {
+        // Read by the Classic knobs' formatters, written by `comp_faithful`.
+        let comp_faithful_flag = Arc::new(std::sync::atomic::AtomicBool::new(false));
         Self {
             global_bypass: BoolParam::new("Bypass", false),
             global_auto_gain: BoolParam::new("Auto Gain", false),
@@ -774,9 +1928,101 @@ impl Default for BusChannelStripParams {
             // `.with_step_size(0.1)` function to get internal rounding.
             .with_value_to_string(formatters::v2s_f32_gain_to_db(2))
             .with_string_to_value(formatters::s2v_f32_gain_to_db()),
+            global_analog_amount: FloatParam::new(
+                "Analog Amount",
+                1.0,
+                FloatRange::Linear {
+                    min: 0.0,
+                    max: analog::MAX_AMOUNT,
+                },
+            )
+            .with_step_size(0.01)
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+            global_channel_mode: EnumParam::new("Channel Mode", ChannelMode::Stereo),
+            global_lfe_saturation: BoolParam::new("LFE Saturation", false),
+            block_size: EnumParam::new("Processing Block", BlockSize::Host)
+                .non_automatable()
+                .rarely_automated(),
+            global_sc_listen: BoolParam::new("SC Listen", false),
+            global_delta_listen: EnumParam::new("Delta Listen", DeltaListen::Off),
+            global_key_listen: EnumParam::new("Key Listen", KeyListen::Off),
+            global_safety: BoolParam::new("Safety", true).non_automatable().rarely_automated(),
+            global_transport_reset: BoolParam::new("Transport Reset", true)
+                .non_automatable()
+                .rarely_automated(),
+            global_dither: EnumParam::new("Dither", dither::DitherDepth::Off)
+                .non_automatable()
+                .rarely_automated(),
+            global_dither_shaping: BoolParam::new("Dither Shaping", false)
+                .non_automatable()
+                .rarely_automated(),
+
+            // MIDI duck. Note 36 is C1, the GM kick.
+            duck_enable: BoolParam::new("MIDI Duck", false),
+            duck_channel: IntParam::new("Duck Channel", 0, IntRange::Linear { min: 0, max: 16 })
+                .with_value_to_string(Arc::new(|ch| match ch {
+                    0 => "Any".to_owned(),
+                    ch => ch.to_string(),
+                }))
+                .with_string_to_value(Arc::new(|s| match s.trim() {
+                    s if s.eq_ignore_ascii_case("any") => Some(0),
+                    s => s.parse().ok(),
+                }))
+                .rarely_automated(),
+            duck_note: IntParam::new("Duck Note", 36, IntRange::Linear { min: 0, max: 127 })
+                .with_value_to_string(formatters::v2s_i32_note_formatter())
+                .with_string_to_value(formatters::s2v_i32_note_formatter())
+                .rarely_automated(),
+            duck_depth: FloatParam::new(
+                "Duck Depth",
+                -12.0,
+                FloatRange::Linear { min: -48.0, max: 0.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.1)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            duck_attack: FloatParam::new(
+                "Duck Attack",
+                5.0,
+                FloatRange::Skewed {
+                    min: 0.1,
+                    max: 100.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            duck_hold: FloatParam::new(
+                "Duck Hold",
+                50.0,
+                FloatRange::Skewed {
+                    min: 0.0,
+                    max: 1000.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+            duck_release: FloatParam::new(
+                "Duck Release",
+                250.0,
+                FloatRange::Skewed {
+                    min: 10.0,
+                    max: 2000.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+            duck_point: EnumParam::new("Duck Point", duck::DuckPoint::Input).rarely_automated(),
 
             // API5500 EQ Parameters
             eq_bypass: BoolParam::new("EQ Bypass", true),
+            eq_linear_phase: BoolParam::new("EQ Linear Phase", false)
+                .non_automatable()
+                .rarely_automated(),
+            eq_character: EnumParam::new("EQ Character", EqCharacter::Clean).rarely_automated(),
 
             // Low Frequency (LF) - Shelving at 100Hz
             lf_freq: FloatParam::new(
@@ -921,24 +2167,77 @@ impl Default for BusChannelStripParams {
             .with_step_size(1.0)
             .with_value_to_string(formatters::v2s_f32_rounded(0)),
 
+            // L/R offsets, dual-mono channel mode only
+            eq_lf_offset: FloatParam::new(
+                "LF L/R Offset",
+                0.0,
+                FloatRange::Linear { min: -2.0, max: 2.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.1)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
+            eq_lmf_offset: FloatParam::new(
+                "LMF L/R Offset",
+                0.0,
+                FloatRange::Linear { min: -2.0, max: 2.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.1)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
+            eq_mf_offset: FloatParam::new(
+                "MF L/R Offset",
+                0.0,
+                FloatRange::Linear { min: -2.0, max: 2.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.1)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
+            eq_hmf_offset: FloatParam::new(
+                "HMF L/R Offset",
+                0.0,
+                FloatRange::Linear { min: -2.0, max: 2.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.1)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
+            eq_hf_offset: FloatParam::new(
+                "HF L/R Offset",
+                0.0,
+                FloatRange::Linear { min: -2.0, max: 2.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.1)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+
             // ButterComp2 Compressor Parameters
             comp_bypass: BoolParam::new("Comp Bypass", true),
 
+            // Both stay 0–1 so sessions keep their values; `comp_scale`
+            // shows them as the dB the classic core actually applies.
             comp_compress: FloatParam::new(
                 "Compress",
                 0.0,
                 FloatRange::Linear { min: 0.0, max: 1.0 },
             )
-            .with_unit("")
-            .with_step_size(0.01),
+            .with_unit(" dB GR")
+            .with_step_size(0.01)
+            .with_value_to_string(comp_scale::v2s_compress(comp_faithful_flag.clone()))
+            .with_string_to_value(comp_scale::s2v_compress(comp_faithful_flag.clone())),
 
             comp_output: FloatParam::new(
                 "Comp Output",
-                0.5, // 0.5 = unity gain
+                comp_scale::UNITY_OUTPUT, // 0 dB
                 FloatRange::Linear { min: 0.0, max: 1.0 },
             )
-            .with_unit("")
-            .with_step_size(0.01),
+            .with_unit(" dB")
+            // Fine enough that unity is on the grid.
+            .with_step_size(0.005)
+            .with_value_to_string(comp_scale::v2s_output(comp_faithful_flag.clone()))
+            .with_string_to_value(comp_scale::s2v_output(comp_faithful_flag.clone())),
 
             comp_dry_wet: FloatParam::new(
                 "Comp Mix",
@@ -948,6 +2247,11 @@ impl Default for BusChannelStripParams {
             .with_unit("")
             .with_step_size(0.01),
 
+            // Off keeps the safe mapping older sessions were made with.
+            comp_faithful: BoolParam::new("Comp Faithful", false)
+                .with_callback(comp_scale::faithful_callback(comp_faithful_flag))
+                .rarely_automated(),
+
             #[cfg(feature = "buttercomp2")]
             comp_model: EnumParam::<ButterComp2Model>::new("Model", ButterComp2Model::default()),
 
@@ -963,7 +2267,92 @@ impl Default for BusChannelStripParams {
                 },
             )
             .with_unit(" Hz")
-            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "buttercomp2")]
+            comp_key: EnumParam::new("Comp Key", KeySource::Internal).rarely_automated(),
+            // The key EQ's HP is `comp_sc_hp`; 20 kHz = LP off.
+            #[cfg(feature = "buttercomp2")]
+            comp_key_eq: BoolParam::new("Comp Key EQ", false).rarely_automated(),
+            #[cfg(feature = "buttercomp2")]
+            comp_key_bell_freq: FloatParam::new(
+                "Comp Key Bell",
+                1000.0,
+                FloatRange::Skewed {
+                    min: 100.0,
+                    max: 12000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "buttercomp2")]
+            comp_key_bell_gain: FloatParam::new(
+                "Comp Key Bell Gain",
+                0.0,
+                FloatRange::Linear { min: -18.0, max: 18.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5)
+            .with_value_to_string(formatters::v2s_f32_rounded(1))
+            .rarely_automated(),
+            #[cfg(feature = "buttercomp2")]
+            comp_key_bell_q: FloatParam::new(
+                "Comp Key Bell Q",
+                1.0,
+                FloatRange::Skewed {
+                    min: 0.3,
+                    max: 8.0,
+                    factor: FloatRange::skew_factor(0.5),
+                },
+            )
+            .with_step_size(0.01)
+            .rarely_automated(),
+            #[cfg(feature = "buttercomp2")]
+            comp_key_lp: FloatParam::new(
+                "Comp Key LP",
+                20000.0,
+                FloatRange::Skewed {
+                    min: 1000.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(1))
+            .rarely_automated(),
+            #[cfg(feature = "buttercomp2")]
+            comp_transient_protect: FloatParam::new(
+                "Comp Transient Protect",
+                0.0,
+                FloatRange::Linear { min: 0.0, max: 1.0 },
+            )
+            .with_step_size(0.01)
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+            #[cfg(feature = "buttercomp2")]
+            comp_trim_l: FloatParam::new(
+                "Comp Trim L",
+                0.0,
+                FloatRange::Linear { min: -6.0, max: 6.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.1)
+            .with_value_to_string(formatters::v2s_f32_rounded(1))
+            .rarely_automated(),
+            #[cfg(feature = "buttercomp2")]
+            comp_trim_r: FloatParam::new(
+                "Comp Trim R",
+                0.0,
+                FloatRange::Linear { min: -6.0, max: 6.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.1)
+            .with_value_to_string(formatters::v2s_f32_rounded(1))
+            .rarely_automated(),
+            #[cfg(feature = "buttercomp2")]
+            comp_auto_balance: BoolParam::new("Comp Auto Balance", false).rarely_automated(),
 
             // VCA model parameters
             vca_thresh: FloatParam::new(
@@ -1141,6 +2530,7 @@ impl Default for BusChannelStripParams {
             )
             .with_unit(" Hz")
             .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
+            pultec_lf_cut_link: BoolParam::new("LF Atten Tracks Boost", false),
 
             pultec_lf_cut_gain: FloatParam::new(
                 "LF Atten",
@@ -1211,10 +2601,20 @@ impl Default for BusChannelStripParams {
             )
             .with_unit("")
             .with_step_size(0.01),
+            pultec_print: BoolParam::new("Pultec Print Under Load", false).rarely_automated(),
+            pultec_variance: BoolParam::new("Pultec Vintage Variance", false).rarely_automated(),
+            pultec_adaa: BoolParam::new("Pultec ADAA", false).rarely_automated(),
+            pultec_character: EnumParam::new("Pultec Character", EqCharacter::Clean)
+                .rarely_automated(),
 
             #[cfg(feature = "dynamic_eq")]
             // Dynamic EQ Parameters
             dyneq_bypass: BoolParam::new("DynEQ Bypass", true),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_key: EnumParam::new("DynEQ Key", KeySource::Internal).rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_control_law: EnumParam::new("DynEQ Control Law", ControlLaw::ThresholdRatio)
+                .rarely_automated(),
 
             #[cfg(feature = "dynamic_eq")]
             // Band 1 (Low) - 200Hz
@@ -1317,12 +2717,94 @@ impl Default for BusChannelStripParams {
                 },
             )
             .with_unit(" Hz")
-            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
 
             #[cfg(feature = "dynamic_eq")]
             dyneq_band1_mode: EnumParam::new("DynEQ 1 Mode", DynamicMode::CompressDownward),
             #[cfg(feature = "dynamic_eq")]
             dyneq_band1_solo: BoolParam::new("DynEQ 1 Solo", false),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band1_range: FloatParam::new(
+                "DynEQ 1 Range",
+                6.0,
+                FloatRange::Linear { min: 0.0, max: 24.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band1_sensitivity: FloatParam::new(
+                "DynEQ 1 Sensitivity",
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 },
+            )
+            .with_step_size(0.01)
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band1_key_eq: BoolParam::new("DynEQ 1 Key EQ", false).rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band1_key_hp: FloatParam::new(
+                "DynEQ 1 Key HP",
+                20.0,
+                FloatRange::Skewed {
+                    min: 20.0,
+                    max: 2000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band1_key_bell_freq: FloatParam::new(
+                "DynEQ 1 Key Bell",
+                1000.0,
+                FloatRange::Skewed {
+                    min: 100.0,
+                    max: 12000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band1_key_bell_gain: FloatParam::new(
+                "DynEQ 1 Key Bell Gain",
+                0.0,
+                FloatRange::Linear { min: -18.0, max: 18.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5)
+            .with_value_to_string(formatters::v2s_f32_rounded(1))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band1_key_bell_q: FloatParam::new(
+                "DynEQ 1 Key Bell Q",
+                1.0,
+                FloatRange::Skewed {
+                    min: 0.3,
+                    max: 8.0,
+                    factor: FloatRange::skew_factor(0.5),
+                },
+            )
+            .with_step_size(0.01)
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band1_key_lp: FloatParam::new(
+                "DynEQ 1 Key LP",
+                20000.0,
+                FloatRange::Skewed {
+                    min: 1000.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(1))
+            .rarely_automated(),
 
             #[cfg(feature = "dynamic_eq")]
             // Band 2 (Low-Mid) - 800Hz (similar pattern, different defaults)
@@ -1364,12 +2846,94 @@ impl Default for BusChannelStripParams {
                 },
             )
             .with_unit(" Hz")
-            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
 
             #[cfg(feature = "dynamic_eq")]
             dyneq_band2_mode: EnumParam::new("DynEQ 2 Mode", DynamicMode::CompressDownward),
             #[cfg(feature = "dynamic_eq")]
             dyneq_band2_solo: BoolParam::new("DynEQ 2 Solo", false),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band2_range: FloatParam::new(
+                "DynEQ 2 Range",
+                6.0,
+                FloatRange::Linear { min: 0.0, max: 24.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band2_sensitivity: FloatParam::new(
+                "DynEQ 2 Sensitivity",
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 },
+            )
+            .with_step_size(0.01)
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band2_key_eq: BoolParam::new("DynEQ 2 Key EQ", false).rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band2_key_hp: FloatParam::new(
+                "DynEQ 2 Key HP",
+                20.0,
+                FloatRange::Skewed {
+                    min: 20.0,
+                    max: 2000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band2_key_bell_freq: FloatParam::new(
+                "DynEQ 2 Key Bell",
+                1000.0,
+                FloatRange::Skewed {
+                    min: 100.0,
+                    max: 12000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band2_key_bell_gain: FloatParam::new(
+                "DynEQ 2 Key Bell Gain",
+                0.0,
+                FloatRange::Linear { min: -18.0, max: 18.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5)
+            .with_value_to_string(formatters::v2s_f32_rounded(1))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band2_key_bell_q: FloatParam::new(
+                "DynEQ 2 Key Bell Q",
+                1.0,
+                FloatRange::Skewed {
+                    min: 0.3,
+                    max: 8.0,
+                    factor: FloatRange::skew_factor(0.5),
+                },
+            )
+            .with_step_size(0.01)
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band2_key_lp: FloatParam::new(
+                "DynEQ 2 Key LP",
+                20000.0,
+                FloatRange::Skewed {
+                    min: 1000.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(1))
+            .rarely_automated(),
 
             #[cfg(feature = "dynamic_eq")]
             // Band 3 (High-Mid) - 3kHz
@@ -1410,30 +2974,112 @@ impl Default for BusChannelStripParams {
                 },
             )
             .with_unit(" Hz")
-            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
             #[cfg(feature = "dynamic_eq")]
             dyneq_band3_mode: EnumParam::new("DynEQ 3 Mode", DynamicMode::CompressDownward),
             #[cfg(feature = "dynamic_eq")]
             dyneq_band3_solo: BoolParam::new("DynEQ 3 Solo", false),
-
             #[cfg(feature = "dynamic_eq")]
-            // Band 4 (High) - 8kHz
-            dyneq_band4_freq: FloatParam::new(
-                "DynEQ 4 Freq",
-                8000.0,
-                FloatRange::Skewed {
-                    min: 3000.0,
-                    max: 20000.0,
-                    factor: FloatRange::skew_factor(-2.0),
-                },
+            dyneq_band3_range: FloatParam::new(
+                "DynEQ 3 Range",
+                6.0,
+                FloatRange::Linear { min: 0.0, max: 24.0 },
             )
-            .with_unit(" Hz")
-            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
-
-            #[cfg(feature = "dynamic_eq")]
-            dyneq_band4_threshold: FloatParam::new("DynEQ 4 Thresh", -18.0, FloatRange::Linear { min: -60.0, max: 0.0 }).with_unit(" dB").with_step_size(1.0).with_value_to_string(formatters::v2s_f32_rounded(0)),
-            #[cfg(feature = "dynamic_eq")]
-            dyneq_band4_ratio: FloatParam::new("DynEQ 4 Ratio", 4.0, FloatRange::Skewed { min: 1.0, max: 20.0, factor: FloatRange::skew_factor(-1.5) }).with_step_size(1.0).with_value_to_string(formatters::v2s_f32_rounded(0)),
+            .with_unit(" dB")
+            .with_step_size(0.5)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band3_sensitivity: FloatParam::new(
+                "DynEQ 3 Sensitivity",
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 },
+            )
+            .with_step_size(0.01)
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band3_key_eq: BoolParam::new("DynEQ 3 Key EQ", false).rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band3_key_hp: FloatParam::new(
+                "DynEQ 3 Key HP",
+                20.0,
+                FloatRange::Skewed {
+                    min: 20.0,
+                    max: 2000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band3_key_bell_freq: FloatParam::new(
+                "DynEQ 3 Key Bell",
+                1000.0,
+                FloatRange::Skewed {
+                    min: 100.0,
+                    max: 12000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band3_key_bell_gain: FloatParam::new(
+                "DynEQ 3 Key Bell Gain",
+                0.0,
+                FloatRange::Linear { min: -18.0, max: 18.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5)
+            .with_value_to_string(formatters::v2s_f32_rounded(1))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band3_key_bell_q: FloatParam::new(
+                "DynEQ 3 Key Bell Q",
+                1.0,
+                FloatRange::Skewed {
+                    min: 0.3,
+                    max: 8.0,
+                    factor: FloatRange::skew_factor(0.5),
+                },
+            )
+            .with_step_size(0.01)
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band3_key_lp: FloatParam::new(
+                "DynEQ 3 Key LP",
+                20000.0,
+                FloatRange::Skewed {
+                    min: 1000.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(1))
+            .rarely_automated(),
+
+            #[cfg(feature = "dynamic_eq")]
+            // Band 4 (High) - 8kHz
+            dyneq_band4_freq: FloatParam::new(
+                "DynEQ 4 Freq",
+                8000.0,
+                FloatRange::Skewed {
+                    min: 3000.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
+
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_threshold: FloatParam::new("DynEQ 4 Thresh", -18.0, FloatRange::Linear { min: -60.0, max: 0.0 }).with_unit(" dB").with_step_size(1.0).with_value_to_string(formatters::v2s_f32_rounded(0)),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_ratio: FloatParam::new("DynEQ 4 Ratio", 4.0, FloatRange::Skewed { min: 1.0, max: 20.0, factor: FloatRange::skew_factor(-1.5) }).with_step_size(1.0).with_value_to_string(formatters::v2s_f32_rounded(0)),
             #[cfg(feature = "dynamic_eq")]
             dyneq_band4_attack: FloatParam::new("DynEQ 4 Attack", 2.0, FloatRange::Skewed { min: 0.1, max: 200.0, factor: FloatRange::skew_factor(-2.0) }).with_unit(" ms").with_step_size(1.0).with_value_to_string(formatters::v2s_f32_rounded(0)),
             #[cfg(feature = "dynamic_eq")]
@@ -1455,11 +3101,93 @@ impl Default for BusChannelStripParams {
                 },
             )
             .with_unit(" Hz")
-            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
             #[cfg(feature = "dynamic_eq")]
             dyneq_band4_mode: EnumParam::new("DynEQ 4 Mode", DynamicMode::CompressDownward),
             #[cfg(feature = "dynamic_eq")]
             dyneq_band4_solo: BoolParam::new("DynEQ 4 Solo", false),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_range: FloatParam::new(
+                "DynEQ 4 Range",
+                6.0,
+                FloatRange::Linear { min: 0.0, max: 24.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5)
+            .with_value_to_string(formatters::v2s_f32_rounded(1)),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_sensitivity: FloatParam::new(
+                "DynEQ 4 Sensitivity",
+                0.5,
+                FloatRange::Linear { min: 0.0, max: 1.0 },
+            )
+            .with_step_size(0.01)
+            .with_value_to_string(formatters::v2s_f32_percentage(0))
+            .with_string_to_value(formatters::s2v_f32_percentage()),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_key_eq: BoolParam::new("DynEQ 4 Key EQ", false).rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_key_hp: FloatParam::new(
+                "DynEQ 4 Key HP",
+                20.0,
+                FloatRange::Skewed {
+                    min: 20.0,
+                    max: 2000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_key_bell_freq: FloatParam::new(
+                "DynEQ 4 Key Bell",
+                1000.0,
+                FloatRange::Skewed {
+                    min: 100.0,
+                    max: 12000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_key_bell_gain: FloatParam::new(
+                "DynEQ 4 Key Bell Gain",
+                0.0,
+                FloatRange::Linear { min: -18.0, max: 18.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.5)
+            .with_value_to_string(formatters::v2s_f32_rounded(1))
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_key_bell_q: FloatParam::new(
+                "DynEQ 4 Key Bell Q",
+                1.0,
+                FloatRange::Skewed {
+                    min: 0.3,
+                    max: 8.0,
+                    factor: FloatRange::skew_factor(0.5),
+                },
+            )
+            .with_step_size(0.01)
+            .rarely_automated(),
+            #[cfg(feature = "dynamic_eq")]
+            dyneq_band4_key_lp: FloatParam::new(
+                "DynEQ 4 Key LP",
+                20000.0,
+                FloatRange::Skewed {
+                    min: 1000.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(1))
+            .rarely_automated(),
 
             // Transformer Module Parameters
             transformer_bypass: BoolParam::new("Transformer Bypass", true),
@@ -1522,6 +3250,20 @@ impl Default for BusChannelStripParams {
             .with_unit("")
             .with_step_size(0.01),
 
+            transformer_age: FloatParam::new(
+                "Transformer Age",
+                0.0, // Pristine: the model exactly as it was
+                FloatRange::Linear { min: 0.0, max: 1.0 },
+            )
+            .with_unit("")
+            .with_step_size(0.01),
+            transformer_variance: BoolParam::new("Transformer Vintage Variance", false)
+                .rarely_automated(),
+            transformer_adaa: BoolParam::new("Transformer ADAA", false).rarely_automated(),
+            transformer_mono_safe: BoolParam::new("Transformer Mono Safe", false)
+                .non_automatable()
+                .rarely_automated(),
+
             // Punch Module Parameters (Clipper + Transient Shaper)
             // Default: BYPASSED - user must enable intentionally
             #[cfg(feature = "punch")]
@@ -1549,7 +3291,8 @@ impl Default for BusChannelStripParams {
             .with_step_size(0.01),
 
             #[cfg(feature = "punch")]
-            punch_oversampling: EnumParam::new("Oversampling", OversamplingFactor::X8),
+            punch_oversampling: EnumParam::new("Oversampling", OversamplingFactor::X8)
+                .rarely_automated(),
 
             #[cfg(feature = "punch")]
             punch_attack: FloatParam::new(
@@ -1643,7 +3386,51 @@ impl Default for BusChannelStripParams {
             )
             .with_unit(" Hz")
             .with_step_size(1.0)
-            .with_value_to_string(formatters::v2s_f32_rounded(0)),
+            .with_value_to_string(formatters::v2s_f32_rounded(0))
+            .rarely_automated(),
+
+            #[cfg(feature = "punch")]
+            punch_tilt: FloatParam::new(
+                "Punch Clip Tilt",
+                0.0, // Off: the clipper sees the signal as it is
+                FloatRange::Linear { min: 0.0, max: 12.0 },
+            )
+            .with_unit(" dB")
+            .with_step_size(0.1),
+
+            #[cfg(feature = "punch")]
+            punch_tilt_pivot: FloatParam::new(
+                "Punch Tilt Pivot",
+                1000.0,
+                FloatRange::Skewed {
+                    min: 100.0,
+                    max: 5000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_step_size(1.0)
+            .with_value_to_string(formatters::v2s_f32_rounded(0))
+            .rarely_automated(),
+            #[cfg(feature = "punch")]
+            punch_smoothing: FloatParam::new(
+                "Punch Smoothing",
+                punch::DEFAULT_SMOOTHING_MS,
+                FloatRange::Skewed {
+                    min: 0.0,
+                    max: 20.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" ms")
+            .with_step_size(0.1)
+            .with_value_to_string(formatters::v2s_f32_rounded(1))
+            .rarely_automated(),
+            #[cfg(feature = "punch")]
+            punch_link: FloatParam::new("Punch Link", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 })
+                .with_step_size(0.01)
+                .with_value_to_string(formatters::v2s_f32_percentage(0))
+                .with_string_to_value(formatters::s2v_f32_percentage()),
 
             // ── Haas Module defaults ────────────────────────────────────
             // Default: BYPASSED so the chain remains audibly unchanged on
@@ -1770,39 +3557,263 @@ impl Default for BusChannelStripParams {
             #[cfg(feature = "sheen")]
             sheen_width_bypass: BoolParam::new("Sheen Width Bypass", false),
 
+            // ── Input conditioning ─────────────────────────────────────
+            // Bypassed by default; switching it in gives a 20 Hz, 12 dB/oct
+            // rumble filter and nothing else.
+            #[cfg(feature = "conditioning")]
+            input_bypass: BoolParam::new("Input Bypass", true),
+            #[cfg(feature = "conditioning")]
+            input_hpf_freq: FloatParam::new(
+                "Input HPF",
+                20.0,
+                FloatRange::Skewed {
+                    min: 10.0,
+                    max: 1000.0,
+                    factor: FloatRange::skew_factor(-2.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
+            #[cfg(feature = "conditioning")]
+            input_hpf_slope: EnumParam::new("Input HPF Slope", Slope::Db12).rarely_automated(),
+            #[cfg(feature = "conditioning")]
+            input_lpf_freq: FloatParam::new(
+                "Input LPF",
+                20000.0,
+                FloatRange::Skewed {
+                    min: 1000.0,
+                    max: 20000.0,
+                    factor: FloatRange::skew_factor(-1.0),
+                },
+            )
+            .with_unit(" Hz")
+            .with_value_to_string(formatters::v2s_f32_hz_then_khz(0)),
+            #[cfg(feature = "conditioning")]
+            input_lpf_slope: EnumParam::new("Input LPF Slope", Slope::Off).rarely_automated(),
+            #[cfg(feature = "conditioning")]
+            input_rotation: FloatParam::new(
+                "Input Phase Rotation",
+                0.0,
+                FloatRange::Linear {
+                    min: -180.0,
+                    max: 180.0,
+                },
+            )
+            .with_unit("\u{00B0}")
+            .with_step_size(1.0),
+            #[cfg(feature = "conditioning")]
+            input_polarity: EnumParam::new("Input Polarity", Polarity::Normal),
+
             // Module Ordering Parameters (default signal chain)
             // Default order places Haas before Punch so the clipper catches
             // any residual peaks introduced by the widener. Slot 7 is Empty
             // by default — users can drop any module (including DynamicEQ)
             // into it via the rack picker. Existing sessions saved before
             // this default change retain their stored slot 7 value.
-            module_order_1: EnumParam::new("Module Order 1", ModuleType::Api5500EQ),
-            module_order_2: EnumParam::new("Module Order 2", ModuleType::ButterComp2),
-            module_order_3: EnumParam::new("Module Order 3", ModuleType::PultecEQ),
-            module_order_4: EnumParam::new("Module Order 4", ModuleType::Transformer),
-            module_order_5: EnumParam::new("Module Order 5", ModuleType::Haas),
-            module_order_6: EnumParam::new("Module Order 6", ModuleType::Punch),
-            module_order_7: EnumParam::new("Module Order 7", ModuleType::Empty),
+            module_order_1: EnumParam::new("Module Order 1", ModuleType::Api5500EQ)
+                .rarely_automated(),
+            module_order_2: EnumParam::new("Module Order 2", ModuleType::ButterComp2)
+                .rarely_automated(),
+            module_order_3: EnumParam::new("Module Order 3", ModuleType::PultecEQ)
+                .rarely_automated(),
+            module_order_4: EnumParam::new("Module Order 4", ModuleType::Transformer)
+                .rarely_automated(),
+            module_order_5: EnumParam::new("Module Order 5", ModuleType::Haas).rarely_automated(),
+            module_order_6: EnumParam::new("Module Order 6", ModuleType::Punch).rarely_automated(),
+            module_order_7: EnumParam::new("Module Order 7", ModuleType::Empty).rarely_automated(),
 
             // Hide flags — all modules visible by default. Marked non-automatable
             // so hosts don't clutter automation lists with per-module view state.
-            hide_api5500: BoolParam::new("Hide API5500", false).non_automatable(),
-            hide_buttercomp2: BoolParam::new("Hide ButterComp2", false).non_automatable(),
-            hide_pultec: BoolParam::new("Hide Pultec", false).non_automatable(),
-            hide_dynamic_eq: BoolParam::new("Hide Dynamic EQ", false).non_automatable(),
-            hide_transformer: BoolParam::new("Hide Transformer", false).non_automatable(),
-            hide_punch: BoolParam::new("Hide Punch", false).non_automatable(),
-            hide_haas: BoolParam::new("Hide Haas", false).non_automatable(),
+            hide_api5500: BoolParam::new("Hide API5500", false)
+                .non_automatable()
+                .rarely_automated(),
+            hide_buttercomp2: BoolParam::new("Hide ButterComp2", false)
+                .non_automatable()
+                .rarely_automated(),
+            hide_pultec: BoolParam::new("Hide Pultec", false).non_automatable().rarely_automated(),
+            hide_dynamic_eq: BoolParam::new("Hide Dynamic EQ", false)
+                .non_automatable()
+                .rarely_automated(),
+            hide_transformer: BoolParam::new("Hide Transformer", false)
+                .non_automatable()
+                .rarely_automated(),
+            hide_punch: BoolParam::new("Hide Punch", false).non_automatable().rarely_automated(),
+            hide_haas: BoolParam::new("Hide Haas", false).non_automatable().rarely_automated(),
+
+            analyzer_tap: EnumParam::new("Analyzer Tap", AnalyzerTap::Output)
+                .non_automatable()
+                .rarely_automated(),
+            analyzer_channels: EnumParam::new("Analyzer Channels", AnalyzerChannels::Sum)
+                .non_automatable()
+                .rarely_automated(),
+            analyzer_window: EnumParam::new("Analyzer Window", AnalyzerWindow::Hann)
+                .non_automatable()
+                .rarely_automated(),
+            analyzer_overlap: EnumParam::new("Analyzer Overlap", AnalyzerOverlap::Half)
+                .non_automatable()
+                .rarely_automated(),
+            analyzer_averaging: FloatParam::new(
+                "Analyzer Averaging",
+                200.0,
+                FloatRange::Skewed {
+                    min: 0.0,
+                    max: 3000.0,
+                    factor: FloatRange::skew_factor(-1.5),
+                },
+            )
+            .with_unit(" ms")
+            .with_value_to_string(formatters::v2s_f32_rounded(0))
+            .non_automatable()
+            .rarely_automated(),
+            analyzer_floor: FloatParam::new(
+                "Analyzer Floor",
+                -90.0,
+                FloatRange::Linear {
+                    min: -120.0,
+                    max: -48.0,
+                },
+            )
+            .with_unit(" dB")
+            .with_step_size(6.0)
+            .with_value_to_string(formatters::v2s_f32_rounded(0))
+            .non_automatable()
+            .rarely_automated(),
+
+            editor_skin: RwLock::new(EditorSkin::default()),
+            editor_language: RwLock::new(EditorLanguage::default()),
+            meter_rate: RwLock::new(MeterRate::default()),
+            editor_settings: RwLock::new(EditorSettings::default()),
+            analyzer_snapshots: RwLock::new(spectral::SpectrumSnapshots::default()),
+            context_preset: RwLock::new(host_context::ContextPreset::default()),
         }
     }
 }
 
+/// Current analyzer options, read by the worker at each drain.
+#[cfg(feature = "plugin")]
+fn analyzer_settings(params: &BusChannelStripParams) -> analyzer::AnalyzerSettings {
+    analyzer::AnalyzerSettings {
+        window: params.analyzer_window.value(),
+        overlap: params.analyzer_overlap.value(),
+        averaging_ms: params.analyzer_averaging.value(),
+        floor_db: params.analyzer_floor.value(),
+    }
+}
+
+/// A coloration knob's `value` as its stage hears it under the Analog
+/// Amount macro.
+#[cfg(all(
+    feature = "plugin",
+    any(feature = "pultec", feature = "transformer", feature = "sheen")
+))]
+fn analog_scaled(params: &BusChannelStripParams, target: AnalogTarget, value: f32) -> f32 {
+    target.apply(params.global_analog_amount.value(), value)
+}
+
+/// Load the API5500's bands, character and dual-mono offsets from
+/// `params`. Shared by the audio path and the response export.
+#[cfg(all(feature = "api5500", feature = "plugin"))]
+fn configure_api5500(eq: &mut Api5500, params: &BusChannelStripParams) {
+    eq.update_parameters(
+        params.lf_freq.value(),
+        params.lf_gain.value(),
+        params.lmf_freq.value(),
+        params.lmf_gain.value(),
+        params.lmf_q.value(),
+        params.mf_freq.value(),
+        params.mf_gain.value(),
+        params.mf_q.value(),
+        params.hmf_freq.value(),
+        params.hmf_gain.value(),
+        params.hmf_q.value(),
+        params.hf_freq.value(),
+        params.hf_gain.value(),
+    );
+    eq.set_character(params.eq_character.value());
+    eq.set_lr_offsets(
+        params.global_channel_mode.value() == ChannelMode::DualMono,
+        [
+            params.eq_lf_offset.value(),
+            params.eq_lmf_offset.value(),
+            params.eq_mf_offset.value(),
+            params.eq_hmf_offset.value(),
+            params.eq_hf_offset.value(),
+        ],
+    );
+}
+
+/// Load the Pultec's sections and character from `params`, with the tube
+/// stage at `tube_drive`. Shared by the audio path and the response
+/// export, which runs the tube stage off.
+#[cfg(all(feature = "pultec", feature = "plugin"))]
+fn configure_pultec(eq: &mut PultecEQ, params: &BusChannelStripParams, tube_drive: f32) {
+    eq.set_character(params.pultec_character.value());
+    let lf_cut_freq = if params.pultec_lf_cut_link.value() {
+        params.pultec_lf_boost_freq.value()
+    } else {
+        params.pultec_lf_cut_freq.value()
+    };
+    eq.update_parameters(
+        params.pultec_lf_boost_freq.value(),
+        params.pultec_lf_boost_gain.value(),
+        params.pultec_lf_boost_bandwidth.value(),
+        lf_cut_freq,
+        params.pultec_lf_cut_gain.value(),
+        params.pultec_lf_cut_bandwidth.value(),
+        params.pultec_hf_boost_freq.value(),
+        params.pultec_hf_boost_gain.value(),
+        params.pultec_hf_boost_bandwidth.value(),
+        params.pultec_hf_cut_freq.value(),
+        params.pultec_hf_cut_gain.value(),
+        tube_drive,
+    );
+}
+
+/// Current transformer settings, compared by the audio thread and read by
+/// the probe worker.
+#[cfg(all(feature = "transformer", feature = "plugin"))]
+fn transformer_probe_settings(params: &BusChannelStripParams) -> harmonics::ProbeSettings {
+    harmonics::ProbeSettings {
+        model: params.transformer_model.value(),
+        input_drive: analog_scaled(
+            params,
+            AnalogTarget::TransformerDrive,
+            params.transformer_input_drive.value(),
+        ),
+        input_saturation: analog_scaled(
+            params,
+            AnalogTarget::TransformerSaturation,
+            params.transformer_input_saturation.value(),
+        ),
+        output_drive: analog_scaled(
+            params,
+            AnalogTarget::TransformerDrive,
+            params.transformer_output_drive.value(),
+        ),
+        output_saturation: analog_scaled(
+            params,
+            AnalogTarget::TransformerSaturation,
+            params.transformer_output_saturation.value(),
+        ),
+        low_response: params.transformer_low_response.value(),
+        high_response: params.transformer_high_response.value(),
+        compression: params.transformer_compression.value(),
+        age: analog_scaled(
+            params,
+            AnalogTarget::TransformerAge,
+            params.transformer_age.value(),
+        ),
+        adaa: params.transformer_adaa.value(),
+    }
+}
+
 /// Compact 0..7 index for ModuleType — used for duplicate-detection when
 /// dispatching modules in user-chosen order. Keep in lock-step with the
 /// enum definition; any reorder there requires updating this match.
 /// Empty is included so the dedup `seen` array in process() has a stable
 /// slot for it; duplicate Empties are harmless (no-op dispatch) but the
 /// dedup loop still needs a valid index.
+#[cfg(feature = "plugin")]
 fn module_type_index(mt: ModuleType) -> usize {
     match mt {
         ModuleType::Api5500EQ => 0,
@@ -1816,145 +3827,509 @@ fn module_type_index(mt: ModuleType) -> usize {
     }
 }
 
+#[cfg(feature = "plugin")]
 impl BusChannelStrip {
     // ── Per-module processing helpers ────────────────────────────────────────
     // Each helper is idempotent-safe to call zero or one times per buffer:
-    //   • update_parameters() advances smoothers/coefficients even when bypassed
-    //   • the bypass gate skips the actual DSP work
+    //   • a bypassed module is skipped entirely — no update_parameters(), no
+    //     DSP — and its state is cleared on the first bypassed buffer, so
+    //     re-enabling starts from silence instead of stale filter history
+    //   • otherwise update_parameters() runs, then the DSP
     // The module_order dispatch loop in process() calls each helper at most
     // once per buffer (duplicates are deduplicated).
 
+    /// Whether the channels run as two independent mono strips.
+    fn dual_mono(&self) -> bool {
+        self.params.global_channel_mode.value() == ChannelMode::DualMono
+    }
+
+    /// Whether the saturation stages run on the group in hand; only a 5.1
+    /// bus's LFE can skip them.
+    fn saturates(&self) -> bool {
+        self.group
+            .saturates(self.params.global_lfe_saturation.value())
+    }
+
+    /// Trade the strip's chain state for a surround group's; a second call
+    /// trades it back.
+    fn swap_group(&mut self, state: &mut surround::GroupState) {
+        std::mem::swap(&mut self.group, &mut state.group);
+        std::mem::swap(&mut self.chain, &mut state.chain);
+        std::mem::swap(&mut self.spare_chain, &mut state.spare_chain);
+        std::mem::swap(&mut self.order_fade, &mut state.order_fade);
+        #[cfg(feature = "sheen")]
+        std::mem::swap(&mut self.sheen, &mut state.sheen);
+        #[cfg(feature = "conditioning")]
+        std::mem::swap(&mut self.input_conditioning, &mut state.input_conditioning);
+    }
+
+    /// A smoothed parameter's value for this block. The metered pass moves
+    /// the smoother on, once per block; any other pass reads where it
+    /// stands, so the extra passes don't hurry the glide.
+    fn smoothed(&self, param: &FloatParam) -> f32 {
+        if self.metered {
+            param.smoothed.next()
+        } else {
+            param.smoothed.previous()
+        }
+    }
+
+    /// Record `mt`'s bypass switch; true on the first bypassed buffer, when
+    /// the caller resets the module.
+    fn enter_bypass(&mut self, mt: ModuleType, bypassed: bool) -> bool {
+        let was_bypassed = std::mem::replace(
+            &mut self.chain.module_bypassed[module_type_index(mt)],
+            bypassed,
+        );
+        bypassed && !was_bypassed
+    }
+
     #[cfg(feature = "api5500")]
     fn process_module_api5500(&mut self, buffer: &mut Buffer) {
-        self.eq_api5500.update_parameters(
-            self.params.lf_freq.value(),
-            self.params.lf_gain.value(),
-            self.params.lmf_freq.value(),
-            self.params.lmf_gain.value(),
-            self.params.lmf_q.value(),
-            self.params.mf_freq.value(),
-            self.params.mf_gain.value(),
-            self.params.mf_q.value(),
-            self.params.hmf_freq.value(),
-            self.params.hmf_gain.value(),
-            self.params.hmf_q.value(),
-            self.params.hf_freq.value(),
-            self.params.hf_gain.value(),
-        );
-        if !self.params.eq_bypass.value() {
-            self.eq_api5500.process(buffer);
+        let bypassed = self.params.eq_bypass.value();
+        if self.enter_bypass(ModuleType::Api5500EQ, bypassed) {
+            self.chain.eq_api5500.reset_iir();
+        }
+        if bypassed {
+            self.chain.eq_api5500.process_bypassed(buffer);
+            return;
+        }
+        configure_api5500(&mut self.chain.eq_api5500, &self.params);
+        self.chain
+            .eq_api5500
+            .set_noise_gain(AnalogTarget::Noise.gain(self.params.global_analog_amount.value()));
+        self.chain.eq_api5500.process(buffer, &self.eq_fir);
+    }
+
+    /// Run the compressor's key (the routed sidechain, or the input) through
+    /// `comp_key_eq` into `comp_key_scratch`. VCA and FET always high-pass
+    /// their key at SC HP; the Optical only takes it with the key EQ on.
+    /// False when the block is too long for the scratch.
+    #[cfg(feature = "buttercomp2")]
+    fn filter_comp_key(
+        &mut self,
+        input: &[&mut [f32]],
+        key: Option<&[&mut [f32]]>,
+        model: ButterComp2Model,
+    ) -> bool {
+        let n = input.first().map_or(0, |ch| ch.len());
+        if n > self.comp_key_scratch[0].len() {
+            return false;
+        }
+        let enabled = self.params.comp_key_eq.value();
+        let hp_hz = if enabled || model != ButterComp2Model::Optical {
+            self.params.comp_sc_hp_freq.value()
+        } else {
+            key_eq::HP_OFF_HZ
+        };
+        self.chain
+            .comp_key_eq
+            .update_parameters(key_eq::KeyEqSettings {
+                enabled,
+                hp_hz,
+                bell_hz: self.params.comp_key_bell_freq.value(),
+                bell_gain_db: self.params.comp_key_bell_gain.value(),
+                bell_q: self.params.comp_key_bell_q.value(),
+                lp_hz: self.params.comp_key_lp.value(),
+            });
+        let source = key.unwrap_or(input);
+        for (ch, scratch) in self.comp_key_scratch.iter_mut().enumerate() {
+            let scratch = &mut scratch[..n];
+            // A mono key feeds both detectors; samples past its end are silence.
+            scratch.fill(0.0);
+            if let Some(src) = source.get(ch).or(source.first()) {
+                for (out, &x) in scratch.iter_mut().zip(src.iter()) {
+                    *out = x;
+                }
+            }
+            for s in scratch.iter_mut() {
+                *s = self.chain.comp_key_eq.run(*s, ch);
+            }
         }
+        true
     }
 
     #[cfg(feature = "buttercomp2")]
-    fn process_module_buttercomp(&mut self, buffer: &mut Buffer) {
-        if self.params.comp_bypass.value() {
+    fn process_module_buttercomp(&mut self, buffer: &mut Buffer, sidechain: Option<&[&mut [f32]]>) {
+        let bypassed = self.params.comp_bypass.value();
+        if self.enter_bypass(ModuleType::ButterComp2, bypassed) {
+            self.chain.compressor.reset();
+            self.chain.fet_compressor.reset();
+            self.chain.vca_compressor.reset();
+            self.chain.optical_compressor.reset();
+            self.chain.comp_balance.reset();
+            self.chain.comp_key_eq.reset();
+            self.chain.comp_protect.reset();
+        }
+        if bypassed {
             return;
         }
-        match self.params.comp_model.value() {
+        self.chain.comp_balance.measure_input(buffer.as_slice());
+        let model = self.params.comp_model.value();
+        let key = route_key(self.params.comp_key.value(), sidechain);
+        // Key EQ, transient protect and key listen hand the model its key
+        // through the key EQ, which then also carries the SC HP. The
+        // Classic core has no detector tap: it keeps detecting on its
+        // input, and key listen plays that input.
+        let listen = self.params.global_key_listen.value() == KeyListen::Compressor;
+        let protect = self.params.comp_transient_protect.value();
+        let linked = !self.dual_mono();
+        self.chain.fet_compressor.set_linked(linked);
+        self.chain.vca_compressor.set_linked(linked);
+        self.chain.comp_protect.set_linked(linked);
+        let filtered = model != ButterComp2Model::Classic
+            && (listen || protect > 0.0 || self.params.comp_key_eq.value())
+            && self.filter_comp_key(buffer.as_slice(), key, model);
+        let n = if filtered { buffer.samples() } else { 0 };
+        let [scratch_l, scratch_r] = &mut self.comp_key_scratch;
+        let mut filtered_key = [&mut scratch_l[..n], &mut scratch_r[..n]];
+        if protect > 0.0 {
+            self.chain.comp_protect.process(&mut filtered_key, protect);
+        }
+        if listen && self.metered {
+            if filtered {
+                self.key_tap.capture(&filtered_key, 0);
+            } else if model == ButterComp2Model::Classic {
+                self.key_tap.capture(buffer.as_slice(), 0);
+            }
+        }
+        let (key, sc_hp_hz) = if filtered {
+            (Some(&filtered_key[..]), key_eq::HP_OFF_HZ)
+        } else {
+            (key, self.params.comp_sc_hp_freq.value())
+        };
+        match model {
             ButterComp2Model::Classic => {
-                self.compressor.update_parameters(
+                self.chain.compressor.update_parameters(
                     self.params.comp_compress.value(),
                     self.params.comp_output.value(),
                     self.params.comp_dry_wet.value(),
+                    self.params.comp_faithful.value(),
                 );
-                self.compressor.process(buffer);
+                // The classic core is FFI with no GR tap — estimate it from
+                // the level drop across the stage, overall and per channel.
+                let channel_rms = |buffer: &Buffer| {
+                    let channels = buffer.as_slice_immutable();
+                    [0, 1].map(|ch| {
+                        channels
+                            .get(ch)
+                            .map_or(0.0, |ch| rms_linear(std::slice::from_ref(ch)))
+                    })
+                };
+                let drop_db = |in_rms: f32, out_rms: f32| {
+                    (util::gain_to_db(in_rms) - util::gain_to_db(out_rms)).max(0.0)
+                };
+                let in_rms = rms_linear(buffer.as_slice());
+                let in_channels = channel_rms(buffer);
+                self.chain.compressor.process(buffer);
+                let out_rms = rms_linear(buffer.as_slice());
+                if in_rms > 1e-6 {
+                    self.comp_gr_db = drop_db(in_rms, out_rms);
+                }
+                let out_channels = channel_rms(buffer);
+                for (ch, gr) in self.comp_channel_gr_db.iter_mut().enumerate() {
+                    if in_channels[ch] > 1e-6 {
+                        *gr = drop_db(in_channels[ch], out_channels[ch]);
+                    }
+                }
             }
             ButterComp2Model::Vca => {
-                self.vca_compressor.update_parameters(
-                    self.params.vca_thresh.smoothed.next(),
-                    self.params.vca_ratio.smoothed.next(),
-                    self.params.vca_atk.smoothed.next(),
-                    self.params.vca_rel.smoothed.next(),
-                    self.params.comp_sc_hp_freq.value(),
+                self.chain.vca_compressor.update_parameters(
+                    self.smoothed(&self.params.vca_thresh),
+                    self.smoothed(&self.params.vca_ratio),
+                    self.smoothed(&self.params.vca_atk),
+                    self.smoothed(&self.params.vca_rel),
+                    sc_hp_hz,
                 );
-                self.vca_compressor.process(buffer);
+                match key {
+                    Some(key) => self.chain.vca_compressor.process_keyed(buffer, key),
+                    None => self.chain.vca_compressor.process(buffer),
+                }
+                self.comp_gr_db = self.chain.vca_compressor.gain_reduction_db();
+                self.comp_channel_gr_db = self.chain.vca_compressor.channel_gain_reduction_db();
             }
             ButterComp2Model::Optical => {
-                let thresh = self.params.opt_thresh.smoothed.next();
-                let speed = self.params.opt_speed.smoothed.next();
-                let char_v = self.params.opt_char.smoothed.next();
-                self.optical_compressor
+                let thresh = self.smoothed(&self.params.opt_thresh);
+                let speed = self.smoothed(&self.params.opt_speed);
+                let char_v = self.smoothed(&self.params.opt_char);
+                self.chain
+                    .optical_compressor
                     .update_parameters(thresh, speed, char_v);
-                self.optical_compressor.process(buffer, thresh);
+                match key {
+                    Some(key) => self
+                        .chain
+                        .optical_compressor
+                        .process_keyed(buffer, key, thresh),
+                    None => self.chain.optical_compressor.process(buffer, thresh),
+                }
+                self.comp_gr_db = self.chain.optical_compressor.gain_reduction_db();
+                self.comp_channel_gr_db = self.chain.optical_compressor.channel_gain_reduction_db();
             }
             ButterComp2Model::Fet => {
-                self.fet_compressor.update_parameters(
-                    self.params.fet_input_db.smoothed.next(),
-                    self.params.fet_output_db.smoothed.next(),
-                    self.params.fet_attack_ms.smoothed.next(),
-                    self.params.fet_release_ms.smoothed.next(),
+                self.chain.fet_compressor.update_parameters(
+                    self.smoothed(&self.params.fet_input_db),
+                    self.smoothed(&self.params.fet_output_db),
+                    self.smoothed(&self.params.fet_attack_ms),
+                    self.smoothed(&self.params.fet_release_ms),
                     self.params.fet_ratio.value(),
                     self.params.fet_auto_release.value(),
-                    self.params.comp_sc_hp_freq.value(),
+                    sc_hp_hz,
                 );
-                self.fet_compressor.process(buffer);
+                match key {
+                    Some(key) => self.chain.fet_compressor.process_keyed(buffer, key),
+                    None => self.chain.fet_compressor.process(buffer),
+                }
+                self.comp_gr_db = self.chain.fet_compressor.gain_reduction_db();
+                self.comp_channel_gr_db = self.chain.fet_compressor.channel_gain_reduction_db();
             }
         }
+        self.chain.comp_balance.process(
+            buffer.as_slice(),
+            [
+                self.params.comp_trim_l.value(),
+                self.params.comp_trim_r.value(),
+            ],
+            self.params.comp_auto_balance.value() && linked,
+        );
     }
 
     #[cfg(feature = "pultec")]
     fn process_module_pultec(&mut self, buffer: &mut Buffer) {
-        self.pultec.update_parameters(
-            self.params.pultec_lf_boost_freq.value(),
-            self.params.pultec_lf_boost_gain.value(),
-            self.params.pultec_lf_boost_bandwidth.value(),
-            self.params.pultec_lf_cut_freq.value(),
-            self.params.pultec_lf_cut_gain.value(),
-            self.params.pultec_lf_cut_bandwidth.value(),
-            self.params.pultec_hf_boost_freq.value(),
-            self.params.pultec_hf_boost_gain.value(),
-            self.params.pultec_hf_boost_bandwidth.value(),
-            self.params.pultec_hf_cut_freq.value(),
-            self.params.pultec_hf_cut_gain.value(),
+        let bypassed = self.params.pultec_bypass.value();
+        if self.enter_bypass(ModuleType::PultecEQ, bypassed) {
+            self.chain.pultec.reset();
+        }
+        if bypassed {
+            self.publish_engagement(spectral::EngagementStage::PultecTube, 0.0, buffer.samples());
+            return;
+        }
+        self.chain
+            .pultec
+            .set_variance(self.params.pultec_variance.value());
+        self.chain.pultec.set_adaa(self.params.pultec_adaa.value());
+        let tube_drive = analog_scaled(
+            &self.params,
+            AnalogTarget::PultecTube,
             self.params.pultec_tube_drive.value(),
         );
-        if !self.params.pultec_bypass.value() {
-            self.pultec.process(buffer);
+        configure_pultec(&mut self.chain.pultec, &self.params, tube_drive);
+        self.chain
+            .pultec
+            .set_noise_gain(AnalogTarget::Noise.gain(self.params.global_analog_amount.value()));
+        self.chain
+            .pultec
+            .set_printed(self.params.pultec_print.value() && self.load_monitor.pressured());
+        self.pultec_print_curve.update();
+        self.chain
+            .pultec
+            .process_printable(buffer, self.pultec_print_curve.read());
+        let share = self
+            .chain
+            .pultec
+            .take_engagement()
+            .fraction()
+            .unwrap_or(0.0);
+        self.publish_engagement(
+            spectral::EngagementStage::PultecTube,
+            share,
+            buffer.samples(),
+        );
+    }
+
+    /// Fold one block's engagement share (0 while bypassed) into a stage's
+    /// smoothed readout; see engagement.rs. Only the metered pass counts.
+    #[cfg(any(feature = "pultec", feature = "punch", feature = "transformer"))]
+    fn publish_engagement(&mut self, stage: spectral::EngagementStage, share: f32, samples: usize) {
+        if !self.metered {
+            return;
         }
+        let keep = block_smoothing(ENGAGEMENT_TAU_S, samples, self.sample_rate);
+        let smoothed = &mut self.engagement_share[stage as usize];
+        *smoothed = *smoothed * keep + share * (1.0 - keep);
+        self.engagement_data.store(stage, *smoothed);
     }
 
-    #[cfg(feature = "transformer")]
-    fn process_module_transformer(&mut self, buffer: &mut Buffer) {
-        self.transformer.update_parameters(
-            self.params.transformer_model.value(),
-            self.params.transformer_input_drive.value(),
-            self.params.transformer_input_saturation.value(),
-            self.params.transformer_output_drive.value(),
-            self.params.transformer_output_saturation.value(),
-            self.params.transformer_low_response.value(),
-            self.params.transformer_high_response.value(),
-            self.params.transformer_compression.value(),
+    /// Store the dynamics readings for the bridge. PSR and PLR pair the
+    /// output meter's peaks with the loudness meter's readings.
+    fn publish_dynamics(&self) {
+        use spectral::DynamicsStat;
+        let data = &self.dynamics_data;
+        data.store(
+            DynamicsStat::InputCrest,
+            self.input_dynamics.crest_factor_db(),
         );
-        if !self.params.transformer_bypass.value() {
-            self.transformer.process(buffer);
-        }
+        data.store(
+            DynamicsStat::OutputCrest,
+            self.output_dynamics.crest_factor_db(),
+        );
+        data.store(
+            DynamicsStat::Psr,
+            self.output_dynamics.psr_db(self.loudness.short_term()),
+        );
+        data.store(
+            DynamicsStat::Plr,
+            self.output_dynamics.plr_db(self.loudness.integrated()),
+        );
+        data.store(DynamicsStat::Spread, self.output_dynamics.spread_db());
     }
 
-    #[cfg(feature = "dynamic_eq")]
-    fn process_module_dynamic_eq(&mut self, buffer: &mut Buffer, aux: &mut AuxiliaryBuffers) {
+    /// Publish one stage's mid and side RMS (linear) in dBFS.
+    fn store_stage_mid_side(&self, stage: usize, [mid, side]: [f32; 2]) {
+        self.stage_levels
+            .store_mid_side_db(stage, util::gain_to_db(mid), util::gain_to_db(side));
+    }
+
+    /// Spectrum-match capture: the chain input is the program and the
+    /// sidechain (silence when unconnected) the reference. Idle until the
+    /// editor requests a capture.
+    fn capture_match_spectra(&mut self, buffer: &Buffer, sidechain: Option<&[&mut [f32]]>) {
+        if self.match_data.take_capture_request() {
+            self.match_capture.start();
+        }
+        if !self.match_capture.is_active() {
+            return;
+        }
+        let mono = |channels: &[&mut [f32]], i: usize| {
+            let sum: f32 = channels.iter().filter_map(|ch| ch.get(i)).sum();
+            sum / channels.len().max(1) as f32
+        };
+        let main = buffer.as_slice_immutable();
+        for i in 0..buffer.samples() {
+            let reference = sidechain.map_or(0.0, |sc| mono(sc, i));
+            if self.match_capture.push(mono(main, i), reference) {
+                self.match_capture.publish(&self.match_data);
+                break;
+            }
+        }
+    }
+
+    /// Feed the master-section goniometer and correlation meter. Mono
+    /// buffers read as fully correlated. The trace gets every point; the
+    /// correlation reading is stored only when `publish` is set.
+    fn publish_stereo_scope(&mut self, buffer: &mut Buffer, publish: bool) {
+        let [left, right, ..] = buffer.as_slice() else {
+            if publish {
+                self.scope_data.store_correlation(1.0);
+            }
+            return;
+        };
+        let (mut lr, mut ll, mut rr) = (0.0_f32, 0.0_f32, 0.0_f32);
+        for (&l, &r) in left.iter().zip(right.iter()) {
+            lr += l * r;
+            ll += l * l;
+            rr += r * r;
+            if self.scope_countdown == 0 {
+                self.scope_data.push_point(l, r);
+                self.scope_countdown = self.scope_decimation;
+            }
+            self.scope_countdown -= 1;
+        }
+        let smooth = block_smoothing(CORRELATION_TAU_S, left.len(), self.sample_rate);
+        self.scope_lr = self.scope_lr * smooth + lr * (1.0 - smooth);
+        self.scope_ll = self.scope_ll * smooth + ll * (1.0 - smooth);
+        self.scope_rr = self.scope_rr * smooth + rr * (1.0 - smooth);
+        // Silence has no phase relationship; show it as mono rather than 0.
+        let energy = (self.scope_ll * self.scope_rr).sqrt();
+        let correlation = if energy > 1e-12 {
+            self.scope_lr / energy
+        } else {
+            1.0
+        };
+        if publish {
+            self.scope_data.store_correlation(correlation);
+        }
+    }
+
+    #[cfg(feature = "transformer")]
+    fn process_module_transformer(&mut self, buffer: &mut Buffer) {
+        let bypassed = self.params.transformer_bypass.value() || !self.saturates();
+        if self.enter_bypass(ModuleType::Transformer, bypassed) {
+            self.chain.transformer.reset();
+        }
+        if bypassed {
+            if self.metered {
+                self.transformer_sat.store(0.0);
+            }
+            self.publish_engagement(
+                spectral::EngagementStage::Transformer,
+                0.0,
+                buffer.samples(),
+            );
+            return;
+        }
+        self.chain
+            .transformer
+            .set_variance(self.params.transformer_variance.value());
+        self.chain
+            .transformer
+            .set_adaa(self.params.transformer_adaa.value());
+        self.chain
+            .transformer
+            .set_mono_safe(self.params.transformer_mono_safe.value());
+        self.chain.transformer.set_linked(!self.dual_mono());
+        // The probe settings already carry the Analog Amount's scaling.
+        let settings = transformer_probe_settings(&self.params);
+        self.chain.transformer.set_age(settings.age);
+        self.chain.transformer.update_parameters(
+            self.params.transformer_model.value(),
+            settings.input_drive,
+            settings.input_saturation,
+            settings.output_drive,
+            settings.output_saturation,
+            self.params.transformer_low_response.value(),
+            self.params.transformer_high_response.value(),
+            self.params.transformer_compression.value(),
+        );
+        self.chain.transformer.process(buffer);
+        if self.metered {
+            self.transformer_sat
+                .store(self.chain.transformer.saturation_activity());
+        }
+        let share = self
+            .chain
+            .transformer
+            .take_engagement()
+            .fraction()
+            .unwrap_or(0.0);
+        self.publish_engagement(
+            spectral::EngagementStage::Transformer,
+            share,
+            buffer.samples(),
+        );
+    }
+
+    #[cfg(feature = "dynamic_eq")]
+    fn process_module_dynamic_eq(&mut self, buffer: &mut Buffer, sidechain: Option<&[&mut [f32]]>) {
         // Sidechain ring accumulation — runs regardless of bypass so the
-        // ANALYZE SC feature always reflects the live sidechain.
-        if !aux.inputs.is_empty() {
-            for channel_samples in aux.inputs[0].iter_samples() {
-                let mut mono = 0.0_f32;
-                let mut n = 0_usize;
-                for s in channel_samples {
-                    mono += *s;
-                    n += 1;
+        // ANALYZE SC feature always reflects the live sidechain. Once per
+        // block: the outgoing order's pass during a reorder skips it.
+        if self.metered {
+            if let Some(channels) = sidechain.filter(|sc| !sc.is_empty()) {
+                let scale = 1.0 / channels.len() as f32;
+                for i in 0..buffer.samples() {
+                    let mono = channels.iter().map(|ch| ch[i]).sum::<f32>() * scale;
+                    self.analyzer_ring.push_sidechain(mono);
                 }
-                if n > 0 {
-                    mono /= n as f32;
+            } else {
+                for _ in 0..buffer.samples() {
+                    self.analyzer_ring.push_sidechain(0.0);
                 }
-                self.sc_ring[self.sc_ring_pos] = mono;
-                self.sc_ring_pos = (self.sc_ring_pos + 1) % spectral::FFT_SIZE;
             }
-        } else {
-            for _ in 0..buffer.samples() {
-                self.sc_ring[self.sc_ring_pos] = 0.0;
-                self.sc_ring_pos = (self.sc_ring_pos + 1) % spectral::FFT_SIZE;
+        }
+
+        let bypassed = self.params.dyneq_bypass.value();
+        if self.enter_bypass(ModuleType::DynamicEQ, bypassed) {
+            use std::sync::atomic::Ordering;
+            self.chain.dynamic_eq.reset();
+            if self.metered {
+                for band in &self.gr_data.bands {
+                    band.store(0.0_f32.to_bits(), Ordering::Relaxed);
+                }
             }
         }
+        if bypassed {
+            return;
+        }
 
         let dyneq_params = [
             DynamicBandParams {
@@ -2010,160 +4385,249 @@ impl BusChannelStrip {
                 solo: self.params.dyneq_band4_solo.value(),
             },
         ];
-        self.dynamic_eq.update_parameters(&dyneq_params);
-
-        if !self.params.dyneq_bypass.value() {
-            self.dynamic_eq.process(buffer);
+        self.chain.dynamic_eq.update_parameters(&dyneq_params);
+        self.chain.dynamic_eq.set_linked(!self.dual_mono());
+        let p = &self.params;
+        let law = |range: &FloatParam, sensitivity: &FloatParam| {
+            (p.dyneq_control_law.value() == ControlLaw::RangeSensitivity).then(|| RangeLaw {
+                range_db: range.value(),
+                sensitivity: sensitivity.value(),
+            })
+        };
+        self.chain.dynamic_eq.set_range_law(&[
+            law(&p.dyneq_band1_range, &p.dyneq_band1_sensitivity),
+            law(&p.dyneq_band2_range, &p.dyneq_band2_sensitivity),
+            law(&p.dyneq_band3_range, &p.dyneq_band3_sensitivity),
+            law(&p.dyneq_band4_range, &p.dyneq_band4_sensitivity),
+        ]);
+        let settings = |enabled: &BoolParam,
+                        hp: &FloatParam,
+                        bell: &FloatParam,
+                        gain: &FloatParam,
+                        q: &FloatParam,
+                        lp: &FloatParam| key_eq::KeyEqSettings {
+            enabled: enabled.value(),
+            hp_hz: hp.value(),
+            bell_hz: bell.value(),
+            bell_gain_db: gain.value(),
+            bell_q: q.value(),
+            lp_hz: lp.value(),
+        };
+        self.chain.dynamic_eq.set_key_eq(&[
+            settings(
+                &p.dyneq_band1_key_eq,
+                &p.dyneq_band1_key_hp,
+                &p.dyneq_band1_key_bell_freq,
+                &p.dyneq_band1_key_bell_gain,
+                &p.dyneq_band1_key_bell_q,
+                &p.dyneq_band1_key_lp,
+            ),
+            settings(
+                &p.dyneq_band2_key_eq,
+                &p.dyneq_band2_key_hp,
+                &p.dyneq_band2_key_bell_freq,
+                &p.dyneq_band2_key_bell_gain,
+                &p.dyneq_band2_key_bell_q,
+                &p.dyneq_band2_key_lp,
+            ),
+            settings(
+                &p.dyneq_band3_key_eq,
+                &p.dyneq_band3_key_hp,
+                &p.dyneq_band3_key_bell_freq,
+                &p.dyneq_band3_key_bell_gain,
+                &p.dyneq_band3_key_bell_q,
+                &p.dyneq_band3_key_lp,
+            ),
+            settings(
+                &p.dyneq_band4_key_eq,
+                &p.dyneq_band4_key_hp,
+                &p.dyneq_band4_key_bell_freq,
+                &p.dyneq_band4_key_bell_gain,
+                &p.dyneq_band4_key_bell_q,
+                &p.dyneq_band4_key_lp,
+            ),
+        ]);
+        // Spectral Duck bands take the sidechain whatever KEY says.
+        let key = route_key(self.params.dyneq_key.value(), sidechain);
+        let duck_key = sidechain.filter(|sc| !sc.is_empty());
+        let samples = buffer.samples();
+        match self.params.global_key_listen.value().dyneq_band() {
+            Some(band) if samples <= self.dyneq_listen.len() => {
+                let listen = &mut self.dyneq_listen[..samples];
+                self.chain.dynamic_eq.process_channels_listening(
+                    buffer.as_slice(),
+                    key,
+                    duck_key,
+                    band,
+                    listen,
+                );
+                if self.metered {
+                    self.key_tap.capture(&[listen], 0);
+                }
+            }
+            _ => self
+                .chain
+                .dynamic_eq
+                .process_sidechained(buffer, key, duck_key),
         }
 
         // Publish per-band gain reduction to the GUI display (Relaxed — display only).
-        {
+        if self.metered {
             use std::sync::atomic::Ordering;
-            let gr = self.dynamic_eq.get_gain_reduction_db();
+            let gr = self.chain.dynamic_eq.get_gain_reduction_db();
             for (i, &db) in gr.iter().enumerate() {
                 self.gr_data.bands[i].store(db.to_bits(), Ordering::Relaxed);
             }
+            self.dyneq_gr_db = gr.iter().fold(0.0_f32, |acc, &db| acc.max(db));
         }
+    }
 
-        // Accumulate post-DynEQ samples into the FFT ring buffer.
-        // All buffers are pre-allocated in initialize() — no audio-thread alloc.
-        for channel_samples in buffer.iter_samples() {
-            let mut mono = 0.0_f32;
-            let mut n = 0_usize;
-            for s in channel_samples {
-                mono += *s;
-                n += 1;
-            }
-            if n > 0 {
-                mono /= n as f32;
+    /// Tell the host the chain's latency when it changes: the API5500's
+    /// linear-phase mode while the EQ is in a slot, plus the block
+    /// scheduler's FIFO when a fixed block size is on.
+    fn report_latency(&mut self, order: &[ModuleType], context: &mut impl ProcessContext<Self>) {
+        #[cfg(feature = "api5500")]
+        self.chain.eq_api5500.set_linear_phase(
+            self.params.eq_linear_phase.value() && order.contains(&ModuleType::Api5500EQ),
+        );
+        #[cfg(feature = "api5500")]
+        let latency = self.chain.eq_api5500.latency_samples();
+        #[cfg(not(feature = "api5500"))]
+        let latency = {
+            let _ = order;
+            0
+        };
+        let latency = latency + self.block_latency;
+        if latency != self.reported_latency {
+            self.reported_latency = latency;
+            context.set_latency_samples(latency);
+        }
+    }
+
+    /// The order the `module_order_*` params ask for.
+    fn requested_order(&self) -> reorder::ModuleOrder {
+        [
+            self.params.module_order_1.value(),
+            self.params.module_order_2.value(),
+            self.params.module_order_3.value(),
+            self.params.module_order_4.value(),
+            self.params.module_order_5.value(),
+            self.params.module_order_6.value(),
+            self.params.module_order_7.value(),
+        ]
+    }
+
+    /// Latency the chain reports running `order`, before it runs it: the
+    /// linear-phase EQ's while it sits in a slot.
+    fn order_latency(&self, order: &[ModuleType]) -> usize {
+        #[cfg(feature = "api5500")]
+        if self.params.eq_linear_phase.value() && order.contains(&ModuleType::Api5500EQ) {
+            return linear_phase::LATENCY_SAMPLES as usize;
+        }
+        #[cfg(not(feature = "api5500"))]
+        let _ = order;
+        0
+    }
+
+    /// A slot module's latency, for delta listen's alignment: whole
+    /// samples, and the trips it makes through its oversamplers.
+    fn module_latency(&self, mt: ModuleType) -> (usize, oversampler::CascadeDelay) {
+        let none = oversampler::CascadeDelay::NONE;
+        match mt {
+            #[cfg(feature = "api5500")]
+            ModuleType::Api5500EQ => (self.chain.eq_api5500.latency_samples() as usize, none),
+            #[cfg(feature = "buttercomp2")]
+            ModuleType::ButterComp2 if self.params.comp_model.value() == ButterComp2Model::Fet => {
+                (0, self.chain.fet_compressor.cascade_delay())
             }
-            self.fft_ring[self.fft_ring_pos] = mono;
-            self.fft_ring_pos += 1;
-
-            if self.fft_ring_pos >= spectral::FFT_SIZE {
-                self.fft_ring_pos = 0;
-                for (dst, (&src, &win)) in self
-                    .fft_input
-                    .iter_mut()
-                    .zip(self.fft_ring.iter().zip(self.fft_window.iter()))
-                {
-                    *dst = src * win;
-                }
-                if let Some(ref fft) = self.fft_engine {
-                    if fft
-                        .process_with_scratch(
-                            &mut self.fft_input,
-                            &mut self.fft_output,
-                            &mut self.fft_scratch,
-                        )
-                        .is_ok()
-                    {
-                        const SMOOTH_ALPHA: f32 = 0.8;
-                        const SMOOTH_BETA: f32 = 1.0 - SMOOTH_ALPHA;
-                        let scale = 2.0 / spectral::FFT_SIZE as f32;
-                        for (smooth, bin) in self.fft_magnitude_smooth[..spectral::SPECTRUM_BINS]
-                            .iter_mut()
-                            .zip(self.fft_output[..spectral::SPECTRUM_BINS].iter())
-                        {
-                            let mag = bin.norm() * scale;
-                            *smooth = *smooth * SMOOTH_ALPHA + mag * SMOOTH_BETA;
-                        }
-                        self.spectrum_data.write_from_slice(
-                            &self.fft_magnitude_smooth[..spectral::SPECTRUM_BINS],
-                        );
-
-                        use std::sync::atomic::Ordering;
-                        if self.analysis_requested.swap(false, Ordering::Relaxed) {
-                            for i in 0..spectral::FFT_SIZE {
-                                let ring_idx = (self.sc_ring_pos + i) % spectral::FFT_SIZE;
-                                self.sc_fft_input[i] = self.sc_ring[ring_idx] * self.fft_window[i];
-                            }
-                            if fft
-                                .process_with_scratch(
-                                    &mut self.sc_fft_input,
-                                    &mut self.sc_fft_output,
-                                    &mut self.fft_scratch,
-                                )
-                                .is_ok()
-                            {
-                                let scale = 2.0 / spectral::FFT_SIZE as f32;
-                                let mut peak_overlap = 0.0_f32;
-                                let mut peak_bin = 1_usize;
-
-                                for i in 1..spectral::SPECTRUM_BINS {
-                                    let main_mag = self.fft_output[i].norm() * scale;
-                                    let sc_mag = self.sc_fft_output[i].norm() * scale;
-                                    let overlap = main_mag * sc_mag;
-                                    self.analysis_result.overlap_bins[i]
-                                        .store(overlap.to_bits(), Ordering::Relaxed);
-                                    if overlap > peak_overlap {
-                                        peak_overlap = overlap;
-                                        peak_bin = i;
-                                    }
-                                }
-                                self.analysis_result.overlap_bins[0]
-                                    .store(0_u32, Ordering::Relaxed);
-
-                                let target_freq =
-                                    peak_bin as f32 * self.sample_rate / spectral::FFT_SIZE as f32;
-
-                                let target_band: u32 = if target_freq < 500.0 {
-                                    0
-                                } else if target_freq < 2000.0 {
-                                    1
-                                } else if target_freq < 6000.0 {
-                                    2
-                                } else {
-                                    3
-                                };
-
-                                let sc_mag_at_peak = self.sc_fft_output[peak_bin].norm() * scale;
-                                let sc_db = 20.0 * sc_mag_at_peak.max(f32::MIN_POSITIVE).log10();
-                                let suggested_threshold = (sc_db - 6.0).clamp(-60.0, 0.0);
-
-                                self.analysis_result
-                                    .target_band
-                                    .store(target_band, Ordering::Relaxed);
-                                self.analysis_result
-                                    .target_freq
-                                    .store(target_freq.to_bits(), Ordering::Relaxed);
-                                self.analysis_result
-                                    .target_threshold_db
-                                    .store(suggested_threshold.to_bits(), Ordering::Relaxed);
-                                self.analysis_result.ready.store(true, Ordering::Release);
-                            }
-                        }
-                    }
-                }
+            #[cfg(feature = "pultec")]
+            ModuleType::PultecEQ => (0, self.chain.pultec.cascade_delay()),
+            #[cfg(feature = "transformer")]
+            ModuleType::Transformer => (0, self.chain.transformer.cascade_delay()),
+            #[cfg(feature = "punch")]
+            ModuleType::Punch => (0, self.chain.punch.cascade_delay()),
+            _ => (0, none),
+        }
+    }
+
+    /// Copy `channels` into the analyzer ring at the current tap point. This
+    /// is the analyzer's whole audio-thread cost; the FFTs run on the
+    /// background thread.
+    fn tap_analyzer(&mut self, channels: &[&mut [f32]]) {
+        let samples = channels.first().map_or(0, |ch| ch.len());
+        let split =
+            self.params.analyzer_channels.value() == AnalyzerChannels::Split && channels.len() >= 2;
+        self.analyzer_ring.set_split(split);
+        let scale = 1.0 / channels.len().max(1) as f32;
+        for i in 0..samples {
+            if split {
+                self.analyzer_ring.push(channels[0][i], channels[1][i]);
+            } else {
+                let sum = channels.iter().map(|ch| ch[i]).sum::<f32>();
+                self.analyzer_ring.push(sum * scale, 0.0);
             }
         }
+        self.analyzer_pending += samples;
     }
 
     #[cfg(feature = "haas")]
     fn process_module_haas(&mut self, buffer: &mut Buffer) {
-        let mid_gain = util::db_to_gain(self.params.haas_mid_gain.smoothed.next());
-        let side_gain = util::db_to_gain(self.params.haas_side_gain.smoothed.next());
-        self.haas.update_parameters(
+        // Haas works on the pair; in dual mono, or on a mono surround
+        // group, there is no image to widen.
+        let bypassed = self.params.haas_bypass.value() || self.dual_mono() || self.group.is_mono();
+        if self.enter_bypass(ModuleType::Haas, bypassed) {
+            self.chain.haas.reset();
+        }
+        if bypassed {
+            return;
+        }
+        let mid_gain = util::db_to_gain(self.smoothed(&self.params.haas_mid_gain));
+        let side_gain = util::db_to_gain(self.smoothed(&self.params.haas_side_gain));
+        self.chain.haas.update_parameters(
             mid_gain,
             side_gain,
-            self.params.haas_comb_depth.smoothed.next(),
+            self.smoothed(&self.params.haas_comb_depth),
             self.params.haas_comb_time.value(),
             self.params.haas_comb_mode.value(),
-            self.params.haas_mix.smoothed.next(),
+            self.smoothed(&self.params.haas_mix),
         );
-        if !self.params.haas_bypass.value() {
-            self.haas.process(buffer);
-        }
+        self.chain.haas.process(buffer);
     }
 
     #[cfg(feature = "punch")]
     fn process_module_punch(&mut self, buffer: &mut Buffer) {
-        self.punch.update_parameters(
+        // The learn pass hears Punch's input whether or not it is engaged.
+        if self.metered {
+            self.loudness_learn.process_input(buffer.as_slice());
+        }
+        let bypassed = self.params.punch_bypass.value() || !self.saturates();
+        if self.enter_bypass(ModuleType::Punch, bypassed) {
+            self.chain.punch.reset();
+            self.chain.punch_link.reset();
+        }
+        if bypassed {
+            if self.metered {
+                self.punch_clip.store(0.0);
+            }
+            self.publish_engagement(spectral::EngagementStage::PunchClip, 0.0, buffer.samples());
+            return;
+        }
+        // The compressor's reduction from earlier in this buffer; zero when
+        // it hasn't run ahead of Punch. Dual mono, each channel follows its
+        // own side's.
+        let comp_gr_db = if self.dual_mono() {
+            self.comp_channel_gr_db
+        } else {
+            [self.comp_gr_db; 2]
+        };
+        let comp_gr_db = self.chain.punch_link.follow(comp_gr_db, buffer.samples());
+        let attack = self.params.punch_attack.value();
+        self.chain.punch.update_parameters(
             self.params.punch_threshold.value(),
             self.params.punch_clip_mode.value(),
             self.params.punch_softness.value(),
             self.params.punch_oversampling.value(),
-            self.params.punch_attack.value(),
+            attack,
             self.params.punch_sustain.value(),
             self.params.punch_attack_time.value(),
             self.params.punch_release_time.value(),
@@ -2173,16 +4637,39 @@ impl BusChannelStrip {
             self.params.punch_mix.value(),
             self.params.punch_wet_hpf_hz.value(),
         );
-        if !self.params.punch_bypass.value() {
-            self.punch.process(buffer);
+        self.chain.punch.set_tilt(
+            self.params.punch_tilt.value(),
+            self.params.punch_tilt_pivot.value(),
+        );
+        self.chain
+            .punch
+            .set_smoothing(self.params.punch_smoothing.value());
+        let link = self.params.punch_link.value();
+        self.chain
+            .punch
+            .set_channel_attack(comp_gr_db.map(|gr| punch_link::linked_attack(attack, link, gr)));
+        self.chain.punch.process(buffer);
+        if self.metered {
+            self.punch_clip.store(self.chain.punch.get_gain_reduction());
         }
+        let share = self.chain.punch.take_engagement().fraction().unwrap_or(0.0);
+        self.publish_engagement(
+            spectral::EngagementStage::PunchClip,
+            share,
+            buffer.samples(),
+        );
     }
 
     /// Dispatch a single module by type, honoring feature flags.
     /// When a feature is disabled the corresponding arm is a no-op — the
     /// module_order_* params remain host-visible regardless of feature set,
     /// so out-of-feature selections silently pass the signal through.
-    fn dispatch_module(&mut self, mt: ModuleType, buffer: &mut Buffer, aux: &mut AuxiliaryBuffers) {
+    fn dispatch_module(
+        &mut self,
+        mt: ModuleType,
+        buffer: &mut Buffer,
+        sidechain: Option<&[&mut [f32]]>,
+    ) {
         match mt {
             ModuleType::Api5500EQ => {
                 #[cfg(feature = "api5500")]
@@ -2194,7 +4681,7 @@ impl BusChannelStrip {
             }
             ModuleType::ButterComp2 => {
                 #[cfg(feature = "buttercomp2")]
-                self.process_module_buttercomp(buffer);
+                self.process_module_buttercomp(buffer, sidechain);
                 #[cfg(not(feature = "buttercomp2"))]
                 {
                     let _ = buffer;
@@ -2218,10 +4705,10 @@ impl BusChannelStrip {
             }
             ModuleType::DynamicEQ => {
                 #[cfg(feature = "dynamic_eq")]
-                self.process_module_dynamic_eq(buffer, aux);
+                self.process_module_dynamic_eq(buffer, sidechain);
                 #[cfg(not(feature = "dynamic_eq"))]
                 {
-                    let _ = (buffer, aux);
+                    let _ = (buffer, sidechain);
                 }
             }
             ModuleType::Punch => {
@@ -2242,12 +4729,13 @@ impl BusChannelStrip {
             }
             // Empty slot: pass-through. No DSP runs, no buffers touched.
             ModuleType::Empty => {
-                let _ = (buffer, aux);
+                let _ = (buffer, sidechain);
             }
         }
     }
 }
 
+#[cfg(feature = "plugin")]
 impl Plugin for BusChannelStrip {
     const NAME: &'static str = "Bus Channel Strip";
     const VENDOR: &'static str = "Francis Secada";
@@ -2277,9 +4765,30 @@ impl Plugin for BusChannelStrip {
             aux_output_ports: &[],
             names: PortNames::const_default(),
         },
+        // 5.1 buses, run as four linking groups; see surround.rs.
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(surround::CHANNELS as u32),
+            main_output_channels: NonZeroU32::new(surround::CHANNELS as u32),
+            aux_input_ports: &[],
+            aux_output_ports: &[],
+            names: PortNames {
+                layout: Some("5.1"),
+                ..PortNames::const_default()
+            },
+        },
+        AudioIOLayout {
+            main_input_channels: NonZeroU32::new(surround::CHANNELS as u32),
+            main_output_channels: NonZeroU32::new(surround::CHANNELS as u32),
+            aux_input_ports: &[new_nonzero_u32(2)],
+            aux_output_ports: &[],
+            names: PortNames {
+                layout: Some("5.1 + sidechain"),
+                ..PortNames::const_default()
+            },
+        },
     ];
 
-    const MIDI_INPUT: MidiConfig = MidiConfig::None;
+    const MIDI_INPUT: MidiConfig = MidiConfig::Basic;
     const MIDI_OUTPUT: MidiConfig = MidiConfig::None;
 
     const SAMPLE_ACCURATE_AUTOMATION: bool = true;
@@ -2288,30 +4797,118 @@ impl Plugin for BusChannelStrip {
     // messages here. The type implements the `SysExMessage` trait, which allows conversion to and
     // from plain byte buffers.
     type SysExMessage = ();
-    // More advanced plugins can use this to run expensive background tasks. See the field's
-    // documentation for more information. `()` means that the plugin does not have any background
-    // tasks.
-    type BackgroundTask = ();
+    // Preset file IO and reference decoding run off the GUI thread.
+    type BackgroundTask = BackgroundTask;
 
     fn params(&self) -> Arc<dyn Params> {
-        self.params.clone()
+        // The host sees the parameters filed by module; see param_groups.rs.
+        Arc::new(param_groups::HostParams(self.params.clone()))
+    }
+
+    fn task_executor(&mut self) -> TaskExecutor<Self> {
+        let library = self.preset_library.clone();
+        let match_data = self.match_data.clone();
+        let params = self.params.clone();
+        let analyzer_ring = self.analyzer_ring.clone();
+        let spectrum_data = self.spectrum_data.clone();
+        let analysis_requested = self.analysis_requested.clone();
+        let analysis_result = self.analysis_result.clone();
+        // Workers are only ever locked here, on the background thread.
+        let analyzer_worker = Mutex::new(analyzer::AnalyzerWorker::new());
+        #[cfg(feature = "api5500")]
+        let eq_fir = self.eq_fir.clone();
+        #[cfg(feature = "api5500")]
+        let eq_fir_worker = Mutex::new(api5500::EqFirWorker::new());
+        #[cfg(feature = "pultec")]
+        let pultec_print = self.pultec_print.clone();
+        #[cfg(feature = "pultec")]
+        let print_worker = self.pultec_print_worker.clone();
+        #[cfg(feature = "transformer")]
+        let harmonic_data = self.harmonic_data.clone();
+        #[cfg(any(feature = "api5500", feature = "pultec"))]
+        let response_export = self.response_export.clone();
+        Box::new(move |task| match task {
+            BackgroundTask::Preset(task) => library.run_task(task),
+            BackgroundTask::LoadMatchReference(path) => {
+                matching::load_reference(&path, &match_data)
+            }
+            BackgroundTask::AnalyzeSpectrum => {
+                if let Ok(mut worker) = analyzer_worker.lock() {
+                    worker.drain(
+                        &analyzer_ring,
+                        &analyzer_settings(&params),
+                        &analyzer::AnalyzerOutputs {
+                            spectrum: &spectrum_data,
+                            analysis_requested: &analysis_requested,
+                            analysis_result: &analysis_result,
+                        },
+                    );
+                }
+                analyzer_ring.finish_drain();
+            }
+            #[cfg(feature = "api5500")]
+            BackgroundTask::DesignEqFir => {
+                if let Ok(mut worker) = eq_fir_worker.lock() {
+                    worker.run(&eq_fir);
+                }
+            }
+            #[cfg(feature = "pultec")]
+            BackgroundTask::PrintTube => {
+                if let Ok(mut worker) = print_worker.lock() {
+                    worker.run(&pultec_print, pultec::tube_transfer);
+                }
+            }
+            #[cfg(feature = "transformer")]
+            BackgroundTask::ProbeTransformer => {
+                let settings = transformer_probe_settings(&params);
+                harmonic_data.finish_read();
+                let profile = harmonics::probe(&settings);
+                harmonic_data.publish(profile.thd, profile.even_share);
+            }
+            #[cfg(any(feature = "api5500", feature = "pultec"))]
+            BackgroundTask::ExportResponse(path, format) => {
+                response::export(&params, &path, format, &response_export);
+            }
+        })
     }
 
     #[cfg(feature = "gui")]
-    fn editor(&mut self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
+    fn editor(&mut self, async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
         editor::create(
             self.params.clone(),
             self.editor_state.clone(),
+            async_executor,
+            self.preset_library.clone(),
+            self.output_level.clone(),
+            self.transformer_sat.clone(),
+            self.scope_data.clone(),
+            self.gr_history.clone(),
+            self.punch_clip.clone(),
+            self.loudness_data.clone(),
+            self.dynamics_data.clone(),
+            self.engagement_data.clone(),
+            self.harmonic_data.clone(),
+            self.response_export.clone(),
+            #[cfg(feature = "punch")]
+            self.loudness_learn_data.clone(),
+            self.stage_levels.clone(),
+            self.match_data.clone(),
             self.spectrum_data.clone(),
             self.analysis_requested.clone(),
             self.analysis_result.clone(),
             self.gr_data.clone(),
+            self.print_active.clone(),
+            self.comp_degraded.clone(),
+            self.safety_fault.clone(),
+            self.meter_rate_hz.clone(),
+            #[cfg(feature = "osc")]
+            self.osc_inbox.clone(),
         )
     }
 
     fn initialize(
         &mut self,
-        _audio_io_layout: &AudioIOLayout,
+        audio_io_layout: &AudioIOLayout,
         _buffer_config: &BufferConfig,
         _context: &mut impl InitContext<Self>,
     ) -> bool {
@@ -2319,86 +4916,81 @@ impl Plugin for BusChannelStrip {
         // TODO: query actual sample rate from _context or BufferConfig
         // Reinitialize modules with the actual sample rate
         let sr = _buffer_config.sample_rate;
-        #[cfg(feature = "api5500")]
-        {
-            self.eq_api5500 = Api5500::new(sr);
+        self.sample_rate = sr;
+        self.scope_decimation = ((sr / SCOPE_POINT_RATE_HZ).round() as usize).max(1);
+        self.gr_history_acc.set_sample_rate(sr);
+        // The persisted rate arrives with the state, before the editor
+        // (if any) opens; mirror it for the audio thread.
+        if let Ok(rate) = self.params.meter_rate.read() {
+            self.meter_rate_hz
+                .store(rate.hz(), std::sync::atomic::Ordering::Relaxed);
         }
-        #[cfg(feature = "buttercomp2")]
-        {
-            self.compressor = ButterComp2::new(sr);
-        }
-        #[cfg(feature = "buttercomp2")]
-        {
-            self.fet_compressor = FetCompressor::new(sr);
+        // A restored session arrives Settled; only a fresh instance picks a
+        // flavor. nih-plug doesn't expose the host's track name, so the
+        // layout decides for now.
+        if let Ok(mut state) = self.params.context_preset.write() {
+            if *state == host_context::ContextPreset::Undecided {
+                *state = if host_context::enabled() {
+                    host_context::ContextPreset::Pending(host_context::pick_flavor(
+                        None,
+                        audio_io_layout,
+                    ))
+                } else {
+                    host_context::ContextPreset::Settled
+                };
+            }
         }
-        #[cfg(feature = "buttercomp2")]
-        {
-            self.vca_compressor = VcaCompressor::new(sr);
+        #[cfg(feature = "osc")]
+        if self.osc_listener.is_none() {
+            let ids = self.params.param_map().into_iter().map(|(id, ..)| id);
+            match osc::OscListener::spawn(ids.collect(), self.osc_inbox.clone()) {
+                Ok(listener) => self.osc_listener = Some(listener),
+                Err(e) => nih_log!("OSC: no listener ({e})"),
+            }
         }
+        self.loudness.set_sample_rate(sr);
+        #[cfg(feature = "punch")]
+        self.loudness_learn.set_sample_rate(sr);
+        self.input_dynamics.set_sample_rate(sr);
+        self.output_dynamics.set_sample_rate(sr);
+        self.safety.set_sample_rate(sr);
+        // Blocks come from the host or the scheduler, whichever is longer.
+        let max_block = (_buffer_config.max_buffer_size as usize).max(block::MAX_BLOCK);
+        self.delta_tap = delta::DeltaTap::new(max_block, DELTA_MAX_LATENCY);
+        self.key_tap = delta::DeltaTap::new(max_block, 0);
+        self.order_fade = reorder::OrderFade::new(max_block, sr);
+        self.duck = duck::DuckEnvelope::new(sr);
+        self.duck_gains = vec![1.0; max_block];
         #[cfg(feature = "buttercomp2")]
         {
-            self.optical_compressor = OpticalCompressor::new(sr);
-        }
-        #[cfg(feature = "pultec")]
-        {
-            self.pultec = PultecEQ::new(sr);
+            self.comp_key_scratch = [vec![0.0; max_block], vec![0.0; max_block]];
         }
         #[cfg(feature = "dynamic_eq")]
         {
-            self.dynamic_eq = DynamicEQ::new(sr);
-        }
-        #[cfg(feature = "transformer")]
-        {
-            self.transformer = TransformerModule::new(sr);
-        }
-        #[cfg(feature = "punch")]
-        {
-            self.punch = PunchModule::new(sr);
-        }
-        #[cfg(feature = "haas")]
-        {
-            self.haas = HaasModule::new(sr);
+            self.dyneq_listen = vec![0.0; max_block];
         }
+        self.match_capture.set_sample_rate(sr);
+        self.analyzer_ring.set_sample_rate(sr);
+        self.analyzer_ring.request_reset();
+        self.analyzer_pending = 0;
+        self.chain = Box::new(chain::Chain::new(sr));
+        self.spare_chain = Box::new(chain::Chain::new(sr));
+        #[cfg(feature = "buttercomp2")]
+        self.comp_degraded.store(
+            self.chain.compressor.is_degraded(),
+            std::sync::atomic::Ordering::Relaxed,
+        );
         #[cfg(feature = "sheen")]
         {
             self.sheen = SheenModule::new(sr);
         }
-
-        // Initialize temporary buffers for module reordering
-        let max_buffer_size = _buffer_config.max_buffer_size as usize;
-        let num_channels = _audio_io_layout.main_output_channels.unwrap().get() as usize;
-
-        self.temp_buffer_1 = vec![vec![0.0; max_buffer_size]; num_channels];
-        self.temp_buffer_2 = vec![vec![0.0; max_buffer_size]; num_channels];
-
-        // Pre-allocate FFT buffers — must happen here so the audio thread never allocates.
-        #[cfg(feature = "dynamic_eq")]
+        #[cfg(feature = "conditioning")]
         {
-            use realfft::RealFftPlanner;
-            let mut planner = RealFftPlanner::<f32>::new();
-            let fft = planner.plan_fft_forward(spectral::FFT_SIZE);
-            self.fft_input = fft.make_input_vec();
-            self.fft_output = fft.make_output_vec();
-            self.fft_scratch = fft.make_scratch_vec();
-            // Sidechain analysis buffers (same FFT size, separate allocation).
-            self.sc_fft_input = fft.make_input_vec();
-            self.sc_fft_output = fft.make_output_vec();
-            self.fft_engine = Some(fft);
-            self.fft_ring = vec![0.0_f32; spectral::FFT_SIZE];
-            self.fft_ring_pos = 0;
-            self.sc_ring = vec![0.0_f32; spectral::FFT_SIZE];
-            self.sc_ring_pos = 0;
-            self.sample_rate = sr;
-            // Hann window: w[n] = 0.5 * (1 - cos(2π*n / (N-1)))
-            self.fft_window = (0..spectral::FFT_SIZE)
-                .map(|n| {
-                    0.5 * (1.0
-                        - (std::f32::consts::TAU * n as f32 / (spectral::FFT_SIZE - 1) as f32)
-                            .cos())
-                })
-                .collect();
-            self.fft_magnitude_smooth = vec![0.0_f32; spectral::SPECTRUM_BINS];
+            self.input_conditioning = InputConditioning::new(sr);
         }
+        let channels = audio_io_layout.main_input_channels.map(NonZeroU32::get);
+        self.surround = (channels == Some(surround::CHANNELS as u32))
+            .then(|| Box::new(surround::Surround::new(max_block, sr)));
 
         true
     }
@@ -2406,95 +4998,366 @@ impl Plugin for BusChannelStrip {
     fn reset(&mut self) {
         // Reset buffers and envelopes here. This can be called from the audio thread and may not
         // allocate. You can remove this function if you do not need it.
-        #[cfg(feature = "buttercomp2")]
-        {
-            self.compressor.reset();
+        self.chain.reset();
+        self.spare_chain.reset();
+        if let Some(surround) = self.surround.as_mut() {
+            surround.reset();
         }
-        #[cfg(feature = "buttercomp2")]
+        #[cfg(feature = "transformer")]
+        self.transformer_sat.store(0.0);
+        #[cfg(feature = "pultec")]
+        self.load_monitor.reset();
+        #[cfg(feature = "sheen")]
         {
-            self.fet_compressor.reset();
+            self.sheen.reset();
         }
-        #[cfg(feature = "buttercomp2")]
+        #[cfg(feature = "conditioning")]
         {
-            self.vca_compressor.reset();
+            self.input_conditioning.reset();
         }
-        #[cfg(feature = "buttercomp2")]
-        {
-            self.optical_compressor.reset();
+        self.output_mean_square = 0.0;
+        self.stage_peaks = [0.0; spectral::STAGE_LEVELS];
+        self.scope_lr = 0.0;
+        self.scope_ll = 0.0;
+        self.scope_rr = 0.0;
+        self.scope_countdown = 0;
+        self.gr_history_acc.reset();
+        self.meter_clock.reset();
+        self.gr_history_tx.push(spectral::GrHistoryItem::Clear);
+        self.punch_clip.store(0.0);
+        self.engagement_share = [0.0; spectral::ENGAGEMENT_STAGES];
+        self.engagement_data.clear();
+        self.loudness.reset();
+        self.input_dynamics.reset();
+        self.output_dynamics.reset();
+        self.analyzer_ring.request_reset();
+        self.analyzer_pending = 0;
+        self.block_scheduler.reset();
+        self.transport_watch.reset();
+        self.delta_tap.reset();
+        self.order_fade.reset();
+        self.duck.reset();
+        self.key_tap.reset();
+        self.safety.reset();
+        self.dither.reset();
+        self.safety_fault
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        self.loudness_data.store(
+            self.loudness.momentary(),
+            self.loudness.short_term(),
+            self.loudness.integrated(),
+        );
+        self.publish_dynamics();
+    }
+
+    fn process(
+        &mut self,
+        buffer: &mut Buffer,
+        aux: &mut AuxiliaryBuffers,
+        context: &mut impl ProcessContext<Self>,
+    ) -> ProcessStatus {
+        let transport = context.transport();
+        let jumped = self.transport_watch.update(
+            transport.playing,
+            transport.pos_samples(),
+            buffer.samples(),
+        );
+        if jumped && self.params.global_transport_reset.value() {
+            self.reset_envelopes();
         }
-        #[cfg(feature = "dynamic_eq")]
-        {
-            self.dynamic_eq.reset();
+
+        let sidechain = aux.inputs.first_mut().map(|sc| &*sc.as_slice());
+        // The scheduler is taken out for the call so the chain can borrow
+        // `self`; `take` leaves an empty one behind and allocates nothing.
+        let mut scheduler = std::mem::take(&mut self.block_scheduler);
+        scheduler.set_size(self.params.block_size.value().samples());
+        self.block_latency = scheduler.latency();
+
+        // MIDI duck triggers. The scheduler's chain runs a block behind the
+        // host buffer, so there they fire at the start of its next block.
+        while let Some(event) = context.next_event() {
+            if let NoteEvent::NoteOn {
+                timing,
+                channel,
+                note,
+                velocity,
+                ..
+            } = event
+            {
+                if velocity > 0.0
+                    && self.params.duck_enable.value()
+                    && duck::matches(
+                        self.params.duck_channel.value(),
+                        self.params.duck_note.value(),
+                        channel,
+                        note,
+                    )
+                {
+                    let offset = if scheduler.is_active() {
+                        0
+                    } else {
+                        timing as usize
+                    };
+                    self.duck.trigger(offset);
+                }
+            }
         }
-        #[cfg(feature = "transformer")]
-        {
-            self.transformer.reset();
+        if scheduler.is_active() {
+            scheduler.run(buffer, sidechain, |block, sc| {
+                self.process_block(block, sc, context);
+            });
+        } else {
+            self.process_block(buffer, sidechain, context);
         }
-        #[cfg(feature = "pultec")]
-        {
-            self.pultec.reset();
+        self.block_scheduler = scheduler;
+        ProcessStatus::Normal
+    }
+}
+
+#[cfg(feature = "plugin")]
+impl BusChannelStrip {
+    /// Clear the state that carries level history from one place in the
+    /// song to another: detector envelopes and the analyzer's averages.
+    /// The static EQs and the Haas/Sheen delay lines keep running, so the
+    /// seam doesn't click.
+    fn reset_envelopes(&mut self) {
+        self.chain.reset_envelopes();
+        self.spare_chain.reset_envelopes();
+        if let Some(surround) = self.surround.as_mut() {
+            surround.reset_envelopes();
         }
-        #[cfg(feature = "punch")]
-        {
-            self.punch.reset();
+        self.duck.reset();
+        self.analyzer_ring.request_reset();
+        self.analyzer_pending = 0;
+    }
+
+    /// Advance the MIDI duck envelope over one block of `samples` into
+    /// `duck_gains`. Turning the duck off drops any envelope in flight.
+    fn render_duck(&mut self, samples: usize) {
+        self.ducking = false;
+        if !self.params.duck_enable.value() {
+            self.duck.reset();
+            return;
         }
-        #[cfg(feature = "haas")]
-        {
-            self.haas.reset();
+        if !self.duck.is_active() || samples > self.duck_gains.len() {
+            return;
         }
-        #[cfg(feature = "sheen")]
-        {
-            self.sheen.reset();
+        let gains = &mut self.duck_gains[..samples];
+        gains.fill(1.0);
+        self.duck.process(
+            &mut [gains],
+            duck::DuckSettings {
+                depth_db: self.params.duck_depth.value(),
+                attack_ms: self.params.duck_attack.value(),
+                hold_ms: self.params.duck_hold.value(),
+                release_ms: self.params.duck_release.value(),
+            },
+        );
+        self.ducking = true;
+    }
+
+    /// Apply this block's duck gains if the duck sits on slot `boundary`,
+    /// counted as in `duck::DuckPoint::boundary`; true if it did.
+    fn duck_at(&self, buffer: &mut Buffer, boundary: usize) -> bool {
+        if !self.ducking || self.params.duck_point.value().boundary() != boundary {
+            return false;
         }
+        self.apply_duck(buffer);
+        true
     }
 
-    fn process(
+    /// Multiply `buffer` by this block's rendered duck gains.
+    fn apply_duck(&self, buffer: &mut Buffer) {
+        let gains = &self.duck_gains[..buffer.samples()];
+        for channel in buffer.as_slice() {
+            for (sample, &gain) in channel.iter_mut().zip(gains) {
+                *sample *= gain;
+            }
+        }
+    }
+
+    /// Run the slot modules in `order` on `buffer` with the spare chain and
+    /// none of the chain's metering or taps: the outgoing order while a
+    /// reorder crossfades. Runs after the live pass, whose readings it
+    /// leaves as they were.
+    fn run_order(
         &mut self,
+        order: reorder::ModuleOrder,
         buffer: &mut Buffer,
-        aux: &mut AuxiliaryBuffers,
-        _context: &mut impl ProcessContext<Self>,
-    ) -> ProcessStatus {
-        // Global bypass — pass audio through untouched.
-        if self.params.global_bypass.value() {
-            return ProcessStatus::Normal;
+        sidechain: Option<&[&mut [f32]]>,
+    ) {
+        std::mem::swap(&mut self.chain, &mut self.spare_chain);
+        let metered = std::mem::replace(&mut self.metered, false);
+        let readings = (self.comp_gr_db, self.dyneq_gr_db, self.comp_channel_gr_db);
+        self.comp_gr_db = 0.0;
+        self.dyneq_gr_db = 0.0;
+        self.comp_channel_gr_db = [0.0; 2];
+        let mut seen = [false; 8];
+        for mt in order {
+            let idx = module_type_index(mt);
+            if mt != ModuleType::Empty && !seen[idx] {
+                seen[idx] = true;
+                self.dispatch_module(mt, buffer, sidechain);
+            }
         }
+        (self.comp_gr_db, self.dyneq_gr_db, self.comp_channel_gr_db) = readings;
+        self.metered = metered;
+        std::mem::swap(&mut self.chain, &mut self.spare_chain);
+    }
 
-        // Auto-gain: capture input RMS before any processing.
-        let auto_gain_enabled = self.params.global_auto_gain.value();
-        let pre_rms = if auto_gain_enabled {
-            rms_linear(buffer.as_slice())
-        } else {
-            0.0
-        };
+    /// One linking group's pass: input conditioning, the slot modules in
+    /// the user's order and Sheen, on `buffer` (the whole buffer on a
+    /// stereo bus). `pre_rms` is the front pair's input level, for the
+    /// flow strip. Returns the order the slots ran in.
+    fn run_chain(
+        &mut self,
+        buffer: &mut Buffer,
+        sidechain: Option<&[&mut [f32]]>,
+        context: &mut impl ProcessContext<Self>,
+        pre_rms: f32,
+        publish_meters: bool,
+    ) -> reorder::ModuleOrder {
+        self.comp_gr_db = 0.0;
+        self.dyneq_gr_db = 0.0;
+        self.comp_channel_gr_db = [0.0; 2];
+
+        let dual_mono = self.dual_mono();
+        let analyzer_tap = self.params.analyzer_tap.value().boundary();
+        let mut stage_peak = 0.0;
+        let mut stage_mid_side = [0.0; 2];
+        if self.metered {
+            stage_peak = peak_linear(buffer.as_slice());
+            self.stage_peaks[0] = self.stage_peaks[0].max(stage_peak);
+            if publish_meters {
+                self.stage_levels.store_db(0, util::gain_to_db(pre_rms));
+                stage_mid_side = mid_side_rms(buffer.as_slice());
+                self.store_stage_mid_side(0, stage_mid_side);
+            }
+            self.capture_match_spectra(buffer, sidechain);
+            self.input_dynamics.process(buffer.as_slice());
+            if analyzer_tap == 0 {
+                self.tap_analyzer(buffer.as_slice_immutable());
+            }
+        }
+
+        // Input conditioning — pinned ahead of slot 1. The input meter,
+        // the analyzer's input tap and auto-gain's reference all read the
+        // signal as it arrived, so cleaning it up counts as processing.
+        #[cfg(feature = "conditioning")]
+        {
+            self.input_conditioning.update_parameters(
+                self.params.input_bypass.value(),
+                self.params.input_hpf_freq.value(),
+                self.params.input_hpf_slope.value(),
+                self.params.input_lpf_freq.value(),
+                self.params.input_lpf_slope.value(),
+                self.params.input_rotation.value(),
+                self.params.input_polarity.value(),
+            );
+            self.input_conditioning.process(buffer);
+        }
+        self.duck_at(buffer, 0);
 
         // Dispatch modules in user-chosen order.
         // Each of the seven module_order_N params selects which module lands
         // in slot N. Duplicates are deduplicated: if the user puts API5500
         // in two slots, the module only runs once. Any slot whose feature
         // is disabled at build time becomes a no-op inside dispatch_module.
-        let order = [
-            self.params.module_order_1.value(),
-            self.params.module_order_2.value(),
-            self.params.module_order_3.value(),
-            self.params.module_order_4.value(),
-            self.params.module_order_5.value(),
-            self.params.module_order_6.value(),
-            self.params.module_order_7.value(),
-        ];
+        // Every module works in place on the host's buffer, so any order
+        // costs the same as the default one: no copies, and no scratch sized
+        // to a block length or channel count the host may change later.
+        // During playback a reorder fades from the old order, on the spare
+        // chain, to the new one; see reorder.rs.
+        let requested = self.requested_order();
+        let (order, switch) = self.order_fade.begin_block(
+            requested,
+            context.transport().playing,
+            self.order_latency(&requested),
+        );
+        match switch {
+            reorder::ChainSwitch::Keep => {}
+            reorder::ChainSwitch::Fresh => {
+                self.spare_chain.reset();
+                std::mem::swap(&mut self.chain, &mut self.spare_chain);
+            }
+            reorder::ChainSwitch::Restart => self.chain.reset(),
+            reorder::ChainSwitch::Reverse => {
+                std::mem::swap(&mut self.chain, &mut self.spare_chain);
+            }
+        }
+        self.report_latency(&order, context);
+        // The outgoing order runs on a copy of the input after the live
+        // pass; see `run_order`.
+        let outgoing = self.order_fade.take_outgoing(buffer.as_slice_immutable());
         // Sized to 8: indices 0..6 are real modules, index 7 is Empty.
         // Empties are skipped before the dedup check so the slot can be
         // unoccupied in any number of positions without losing pass-through.
+        // A slot that runs nothing reports the level it was handed, so the
+        // flow strip always shows the level leaving each position.
         let mut seen = [false; 8];
-        for mt in order {
-            if mt == ModuleType::Empty {
-                continue;
-            }
+        let mut stage_rms = pre_rms;
+        // Delta listen captures the chosen module's input around its slot.
+        let delta_module = self.params.global_delta_listen.value().module();
+        self.delta_tap.select(delta_module);
+        for (slot, mt) in order.into_iter().enumerate() {
             let idx = module_type_index(mt);
-            if seen[idx] {
-                continue;
+            if mt != ModuleType::Empty && !seen[idx] {
+                seen[idx] = true;
+                let listen = self.metered && delta_module == Some(mt);
+                if listen {
+                    let (latency, _) = self.module_latency(mt);
+                    self.delta_tap.capture(buffer.as_slice(), latency);
+                }
+                self.dispatch_module(mt, buffer, sidechain);
+                if listen {
+                    // Taken after the module ran, so it reflects this
+                    // block's settings.
+                    let (_, cascade) = self.module_latency(mt);
+                    self.delta_tap.subtract_from(buffer.as_slice(), cascade);
+                }
+                stage_peak = peak_linear(buffer.as_slice());
+                if publish_meters {
+                    stage_rms = rms_linear(buffer.as_slice());
+                    stage_mid_side = mid_side_rms(buffer.as_slice());
+                }
+            }
+            if self.duck_at(buffer, slot + 1) {
+                stage_peak = peak_linear(buffer.as_slice());
+                if publish_meters {
+                    stage_rms = rms_linear(buffer.as_slice());
+                    stage_mid_side = mid_side_rms(buffer.as_slice());
+                }
+            }
+            if self.metered {
+                self.stage_peaks[slot + 1] = self.stage_peaks[slot + 1].max(stage_peak);
+            }
+            if publish_meters {
+                self.stage_levels
+                    .store_db(slot + 1, util::gain_to_db(stage_rms));
+                self.store_stage_mid_side(slot + 1, stage_mid_side);
+            }
+            if analyzer_tap == slot + 1 && self.metered {
+                self.tap_analyzer(buffer.as_slice_immutable());
+            }
+        }
+        if let Some((old_order, mut path)) = outgoing {
+            self.run_order(old_order, path.buffer(), sidechain);
+            // A duck on a slot boundary lands on the old order's output as
+            // a whole: the old order is on its way out, and the gains are
+            // the ones the live pass applies, so the two paths duck together.
+            let point = self.params.duck_point.value().boundary();
+            if self.ducking && point != 0 && point != duck::OUTPUT_BOUNDARY {
+                self.apply_duck(path.buffer());
             }
-            seen[idx] = true;
-            self.dispatch_module(mt, buffer, aux);
+            self.order_fade.blend(path, buffer.as_slice());
+        }
+        if self.metered {
+            self.gr_history_acc.add_block(
+                self.comp_gr_db,
+                self.dyneq_gr_db,
+                buffer.samples(),
+                &mut self.gr_history_tx,
+            );
         }
 
         // 6.5) Sheen — pinned master-end polish coat. Always last in the
@@ -2509,9 +5372,11 @@ impl Plugin for BusChannelStrip {
         // buffer — so the smoother takes ~240 buffers to reach a new
         // target (perceptually unresponsive). Filter biquad state acts
         // as the per-sample smoother; param-level snapping into a fresh
-        // set of coefficients is fine for a slow user-drag rate.
+        // set of coefficients is fine for a slow user-drag rate. A mono
+        // surround group has no width to work on, and the LFE can skip
+        // Sheen with the other saturation stages.
         #[cfg(feature = "sheen")]
-        {
+        if self.saturates() {
             self.sheen.update_parameters(
                 self.params.sheen_bypass.value(),
                 self.params.sheen_body_db.value(),
@@ -2520,31 +5385,121 @@ impl Plugin for BusChannelStrip {
                 self.params.sheen_presence_bypass.value(),
                 self.params.sheen_air_db.value(),
                 self.params.sheen_air_bypass.value(),
-                self.params.sheen_warmth.value(),
+                analog_scaled(
+                    &self.params,
+                    AnalogTarget::SheenWarmth,
+                    self.params.sheen_warmth.value(),
+                ),
                 self.params.sheen_warmth_bypass.value(),
                 self.params.sheen_width.value(),
-                self.params.sheen_width_bypass.value(),
+                self.params.sheen_width_bypass.value() || dual_mono || self.group.is_mono(),
             );
             self.sheen.process(buffer);
+        } else {
+            self.sheen.reset();
+        }
+        order
+    }
+
+    /// The whole chain for one block: host-sized in `Host` mode, otherwise
+    /// exactly `block_size` samples from the scheduler. A global bypass
+    /// still passes through the scheduler's FIFO, so toggling it doesn't
+    /// shift the audio against the latency the host compensates for.
+    fn process_block(
+        &mut self,
+        buffer: &mut Buffer,
+        sidechain: Option<&[&mut [f32]]>,
+        context: &mut impl ProcessContext<Self>,
+    ) {
+        // Global bypass — pass audio through untouched.
+        if self.params.global_bypass.value() {
+            return;
         }
+        #[cfg(feature = "pultec")]
+        let started = std::time::Instant::now();
+
+        // Capture input RMS before any processing — the reference for
+        // auto-gain and the first point of the signal-flow level strip. On
+        // a 5.1 bus both read the front pair, like every other meter.
+        let front = buffer.channels().min(2);
+        let auto_gain_enabled = self.params.global_auto_gain.value();
+        let dual_mono = self.dual_mono();
+        let pre_rms = rms_linear(&buffer.as_slice()[..front]);
+        // Dual mono compensates each channel against its own input.
+        let pre_rms_auto = if auto_gain_enabled && dual_mono {
+            channel_rms(&buffer.as_slice()[..front])
+        } else {
+            [pre_rms; 2]
+        };
+        // Meters and the analyzer hand-off publish at the editor's refresh
+        // rate, not once per buffer; see `spectral::MeterClock`.
+        let publish_meters = self.meter_clock.tick(
+            buffer.samples(),
+            self.sample_rate,
+            self.meter_rate_hz
+                .load(std::sync::atomic::Ordering::Relaxed),
+        );
+        self.render_duck(buffer.samples());
+
+        // The front pair runs on the strip's own state and feeds the
+        // meters. On a 5.1 bus the other groups follow, each swapped in for
+        // its turn; see surround.rs.
+        let order = match self.surround.take() {
+            None => self.run_chain(buffer, sidechain, context, pre_rms, publish_meters),
+            Some(mut bus) => {
+                let mut order = self.requested_order();
+                bus.view.run(buffer, surround::LinkGroup::Front, |pair| {
+                    order = self.run_chain(pair, sidechain, context, pre_rms, publish_meters);
+                });
+                for state in &mut bus.groups {
+                    self.swap_group(state);
+                    self.metered = false;
+                    bus.view.run(buffer, self.group, |pair| {
+                        self.run_chain(pair, sidechain, context, pre_rms, false);
+                    });
+                    self.metered = true;
+                    self.swap_group(state);
+                }
+                self.surround = Some(bus);
+                order
+            }
+        };
 
         // 7) Auto-gain compensation (before master trim so it doesn't fight the user's gain knob).
         if auto_gain_enabled {
-            let post_rms = rms_linear(buffer.as_slice());
-            if post_rms > 1e-6 {
-                let target = (pre_rms / post_rms).clamp(AUTO_GAIN_MIN, AUTO_GAIN_MAX);
-                self.auto_gain_correction = self.auto_gain_correction * AUTO_GAIN_SMOOTH
-                    + target * (1.0 - AUTO_GAIN_SMOOTH);
+            let post_rms = if dual_mono {
+                channel_rms(&buffer.as_slice()[..front])
+            } else {
+                [rms_linear(&buffer.as_slice()[..front]); 2]
+            };
+            let smooth = block_smoothing(AUTO_GAIN_TAU_S, buffer.samples(), self.sample_rate);
+            for ((correction, pre), post) in self
+                .auto_gain_correction
+                .iter_mut()
+                .zip(pre_rms_auto)
+                .zip(post_rms)
+            {
+                if post > 1e-6 {
+                    let target = (pre / post).clamp(AUTO_GAIN_MIN, AUTO_GAIN_MAX);
+                    *correction = *correction * smooth + target * (1.0 - smooth);
+                }
             }
-            // Apply smoothed correction.
-            for ch in buffer.as_slice() {
+            // Apply smoothed correction; the channels past the front pair
+            // take the mean of its two.
+            let [left, right] = self.auto_gain_correction;
+            for (i, ch) in buffer.as_slice().iter_mut().enumerate() {
+                let correction = match i {
+                    0 => left,
+                    1 => right,
+                    _ => 0.5 * (left + right),
+                };
                 for s in ch.iter_mut() {
-                    *s *= self.auto_gain_correction;
+                    *s *= correction;
                 }
             }
         } else {
             // Reset to unity so re-enabling starts smoothly from 1.0.
-            self.auto_gain_correction = 1.0;
+            self.auto_gain_correction = [1.0; 2];
         }
 
         // 8) Master output trim (intentional user gain, always last).
@@ -2554,11 +5509,160 @@ impl Plugin for BusChannelStrip {
                 *sample *= gain;
             }
         }
+        self.duck_at(buffer, duck::OUTPUT_BOUNDARY);
+
+        // 9) Publish the smoothed output level (post master gain) so the
+        // editor can match loudness between A/B snapshots, then the flow
+        // strip's output level and every stage's peak.
+        let out_rms = rms_linear(&buffer.as_slice()[..front]);
+        let smooth = block_smoothing(OUTPUT_LEVEL_TAU_S, buffer.samples(), self.sample_rate);
+        self.output_mean_square =
+            self.output_mean_square * smooth + out_rms * out_rms * (1.0 - smooth);
+        let output = spectral::STAGE_LEVELS - 1;
+        self.stage_peaks[output] =
+            self.stage_peaks[output].max(peak_linear(&buffer.as_slice()[..front]));
+        if publish_meters {
+            self.output_level
+                .store_db(util::gain_to_db(self.output_mean_square.sqrt()));
+            self.stage_levels
+                .store_db(output, util::gain_to_db(out_rms));
+            self.store_stage_mid_side(output, mid_side_rms(&buffer.as_slice()[..front]));
+            for (stage, peak) in self.stage_peaks.iter_mut().enumerate() {
+                self.stage_levels
+                    .store_peak_db(stage, util::gain_to_db(*peak));
+                *peak = 0.0;
+            }
+        }
 
-        ProcessStatus::Normal
+        // 10) Stereo scope tap (post master gain, what the bus sends on).
+        self.publish_stereo_scope(buffer, publish_meters);
+        if self.params.analyzer_tap.value().boundary() == ANALYZER_TAP_OUTPUT {
+            self.tap_analyzer(&buffer.as_slice()[..front]);
+        }
+
+        // 11) Loudness and dynamics for the metering bridge (same tap as
+        // the scope). PLR's peak hold restarts with integrated loudness.
+        // Loudness weighs in every channel of a 5.1 bus; see loudness.rs.
+        if self.loudness_data.take_reset_request() {
+            self.loudness.reset_integrated();
+            self.output_dynamics.reset_peak_hold();
+        }
+        self.loudness.process(buffer.as_slice());
+        self.output_dynamics.process(buffer.as_slice());
+        // A requested learn pass starts after this buffer, so both of its
+        // taps begin on the same one.
+        #[cfg(feature = "punch")]
+        {
+            if let Some(levels) = self
+                .loudness_learn
+                .process_output(&buffer.as_slice()[..front])
+            {
+                self.loudness_learn_data.publish(
+                    levels.input_lufs,
+                    levels.input_peak_db,
+                    levels.output_lufs,
+                );
+            }
+            if self.loudness_learn_data.take_learn_request() {
+                self.loudness_learn.start();
+            }
+        }
+        if publish_meters {
+            self.loudness_data.store(
+                self.loudness.momentary(),
+                self.loudness.short_term(),
+                self.loudness.integrated(),
+            );
+            self.publish_dynamics();
+        }
+
+        // 12) Hand the FFT work to the background thread. At most one drain
+        // is queued at a time; samples keep landing in the ring meanwhile
+        // (it holds a whole refresh interval even at 15 Hz and 192 kHz).
+        let hop = self.params.analyzer_overlap.value().hop();
+        if publish_meters && self.analyzer_pending >= hop && self.analyzer_ring.try_queue() {
+            self.analyzer_pending = 0;
+            context.execute_background(BackgroundTask::AnalyzeSpectrum);
+        }
+        #[cfg(feature = "api5500")]
+        if self.chain.eq_api5500.request_fir_design(&self.eq_fir) {
+            context.execute_background(BackgroundTask::DesignEqFir);
+        }
+        // The transformer's harmonic readout follows its settings, bypassed
+        // or not; a knob sweep collapses into few probes.
+        #[cfg(feature = "transformer")]
+        {
+            let settings = transformer_probe_settings(&self.params);
+            if self.transformer_probed != Some(settings) && self.harmonic_data.try_queue() {
+                self.transformer_probed = Some(settings);
+                context.execute_background(BackgroundTask::ProbeTransformer);
+            }
+        }
+
+        // 13) Print mode: keep a curve printed for the current drive while
+        // the switch is on, so it's ready the moment the load climbs, and
+        // fold this buffer's processing time into the load estimate.
+        #[cfg(feature = "pultec")]
+        {
+            let print_enabled = self.params.pultec_print.value();
+            if print_enabled
+                && self
+                    .chain
+                    .pultec
+                    .request_print(self.pultec_print_curve.read(), &self.pultec_print)
+            {
+                context.execute_background(BackgroundTask::PrintTube);
+            }
+            let printing = print_enabled
+                && !self.params.pultec_bypass.value()
+                && order.contains(&ModuleType::PultecEQ)
+                && self.chain.pultec.is_printed();
+            self.print_active
+                .store(printing, std::sync::atomic::Ordering::Relaxed);
+            self.load_monitor.update(
+                started.elapsed().as_secs_f32(),
+                buffer.samples() as f32 / self.sample_rate,
+            );
+        }
+
+        // 14) Delta, key and SC listen replace what leaves the strip, after
+        // every meter: the meters keep reading the program while you
+        // audition. The delta skips Sheen, auto-gain and master gain, so it
+        // plays at the level the module changed it by; key listen plays the
+        // detector's key as its key EQ shapes it. Key listen wins over the
+        // delta, and SC listen over both.
+        if self.params.global_delta_listen.value().module().is_some() {
+            self.delta_tap.replace(buffer.as_slice());
+        }
+        if self.params.global_key_listen.value() != KeyListen::Off {
+            self.key_tap.replace(buffer.as_slice());
+        }
+        if self.params.global_sc_listen.value() {
+            monitor_key(buffer, sidechain);
+        }
+
+        // 15) Safety stage, last before the dither, so no
+        // module setting (or the key in SC listen) can send NaN, DC or an
+        // overload past +6 dBFS to the monitors.
+        if self.params.global_safety.value() {
+            self.safety.process(buffer.as_slice());
+        } else {
+            self.safety.reset();
+        }
+        self.safety_fault
+            .store(self.safety.fault(), std::sync::atomic::Ordering::Relaxed);
+
+        // 16) Dither to the print's word length, after everything that
+        // changes a sample, so the host's conversion has nothing to round.
+        self.dither.process(
+            buffer.as_slice(),
+            self.params.global_dither.value(),
+            self.params.global_dither_shaping.value(),
+        );
     }
 }
 
+#[cfg(feature = "plugin")]
 impl ClapPlugin for BusChannelStrip {
     const CLAP_ID: &'static str = "com.your-domain.your-plugin-name (use underscores)";
     const CLAP_DESCRIPTION: Option<&'static str> = Some("A short description of your plugin");
@@ -2566,9 +5670,14 @@ impl ClapPlugin for BusChannelStrip {
     const CLAP_SUPPORT_URL: Option<&'static str> = None;
 
     // Don't forget to change these features
-    const CLAP_FEATURES: &'static [ClapFeature] = &[ClapFeature::AudioEffect, ClapFeature::Stereo];
+    const CLAP_FEATURES: &'static [ClapFeature] = &[
+        ClapFeature::AudioEffect,
+        ClapFeature::Stereo,
+        ClapFeature::Surround,
+    ];
 }
 
+#[cfg(feature = "plugin")]
 impl Vst3Plugin for BusChannelStrip {
     const VST3_CLASS_ID: [u8; 16] = *b"Exactly16Chars!!";
 
@@ -2577,5 +5686,7 @@ impl Vst3Plugin for BusChannelStrip {
         &[Vst3SubCategory::Fx, Vst3SubCategory::Dynamics];
 }
 
+#[cfg(feature = "plugin")]
 nih_export_clap!(BusChannelStrip);
+#[cfg(feature = "plugin")]
 nih_export_vst3!(BusChannelStrip);
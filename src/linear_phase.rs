@@ -0,0 +1,421 @@
+// src/linear_phase.rs
+// Linear-phase FIR for the API5500's mastering mode.
+//
+// Three parts, split by thread:
+//   • `FirDesigner` (background thread) samples a zero-phase magnitude
+//     response on an FFT_LEN grid, inverse-transforms it to a centred
+//     impulse, truncates that to TAPS with a symmetric Hann taper and keeps
+//     the kernel's spectrum.
+//   • `KernelExchange` hands finished kernels to the audio thread through a
+//     sequence lock: the writer bumps `seq` to odd, stores the bins and bumps
+//     it back to even; a reader that sees the same even value before and
+//     after its copy has a whole kernel, otherwise it retries next buffer.
+//   • `LinearPhaseFir` (audio thread) applies the kernel per channel with
+//     overlap-add in BLOCK-sample hops. Every buffer and FFT plan is
+//     allocated in `new`.
+//
+// The filter delays by `LATENCY_SAMPLES`: one BLOCK of input buffering plus
+// the FIR's group delay. The flat kernel is an exact delay of that length,
+// which is what bypass runs so host compensation stays valid.
+
+use realfft::num_complex::Complex;
+use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
+use std::sync::atomic::{fence, AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// FIR length. Odd, so the impulse has a centre sample.
+const TAPS: usize = 4095;
+/// Samples between convolutions.
+const BLOCK: usize = 2048;
+/// Convolution and design FFT size; BLOCK + TAPS - 1 fits without wrap.
+const FFT_LEN: usize = 8192;
+/// Bins in a kernel spectrum.
+pub const KERNEL_BINS: usize = FFT_LEN / 2 + 1;
+const CENTRE: usize = (TAPS - 1) / 2;
+
+/// Delay the host must compensate while the FIR is in the path.
+pub const LATENCY_SAMPLES: u32 = (BLOCK + CENTRE) as u32;
+
+/// Builds kernel spectra. Owns its own FFT plans and scratch, so it can
+/// live on the background thread.
+pub struct FirDesigner {
+    forward: Arc<dyn RealToComplex<f32>>,
+    inverse: Arc<dyn ComplexToReal<f32>>,
+    time: Vec<f32>,
+    spectrum: Vec<Complex<f32>>,
+    forward_scratch: Vec<Complex<f32>>,
+    inverse_scratch: Vec<Complex<f32>>,
+    taper: Vec<f32>,
+    /// Tapered impulse.
+    fir: Vec<f32>,
+}
+
+impl FirDesigner {
+    pub fn new() -> Self {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let forward = planner.plan_fft_forward(FFT_LEN);
+        let inverse = planner.plan_fft_inverse(FFT_LEN);
+        let taper = (0..TAPS)
+            .map(|n| {
+                let x = std::f32::consts::TAU * n as f32 / (TAPS - 1) as f32;
+                0.5 - 0.5 * x.cos()
+            })
+            .collect();
+        Self {
+            time: forward.make_input_vec(),
+            spectrum: forward.make_output_vec(),
+            forward_scratch: forward.make_scratch_vec(),
+            inverse_scratch: inverse.make_scratch_vec(),
+            forward,
+            inverse,
+            taper,
+            fir: vec![0.0; TAPS],
+        }
+    }
+
+    /// Write the kernel for a magnitude response, given in linear gain at
+    /// `omega` radians per sample (0..=π), into `kernel` (KERNEL_BINS long).
+    /// The kernel is pre-scaled by 1/FFT_LEN for the convolution's inverse
+    /// transform.
+    pub fn design(&mut self, magnitude: impl Fn(f32) -> f32, kernel: &mut [Complex<f32>]) {
+        for (k, bin) in self.spectrum.iter_mut().enumerate() {
+            let omega = std::f32::consts::PI * k as f32 / (KERNEL_BINS - 1) as f32;
+            *bin = Complex::new(magnitude(omega), 0.0);
+        }
+        if self
+            .inverse
+            .process_with_scratch(
+                &mut self.spectrum,
+                &mut self.time,
+                &mut self.inverse_scratch,
+            )
+            .is_err()
+        {
+            return;
+        }
+        // The zero-phase impulse is centred on sample 0 and wraps around;
+        // rotate its middle TAPS samples to start at 0 and taper them.
+        let norm = 1.0 / FFT_LEN as f32;
+        for (n, (tap, &w)) in self.fir.iter_mut().zip(&self.taper).enumerate() {
+            *tap = self.time[(n + FFT_LEN - CENTRE) % FFT_LEN] * norm * w;
+        }
+        self.time[..TAPS].copy_from_slice(&self.fir);
+        self.time[TAPS..].fill(0.0);
+        if self
+            .forward
+            .process_with_scratch(&mut self.time, kernel, &mut self.forward_scratch)
+            .is_err()
+        {
+            return;
+        }
+        for bin in kernel.iter_mut() {
+            *bin *= norm;
+        }
+    }
+}
+
+impl Default for FirDesigner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Background → audio hand-off for kernels; see the sequence lock above.
+pub struct KernelExchange {
+    seq: AtomicU32,
+    re: Vec<AtomicU32>,
+    im: Vec<AtomicU32>,
+}
+
+impl KernelExchange {
+    pub fn new() -> Self {
+        Self {
+            seq: AtomicU32::new(0),
+            re: (0..KERNEL_BINS).map(|_| AtomicU32::new(0)).collect(),
+            im: (0..KERNEL_BINS).map(|_| AtomicU32::new(0)).collect(),
+        }
+    }
+
+    /// **Background thread only** (single writer).
+    pub fn publish(&self, kernel: &[Complex<f32>]) {
+        self.seq.fetch_add(1, Ordering::Relaxed);
+        fence(Ordering::Release);
+        for ((re, im), bin) in self.re.iter().zip(&self.im).zip(kernel) {
+            re.store(bin.re.to_bits(), Ordering::Relaxed);
+            im.store(bin.im.to_bits(), Ordering::Relaxed);
+        }
+        self.seq.fetch_add(1, Ordering::Release);
+    }
+
+    /// **Audio thread only.** Copy the newest kernel into `out` if it is
+    /// newer than `*seen` and was not being written meanwhile.
+    pub fn read_into(&self, out: &mut [Complex<f32>], seen: &mut u32) -> bool {
+        let before = self.seq.load(Ordering::Acquire);
+        if before == *seen || before % 2 == 1 {
+            return false;
+        }
+        for ((re, im), bin) in self.re.iter().zip(&self.im).zip(out.iter_mut()) {
+            *bin = Complex::new(
+                f32::from_bits(re.load(Ordering::Relaxed)),
+                f32::from_bits(im.load(Ordering::Relaxed)),
+            );
+        }
+        fence(Ordering::Acquire);
+        if self.seq.load(Ordering::Relaxed) != before {
+            return false;
+        }
+        *seen = before;
+        true
+    }
+}
+
+impl Default for KernelExchange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct ChannelState {
+    input: Vec<f32>,
+    output: Vec<f32>,
+    overlap: Vec<f32>,
+}
+
+impl ChannelState {
+    fn new() -> Self {
+        Self {
+            input: vec![0.0; BLOCK],
+            output: vec![0.0; BLOCK],
+            overlap: vec![0.0; FFT_LEN],
+        }
+    }
+
+    fn reset(&mut self) {
+        self.input.fill(0.0);
+        self.output.fill(0.0);
+        self.overlap.fill(0.0);
+    }
+}
+
+/// Overlap-add convolver. Never designs kernels itself: it either runs the
+/// latest one from a `KernelExchange` or the flat one built in `new`.
+pub struct LinearPhaseFir {
+    forward: Arc<dyn RealToComplex<f32>>,
+    inverse: Arc<dyn ComplexToReal<f32>>,
+    kernel: Vec<Complex<f32>>,
+    flat_kernel: Vec<Complex<f32>>,
+    /// Run `flat_kernel` instead of `kernel`.
+    flat: bool,
+    /// Exchange sequence of the kernel in `kernel`.
+    kernel_seq: u32,
+    time: Vec<f32>,
+    spectrum: Vec<Complex<f32>>,
+    forward_scratch: Vec<Complex<f32>>,
+    inverse_scratch: Vec<Complex<f32>>,
+    channels: [ChannelState; 2],
+    /// Position inside the current block, shared by both channels.
+    fill: usize,
+}
+
+impl LinearPhaseFir {
+    pub fn new() -> Self {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let forward = planner.plan_fft_forward(FFT_LEN);
+        let inverse = planner.plan_fft_inverse(FFT_LEN);
+        let mut flat_kernel = vec![Complex::new(0.0, 0.0); KERNEL_BINS];
+        FirDesigner::new().design(|_| 1.0, &mut flat_kernel);
+        Self {
+            kernel: flat_kernel.clone(),
+            flat_kernel,
+            flat: false,
+            kernel_seq: 0,
+            time: forward.make_input_vec(),
+            spectrum: forward.make_output_vec(),
+            forward_scratch: forward.make_scratch_vec(),
+            inverse_scratch: inverse.make_scratch_vec(),
+            forward,
+            inverse,
+            channels: [ChannelState::new(), ChannelState::new()],
+            fill: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for ch in &mut self.channels {
+            ch.reset();
+        }
+        self.fill = 0;
+    }
+
+    /// Pick up a newer kernel from `exchange`, if one is ready. It takes
+    /// effect at the next block without a gap.
+    pub fn poll_kernel(&mut self, exchange: &KernelExchange) {
+        exchange.read_into(&mut self.kernel, &mut self.kernel_seq);
+    }
+
+    /// Run the flat (pure delay) kernel instead of the designed one.
+    pub fn set_flat(&mut self, flat: bool) {
+        self.flat = flat;
+    }
+
+    /// Filter the first two channels in place; the strip is stereo.
+    pub fn process(&mut self, channels: &mut [&mut [f32]]) {
+        let len = channels.first().map_or(0, |ch| ch.len());
+        let active = channels.len().min(2);
+        for i in 0..len {
+            for (samples, state) in channels.iter_mut().zip(&mut self.channels) {
+                let x = samples[i];
+                samples[i] = state.output[self.fill];
+                state.input[self.fill] = x;
+            }
+            self.fill += 1;
+            if self.fill == BLOCK {
+                self.fill = 0;
+                for ch in 0..active {
+                    self.convolve(ch);
+                }
+            }
+        }
+    }
+
+    /// Overlap-add one block of channel `ch`.
+    fn convolve(&mut self, ch: usize) {
+        let state = &mut self.channels[ch];
+        let kernel = if self.flat {
+            &self.flat_kernel
+        } else {
+            &self.kernel
+        };
+        self.time[..BLOCK].copy_from_slice(&state.input);
+        self.time[BLOCK..].fill(0.0);
+        if self
+            .forward
+            .process_with_scratch(
+                &mut self.time,
+                &mut self.spectrum,
+                &mut self.forward_scratch,
+            )
+            .is_err()
+        {
+            return;
+        }
+        for (bin, &k) in self.spectrum.iter_mut().zip(kernel) {
+            *bin *= k;
+        }
+        if self
+            .inverse
+            .process_with_scratch(
+                &mut self.spectrum,
+                &mut self.time,
+                &mut self.inverse_scratch,
+            )
+            .is_err()
+        {
+            return;
+        }
+        for (acc, &y) in state.overlap.iter_mut().zip(&self.time) {
+            *acc += y;
+        }
+        state.output.copy_from_slice(&state.overlap[..BLOCK]);
+        state.overlap.copy_within(BLOCK.., 0);
+        state.overlap[FFT_LEN - BLOCK..].fill(0.0);
+    }
+}
+
+impl Default for LinearPhaseFir {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `input` plus `LATENCY_SAMPLES` of silence through both channels
+    /// and return the left output, latency included.
+    fn run(fir: &mut LinearPhaseFir, input: &[f32]) -> Vec<f32> {
+        let total = input.len() + LATENCY_SAMPLES as usize;
+        let mut left: Vec<f32> = input
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(0.0))
+            .take(total)
+            .collect();
+        let mut right = left.clone();
+        // Odd chunk sizes so block boundaries fall mid-buffer.
+        for (l, r) in left.chunks_mut(333).zip(right.chunks_mut(333)) {
+            fir.process(&mut [l, r]);
+        }
+        left
+    }
+
+    /// A convolver running `magnitude`, delivered through an exchange.
+    fn designed(magnitude: impl Fn(f32) -> f32) -> LinearPhaseFir {
+        let mut kernel = vec![Complex::new(0.0, 0.0); KERNEL_BINS];
+        FirDesigner::new().design(magnitude, &mut kernel);
+        let exchange = KernelExchange::new();
+        exchange.publish(&kernel);
+        let mut fir = LinearPhaseFir::new();
+        fir.poll_kernel(&exchange);
+        fir
+    }
+
+    #[test]
+    fn flat_kernel_is_a_pure_delay() {
+        let mut fir = LinearPhaseFir::new();
+        fir.set_flat(true);
+        let input: Vec<f32> = (0..5000)
+            .map(|n| ((n * 7919) % 200) as f32 / 100.0 - 1.0)
+            .collect();
+        let out = run(&mut fir, &input);
+        for (i, (&x, &y)) in input
+            .iter()
+            .zip(&out[LATENCY_SAMPLES as usize..])
+            .enumerate()
+        {
+            assert!((x - y).abs() < 1e-4, "sample {i}: {x} vs {y}");
+        }
+    }
+
+    #[test]
+    fn impulse_response_is_symmetric_and_sets_gain() {
+        // A bump of +6 dB around 0.3 rad/sample.
+        let bump = |w: f32| 1.0 + (-(w - 0.3).powi(2) / 0.002).exp();
+        let mut fir = designed(bump);
+
+        let mut impulse = vec![0.0; TAPS];
+        impulse[0] = 1.0;
+        let out = run(&mut fir, &impulse);
+        let h = &out[BLOCK..BLOCK + TAPS];
+        for k in 1..=CENTRE {
+            assert!(
+                (h[CENTRE + k] - h[CENTRE - k]).abs() < 1e-5,
+                "asymmetric at ±{k}"
+            );
+        }
+
+        // Steady-state sine gain follows the designed magnitude.
+        let mut fir = designed(bump);
+        let sine: Vec<f32> = (0..16384).map(|n| (0.3 * n as f32).sin()).collect();
+        let out = run(&mut fir, &sine);
+        let peak = out[12000..16000]
+            .iter()
+            .fold(0.0_f32, |m, &y| m.max(y.abs()));
+        assert!((peak - 2.0).abs() < 0.02, "peak {peak}");
+    }
+
+    #[test]
+    fn exchange_hands_over_each_kernel_once() {
+        let exchange = KernelExchange::new();
+        let mut out = vec![Complex::new(0.0, 0.0); KERNEL_BINS];
+        let mut seen = 0;
+        assert!(!exchange.read_into(&mut out, &mut seen));
+
+        let kernel = vec![Complex::new(0.5, -0.25); KERNEL_BINS];
+        exchange.publish(&kernel);
+        assert!(exchange.read_into(&mut out, &mut seen));
+        assert_eq!(out, kernel);
+        assert!(!exchange.read_into(&mut out, &mut seen));
+    }
+}
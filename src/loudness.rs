@@ -0,0 +1,361 @@
+// src/loudness.rs
+// ITU-R BS.1770 / EBU R128 loudness meter for the metering bridge.
+//
+// Signal path per channel: K-weighting (high shelf + high pass, coefficients
+// derived for the running sample rate the same way libebur128 does), then
+// mean-square energy summed across channels in 100 ms blocks. On a 5.1 bus
+// the surrounds count +1.5 dB and the LFE not at all, per BS.1770's channel
+// weights.
+//   • momentary  — mean of the last 4 blocks (400 ms window)
+//   • short-term — mean of the last 30 blocks (3 s window)
+//   • integrated — gated mean of every 400 ms window since the last reset
+//                  (absolute gate -70 LUFS, relative gate -10 LU)
+//
+// Integrated loudness keeps a fixed 0.1 LU histogram of window energies
+// instead of a growing list, so the meter never allocates after `new()` and
+// can run on the audio thread indefinitely.
+
+/// Reported for silence and before the first window completes.
+pub const LOUDNESS_FLOOR_LUFS: f32 = -70.0;
+
+/// Block hop in seconds (75 % overlap of the 400 ms momentary window).
+const BLOCK_S: f64 = 0.1;
+const MOMENTARY_BLOCKS: usize = 4;
+const SHORT_TERM_BLOCKS: usize = 30;
+/// Histogram covers the absolute gate up to +10 LUFS in 0.1 LU bins.
+const HIST_MAX_LUFS: f64 = 10.0;
+const HIST_BIN_LU: f64 = 0.1;
+const HIST_BINS: usize = ((HIST_MAX_LUFS - LOUDNESS_FLOOR_LUFS as f64) / HIST_BIN_LU) as usize;
+const RELATIVE_GATE_LU: f64 = -10.0;
+/// Channels metered: stereo, or 5.1 as L, R, C, LFE, Ls, Rs.
+const CHANNELS: usize = 6;
+/// BS.1770 channel weights in that order. A stereo buffer only uses the
+/// first two.
+const WEIGHTS: [f64; CHANNELS] = [1.0, 1.0, 1.0, 0.0, 1.41, 1.41];
+
+/// Transposed direct form II biquad in f64 — the K-weighting shelf sits at
+/// very low normalized frequencies where f32 state drifts.
+#[derive(Clone, Copy, Default)]
+struct Biquad64 {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad64 {
+    #[inline]
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.z1;
+        self.z1 = self.b1 * x - self.a1 * y + self.z2;
+        self.z2 = self.b2 * x - self.a2 * y;
+        y
+    }
+
+    fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
+}
+
+/// BS.1770 stage 1: +4 dB high shelf around 1.68 kHz (head diffraction).
+fn k_shelf(sample_rate: f64) -> Biquad64 {
+    let f0 = 1681.974_450_955_533;
+    let gain_db = 3.999_843_853_973_347;
+    let q = 0.707_175_236_955_419_6;
+    let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+    let vh = 10.0_f64.powf(gain_db / 20.0);
+    let vb = vh.powf(0.499_666_774_154_541_6);
+    let a0 = 1.0 + k / q + k * k;
+    Biquad64 {
+        b0: (vh + vb * k / q + k * k) / a0,
+        b1: 2.0 * (k * k - vh) / a0,
+        b2: (vh - vb * k / q + k * k) / a0,
+        a1: 2.0 * (k * k - 1.0) / a0,
+        a2: (1.0 - k / q + k * k) / a0,
+        ..Biquad64::default()
+    }
+}
+
+/// BS.1770 stage 2: RLB high pass around 38 Hz.
+fn k_highpass(sample_rate: f64) -> Biquad64 {
+    let f0 = 38.135_470_876_024_44;
+    let q = 0.500_327_037_323_877_3;
+    let k = (std::f64::consts::PI * f0 / sample_rate).tan();
+    let a0 = 1.0 + k / q + k * k;
+    Biquad64 {
+        b0: 1.0,
+        b1: -2.0,
+        b2: 1.0,
+        a1: 2.0 * (k * k - 1.0) / a0,
+        a2: (1.0 - k / q + k * k) / a0,
+        ..Biquad64::default()
+    }
+}
+
+fn energy_to_lufs(energy: f64) -> f64 {
+    if energy <= 0.0 {
+        f64::NEG_INFINITY
+    } else {
+        -0.691 + 10.0 * energy.log10()
+    }
+}
+
+fn to_display(lufs: f64) -> f32 {
+    (lufs as f32).max(LOUDNESS_FLOOR_LUFS)
+}
+
+pub struct LoudnessMeter {
+    shelf: [Biquad64; CHANNELS],
+    highpass: [Biquad64; CHANNELS],
+    block_len: usize,
+    block_filled: usize,
+    /// Weighted sum of K-weighted squares (all channels) in the current
+    /// block.
+    block_sum: f64,
+    /// Mean-square energy of the most recent blocks, ring-indexed.
+    blocks: [f64; SHORT_TERM_BLOCKS],
+    block_pos: usize,
+    blocks_seen: usize,
+    hist_count: [u32; HIST_BINS],
+    hist_energy: [f64; HIST_BINS],
+    momentary: f32,
+    short_term: f32,
+    integrated: f32,
+}
+
+impl LoudnessMeter {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut meter = Self {
+            shelf: [Biquad64::default(); CHANNELS],
+            highpass: [Biquad64::default(); CHANNELS],
+            block_len: 1,
+            block_filled: 0,
+            block_sum: 0.0,
+            blocks: [0.0; SHORT_TERM_BLOCKS],
+            block_pos: 0,
+            blocks_seen: 0,
+            hist_count: [0; HIST_BINS],
+            hist_energy: [0.0; HIST_BINS],
+            momentary: LOUDNESS_FLOOR_LUFS,
+            short_term: LOUDNESS_FLOOR_LUFS,
+            integrated: LOUDNESS_FLOOR_LUFS,
+        };
+        meter.set_sample_rate(sample_rate);
+        meter
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        let sr = sample_rate as f64;
+        self.shelf = [k_shelf(sr); CHANNELS];
+        self.highpass = [k_highpass(sr); CHANNELS];
+        self.block_len = ((sr * BLOCK_S) as usize).max(1);
+        self.reset();
+    }
+
+    /// Clear all windows, the integrated history and filter state.
+    pub fn reset(&mut self) {
+        for f in self.shelf.iter_mut().chain(self.highpass.iter_mut()) {
+            f.reset();
+        }
+        self.block_filled = 0;
+        self.block_sum = 0.0;
+        self.blocks = [0.0; SHORT_TERM_BLOCKS];
+        self.block_pos = 0;
+        self.blocks_seen = 0;
+        self.reset_integrated();
+        self.momentary = LOUDNESS_FLOOR_LUFS;
+        self.short_term = LOUDNESS_FLOOR_LUFS;
+    }
+
+    /// Restart integrated loudness only (the bridge's reset button).
+    pub fn reset_integrated(&mut self) {
+        self.hist_count = [0; HIST_BINS];
+        self.hist_energy = [0.0; HIST_BINS];
+        self.integrated = LOUDNESS_FLOOR_LUFS;
+    }
+
+    /// Feed one buffer. Channels beyond the sixth are ignored.
+    pub fn process(&mut self, channels: &[&mut [f32]]) {
+        let num_channels = channels.len().min(CHANNELS);
+        let Some(samples) = channels.first().map(|ch| ch.len()) else {
+            return;
+        };
+        for i in 0..samples {
+            for ch in 0..num_channels {
+                if WEIGHTS[ch] == 0.0 {
+                    continue;
+                }
+                let x = channels[ch][i] as f64;
+                let y = self.highpass[ch].process(self.shelf[ch].process(x));
+                self.block_sum += WEIGHTS[ch] * y * y;
+            }
+            self.block_filled += 1;
+            if self.block_filled >= self.block_len {
+                self.finish_block();
+            }
+        }
+    }
+
+    fn finish_block(&mut self) {
+        self.blocks[self.block_pos] = self.block_sum / self.block_len as f64;
+        self.block_pos = (self.block_pos + 1) % SHORT_TERM_BLOCKS;
+        self.blocks_seen = self.blocks_seen.saturating_add(1);
+        self.block_filled = 0;
+        self.block_sum = 0.0;
+
+        let momentary_energy = self.mean_of_last(MOMENTARY_BLOCKS);
+        self.momentary = to_display(energy_to_lufs(momentary_energy));
+        self.short_term = to_display(energy_to_lufs(self.mean_of_last(SHORT_TERM_BLOCKS)));
+
+        // Every completed 400 ms window is one gating block.
+        if self.blocks_seen >= MOMENTARY_BLOCKS {
+            let lufs = energy_to_lufs(momentary_energy);
+            if lufs > LOUDNESS_FLOOR_LUFS as f64 {
+                let bin = ((lufs - LOUDNESS_FLOOR_LUFS as f64) / HIST_BIN_LU) as usize;
+                let bin = bin.min(HIST_BINS - 1);
+                self.hist_count[bin] += 1;
+                self.hist_energy[bin] += momentary_energy;
+                self.integrated = to_display(self.gated_loudness());
+            }
+        }
+    }
+
+    /// Mean energy of the newest `n` blocks (fewer while the meter warms up).
+    fn mean_of_last(&self, n: usize) -> f64 {
+        let n = n.min(self.blocks_seen);
+        if n == 0 {
+            return 0.0;
+        }
+        let sum: f64 = (1..=n)
+            .map(|back| {
+                self.blocks[(self.block_pos + SHORT_TERM_BLOCKS - back) % SHORT_TERM_BLOCKS]
+            })
+            .sum();
+        sum / n as f64
+    }
+
+    /// Two-stage gated loudness over the histogram (all entries already
+    /// pass the absolute gate).
+    fn gated_loudness(&self) -> f64 {
+        let gated_mean = |first_bin: usize| {
+            let (count, energy) = (first_bin..HIST_BINS).fold((0_u64, 0.0_f64), |acc, b| {
+                (
+                    acc.0 + self.hist_count[b] as u64,
+                    acc.1 + self.hist_energy[b],
+                )
+            });
+            if count == 0 {
+                0.0
+            } else {
+                energy / count as f64
+            }
+        };
+        let relative_gate = energy_to_lufs(gated_mean(0)) + RELATIVE_GATE_LU;
+        let first_bin = ((relative_gate - LOUDNESS_FLOOR_LUFS as f64) / HIST_BIN_LU)
+            .ceil()
+            .max(0.0) as usize;
+        energy_to_lufs(gated_mean(first_bin.min(HIST_BINS)))
+    }
+
+    pub fn momentary(&self) -> f32 {
+        self.momentary
+    }
+
+    pub fn short_term(&self) -> f32 {
+        self.short_term
+    }
+
+    pub fn integrated(&self) -> f32 {
+        self.integrated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    fn feed_sine(meter: &mut LoudnessMeter, amplitude: f32, seconds: f32) {
+        let n = (SR * seconds) as usize;
+        let omega = 2.0 * std::f32::consts::PI * 997.0 / SR;
+        let mut l: Vec<f32> = (0..n)
+            .map(|i| amplitude * (omega * i as f32).sin())
+            .collect();
+        let mut r = l.clone();
+        // Feed in host-sized buffers so block boundaries straddle calls.
+        for (cl, cr) in l.chunks_mut(480).zip(r.chunks_mut(480)) {
+            meter.process(&[cl, cr]);
+        }
+    }
+
+    #[test]
+    fn stereo_sine_reads_its_peak_level() {
+        // A 997 Hz sine on both channels reads its dBFS peak level in LUFS.
+        let mut meter = LoudnessMeter::new(SR);
+        feed_sine(&mut meter, 0.1, 4.0);
+        for (name, lufs) in [
+            ("momentary", meter.momentary()),
+            ("short-term", meter.short_term()),
+            ("integrated", meter.integrated()),
+        ] {
+            assert!(
+                (lufs + 20.0).abs() < 0.2,
+                "{name}: expected -20 LUFS, got {lufs}"
+            );
+        }
+    }
+
+    #[test]
+    fn surround_weights_lift_the_surrounds_and_skip_the_lfe() {
+        // The same sine alone on L, on Ls and on the LFE of a 5.1 buffer.
+        let read = |channel: usize| {
+            let mut meter = LoudnessMeter::new(SR);
+            let omega = 2.0 * std::f32::consts::PI * 997.0 / SR;
+            let mut bus: Vec<Vec<f32>> = vec![vec![0.0; (SR * 2.0) as usize]; CHANNELS];
+            for (i, s) in bus[channel].iter_mut().enumerate() {
+                *s = 0.1 * (omega * i as f32).sin();
+            }
+            for start in (0..bus[0].len()).step_by(480) {
+                let slices: Vec<&mut [f32]> = bus
+                    .iter_mut()
+                    .map(|ch| &mut ch[start..start + 480])
+                    .collect();
+                meter.process(&slices);
+            }
+            meter.momentary()
+        };
+        let front = read(0);
+        let surround = read(4);
+        assert!(
+            (surround - front - 1.49).abs() < 0.05,
+            "L {front}, Ls {surround}"
+        );
+        assert_eq!(read(3), LOUDNESS_FLOOR_LUFS);
+    }
+
+    #[test]
+    fn silence_is_gated_out_of_integrated() {
+        let mut meter = LoudnessMeter::new(SR);
+        feed_sine(&mut meter, 0.1, 3.0);
+        feed_sine(&mut meter, 0.0, 3.0);
+        assert_eq!(meter.momentary(), LOUDNESS_FLOOR_LUFS);
+        // The windows straddling the tone's end legitimately pass the gates
+        // and pull the result down a fraction of a LU.
+        let integrated = meter.integrated();
+        assert!((integrated + 20.0).abs() < 0.5, "integrated: {integrated}");
+    }
+
+    #[test]
+    fn reset_integrated_keeps_short_term_running() {
+        let mut meter = LoudnessMeter::new(SR);
+        feed_sine(&mut meter, 0.1, 3.0);
+        meter.reset_integrated();
+        assert_eq!(meter.integrated(), LOUDNESS_FLOOR_LUFS);
+        assert!(meter.short_term() > -21.0);
+    }
+}
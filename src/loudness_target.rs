@@ -0,0 +1,318 @@
+// src/loudness_target.rs
+// Loudness-target assistant: Punch's drive and ceiling for a LUFS target.
+//
+// A learn pass plays the program through the strip for `LEARN_SECONDS` and
+// measures two places at once: the loudness and sample peak going into
+// Punch (ahead of its input gain), and the loudness leaving the strip.
+// `suggest` turns those and a target into Punch's input gain and ceiling:
+//
+// - The ceiling puts the strip's output peaks at −1 dBFS for streaming
+//   targets (−12 LUFS and quieter), leaving codecs their headroom, and at
+//   −0.3 dBFS for louder masters. Whatever the stages after Punch add
+//   (master gain, Sheen, Punch's own output gain) is taken off first.
+// - The input gain is what lifts the program to the target once the
+//   clipper has shaved what rises past the ceiling. Clipping short peaks
+//   costs little loudness; the model charges `CLIP_LOSS_LU` per dB clipped.
+// - What the stages after Punch add is read from the pass itself: the
+//   output's loudness less what the model says Punch put out at the
+//   settings it ran with.
+//
+// Punch's transient shaper, mix and tilt are taken as they are. The result
+// lands within a dB or so on typical bus material; a second pass after
+// applying it closes the rest. `LoudnessLearn` never allocates after
+// `new()`.
+
+use crate::loudness::{LoudnessMeter, LOUDNESS_FLOOR_LUFS};
+use nih_plug::prelude::util;
+
+/// Length of a learn pass.
+pub const LEARN_SECONDS: f32 = 10.0;
+/// Targets the editor steps through.
+pub const MIN_TARGET_LUFS: i8 = -24;
+pub const MAX_TARGET_LUFS: i8 = -6;
+
+/// Targets above this get the loud-master ceiling.
+const STREAMING_LIMIT_LUFS: f32 = -12.0;
+const STREAMING_CEILING_DBFS: f32 = -1.0;
+const LOUD_CEILING_DBFS: f32 = -0.3;
+/// Loudness the clipper takes away per dB of peak it removes.
+const CLIP_LOSS_LU: f32 = 0.25;
+/// Driving the peaks further than this past the ceiling is audible on most
+/// material; the suggestion says so rather than quietly going there.
+const MAX_CLIP_DB: f32 = 6.0;
+/// Ranges of `punch_input_gain` and `punch_threshold`.
+const INPUT_GAIN_RANGE_DB: (f32, f32) = (-12.0, 12.0);
+const CEILING_RANGE_DB: (f32, f32) = (-12.0, 0.0);
+
+/// What a learn pass measured.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LearnedLevels {
+    /// Integrated loudness going into Punch (LUFS).
+    pub input_lufs: f32,
+    /// Sample peak going into Punch (dBFS).
+    pub input_peak_db: f32,
+    /// Integrated loudness leaving the strip (LUFS).
+    pub output_lufs: f32,
+}
+
+/// Punch's settings during the pass.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PunchSettings {
+    /// False when Punch was bypassed; the other fields are then ignored.
+    pub engaged: bool,
+    pub input_gain_db: f32,
+    pub ceiling_db: f32,
+    pub output_gain_db: f32,
+}
+
+/// Suggested Punch settings, on the params' 0.1 dB step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoudnessSuggestion {
+    pub input_gain_db: f32,
+    pub ceiling_db: f32,
+    /// How far the loudest peak is driven past the ceiling.
+    pub clip_db: f32,
+    /// Output loudness the model expects at these settings.
+    pub expected_lufs: f32,
+    /// False when the target needs more gain than Punch has, or more
+    /// clipping than `MAX_CLIP_DB`.
+    pub reachable: bool,
+}
+
+/// Punch's output loudness, by the model, for a program at `levels` run
+/// through `input_gain_db` into `ceiling_db`.
+fn punch_output(levels: &LearnedLevels, input_gain_db: f32, ceiling_db: f32) -> f32 {
+    let clip = (levels.input_peak_db + input_gain_db - ceiling_db).max(0.0);
+    levels.input_lufs + input_gain_db - CLIP_LOSS_LU * clip
+}
+
+/// Settings that bring the strip's output to `target_lufs`. `None` when
+/// the pass heard nothing at Punch or at the output.
+pub fn suggest(
+    levels: &LearnedLevels,
+    current: &PunchSettings,
+    target_lufs: f32,
+) -> Option<LoudnessSuggestion> {
+    if levels.input_lufs <= LOUDNESS_FLOOR_LUFS || levels.output_lufs <= LOUDNESS_FLOOR_LUFS {
+        return None;
+    }
+    // Everything after Punch's clipper, Punch's output gain included. The
+    // output gain is kept as set, so it carries over into the suggestion.
+    let after_clipper = if current.engaged {
+        levels.output_lufs - punch_output(levels, current.input_gain_db, current.ceiling_db)
+    } else {
+        levels.output_lufs - levels.input_lufs + current.output_gain_db
+    };
+
+    let output_ceiling = if target_lufs > STREAMING_LIMIT_LUFS {
+        LOUD_CEILING_DBFS
+    } else {
+        STREAMING_CEILING_DBFS
+    };
+    let (lowest, highest) = CEILING_RANGE_DB;
+    let ceiling = round_step((output_ceiling - after_clipper).clamp(lowest, highest));
+
+    // Solve input + g − k·max(0, peak + g − ceiling) = wanted for g.
+    let wanted = target_lufs - after_clipper;
+    let mut gain = wanted - levels.input_lufs;
+    if levels.input_peak_db + gain > ceiling {
+        gain = (gain + CLIP_LOSS_LU * (levels.input_peak_db - ceiling)) / (1.0 - CLIP_LOSS_LU);
+    }
+    let (lowest, highest) = INPUT_GAIN_RANGE_DB;
+    let input_gain = round_step(gain.clamp(lowest, highest));
+
+    let clip_db = (levels.input_peak_db + input_gain - ceiling).max(0.0);
+    Some(LoudnessSuggestion {
+        input_gain_db: input_gain,
+        ceiling_db: ceiling,
+        clip_db,
+        expected_lufs: punch_output(levels, input_gain, ceiling) + after_clipper,
+        reachable: (lowest..=highest).contains(&gain) && clip_db <= MAX_CLIP_DB,
+    })
+}
+
+fn round_step(db: f32) -> f32 {
+    (db * 10.0).round() / 10.0
+}
+
+/// Audio side of a learn pass: a loudness meter at Punch's input and one at
+/// the strip's output, run together for `LEARN_SECONDS`.
+pub struct LoudnessLearn {
+    input: LoudnessMeter,
+    output: LoudnessMeter,
+    /// Highest sample into Punch this pass (linear).
+    input_peak: f32,
+    /// Output samples left in the pass; 0 when idle.
+    remaining: usize,
+    sample_rate: f32,
+}
+
+impl LoudnessLearn {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            input: LoudnessMeter::new(sample_rate),
+            output: LoudnessMeter::new(sample_rate),
+            input_peak: 0.0,
+            remaining: 0,
+            sample_rate,
+        }
+    }
+
+    /// Abandons a pass in progress.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        self.input.set_sample_rate(sample_rate);
+        self.output.set_sample_rate(sample_rate);
+        self.remaining = 0;
+    }
+
+    pub fn start(&mut self) {
+        self.input.reset();
+        self.output.reset();
+        self.input_peak = 0.0;
+        self.remaining = (LEARN_SECONDS * self.sample_rate) as usize;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.remaining > 0
+    }
+
+    /// Feed the program as it enters Punch.
+    pub fn process_input(&mut self, channels: &[&mut [f32]]) {
+        if !self.is_active() {
+            return;
+        }
+        self.input.process(channels);
+        for channel in channels {
+            for &x in channel.iter() {
+                self.input_peak = self.input_peak.max(x.abs());
+            }
+        }
+    }
+
+    /// Feed the strip's output. Returns the pass's levels once it has run
+    /// its length; a Punch that never saw audio reads as silence.
+    pub fn process_output(&mut self, channels: &[&mut [f32]]) -> Option<LearnedLevels> {
+        if !self.is_active() {
+            return None;
+        }
+        self.output.process(channels);
+        let samples = channels.first().map_or(0, |ch| ch.len());
+        self.remaining = self.remaining.saturating_sub(samples);
+        if self.is_active() {
+            return None;
+        }
+        Some(LearnedLevels {
+            input_lufs: self.input.integrated(),
+            input_peak_db: util::gain_to_db(self.input_peak),
+            output_lufs: self.output.integrated(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BYPASSED: PunchSettings = PunchSettings {
+        engaged: false,
+        input_gain_db: 0.0,
+        ceiling_db: -0.1,
+        output_gain_db: 0.0,
+    };
+
+    #[test]
+    fn test_quiet_program_is_lifted_without_clipping() {
+        // −24 LUFS with 14 dB of crest, straight through the strip.
+        let levels = LearnedLevels {
+            input_lufs: -24.0,
+            input_peak_db: -10.0,
+            output_lufs: -24.0,
+        };
+        let s = suggest(&levels, &BYPASSED, -16.0).unwrap();
+        assert_eq!(s.ceiling_db, -1.0);
+        assert_eq!(s.input_gain_db, 8.0);
+        assert_eq!(s.clip_db, 0.0);
+        assert!((s.expected_lufs + 16.0).abs() < 0.05);
+        assert!(s.reachable);
+    }
+
+    #[test]
+    fn test_clipping_is_paid_for_in_gain() {
+        let levels = LearnedLevels {
+            input_lufs: -20.0,
+            input_peak_db: -6.0,
+            output_lufs: -20.0,
+        };
+        let s = suggest(&levels, &BYPASSED, -14.0).unwrap();
+        // Six dB of lift alone would put the peaks at 0; the clipper costs
+        // some of it back, so the drive goes further.
+        assert!(s.input_gain_db > 6.0, "{s:?}");
+        assert!(s.clip_db > 1.0, "{s:?}");
+        assert!((s.expected_lufs + 14.0).abs() < 0.1, "{s:?}");
+        assert!(s.reachable);
+        // Loud targets get the tighter ceiling.
+        assert_eq!(suggest(&levels, &BYPASSED, -9.0).unwrap().ceiling_db, -0.3);
+    }
+
+    #[test]
+    fn test_stages_after_punch_are_taken_off() {
+        // Punch ran at +2 dB into −3 dB with no clipping; the strip then
+        // added 1.5 dB on the way out.
+        let levels = LearnedLevels {
+            input_lufs: -22.0,
+            input_peak_db: -12.0,
+            output_lufs: -18.5,
+        };
+        let current = PunchSettings {
+            engaged: true,
+            input_gain_db: 2.0,
+            ceiling_db: -3.0,
+            output_gain_db: 0.0,
+        };
+        let s = suggest(&levels, &current, -18.5).unwrap();
+        assert_eq!(s.ceiling_db, -2.5);
+        assert_eq!(s.input_gain_db, 2.0);
+        assert!((s.expected_lufs + 18.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_out_of_reach_and_silence() {
+        let levels = LearnedLevels {
+            input_lufs: -40.0,
+            input_peak_db: -30.0,
+            output_lufs: -40.0,
+        };
+        let s = suggest(&levels, &BYPASSED, -8.0).unwrap();
+        assert_eq!(s.input_gain_db, 12.0);
+        assert!(!s.reachable);
+        let silent = LearnedLevels {
+            input_lufs: LOUDNESS_FLOOR_LUFS,
+            ..levels
+        };
+        assert_eq!(suggest(&silent, &BYPASSED, -14.0), None);
+    }
+
+    #[test]
+    fn test_learn_pass_reports_after_its_length() {
+        let rate = 8_000.0;
+        let mut learn = LoudnessLearn::new(rate);
+        let mut tone: Vec<f32> = (0..800)
+            .map(|i| 0.5 * (i as f32 * std::f32::consts::TAU * 1000.0 / rate).sin())
+            .collect();
+        let blocks = (LEARN_SECONDS * rate) as usize / tone.len();
+        assert_eq!(learn.process_output(&[&mut tone[..]]), None);
+        learn.start();
+        let mut levels = None;
+        for block in 0..blocks {
+            learn.process_input(&[&mut tone[..]]);
+            levels = learn.process_output(&[&mut tone[..]]);
+            assert_eq!(levels.is_some(), block == blocks - 1);
+        }
+        let levels = levels.unwrap();
+        assert!(!learn.is_active());
+        assert!((levels.input_peak_db + 6.02).abs() < 0.05);
+        assert!((levels.input_lufs - levels.output_lufs).abs() < 1e-3);
+        assert!(levels.input_lufs > -12.0 && levels.input_lufs < -6.0);
+    }
+}
@@ -0,0 +1,600 @@
+// src/matching.rs
+// Spectrum-matching EQ suggestions.
+//
+// A capture averages a few seconds of the chain input (the program) and a
+// reference into third-octave levels. The reference is either the sidechain,
+// captured alongside the program, or a WAV file decoded on the background
+// thread. `suggest_api5500` turns the level difference into API5500 settings:
+// the shelves take the broad tilt at either end, then each parametric band
+// takes the largest remaining deviation in its own region.
+//
+// The program is tapped before the chain, so suggestions are absolute EQ
+// settings rather than corrections on top of the current curve. Overall
+// level is normalised out — matching is about balance, not loudness.
+
+use crate::spectral::{MatchData, FFT_SIZE, MATCH_BANDS};
+use realfft::num_complex::Complex;
+use realfft::{RealFftPlanner, RealToComplex};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Nominal third-octave centres the levels are reported at.
+pub const MATCH_CENTERS_HZ: [f32; MATCH_BANDS] = [
+    20.0, 25.0, 31.5, 40.0, 50.0, 63.0, 80.0, 100.0, 125.0, 160.0, 200.0, 250.0, 315.0, 400.0,
+    500.0, 630.0, 800.0, 1000.0, 1250.0, 1600.0, 2000.0, 2500.0, 3150.0, 4000.0, 5000.0, 6300.0,
+    8000.0, 10000.0, 12500.0, 16000.0, 20000.0,
+];
+
+/// FFT frames per sidechain capture (~3 s at 44.1 kHz).
+pub const CAPTURE_FRAMES: usize = 64;
+
+/// Band levels below this are treated as silence.
+const SILENCE_DB: f32 = -100.0;
+/// Deviations smaller than this are left alone.
+const DEADBAND_DB: f32 = 1.5;
+/// Suggested gains stay well inside the API5500's ±15 dB.
+const MAX_GAIN_DB: f32 = 12.0;
+/// Q for the suggested parametric bands — broad, as tonal matching wants.
+const MATCH_Q: f32 = 1.0;
+/// Fixed shelf corners; the shelves only carry the tilt at either end.
+const LF_SHELF_HZ: f32 = 100.0;
+const HF_SHELF_HZ: f32 = 10_000.0;
+
+/// One suggested parametric band.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchBell {
+    pub freq: f32,
+    pub gain_db: f32,
+}
+
+/// Suggested API5500 settings. Gains are whole dB, matching the params' step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchSuggestion {
+    pub lf_freq: f32,
+    pub lf_gain_db: f32,
+    pub lmf: MatchBell,
+    pub mf: MatchBell,
+    pub hmf: MatchBell,
+    pub hf_freq: f32,
+    pub hf_gain_db: f32,
+    pub q: f32,
+}
+
+/// Why a WAV reference could not be used. Codes travel through
+/// `MatchData::file_status`; 0 means success.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceError {
+    Io = 1,
+    NotWav = 2,
+    UnsupportedFormat = 3,
+    TooShort = 4,
+}
+
+impl ReferenceError {
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            1 => Some(Self::Io),
+            2 => Some(Self::NotWav),
+            3 => Some(Self::UnsupportedFormat),
+            4 => Some(Self::TooShort),
+            _ => None,
+        }
+    }
+
+    /// English status text; the editor translates it.
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::Io => "Couldn't read the file",
+            Self::NotWav => "Not a WAV file",
+            Self::UnsupportedFormat => "Unsupported WAV format",
+            Self::TooShort => "Reference is too short",
+        }
+    }
+}
+
+// ── Capture ──────────────────────────────────────────────────────────────────
+
+/// Averages windowed power spectra of the program and reference. Everything
+/// is allocated in `new`, so `push` is safe on the audio thread.
+pub struct SpectrumCapture {
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    frame: Vec<f32>,
+    spectrum: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    program_ring: Vec<f32>,
+    reference_ring: Vec<f32>,
+    fill: usize,
+    program_power: Vec<f32>,
+    reference_power: Vec<f32>,
+    frames: usize,
+    active: bool,
+    sample_rate: f32,
+}
+
+impl SpectrumCapture {
+    pub fn new() -> Self {
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+        let bins = FFT_SIZE / 2 + 1;
+        Self {
+            window: (0..FFT_SIZE)
+                .map(|n| {
+                    0.5 * (1.0 - (std::f32::consts::TAU * n as f32 / (FFT_SIZE - 1) as f32).cos())
+                })
+                .collect(),
+            frame: fft.make_input_vec(),
+            spectrum: fft.make_output_vec(),
+            scratch: fft.make_scratch_vec(),
+            fft,
+            program_ring: vec![0.0; FFT_SIZE],
+            reference_ring: vec![0.0; FFT_SIZE],
+            fill: 0,
+            program_power: vec![0.0; bins],
+            reference_power: vec![0.0; bins],
+            frames: 0,
+            active: false,
+            sample_rate: 44100.0,
+        }
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+    }
+
+    /// Begin a fresh `CAPTURE_FRAMES` capture.
+    pub fn start(&mut self) {
+        self.program_power.fill(0.0);
+        self.reference_power.fill(0.0);
+        self.fill = 0;
+        self.frames = 0;
+        self.active = true;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Feed one mono sample of each signal. Returns true on the sample that
+    /// completes the capture.
+    pub fn push(&mut self, program: f32, reference: f32) -> bool {
+        if !self.active {
+            return false;
+        }
+        self.program_ring[self.fill] = program;
+        self.reference_ring[self.fill] = reference;
+        self.fill += 1;
+        if self.fill < FFT_SIZE {
+            return false;
+        }
+        self.fill = 0;
+        self.accumulate(false);
+        self.accumulate(true);
+        self.frames += 1;
+        if self.frames >= CAPTURE_FRAMES {
+            self.active = false;
+            return true;
+        }
+        false
+    }
+
+    /// Publish the finished capture's levels.
+    pub fn publish(&self, data: &MatchData) {
+        let mut program = [0.0; MATCH_BANDS];
+        let mut reference = [0.0; MATCH_BANDS];
+        band_levels_db(
+            &self.program_power,
+            self.frames,
+            self.sample_rate,
+            &mut program,
+        );
+        band_levels_db(
+            &self.reference_power,
+            self.frames,
+            self.sample_rate,
+            &mut reference,
+        );
+        data.publish_capture(&program, &reference);
+    }
+
+    /// Window the buffered frame of one signal and add its power spectrum.
+    fn accumulate(&mut self, reference: bool) {
+        let (ring, power) = if reference {
+            (&self.reference_ring, &mut self.reference_power)
+        } else {
+            (&self.program_ring, &mut self.program_power)
+        };
+        for ((dst, &src), &win) in self.frame.iter_mut().zip(ring).zip(&self.window) {
+            *dst = src * win;
+        }
+        if self
+            .fft
+            .process_with_scratch(&mut self.frame, &mut self.spectrum, &mut self.scratch)
+            .is_err()
+        {
+            return;
+        }
+        let scale = 2.0 / FFT_SIZE as f32;
+        for (acc, bin) in power.iter_mut().zip(&self.spectrum) {
+            *acc += (bin.norm() * scale).powi(2);
+        }
+    }
+}
+
+impl Default for SpectrumCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reduce summed power bins to third-octave levels in dB. Bands above 95%
+/// of Nyquist come out NaN; narrow low bands with no bin inside them take
+/// the nearest bin.
+pub fn band_levels_db(
+    power: &[f32],
+    frames: usize,
+    sample_rate: f32,
+    out: &mut [f32; MATCH_BANDS],
+) {
+    let bin_hz = sample_rate / FFT_SIZE as f32;
+    let norm = 1.0 / frames.max(1) as f32;
+    let last = power.len().saturating_sub(1);
+    let edge = 2.0_f32.powf(1.0 / 6.0);
+    for (level, &center) in out.iter_mut().zip(MATCH_CENTERS_HZ.iter()) {
+        if center > 0.95 * sample_rate * 0.5 || power.is_empty() {
+            *level = f32::NAN;
+            continue;
+        }
+        let lo = ((center / edge / bin_hz).ceil() as usize).max(1);
+        let hi = ((center * edge / bin_hz).floor() as usize).min(last);
+        let mean = if lo <= hi {
+            power[lo..=hi].iter().sum::<f32>() / (hi - lo + 1) as f32
+        } else {
+            power[((center / bin_hz).round() as usize).clamp(1, last)]
+        };
+        *level = 10.0 * (mean * norm).max(1e-20).log10();
+    }
+}
+
+/// Third-octave levels of a whole mono recording, e.g. a decoded reference.
+/// `None` when it is shorter than one FFT frame.
+pub fn average_levels(samples: &[f32], sample_rate: f32) -> Option<[f32; MATCH_BANDS]> {
+    if samples.len() < FFT_SIZE {
+        return None;
+    }
+    let mut capture = SpectrumCapture::new();
+    capture.set_sample_rate(sample_rate);
+    for chunk in samples.chunks_exact(FFT_SIZE) {
+        capture.program_ring.copy_from_slice(chunk);
+        capture.accumulate(false);
+        capture.frames += 1;
+    }
+    let mut out = [0.0; MATCH_BANDS];
+    band_levels_db(
+        &capture.program_power,
+        capture.frames,
+        sample_rate,
+        &mut out,
+    );
+    Some(out)
+}
+
+// ── WAV reference ────────────────────────────────────────────────────────────
+
+/// Background-thread entry point: decode `path` and install it as the
+/// reference, or record why it couldn't be.
+pub fn load_reference(path: &Path, data: &MatchData) {
+    let result = std::fs::read(path)
+        .map_err(|_| ReferenceError::Io)
+        .and_then(|bytes| decode_wav_mono(&bytes))
+        .and_then(|(samples, sample_rate)| {
+            average_levels(&samples, sample_rate).ok_or(ReferenceError::TooShort)
+        });
+    match result {
+        Ok(levels) => data.store_file_reference(&levels),
+        Err(err) => data.store_file_error(err.code()),
+    }
+}
+
+/// Decode a PCM (16/24/32-bit) or 32-bit float WAV into a mono mix-down.
+/// Returns the samples and the file's sample rate.
+pub fn decode_wav_mono(bytes: &[u8]) -> Result<(Vec<f32>, f32), ReferenceError> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(ReferenceError::NotWav);
+    }
+    let u16_at = |b: &[u8], i: usize| u16::from_le_bytes([b[i], b[i + 1]]);
+    let u32_at = |b: &[u8], i: usize| u32::from_le_bytes([b[i], b[i + 1], b[i + 2], b[i + 3]]);
+
+    let mut format = None;
+    let mut data = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let len = u32_at(bytes, pos + 4) as usize;
+        let body = &bytes[pos + 8..(pos + 8).saturating_add(len).min(bytes.len())];
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let mut tag = u16_at(body, 0);
+                // WAVE_FORMAT_EXTENSIBLE carries the real tag in its sub-format GUID.
+                if tag == 0xFFFE && body.len() >= 26 {
+                    tag = u16_at(body, 24);
+                }
+                let channels = u16_at(body, 2) as usize;
+                let sample_rate = u32_at(body, 4) as f32;
+                let bits = u16_at(body, 14);
+                format = Some((tag, channels, sample_rate, bits));
+            }
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        pos = pos.saturating_add(8 + len + (len & 1));
+    }
+
+    let (Some((tag, channels, sample_rate, bits)), Some(data)) = (format, data) else {
+        return Err(ReferenceError::NotWav);
+    };
+    if channels == 0 || sample_rate <= 0.0 {
+        return Err(ReferenceError::UnsupportedFormat);
+    }
+    let decode: fn(&[u8]) -> f32 = match (tag, bits) {
+        (1, 16) => |b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32_768.0,
+        (1, 24) => |b| i32::from_le_bytes([0, b[0], b[1], b[2]]) as f32 / 2_147_483_648.0,
+        (1, 32) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0,
+        (3, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        _ => return Err(ReferenceError::UnsupportedFormat),
+    };
+    let width = bits as usize / 8;
+    let samples = data
+        .chunks_exact(width * channels)
+        .map(|frame| frame.chunks_exact(width).map(decode).sum::<f32>() / channels as f32)
+        .collect();
+    Ok((samples, sample_rate))
+}
+
+// ── Suggestion ───────────────────────────────────────────────────────────────
+
+/// Fit API5500 settings that move `program` towards `reference` (both
+/// third-octave levels in dB). `None` when either side is silent.
+pub fn suggest_api5500(
+    reference: &[f32; MATCH_BANDS],
+    program: &[f32; MATCH_BANDS],
+) -> Option<MatchSuggestion> {
+    let valid = |i: usize| reference[i].is_finite() && program[i].is_finite();
+    let loudest = |levels: &[f32; MATCH_BANDS]| {
+        (0..MATCH_BANDS)
+            .filter(|&i| valid(i))
+            .map(|i| levels[i])
+            .fold(f32::NEG_INFINITY, f32::max)
+    };
+    if loudest(reference) < SILENCE_DB || loudest(program) < SILENCE_DB {
+        return None;
+    }
+
+    // Difference curve with the overall level offset (mean over the
+    // midrange) removed.
+    let mut diff = [0.0_f32; MATCH_BANDS];
+    for (i, d) in diff.iter_mut().enumerate() {
+        *d = if valid(i) {
+            reference[i] - program[i]
+        } else {
+            0.0
+        };
+    }
+    let offset = mean_over(&diff, valid, 200.0, 5000.0).unwrap_or(0.0);
+    for d in &mut diff {
+        *d -= offset;
+    }
+
+    // Shelves take the broad tilt below LF / above HF.
+    let lf_gain_db = round_gain(mean_over(&diff, valid, 30.0, LF_SHELF_HZ).unwrap_or(0.0));
+    let hf_gain_db = round_gain(mean_over(&diff, valid, HF_SHELF_HZ, 16_000.0).unwrap_or(0.0));
+    for (d, &f) in diff.iter_mut().zip(MATCH_CENTERS_HZ.iter()) {
+        *d -= lf_gain_db / (1.0 + (f / LF_SHELF_HZ).powi(2));
+        *d -= hf_gain_db / (1.0 + (HF_SHELF_HZ / f).powi(2));
+    }
+
+    // Each parametric band takes the largest residual in its region.
+    let mut fit_bell = |lo: f32, hi: f32, fallback: f32| {
+        let peak = (0..MATCH_BANDS)
+            .filter(|&i| valid(i) && (lo..=hi).contains(&MATCH_CENTERS_HZ[i]))
+            .max_by(|&a, &b| diff[a].abs().total_cmp(&diff[b].abs()));
+        let Some(peak) = peak else {
+            return MatchBell {
+                freq: fallback,
+                gain_db: 0.0,
+            };
+        };
+        let freq = MATCH_CENTERS_HZ[peak];
+        let gain_db = round_gain(diff[peak]);
+        for (d, &f) in diff.iter_mut().zip(MATCH_CENTERS_HZ.iter()) {
+            let x = MATCH_Q * (f / freq - freq / f);
+            *d -= gain_db / (1.0 + x * x);
+        }
+        MatchBell { freq, gain_db }
+    };
+    let lmf = fit_bell(63.0, 400.0, 200.0);
+    let mf = fit_bell(500.0, 2500.0, 1000.0);
+    let hmf = fit_bell(3150.0, 8000.0, 3000.0);
+
+    Some(MatchSuggestion {
+        lf_freq: LF_SHELF_HZ,
+        lf_gain_db,
+        lmf,
+        mf,
+        hmf,
+        hf_freq: HF_SHELF_HZ,
+        hf_gain_db,
+        q: MATCH_Q,
+    })
+}
+
+/// Mean of `values` over the valid bands centred in `lo..=hi` Hz.
+fn mean_over(
+    values: &[f32; MATCH_BANDS],
+    valid: impl Fn(usize) -> bool,
+    lo: f32,
+    hi: f32,
+) -> Option<f32> {
+    let (sum, n) = (0..MATCH_BANDS)
+        .filter(|&i| valid(i) && (lo..=hi).contains(&MATCH_CENTERS_HZ[i]))
+        .fold((0.0, 0), |(sum, n), i| (sum + values[i], n + 1));
+    (n > 0).then(|| sum / n as f32)
+}
+
+fn round_gain(db: f32) -> f32 {
+    if db.abs() < DEADBAND_DB {
+        0.0
+    } else {
+        db.clamp(-MAX_GAIN_DB, MAX_GAIN_DB).round()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(level: f32) -> [f32; MATCH_BANDS] {
+        [level; MATCH_BANDS]
+    }
+
+    fn band(hz: f32) -> usize {
+        MATCH_CENTERS_HZ.iter().position(|&c| c == hz).unwrap()
+    }
+
+    #[test]
+    fn identical_or_louder_reference_suggests_nothing() {
+        let program = flat(-30.0);
+        for reference in [flat(-30.0), flat(-24.0)] {
+            let s = suggest_api5500(&reference, &program).unwrap();
+            assert_eq!(s.lf_gain_db, 0.0);
+            assert_eq!(s.hf_gain_db, 0.0);
+            assert_eq!([s.lmf.gain_db, s.mf.gain_db, s.hmf.gain_db], [0.0; 3]);
+        }
+    }
+
+    #[test]
+    fn low_end_difference_lands_on_the_lf_shelf() {
+        let program = flat(-30.0);
+        let mut reference = program;
+        for level in &mut reference[..=band(63.0)] {
+            *level += 6.0;
+        }
+        let s = suggest_api5500(&reference, &program).unwrap();
+        assert!(s.lf_gain_db >= 3.0, "{s:?}");
+        assert_eq!(s.hf_gain_db, 0.0);
+        assert_eq!(s.mf.gain_db, 0.0);
+    }
+
+    #[test]
+    fn midrange_bump_lands_on_the_mf_band() {
+        let program = flat(-30.0);
+        let mut reference = program;
+        reference[band(1000.0)] += 6.0;
+        reference[band(800.0)] += 3.0;
+        reference[band(1250.0)] += 3.0;
+        let s = suggest_api5500(&reference, &program).unwrap();
+        assert_eq!(s.mf.freq, 1000.0);
+        assert!((4.0..=6.0).contains(&s.mf.gain_db), "{s:?}");
+        assert_eq!(s.lf_gain_db, 0.0);
+    }
+
+    #[test]
+    fn silence_gives_no_suggestion() {
+        assert!(suggest_api5500(&flat(-30.0), &flat(-200.0)).is_none());
+        assert!(suggest_api5500(&flat(-200.0), &flat(-30.0)).is_none());
+    }
+
+    #[test]
+    fn capture_puts_a_sine_in_its_band() {
+        let sr = 48000.0;
+        let mut capture = SpectrumCapture::new();
+        capture.set_sample_rate(sr);
+        capture.start();
+        let data = MatchData::new();
+        let mut n = 0_usize;
+        loop {
+            let s = (std::f32::consts::TAU * 1000.0 * n as f32 / sr).sin();
+            n += 1;
+            if capture.push(s, 0.0) {
+                break;
+            }
+        }
+        assert!(!capture.is_active());
+        capture.publish(&data);
+        assert!(data.take_capture_done());
+        let program = data.program();
+        let loudest = (0..MATCH_BANDS)
+            .max_by(|&a, &b| program[a].total_cmp(&program[b]))
+            .unwrap();
+        assert_eq!(MATCH_CENTERS_HZ[loudest], 1000.0);
+        // The sidechain was silent, so there is nothing to match against.
+        assert!(suggest_api5500(&data.reference(), &program).is_none());
+    }
+
+    #[test]
+    fn bands_above_nyquist_are_nan() {
+        let mut out = [0.0; MATCH_BANDS];
+        band_levels_db(&vec![1.0; FFT_SIZE / 2 + 1], 1, 32000.0, &mut out);
+        assert!(out[band(16000.0)].is_nan());
+        assert!(out[band(1000.0)].is_finite());
+    }
+
+    fn wav(tag: u16, channels: u16, bits: u16, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16_u32.to_le_bytes());
+        out.extend_from_slice(&tag.to_le_bytes());
+        out.extend_from_slice(&channels.to_le_bytes());
+        out.extend_from_slice(&44100_u32.to_le_bytes());
+        let block = channels * bits / 8;
+        out.extend_from_slice(&(44100 * block as u32).to_le_bytes());
+        out.extend_from_slice(&block.to_le_bytes());
+        out.extend_from_slice(&bits.to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+
+    #[test]
+    fn wav_decodes_to_a_mono_mix() {
+        // Stereo 16-bit: (16384, 0) → 0.25.
+        let pcm: Vec<u8> = [16384_i16, 0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let (samples, sr) = decode_wav_mono(&wav(1, 2, 16, &pcm)).unwrap();
+        assert_eq!(sr, 44100.0);
+        assert_eq!(samples, vec![0.25]);
+
+        let float: Vec<u8> = [0.5_f32, -0.5]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let (samples, _) = decode_wav_mono(&wav(3, 1, 32, &float)).unwrap();
+        assert_eq!(samples, vec![0.5, -0.5]);
+    }
+
+    #[test]
+    fn wav_rejects_other_files() {
+        assert_eq!(decode_wav_mono(b"OggS"), Err(ReferenceError::NotWav));
+        assert_eq!(
+            decode_wav_mono(&wav(1, 1, 8, &[0, 0])),
+            Err(ReferenceError::UnsupportedFormat)
+        );
+        for err in [
+            ReferenceError::Io,
+            ReferenceError::NotWav,
+            ReferenceError::UnsupportedFormat,
+            ReferenceError::TooShort,
+        ] {
+            assert_eq!(ReferenceError::from_code(err.code()), Some(err));
+        }
+    }
+}
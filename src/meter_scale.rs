@@ -0,0 +1,218 @@
+// src/meter_scale.rs
+// Calibrated scales for the editor's level meters.
+//
+// The audio thread publishes raw levels only, RMS and sample peak in dBFS
+// per stage (see `spectral::StageLevelData`). Which of them a meter shows,
+// how it moves and where its zero sits is decided here on the GUI thread,
+// so switching scales never touches the audio path:
+//
+// - Digital: sample peak, 0 at full scale. Jumps up at once and falls back
+//   at the meter ballistics' rate.
+// - K-12 / K-14 / K-20: Katz's K-System. RMS, with the scale's 0 at −12,
+//   −14 or −20 dBFS; the space above it is the headroom the material
+//   leaves, amber for the first 4 dB and red beyond.
+// - VU: RMS through a 300 ms integrator (99 % of a step in 300 ms), 0 VU
+//   at −18 dBFS, reading −20 to +3 VU like the hardware's dial.
+//
+// Ballistics are applied per drawn frame. The VU's integration is timed
+// from the frame interval so it holds at any refresh rate; the other
+// scales keep the per-frame smoothing of `MeterBallistics`.
+
+use nih_plug::prelude::util;
+use serde::{Deserialize, Serialize};
+
+/// Lowest level a reading settles at (dBFS).
+pub const READING_FLOOR_DB: f32 = -120.0;
+/// VU integration: a step reaches 99 % in 300 ms, so τ = 0.3 s / ln 100.
+const VU_TAU_S: f32 = 0.065_14;
+/// Longest frame gap integrated in one go; a hidden editor resumes from
+/// wherever the signal is rather than sweeping in slowly.
+const MAX_FRAME_S: f32 = 0.1;
+/// Extent of a K-System scale below its zero.
+const K_SPAN_DB: f32 = 40.0;
+/// Start of the red zone above a K-System zero.
+const K_OVER_DB: f32 = 4.0;
+
+/// Level meter calibration. Persisted with the editor settings.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum MeterScale {
+    #[default]
+    Digital,
+    K12,
+    K14,
+    K20,
+    Vu,
+}
+
+/// Colour band a displayed level falls in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MeterZone {
+    Normal,
+    Hot,
+    Over,
+}
+
+impl MeterScale {
+    pub const ALL: [MeterScale; 5] = [
+        MeterScale::Digital,
+        MeterScale::K12,
+        MeterScale::K14,
+        MeterScale::K20,
+        MeterScale::Vu,
+    ];
+
+    /// Button tag.
+    pub fn label(self) -> &'static str {
+        match self {
+            MeterScale::Digital => "PK",
+            MeterScale::K12 => "K12",
+            MeterScale::K14 => "K14",
+            MeterScale::K20 => "K20",
+            MeterScale::Vu => "VU",
+        }
+    }
+
+    /// Level in dBFS that reads 0 on this scale.
+    pub fn reference_dbfs(self) -> f32 {
+        match self {
+            MeterScale::Digital => 0.0,
+            MeterScale::K12 => -12.0,
+            MeterScale::K14 => -14.0,
+            MeterScale::K20 => -20.0,
+            MeterScale::Vu => -18.0,
+        }
+    }
+
+    /// Bottom and top of the meter, in dBFS.
+    pub fn range_dbfs(self) -> (f32, f32) {
+        let zero = self.reference_dbfs();
+        match self {
+            MeterScale::Digital => (-60.0, 0.0),
+            MeterScale::K12 | MeterScale::K14 | MeterScale::K20 => (zero - K_SPAN_DB, 0.0),
+            MeterScale::Vu => (zero - 20.0, zero + 3.0),
+        }
+    }
+
+    /// How far up the meter `db` sits, 0 to 1.
+    pub fn fraction(self, db: f32) -> f32 {
+        let (bottom, top) = self.range_dbfs();
+        ((db - bottom) / (top - bottom)).clamp(0.0, 1.0)
+    }
+
+    pub fn zone(self, db: f32) -> MeterZone {
+        let above_zero = db - self.reference_dbfs();
+        match self {
+            MeterScale::Digital if db > -1.0 => MeterZone::Over,
+            MeterScale::Digital if db > -6.0 => MeterZone::Hot,
+            MeterScale::Digital => MeterZone::Normal,
+            MeterScale::Vu if above_zero > 0.0 => MeterZone::Over,
+            MeterScale::Vu => MeterZone::Normal,
+            _ if above_zero > K_OVER_DB => MeterZone::Over,
+            _ if above_zero > 0.0 => MeterZone::Hot,
+            _ => MeterZone::Normal,
+        }
+    }
+}
+
+/// One meter's displayed level, advanced once per drawn frame.
+#[derive(Clone, Copy, Debug)]
+pub struct MeterReading {
+    shown_db: f32,
+}
+
+impl MeterReading {
+    pub fn new() -> Self {
+        Self {
+            shown_db: READING_FLOOR_DB,
+        }
+    }
+
+    /// Move toward the published `rms_db` / `peak_db` for a frame `dt_s`
+    /// seconds after the last one and return the level to draw (dBFS).
+    /// `smoothing` is `MeterBallistics::smoothing`.
+    pub fn update(
+        &mut self,
+        scale: MeterScale,
+        rms_db: f32,
+        peak_db: f32,
+        smoothing: f32,
+        dt_s: f32,
+    ) -> f32 {
+        let shown = self.shown_db;
+        self.shown_db = match scale {
+            MeterScale::Digital if peak_db >= shown => peak_db,
+            MeterScale::Digital => shown + (peak_db - shown) * smoothing,
+            MeterScale::K12 | MeterScale::K14 | MeterScale::K20 => {
+                shown + (rms_db - shown) * smoothing
+            }
+            MeterScale::Vu => {
+                // Integrate the amplitude, not the dB figure, so the needle
+                // rises and falls like the hardware's.
+                let coeff = 1.0 - (-dt_s.clamp(0.0, MAX_FRAME_S) / VU_TAU_S).exp();
+                let shown = util::db_to_gain(shown);
+                let target = util::db_to_gain(rms_db);
+                util::gain_to_db(shown + (target - shown) * coeff)
+            }
+        }
+        .max(READING_FLOOR_DB);
+        self.shown_db
+    }
+}
+
+impl Default for MeterReading {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frames at 60 Hz.
+    const FRAME_S: f32 = 1.0 / 60.0;
+
+    #[test]
+    fn test_vu_integrates_to_99_percent_in_300_ms() {
+        let mut reading = MeterReading::new();
+        let mut shown = READING_FLOOR_DB;
+        for _ in 0..18 {
+            shown = reading.update(MeterScale::Vu, -18.0, 0.0, 1.0, FRAME_S);
+        }
+        let ratio = util::db_to_gain(shown) / util::db_to_gain(-18.0);
+        assert!((0.985..=1.0).contains(&ratio), "after 300 ms {ratio}");
+        // A third of the way in, it is still well short.
+        let mut reading = MeterReading::new();
+        for _ in 0..6 {
+            shown = reading.update(MeterScale::Vu, -18.0, 0.0, 1.0, FRAME_S);
+        }
+        assert!(util::db_to_gain(shown) / util::db_to_gain(-18.0) < 0.85);
+    }
+
+    #[test]
+    fn test_digital_peak_jumps_up_and_falls_back() {
+        let mut reading = MeterReading::new();
+        // RMS is ignored: a peak meter shows the peak at once.
+        assert_eq!(
+            reading.update(MeterScale::Digital, -30.0, -3.0, 0.25, FRAME_S),
+            -3.0
+        );
+        let fallen = reading.update(MeterScale::Digital, -60.0, -40.0, 0.25, FRAME_S);
+        assert!(fallen < -3.0 && fallen > -40.0, "{fallen}");
+    }
+
+    #[test]
+    fn test_scales_put_zero_where_they_say() {
+        assert_eq!(MeterScale::Digital.fraction(0.0), 1.0);
+        // K-14's zero sits 40 dB up a 54 dB scale.
+        assert!((MeterScale::K14.fraction(-14.0) - 40.0 / 54.0).abs() < 1e-6);
+        assert_eq!(MeterScale::K14.zone(-15.0), MeterZone::Normal);
+        assert_eq!(MeterScale::K14.zone(-12.0), MeterZone::Hot);
+        assert_eq!(MeterScale::K14.zone(-8.0), MeterZone::Over);
+        assert_eq!(MeterScale::K20.zone(-14.0), MeterZone::Over);
+        // 0 VU at −18 dBFS, +3 VU at the top of the dial.
+        assert!((MeterScale::Vu.fraction(-18.0) - 20.0 / 23.0).abs() < 1e-6);
+        assert_eq!(MeterScale::Vu.fraction(-15.0), 1.0);
+        assert_eq!(MeterScale::Vu.zone(-17.0), MeterZone::Over);
+    }
+}
@@ -0,0 +1,418 @@
+//! Remote control over OSC (`osc` feature).
+//!
+//! A listener thread reads UDP packets and maps addresses onto parameter IDs:
+//!
+//! ```text
+//! /bcs/<module>/<name>   e.g. /bcs/comp/compress, /bcs/eq/lf_gain,
+//!                             /bcs/dyneq/band2_threshold, /bcs/master/gain
+//! ```
+//!
+//! `<module>` is one of the host groups from param_groups.rs (`eq`, `comp`,
+//! `pultec`, `dyneq`, `transformer`, `punch`, `haas`, `sheen`, `input`,
+//! `master`) and `<name>` is the parameter ID with that module's prefix left
+//! off. A float argument is a normalized 0–1 position, as faders and knobs on
+//! control surfaces send. An integer is a plain value: an enum's index, 0/1
+//! for a switch, or dB, Hz or ms. `T`/`F` set switches. Bundles are unpacked
+//! and their time tags ignored.
+//!
+//! Whoever can reach the port can move any parameter, so the listener binds
+//! to the loopback address and only takes packets from this machine.
+//! Listening on the network is opt-in: `BCS_OSC_BIND` names the address to
+//! bind instead (`0.0.0.0` for every interface, or one interface's IP).
+//!
+//! The listener never touches the parameters itself. nih-plug only lets the
+//! editor's `GuiContext` change them in a way the host records (automation,
+//! undo, session state), so resolved changes wait in an [`OscInbox`] and the
+//! editor applies them on its OSC timer.
+//!
+//! Limitation: OSC only works while the plugin window is open. The plugin's
+//! task executor would run without the window, but nih-plug hands it no
+//! `GuiContext`, and setting a parameter any other way would leave the host
+//! out of step with the plugin. A context kept from an earlier window can't
+//! stand in either: it holds the wrapper that owns the plugin. With the
+//! window closed, messages pile up in the inbox (one per parameter) and
+//! anything older than [`OscInbox::MAX_AGE`] is dropped rather than replayed
+//! when the window next opens.
+
+use crate::param_groups::group_for;
+use nih_plug::nih_log;
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// First port tried. Each further instance takes the next free one, up to
+/// `PORT_SPAN` ports on; `BCS_OSC_PORT` overrides the base.
+pub const DEFAULT_PORT: u16 = 9000;
+const PORT_SPAN: u16 = 8;
+/// Address bound unless `BCS_OSC_BIND` names another.
+const DEFAULT_BIND: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+/// Address prefix every message must carry.
+const ROOT: &str = "/bcs/";
+/// How long a blocked `recv` waits before re-checking the stop flag.
+const POLL_TIMEOUT: Duration = Duration::from_millis(250);
+
+/// Address module segment → host group (from param_groups.rs) and the ID
+/// prefix its parameters carry.
+const MODULES: &[(&str, &str, &str)] = &[
+    ("master", "Master", "global_"),
+    ("eq", "API EQ", "eq_"),
+    ("comp", "Compressor", "comp_"),
+    ("pultec", "Pultec", "pultec_"),
+    ("dyneq", "Dynamic EQ", "dyneq_"),
+    ("transformer", "Transformer", "transformer_"),
+    ("punch", "Punch", "punch_"),
+    ("haas", "Haas", "haas_"),
+    ("sheen", "Sheen", "sheen_"),
+    ("input", "Input", "input_"),
+    ("duck", "MIDI Duck", "duck_"),
+];
+
+/// An argument as it came in; the editor converts it with the parameter's
+/// own range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OscValue {
+    Normalized(f32),
+    Plain(f32),
+}
+
+/// Resolved changes waiting for the editor. Holds at most one entry per
+/// parameter, the latest, so a flood of fader moves can't grow it.
+#[derive(Default)]
+pub struct OscInbox {
+    pending: Mutex<Vec<(String, OscValue, Instant)>>,
+}
+
+impl OscInbox {
+    /// Changes older than this when the editor polls are dropped.
+    pub const MAX_AGE: Duration = Duration::from_secs(1);
+
+    pub fn post(&self, id: &str, value: OscValue) {
+        let Ok(mut pending) = self.pending.lock() else {
+            return;
+        };
+        let now = Instant::now();
+        match pending.iter_mut().find(|(pending_id, ..)| pending_id == id) {
+            Some(entry) => {
+                entry.1 = value;
+                entry.2 = now;
+            }
+            None => pending.push((id.to_owned(), value, now)),
+        }
+    }
+
+    /// Everything posted since the last call that is still recent enough to
+    /// apply, oldest first.
+    pub fn take_fresh(&self) -> Vec<(String, OscValue)> {
+        let Ok(mut pending) = self.pending.lock() else {
+            return Vec::new();
+        };
+        let now = Instant::now();
+        pending
+            .drain(..)
+            .filter(|(.., at)| now.duration_since(*at) <= Self::MAX_AGE)
+            .map(|(id, value, _)| (id, value))
+            .collect()
+    }
+}
+
+/// The listener thread. Stops and joins when dropped.
+pub struct OscListener {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl OscListener {
+    /// Bind the first free port from the base and start listening. `ids` is
+    /// every parameter ID; only those can be addressed.
+    pub fn spawn(ids: Vec<String>, inbox: Arc<OscInbox>) -> std::io::Result<Self> {
+        let bind = std::env::var("BCS_OSC_BIND").ok();
+        let address = bind_address(bind.as_deref()).unwrap_or_else(|| {
+            nih_log!("OSC: BCS_OSC_BIND is not an IP address; binding {DEFAULT_BIND}");
+            DEFAULT_BIND
+        });
+        let base = std::env::var("BCS_OSC_PORT")
+            .ok()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(DEFAULT_PORT);
+        let mut bound = None;
+        let mut last_error = None;
+        for port in (0..PORT_SPAN).filter_map(|offset| base.checked_add(offset)) {
+            match UdpSocket::bind((address, port)) {
+                Ok(socket) => {
+                    bound = Some((socket, port));
+                    break;
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        let (socket, port) = match bound {
+            Some(bound) => bound,
+            None => {
+                return Err(last_error.unwrap_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::AddrInUse, "no free OSC port")
+                }))
+            }
+        };
+        socket.set_read_timeout(Some(POLL_TIMEOUT))?;
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = std::thread::Builder::new().name("bcs-osc".into()).spawn({
+            let stop = stop.clone();
+            move || listen(&socket, &ids, &inbox, &stop)
+        })?;
+        nih_log!("OSC: listening on {address}, UDP port {port}");
+        Ok(Self {
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for OscListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The address `BCS_OSC_BIND` asks for, the loopback one when it's unset;
+/// `None` when it isn't an IP address.
+fn bind_address(bind: Option<&str>) -> Option<IpAddr> {
+    match bind.map(str::trim) {
+        None | Some("") => Some(DEFAULT_BIND),
+        Some(address) => address.parse().ok(),
+    }
+}
+
+fn listen(socket: &UdpSocket, ids: &[String], inbox: &OscInbox, stop: &AtomicBool) {
+    // The largest UDP payload; OSC packets from surfaces are far smaller.
+    let mut packet = vec![0u8; 65_507];
+    while !stop.load(Ordering::Relaxed) {
+        let Ok((len, _)) = socket.recv_from(&mut packet) else {
+            // Timeouts land here too; either way, check the flag again.
+            continue;
+        };
+        parse_packet(&packet[..len], &mut |address, value| {
+            if let Some(id) = resolve(address, ids) {
+                inbox.post(id, value);
+            }
+        });
+    }
+}
+
+/// The parameter ID an address names, if any.
+pub fn resolve<'a>(address: &str, ids: &'a [String]) -> Option<&'a str> {
+    let (module, name) = address.strip_prefix(ROOT)?.split_once('/')?;
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+    let &(_, group, prefix) = MODULES.iter().find(|(m, ..)| *m == module)?;
+    // Dynamic EQ bands sit in subgroups ("Dynamic EQ/Band 2").
+    let in_module = |id: &&String| {
+        group_for(id).is_some_and(|g| {
+            g.strip_prefix(group)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    };
+    [format!("{prefix}{name}"), name.to_owned()]
+        .iter()
+        .find_map(|candidate| ids.iter().filter(in_module).find(|id| *id == candidate))
+        .map(String::as_str)
+}
+
+/// Call `on_message` with the address and first argument of every message
+/// in `packet`, descending into bundles. Malformed data ends the parse
+/// quietly; a remote sender can't be made to fail.
+pub fn parse_packet(packet: &[u8], on_message: &mut impl FnMut(&str, OscValue)) {
+    if let Some(mut rest) = packet.strip_prefix(b"#bundle\0") {
+        // Skip the time tag; elements are applied on arrival.
+        rest = match rest.get(8..) {
+            Some(rest) => rest,
+            None => return,
+        };
+        while rest.len() >= 4 {
+            let size = i32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]);
+            let Some(element) = usize::try_from(size)
+                .ok()
+                .and_then(|size| rest.get(4..4 + size))
+            else {
+                return;
+            };
+            parse_packet(element, on_message);
+            rest = &rest[4 + element.len()..];
+        }
+    } else if let Some((address, value)) = parse_message(packet) {
+        on_message(address, value);
+    }
+}
+
+fn parse_message(packet: &[u8]) -> Option<(&str, OscValue)> {
+    let (address, rest) = read_string(packet)?;
+    if !address.starts_with('/') {
+        return None;
+    }
+    let (tags, args) = read_string(rest)?;
+    let tag = tags.strip_prefix(',')?.bytes().next()?;
+    let value = match tag {
+        b'f' => OscValue::Normalized(f32::from_be_bytes(args.get(..4)?.try_into().ok()?)),
+        b'd' => OscValue::Normalized(f64::from_be_bytes(args.get(..8)?.try_into().ok()?) as f32),
+        b'i' => OscValue::Plain(i32::from_be_bytes(args.get(..4)?.try_into().ok()?) as f32),
+        b'h' => OscValue::Plain(i64::from_be_bytes(args.get(..8)?.try_into().ok()?) as f32),
+        b'T' => OscValue::Plain(1.0),
+        b'F' => OscValue::Plain(0.0),
+        _ => return None,
+    };
+    match value {
+        OscValue::Normalized(v) | OscValue::Plain(v) if !v.is_finite() => None,
+        OscValue::Normalized(v) => Some((address, OscValue::Normalized(v.clamp(0.0, 1.0)))),
+        plain => Some((address, plain)),
+    }
+}
+
+/// A NUL-terminated string padded to four bytes, and what follows it.
+fn read_string(data: &[u8]) -> Option<(&str, &[u8])> {
+    let len = data.iter().position(|&b| b == 0)?;
+    let text = std::str::from_utf8(&data[..len]).ok()?;
+    let padded = (len + 4) & !3;
+    Some((text, data.get(padded..)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn osc_string(s: &str) -> Vec<u8> {
+        let mut bytes = s.as_bytes().to_vec();
+        bytes.push(0);
+        while !bytes.len().is_multiple_of(4) {
+            bytes.push(0);
+        }
+        bytes
+    }
+
+    fn message(address: &str, tags: &str, args: &[u8]) -> Vec<u8> {
+        let mut packet = osc_string(address);
+        packet.extend(osc_string(tags));
+        packet.extend_from_slice(args);
+        packet
+    }
+
+    fn parse_all(packet: &[u8]) -> Vec<(String, OscValue)> {
+        let mut out = Vec::new();
+        parse_packet(packet, &mut |address, value| {
+            out.push((address.to_owned(), value))
+        });
+        out
+    }
+
+    fn ids() -> Vec<String> {
+        [
+            "gain",
+            "global_bypass",
+            "lf_gain",
+            "eq_bypass",
+            "comp_compress",
+            "dyneq_band2_threshold",
+            "pultec_lf_boost_gain",
+        ]
+        .iter()
+        .map(|id| id.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn float_is_normalized_and_clamped() {
+        let packet = message("/bcs/comp/compress", ",f", &1.5f32.to_be_bytes());
+        assert_eq!(
+            parse_all(&packet),
+            vec![("/bcs/comp/compress".into(), OscValue::Normalized(1.0))]
+        );
+    }
+
+    #[test]
+    fn int_and_bool_are_plain() {
+        let packet = message("/bcs/master/gain", ",i", &(-3i32).to_be_bytes());
+        assert_eq!(parse_all(&packet)[0].1, OscValue::Plain(-3.0));
+        let packet = message("/bcs/eq/bypass", ",T", &[]);
+        assert_eq!(parse_all(&packet)[0].1, OscValue::Plain(1.0));
+    }
+
+    #[test]
+    fn bundles_are_unpacked() {
+        let first = message("/bcs/eq/lf_gain", ",f", &0.25f32.to_be_bytes());
+        let second = message("/bcs/master/bypass", ",F", &[]);
+        let mut packet = osc_string("#bundle");
+        packet.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1]);
+        for element in [&first, &second] {
+            packet.extend_from_slice(&(element.len() as i32).to_be_bytes());
+            packet.extend_from_slice(element);
+        }
+        let parsed = parse_all(&packet);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].0, "/bcs/master/bypass");
+    }
+
+    #[test]
+    fn malformed_packets_are_ignored() {
+        let packet = message("/bcs/comp/compress", ",f", &[0, 0]);
+        assert!(parse_all(&packet).is_empty());
+        assert!(parse_all(b"/bcs").is_empty());
+        let mut bundle = osc_string("#bundle");
+        bundle.extend_from_slice(&[0; 8]);
+        bundle.extend_from_slice(&100i32.to_be_bytes());
+        assert!(parse_all(&bundle).is_empty());
+        let nan = message("/bcs/comp/compress", ",f", &f32::NAN.to_be_bytes());
+        assert!(parse_all(&nan).is_empty());
+    }
+
+    #[test]
+    fn addresses_resolve_within_their_module() {
+        let ids = ids();
+        assert_eq!(resolve("/bcs/comp/compress", &ids), Some("comp_compress"));
+        assert_eq!(resolve("/bcs/eq/lf_gain", &ids), Some("lf_gain"));
+        assert_eq!(resolve("/bcs/eq/bypass", &ids), Some("eq_bypass"));
+        assert_eq!(resolve("/bcs/master/gain", &ids), Some("gain"));
+        assert_eq!(resolve("/bcs/master/bypass", &ids), Some("global_bypass"));
+        assert_eq!(
+            resolve("/bcs/dyneq/band2_threshold", &ids),
+            Some("dyneq_band2_threshold")
+        );
+        // Right name, wrong module.
+        assert_eq!(resolve("/bcs/comp/lf_gain", &ids), None);
+        assert_eq!(resolve("/bcs/pultec/gain", &ids), None);
+        assert_eq!(resolve("/other/comp/compress", &ids), None);
+        assert_eq!(resolve("/bcs/comp/", &ids), None);
+    }
+
+    #[test]
+    fn binds_loopback_unless_asked_otherwise() {
+        assert_eq!(bind_address(None), Some(DEFAULT_BIND));
+        assert_eq!(bind_address(Some(" ")), Some(DEFAULT_BIND));
+        assert_eq!(
+            bind_address(Some("0.0.0.0")),
+            Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED))
+        );
+        assert_eq!(bind_address(Some("::1")), Some("::1".parse().unwrap()));
+        assert_eq!(bind_address(Some("studio-mac")), None);
+    }
+
+    #[test]
+    fn inbox_keeps_the_latest_value_per_parameter() {
+        let inbox = OscInbox::default();
+        inbox.post("gain", OscValue::Normalized(0.1));
+        inbox.post("lf_gain", OscValue::Normalized(0.2));
+        inbox.post("gain", OscValue::Normalized(0.3));
+        assert_eq!(
+            inbox.take_fresh(),
+            vec![
+                ("gain".into(), OscValue::Normalized(0.3)),
+                ("lf_gain".into(), OscValue::Normalized(0.2)),
+            ]
+        );
+        assert!(inbox.take_fresh().is_empty());
+    }
+}
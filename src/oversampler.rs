@@ -10,6 +10,44 @@
 pub const HB_NUM_TAPS: usize = 23;
 pub const MAX_OS_STAGES: usize = 4; // 2^4 = 16× max
 
+/// Base rates above this already leave ultrasonic room for the harmonics;
+/// each doubling past it halves the factor a module asks for.
+const OS_REFERENCE_RATE: f32 = 50_000.0;
+
+/// Oversampling factor to run at `sample_rate` for a module tuned for
+/// `factor`× at 44.1/48 kHz: halved at 88.2/96 kHz, quartered at
+/// 176.4/192 kHz, never below 1×. Keeps the oversampled rate (and the CPU
+/// cost) roughly constant across base rates.
+pub fn factor_for_rate(factor: usize, sample_rate: f32) -> usize {
+    let mut factor = factor.max(1);
+    let mut rate = sample_rate;
+    while factor > 1 && rate > OS_REFERENCE_RATE {
+        factor /= 2;
+        rate /= 2.0;
+    }
+    factor
+}
+
+/// A module's current trips through halfband cascades: `passes` times up
+/// and straight back down at `factor`. Each 2× stage's FIR delays the
+/// signal 11 samples at its upper rate going up and again coming down, so
+/// one pass is 11 base-rate samples late at 2× but 16.5 at 4×, 19.25 at 8×
+/// and 20.625 at 16×. No whole-sample delay lines that up; a path that has
+/// to (Punch's dry, delta listen's input) takes the same trip instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CascadeDelay {
+    pub factor: usize,
+    pub passes: usize,
+}
+
+impl CascadeDelay {
+    /// Nothing oversampled.
+    pub const NONE: Self = Self {
+        factor: 1,
+        passes: 0,
+    };
+}
+
 /// Modified Bessel function of the first kind, order 0.
 /// Series expansion — called at init time only.
 fn bessel_i0(x: f32) -> f32 {
@@ -211,7 +249,6 @@ impl Oversampler {
         self.num_stages = new_num_stages;
     }
 
-    #[allow(dead_code)]
     pub fn factor(&self) -> usize {
         self.factor
     }
@@ -308,6 +345,17 @@ impl Oversampler {
         result
     }
 
+    /// Run `input` up and straight back down: the cascade's delay and
+    /// band-limiting with nothing in between. Needs `max_block_size` ≥ 1.
+    #[inline]
+    pub fn round_trip(&mut self, input: f32) -> f32 {
+        let mut frame = [0.0_f32; 1 << MAX_OS_STAGES];
+        let up = self.upsample(input, 0);
+        let factor = up.len();
+        frame[..factor].copy_from_slice(up);
+        self.downsample(&frame[..factor], 0)
+    }
+
     pub fn reset(&mut self) {
         for s in &mut self.up_stages {
             s.reset();
@@ -318,3 +366,25 @@ impl Oversampler {
         self.downsample_buffer.fill(0.0);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factor_scales_down_at_high_base_rates() {
+        for (sr, want_4x, want_16x) in [
+            (44100.0, 4, 16),
+            (48000.0, 4, 16),
+            (88200.0, 2, 8),
+            (96000.0, 2, 8),
+            (176400.0, 1, 4),
+            (192000.0, 1, 4),
+        ] {
+            assert_eq!(factor_for_rate(4, sr), want_4x, "4x at {sr}");
+            assert_eq!(factor_for_rate(16, sr), want_16x, "16x at {sr}");
+        }
+        assert_eq!(factor_for_rate(1, 192000.0), 1);
+        assert_eq!(factor_for_rate(0, 44100.0), 1);
+    }
+}
@@ -0,0 +1,129 @@
+//! Host-facing parameter groups.
+//!
+//! `BusChannelStripParams` is one flat struct, so the derived `param_map()`
+//! puts all of its parameters in the root group and a host's generic UI or
+//! automation menu shows them as one long list. [`HostParams`] is what
+//! `Plugin::params()` hands the host instead: the same parameters in the same
+//! order, each filed under its module's group (nested as `Dynamic EQ/Band 2`
+//! for the bands). nih-plug turns the groups into CLAP modules and VST3 units.
+//!
+//! Parameter IDs don't change, so sessions and automation recorded before
+//! the grouping load as before. The editor keeps using the flat struct.
+
+use crate::BusChannelStripParams;
+use nih_plug::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Group for parameters that only affect the strip as a whole.
+const MASTER: &str = "Master";
+
+/// The plugin's parameters with their module groups filled in.
+pub struct HostParams(pub Arc<BusChannelStripParams>);
+
+// SAFETY: every `ParamPtr` comes from the wrapped struct's own `param_map()`
+// and points into that struct, which the `Arc` keeps alive and in place for
+// as long as this value exists.
+unsafe impl Params for HostParams {
+    fn param_map(&self) -> Vec<(String, ParamPtr, String)> {
+        self.0
+            .param_map()
+            .into_iter()
+            .map(|(id, ptr, _)| {
+                let group = group_for(&id).unwrap_or(MASTER).to_string();
+                (id, ptr, group)
+            })
+            .collect()
+    }
+
+    fn serialize_fields(&self) -> BTreeMap<String, String> {
+        self.0.serialize_fields()
+    }
+
+    fn deserialize_fields(&self, serialized: &BTreeMap<String, String>) {
+        self.0.deserialize_fields(serialized)
+    }
+}
+
+/// The group a parameter ID belongs to, or `None` for an ID this table
+/// doesn't know yet (it then lands in Master).
+pub fn group_for(id: &str) -> Option<&'static str> {
+    const API_EQ_BANDS: [&str; 5] = ["lf_", "lmf_", "mf_", "hmf_", "hf_"];
+
+    if let Some(rest) = id.strip_prefix("dyneq_band") {
+        return match rest.as_bytes().first() {
+            Some(b'1') => Some("Dynamic EQ/Band 1"),
+            Some(b'2') => Some("Dynamic EQ/Band 2"),
+            Some(b'3') => Some("Dynamic EQ/Band 3"),
+            Some(b'4') => Some("Dynamic EQ/Band 4"),
+            _ => None,
+        };
+    }
+
+    let group = match id {
+        "global_bypass"
+        | "global_auto_gain"
+        | "global_channel_mode"
+        | "global_lfe_saturation"
+        | "global_analog_amount"
+        | "global_sc_listen"
+        | "global_delta_listen"
+        | "global_key_listen"
+        | "global_safety"
+        | "global_transport_reset"
+        | "global_dither"
+        | "global_dither_shaping"
+        | "gain"
+        | "block_size" => MASTER,
+        _ if id.starts_with("eq_") || API_EQ_BANDS.iter().any(|p| id.starts_with(p)) => "API EQ",
+        _ if id.starts_with("comp_") => "Compressor",
+        _ if id.starts_with("pultec_") => "Pultec",
+        _ if id.starts_with("dyneq_") => "Dynamic EQ",
+        _ if id.starts_with("transformer_") => "Transformer",
+        _ if id.starts_with("punch_") => "Punch",
+        _ if id.starts_with("haas_") => "Haas",
+        _ if id.starts_with("sheen_") => "Sheen",
+        _ if id.starts_with("input_") => "Input",
+        _ if id.starts_with("duck_") => "MIDI Duck",
+        _ if id.starts_with("module_order_") || id.starts_with("hide_") => "Rack",
+        _ if id.starts_with("analyzer_") => "Analyzer",
+        _ => return None,
+    };
+    Some(group)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_parameter_has_a_group() {
+        let params = BusChannelStripParams::default();
+        for (id, _, _) in params.param_map() {
+            assert!(group_for(&id).is_some(), "parameter {id} has no host group");
+        }
+    }
+
+    #[test]
+    fn groups_keep_ids_and_order() {
+        let params = Arc::new(BusChannelStripParams::default());
+        let flat: Vec<String> = params.param_map().into_iter().map(|(id, ..)| id).collect();
+        let grouped: Vec<String> = HostParams(params)
+            .param_map()
+            .into_iter()
+            .map(|(id, ..)| id)
+            .collect();
+        assert_eq!(flat, grouped);
+    }
+
+    #[test]
+    fn bands_and_modules_are_told_apart() {
+        assert_eq!(group_for("hmf_q"), Some("API EQ"));
+        assert_eq!(group_for("mf_gain"), Some("API EQ"));
+        assert_eq!(group_for("comp_vca_thresh"), Some("Compressor"));
+        assert_eq!(group_for("dyneq_bypass"), Some("Dynamic EQ"));
+        assert_eq!(group_for("dyneq_band3_gain"), Some("Dynamic EQ/Band 3"));
+        assert_eq!(group_for("gain"), Some(MASTER));
+        assert_eq!(group_for("something_new"), None);
+    }
+}
@@ -9,7 +9,7 @@
 ///   4. Module not processing audio when bypass is explicitly off
 ///
 /// All modules default to bypass=true (the GUI bypass button activates each one).
-/// Tests that need bypass OFF call `plugin.pultec.update_parameters + plugin.pultec.process`
+/// Tests that need bypass OFF call `plugin.chain.pultec.update_parameters + plugin.chain.pultec.process`
 /// directly — this exercises the plugin's real module instance (same init, same sample rate)
 /// without needing NIH-plug's crate-private param setters.
 #[cfg(test)]
@@ -76,6 +76,91 @@ mod plugin_integration_tests {
         );
     }
 
+    /// The output safety stage protects monitors out of the box.
+    #[test]
+    fn test_safety_defaults_to_on() {
+        let plugin = BusChannelStrip::default();
+        assert!(
+            plugin.params.global_safety.value(),
+            "global_safety must default to true (NaN/DC/overload guard active)"
+        );
+    }
+
+    /// A new strip links its channels; dual mono is opt-in.
+    #[test]
+    fn test_channel_mode_defaults_to_stereo() {
+        let plugin = BusChannelStrip::default();
+        assert_eq!(
+            plugin.params.global_channel_mode.value(),
+            crate::ChannelMode::Stereo
+        );
+    }
+
+    /// A 5.1 layout is offered, with and without the sidechain, and its LFE
+    /// skips the saturation stages until asked.
+    #[test]
+    fn test_surround_layouts_keep_saturation_off_the_lfe() {
+        use nih_plug::prelude::Plugin;
+
+        let surround = BusChannelStrip::AUDIO_IO_LAYOUTS
+            .iter()
+            .filter(|layout| {
+                layout.main_input_channels.map(|n| n.get()) == Some(6)
+                    && layout.main_output_channels.map(|n| n.get()) == Some(6)
+            })
+            .count();
+        assert_eq!(surround, 2);
+        let plugin = BusChannelStrip::default();
+        assert!(!plugin.params.global_lfe_saturation.value());
+    }
+
+    /// Every delta-listen choice but Off names its own rack module.
+    #[test]
+    fn test_delta_listen_choices_map_to_distinct_modules() {
+        use crate::{DeltaListen, ModuleType};
+        use nih_plug::prelude::Enum;
+
+        assert_eq!(DeltaListen::Off.module(), None);
+        let modules: Vec<ModuleType> = (1..DeltaListen::variants().len())
+            .filter_map(|i| DeltaListen::from_index(i).module())
+            .collect();
+        assert_eq!(modules.len(), DeltaListen::variants().len() - 1);
+        for (i, mt) in modules.iter().enumerate() {
+            assert_ne!(*mt, ModuleType::Empty);
+            assert!(!modules[i + 1..].contains(mt), "{mt:?} listed twice");
+        }
+    }
+
+    /// The four DynEQ key-listen choices pick bands 1 to 4 in order; Off and
+    /// Compressor pick none.
+    #[test]
+    fn test_key_listen_choices_map_to_dyneq_bands() {
+        use crate::KeyListen;
+        use nih_plug::prelude::Enum;
+
+        let bands: Vec<Option<usize>> = (0..KeyListen::variants().len())
+            .map(|i| KeyListen::from_index(i).dyneq_band())
+            .collect();
+        assert_eq!(bands, [None, None, Some(0), Some(1), Some(2), Some(3)]);
+    }
+
+    /// The API5500's five band frequencies default in ascending order, so
+    /// each band starts out on its own region of the spectrum.
+    #[cfg(feature = "api5500")]
+    #[test]
+    fn test_api5500_band_frequency_defaults_ascend() {
+        let plugin = BusChannelStrip::default();
+        let p = &plugin.params;
+        let (lmf, mf, hmf) = (p.lmf_freq.value(), p.mf_freq.value(), p.hmf_freq.value());
+        assert!(
+            p.lf_freq.value() < lmf && lmf < mf && mf < hmf && hmf < p.hf_freq.value(),
+            "API5500 band defaults must ascend LF < LMF < MF < HMF < HF, got \
+             {} / {lmf} / {mf} / {hmf} / {}",
+            p.lf_freq.value(),
+            p.hf_freq.value()
+        );
+    }
+
     // ─── Gain delivery through the plugin's pultec instance ───────────────────
 
     /// Zero gains through the plugin's own PultecEQ instance must be transparent.
@@ -85,14 +170,14 @@ mod plugin_integration_tests {
     fn test_pultec_zero_gains_are_transparent() {
         let sr = 48_000.0_f32;
         let mut plugin = BusChannelStrip::default();
-        plugin.pultec = crate::pultec::PultecEQ::new(sr);
-        plugin.pultec.update_parameters(
+        plugin.chain.pultec = crate::pultec::PultecEQ::new(sr);
+        plugin.chain.pultec.update_parameters(
             60.0, 0.0, 0.67, 100.0, 0.0, 0.5, 10000.0, 0.0, 0.5, 10000.0, 0.0, 0.0,
         );
 
         let (mut l, mut r) = make_sine_buffer(100.0, sr, 8192);
         run_pultec(&mut l, &mut r, |buf| {
-            plugin.pultec.process(buf);
+            plugin.chain.pultec.process(buf);
         });
         let gain_db = peak_gain_db(&l);
         assert!(
@@ -109,8 +194,8 @@ mod plugin_integration_tests {
     fn test_pultec_lf_boost_plugin_instance_30hz() {
         let sr = 48_000.0_f32;
         let mut plugin = BusChannelStrip::default();
-        plugin.pultec = crate::pultec::PultecEQ::new(sr);
-        plugin.pultec.update_parameters(
+        plugin.chain.pultec = crate::pultec::PultecEQ::new(sr);
+        plugin.chain.pultec.update_parameters(
             60.0, 15.0, 0.67, // LF boost: 60 Hz, +15 dB, default width
             100.0, 0.0, 0.5, // LF cut: off
             10000.0, 0.0, 0.5, // HF boost: off
@@ -120,7 +205,7 @@ mod plugin_integration_tests {
 
         let (mut l, mut r) = make_sine_buffer(30.0, sr, 8192);
         run_pultec(&mut l, &mut r, |buf| {
-            plugin.pultec.process(buf);
+            plugin.chain.pultec.process(buf);
         });
         let gain_db = peak_gain_db(&l);
         assert!(
@@ -136,14 +221,14 @@ mod plugin_integration_tests {
     fn test_pultec_lf_resonant_bump_at_corner() {
         let sr = 48_000.0_f32;
         let mut plugin = BusChannelStrip::default();
-        plugin.pultec = crate::pultec::PultecEQ::new(sr);
-        plugin.pultec.update_parameters(
+        plugin.chain.pultec = crate::pultec::PultecEQ::new(sr);
+        plugin.chain.pultec.update_parameters(
             100.0, 15.0, 0.67, 100.0, 0.0, 0.5, 10000.0, 0.0, 0.5, 10000.0, 0.0, 0.0,
         );
 
         let (mut l, mut r) = make_sine_buffer(100.0, sr, 8192);
         run_pultec(&mut l, &mut r, |buf| {
-            plugin.pultec.process(buf);
+            plugin.chain.pultec.process(buf);
         });
         let gain_db = peak_gain_db(&l);
         assert!(
@@ -158,14 +243,14 @@ mod plugin_integration_tests {
     fn test_pultec_hf_boost_plugin_instance_8khz() {
         let sr = 48_000.0_f32;
         let mut plugin = BusChannelStrip::default();
-        plugin.pultec = crate::pultec::PultecEQ::new(sr);
-        plugin.pultec.update_parameters(
+        plugin.chain.pultec = crate::pultec::PultecEQ::new(sr);
+        plugin.chain.pultec.update_parameters(
             60.0, 0.0, 0.67, 100.0, 0.0, 0.5, 8000.0, 10.0, 0.5, 10000.0, 0.0, 0.0,
         );
 
         let (mut l, mut r) = make_sine_buffer(8000.0, sr, 8192);
         run_pultec(&mut l, &mut r, |buf| {
-            plugin.pultec.process(buf);
+            plugin.chain.pultec.process(buf);
         });
         let gain_db = peak_gain_db(&l);
         assert!(
@@ -173,4 +258,62 @@ mod plugin_integration_tests {
             "Plugin instance: Pultec HF +10 dB / 8 kHz must deliver ≥ +6 dB at 8 kHz, got {gain_db:.2} dB"
         );
     }
+
+    /// Routing falls back to internal detection without a sidechain port,
+    /// and SC listen spreads a mono key across both outputs.
+    #[test]
+    fn test_sidechain_routing_and_listen() {
+        use crate::{monitor_key, route_key, KeySource};
+
+        let mut key = vec![0.5_f32; 4];
+        let sidechain: &[&mut [f32]] = &[&mut key[..]];
+        assert!(route_key(KeySource::External, Some(sidechain)).is_some());
+        assert!(route_key(KeySource::Internal, Some(sidechain)).is_none());
+        assert!(route_key(KeySource::External, None).is_none());
+
+        let (mut l, mut r) = (vec![1.0_f32; 4], vec![-1.0_f32; 4]);
+        let mut buf = Buffer::default();
+        unsafe {
+            buf.set_slices(4, |ss| {
+                ss.clear();
+                ss.push(&mut l);
+                ss.push(&mut r);
+            });
+        }
+        monitor_key(&mut buf, Some(sidechain));
+        assert!(buf.as_slice().iter().all(|ch| ch.iter().all(|&s| s == 0.5)));
+        monitor_key(&mut buf, None);
+        assert!(buf.as_slice().iter().all(|ch| ch.iter().all(|&s| s == 0.0)));
+    }
+
+    /// Editor settings round-trip as one JSON blob, and a blob saved before
+    /// a field existed loads with that field's default.
+    #[test]
+    fn test_editor_settings_blob_round_trips_and_defaults() {
+        use crate::meter_scale::MeterScale;
+        use crate::{EditorSettings, MeterBallistics};
+
+        let settings = EditorSettings {
+            zoom: 150,
+            analyzer: false,
+            meter_ballistics: MeterBallistics::Slow,
+            meter_scale: MeterScale::K14,
+            tooltips: false,
+            ab_match_loudness: true,
+            loudness_target: -9,
+            loudness_auto_apply: true,
+        };
+        let json = serde_json::to_string(&settings).unwrap();
+        assert_eq!(
+            serde_json::from_str::<EditorSettings>(&json).unwrap(),
+            settings
+        );
+
+        let old: EditorSettings = serde_json::from_str(r#"{"zoom":125}"#).unwrap();
+        assert_eq!(old.zoom, 125);
+        assert!(old.analyzer && old.tooltips);
+        assert_eq!(old.meter_ballistics, MeterBallistics::Normal);
+        assert_eq!(old.meter_scale, MeterScale::Digital);
+        assert_eq!(old.loudness_target, -14);
+    }
 }
@@ -0,0 +1,651 @@
+// src/presets.rs
+// Preset subsystem — factory bank + user preset files on disk.
+//
+// A preset is a flat map of parameter ID → plain value. Plain (not
+// normalized) values keep files readable and survive range tweaks between
+// versions; the editor converts back with `ParamPtr::preview_normalized` at
+// load time. Parameters missing from a preset load at their default, so
+// factory presets only need to list what they change.
+//
+// Threading:
+//   • Capture/apply run on the GUI thread (the editor owns the ParamPtrs).
+//   • Every filesystem touch (scan, save, delete, chain export/import) runs as a
+//     `Plugin::BackgroundTask` on nih-plug's background thread. Results are
+//     published through `PresetLibrary`, whose `generation` counter the
+//     editor polls to know when to rebuild its list.
+//   • Nothing here is ever reached from the audio thread, so the Mutexes
+//     below are fine.
+
+use nih_plug::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+
+use crate::host_context;
+
+/// File extension for user preset files.
+pub const PRESET_EXTENSION: &str = "bcpreset";
+/// Bumped when the on-disk layout changes incompatibly.
+pub const PRESET_FORMAT_VERSION: u32 = 1;
+/// File extension for exported chain files.
+pub const CHAIN_EXTENSION: &str = "bcsp";
+/// Bumped when the chain file layout changes incompatibly.
+pub const CHAIN_FORMAT_VERSION: u32 = 1;
+
+/// Where a preset came from. Factory presets are compiled in and read-only.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresetSource {
+    Factory,
+    User,
+}
+
+#[derive(Clone, Debug)]
+pub struct Preset {
+    pub name: String,
+    pub category: String,
+    pub source: PresetSource,
+    /// Backing file for user presets; `None` for factory presets.
+    pub path: Option<PathBuf>,
+    pub values: BTreeMap<String, f32>,
+}
+
+/// On-disk representation. Kept separate from `Preset` so the in-memory type
+/// can carry runtime-only fields (source, path) without leaking into files.
+#[derive(Serialize, Deserialize)]
+struct PresetFile {
+    version: u32,
+    name: String,
+    #[serde(default)]
+    category: String,
+    params: BTreeMap<String, f32>,
+}
+
+/// Exported chain: the whole strip in one compact file for moving a bus
+/// between machines or sessions without the host's preset format. The
+/// slot order travels as the `module_order_*` entries in `params`; the
+/// plugin version is informational, `version` is what gates loading.
+#[derive(Serialize, Deserialize)]
+struct ChainFile {
+    version: u32,
+    #[serde(default)]
+    plugin_version: String,
+    name: String,
+    params: BTreeMap<String, f32>,
+}
+
+#[derive(Debug)]
+pub enum PresetError {
+    Io(std::io::Error),
+    Parse(String),
+    UnsupportedVersion(u32),
+    NoPresetDirectory,
+}
+
+impl fmt::Display for PresetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "preset file error: {e}"),
+            Self::Parse(e) => write!(f, "invalid preset: {e}"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported preset version {v}"),
+            Self::NoPresetDirectory => write!(f, "no user preset directory available"),
+        }
+    }
+}
+
+impl From<std::io::Error> for PresetError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Parameters that are deliberately NOT stored in presets:
+///   • `hide_*` — per-module view state, not sound.
+///   • `analyzer_*` — spectrum display options, likewise.
+///   • `global_bypass` — loading a preset must never silently bypass the
+///     whole strip (or un-bypass it mid-comparison).
+///   • `block_size` — an engine setting that changes latency, not sound.
+///   • `global_channel_mode`, `global_lfe_saturation` — depend on what the
+///     track carries, not on the sound.
+///   • `global_sc_listen`, `global_delta_listen`, `global_key_listen` —
+///     monitoring switches, like `global_bypass`.
+///   • `global_safety` — a preset must never switch off the output guard.
+///   • `global_transport_reset` — host-playback behaviour, not sound.
+///   • `global_dither`, `global_dither_shaping` — delivery format of the
+///     print, chosen per session rather than per sound.
+pub fn is_preset_param(id: &str) -> bool {
+    !(id.starts_with("hide_")
+        || id.starts_with("analyzer_")
+        || id == "global_bypass"
+        || id == "global_sc_listen"
+        || id == "global_delta_listen"
+        || id == "global_key_listen"
+        || id == "global_safety"
+        || id == "global_transport_reset"
+        || id == "global_dither"
+        || id == "global_dither_shaping"
+        || id == "global_channel_mode"
+        || id == "global_lfe_saturation"
+        || id == "block_size")
+}
+
+/// Snapshot every preset-relevant parameter as plain values. GUI thread only.
+pub fn capture(params: &dyn Params) -> BTreeMap<String, f32> {
+    params
+        .param_map()
+        .into_iter()
+        .filter(|(id, _, _)| is_preset_param(id))
+        // SAFETY: the ParamPtrs come from `params`, which is borrowed for
+        // the duration of this call.
+        .map(|(id, ptr, _)| (id, unsafe { ptr.unmodulated_plain_value() }))
+        .collect()
+}
+
+pub fn serialize_preset(
+    name: &str,
+    category: &str,
+    values: &BTreeMap<String, f32>,
+) -> Result<String, PresetError> {
+    let file = PresetFile {
+        version: PRESET_FORMAT_VERSION,
+        name: name.to_owned(),
+        category: category.to_owned(),
+        params: values.clone(),
+    };
+    serde_json::to_string_pretty(&file).map_err(|e| PresetError::Parse(e.to_string()))
+}
+
+/// Parse preset text into a `Preset`. `source`/`path` are supplied by the
+/// caller because the file contents do not know where they were read from.
+pub fn parse_preset(
+    text: &str,
+    source: PresetSource,
+    path: Option<PathBuf>,
+) -> Result<Preset, PresetError> {
+    let file: PresetFile =
+        serde_json::from_str(text).map_err(|e| PresetError::Parse(e.to_string()))?;
+    if file.version > PRESET_FORMAT_VERSION {
+        return Err(PresetError::UnsupportedVersion(file.version));
+    }
+    Ok(Preset {
+        name: file.name,
+        category: file.category,
+        source,
+        path,
+        values: file.params,
+    })
+}
+
+/// Chain file text: single-line JSON, since it is meant to be passed
+/// around rather than edited.
+pub fn serialize_chain(name: &str, values: &BTreeMap<String, f32>) -> Result<String, PresetError> {
+    let file = ChainFile {
+        version: CHAIN_FORMAT_VERSION,
+        plugin_version: env!("CARGO_PKG_VERSION").to_owned(),
+        name: name.to_owned(),
+        params: values.clone(),
+    };
+    serde_json::to_string(&file).map_err(|e| PresetError::Parse(e.to_string()))
+}
+
+/// Parse an exported chain. Parameters the file doesn't know (a chain
+/// from an older build) load at their default, like a preset's.
+pub fn parse_chain(text: &str, path: Option<PathBuf>) -> Result<Preset, PresetError> {
+    let file: ChainFile =
+        serde_json::from_str(text).map_err(|e| PresetError::Parse(e.to_string()))?;
+    if file.version > CHAIN_FORMAT_VERSION {
+        return Err(PresetError::UnsupportedVersion(file.version));
+    }
+    Ok(Preset {
+        name: file.name,
+        category: "Chain".to_owned(),
+        source: PresetSource::User,
+        path,
+        values: file.params,
+    })
+}
+
+/// Write the chain to `path`.
+pub fn write_chain(
+    path: &Path,
+    name: &str,
+    values: &BTreeMap<String, f32>,
+) -> Result<(), PresetError> {
+    let text = serialize_chain(name, values)?;
+    std::fs::write(path, text).map_err(PresetError::from)
+}
+
+/// Where to export a chain picked as `path`: a directory gets a file named
+/// after the chain, and a missing extension gets `.bcsp`.
+pub fn chain_export_path(path: &Path, name: &str) -> PathBuf {
+    if path.is_dir() {
+        path.join(sanitize_file_stem(name))
+            .with_extension(CHAIN_EXTENSION)
+    } else if path.extension().is_none() {
+        path.with_extension(CHAIN_EXTENSION)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Native dialog for `.bcsp` files, opening in the user preset folder.
+#[cfg(feature = "gui")]
+fn chain_file_dialog() -> rfd::FileDialog {
+    let dialog = rfd::FileDialog::new().add_filter("Chain", &[CHAIN_EXTENSION]);
+    match user_preset_dir() {
+        Some(dir) => dialog.set_directory(dir),
+        None => dialog,
+    }
+}
+
+/// Turn a user-typed preset name into a safe file stem. Keeps letters,
+/// digits, space, dash and underscore; everything else becomes `_`.
+pub fn sanitize_file_stem(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "Untitled".to_owned()
+    } else {
+        stem
+    }
+}
+
+/// Platform user-preset directory. Resolved from environment variables to
+/// avoid pulling in a directories crate for one path:
+///   • Windows: %APPDATA%\Bus Channel Strip\Presets
+///   • macOS:   ~/Library/Audio/Presets/Bus Channel Strip
+///   • other:   $XDG_DATA_HOME (or ~/.local/share)/bus_channel_strip/presets
+pub fn user_preset_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA")
+            .map(|p| PathBuf::from(p).join("Bus Channel Strip").join("Presets"))
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|p| {
+            PathBuf::from(p)
+                .join("Library/Audio/Presets")
+                .join("Bus Channel Strip")
+        })
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|p| PathBuf::from(p).join(".local/share")))
+            .map(|p| p.join("bus_channel_strip").join("presets"))
+    }
+}
+
+/// Read every `*.bcpreset` in `dir`, sorted by name. Unreadable or invalid
+/// files are skipped — one bad file must not hide the rest of the bank.
+pub fn scan_dir(dir: &Path) -> Vec<Preset> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut presets: Vec<Preset> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == PRESET_EXTENSION))
+        .filter_map(|p| {
+            let text = std::fs::read_to_string(&p).ok()?;
+            parse_preset(&text, PresetSource::User, Some(p)).ok()
+        })
+        .collect();
+    presets.sort_by_key(|p| p.name.to_lowercase());
+    presets
+}
+
+// ── Factory bank ──────────────────────────────────────────────────────────────
+
+// The bank lives in `presets/` at the crate root, one `.bcpreset` per
+// preset in the same format user presets are saved in, and is compiled in
+// with `include_str!` so it's there before any user preset exists. A file
+// can be copied into the user preset directory as is. Parameters use plain
+// values; module order follows the `ModuleType` variant index:
+// 0 API5500, 1 ButterComp2, 2 Pultec, 3 DynEQ, 4 Transformer, 5 Haas,
+// 6 Punch, 7 Empty.
+const FACTORY_FILES: &[&str] = &[
+    include_str!("../presets/init.bcpreset"),
+    include_str!("../presets/gentle-glue.bcpreset"),
+    include_str!("../presets/drum-smack.bcpreset"),
+    include_str!("../presets/vocal-air.bcpreset"),
+    include_str!("../presets/wide-and-warm.bcpreset"),
+    // Bus starting points (docs/presets/buses.md).
+    include_str!("../presets/bus-drum-parallel.bcpreset"),
+    include_str!("../presets/bus-bass-anchor.bcpreset"),
+    include_str!("../presets/bus-guitar-focus.bcpreset"),
+    include_str!("../presets/bus-vocal-polish.bcpreset"),
+    include_str!("../presets/bus-keys-sparkle.bcpreset"),
+    include_str!("../presets/bus-master-polish.bcpreset"),
+    // Genre chains (docs/presets/genres.md).
+    include_str!("../presets/genre-pop.bcpreset"),
+    include_str!("../presets/genre-hip-hop.bcpreset"),
+    include_str!("../presets/genre-rock.bcpreset"),
+    include_str!("../presets/genre-edm.bcpreset"),
+    include_str!("../presets/genre-jazz.bcpreset"),
+    include_str!("../presets/genre-extreme-metal.bcpreset"),
+];
+
+/// The compiled-in factory bank, in display order. A file that fails to
+/// parse is skipped like a bad user preset; the tests keep that from
+/// shipping.
+pub fn factory_presets() -> Vec<Preset> {
+    FACTORY_FILES
+        .iter()
+        .filter_map(|text| parse_preset(text, PresetSource::Factory, None).ok())
+        .collect()
+}
+
+// ── Background task + shared library ─────────────────────────────────────────
+
+/// File IO requested by the editor, executed by `BusChannelStrip::task_executor`.
+#[derive(Debug)]
+pub enum PresetTask {
+    /// Re-read the user preset directory.
+    Rescan,
+    /// Write a user preset (overwrites a same-named file), then rescan.
+    Save {
+        name: String,
+        category: String,
+        values: BTreeMap<String, f32>,
+    },
+    /// Remove a user preset file, then rescan.
+    Delete(PathBuf),
+    /// Turn the first-instantiation preset on or off for every instance;
+    /// see host_context.rs.
+    SetContextPresets(bool),
+    /// Ask where to save the whole chain, then write it as a `.bcsp` file.
+    #[cfg(feature = "gui")]
+    ExportChain {
+        name: String,
+        values: BTreeMap<String, f32>,
+    },
+    /// Ask for a `.bcsp` file and read it; the editor applies it on its
+    /// next poll.
+    #[cfg(feature = "gui")]
+    ImportChain,
+}
+
+/// Shared between the background executor (writer) and the editor (reader).
+pub struct PresetLibrary {
+    user: Mutex<Vec<Preset>>,
+    /// Last IO failure, shown in the browser footer. Cleared on success.
+    last_error: Mutex<Option<String>>,
+    /// Chain read by `ImportChain`, waiting for the editor to apply it.
+    imported: Mutex<Option<Preset>>,
+    /// Mirror of `host_context::enabled()`, refreshed by every task.
+    context_presets: AtomicBool,
+    /// Bumped after every completed task so the editor can cheaply detect
+    /// that `user_presets()` / `last_error()` changed.
+    generation: AtomicU32,
+}
+
+impl Default for PresetLibrary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PresetLibrary {
+    pub fn new() -> Self {
+        Self {
+            user: Mutex::new(Vec::new()),
+            last_error: Mutex::new(None),
+            imported: Mutex::new(None),
+            context_presets: AtomicBool::new(true),
+            generation: AtomicU32::new(0),
+        }
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    pub fn user_presets(&self) -> Vec<Preset> {
+        self.user.lock().map(|u| u.clone()).unwrap_or_default()
+    }
+
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().ok().and_then(|e| e.clone())
+    }
+
+    pub fn context_presets(&self) -> bool {
+        self.context_presets.load(Ordering::Acquire)
+    }
+
+    /// Take the last imported chain, if the editor hasn't applied it yet.
+    pub fn take_imported(&self) -> Option<Preset> {
+        self.imported.lock().ok().and_then(|mut i| i.take())
+    }
+
+    /// Background-thread entry point.
+    pub fn run_task(&self, task: PresetTask) {
+        let result = match task {
+            PresetTask::Rescan => Ok(()),
+            PresetTask::Save {
+                name,
+                category,
+                values,
+            } => Self::save(&name, &category, &values),
+            PresetTask::Delete(path) => std::fs::remove_file(path).map_err(PresetError::from),
+            PresetTask::SetContextPresets(enabled) => host_context::set_enabled(enabled),
+            // The dialogs block this thread, not the editor's; cancelling
+            // one does nothing.
+            #[cfg(feature = "gui")]
+            PresetTask::ExportChain { name, values } => {
+                match chain_file_dialog()
+                    .set_file_name(format!("{}.{CHAIN_EXTENSION}", sanitize_file_stem(&name)))
+                    .save_file()
+                {
+                    Some(path) => {
+                        let path = chain_export_path(&path, &name);
+                        write_chain(&path, &name, &values)
+                    }
+                    None => Ok(()),
+                }
+            }
+            #[cfg(feature = "gui")]
+            PresetTask::ImportChain => match chain_file_dialog().pick_file() {
+                Some(path) => std::fs::read_to_string(&path)
+                    .map_err(PresetError::from)
+                    .and_then(|text| parse_chain(&text, Some(path)))
+                    .map(|chain| {
+                        if let Ok(mut imported) = self.imported.lock() {
+                            *imported = Some(chain);
+                        }
+                    }),
+                None => Ok(()),
+            },
+        };
+
+        let scanned = user_preset_dir()
+            .map(|dir| scan_dir(&dir))
+            .unwrap_or_default();
+        if let Ok(mut user) = self.user.lock() {
+            *user = scanned;
+        }
+        if let Ok(mut err) = self.last_error.lock() {
+            *err = result.err().map(|e| e.to_string());
+        }
+        self.context_presets
+            .store(host_context::enabled(), Ordering::Release);
+        self.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    fn save(name: &str, category: &str, values: &BTreeMap<String, f32>) -> Result<(), PresetError> {
+        let dir = user_preset_dir().ok_or(PresetError::NoPresetDirectory)?;
+        std::fs::create_dir_all(&dir)?;
+        let text = serialize_preset(name, category, values)?;
+        let path = dir
+            .join(sanitize_file_stem(name))
+            .with_extension(PRESET_EXTENSION);
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_preserves_name_category_and_values() {
+        let mut values = BTreeMap::new();
+        values.insert("lf_gain".to_owned(), 3.0);
+        values.insert("comp_compress".to_owned(), 0.42);
+        let text = serialize_preset("Test", "Drum Bus", &values).unwrap();
+        let preset = parse_preset(&text, PresetSource::User, None).unwrap();
+        assert_eq!(preset.name, "Test");
+        assert_eq!(preset.category, "Drum Bus");
+        assert_eq!(preset.values, values);
+    }
+
+    #[test]
+    fn newer_format_version_is_rejected() {
+        let text = format!(
+            r#"{{"version": {}, "name": "x", "params": {{}}}}"#,
+            PRESET_FORMAT_VERSION + 1
+        );
+        assert!(matches!(
+            parse_preset(&text, PresetSource::User, None),
+            Err(PresetError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn chain_roundtrip_is_compact_and_keeps_the_order() {
+        let mut values = BTreeMap::new();
+        values.insert("module_order_1".to_owned(), 3.0);
+        values.insert("lf_gain".to_owned(), -2.5);
+        let text = serialize_chain("Drum Bus", &values).unwrap();
+        assert!(!text.contains('\n'));
+        let chain = parse_chain(&text, None).unwrap();
+        assert_eq!(chain.name, "Drum Bus");
+        assert_eq!(chain.values, values);
+        let text = format!(
+            r#"{{"version": {}, "name": "x", "params": {{}}}}"#,
+            CHAIN_FORMAT_VERSION + 1
+        );
+        assert!(matches!(
+            parse_chain(&text, None),
+            Err(PresetError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn chain_export_path_adds_the_extension() {
+        assert_eq!(
+            chain_export_path(Path::new("/tmp/bus chains/drums"), "x"),
+            PathBuf::from("/tmp/bus chains/drums.bcsp")
+        );
+        assert_eq!(
+            chain_export_path(Path::new("drums.json"), "x"),
+            PathBuf::from("drums.json")
+        );
+        let dir = std::env::temp_dir();
+        assert_eq!(chain_export_path(&dir, "Mix/Bus"), dir.join("Mix_Bus.bcsp"));
+    }
+
+    #[test]
+    fn view_state_and_global_bypass_are_not_preset_params() {
+        assert!(!is_preset_param("hide_api5500"));
+        assert!(!is_preset_param("analyzer_floor"));
+        assert!(!is_preset_param("global_bypass"));
+        assert!(!is_preset_param("block_size"));
+        assert!(!is_preset_param("global_sc_listen"));
+        assert!(!is_preset_param("global_delta_listen"));
+        assert!(!is_preset_param("global_key_listen"));
+        assert!(!is_preset_param("global_safety"));
+        assert!(!is_preset_param("global_transport_reset"));
+        assert!(!is_preset_param("global_dither"));
+        assert!(!is_preset_param("global_channel_mode"));
+        assert!(!is_preset_param("global_lfe_saturation"));
+        assert!(is_preset_param("global_analog_amount"));
+        assert!(is_preset_param("eq_bypass"));
+        assert!(is_preset_param("module_order_1"));
+    }
+
+    #[test]
+    fn file_stem_strips_path_separators() {
+        assert_eq!(sanitize_file_stem("../evil/name"), "___evil_name");
+        assert_eq!(sanitize_file_stem("   "), "Untitled");
+        assert_eq!(sanitize_file_stem("Drum Bus 2"), "Drum Bus 2");
+    }
+
+    #[test]
+    fn every_factory_file_loads() {
+        let factory = factory_presets();
+        assert_eq!(
+            factory.len(),
+            FACTORY_FILES.len(),
+            "a factory file failed to parse"
+        );
+        assert_eq!(factory[0].name, "Init");
+        for (i, preset) in factory.iter().enumerate() {
+            assert_eq!(preset.source, PresetSource::Factory);
+            assert!(
+                !preset.category.is_empty(),
+                "'{}' has no category",
+                preset.name
+            );
+            assert!(
+                factory[..i].iter().all(|p| p.name != preset.name),
+                "two factory presets are named '{}'",
+                preset.name
+            );
+        }
+    }
+
+    #[test]
+    fn factory_bank_only_references_known_ids() {
+        let params = crate::BusChannelStripParams::default();
+        let ids: Vec<String> = params
+            .param_map()
+            .into_iter()
+            .map(|(id, _, _)| id)
+            .collect();
+        for preset in factory_presets() {
+            for id in preset.values.keys() {
+                assert!(
+                    ids.contains(id),
+                    "factory preset '{}' uses unknown id '{id}'",
+                    preset.name
+                );
+                assert!(
+                    is_preset_param(id),
+                    "factory preset '{}' sets '{id}', which presets don't store",
+                    preset.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn factory_values_sit_on_their_params_grid() {
+        // A value outside the range or between steps would load as
+        // something else than the file says.
+        let params = crate::BusChannelStripParams::default();
+        for preset in factory_presets() {
+            for (id, ptr, _) in params.param_map() {
+                let Some(&plain) = preset.values.get(&id) else {
+                    continue;
+                };
+                // SAFETY: `params` outlives the loop.
+                let loaded = unsafe { ptr.preview_plain(ptr.preview_normalized(plain)) };
+                assert!(
+                    (loaded - plain).abs() <= 1e-3 * plain.abs().max(1.0),
+                    "factory preset '{}' sets {id} to {plain}, which loads as {loaded}",
+                    preset.name
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// src/print.rs
+// Print mode: a cheaper stand-in for the Pultec that the strip swaps in
+// while the audio thread is short of time.
+//
+// The Pultec is a linear filter network followed by a memoryless tube curve,
+// so it prints exactly into those two parts:
+//   • The linear part keeps running as its own five biquads. Its impulse
+//     response rings for thousands of samples under a 20 Hz shelf, and no
+//     FIR that long is cheaper than the filters that generate it.
+//   • The tube curve is printed into a `StaticCurve` lookup table on the
+//     background thread and read at the native rate, replacing the 4×
+//     oversampler and its tanh calls.
+// What the print gives up is the oversampling: at high drive, its harmonics
+// alias. That is the quality disclaimer behind the Print switch, which is
+// off by default.
+//
+// `LoadMonitor` decides when to swap. It times each `process()` call against
+// the buffer's duration and reports pressure with hysteresis, so a load that
+// hovers near the threshold doesn't flip the print on and off.
+
+use crate::telemetry::{triple_buffer, TripleReader, TripleWriter};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Table points. Odd, so x = 0 lands on a point.
+const CURVE_POINTS: usize = 1025;
+/// Input span of the table (±). Past it the curve continues along its end
+/// segment; the tube curve is flat there by then.
+const CURVE_RANGE: f32 = 4.0;
+const POINTS_PER_UNIT: f32 = (CURVE_POINTS - 1) as f32 / (2.0 * CURVE_RANGE);
+
+/// Smoothed share of the buffer's duration spent in `process()` above
+/// which the print engages…
+const LOAD_ENGAGE: f32 = 0.75;
+/// …and below which it lets go again.
+const LOAD_RELEASE: f32 = 0.45;
+/// Per-buffer one-pole smoothing of the measured load.
+const LOAD_SMOOTHING: f32 = 0.9;
+
+/// A memoryless transfer curve sampled on a fixed grid.
+#[derive(Clone)]
+pub struct StaticCurve {
+    /// Tube drive the table was printed for; NaN until the first print.
+    drive: f32,
+    points: [f32; CURVE_POINTS],
+}
+
+impl StaticCurve {
+    /// An unprinted (identity) table.
+    pub fn new() -> Self {
+        let mut curve = Self {
+            drive: f32::NAN,
+            points: [0.0; CURVE_POINTS],
+        };
+        curve.print(f32::NAN, |x| x);
+        curve
+    }
+
+    /// Sample `transfer` over the table, tagging it with `drive`.
+    pub fn print(&mut self, drive: f32, transfer: impl Fn(f32) -> f32) {
+        for (i, point) in self.points.iter_mut().enumerate() {
+            *point = transfer(i as f32 / POINTS_PER_UNIT - CURVE_RANGE);
+        }
+        self.drive = drive;
+    }
+
+    pub fn drive(&self) -> f32 {
+        self.drive
+    }
+
+    /// Linear interpolation between the two nearest points.
+    #[inline]
+    pub fn eval(&self, x: f32) -> f32 {
+        let pos = (x + CURVE_RANGE) * POINTS_PER_UNIT;
+        // The saturating cast sends negatives and NaN to 0; past either end
+        // `frac` leaves 0..1 and extrapolates the end segment.
+        let i = (pos as usize).min(CURVE_POINTS - 2);
+        let frac = pos - i as f32;
+        let (a, b) = (self.points[i], self.points[i + 1]);
+        a + (b - a) * frac
+    }
+}
+
+impl Default for StaticCurve {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub type CurveReader = TripleReader<StaticCurve>;
+
+/// Audio → background request for a print. The curve itself comes back
+/// through the triple buffer built by `print_channel`.
+pub struct PrintShared {
+    drive: AtomicU32,
+    /// A print task is queued or running.
+    queued: AtomicBool,
+}
+
+impl PrintShared {
+    pub fn new() -> Self {
+        Self {
+            drive: AtomicU32::new(0),
+            queued: AtomicBool::new(false),
+        }
+    }
+
+    /// Post `drive` for printing. Returns true when the caller should queue
+    /// `BackgroundTask::PrintTube`; while one is already queued the request
+    /// waits for a later buffer, so a drive sweep collapses into few prints.
+    pub fn request(&self, drive: f32) -> bool {
+        if self.queued.swap(true, Ordering::AcqRel) {
+            return false;
+        }
+        self.drive.store(drive.to_bits(), Ordering::Relaxed);
+        true
+    }
+}
+
+impl Default for PrintShared {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Background-thread half: prints the requested curve and publishes it.
+/// Owned by the task executor behind a Mutex the audio thread never touches.
+pub struct PrintWorker {
+    writer: TripleWriter<StaticCurve>,
+}
+
+impl PrintWorker {
+    /// Print the posted drive with `transfer(x, drive)` and publish it.
+    pub fn run(&mut self, shared: &PrintShared, transfer: impl Fn(f32, f32) -> f32) {
+        // Same handshake as the EQ's FIR design: the task queue orders this
+        // load after the audio thread's store, and clearing `queued` only
+        // afterwards keeps the drive from being rewritten mid-read.
+        let drive = f32::from_bits(shared.drive.load(Ordering::Relaxed));
+        shared.queued.store(false, Ordering::Release);
+        self.writer.back_mut().print(drive, |x| transfer(x, drive));
+        self.writer.publish();
+    }
+}
+
+/// Build the worker and the audio thread's curve reader. Allocates; call
+/// on the main thread.
+pub fn print_channel() -> (PrintWorker, CurveReader) {
+    let (writer, reader) = triple_buffer(StaticCurve::new());
+    (PrintWorker { writer }, reader)
+}
+
+/// Tracks how much of each buffer's real-time budget `process()` uses.
+#[derive(Default)]
+pub struct LoadMonitor {
+    load: f32,
+    pressured: bool,
+}
+
+impl LoadMonitor {
+    /// Fold in one buffer: `busy_s` spent processing a buffer that lasts
+    /// `buffer_s`. Returns whether the strip is under pressure.
+    pub fn update(&mut self, busy_s: f32, buffer_s: f32) -> bool {
+        if buffer_s > 0.0 && busy_s.is_finite() {
+            let load = busy_s / buffer_s;
+            self.load = self.load * LOAD_SMOOTHING + load * (1.0 - LOAD_SMOOTHING);
+            if self.load > LOAD_ENGAGE {
+                self.pressured = true;
+            } else if self.load < LOAD_RELEASE {
+                self.pressured = false;
+            }
+        }
+        self.pressured
+    }
+
+    pub fn pressured(&self) -> bool {
+        self.pressured
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curve_tracks_tanh_between_points() {
+        let mut curve = StaticCurve::new();
+        curve.print(0.5, f32::tanh);
+        let worst = (-4000..=4000)
+            .map(|i| i as f32 * 1e-3)
+            .map(|x| (curve.eval(x) - x.tanh()).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(worst < 1e-4, "interpolation error {worst}");
+        assert_eq!(curve.drive(), 0.5);
+    }
+
+    #[test]
+    fn test_curve_extrapolates_past_its_range() {
+        let curve = StaticCurve::new();
+        assert!(curve.drive().is_nan());
+        for x in [-10.0, -4.5, 0.0, 4.5, 10.0] {
+            assert!((curve.eval(x) - x).abs() < 1e-4, "identity at {x}");
+        }
+    }
+
+    #[test]
+    fn test_worker_publishes_requested_drive() {
+        let shared = PrintShared::new();
+        let (mut worker, mut reader) = print_channel();
+        assert!(shared.request(0.8));
+        assert!(!shared.request(0.9), "second request waits");
+        worker.run(&shared, |x, drive| x * drive);
+        assert!(reader.update());
+        assert_eq!(reader.read().drive(), 0.8);
+        assert!((reader.read().eval(1.0) - 0.8).abs() < 1e-6);
+        assert!(shared.request(0.9), "queue reopens after a run");
+    }
+
+    #[test]
+    fn test_load_monitor_hysteresis() {
+        let mut monitor = LoadMonitor::default();
+        let buffer_s = 0.01;
+        // A sustained 90% load engages after a few buffers…
+        let engaged = (0..50).position(|_| monitor.update(0.009, buffer_s));
+        assert!(matches!(engaged, Some(n) if n > 0));
+        // …60% sits inside the band and holds it…
+        for _ in 0..100 {
+            assert!(monitor.update(0.006, buffer_s));
+        }
+        // …and 20% lets it go.
+        assert!((0..100).any(|_| !monitor.update(0.002, buffer_s)));
+        assert!(!monitor.pressured());
+    }
+}
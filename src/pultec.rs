@@ -1,6 +1,14 @@
-use crate::oversampler::Oversampler;
-use crate::shaping::biquad_coeffs;
-use biquad::{Biquad, DirectForm1, Type};
+use crate::character::{EqCharacter, VintageStage};
+use crate::engagement::Engagement;
+use crate::oversampler::{factor_for_rate, CascadeDelay, Oversampler};
+use crate::print::{PrintShared, StaticCurve};
+use crate::shaping::adaa::{ln_cosh, Adaa1, Antiderivative};
+use crate::shaping::biquad_coeffs_f64;
+use crate::shaping::waveshaper::{Waveshaper, DEFAULT_POINTS};
+use crate::stereo_biquad::StereoBiquads;
+use crate::variance::Variance;
+use biquad::{Coefficients, Type};
+#[cfg(feature = "plugin")]
 use nih_plug::buffer::Buffer;
 
 /// Oversampling factor for the tube saturation stage. 4× (2 halfband stages)
@@ -20,6 +28,52 @@ const LF_RESONANT_Q: f32 = 1.8;
 const LF_SHELF_Q_NARROW: f32 = 1.0;
 const LF_SHELF_Q_WIDE: f32 = 0.25;
 
+/// Crossfade between the live and printed tube stages, in samples. Long
+/// enough to hide the oversampler's few samples of group delay.
+const PRINT_FADE_SAMPLES: f32 = 256.0;
+
+/// Level above which the tube curve's tanh falls `ENGAGE_DEVIATION` below
+/// linear. The curve's make-up scale is a clean gain, so engagement is read
+/// off the level going in rather than the deviation coming out.
+const TUBE_ENGAGE_LEVEL: f32 = 0.174;
+
+/// Input range of the live stage's `tanh` table. tanh(8) is within 3e-7 of
+/// 1, and past the table the curve carries on along its flat end.
+const TUBE_TABLE_RANGE: f32 = 8.0;
+
+/// Sections of the biquad cascade, in signal order.
+const LF_BOOST: usize = 0;
+/// Resonant peak from the passive LCR network — centered at the same
+/// frequency as the shelf, gain proportional to shelf gain.
+const LF_RESONANT: usize = 1;
+const LF_CUT: usize = 2;
+const HF_BOOST: usize = 3;
+const HF_CUT: usize = 4;
+
+/// Components vintage variance moves, indices into `PultecEQ::variance`.
+const VAR_LF_BOOST_FREQ: usize = 0;
+const VAR_LF_BOOST_GAIN: usize = 1;
+const VAR_LF_CUT_FREQ: usize = 2;
+const VAR_LF_CUT_GAIN: usize = 3;
+const VAR_HF_BOOST_FREQ: usize = 4;
+const VAR_HF_BOOST_GAIN: usize = 5;
+const VAR_HF_CUT_FREQ: usize = 6;
+const VAR_HF_CUT_GAIN: usize = 7;
+/// This unit's serial number, as far as the tolerances are concerned.
+const VARIANCE_SEED: u32 = 0x1A_1956;
+
+/// Vintage character: share of a cut's attenuation that comes off the
+/// boost on the same side, as the passive sections load each other.
+const VINTAGE_LOADING: f32 = 0.15;
+/// Vintage HF boost Q: scales from `MIN` with no boost by `SPAN` at the
+/// full 10 dB, so the peak narrows as it rises.
+const VINTAGE_HF_Q_MIN: f32 = 0.7;
+const VINTAGE_HF_Q_SPAN: f32 = 0.6;
+/// Vintage electronics: the output transformer's allpass corner and the
+/// make-up amp's noise floor.
+const VINTAGE_CORNER_HZ: f32 = 14_000.0;
+const VINTAGE_NOISE_DB: f32 = -94.0;
+
 /// Pultec EQP-1A style EQ module
 ///
 /// Classic passive tube EQ with simultaneous boost/cut characteristics
@@ -29,17 +83,14 @@ const LF_SHELF_Q_WIDE: f32 = 0.25;
 pub struct PultecEQ {
     sample_rate: f32,
 
-    // Each biquad carries its own state (z1, z2); stereo processing REQUIRES
-    // a separate filter instance per channel. Sharing one filter across L and
-    // R makes consecutive samples from alternating channels corrupt the
-    // filter memory, smearing the shelf and blunting perceived gain.
-    lf_boost_filter: [DirectForm1<f32>; 2],
-    // Resonant peak from the passive LCR network — centered at the same
-    // frequency as the shelf, gain proportional to shelf gain.
-    lf_resonant_filter: [DirectForm1<f32>; 2],
-    lf_cut_filter: [DirectForm1<f32>; 2],
-    hf_boost_filter: [DirectForm1<f32>; 2],
-    hf_cut_filter: [DirectForm1<f32>; 2],
+    // The five biquads as one stereo cascade. Each section keeps separate
+    // L and R state (z1, z2) in its SIMD lanes: sharing one state across
+    // alternating channels corrupts the filter memory, smearing the shelf
+    // and blunting perceived gain.
+    filters: StereoBiquads<5>,
+    /// Last `update_parameters` filter arguments (everything but tube
+    /// drive). NaN forces the first computation.
+    cached_eq: [f32; 11],
 
     // Tube saturation state
     tube_drive: f32,
@@ -47,50 +98,170 @@ pub struct PultecEQ {
     // Per-channel oversamplers for the tube saturation nonlinearity.
     tube_os_l: Oversampler,
     tube_os_r: Oversampler,
+    /// `tanh` for the oversampled tube stage, which would otherwise
+    /// evaluate it up to four times a sample per channel.
+    tanh_table: Waveshaper,
+    /// Run the live tube stage through first-order ADAA at the native rate
+    /// instead of the oversamplers.
+    adaa: bool,
+    tube_adaa: [Adaa1; 2],
+
+    /// Print mode requested (see print.rs).
+    print_on: bool,
+    /// 0 = live tube stage, 1 = printed curve; in between, both run and
+    /// crossfade.
+    print_mix: f32,
+
+    /// Tolerance and drift on the band frequencies and gains.
+    variance: Variance<8>,
+
+    /// Samples the tube stage bent since the last `take_engagement`.
+    engagement: Engagement,
+
+    /// Vintage character: section loading in the voicing, plus `vintage`.
+    character: EqCharacter,
+    vintage: VintageStage,
 }
 
 impl PultecEQ {
     /// Create a new Pultec EQ with the given sample rate.
     ///
     /// Filters are initialized flat (0 dB). Coefficients are updated in-place
-    /// via `update_coefficients()` in `update_parameters()`, which preserves
+    /// via `StereoBiquads::set_coefficients()` in `update_parameters()`, which preserves
     /// filter state across parameter changes and avoids per-buffer allocation.
     pub fn new(sample_rate: f32) -> Self {
         // Helper: flat 0 dB filter at a nominal per-section frequency.
-        let flat_at = |freq_hz: f32| -> DirectForm1<f32> {
-            let coeff = biquad_coeffs(Type::PeakingEQ(0.0), sample_rate, freq_hz, 0.707)
-                .expect("0 dB PeakingEQ is always valid");
-            DirectForm1::<f32>::new(coeff)
+        let flat_at = |freq_hz: f32| -> Coefficients<f64> {
+            biquad_coeffs_f64(Type::PeakingEQ(0.0), sample_rate, freq_hz, 0.707)
+                .expect("0 dB PeakingEQ is always valid")
         };
 
         // Oversamplers are used inline (one sample in → one sample out), so
         // `max_block_size = 1` keeps their scratch buffers at 16 samples.
+        // High base rates run fewer stages.
         let make_os = || {
             let mut os = Oversampler::new(PULTEC_TUBE_OS_FACTOR, 1);
-            os.set_factor(PULTEC_TUBE_OS_FACTOR);
+            os.set_factor(factor_for_rate(PULTEC_TUBE_OS_FACTOR, sample_rate));
             os
         };
 
         Self {
             sample_rate,
-            lf_boost_filter: [flat_at(100.0), flat_at(100.0)],
-            lf_resonant_filter: [flat_at(100.0), flat_at(100.0)],
-            lf_cut_filter: [flat_at(80.0), flat_at(80.0)],
-            hf_boost_filter: [flat_at(8000.0), flat_at(8000.0)],
-            hf_cut_filter: [flat_at(10000.0), flat_at(10000.0)],
+            filters: StereoBiquads::new([
+                flat_at(100.0),
+                flat_at(100.0),
+                flat_at(80.0),
+                flat_at(8000.0),
+                flat_at(10000.0),
+            ]),
+            cached_eq: [f32::NAN; 11],
             tube_drive: 0.0,
             tube_os_l: make_os(),
             tube_os_r: make_os(),
+            tanh_table: Waveshaper::from_fn(TUBE_TABLE_RANGE, DEFAULT_POINTS, f32::tanh),
+            adaa: false,
+            tube_adaa: [Adaa1::new(); 2],
+            print_on: false,
+            print_mix: 0.0,
+            variance: Variance::new(VARIANCE_SEED, sample_rate),
+            engagement: Engagement::default(),
+            character: EqCharacter::Clean,
+            vintage: VintageStage::new(sample_rate, VINTAGE_CORNER_HZ, VINTAGE_NOISE_DB),
         }
     }
 
     /// Reset filter and saturation state. Call on sample-rate change or
     /// buffer discontinuity.
     pub fn reset(&mut self) {
+        self.filters.reset();
+        self.tube_os_l.reset();
+        self.tube_os_r.reset();
+        self.tube_adaa = [Adaa1::new(); 2];
+        self.print_mix = 0.0;
+        self.variance.reset();
+        self.engagement = Engagement::default();
+        self.vintage.reset();
+    }
+
+    /// How often the tube stage engaged since the last call; see
+    /// engagement.rs.
+    pub fn take_engagement(&mut self) -> Engagement {
+        self.engagement.take()
+    }
+
+    /// Vintage variance: ±2% tolerance and slow drift on the band
+    /// frequencies and gains (see variance.rs). Takes effect at the next
+    /// `update_parameters`.
+    pub fn set_variance(&mut self, on: bool) {
+        self.variance.set_enabled(on);
+    }
+
+    /// Clean runs the filters as set. Vintage lets each cut take a share off
+    /// the boost beside it, narrows the HF boost as it rises, and adds the
+    /// output transformer's top-octave phase lag and a noise floor after
+    /// the tube stage.
+    pub fn set_character(&mut self, character: EqCharacter) {
+        if character == self.character {
+            return;
+        }
+        if character == EqCharacter::Vintage {
+            self.vintage.reset();
+        }
+        self.character = character;
+        // Nothing to voice until the first `update_parameters`.
+        if !self.cached_eq[0].is_nan() {
+            self.update_filters();
+        }
+    }
+
+    /// Level of the Vintage noise floor relative to its voiced level.
+    pub fn set_noise_gain(&mut self, gain: f32) {
+        self.vintage.set_noise_gain(gain);
+    }
+
+    /// Anti-alias the live tube stage with first-order ADAA at the native
+    /// rate instead of 4× oversampling. The path switched to starts from
+    /// clean state.
+    pub fn set_adaa(&mut self, on: bool) {
+        if on == self.adaa {
+            return;
+        }
+        self.adaa = on;
+        self.tube_adaa = [Adaa1::new(); 2];
         self.tube_os_l.reset();
         self.tube_os_r.reset();
     }
 
+    /// Ask for the printed tube stage. It fades in once a curve printed
+    /// for the current drive is available, and fades out when turned off.
+    pub fn set_printed(&mut self, on: bool) {
+        self.print_on = on;
+    }
+
+    /// Whether any of the output currently comes from the print.
+    pub fn is_printed(&self) -> bool {
+        self.print_mix > 0.0
+    }
+
+    /// The live tube stage's trip through its oversampler, when it runs.
+    /// Once fully printed, or under ADAA, the output isn't delayed.
+    pub fn cascade_delay(&self) -> CascadeDelay {
+        if self.tube_drive <= 0.01 || self.adaa || self.print_mix >= 1.0 {
+            return CascadeDelay::NONE;
+        }
+        CascadeDelay {
+            factor: self.tube_os_l.factor(),
+            passes: 1,
+        }
+    }
+
+    /// When `curve` wasn't printed for the current drive, post the drive to
+    /// `shared` and return true: the caller then queues
+    /// `BackgroundTask::PrintTube`.
+    pub fn request_print(&self, curve: &StaticCurve, shared: &PrintShared) -> bool {
+        curve.drive() != self.tube_drive && shared.request(self.tube_drive)
+    }
+
     /// Update Pultec parameters
     ///
     /// # Arguments
@@ -125,11 +296,59 @@ impl PultecEQ {
     ) {
         self.tube_drive = tube_drive.clamp(0.0, 1.0);
 
+        // Called every buffer; only recompute coefficients when a filter
+        // argument actually changed or the variance drifted. Exact f32
+        // comparison is valid: these are the host's stored parameter
+        // values, not computed results.
+        let eq = [
+            lf_boost_freq,
+            lf_boost_db,
+            lf_boost_bandwidth,
+            lf_cut_freq,
+            lf_cut_db,
+            lf_cut_bandwidth,
+            hf_boost_freq,
+            hf_boost_db,
+            hf_boost_bandwidth,
+            hf_cut_freq,
+            hf_cut_db,
+        ];
+        let drifted = self.variance.take_dirty();
+        if eq == self.cached_eq && !drifted {
+            return;
+        }
+        self.cached_eq = eq;
+        self.update_filters();
+    }
+
+    /// Recompute the cascade from `cached_eq`, with vintage variance.
+    fn update_filters(&mut self) {
+        let [lf_boost_freq, lf_boost_db, lf_boost_bandwidth, lf_cut_freq, lf_cut_db, lf_cut_bandwidth, hf_boost_freq, hf_boost_db, hf_boost_bandwidth, hf_cut_freq, hf_cut_db] =
+            self.cached_eq;
+        let v = |i: usize| self.variance.factor(i);
+        let lf_boost_freq = lf_boost_freq * v(VAR_LF_BOOST_FREQ);
+        let lf_boost_db = lf_boost_db * v(VAR_LF_BOOST_GAIN);
+        let lf_cut_freq = lf_cut_freq * v(VAR_LF_CUT_FREQ);
+        let lf_cut_db = lf_cut_db * v(VAR_LF_CUT_GAIN);
+        let hf_boost_freq = hf_boost_freq * v(VAR_HF_BOOST_FREQ);
+        let hf_boost_db = hf_boost_db * v(VAR_HF_BOOST_GAIN);
+        let hf_cut_freq = hf_cut_freq * v(VAR_HF_CUT_FREQ);
+        let hf_cut_db = hf_cut_db * v(VAR_HF_CUT_GAIN);
+        let vintage = self.character == EqCharacter::Vintage;
+        let (lf_boost_db, hf_boost_db) = if vintage {
+            (
+                (lf_boost_db - VINTAGE_LOADING * lf_cut_db.max(0.0)).max(0.0),
+                (hf_boost_db - VINTAGE_LOADING * hf_cut_db.max(0.0)).max(0.0),
+            )
+        } else {
+            (lf_boost_db, hf_boost_db)
+        };
+
         // All four sections follow the same pattern:
         //   - compute dB (0.0 when the gain control is below noise floor)
-        //   - call update_coefficients() on the existing filter object
+        //   - swap the section's coefficients in the existing cascade
         // This preserves filter state across parameter changes (no state reset,
-        // no clicks) and avoids creating new DirectForm1 objects on the audio thread.
+        // no clicks) and avoids creating new filter objects on the audio thread.
 
         // Low Frequency Boost — LowShelf + resonant peak at the same frequency.
         // The passive LCR network in the real EQP-1A creates a resonant bump
@@ -140,26 +359,24 @@ impl PultecEQ {
         // BW=0 → Q=LF_SHELF_Q_NARROW (tight/modern), BW=1 → Q=LF_SHELF_Q_WIDE (vintage/gradual)
         let lf_boost_q = LF_SHELF_Q_NARROW
             + lf_boost_bandwidth.clamp(0.0, 1.0) * (LF_SHELF_Q_WIDE - LF_SHELF_Q_NARROW);
-        if let Ok(coeff) = biquad_coeffs(
-            Type::LowShelf(lf_boost_db),
+        if let Ok(coeff) = biquad_coeffs_f64(
+            Type::LowShelf(lf_boost_db as f64),
             self.sample_rate,
             safe_lf_freq,
             lf_boost_q,
         ) {
-            self.lf_boost_filter[0].update_coefficients(coeff);
-            self.lf_boost_filter[1].update_coefficients(coeff);
+            self.filters.set_coefficients(LF_BOOST, coeff);
         }
         // Resonant peak: 45% of shelf gain, Q=1.8, same center frequency.
         // Goes flat (0 dB) when the shelf is inactive.
         let resonant_db = lf_boost_db * LF_RESONANT_RATIO;
-        if let Ok(coeff) = biquad_coeffs(
-            Type::PeakingEQ(resonant_db),
+        if let Ok(coeff) = biquad_coeffs_f64(
+            Type::PeakingEQ(resonant_db as f64),
             self.sample_rate,
             safe_lf_freq,
             LF_RESONANT_Q,
         ) {
-            self.lf_resonant_filter[0].update_coefficients(coeff);
-            self.lf_resonant_filter[1].update_coefficients(coeff);
+            self.filters.set_coefficients(LF_RESONANT, coeff);
         }
 
         // Low Frequency Cut — independent frequency from boost. Classic
@@ -170,89 +387,213 @@ impl PultecEQ {
         let safe_lf_cut_freq = lf_cut_freq.clamp(20.0, 500.0);
         let lf_cut_q = LF_SHELF_Q_NARROW
             + lf_cut_bandwidth.clamp(0.0, 1.0) * (LF_SHELF_Q_WIDE - LF_SHELF_Q_NARROW);
-        if let Ok(coeff) = biquad_coeffs(
-            Type::LowShelf(lf_cut_db),
+        if let Ok(coeff) = biquad_coeffs_f64(
+            Type::LowShelf(lf_cut_db as f64),
             self.sample_rate,
             safe_lf_cut_freq,
             lf_cut_q,
         ) {
-            self.lf_cut_filter[0].update_coefficients(coeff);
-            self.lf_cut_filter[1].update_coefficients(coeff);
+            self.filters.set_coefficients(LF_CUT, coeff);
         }
 
         // High Frequency Boost — PeakingEQ, 0 dB when inactive.
         // Value is already in dB (parameter range 0..10 dB).
         let hf_boost_db = if hf_boost_db > 0.05 { hf_boost_db } else { 0.0 };
-        let hf_q = 0.6 + hf_boost_bandwidth * hf_boost_bandwidth * 1.4; // 0.6–2.0
+        let mut hf_q = 0.6 + hf_boost_bandwidth * hf_boost_bandwidth * 1.4; // 0.6–2.0
+        if vintage {
+            hf_q *= VINTAGE_HF_Q_MIN + VINTAGE_HF_Q_SPAN * (hf_boost_db / 10.0).min(1.0);
+        }
         let safe_hf_freq = hf_boost_freq.clamp(3000.0, 20000.0);
-        if let Ok(coeff) = biquad_coeffs(
-            Type::PeakingEQ(hf_boost_db),
+        if let Ok(coeff) = biquad_coeffs_f64(
+            Type::PeakingEQ(hf_boost_db as f64),
             self.sample_rate,
             safe_hf_freq,
             hf_q,
         ) {
-            self.hf_boost_filter[0].update_coefficients(coeff);
-            self.hf_boost_filter[1].update_coefficients(coeff);
+            self.filters.set_coefficients(HF_BOOST, coeff);
         }
 
         // High Frequency Cut — HighShelf, 0 dB when inactive.
         // Value is already in dB; negate for shelf cut.
         let hf_cut_db = if hf_cut_db > 0.05 { -hf_cut_db } else { 0.0 };
         let safe_hf_cut_freq = hf_cut_freq.clamp(5000.0, 20000.0);
-        if let Ok(coeff) = biquad_coeffs(
-            Type::HighShelf(hf_cut_db),
+        if let Ok(coeff) = biquad_coeffs_f64(
+            Type::HighShelf(hf_cut_db as f64),
             self.sample_rate,
             safe_hf_cut_freq,
             0.9,
         ) {
-            self.hf_cut_filter[0].update_coefficients(coeff);
-            self.hf_cut_filter[1].update_coefficients(coeff);
+            self.filters.set_coefficients(HF_CUT, coeff);
         }
     }
 
     /// Process audio buffer through Pultec EQ
+    #[cfg(feature = "plugin")]
     pub fn process(&mut self, buffer: &mut Buffer) {
-        let mut scratch = [0.0_f32; PULTEC_TUBE_OS_FACTOR];
-        for mut samples in buffer.iter_samples() {
-            for (ch, sample) in samples.iter_mut().enumerate() {
-                let ch = ch.min(1);
-                let mut s = *sample;
-
-                // Linear biquad chain. No inline clamps: stability is guaranteed
-                // by the coefficient math, and clamps between stages would inject
-                // memoryless distortion that aliases into the midrange.
-                s = self.lf_boost_filter[ch].run(s);
-                s = self.lf_resonant_filter[ch].run(s);
-                s = self.lf_cut_filter[ch].run(s);
-                s = self.hf_boost_filter[ch].run(s);
-                s = self.hf_cut_filter[ch].run(s);
-
-                // Tube saturation — the one intentional nonlinearity in this
-                // module. Run through a 4× halfband oversampler so the tanh
-                // harmonics do not fold back into the audible range.
-                if self.tube_drive > 0.01 {
-                    let drive_amount = self.tube_drive * 0.3;
-                    let scale = 1.0 + drive_amount * 0.2;
-                    let os = if ch == 0 {
-                        &mut self.tube_os_l
-                    } else {
-                        &mut self.tube_os_r
-                    };
-                    {
-                        let up = os.upsample(s, 0);
-                        for i in 0..PULTEC_TUBE_OS_FACTOR {
-                            scratch[i] = up[i].tanh() * scale;
-                        }
-                    }
-                    s = os.downsample(&scratch[..PULTEC_TUBE_OS_FACTOR], 0);
-                }
+        self.run(buffer.as_slice(), None);
+    }
+
+    /// Process with `curve` available as the printed tube stage.
+    #[cfg(feature = "plugin")]
+    pub fn process_printable(&mut self, buffer: &mut Buffer, curve: &StaticCurve) {
+        self.run(buffer.as_slice(), Some(curve));
+    }
+
+    /// Process one block given as per-channel slices (L, R, or mono).
+    pub fn process_channels(&mut self, channels: &mut [&mut [f32]]) {
+        self.run(channels, None);
+    }
+
+    /// Slice form of `process_printable`.
+    pub fn process_channels_printable(&mut self, channels: &mut [&mut [f32]], curve: &StaticCurve) {
+        self.run(channels, Some(curve));
+    }
+
+    fn run(&mut self, channels: &mut [&mut [f32]], curve: Option<&StaticCurve>) {
+        self.variance
+            .advance(channels.first().map_or(0, |ch| ch.len()));
+        let printed = self.print_on && curve.is_some_and(|c| c.drive() == self.tube_drive);
+        let step = if printed {
+            1.0 / PRINT_FADE_SAMPLES
+        } else {
+            -1.0 / PRINT_FADE_SAMPLES
+        };
+        // The oversamplers sat idle while fully printed; start them clean.
+        if !printed && self.print_mix >= 1.0 {
+            self.tube_os_l.reset();
+            self.tube_os_r.reset();
+        }
+        let drive = self.tube_drive;
+        let saturating = drive > 0.01;
 
-                *sample = s;
+        // Linear biquad chain, L and R together. No inline clamps: stability
+        // is guaranteed by the coefficient math, and clamps between stages
+        // would inject memoryless distortion that aliases into the midrange.
+        match channels {
+            [left, right, ..] => {
+                for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+                    let (out_l, out_r) = self.filters.run(*l, *r);
+                    self.engagement
+                        .tally(saturating && out_l.abs() > TUBE_ENGAGE_LEVEL);
+                    self.engagement
+                        .tally(saturating && out_r.abs() > TUBE_ENGAGE_LEVEL);
+                    let mix = self.print_mix;
+                    let [adaa_l, adaa_r] = &mut self.tube_adaa;
+                    let (adaa_l, adaa_r) =
+                        (self.adaa.then_some(adaa_l), self.adaa.then_some(adaa_r));
+                    let table = &self.tanh_table;
+                    *l = tube_stage(&mut self.tube_os_l, table, adaa_l, out_l, drive, curve, mix);
+                    *r = tube_stage(&mut self.tube_os_r, table, adaa_r, out_r, drive, curve, mix);
+                    self.print_mix = (mix + step).clamp(0.0, 1.0);
+                }
+            }
+            [mono] => {
+                for sample in mono.iter_mut() {
+                    let (out, _) = self.filters.run(*sample, 0.0);
+                    self.engagement
+                        .tally(saturating && out.abs() > TUBE_ENGAGE_LEVEL);
+                    let mix = self.print_mix;
+                    let adaa = self.adaa.then_some(&mut self.tube_adaa[0]);
+                    let table = &self.tanh_table;
+                    *sample = tube_stage(&mut self.tube_os_l, table, adaa, out, drive, curve, mix);
+                    self.print_mix = (mix + step).clamp(0.0, 1.0);
+                }
             }
+            [] => {}
+        }
+        if self.character == EqCharacter::Vintage {
+            self.vintage.process(channels, true);
+        }
+    }
+}
+
+/// The tube curve itself, as both the live stage and the print use it.
+#[inline]
+pub fn tube_transfer(x: f32, tube_drive: f32) -> f32 {
+    if tube_drive <= 0.01 {
+        return x;
+    }
+    x.tanh() * tube_scale(tube_drive)
+}
+
+/// The tube curve's make-up gain.
+#[inline]
+fn tube_scale(tube_drive: f32) -> f32 {
+    let drive_amount = tube_drive * 0.3;
+    1.0 + drive_amount * 0.2
+}
+
+/// `tube_transfer` with its antiderivative, for the ADAA path.
+struct TubeCurve {
+    drive: f32,
+}
+
+impl Antiderivative for TubeCurve {
+    #[inline]
+    fn eval(&self, x: f64) -> f64 {
+        tube_transfer(x as f32, self.drive) as f64
+    }
+
+    #[inline]
+    fn ad1(&self, x: f64) -> f64 {
+        if self.drive <= 0.01 {
+            return 0.5 * x * x;
         }
+        let scale = 1.0 + self.drive as f64 * 0.3 * 0.2;
+        ln_cosh(x) * scale
     }
 }
 
+/// Live tube stage, printed curve, or a `mix` of the two.
+#[inline]
+fn tube_stage(
+    os: &mut Oversampler,
+    table: &Waveshaper,
+    adaa: Option<&mut Adaa1>,
+    s: f32,
+    tube_drive: f32,
+    curve: Option<&StaticCurve>,
+    mix: f32,
+) -> f32 {
+    let printed = match curve {
+        Some(curve) if mix > 0.0 => curve.eval(s),
+        _ => return tube_saturate(os, table, adaa, s, tube_drive),
+    };
+    if mix >= 1.0 {
+        return printed;
+    }
+    let live = tube_saturate(os, table, adaa, s, tube_drive);
+    live + (printed - live) * mix
+}
+
+/// Tube saturation — the one intentional nonlinearity in this module. Runs
+/// through a 4× halfband oversampler so the tanh harmonics do not fold back
+/// into the audible range, reading `tanh` from `table`, or through
+/// first-order ADAA on the curve's nonlinear part when `adaa` is given.
+#[inline]
+fn tube_saturate(
+    os: &mut Oversampler,
+    table: &Waveshaper,
+    adaa: Option<&mut Adaa1>,
+    s: f32,
+    tube_drive: f32,
+) -> f32 {
+    if tube_drive <= 0.01 {
+        return s;
+    }
+    if let Some(adaa) = adaa {
+        return adaa.process_residual(&TubeCurve { drive: tube_drive }, s);
+    }
+    let mut scratch = [0.0_f32; PULTEC_TUBE_OS_FACTOR];
+    let scale = tube_scale(tube_drive);
+    let up = os.upsample(s, 0);
+    let factor = up.len();
+    for (out, &up) in scratch.iter_mut().zip(up) {
+        *out = table.eval(up) * scale;
+    }
+    os.downsample(&scratch[..factor], 0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,6 +624,83 @@ mod tests {
         );
     }
 
+    /// Run two seconds of a 100 Hz sine through a boosted Pultec.
+    fn render(variance: bool) -> Vec<f32> {
+        let sr = 48000.0;
+        let mut eq = PultecEQ::new(sr);
+        eq.set_variance(variance);
+        let mut out = Vec::new();
+        for block in 0..(2 * 48000 / 480) {
+            eq.update_parameters(
+                100.0, 8.0, 0.5, 300.0, 3.0, 0.5, 8000.0, 4.0, 0.5, 10000.0, 2.0, 0.0,
+            );
+            let mut l: Vec<f32> = (0..480)
+                .map(|i| {
+                    let t = (block * 480 + i) as f32 / sr;
+                    0.25 * (std::f32::consts::TAU * 100.0 * t).sin()
+                })
+                .collect();
+            let mut r = l.clone();
+            eq.process_channels(&mut [&mut l[..], &mut r[..]]);
+            out.extend_from_slice(&l);
+        }
+        out
+    }
+
+    #[test]
+    fn test_pultec_vintage_variance_is_subtle_and_reproducible() {
+        let clean = render(false);
+        let varied = render(true);
+        assert_eq!(varied, render(true), "same seed must render identically");
+        let peak = |x: &[f32]| x[48000..].iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+        let diff_db = 20.0 * (peak(&varied) / peak(&clean)).log10();
+        // ±2% on an 8 dB boost moves the level by a fraction of a dB.
+        assert!(diff_db != 0.0 && diff_db.abs() < 0.5, "{diff_db} dB");
+    }
+
+    #[test]
+    fn test_pultec_high_sample_rates_run_fewer_tube_stages() {
+        for (sr, factor) in [(88200.0, 2), (96000.0, 2), (192000.0, 1)] {
+            let mut eq = PultecEQ::new(sr);
+            assert_eq!(eq.tube_os_l.factor(), factor, "at {sr}");
+            for i in 0..1024 {
+                let x = 1.5 * (2.0 * core::f32::consts::PI * 0.2 * i as f32).sin();
+                let y = tube_saturate(&mut eq.tube_os_l, &eq.tanh_table, None, x, 1.0);
+                assert!(y.is_finite() && y.abs() < 2.0, "{y} at i={i}, sr={sr}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_pultec_update_parameters_skips_unchanged_filters() {
+        let mut eq = PultecEQ::new(44100.0);
+        assert!(eq.cached_eq.iter().all(|v| v.is_nan()));
+        let update = |eq: &mut PultecEQ, lf_boost_freq: f32, tube_drive: f32| {
+            eq.update_parameters(
+                lf_boost_freq,
+                7.5,
+                0.67,
+                200.0,
+                4.5,
+                0.5,
+                8000.0,
+                6.0,
+                0.5,
+                10000.0,
+                1.6,
+                tube_drive,
+            );
+        };
+        update(&mut eq, 60.0, 0.0);
+        assert_eq!(eq.cached_eq[0], 60.0);
+        assert_eq!(eq.cached_eq[10], 1.6);
+        // Tube drive is not a filter argument: it still updates on a cache hit.
+        update(&mut eq, 60.0, 0.5);
+        assert_eq!(eq.tube_drive, 0.5);
+        update(&mut eq, 90.0, 0.5);
+        assert_eq!(eq.cached_eq[0], 90.0);
+    }
+
     #[test]
     fn test_pultec_lf_cut_freq_clamping() {
         // lf_cut_freq is clamped to [20, 200]; extreme values must not panic.
@@ -362,24 +780,16 @@ mod tests {
     }
 
     /// Measure the actual steady-state gain PultecEQ applies through
-    /// `process()`, using a real nih_plug `Buffer`. This is the end-to-end
-    /// check the user-facing issue needs: "when I crank LF BOOST, do I
-    /// actually get ~+15 dB of boost below the shelf corner?"
+    /// `process_channels()`, the path `process()` runs a host buffer
+    /// through. This is the end-to-end check the user-facing issue needs:
+    /// "when I crank LF BOOST, do I actually get ~+15 dB of boost below the
+    /// shelf corner?"
     fn measure_gain_db(eq: &mut PultecEQ, freq_hz: f32, sr: f32) -> f32 {
-        use nih_plug::buffer::Buffer;
         let n = 8192_usize;
         let omega = 2.0 * core::f32::consts::PI * freq_hz / sr;
         let mut l: Vec<f32> = (0..n).map(|i| (omega * i as f32).sin()).collect();
         let mut r: Vec<f32> = l.clone();
-        let mut buf = Buffer::default();
-        unsafe {
-            buf.set_slices(n, |ss| {
-                ss.clear();
-                ss.push(&mut l);
-                ss.push(&mut r);
-            });
-        }
-        eq.process(&mut buf);
+        eq.process_channels(&mut [&mut l[..], &mut r[..]]);
         // Measure peak in the second half of the buffer — the first half
         // covers the biquad's transient warm-up.
         let peak = l[n / 2..].iter().fold(0.0_f32, |acc, &x| acc.max(x.abs()));
@@ -442,6 +852,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pultec_vintage_cut_loads_the_boost() {
+        // +10 dB at 60 Hz with 6 dB cut at 400 Hz: vintage takes 0.9 dB off
+        // the boost, so 30 Hz sits about that much lower than clean.
+        let sr = 48_000.0;
+        let render = |character| {
+            let mut eq = PultecEQ::new(sr);
+            eq.set_character(character);
+            eq.update_parameters(
+                60.0, 10.0, 0.67, 400.0, 6.0, 0.5, 10000.0, 0.0, 0.5, 10000.0, 0.0, 0.0,
+            );
+            measure_gain_db(&mut eq, 30.0, sr)
+        };
+        let (clean, vintage) = (render(EqCharacter::Clean), render(EqCharacter::Vintage));
+        let loss = clean - vintage;
+        assert!(
+            loss > 0.5 && loss < 1.5,
+            "clean {clean:.2} vintage {vintage:.2}"
+        );
+    }
+
     #[test]
     fn test_pultec_lf_boost_zero_is_unity() {
         // Sanity guard: with every gain at 0, the chain is transparent.
@@ -485,4 +916,127 @@ mod tests {
             assert!(y.abs() < 2.0, "implausibly large sample {y} at i={i}");
         }
     }
+
+    #[test]
+    fn test_pultec_engagement_follows_level_and_drive() {
+        let run = |amplitude: f32, drive: f32| {
+            let mut eq = PultecEQ::new(44100.0);
+            eq.update_parameters(
+                100.0, 0.0, 0.67, 100.0, 0.0, 0.5, 8000.0, 0.0, 0.5, 10000.0, 0.0, drive,
+            );
+            let omega = 2.0 * core::f32::consts::PI * 1000.0 / 44100.0;
+            let mut l: Vec<f32> = (0..4096)
+                .map(|i| amplitude * (omega * i as f32).sin())
+                .collect();
+            let mut r = l.clone();
+            eq.process_channels(&mut [&mut l[..], &mut r[..]]);
+            let e = eq.take_engagement();
+            assert_eq!(e.total, 8192);
+            assert_eq!(eq.take_engagement(), Engagement::default());
+            e.fraction().unwrap()
+        };
+        assert_eq!(run(0.1, 1.0), 0.0, "quiet program never reaches the knee");
+        assert_eq!(run(0.8, 0.0), 0.0, "no drive, no tube");
+        let hot = run(0.8, 1.0);
+        assert!(
+            hot > 0.7 && hot < 0.95,
+            "hot sine bends most samples: {hot}"
+        );
+    }
+
+    /// Run `n` samples of a 0.8-amplitude sine through `eq` (printable,
+    /// with `curve`) and return the output's RMS over the last half.
+    fn printable_rms(eq: &mut PultecEQ, curve: &StaticCurve, freq_hz: f32, sr: f32) -> f32 {
+        let n = 8192_usize;
+        let omega = 2.0 * core::f32::consts::PI * freq_hz / sr;
+        let mut l: Vec<f32> = (0..n).map(|i| 0.8 * (omega * i as f32).sin()).collect();
+        let mut r: Vec<f32> = l.clone();
+        eq.process_channels_printable(&mut [&mut l[..], &mut r[..]], curve);
+        (l[n / 2..].iter().map(|x| x * x).sum::<f32>() / (n / 2) as f32).sqrt()
+    }
+
+    #[test]
+    fn test_pultec_print_matches_live_tube_stage() {
+        let sr = 48_000.0;
+        let drive = 1.0;
+        let mut curve = StaticCurve::new();
+        curve.print(drive, |x| tube_transfer(x, drive));
+        let configure = |eq: &mut PultecEQ| {
+            eq.update_parameters(
+                100.0, 4.0, 0.67, 200.0, 2.0, 0.5, 10000.0, 3.0, 0.5, 10000.0, 0.0, drive,
+            );
+        };
+        let mut live = PultecEQ::new(sr);
+        let mut printed = PultecEQ::new(sr);
+        configure(&mut live);
+        configure(&mut printed);
+        printed.set_printed(true);
+
+        let live_rms = printable_rms(&mut live, &curve, 200.0, sr);
+        let printed_rms = printable_rms(&mut printed, &curve, 200.0, sr);
+        assert!(!live.is_printed() && printed.is_printed());
+        let diff_db = 20.0 * (printed_rms / live_rms).log10();
+        assert!(
+            diff_db.abs() < 0.1,
+            "print is {diff_db:.3} dB off the live stage"
+        );
+    }
+
+    #[test]
+    fn test_pultec_print_waits_for_a_matching_curve() {
+        let sr = 48_000.0;
+        let mut eq = PultecEQ::new(sr);
+        eq.update_parameters(
+            100.0, 0.0, 0.67, 100.0, 0.0, 0.5, 8000.0, 0.0, 0.5, 10000.0, 0.0, 0.6,
+        );
+        eq.set_printed(true);
+
+        // Unprinted curve: the live stage keeps running and a print is asked for.
+        let mut curve = StaticCurve::new();
+        let shared = PrintShared::new();
+        printable_rms(&mut eq, &curve, 1000.0, sr);
+        assert!(!eq.is_printed());
+        assert!(eq.request_print(&curve, &shared));
+
+        // A curve for another drive doesn't count either.
+        curve.print(0.3, |x| tube_transfer(x, 0.3));
+        printable_rms(&mut eq, &curve, 1000.0, sr);
+        assert!(!eq.is_printed());
+
+        curve.print(0.6, |x| tube_transfer(x, 0.6));
+        printable_rms(&mut eq, &curve, 1000.0, sr);
+        assert!(eq.is_printed());
+        assert!(!eq.request_print(&curve, &PrintShared::new()));
+
+        eq.set_printed(false);
+        printable_rms(&mut eq, &curve, 1000.0, sr);
+        assert!(!eq.is_printed());
+    }
+
+    #[test]
+    fn test_pultec_adaa_tube_stage_matches_oversampled() {
+        // Same curve either way: the antiderivative must differentiate back
+        // to it, and a 200 Hz program must come out at the same level.
+        let h = 1e-4;
+        for drive in [0.0, 0.5, 1.0] {
+            let curve = TubeCurve { drive };
+            for i in -30..=30 {
+                let x = i as f64 * 0.1 + 0.013;
+                let slope = (curve.ad1(x + h) - curve.ad1(x - h)) / (2.0 * h);
+                assert!((slope - curve.eval(x)).abs() < 1e-5, "drive {drive} at {x}");
+            }
+        }
+        let sr = 48_000.0;
+        let curve = StaticCurve::new();
+        let rms = |adaa: bool| {
+            let mut eq = PultecEQ::new(sr);
+            eq.set_adaa(adaa);
+            eq.update_parameters(
+                100.0, 0.0, 0.67, 100.0, 0.0, 0.5, 8000.0, 0.0, 0.5, 10000.0, 0.0, 1.0,
+            );
+            printable_rms(&mut eq, &curve, 200.0, sr)
+        };
+        let diff_db = 20.0 * (rms(true) / rms(false)).log10();
+        assert!(diff_db.abs() < 0.05, "ADAA is {diff_db} dB off");
+    }
 }
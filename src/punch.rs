@@ -15,10 +15,14 @@
 //!                    (parallel blend)
 //! ```
 
-use crate::oversampler::Oversampler;
+use crate::engagement::Engagement;
+use crate::envelope::TransientDetector;
+use crate::oversampler::{factor_for_rate, CascadeDelay, Oversampler};
 use crate::shaping::biquad_coeffs;
 use biquad::{Biquad, DirectForm1, Type};
+#[cfg(feature = "plugin")]
 use nih_plug::buffer::Buffer;
+#[cfg(feature = "plugin")]
 use nih_plug::prelude::Enum;
 
 /// Minimum wet-path HPF cutoff. Below this the filter is effectively bypassed
@@ -26,21 +30,67 @@ use nih_plug::prelude::Enum;
 /// sets this between 120 Hz and 400 Hz to leave low end to the dry path.
 const WET_HPF_MIN_HZ: f32 = 20.0;
 
+/// Range of the clipper tilt's pivot, and its maximum amount.
+const TILT_PIVOT_MIN_HZ: f32 = 100.0;
+const TILT_PIVOT_MAX_HZ: f32 = 5000.0;
+const TILT_MAX_DB: f32 = 12.0;
+
+/// Transient detector output smoothing: the default, which is what the
+/// shaper always used, and the top of the SMOOTH control.
+pub const DEFAULT_SMOOTHING_MS: f32 = 1.0;
+const SMOOTHING_MAX_MS: f32 = 20.0;
+
+// ============================================================================
+// Clipper Tilt
+// ============================================================================
+
+/// First-order tilt around a pivot, unity there: `tilt_db / 2` down at DC
+/// and up toward Nyquist. Bilinear with the pivot prewarped, so a tilt of
+/// −T is the exact inverse of +T.
+#[derive(Clone, Copy, Debug)]
+struct TiltCoeffs {
+    b0: f32,
+    b1: f32,
+    a1: f32,
+}
+
+impl TiltCoeffs {
+    fn new(sample_rate: f32, pivot_hz: f32, tilt_db: f32) -> Self {
+        let k = (std::f32::consts::PI * pivot_hz.min(0.45 * sample_rate) / sample_rate).tan();
+        // H(s) = A·(s + ωp/A) / (s + ωp·A), A the half tilt as a gain.
+        let a = 10.0_f32.powf(tilt_db / 40.0);
+        let norm = 1.0 / (1.0 + k * a);
+        Self {
+            b0: (a + k) * norm,
+            b1: (k - a) * norm,
+            a1: (k * a - 1.0) * norm,
+        }
+    }
+
+    #[inline]
+    fn run(&self, state: &mut f32, x: f32) -> f32 {
+        let y = self.b0 * x + *state;
+        *state = self.b1 * x - self.a1 * y;
+        y
+    }
+}
+
 // ============================================================================
 // Clipping Mode Enum
 // ============================================================================
 
 /// Clipping algorithm modes
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "plugin", derive(Enum))]
 pub enum ClipMode {
     /// Hard clip: mathematically cleanest, most transparent for small amounts
-    #[name = "Hard"]
+    #[cfg_attr(feature = "plugin", name = "Hard")]
     Hard,
     /// Soft clip (tanh): natural compression curve, warmer character
-    #[name = "Soft"]
+    #[cfg_attr(feature = "plugin", name = "Soft")]
     Soft,
     /// Cubic soft clip: polynomial curve, reduced high-frequency harmonics
-    #[name = "Cubic"]
+    #[cfg_attr(feature = "plugin", name = "Cubic")]
     Cubic,
 }
 
@@ -55,19 +105,20 @@ impl Default for ClipMode {
 // ============================================================================
 
 /// Oversampling factor for anti-aliasing
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "plugin", derive(Enum))]
 pub enum OversamplingFactor {
     /// No oversampling (testing only)
-    #[name = "1x"]
+    #[cfg_attr(feature = "plugin", name = "1x")]
     X1,
     /// 4x oversampling - good for real-time mixing
-    #[name = "4x"]
+    #[cfg_attr(feature = "plugin", name = "4x")]
     X4,
     /// 8x oversampling - recommended default
-    #[name = "8x"]
+    #[cfg_attr(feature = "plugin", name = "8x")]
     X8,
     /// 16x oversampling - mastering quality
-    #[name = "16x"]
+    #[cfg_attr(feature = "plugin", name = "16x")]
     X16,
 }
 
@@ -89,139 +140,6 @@ impl OversamplingFactor {
     }
 }
 
-// ============================================================================
-// Envelope Follower for Transient Detection
-// ============================================================================
-
-/// Single-pole envelope follower with attack/release
-struct EnvelopeFollower {
-    envelope: f32,
-    attack_coeff: f32,
-    release_coeff: f32,
-}
-
-impl EnvelopeFollower {
-    fn new(sample_rate: f32, attack_ms: f32, release_ms: f32) -> Self {
-        Self {
-            envelope: 0.0,
-            attack_coeff: Self::time_to_coeff(attack_ms, sample_rate),
-            release_coeff: Self::time_to_coeff(release_ms, sample_rate),
-        }
-    }
-
-    #[inline]
-    fn time_to_coeff(time_ms: f32, sample_rate: f32) -> f32 {
-        if time_ms <= 0.0 {
-            1.0
-        } else {
-            (-1.0 / (time_ms * 0.001 * sample_rate)).exp()
-        }
-    }
-
-    fn update_times(&mut self, sample_rate: f32, attack_ms: f32, release_ms: f32) {
-        self.attack_coeff = Self::time_to_coeff(attack_ms, sample_rate);
-        self.release_coeff = Self::time_to_coeff(release_ms, sample_rate);
-    }
-
-    #[inline]
-    fn process(&mut self, input: f32) -> f32 {
-        let abs_input = input.abs();
-        let coeff = if abs_input > self.envelope {
-            self.attack_coeff
-        } else {
-            self.release_coeff
-        };
-        self.envelope = coeff * self.envelope + (1.0 - coeff) * abs_input;
-        self.envelope
-    }
-
-    fn reset(&mut self) {
-        self.envelope = 0.0;
-    }
-}
-
-// ============================================================================
-// Transient Detector
-// ============================================================================
-
-/// Differential envelope transient detector
-/// Uses fast - slow envelope to detect transients
-struct TransientDetector {
-    fast_envelope: EnvelopeFollower,
-    slow_envelope: EnvelopeFollower,
-    sensitivity: f32,
-    smoothed_transient: f32,
-    smoothing_coeff: f32,
-}
-
-impl TransientDetector {
-    fn new(sample_rate: f32) -> Self {
-        // Fast envelope: 0.5ms attack, 5ms release (captures transient onset)
-        let fast_envelope = EnvelopeFollower::new(sample_rate, 0.5, 5.0);
-        // Slow envelope: 20ms attack, 100ms release (captures body/sustain)
-        let slow_envelope = EnvelopeFollower::new(sample_rate, 20.0, 100.0);
-
-        Self {
-            fast_envelope,
-            slow_envelope,
-            sensitivity: 0.5,
-            smoothed_transient: 0.0,
-            smoothing_coeff: Self::calc_smoothing_coeff(sample_rate, 2.0),
-        }
-    }
-
-    fn calc_smoothing_coeff(sample_rate: f32, time_ms: f32) -> f32 {
-        (-1.0 / (time_ms * 0.001 * sample_rate)).exp()
-    }
-
-    fn update_parameters(
-        &mut self,
-        sample_rate: f32,
-        attack_time_ms: f32,
-        release_time_ms: f32,
-        sensitivity: f32,
-    ) {
-        // Fast envelope tracks transients
-        self.fast_envelope
-            .update_times(sample_rate, attack_time_ms * 0.1, attack_time_ms);
-        // Slow envelope tracks body
-        self.slow_envelope
-            .update_times(sample_rate, release_time_ms * 0.2, release_time_ms);
-        self.sensitivity = sensitivity;
-        // Anti-click smoothing
-        self.smoothing_coeff = Self::calc_smoothing_coeff(sample_rate, 1.0);
-    }
-
-    /// Process a sample and return transient amount (0.0 to 1.0+)
-    #[inline]
-    fn process(&mut self, input: f32) -> f32 {
-        let fast_env = self.fast_envelope.process(input);
-        let slow_env = self.slow_envelope.process(input);
-
-        // Differential: transient = how much faster is rising than slow
-        let raw_transient = if slow_env > 0.0001 {
-            ((fast_env - slow_env) / slow_env).max(0.0)
-        } else {
-            0.0
-        };
-
-        // Apply sensitivity scaling
-        let scaled_transient = raw_transient * self.sensitivity * 4.0;
-
-        // Smooth to prevent clicks
-        self.smoothed_transient = self.smoothing_coeff * self.smoothed_transient
-            + (1.0 - self.smoothing_coeff) * scaled_transient;
-
-        self.smoothed_transient
-    }
-
-    fn reset(&mut self) {
-        self.fast_envelope.reset();
-        self.slow_envelope.reset();
-        self.smoothed_transient = 0.0;
-    }
-}
-
 // ============================================================================
 // Clipper Algorithms
 // ============================================================================
@@ -313,11 +231,12 @@ pub struct PunchModule {
     oversampling: OversamplingFactor,
 
     // Transient shaper parameters
-    attack: f32,       // -1.0 to +1.0 (cut to boost)
+    attack: [f32; 2],  // -1.0 to +1.0 (cut to boost), per channel
     sustain: f32,      // -1.0 to +1.0
     attack_time: f32,  // 0.1ms - 30ms
     release_time: f32, // 10ms - 500ms
     sensitivity: f32,  // 0.0 - 1.0
+    smoothing_ms: f32, // detector output smoothing; see `set_smoothing`
 
     // Global controls
     input_gain: f32,  // Linear gain
@@ -330,6 +249,12 @@ pub struct PunchModule {
     transient_detector_r: TransientDetector,
     oversampler_l: Oversampler,
     oversampler_r: Oversampler,
+    /// Dry path through the same halfband cascade, minus the clipper, so
+    /// it arrives with the wet path's delay (a fractional 11–21 samples)
+    /// and band-limiting and the mix stays phase-coherent. Runs only while
+    /// the mix lets any dry through.
+    dry_os_l: Oversampler,
+    dry_os_r: Oversampler,
 
     // Parallel-path HPF — applied to the clipped/shaped wet signal only so
     // Simon-Phillips-style drum submix blends punch/attack energy on top of
@@ -337,15 +262,28 @@ pub struct PunchModule {
     wet_hpf_l: DirectForm1<f32>,
     wet_hpf_r: DirectForm1<f32>,
 
+    /// Clipper tilt: the wet signal is tilted toward the top before the
+    /// clipper and back after it, so a bass-heavy bus doesn't spend the
+    /// whole threshold on its lows. 0 dB is off; see `set_tilt`.
+    tilt_db: f32,
+    tilt_pivot_hz: f32,
+    tilt_pre: TiltCoeffs,
+    tilt_post: TiltCoeffs,
+    /// Pre- and post-clipper filter state per channel.
+    tilt_state: [(f32, f32); 2],
+
     // Metering (for GUI)
     current_gain_reduction: f32,
     current_transient_activity: f32,
+    /// Samples the clipper bent since the last `take_engagement`.
+    engagement: Engagement,
 }
 
 impl PunchModule {
     /// Maximum oversampling factor
     const MAX_OS_FACTOR: usize = 16;
-    /// Maximum block size for oversampling buffers
+    /// Length of the oversamplers' scratch, in native samples. Host blocks
+    /// longer than this wrap around it (see `process`).
     const MAX_BLOCK_SIZE: usize = 8192;
 
     /// Create a new Punch module instance
@@ -353,7 +291,7 @@ impl PunchModule {
         let hpf_coeffs = biquad_coeffs(Type::HighPass, sample_rate, WET_HPF_MIN_HZ, 0.707)
             .expect("HighPass with defaults is always valid");
 
-        Self {
+        let mut punch = Self {
             sample_rate,
 
             // Default clipper settings
@@ -363,11 +301,12 @@ impl PunchModule {
             oversampling: OversamplingFactor::X4,
 
             // Default transient shaper settings
-            attack: 0.2,         // +20% boost
-            sustain: 0.0,        // Neutral
-            attack_time: 5.0,    // 5ms
-            release_time: 100.0, // 100ms
-            sensitivity: 0.5,    // 50%
+            attack: [0.2; 2],       // +20% boost
+            sustain: 0.0,           // Neutral
+            attack_time: 5.0,       // 5ms
+            release_time: 100.0,    // 100ms
+            sensitivity: 0.5,       // 50%
+            smoothing_ms: f32::NAN, // set below
 
             // Default global controls
             input_gain: 1.0,
@@ -380,13 +319,38 @@ impl PunchModule {
             transient_detector_r: TransientDetector::new(sample_rate),
             oversampler_l: Oversampler::new(Self::MAX_OS_FACTOR, Self::MAX_BLOCK_SIZE),
             oversampler_r: Oversampler::new(Self::MAX_OS_FACTOR, Self::MAX_BLOCK_SIZE),
+            dry_os_l: Oversampler::new(Self::MAX_OS_FACTOR, 1),
+            dry_os_r: Oversampler::new(Self::MAX_OS_FACTOR, 1),
 
             wet_hpf_l: DirectForm1::<f32>::new(hpf_coeffs),
             wet_hpf_r: DirectForm1::<f32>::new(hpf_coeffs),
 
+            tilt_db: 0.0,
+            tilt_pivot_hz: 1000.0,
+            tilt_pre: TiltCoeffs::new(sample_rate, 1000.0, 0.0),
+            tilt_post: TiltCoeffs::new(sample_rate, 1000.0, 0.0),
+            tilt_state: [(0.0, 0.0); 2],
+
             // Metering
             current_gain_reduction: 0.0,
             current_transient_activity: 0.0,
+            engagement: Engagement::default(),
+        };
+        punch.set_smoothing(DEFAULT_SMOOTHING_MS);
+        punch
+    }
+
+    /// Oversampling factor in effect: the selected one, scaled down at
+    /// high base rates (X8 runs 4× at 96 kHz, 2× at 192 kHz).
+    fn os_factor(&self) -> usize {
+        factor_for_rate(self.oversampling.factor(), self.sample_rate)
+    }
+
+    /// The whole output, dry included, makes one trip through the cascade.
+    pub fn cascade_delay(&self) -> CascadeDelay {
+        CascadeDelay {
+            factor: self.os_factor(),
+            passes: 1,
         }
     }
 
@@ -417,7 +381,7 @@ impl PunchModule {
         self.softness = softness.clamp(0.0, 1.0);
         self.oversampling = oversampling;
 
-        self.attack = attack.clamp(-1.0, 1.0);
+        self.attack = [attack.clamp(-1.0, 1.0); 2];
         self.sustain = sustain.clamp(-1.0, 1.0);
         self.attack_time = attack_time_ms.clamp(0.1, 30.0);
         self.release_time = release_time_ms.clamp(10.0, 500.0);
@@ -425,7 +389,14 @@ impl PunchModule {
 
         self.input_gain = db_to_linear(input_gain_db);
         self.output_gain = db_to_linear(output_gain_db);
-        self.mix = mix.clamp(0.0, 1.0);
+        let mix = mix.clamp(0.0, 1.0);
+        // The dry path sat idle at full wet; start it from silence rather
+        // than from whatever it held when it stopped.
+        if self.mix >= 1.0 && mix < 1.0 {
+            self.dry_os_l.reset();
+            self.dry_os_r.reset();
+        }
+        self.mix = mix;
 
         // Recompute HPF coefficients only when cutoff changes, preserving
         // filter state across parameter updates (update_coefficients keeps
@@ -440,71 +411,120 @@ impl PunchModule {
         }
 
         // Update oversamplers
-        let os_factor = self.oversampling.factor();
+        let os_factor = self.os_factor();
         self.oversampler_l.set_factor(os_factor);
         self.oversampler_r.set_factor(os_factor);
+        self.dry_os_l.set_factor(os_factor);
+        self.dry_os_r.set_factor(os_factor);
 
         // Update transient detectors at NATIVE sample rate.
         // Detection now runs pre-oversampling, so time constants are calibrated
         // to the native rate. Using oversampled rate would make them too fast
         // (e.g., 8x oversampled at 44.1kHz would be ~353kHz rate).
         self.transient_detector_l.update_parameters(
-            self.sample_rate,
             self.attack_time,
             self.release_time,
             self.sensitivity,
         );
         self.transient_detector_r.update_parameters(
-            self.sample_rate,
             self.attack_time,
             self.release_time,
             self.sensitivity,
         );
     }
 
+    /// Time constant of the anti-click smoothing on the transient
+    /// detectors' output, 0–20 ms (default 1 ms). The detectors run at the
+    /// native rate, so it holds at any sample rate and oversampling factor.
+    /// Longer rounds the shaper's gain moves off; shorter keeps more of the
+    /// detector's edge.
+    pub fn set_smoothing(&mut self, smoothing_ms: f32) {
+        let smoothing_ms = smoothing_ms.clamp(0.0, SMOOTHING_MAX_MS);
+        if smoothing_ms == self.smoothing_ms {
+            return;
+        }
+        self.smoothing_ms = smoothing_ms;
+        self.transient_detector_l
+            .set_smoothing(smoothing_ms, self.sample_rate);
+        self.transient_detector_r
+            .set_smoothing(smoothing_ms, self.sample_rate);
+    }
+
+    /// Tilt the clipper's input by `tilt_db` (0–12) around `pivot_hz`:
+    /// half of it down below the pivot and half up above, undone straight
+    /// after the clipper. The lows reach the threshold later and the highs
+    /// sooner, which is how mastering clippers keep a kick or a bass line
+    /// from doing all the clipping. Below the threshold the pair cancels
+    /// and the wet path is unchanged. 0 dB switches it off.
+    pub fn set_tilt(&mut self, tilt_db: f32, pivot_hz: f32) {
+        let tilt_db = tilt_db.clamp(0.0, TILT_MAX_DB);
+        let pivot_hz = pivot_hz.clamp(TILT_PIVOT_MIN_HZ, TILT_PIVOT_MAX_HZ);
+        if tilt_db == self.tilt_db && pivot_hz == self.tilt_pivot_hz {
+            return;
+        }
+        // The filters sat idle while off; start them from silence.
+        if self.tilt_db == 0.0 {
+            self.tilt_state = [(0.0, 0.0); 2];
+        }
+        self.tilt_db = tilt_db;
+        self.tilt_pivot_hz = pivot_hz;
+        self.tilt_pre = TiltCoeffs::new(self.sample_rate, pivot_hz, tilt_db);
+        self.tilt_post = TiltCoeffs::new(self.sample_rate, pivot_hz, -tilt_db);
+    }
+
+    /// Give each channel its own attack after `update_parameters` set a
+    /// shared one: the punch link in dual mono, where each channel follows
+    /// its own compressor reduction.
+    pub fn set_channel_attack(&mut self, attack: [f32; 2]) {
+        self.attack = attack.map(|a| a.clamp(-1.0, 1.0));
+    }
+
     /// Process a stereo buffer in-place.
     ///
     /// Signal path (pumping-free design):
     ///   Input → InputGain → TransientShape → Oversample → Clip → Downsample → Mix → OutputGain
+    ///   Input → InputGain → Oversample → Downsample ──────────────────────────┘ (dry)
     ///
     /// The transient detector runs at the NATIVE sample rate on the pre-clip signal.
     /// Gain adjustment is applied BEFORE oversampling, so the clipper naturally
     /// limits any resulting peaks. This eliminates post-clip time-varying gain
     /// modulation, which was the root cause of the pumping artifacts.
+    #[cfg(feature = "plugin")]
     pub fn process(&mut self, buffer: &mut Buffer) {
-        let os_factor = self.oversampling.factor();
+        self.process_channels(buffer.as_slice());
+    }
+
+    /// Process one block given as per-channel slices. Only the first two
+    /// channels are processed; any others pass through untouched.
+    pub fn process_channels(&mut self, channels: &mut [&mut [f32]]) {
+        let os_factor = self.os_factor();
         let mut temp_os_buffer = [0.0f32; Self::MAX_OS_FACTOR];
 
         let mut max_gr = 0.0f32;
         let mut max_transient = 0.0f32;
 
-        for (sample_idx, mut channel_samples) in buffer.iter_samples().enumerate() {
-            // Stack-allocated channel pointer array — no Vec, no heap allocation on the audio thread.
-            // SAFETY: each pointer is derived from a valid `&mut f32` reference within this
-            // sample block. Channels are processed sequentially, so no aliasing occurs.
-            let mut channel_ptrs: [*mut f32; 2] = [std::ptr::null_mut(); 2];
-            let mut num_channels = 0_usize;
-            for s in channel_samples.iter_mut() {
-                if num_channels < 2 {
-                    channel_ptrs[num_channels] = s as *mut f32;
-                    num_channels += 1;
-                }
-            }
-
-            for ch_idx in 0..num_channels {
-                // SAFETY: channel_ptrs[ch_idx] was assigned from a valid mutable reference
-                // and remains valid for the duration of this loop body.
-                let sample_ptr = channel_ptrs[ch_idx];
-                let sample = unsafe { *sample_ptr };
+        let num_channels = channels.len().min(2);
+        let len = channels.first().map_or(0, |ch| ch.len());
+        for sample_idx in 0..len {
+            for (ch_idx, channel) in channels[..num_channels].iter_mut().enumerate() {
+                let sample = channel[sample_idx];
 
                 // 1. Apply input gain
                 let gained = sample * self.input_gain;
                 let dry = gained;
 
-                let (oversampler, transient_detector) = if ch_idx == 0 {
-                    (&mut self.oversampler_l, &mut self.transient_detector_l)
+                let (oversampler, dry_os, transient_detector) = if ch_idx == 0 {
+                    (
+                        &mut self.oversampler_l,
+                        &mut self.dry_os_l,
+                        &mut self.transient_detector_l,
+                    )
                 } else {
-                    (&mut self.oversampler_r, &mut self.transient_detector_r)
+                    (
+                        &mut self.oversampler_r,
+                        &mut self.dry_os_r,
+                        &mut self.transient_detector_r,
+                    )
                 };
 
                 // 2. Detect transients at NATIVE sample rate on the pre-clip signal.
@@ -516,10 +536,11 @@ impl PunchModule {
                 // 3. Apply transient shaping gain PRE-CLIP.
                 //    Because the gain change happens before the clipper, any resulting
                 //    peaks are naturally limited by the clipper — no pumping.
-                let pre_clip = if self.attack.abs() > 0.001 || self.sustain.abs() > 0.001 {
+                let attack = self.attack[ch_idx];
+                let pre_clip = if attack.abs() > 0.001 || self.sustain.abs() > 0.001 {
                     let t = transient_amount.min(1.0);
                     // Transient (fast-onset) gain: boost/cut on signal attacks
-                    let transient_mult = 1.0 + t * self.attack * 0.5;
+                    let transient_mult = 1.0 + t * attack * 0.5;
                     // Sustain (slow-decay) gain: boost/cut on held portions
                     let sustain_mult = 1.0 + (1.0 - t) * self.sustain * 0.3;
                     // Blend based on transient amount; clamp to prevent extreme levels
@@ -528,10 +549,22 @@ impl PunchModule {
                 } else {
                     gained
                 };
+                let tilted = self.tilt_db > 0.0;
+                let pre_clip = if tilted {
+                    self.tilt_pre.run(&mut self.tilt_state[ch_idx].0, pre_clip)
+                } else {
+                    pre_clip
+                };
 
                 // 4. Oversample → Clip → Downsample
-                let upsampled = oversampler.upsample(pre_clip, sample_idx);
-
+                // The scratch slot only has to live for this one sample, so
+                // wrapping the index keeps blocks longer than the scratch
+                // (a host raising its block size without re-initializing)
+                // in bounds without reallocating on the audio thread.
+                let os_idx = sample_idx % Self::MAX_BLOCK_SIZE;
+                let upsampled = oversampler.upsample(pre_clip, os_idx);
+
+                let mut bent = false;
                 for (os_idx, &os_sample) in upsampled.iter().enumerate() {
                     let clipped = apply_clipping(
                         os_sample,
@@ -545,11 +578,19 @@ impl PunchModule {
                         let gr = (os_sample.abs() - clipped.abs()) / os_sample.abs();
                         max_gr = max_gr.max(gr);
                     }
+                    bent |= Engagement::bent(os_sample, clipped);
 
                     temp_os_buffer[os_idx] = clipped;
                 }
+                self.engagement.tally(bent);
 
-                let processed = oversampler.downsample(&temp_os_buffer[..os_factor], sample_idx);
+                let processed = oversampler.downsample(&temp_os_buffer[..os_factor], os_idx);
+                let processed = if tilted {
+                    self.tilt_post
+                        .run(&mut self.tilt_state[ch_idx].1, processed)
+                } else {
+                    processed
+                };
 
                 // 5. Apply wet-path HPF so the parallel blend adds attack/punch
                 //    without muddying the dry signal's low end. When cutoff is
@@ -561,14 +602,15 @@ impl PunchModule {
                 };
                 let wet = wet_hpf.run(processed);
 
-                // 6. Mix and output
-                let mixed = dry * (1.0 - self.mix) + wet * self.mix;
+                // 6. Mix and output, the dry delayed to line up with the wet.
+                let mixed = if self.mix < 1.0 {
+                    dry_os.round_trip(dry) * (1.0 - self.mix) + wet * self.mix
+                } else {
+                    wet
+                };
                 let output = mixed * self.output_gain;
 
-                // SAFETY: sample_ptr is valid and aligned (set above from NIH-plug buffer).
-                unsafe {
-                    *sample_ptr = output;
-                }
+                channel[sample_idx] = output;
             }
         }
 
@@ -584,17 +626,25 @@ impl PunchModule {
         self.transient_detector_r.reset();
         self.oversampler_l.reset();
         self.oversampler_r.reset();
+        self.dry_os_l.reset();
+        self.dry_os_r.reset();
+        self.tilt_state = [(0.0, 0.0); 2];
         self.current_gain_reduction = 0.0;
         self.current_transient_activity = 0.0;
+        self.engagement = Engagement::default();
     }
 
     /// Get current gain reduction (0.0 - 1.0) for metering.
-    /// Reserved for future clipper GR visualization.
-    #[allow(dead_code)]
     pub fn get_gain_reduction(&self) -> f32 {
         self.current_gain_reduction
     }
 
+    /// How often the clipper engaged since the last call; see
+    /// engagement.rs.
+    pub fn take_engagement(&mut self) -> Engagement {
+        self.engagement.take()
+    }
+
     /// Get current transient activity (0.0 - 1.0+) for metering.
     /// Reserved for future transient detector visualization.
     #[allow(dead_code)]
@@ -673,10 +723,10 @@ mod tests {
 
     #[test]
     fn test_envelope_follower() {
-        let mut env = EnvelopeFollower::new(44100.0, 1.0, 100.0);
+        let mut env = crate::envelope::EnvelopeFollower::new(44100.0, 1.0, 100.0);
 
         // Initial state should be 0
-        assert!(env.envelope < 0.0001);
+        assert!(env.value() < 0.0001);
 
         // Process a step input
         for _ in 0..100 {
@@ -684,7 +734,7 @@ mod tests {
         }
 
         // Envelope should have risen
-        assert!(env.envelope > 0.5);
+        assert!(env.value() > 0.5);
     }
 
     #[test]
@@ -725,6 +775,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_transient_smoothing_holds_across_sample_rates() {
+        // The detector's peak after a step lands at the same time in ms at
+        // any rate, and more smoothing lands it later.
+        let peak_ms = |sample_rate: f32, smoothing_ms: f32| {
+            let mut detector = TransientDetector::new(sample_rate);
+            detector.update_parameters(5.0, 100.0, 0.5);
+            detector.set_smoothing(smoothing_ms, sample_rate);
+            let n = (0.05 * sample_rate) as usize;
+            let (mut peak, mut at) = (0.0_f32, 0);
+            for i in 0..n {
+                let t = detector.process(1.0);
+                if t > peak {
+                    peak = t;
+                    at = i;
+                }
+            }
+            at as f32 * 1000.0 / sample_rate
+        };
+        let slow_48k = peak_ms(48_000.0, 5.0);
+        let slow_96k = peak_ms(96_000.0, 5.0);
+        assert!(
+            (slow_48k - slow_96k).abs() < 0.1,
+            "{slow_48k} ms vs {slow_96k} ms"
+        );
+        assert!(peak_ms(48_000.0, 0.0) < slow_48k);
+    }
+
     #[test]
     fn test_db_conversion() {
         // 0dB should be 1.0
@@ -766,7 +844,234 @@ mod tests {
 
         assert_eq!(punch.clip_mode, ClipMode::Soft);
         assert!((punch.softness - 0.5).abs() < 0.001);
-        assert!((punch.attack - 0.5).abs() < 0.001);
+        assert!((punch.attack[0] - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_punch_dry_path_lines_up_with_the_oversampled_wet() {
+        // Nothing clips and the shaper is neutral, so wet and dry differ
+        // only by the wet HPF and, unless it is compensated, the
+        // oversamplers' delay: ~16.5 samples at 4×, a quarter turn and more
+        // at 1 kHz, which a 50% mix would turn into a comb.
+        let render = |mix: f32| {
+            let mut punch = PunchModule::new(48000.0);
+            punch.update_parameters(
+                0.0,
+                ClipMode::Hard,
+                0.0,
+                OversamplingFactor::X4,
+                0.0,
+                0.0,
+                5.0,
+                100.0,
+                0.5,
+                0.0,
+                0.0,
+                mix,
+                20.0,
+            );
+            let mut l: Vec<f32> = (0..4800)
+                .map(|i| 0.25 * (std::f32::consts::TAU * 1000.0 * i as f32 / 48000.0).sin())
+                .collect();
+            let mut r = l.clone();
+            punch.process_channels(&mut [&mut l[..], &mut r[..]]);
+            l
+        };
+        let (wet, half) = (render(1.0), render(0.5));
+        let worst = wet[2400..]
+            .iter()
+            .zip(&half[2400..])
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(worst < 0.0125, "50% mix strays {worst} from full wet");
+    }
+
+    #[test]
+    fn test_punch_oversampling_scales_with_sample_rate() {
+        let n = 512;
+        for (sr, factor) in [(48000.0, 8), (96000.0, 4), (192000.0, 2)] {
+            let mut punch = PunchModule::new(sr);
+            punch.update_parameters(
+                -6.0,
+                ClipMode::Hard,
+                0.0,
+                OversamplingFactor::X8,
+                0.5,
+                0.0,
+                5.0,
+                100.0,
+                0.5,
+                6.0,
+                0.0,
+                1.0,
+                20.0,
+            );
+            assert_eq!(punch.os_factor(), factor, "at {sr}");
+            assert_eq!(punch.oversampler_l.factor(), factor, "at {sr}");
+
+            let mut data_l: Vec<f32> = (0..n).map(|i| ((i as f32) * 0.3).sin()).collect();
+            let mut data_r = data_l.clone();
+            punch.process_channels(&mut [&mut data_l[..], &mut data_r[..]]);
+            assert!(data_l.iter().all(|s| s.is_finite() && s.abs() < 1.0));
+        }
+    }
+
+    /// A host may hand over a longer block than it announced at
+    /// initialize; the oversampler scratch must not be indexed past its end.
+    #[test]
+    fn test_punch_handles_blocks_longer_than_scratch() {
+        let n = PunchModule::MAX_BLOCK_SIZE + 1000;
+        let mut punch = PunchModule::new(44100.0);
+        punch.update_parameters(
+            -6.0,
+            ClipMode::Hard,
+            0.0,
+            OversamplingFactor::X16,
+            1.0,
+            0.0,
+            5.0,
+            100.0,
+            0.5,
+            0.0,
+            0.0,
+            1.0,
+            20.0,
+        );
+        let mut data_l: Vec<f32> = (0..n).map(|i| 1.5 * ((i as f32) * 0.01).sin()).collect();
+        let mut data_r = data_l.clone();
+        punch.process_channels(&mut [&mut data_l[..], &mut data_r[..]]);
+        assert!(data_l[PunchModule::MAX_BLOCK_SIZE..]
+            .iter()
+            .all(|s| s.is_finite() && s.abs() < 1.0));
+    }
+
+    #[test]
+    fn test_punch_engagement_tracks_clipped_samples() {
+        let run = |amplitude: f32| {
+            let mut punch = PunchModule::new(44100.0);
+            punch.update_parameters(
+                -6.0,
+                ClipMode::Hard,
+                0.0,
+                OversamplingFactor::X4,
+                0.0,
+                0.0,
+                5.0,
+                100.0,
+                0.5,
+                0.0,
+                0.0,
+                1.0,
+                20.0,
+            );
+            let omega = 2.0 * core::f32::consts::PI * 100.0 / 44100.0;
+            let mut l: Vec<f32> = (0..4096)
+                .map(|i| amplitude * (omega * i as f32).sin())
+                .collect();
+            let mut r = l.clone();
+            punch.process_channels(&mut [&mut l[..], &mut r[..]]);
+            let e = punch.take_engagement();
+            assert_eq!(e.total, 8192);
+            e.fraction().unwrap()
+        };
+        assert_eq!(
+            run(0.25),
+            0.0,
+            "below the ceiling the hard clipper is linear"
+        );
+        // -6 dB ceiling on a full-scale sine: |sin| > 0.5 two thirds of the time.
+        let hot = run(1.0);
+        assert!((hot - 2.0 / 3.0).abs() < 0.05, "got {hot}");
+    }
+
+    /// Two 4096-sample blocks of `signal` through a −6 dB hard clipper
+    /// with `tilt_db` around 1 kHz: the second block, and the share of it
+    /// the clipper bent.
+    fn render_tilted(tilt_db: f32, signal: impl Fn(f32) -> f32) -> (Vec<f32>, f32) {
+        let mut punch = PunchModule::new(48000.0);
+        punch.update_parameters(
+            -6.0,
+            ClipMode::Hard,
+            0.0,
+            OversamplingFactor::X4,
+            0.0,
+            0.0,
+            5.0,
+            100.0,
+            0.5,
+            0.0,
+            0.0,
+            1.0,
+            20.0,
+        );
+        punch.set_tilt(tilt_db, 1000.0);
+        let mut block: Vec<f32> = (0..8192).map(|i| signal(i as f32 / 48000.0)).collect();
+        let (first, second) = block.split_at_mut(4096);
+        punch.process_channels(&mut [first]);
+        punch.take_engagement();
+        punch.process_channels(&mut [second]);
+        (second.to_vec(), punch.take_engagement().fraction().unwrap())
+    }
+
+    #[test]
+    fn test_punch_tilt_spares_the_lows() {
+        // 60 Hz 1.6 dB over the ceiling: clipped flat, but tilted 6 dB down
+        // on the way in it stays under.
+        let bass = |t: f32| 0.6 * (std::f32::consts::TAU * 60.0 * t).sin();
+        assert!(render_tilted(0.0, bass).1 > 0.1);
+        assert_eq!(render_tilted(12.0, bass).1, 0.0);
+        // The top goes the other way.
+        let air = |t: f32| 0.35 * (std::f32::consts::TAU * 12_000.0 * t).sin();
+        assert_eq!(render_tilted(0.0, air).1, 0.0);
+        assert!(render_tilted(12.0, air).1 > 0.1);
+    }
+
+    #[test]
+    fn test_punch_tilt_cancels_below_the_ceiling() {
+        let mix = |t: f32| {
+            0.2 * (std::f32::consts::TAU * 80.0 * t).sin()
+                + 0.1 * (std::f32::consts::TAU * 5000.0 * t).sin()
+        };
+        let (flat, engaged) = render_tilted(0.0, mix);
+        let (tilted, tilted_engaged) = render_tilted(12.0, mix);
+        assert_eq!((engaged, tilted_engaged), (0.0, 0.0));
+        let worst = flat
+            .iter()
+            .zip(&tilted)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(worst < 1e-4, "tilt pair strays {worst}");
+    }
+
+    /// Punch runs the detector, oversampler and clipper per sample; none
+    /// of it may touch the heap, at any oversampling factor.
+    #[test]
+    fn test_punch_process_does_not_allocate() {
+        use crate::alloc_guard::assert_no_alloc;
+
+        let n = 512;
+        let mut data_l: Vec<f32> = (0..n).map(|i| 1.5 * ((i as f32) * 0.05).sin()).collect();
+        let mut data_r = data_l.clone();
+
+        let mut punch = PunchModule::new(44100.0);
+        for oversampling in [OversamplingFactor::X1, OversamplingFactor::X4] {
+            punch.update_parameters(
+                -6.0,
+                ClipMode::Soft,
+                0.5,
+                oversampling,
+                0.5,
+                -0.2,
+                5.0,
+                100.0,
+                0.7,
+                0.0,
+                0.0,
+                0.8,
+                120.0,
+            );
+            assert_no_alloc(|| punch.process_channels(&mut [&mut data_l[..], &mut data_r[..]]));
+        }
     }
 
     #[test]
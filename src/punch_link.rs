@@ -0,0 +1,111 @@
+// src/punch_link.rs
+// Punch link: compressor gain reduction drives extra transient attack.
+//
+// Heavy bus compression flattens the front of every hit. With LINK up,
+// Punch's transient shaper adds attack in proportion to the gain reduction
+// the compressor reported for the same buffer, giving back some of the
+// snap the compressor took:
+//
+// - Gain reduction is followed through a one-pole smoother (`FOLLOW_MS`)
+//   so the shaper's attack moves smoothly rather than by buffer-sized steps.
+// - `FULL_GR_DB` of reduction at 100 % LINK adds `MAX_BOOST` of attack;
+//   less reduction adds proportionally less, more adds no more. The sum
+//   with the ATTACK knob stays within the shaper's ±1.
+//
+// In dual mono each channel follows the reduction on its own side, so a
+// hit that compresses the left gives back attack on the left only.
+//
+// The compressor has to run first: Punch reads the reduction reported
+// earlier in the same buffer, so with Punch ahead of the compressor (or the
+// compressor bypassed or out of the rack) the link adds nothing.
+
+/// Gain reduction that earns the full boost.
+const FULL_GR_DB: f32 = 12.0;
+/// Attack the link can add at 100 % LINK, on the shaper's −1..1 scale.
+pub const MAX_BOOST: f32 = 0.5;
+/// Time constant of the gain-reduction follower.
+const FOLLOW_MS: f32 = 50.0;
+
+/// Extra attack for `amount` (0..=1 LINK) at `gr_db` of gain reduction.
+pub fn attack_boost(amount: f32, gr_db: f32) -> f32 {
+    amount.clamp(0.0, 1.0) * (gr_db.max(0.0) / FULL_GR_DB).min(1.0) * MAX_BOOST
+}
+
+/// The ATTACK knob plus the link's boost, kept on the shaper's scale.
+pub fn linked_attack(attack: f32, amount: f32, gr_db: f32) -> f32 {
+    (attack + attack_boost(amount, gr_db)).clamp(-1.0, 1.0)
+}
+
+/// Smooths the compressor's per-buffer gain reduction for the link, per
+/// channel.
+pub struct PunchLink {
+    sample_rate: f32,
+    gr_db: [f32; 2],
+}
+
+impl PunchLink {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            gr_db: [0.0; 2],
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.gr_db = [0.0; 2];
+    }
+
+    /// Move each channel toward its `gr_db` over a buffer of `samples` and
+    /// return the smoothed reductions.
+    pub fn follow(&mut self, gr_db: [f32; 2], samples: usize) -> [f32; 2] {
+        let tau = FOLLOW_MS * 0.001 * self.sample_rate;
+        let keep = (-(samples as f32) / tau.max(1.0)).exp();
+        for (smoothed, gr_db) in self.gr_db.iter_mut().zip(gr_db) {
+            *smoothed = gr_db.max(0.0) + (*smoothed - gr_db.max(0.0)) * keep;
+        }
+        self.gr_db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boost_scales_with_reduction_and_link_and_is_capped() {
+        assert_eq!(attack_boost(0.0, 12.0), 0.0);
+        assert_eq!(attack_boost(1.0, 0.0), 0.0);
+        assert!((attack_boost(1.0, 6.0) - MAX_BOOST * 0.5).abs() < 1e-6);
+        assert!((attack_boost(0.5, 12.0) - MAX_BOOST * 0.5).abs() < 1e-6);
+        assert_eq!(attack_boost(1.0, 40.0), MAX_BOOST);
+        assert_eq!(attack_boost(1.0, -3.0), 0.0);
+    }
+
+    #[test]
+    fn linked_attack_stays_on_the_shapers_scale() {
+        assert_eq!(linked_attack(0.8, 1.0, 24.0), 1.0);
+        assert!((linked_attack(-0.5, 1.0, 12.0) - 0.0).abs() < 1e-6);
+        assert_eq!(linked_attack(0.3, 0.0, 12.0), 0.3);
+    }
+
+    #[test]
+    fn follower_settles_at_the_same_time_at_any_rate_and_block_size() {
+        // After 50 ms the follower is ~63 % of the way there, however the
+        // time is split into buffers.
+        for (sr, block) in [(44_100.0, 63), (48_000.0, 480), (96_000.0, 96)] {
+            let mut link = PunchLink::new(sr);
+            let buffers = (0.05 * sr / block as f32).round() as usize;
+            let mut gr = [0.0; 2];
+            for _ in 0..buffers {
+                gr = link.follow([10.0, 0.0], block);
+            }
+            assert!((gr[0] - 6.32).abs() < 0.1, "{sr} Hz / {block}: {gr:?}");
+            // The channels don't bleed into each other.
+            assert_eq!(gr[1], 0.0);
+        }
+        let mut link = PunchLink::new(48_000.0);
+        link.follow([10.0; 2], 48_000);
+        link.reset();
+        assert_eq!(link.follow([0.0; 2], 64), [0.0; 2]);
+    }
+}
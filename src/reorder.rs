@@ -0,0 +1,401 @@
+// src/reorder.rs
+// Click-free module reordering.
+//
+// Moving a module to another slot swaps the chain's transfer function
+// between one sample and the next, which clicks on anything but silence.
+// While the transport plays, a reorder fades between two module sets (see
+// chain.rs) instead:
+//
+//   • the live chain keeps running the old order, on a copy of the input
+//   • the spare chain is reset and runs the new order on the buffer itself
+//   • once the new chain has been fed for its latency plus `SETTLE_MS`, so
+//     its delay lines hold audio and its detectors have caught up, the
+//     output crossfades from old to new over `FADE_MS`
+//   • the new chain is then the live one, and the old becomes the spare
+//
+// Both orders run every block of the transition, each on its own modules,
+// so no module's state advances twice. The timings are fixed in samples:
+// a transition spans as many blocks as it needs, whatever the host's
+// buffer size.
+//
+// Asking for the outgoing order back mid-fade turns the fade round from
+// where it is. Any other order waits for the fade to finish, unless the new
+// chain is still pre-rolling, when it restarts on the latest order. A
+// stopped transport switches at once.
+//
+// The copy is allocated in `new()`; a block longer than it switches
+// outright.
+
+use crate::ModuleType;
+use nih_plug::buffer::Buffer;
+
+/// Reorderable slots.
+pub const SLOTS: usize = 7;
+
+pub type ModuleOrder = [ModuleType; SLOTS];
+
+/// Main input is stereo.
+const CHANNELS: usize = 2;
+/// Length of the crossfade.
+const FADE_MS: f32 = 5.0;
+/// Time the new chain runs unheard, on top of its latency, before the fade
+/// starts.
+const SETTLE_MS: f32 = 30.0;
+
+/// What the strip does with its two chains at the start of a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainSwitch {
+    /// Keep running the live chain.
+    Keep,
+    /// Reset the spare and make it the live chain; the old live chain runs
+    /// the outgoing order.
+    Fresh,
+    /// Reset the live chain: the order changed again while it was still
+    /// pre-rolling.
+    Restart,
+    /// Swap the live chain and the spare: back to the outgoing order.
+    Reverse,
+}
+
+/// The copy of the input the outgoing order runs on.
+pub struct OldPath {
+    channels: [Vec<f32>; CHANNELS],
+    /// Points at `channels` only while the outgoing order runs on it.
+    buffer: Buffer<'static>,
+}
+
+impl OldPath {
+    pub fn buffer(&mut self) -> &mut Buffer<'static> {
+        &mut self.buffer
+    }
+}
+
+/// A fade in progress.
+struct Transition {
+    /// The order the spare chain runs.
+    outgoing: ModuleOrder,
+    /// Samples left before the fade starts.
+    wait: usize,
+    /// Samples of the fade done.
+    pos: usize,
+}
+
+pub struct OrderFade {
+    /// Order the live chain runs; `None` until the first block.
+    active: Option<ModuleOrder>,
+    transition: Option<Transition>,
+    fade_len: usize,
+    settle_len: usize,
+    /// Lent out by `take_outgoing` until `blend` hands it back.
+    old: Option<OldPath>,
+}
+
+impl OrderFade {
+    /// Room for blocks of up to `max_block` samples.
+    pub fn new(max_block: usize, sample_rate: f32) -> Self {
+        let mut buffer = Buffer::default();
+        // SAFETY: no slices are handed over; this only reserves the slice
+        // list so later `set_slices` calls don't allocate.
+        unsafe {
+            buffer.set_slices(0, |slices| {
+                slices.clear();
+                slices.reserve(CHANNELS);
+            });
+        }
+        let samples = |ms: f32| ((ms * 0.001 * sample_rate) as usize).max(1);
+        Self {
+            active: None,
+            transition: None,
+            fade_len: samples(FADE_MS),
+            settle_len: samples(SETTLE_MS),
+            old: Some(OldPath {
+                channels: [vec![0.0; max_block], vec![0.0; max_block]],
+                buffer,
+            }),
+        }
+    }
+
+    /// Order the live chain runs this block, given the one the params ask
+    /// for and the latency the chain reports running it, and what to do with
+    /// the chains first. Stopped, a change just takes effect.
+    pub fn begin_block(
+        &mut self,
+        requested: ModuleOrder,
+        playing: bool,
+        latency: usize,
+    ) -> (ModuleOrder, ChainSwitch) {
+        let active = *self.active.get_or_insert(requested);
+        if !playing {
+            self.transition = None;
+            self.active = Some(requested);
+            return (requested, ChainSwitch::Keep);
+        }
+        let wait = latency + self.settle_len;
+        let switch = match self.transition.as_mut() {
+            None if requested != active => {
+                self.transition = Some(Transition {
+                    outgoing: active,
+                    wait,
+                    pos: 0,
+                });
+                ChainSwitch::Fresh
+            }
+            None => ChainSwitch::Keep,
+            Some(t) if requested == t.outgoing => {
+                // The outgoing chain never stopped, so it can take over
+                // from whatever share of the output it still has.
+                t.outgoing = active;
+                t.wait = 0;
+                t.pos = self.fade_len - t.pos;
+                if t.pos >= self.fade_len {
+                    self.transition = None;
+                }
+                ChainSwitch::Reverse
+            }
+            Some(t) if requested != active && t.pos == 0 => {
+                t.wait = wait;
+                ChainSwitch::Restart
+            }
+            Some(_) => return (active, ChainSwitch::Keep),
+        };
+        self.active = Some(requested);
+        (requested, switch)
+    }
+
+    /// The outgoing order and a copy of `input` to run it on, while a
+    /// transition is in progress. Hand the copy back through `blend`.
+    pub fn take_outgoing(&mut self, input: &[&mut [f32]]) -> Option<(ModuleOrder, OldPath)> {
+        let order = self.transition.as_ref()?.outgoing;
+        let samples = input.first().map_or(0, |ch| ch.len());
+        if self.old.as_ref()?.channels[0].len() < samples {
+            self.transition = None;
+            return None;
+        }
+        let mut path = self.old.take()?;
+        let channels = input.len().min(CHANNELS);
+        for (copy, x) in path.channels.iter_mut().zip(input) {
+            copy[..samples].copy_from_slice(x);
+        }
+        let [left, right] = &mut path.channels;
+        // SAFETY: the slices point into `channels`, whose storage is never
+        // resized after `new()` and moves with the path. `buffer` is
+        // emptied in `blend` before the path is filled again.
+        unsafe {
+            let left = std::slice::from_raw_parts_mut(left.as_mut_ptr(), samples);
+            let right = std::slice::from_raw_parts_mut(right.as_mut_ptr(), samples);
+            path.buffer.set_slices(samples, |slices| {
+                slices.clear();
+                slices.push(left);
+                if channels > 1 {
+                    slices.push(right);
+                }
+            });
+        }
+        Some((order, path))
+    }
+
+    /// Mix `output`, the live chain's, with the outgoing order's in `path`
+    /// at this block's point in the transition, and move it along.
+    pub fn blend(&mut self, mut path: OldPath, output: &mut [&mut [f32]]) {
+        // SAFETY: clearing hands nothing over and keeps capacity.
+        unsafe {
+            path.buffer.set_slices(0, |slices| slices.clear());
+        }
+        if let Some(t) = self.transition.as_mut() {
+            let samples = output.first().map_or(0, |ch| ch.len());
+            let step = 1.0 / self.fade_len as f32;
+            for (out, old) in output.iter_mut().zip(&path.channels) {
+                for (i, (y, &x)) in out.iter_mut().zip(old.iter()).enumerate() {
+                    let faded = ((i + 1).saturating_sub(t.wait) + t.pos).min(self.fade_len);
+                    *y = x + (*y - x) * (faded as f32 * step);
+                }
+            }
+            t.pos = (t.pos + samples.saturating_sub(t.wait)).min(self.fade_len);
+            t.wait = t.wait.saturating_sub(samples);
+            if t.pos == self.fade_len {
+                self.transition = None;
+            }
+        }
+        self.old = Some(path);
+    }
+
+    pub fn reset(&mut self) {
+        self.active = None;
+        self.transition = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+    const FADE: usize = (FADE_MS * 0.001 * SR) as usize;
+    const SETTLE: usize = (SETTLE_MS * 0.001 * SR) as usize;
+
+    fn order(first: ModuleType) -> ModuleOrder {
+        let mut order = [ModuleType::Empty; SLOTS];
+        order[0] = first;
+        order
+    }
+
+    /// Two chains that put out a constant each, swapped the way the strip
+    /// swaps them.
+    struct Strip {
+        fade: OrderFade,
+        live: f32,
+        spare: f32,
+    }
+
+    impl Strip {
+        fn new(max_block: usize) -> Self {
+            Self {
+                fade: OrderFade::new(max_block, SR),
+                live: 1.0,
+                spare: -1.0,
+            }
+        }
+
+        /// One block of `len` samples: what it did with the chains, and
+        /// the output.
+        fn block(
+            &mut self,
+            requested: ModuleOrder,
+            len: usize,
+            latency: usize,
+        ) -> (ChainSwitch, Vec<f32>) {
+            let (_, switch) = self.fade.begin_block(requested, true, latency);
+            if matches!(switch, ChainSwitch::Fresh | ChainSwitch::Reverse) {
+                std::mem::swap(&mut self.live, &mut self.spare);
+            }
+            let mut block = vec![0.0; len];
+            let mut channels = [&mut block[..]];
+            if let Some((_, mut path)) = self.fade.take_outgoing(&channels) {
+                for ch in path.buffer().as_slice() {
+                    ch.fill(self.spare);
+                }
+                channels[0].fill(self.live);
+                self.fade.blend(path, &mut channels);
+            } else {
+                channels[0].fill(self.live);
+            }
+            (switch, block)
+        }
+
+        /// `blocks` blocks of `len` samples, output joined.
+        fn run(&mut self, requested: ModuleOrder, blocks: usize, len: usize) -> Vec<f32> {
+            (0..blocks)
+                .flat_map(|_| self.block(requested, len, 0).1)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn reorder_pre_rolls_the_new_chain_then_fades_over_several_blocks() {
+        let (old, new) = (order(ModuleType::Api5500EQ), order(ModuleType::Punch));
+        let mut strip = Strip::new(64);
+        strip.run(old, 1, 64);
+
+        let latency = 100;
+        let (switch, first) = strip.block(new, 64, latency);
+        assert_eq!(switch, ChainSwitch::Fresh);
+        let mut out = first;
+        out.extend(strip.run(new, 40, 64));
+        // Old order alone while the new chain fills and settles.
+        let start = latency + SETTLE;
+        assert!(out[..start].iter().all(|&x| x == 1.0));
+        // Then even steps down to the new order's output, across blocks.
+        assert!(out[start..start + FADE]
+            .windows(2)
+            .all(|w| (w[0] - w[1] - 2.0 / FADE as f32).abs() < 1e-5));
+        assert!(out[start + FADE - 1..].iter().all(|&x| x == -1.0));
+        // Finished: the next block runs the new chain alone.
+        assert_eq!(strip.block(new, 64, 0).0, ChainSwitch::Keep);
+        assert!(strip.fade.take_outgoing(&[&mut [0.0; 64][..]]).is_none());
+    }
+
+    #[test]
+    fn transition_is_the_same_at_any_block_size() {
+        let (old, new) = (order(ModuleType::Haas), order(ModuleType::Punch));
+        let total = 4096;
+        let outputs: Vec<Vec<f32>> = [16, 64, 512]
+            .into_iter()
+            .map(|len| {
+                let mut strip = Strip::new(512);
+                strip.run(old, 1, len);
+                strip.run(new, total / len, len)
+            })
+            .collect();
+        assert!(outputs.windows(2).all(|w| w[0] == w[1]));
+    }
+
+    #[test]
+    fn asking_for_the_old_order_back_turns_the_fade_round() {
+        let (old, new) = (order(ModuleType::Haas), order(ModuleType::Punch));
+        let mut strip = Strip::new(64);
+        strip.run(old, 1, 64);
+        let len = 16;
+        let to_midway = (SETTLE + FADE / 2) / len;
+        let out = strip.run(new, to_midway, len);
+        let last = *out.last().unwrap();
+        assert!(last < 1.0 && last > -1.0, "midway {last}");
+
+        let (switch, back) = strip.block(old, len, 0);
+        assert_eq!(switch, ChainSwitch::Reverse);
+        // No step where the fade turns, then back up to the old order.
+        assert!((back[0] - last).abs() <= 2.0 / FADE as f32 + 1e-5);
+        let rest = strip.run(old, FADE / len + 1, len);
+        assert!(rest.windows(2).all(|w| w[1] >= w[0]));
+        assert_eq!(*rest.last().unwrap(), 1.0);
+    }
+
+    #[test]
+    fn a_third_order_waits_for_the_fade() {
+        let (a, b, c) = (
+            order(ModuleType::Haas),
+            order(ModuleType::Punch),
+            order(ModuleType::Transformer),
+        );
+        let mut strip = Strip::new(64);
+        strip.run(a, 1, 64);
+        strip.run(b, (SETTLE + FADE / 2) / 64, 64);
+        assert_eq!(strip.block(c, 64, 0).0, ChainSwitch::Keep);
+        // C starts its own transition once B's has finished.
+        let switches: Vec<_> = (0..FADE / 64 + 1)
+            .map(|_| strip.block(c, 64, 0).0)
+            .collect();
+        assert_eq!(
+            switches
+                .iter()
+                .filter(|&&s| s == ChainSwitch::Fresh)
+                .count(),
+            1
+        );
+        let mut strip = Strip::new(64);
+        strip.run(a, 1, 64);
+        strip.run(b, 2, 64);
+        // Still pre-rolling: the new chain restarts on C instead.
+        assert_eq!(strip.block(c, 64, 0).0, ChainSwitch::Restart);
+    }
+
+    #[test]
+    fn stopped_transport_switches_at_once() {
+        let mut fade = OrderFade::new(64, SR);
+        fade.begin_block(order(ModuleType::Haas), true, 0);
+        let new = order(ModuleType::Punch);
+        assert_eq!(fade.begin_block(new, false, 0), (new, ChainSwitch::Keep));
+        assert!(fade.take_outgoing(&[&mut [0.0; 64][..]]).is_none());
+    }
+
+    #[test]
+    fn block_longer_than_the_copy_switches_outright() {
+        let mut strip = Strip::new(64);
+        strip.run(order(ModuleType::Haas), 1, 128);
+        let (_, out) = strip.block(order(ModuleType::Punch), 128, 0);
+        assert!(out.iter().all(|&x| x == -1.0));
+        // The copy is still there for the next switch that fits.
+        let (switch, _) = strip.block(order(ModuleType::Haas), 64, 0);
+        assert_eq!(switch, ChainSwitch::Fresh);
+        assert!(strip.fade.take_outgoing(&[&mut [0.0; 64][..]]).is_some());
+    }
+}
@@ -0,0 +1,250 @@
+// src/response.rs
+// Export of the strip's static EQ response.
+//
+// An impulse runs through fresh copies of the EQ modules built from the
+// current settings — the API5500 and the Pultec, each when it sits in the
+// rack and isn't bypassed — on the background thread, so the audio path
+// never sees it. What comes out is the composite linear response, which
+// the user can keep as documentation or load into a convolver elsewhere:
+//
+// - CSV: magnitude (dB) and phase (degrees) per channel at twelve points
+//   per octave, 20 Hz to 20 kHz.
+// - WAV: the impulse response itself, stereo 32-bit float, one second.
+//
+// Only the linear parts are rendered. The Pultec's tube stage and the
+// Vintage character's noise floor are left out; the Vintage phase lag and
+// band interaction stay in. The API5500 renders its minimum-phase filters
+// even in Linear Phase mode: the magnitude is the same, the FIR's phase is
+// a pure delay. The response is taken at 48 kHz whatever the host runs at,
+// so an export doesn't depend on the session it came from.
+
+use crate::spectral::ResponseExportData;
+use crate::{BusChannelStripParams, ModuleType};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "api5500")]
+use crate::api5500::{Api5500, EqFirShared};
+#[cfg(feature = "pultec")]
+use crate::pultec::PultecEQ;
+
+/// Rate the response is rendered and written at.
+pub const EXPORT_RATE: f32 = 48_000.0;
+/// Impulse response length: one second, past the ring of a 20 Hz shelf.
+const IR_LEN: usize = 48_000;
+const POINTS_PER_OCTAVE: f32 = 12.0;
+const LOWEST_HZ: f32 = 20.0;
+const HIGHEST_HZ: f32 = 20_000.0;
+/// File name used when the path typed is a directory.
+const DEFAULT_STEM: &str = "EQ Response";
+
+/// File the export writes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ResponseFormat {
+    Csv,
+    Wav,
+}
+
+impl ResponseFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ResponseFormat::Csv => "csv",
+            ResponseFormat::Wav => "wav",
+        }
+    }
+}
+
+/// Where to write an export typed as `path`: a directory gets a file named
+/// `EQ Response`, and the extension always follows the format.
+pub fn export_path(path: &str, format: ResponseFormat) -> PathBuf {
+    let path = PathBuf::from(path.trim());
+    let path = if path.is_dir() {
+        path.join(DEFAULT_STEM)
+    } else {
+        path
+    };
+    path.with_extension(format.extension())
+}
+
+/// Render the response for the current `params` and write it to `path`,
+/// recording the outcome in `data`. Allocates; call on the background
+/// thread.
+pub fn export(
+    params: &BusChannelStripParams,
+    path: &Path,
+    format: ResponseFormat,
+    data: &ResponseExportData,
+) {
+    let ir = render(params);
+    let bytes = match format {
+        ResponseFormat::Csv => to_csv(&ir).into_bytes(),
+        ResponseFormat::Wav => to_wav(&ir),
+    };
+    data.store(std::fs::write(path, bytes).is_ok());
+}
+
+/// Stereo impulse response of the EQ modules in the rack, at
+/// `EXPORT_RATE`. A strip with none engaged returns the bare impulse.
+pub fn render(params: &BusChannelStripParams) -> [Vec<f32>; 2] {
+    let order = [
+        params.module_order_1.value(),
+        params.module_order_2.value(),
+        params.module_order_3.value(),
+        params.module_order_4.value(),
+        params.module_order_5.value(),
+        params.module_order_6.value(),
+        params.module_order_7.value(),
+    ];
+    let (mut left, mut right) = (vec![0.0; IR_LEN], vec![0.0; IR_LEN]);
+    left[0] = 1.0;
+    right[0] = 1.0;
+    let mut channels = [&mut left[..], &mut right[..]];
+
+    // The modules are linear here, so the order they run in doesn't matter.
+    #[cfg(feature = "api5500")]
+    if order.contains(&ModuleType::Api5500EQ) && !params.eq_bypass.value() {
+        let mut eq = Api5500::new(EXPORT_RATE);
+        crate::configure_api5500(&mut eq, params);
+        eq.set_noise_gain(0.0);
+        eq.process_channels(&mut channels, &EqFirShared::new());
+    }
+    #[cfg(feature = "pultec")]
+    if order.contains(&ModuleType::PultecEQ) && !params.pultec_bypass.value() {
+        let mut eq = PultecEQ::new(EXPORT_RATE);
+        crate::configure_pultec(&mut eq, params, 0.0);
+        eq.set_noise_gain(0.0);
+        eq.process_channels(&mut channels);
+    }
+
+    [left, right]
+}
+
+/// Magnitude and phase per channel at twelve points per octave, as CSV
+/// with a header row.
+pub fn to_csv(ir: &[Vec<f32>; 2]) -> String {
+    let mut out = String::from("frequency_hz,left_db,left_phase_deg,right_db,right_phase_deg\n");
+    let points = (POINTS_PER_OCTAVE * (HIGHEST_HZ / LOWEST_HZ).log2()).floor() as usize;
+    for point in 0..=points {
+        let hz = LOWEST_HZ * 2.0_f32.powf(point as f32 / POINTS_PER_OCTAVE);
+        let (left_db, left_deg) = response_at(&ir[0], hz);
+        let (right_db, right_deg) = response_at(&ir[1], hz);
+        let _ = writeln!(
+            out,
+            "{hz:.2},{left_db:.3},{left_deg:.2},{right_db:.3},{right_deg:.2}"
+        );
+    }
+    out
+}
+
+/// Magnitude in dB and phase in degrees of `ir` at `hz`: the impulse
+/// response's DFT evaluated at that one frequency.
+fn response_at(ir: &[f32], hz: f32) -> (f32, f32) {
+    let step = std::f64::consts::TAU * hz as f64 / EXPORT_RATE as f64;
+    let (re, im) = ir.iter().enumerate().fold((0.0, 0.0), |(re, im), (i, &x)| {
+        let phase = step * i as f64;
+        (re + x as f64 * phase.cos(), im - x as f64 * phase.sin())
+    });
+    let magnitude = re.hypot(im).max(1e-10);
+    (
+        (20.0 * magnitude.log10()) as f32,
+        im.atan2(re).to_degrees() as f32,
+    )
+}
+
+/// The impulse response as a stereo 32-bit float WAV at `EXPORT_RATE`.
+pub fn to_wav(ir: &[Vec<f32>; 2]) -> Vec<u8> {
+    const CHANNELS: u16 = 2;
+    const BITS: u16 = 32;
+    const FLOAT_TAG: u16 = 3;
+    let block = CHANNELS * BITS / 8;
+    let data_len = (ir[0].len() * block as usize) as u32;
+    let rate = EXPORT_RATE as u32;
+
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16_u32.to_le_bytes());
+    out.extend_from_slice(&FLOAT_TAG.to_le_bytes());
+    out.extend_from_slice(&CHANNELS.to_le_bytes());
+    out.extend_from_slice(&rate.to_le_bytes());
+    out.extend_from_slice(&(rate * block as u32).to_le_bytes());
+    out.extend_from_slice(&block.to_le_bytes());
+    out.extend_from_slice(&BITS.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for (l, r) in ir[0].iter().zip(&ir[1]) {
+        out.extend_from_slice(&l.to_le_bytes());
+        out.extend_from_slice(&r.to_le_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_without_an_eq_exports_the_bare_impulse() {
+        // Both EQs are bypassed by default.
+        let ir = render(&BusChannelStripParams::default());
+        for channel in &ir {
+            assert_eq!(channel.len(), IR_LEN);
+            assert_eq!(channel[0], 1.0);
+            assert!(channel[1..].iter().all(|&x| x == 0.0));
+        }
+    }
+
+    #[test]
+    fn test_csv_reads_level_and_delay() {
+        // Half level on the left, one sample late on the right.
+        let mut ir = [vec![0.0; 256], vec![0.0; 256]];
+        ir[0][0] = 0.5;
+        ir[1][1] = 1.0;
+        let csv = to_csv(&ir);
+        let mut rows = csv.lines();
+        assert_eq!(
+            rows.next(),
+            Some("frequency_hz,left_db,left_phase_deg,right_db,right_phase_deg")
+        );
+        let rows: Vec<Vec<f32>> = rows
+            .map(|row| row.split(',').map(|v| v.parse().unwrap()).collect())
+            .collect();
+        assert_eq!(rows.len(), 120);
+        assert_eq!(rows[0][0], 20.0);
+        assert!(rows.last().unwrap()[0] <= HIGHEST_HZ);
+        for row in &rows {
+            assert!((row[1] + 6.02).abs() < 0.01, "{row:?}");
+            assert!(row[2].abs() < 0.01, "{row:?}");
+            assert!(row[3].abs() < 0.01, "{row:?}");
+            // One sample of delay lags by 360° · f / fs.
+            let lag = -360.0 * row[0] / EXPORT_RATE;
+            assert!((row[4] - lag).abs() < 0.05, "{row:?}");
+        }
+    }
+
+    #[test]
+    fn test_wav_decodes_back_to_the_response() {
+        let ir = [vec![0.5, 0.25, 0.0], vec![0.5, -0.25, 0.125]];
+        let (mono, rate) = crate::matching::decode_wav_mono(&to_wav(&ir)).unwrap();
+        assert_eq!(rate, EXPORT_RATE);
+        assert_eq!(mono, vec![0.5, 0.0, 0.0625]);
+    }
+
+    #[test]
+    fn test_export_path_follows_the_format() {
+        assert_eq!(
+            export_path(" /tmp/bus/drums.wav ", ResponseFormat::Csv),
+            PathBuf::from("/tmp/bus/drums.csv")
+        );
+        assert_eq!(
+            export_path("drums", ResponseFormat::Wav),
+            PathBuf::from("drums.wav")
+        );
+        let dir = std::env::temp_dir();
+        assert_eq!(
+            export_path(dir.to_str().unwrap(), ResponseFormat::Csv),
+            dir.join("EQ Response.csv")
+        );
+    }
+}
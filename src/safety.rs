@@ -0,0 +1,161 @@
+// src/safety.rs
+// Output safety stage, after master gain: the last thing before the host.
+//
+//   • non-finite samples (NaN / ±Inf) become silence, and the fault light
+//     stays on for a second after the last one so the editor can show it
+//   • a one-pole DC blocker (~5 Hz) removes offset a module may have built
+//   • a hard clip at +6 dBFS, far above any sane level, so a runaway
+//     setting can't reach the monitors at full scale
+//
+// A non-finite sample also clears that channel's DC blocker, which would
+// otherwise carry the NaN forward forever. Per-channel state is fixed-size;
+// nothing allocates after `new()`.
+
+/// Hard ceiling: +6 dBFS.
+pub const SAFETY_CEILING: f32 = 1.995_262_3;
+/// DC blocker corner.
+const DC_CORNER_HZ: f32 = 5.0;
+/// How long the fault light stays on after the last bad sample.
+const FAULT_HOLD_S: f32 = 1.0;
+/// Channels with their own DC blocker, enough for 5.1; any further
+/// channels are only sanitised and clipped.
+const CHANNELS: usize = 6;
+
+pub struct SafetyStage {
+    /// Pole of the DC blocker.
+    pole: f32,
+    /// Previous input and output per channel.
+    x1: [f32; CHANNELS],
+    y1: [f32; CHANNELS],
+    fault_hold_len: usize,
+    /// Samples left until the fault light goes out.
+    fault_hold: usize,
+}
+
+impl SafetyStage {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut stage = Self {
+            pole: 0.0,
+            x1: [0.0; CHANNELS],
+            y1: [0.0; CHANNELS],
+            fault_hold_len: 0,
+            fault_hold: 0,
+        };
+        stage.set_sample_rate(sample_rate);
+        stage
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.pole = (-std::f32::consts::TAU * DC_CORNER_HZ / sample_rate).exp();
+        self.fault_hold_len = (FAULT_HOLD_S * sample_rate) as usize;
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.x1 = [0.0; CHANNELS];
+        self.y1 = [0.0; CHANNELS];
+        self.fault_hold = 0;
+    }
+
+    /// True while a non-finite sample was caught in the last second.
+    pub fn fault(&self) -> bool {
+        self.fault_hold > 0
+    }
+
+    /// Run the stage in place over per-channel slices.
+    pub fn process(&mut self, channels: &mut [&mut [f32]]) {
+        let samples = channels.first().map_or(0, |ch| ch.len());
+        let mut faulted = false;
+        for (ch, data) in channels.iter_mut().enumerate() {
+            for s in data.iter_mut() {
+                let mut x = *s;
+                if !x.is_finite() {
+                    faulted = true;
+                    x = 0.0;
+                    if ch < CHANNELS {
+                        self.x1[ch] = 0.0;
+                        self.y1[ch] = 0.0;
+                    }
+                }
+                if ch < CHANNELS {
+                    let y = x - self.x1[ch] + self.pole * self.y1[ch];
+                    self.x1[ch] = x;
+                    // Flush the tail of a decaying offset rather than let it
+                    // go denormal.
+                    self.y1[ch] = if y.abs() < 1e-20 { 0.0 } else { y };
+                    x = y;
+                }
+                *s = x.clamp(-SAFETY_CEILING, SAFETY_CEILING);
+            }
+        }
+        self.fault_hold = if faulted {
+            self.fault_hold_len
+        } else {
+            self.fault_hold.saturating_sub(samples)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    #[test]
+    fn non_finite_samples_become_silence_and_light_the_fault() {
+        let mut stage = SafetyStage::new(SR);
+        let mut l = [0.1, f32::NAN, 0.1, f32::INFINITY];
+        let mut r = [f32::NEG_INFINITY, 0.0, 0.0, 0.0];
+        stage.process(&mut [&mut l[..], &mut r[..]]);
+        assert!(l.iter().chain(&r).all(|s| s.is_finite()));
+        assert!(stage.fault());
+
+        // The blocker didn't keep the NaN, and the light goes out.
+        let mut l = vec![0.0; SR as usize];
+        let mut r = l.clone();
+        stage.process(&mut [&mut l[..], &mut r[..]]);
+        stage.process(&mut [&mut l[..16], &mut r[..16]]);
+        assert!(l.iter().all(|s| s.is_finite()));
+        assert!(!stage.fault());
+    }
+
+    #[test]
+    fn dc_offset_is_removed() {
+        let mut stage = SafetyStage::new(SR);
+        let mut l = vec![0.5; SR as usize];
+        let mut r = vec![-0.25; SR as usize];
+        stage.process(&mut [&mut l[..], &mut r[..]]);
+        let tail = SR as usize - 100;
+        assert!(
+            l[tail..].iter().all(|s| s.abs() < 1e-3),
+            "L settled at {}",
+            l[tail]
+        );
+        assert!(
+            r[tail..].iter().all(|s| s.abs() < 1e-3),
+            "R settled at {}",
+            r[tail]
+        );
+    }
+
+    #[test]
+    fn audio_passes_and_peaks_stop_at_the_ceiling() {
+        let mut stage = SafetyStage::new(SR);
+        let sine = |amp: f32| -> Vec<f32> {
+            (0..4800)
+                .map(|i| amp * (std::f32::consts::TAU * 1000.0 * i as f32 / SR).sin())
+                .collect()
+        };
+        let (mut l, mut r) = (sine(0.5), sine(8.0));
+        let reference = sine(0.5);
+        stage.process(&mut [&mut l[..], &mut r[..]]);
+        // A 5 Hz blocker leaves 1 kHz essentially untouched once settled.
+        for (out, x) in l[480..].iter().zip(&reference[480..]) {
+            assert!((out - x).abs() < 0.01, "{out} vs {x}");
+        }
+        let peak = r.iter().fold(0.0_f32, |m, s| m.max(s.abs()));
+        assert!((peak - SAFETY_CEILING).abs() < 1e-6, "peak {peak}");
+        assert!(!stage.fault());
+    }
+}
@@ -17,38 +17,66 @@ pub fn biquad_coeffs(
     Coefficients::<f32>::from_normalized_params(filter_type, normalized, q)
 }
 
-/// Enum for the type of filter to use.
+/// `biquad_coeffs` computed in f64. Below ~100 Hz `cos(w0)` sits so close
+/// to 1 that f32 keeps only a few significant bits of `1 - cos(w0)`, and
+/// the shelf/peak gains drift audibly at high Q and high sample rates. The
+/// filters that run these coefficients keep their state in f64 as well
+/// (`Filter`, `stereo_biquad`, the transformer's response shelves).
+pub fn biquad_coeffs_f64(
+    filter_type: Type<f64>,
+    sample_rate: f32,
+    freq_hz: f32,
+    q: f32,
+) -> Result<Coefficients<f64>, Errors> {
+    let normalized = (freq_hz as f64 * 2.0 / sample_rate as f64).clamp(1.0e-6, 0.999);
+    Coefficients::<f64>::from_normalized_params(filter_type, normalized, q as f64)
+}
+
+impl FilterType {
+    fn to_type(&self, gain: f32) -> Type<f64> {
+        let gain = gain as f64;
+        match self {
+            FilterType::Bell => Type::PeakingEQ(gain),
+            FilterType::LowShelf => Type::LowShelf(gain),
+            FilterType::HighShelf => Type::HighShelf(gain),
+            FilterType::HighPass => Type::HighPass,
+            FilterType::LowPass => Type::LowPass,
+        }
+    }
+}
+
+/// Enum for the type of filter to use. The pass types ignore `gain`.
 pub enum FilterType {
     Bell,
     LowShelf,
     HighShelf,
+    HighPass,
+    LowPass,
 }
 
 /// A stereo biquad filter. Each channel carries its own state (z1, z2) so
 /// feeding interleaved L/R samples through one logical filter does not smear
 /// the transfer function — a single shared biquad fed LRLRLR corrupts its
 /// delay line and measurably reduces perceived gain on shelf/peaking curves.
+/// Coefficients and state are f64; see `biquad_coeffs_f64`.
 pub struct Filter {
-    filter: [DirectForm1<f32>; 2],
+    filter: [DirectForm1<f64>; 2],
+    /// Current coefficients, kept for `magnitude` and for the f64 cascades.
+    coeffs: Coefficients<f64>,
 }
 
 impl Filter {
     /// Create a new filter with the given parameters.
     pub fn new(sample_rate: f32, filter_type: FilterType, freq: f32, q: f32, gain: f32) -> Self {
-        let filter_type = match filter_type {
-            FilterType::Bell => Type::PeakingEQ(gain),
-            FilterType::LowShelf => Type::LowShelf(gain),
-            FilterType::HighShelf => Type::HighShelf(gain),
-        };
-
-        let coeff = biquad_coeffs(filter_type, sample_rate, freq, q)
+        let coeff = biquad_coeffs_f64(filter_type.to_type(gain), sample_rate, freq, q)
             .expect("Failed to create filter coefficients");
 
         Self {
             filter: [
-                DirectForm1::<f32>::new(coeff),
-                DirectForm1::<f32>::new(coeff),
+                DirectForm1::<f64>::new(coeff),
+                DirectForm1::<f64>::new(coeff),
             ],
+            coeffs: coeff,
         }
     }
 
@@ -61,25 +89,759 @@ impl Filter {
         q: f32,
         gain: f32,
     ) {
-        let filter_type = match filter_type {
-            FilterType::Bell => Type::PeakingEQ(gain),
-            FilterType::LowShelf => Type::LowShelf(gain),
-            FilterType::HighShelf => Type::HighShelf(gain),
-        };
-
-        let coeff = biquad_coeffs(filter_type, sample_rate, freq, q)
+        let coeff = biquad_coeffs_f64(filter_type.to_type(gain), sample_rate, freq, q)
             .expect("Failed to create filter coefficients");
 
         // Update coefficients without clearing filter memory
         self.filter[0].update_coefficients(coeff);
         self.filter[1].update_coefficients(coeff);
+        self.coeffs = coeff;
+    }
+
+    /// Current coefficients, for callers that run their own state
+    /// (see `stereo_biquad`).
+    pub fn coefficients(&self) -> Coefficients<f64> {
+        self.coeffs
+    }
+
+    /// Linear magnitude response at `omega` radians per sample.
+    pub fn magnitude(&self, omega: f32) -> f32 {
+        let c = &self.coeffs;
+        let w = omega as f64;
+        // H(e^jw) = (b0 + b1 e^-jw + b2 e^-2jw) / (1 + a1 e^-jw + a2 e^-2jw)
+        let (c1, s1, c2, s2) = (w.cos(), -w.sin(), (2.0 * w).cos(), -(2.0 * w).sin());
+        let num_re = c.b0 + c.b1 * c1 + c.b2 * c2;
+        let num_im = c.b1 * s1 + c.b2 * s2;
+        let den_re = 1.0 + c.a1 * c1 + c.a2 * c2;
+        let den_im = c.a1 * s1 + c.a2 * s2;
+        ((num_re * num_re + num_im * num_im) / (den_re * den_re + den_im * den_im)).sqrt() as f32
     }
 
     /// Process a single sample through a specific channel's state. Callers
     /// iterating stereo audio MUST use the correct `ch` per sample (0 = L,
     /// 1 = R) or the cross-channel smear returns.
     pub fn run_ch(&mut self, sample: f32, ch: usize) -> f32 {
-        self.filter[ch.min(1)].run(sample)
+        self.filter[ch.min(1)].run(sample as f64) as f32
+    }
+}
+
+// ── State-variable filter ────────────────────────────────────────────────────
+
+/// Integrator memories below this are flushed to zero; a decaying SVF
+/// otherwise idles in the subnormal range, which is slow on x86 without FTZ.
+const SVF_DENORMAL_FLUSH: f32 = 1.0e-20;
+
+/// Topology-preserving-transform (zero-delay-feedback) state-variable
+/// filter, after Simper's trapezoidal SVF. The state is two integrator
+/// memories rather than past outputs, so coefficients can change every
+/// sample: a swept gain or cutoff stays smooth, where a direct-form biquad
+/// replays history computed under the old coefficients. Updates preserve
+/// the state; only `reset()` clears it.
+///
+/// Output = m0·input + m1·band + m2·low; each `set_*` picks the mix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Svf {
+    /// Prewarped integrator gain, tan(π·f/fs).
+    g: f32,
+    /// Damping, 1/Q (divided by √gain for the bell).
+    k: f32,
+    /// Q as last set, for `set_bell_gain`.
+    q: f32,
+    a1: f32,
+    a2: f32,
+    a3: f32,
+    m0: f32,
+    m1: f32,
+    m2: f32,
+    ic1eq: f32,
+    ic2eq: f32,
+}
+
+impl Svf {
+    /// A flat (pass-through) filter.
+    pub fn new() -> Self {
+        let mut svf = Self {
+            g: 0.0,
+            k: 1.0,
+            q: 1.0,
+            a1: 1.0,
+            a2: 0.0,
+            a3: 0.0,
+            m0: 1.0,
+            m1: 0.0,
+            m2: 0.0,
+            ic1eq: 0.0,
+            ic2eq: 0.0,
+        };
+        svf.tune(44100.0, 1000.0, 1.0, 1.0);
+        svf
+    }
+
+    /// Set cutoff and damping. `k_scale` divides 1/Q (the bell's √gain).
+    fn tune(&mut self, sample_rate: f32, freq_hz: f32, q: f32, k_scale: f32) {
+        let freq_hz = freq_hz.clamp(20.0, sample_rate * 0.49);
+        self.q = q.max(0.1);
+        self.g = (std::f32::consts::PI * freq_hz / sample_rate).tan();
+        self.set_damping(1.0 / (self.q * k_scale));
+    }
+
+    fn set_damping(&mut self, k: f32) {
+        self.k = k;
+        self.a1 = 1.0 / (1.0 + self.g * (self.g + k));
+        self.a2 = self.g * self.a1;
+        self.a3 = self.g * self.a2;
+    }
+
+    /// Peaking bell (same curve as the RBJ peaking EQ).
+    pub fn set_bell(&mut self, sample_rate: f32, freq_hz: f32, q: f32, gain_db: f32) {
+        let a = bell_amplitude(gain_db);
+        self.tune(sample_rate, freq_hz, q, a);
+        self.m0 = 1.0;
+        self.m1 = self.k * (a * a - 1.0);
+        self.m2 = 0.0;
+    }
+
+    /// Change only the bell's gain, keeping cutoff and Q. Cheap enough to
+    /// call every sample (one `exp`, no trig).
+    pub fn set_bell_gain(&mut self, gain_db: f32) {
+        let a = bell_amplitude(gain_db);
+        self.set_damping(1.0 / (self.q * a));
+        self.m0 = 1.0;
+        self.m1 = self.k * (a * a - 1.0);
+        self.m2 = 0.0;
+    }
+
+    /// Constant-skirt bandpass: peak gain Q (the RBJ "constant skirt gain"
+    /// bandpass).
+    pub fn set_band_pass(&mut self, sample_rate: f32, freq_hz: f32, q: f32) {
+        self.tune(sample_rate, freq_hz, q, 1.0);
+        self.m0 = 0.0;
+        self.m1 = 1.0;
+        self.m2 = 0.0;
+    }
+
+    /// Bandpass with exactly 0 dB at the center, whatever the Q.
+    pub fn set_band_pass_unity(&mut self, sample_rate: f32, freq_hz: f32, q: f32) {
+        self.tune(sample_rate, freq_hz, q, 1.0);
+        self.m0 = 0.0;
+        self.m1 = self.k;
+        self.m2 = 0.0;
+    }
+
+    #[inline]
+    pub fn process(&mut self, input: f32) -> f32 {
+        let v3 = input - self.ic2eq;
+        let v1 = self.a1 * self.ic1eq + self.a2 * v3;
+        let v2 = self.ic2eq + self.a2 * self.ic1eq + self.a3 * v3;
+        self.ic1eq = flush_svf_state(2.0 * v1 - self.ic1eq);
+        self.ic2eq = flush_svf_state(2.0 * v2 - self.ic2eq);
+        self.m0 * input + self.m1 * v1 + self.m2 * v2
+    }
+
+    pub fn reset(&mut self) {
+        self.ic1eq = 0.0;
+        self.ic2eq = 0.0;
+    }
+}
+
+impl Default for Svf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// √(linear gain) for a bell of `gain_db`.
+#[inline]
+fn bell_amplitude(gain_db: f32) -> f32 {
+    // 10^(dB/40) without powf.
+    (gain_db * (std::f32::consts::LN_10 / 40.0)).exp()
+}
+
+#[inline(always)]
+fn flush_svf_state(x: f32) -> f32 {
+    if x.abs() < SVF_DENORMAL_FLUSH {
+        0.0
+    } else {
+        x
+    }
+}
+
+// ── Linkwitz–Riley crossovers ────────────────────────────────────────────────
+
+/// N-way Linkwitz–Riley crossover networks for the multiband stages.
+///
+/// Each split point is a low-pass/high-pass pair (LR2: one critically damped
+/// section per side with the high side inverted; LR4: two Butterworth
+/// sections per side). The high side feeds the next split up, and every band
+/// below a split runs through that split's all-pass so all bands share one
+/// phase response: the bands sum to an all-pass, flat in magnitude at any
+/// split frequencies. State is f64 and per channel, like `Filter`.
+///
+/// Nothing in the chain splits bands yet: the multiband compressor,
+/// per-band transient shaping and frequency-dependent width are still to
+/// come, so outside the tests the module has no caller.
+#[cfg_attr(not(test), allow(dead_code))]
+pub mod crossover {
+    use super::biquad_coeffs_f64;
+    use biquad::{Biquad, Coefficients, DirectForm2Transposed, Type};
+
+    /// Butterworth Q; two sections in series make an LR4 side.
+    const BUTTERWORTH_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+    /// Critically damped Q; one section makes an LR2 side.
+    const CRITICAL_Q: f32 = 0.5;
+
+    type Section = DirectForm2Transposed<f64>;
+
+    /// Slope of every split in a network.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Order {
+        /// 12 dB/oct, high side polarity-inverted so the sum is flat.
+        Lr2,
+        /// 24 dB/oct.
+        Lr4,
+    }
+
+    impl Order {
+        /// Sections per low/high side.
+        fn stages(self) -> usize {
+            match self {
+                Order::Lr2 => 1,
+                Order::Lr4 => 2,
+            }
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct SplitCoeffs {
+        low: Coefficients<f64>,
+        high: Coefficients<f64>,
+        allpass: Coefficients<f64>,
+    }
+
+    fn split_coeffs(order: Order, sample_rate: f32, freq_hz: f32) -> SplitCoeffs {
+        let q = match order {
+            Order::Lr2 => CRITICAL_Q,
+            Order::Lr4 => BUTTERWORTH_Q,
+        };
+        let low = biquad_coeffs_f64(Type::LowPass, sample_rate, freq_hz, q)
+            .expect("Failed to create crossover coefficients");
+        let high = biquad_coeffs_f64(Type::HighPass, sample_rate, freq_hz, q)
+            .expect("Failed to create crossover coefficients");
+        match order {
+            Order::Lr2 => SplitCoeffs {
+                low,
+                high: Coefficients {
+                    b0: -high.b0,
+                    b1: -high.b1,
+                    b2: -high.b2,
+                    ..high
+                },
+                allpass: first_order_allpass(sample_rate, freq_hz),
+            },
+            Order::Lr4 => SplitCoeffs {
+                low,
+                high,
+                allpass: biquad_coeffs_f64(Type::AllPass, sample_rate, freq_hz, q)
+                    .expect("Failed to create crossover coefficients"),
+            },
+        }
+    }
+
+    /// Bilinear (1 − s)/(1 + s), prewarped to `freq_hz` like the RBJ
+    /// sections, so it matches LR2's low-pass minus high-pass exactly.
+    fn first_order_allpass(sample_rate: f32, freq_hz: f32) -> Coefficients<f64> {
+        // Same normalization and clamp as `biquad_coeffs_f64`.
+        let normalized = (freq_hz as f64 * 2.0 / sample_rate as f64).clamp(1.0e-6, 0.999);
+        let t = (std::f64::consts::FRAC_PI_2 * normalized).tan();
+        let a = (t - 1.0) / (t + 1.0);
+        Coefficients {
+            a1: a,
+            a2: 0.0,
+            b0: a,
+            b1: 1.0,
+            b2: 0.0,
+        }
+    }
+
+    /// One split's filters for one channel.
+    struct Split {
+        low: [Section; 2],
+        high: [Section; 2],
+        allpass: Section,
+    }
+
+    impl Split {
+        fn new(c: SplitCoeffs) -> Self {
+            Self {
+                low: [Section::new(c.low), Section::new(c.low)],
+                high: [Section::new(c.high), Section::new(c.high)],
+                allpass: Section::new(c.allpass),
+            }
+        }
+
+        fn update(&mut self, c: SplitCoeffs) {
+            self.low
+                .iter_mut()
+                .for_each(|s| s.update_coefficients(c.low));
+            self.high
+                .iter_mut()
+                .for_each(|s| s.update_coefficients(c.high));
+            self.allpass.update_coefficients(c.allpass);
+        }
+
+        fn reset(&mut self) {
+            self.low.iter_mut().for_each(|s| s.reset_state());
+            self.high.iter_mut().for_each(|s| s.reset_state());
+            self.allpass.reset_state();
+        }
+    }
+
+    /// An N-way crossover: `frequencies.len() + 1` bands, lowest first.
+    pub struct Crossover {
+        order: Order,
+        frequencies: Vec<f32>,
+        coeffs: Vec<SplitCoeffs>,
+        /// Per channel, per split.
+        splits: [Vec<Split>; 2],
+        /// Per channel: band k's all-pass compensation for splits k+1..,
+        /// flattened in band order.
+        compensation: [Vec<Section>; 2],
+    }
+
+    impl Crossover {
+        /// Build a network splitting at `frequencies` (Hz, ascending).
+        pub fn new(order: Order, sample_rate: f32, frequencies: &[f32]) -> Self {
+            debug_assert!(frequencies.windows(2).all(|w| w[0] <= w[1]));
+            let coeffs: Vec<SplitCoeffs> = frequencies
+                .iter()
+                .map(|&f| split_coeffs(order, sample_rate, f))
+                .collect();
+            let splits = std::array::from_fn(|_| coeffs.iter().map(|&c| Split::new(c)).collect());
+            let compensation = std::array::from_fn(|_| {
+                (0..coeffs.len())
+                    .flat_map(|k| coeffs[k + 1..].iter().map(|c| Section::new(c.allpass)))
+                    .collect()
+            });
+            Self {
+                order,
+                frequencies: frequencies.to_vec(),
+                coeffs,
+                splits,
+                compensation,
+            }
+        }
+
+        /// 12 dB/oct network.
+        pub fn lr2(sample_rate: f32, frequencies: &[f32]) -> Self {
+            Self::new(Order::Lr2, sample_rate, frequencies)
+        }
+
+        /// 24 dB/oct network.
+        pub fn lr4(sample_rate: f32, frequencies: &[f32]) -> Self {
+            Self::new(Order::Lr4, sample_rate, frequencies)
+        }
+
+        /// Number of output bands.
+        pub fn bands(&self) -> usize {
+            self.frequencies.len() + 1
+        }
+
+        pub fn frequencies(&self) -> &[f32] {
+            &self.frequencies
+        }
+
+        /// Move the split points (same count, ascending) without clearing
+        /// filter memory, so sweeps don't click.
+        pub fn set_frequencies(&mut self, sample_rate: f32, frequencies: &[f32]) {
+            debug_assert_eq!(frequencies.len(), self.frequencies.len());
+            for (k, &f) in frequencies.iter().enumerate().take(self.coeffs.len()) {
+                self.frequencies[k] = f;
+                self.coeffs[k] = split_coeffs(self.order, sample_rate, f);
+            }
+            for ch in 0..2 {
+                for (split, &c) in self.splits[ch].iter_mut().zip(&self.coeffs) {
+                    split.update(c);
+                }
+                let mut sections = self.compensation[ch].iter_mut();
+                for k in 0..self.coeffs.len() {
+                    for c in &self.coeffs[k + 1..] {
+                        if let Some(section) = sections.next() {
+                            section.update_coefficients(c.allpass);
+                        }
+                    }
+                }
+            }
+        }
+
+        pub fn reset(&mut self) {
+            for split in self.splits.iter_mut().flatten() {
+                split.reset();
+            }
+            for section in self.compensation.iter_mut().flatten() {
+                section.reset_state();
+            }
+        }
+
+        /// Split one sample of channel `ch` (0 = L, 1 = R) into `bands`,
+        /// lowest first. `bands` must hold at least `self.bands()` values.
+        #[inline]
+        pub fn split_ch(&mut self, sample: f32, ch: usize, bands: &mut [f32]) {
+            let ch = ch.min(1);
+            let stages = self.order.stages();
+            let mut compensation = self.compensation[ch].iter_mut();
+            let mut rest = sample as f64;
+            let last = self.splits[ch].len();
+            for (k, split) in self.splits[ch].iter_mut().enumerate() {
+                let mut low = rest;
+                let mut high = rest;
+                for stage in 0..stages {
+                    low = split.low[stage].run(low);
+                    high = split.high[stage].run(high);
+                }
+                for section in compensation.by_ref().take(last - k - 1) {
+                    low = section.run(low);
+                }
+                bands[k] = low as f32;
+                rest = high;
+            }
+            bands[last] = rest as f32;
+        }
+    }
+}
+
+// ── Lookup-table waveshaper ──────────────────────────────────────────────────
+
+/// Memoryless transfer curves read from a table instead of computed per
+/// sample, so a saturator can run a measured curve, or a `tanh`/`powf` one
+/// without the transcendental calls.
+///
+/// The table covers ±`range` on an even grid with Catmull-Rom (cubic)
+/// interpolation between points; past the ends the curve continues along its
+/// end segment, like the Print mode's `StaticCurve`. The table is built
+/// off the audio thread (construction allocates); `eval` and `process` do
+/// not allocate. With oversampling on, `process` runs the curve at 2× through
+/// the shared halfband oversampler, which carries per-channel state: use one
+/// shaper per channel.
+#[cfg_attr(not(feature = "pultec"), allow(dead_code))]
+pub mod waveshaper {
+    use crate::oversampler::Oversampler;
+
+    /// Table points used when a caller has no reason to pick.
+    pub const DEFAULT_POINTS: usize = 2049;
+    /// Oversampling factor for `with_oversampling`.
+    const OS_FACTOR: usize = 2;
+
+    pub struct Waveshaper {
+        range: f32,
+        points_per_unit: f32,
+        table: Vec<f32>,
+        oversampler: Option<Oversampler>,
+    }
+
+    impl Waveshaper {
+        /// Sample `transfer` at `points` (≥ 4) evenly spaced inputs over
+        /// ±`range`.
+        pub fn from_fn(range: f32, points: usize, transfer: impl Fn(f32) -> f32) -> Self {
+            let points = points.max(4);
+            let points_per_unit = (points - 1) as f32 / (2.0 * range);
+            let table = (0..points)
+                .map(|i| transfer(i as f32 / points_per_unit - range))
+                .collect();
+            Self {
+                range,
+                points_per_unit,
+                table,
+                oversampler: None,
+            }
+        }
+
+        /// The curve at `x`, cubic between points.
+        #[inline]
+        pub fn eval(&self, x: f32) -> f32 {
+            let last = self.table.len() - 1;
+            let pos = (x + self.range) * self.points_per_unit;
+            // The saturating cast sends negatives and NaN to 0; past either
+            // end `t` leaves 0..=1 and the end segment is extrapolated.
+            let i = (pos as usize).min(last - 1);
+            let t = pos - i as f32;
+            let (p1, p2) = (self.table[i], self.table[i + 1]);
+            if !(0.0..=1.0).contains(&t) {
+                return p1 + (p2 - p1) * t;
+            }
+            let p0 = self.table[i.saturating_sub(1)];
+            let p3 = self.table[(i + 2).min(last)];
+            // Catmull-Rom
+            let a = -0.5 * p0 + 1.5 * p1 - 1.5 * p2 + 0.5 * p3;
+            let b = p0 - 2.5 * p1 + 2.0 * p2 - 0.5 * p3;
+            let c = 0.5 * (p2 - p0);
+            ((a * t + b) * t + c) * t + p1
+        }
+    }
+
+    // Measured curves and the 2× path. The Pultec's tube stage reads `tanh`
+    // from a table inside its own 4× oversampler, so only the tests use
+    // these until a module ships a measured curve.
+    #[cfg_attr(not(test), allow(dead_code))]
+    impl Waveshaper {
+        /// Build from a measured curve: `(input, output)` pairs sorted by
+        /// input, resampled piecewise-linearly onto `points` grid points over
+        /// ±(largest |input|). Pairs must cover both signs.
+        pub fn from_measured(measured: &[(f32, f32)], points: usize) -> Self {
+            debug_assert!(measured.windows(2).all(|w| w[0].0 <= w[1].0));
+            let range = measured
+                .iter()
+                .fold(0.0_f32, |r, &(x, _)| r.max(x.abs()))
+                .max(f32::EPSILON);
+            Self::from_fn(range, points, |x| interpolate_measured(measured, x))
+        }
+
+        /// Run `process` at 2×.
+        pub fn with_oversampling(mut self) -> Self {
+            let mut os = Oversampler::new(OS_FACTOR, 1);
+            os.set_factor(OS_FACTOR);
+            self.oversampler = Some(os);
+            self
+        }
+
+        pub fn range(&self) -> f32 {
+            self.range
+        }
+
+        /// Shape one sample, oversampled if enabled.
+        #[inline]
+        pub fn process(&mut self, x: f32) -> f32 {
+            let Some(mut os) = self.oversampler.take() else {
+                return self.eval(x);
+            };
+            let mut shaped = [0.0_f32; OS_FACTOR];
+            for (out, &up) in shaped.iter_mut().zip(os.upsample(x, 0)) {
+                *out = self.eval(up);
+            }
+            let y = os.downsample(&shaped, 0);
+            self.oversampler = Some(os);
+            y
+        }
+
+        pub fn reset(&mut self) {
+            if let Some(os) = self.oversampler.as_mut() {
+                os.reset();
+            }
+        }
+    }
+
+    /// Piecewise-linear read of sorted `(input, output)` pairs, held flat
+    /// past either end.
+    fn interpolate_measured(measured: &[(f32, f32)], x: f32) -> f32 {
+        let upper = measured.partition_point(|&(mx, _)| mx < x);
+        match (measured.get(upper.wrapping_sub(1)), measured.get(upper)) {
+            (Some(&(x0, y0)), Some(&(x1, y1))) if x1 > x0 => y0 + (y1 - y0) * (x - x0) / (x1 - x0),
+            (_, Some(&(_, y))) | (Some(&(_, y)), None) => y,
+            (None, None) => x,
+        }
+    }
+}
+
+// ── Antiderivative anti-aliasing ─────────────────────────────────────────────
+
+/// Antiderivative anti-aliasing (ADAA) for memoryless saturators, after
+/// Parker/Zavalishin/Le Bivic and Bilbao/Esqueda/Välimäki: instead of the
+/// curve at each sample, output the curve's average over the straight line
+/// between consecutive input samples, computed from its antiderivative.
+/// Harmonics past Nyquist come out attenuated, at the native rate and the
+/// cost of one or two antiderivative evaluations per sample.
+///
+/// First order delays by half a sample, second order by one, and both
+/// average: on a linear curve they act as a gentle low-pass (first order is
+/// −17 dB at 20 kHz at 44.1 kHz). `process_residual` sidesteps that by
+/// passing the input straight through and running only the curve's
+/// nonlinear part, `f(x) − x`, through ADAA.
+///
+/// Curves are passed per call, so a drive change takes effect on the next
+/// sample with no state to rebuild. Antiderivatives are evaluated in f64:
+/// the method divides their differences by the input step.
+#[allow(dead_code)]
+pub mod adaa {
+    /// Input steps below this fall back to evaluating the curve directly.
+    const ILL_CONDITIONED: f64 = 1.0e-5;
+
+    /// A memoryless curve with its first antiderivative.
+    pub trait Antiderivative {
+        fn eval(&self, x: f64) -> f64;
+        fn ad1(&self, x: f64) -> f64;
+    }
+
+    /// …and its second.
+    pub trait SecondAntiderivative: Antiderivative {
+        fn ad2(&self, x: f64) -> f64;
+    }
+
+    /// `f(x) − x`: the nonlinear part of a curve.
+    pub struct Residual<'a, C>(pub &'a C);
+
+    impl<C: Antiderivative> Antiderivative for Residual<'_, C> {
+        #[inline]
+        fn eval(&self, x: f64) -> f64 {
+            self.0.eval(x) - x
+        }
+        #[inline]
+        fn ad1(&self, x: f64) -> f64 {
+            self.0.ad1(x) - 0.5 * x * x
+        }
+    }
+
+    impl<C: SecondAntiderivative> SecondAntiderivative for Residual<'_, C> {
+        #[inline]
+        fn ad2(&self, x: f64) -> f64 {
+            self.0.ad2(x) - x * x * x / 6.0
+        }
+    }
+
+    /// First-order ADAA state for one channel.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Adaa1 {
+        x1: f64,
+    }
+
+    impl Adaa1 {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn reset(&mut self) {
+            self.x1 = 0.0;
+        }
+
+        #[inline]
+        pub fn process(&mut self, curve: &impl Antiderivative, x: f32) -> f32 {
+            let (x0, x1) = (x as f64, self.x1);
+            let dx = x0 - x1;
+            let y = if dx.abs() < ILL_CONDITIONED {
+                curve.eval(0.5 * (x0 + x1))
+            } else {
+                (curve.ad1(x0) - curve.ad1(x1)) / dx
+            };
+            self.x1 = x0;
+            y as f32
+        }
+
+        /// `x` plus the anti-aliased nonlinear part of `curve`.
+        #[inline]
+        pub fn process_residual(&mut self, curve: &impl Antiderivative, x: f32) -> f32 {
+            x + self.process(&Residual(curve), x)
+        }
+    }
+
+    /// Second-order ADAA state for one channel.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Adaa2 {
+        x1: f64,
+        x2: f64,
+    }
+
+    impl Adaa2 {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn reset(&mut self) {
+            self.x1 = 0.0;
+            self.x2 = 0.0;
+        }
+
+        #[inline]
+        pub fn process(&mut self, curve: &impl SecondAntiderivative, x: f32) -> f32 {
+            let (x0, x1, x2) = (x as f64, self.x1, self.x2);
+            let dx = x0 - x2;
+            let y = if dx.abs() >= ILL_CONDITIONED {
+                2.0 / dx * (divided_difference(curve, x0, x1) - divided_difference(curve, x1, x2))
+            } else {
+                // x0 ≈ x2: expand around their midpoint instead.
+                let mid = 0.5 * (x0 + x2);
+                let delta = mid - x1;
+                if delta.abs() < ILL_CONDITIONED {
+                    curve.eval(0.5 * (mid + x1))
+                } else {
+                    2.0 / delta * (curve.ad1(mid) + (curve.ad2(x1) - curve.ad2(mid)) / delta)
+                }
+            };
+            self.x2 = x1;
+            self.x1 = x0;
+            y as f32
+        }
+
+        /// `x` plus the anti-aliased nonlinear part of `curve`.
+        #[inline]
+        pub fn process_residual(&mut self, curve: &impl SecondAntiderivative, x: f32) -> f32 {
+            x + self.process(&Residual(curve), x)
+        }
+    }
+
+    /// (F2(a) − F2(b)) / (a − b), or F1 at the midpoint when a ≈ b.
+    #[inline]
+    fn divided_difference(curve: &impl SecondAntiderivative, a: f64, b: f64) -> f64 {
+        let d = a - b;
+        if d.abs() < ILL_CONDITIONED {
+            curve.ad1(0.5 * (a + b))
+        } else {
+            (curve.ad2(a) - curve.ad2(b)) / d
+        }
+    }
+
+    /// ln(cosh x) without overflow: the antiderivative of tanh.
+    #[inline]
+    pub fn ln_cosh(x: f64) -> f64 {
+        let a = x.abs();
+        a + (-2.0 * a).exp().ln_1p() - std::f64::consts::LN_2
+    }
+
+    /// `shaping_fns::sigmoid`, x / (1 + |x|).
+    pub struct Sigmoid;
+
+    impl Antiderivative for Sigmoid {
+        #[inline]
+        fn eval(&self, x: f64) -> f64 {
+            x / (1.0 + x.abs())
+        }
+        #[inline]
+        fn ad1(&self, x: f64) -> f64 {
+            let a = x.abs();
+            a - a.ln_1p()
+        }
+    }
+
+    impl SecondAntiderivative for Sigmoid {
+        #[inline]
+        fn ad2(&self, x: f64) -> f64 {
+            let a = x.abs();
+            x.signum() * (0.5 * a * a - (1.0 + a) * a.ln_1p() + a)
+        }
+    }
+
+    /// `shaping_fns::tanh_saturation` at `drive`. First order only: the
+    /// second antiderivative of tanh needs a dilogarithm.
+    pub struct TanhSaturation {
+        pub drive: f32,
+    }
+
+    impl TanhSaturation {
+        #[inline]
+        fn gains(&self) -> (f64, f64) {
+            let drive = self.drive as f64;
+            (1.0 + drive * 2.0, 1.0 / (1.0 + drive * 0.5))
+        }
+    }
+
+    impl Antiderivative for TanhSaturation {
+        #[inline]
+        fn eval(&self, x: f64) -> f64 {
+            let (gain, makeup) = self.gains();
+            (gain * x).tanh() * makeup
+        }
+        #[inline]
+        fn ad1(&self, x: f64) -> f64 {
+            let (gain, makeup) = self.gains();
+            ln_cosh(gain * x) / gain * makeup
+        }
     }
 }
 
@@ -139,8 +901,13 @@ pub mod shaping_fns {
 
 #[cfg(test)]
 mod tests {
+    use super::adaa::{
+        Adaa1, Adaa2, Antiderivative, SecondAntiderivative, Sigmoid, TanhSaturation,
+    };
+    use super::crossover::{Crossover, Order};
     use super::shaping_fns::*;
-    use super::{Filter, FilterType};
+    use super::waveshaper::{Waveshaper, DEFAULT_POINTS};
+    use super::{Filter, FilterType, Svf};
 
     // ── sigmoid ───────────────────────────────────────────────────────────────
 
@@ -355,6 +1122,32 @@ mod tests {
         f.update_parameters(48000.0, FilterType::LowShelf, 200.0, 0.707, -3.0);
     }
 
+    #[test]
+    fn test_filter_low_frequency_shelf_keeps_its_gain() {
+        // 30 Hz at 96 kHz: 1 - cos(w0) ≈ 2e-6, a few f32 ulps. With the
+        // coefficients rounded to f32 the plateau sags to ≈5.83 dB.
+        let sample_rate = 96000.0;
+        let mut f = Filter::new(sample_rate, FilterType::LowShelf, 30.0, 0.707, 6.0);
+        let omega = std::f64::consts::TAU * 3.0 / sample_rate as f64;
+        // Two seconds; measure the second, six whole cycles of the 3 Hz sine.
+        let n = 2 * sample_rate as usize;
+        let mut sum_sq = 0.0_f64;
+        for i in 0..n {
+            let y = f.run_ch((omega * i as f64).sin() as f32, 0) as f64;
+            if i >= n / 2 {
+                sum_sq += y * y;
+            }
+        }
+        let peak = (2.0 * sum_sq / (n / 2) as f64).sqrt();
+        let gain_db = 20.0 * peak.log10();
+        let expected_db = 20.0 * (f.magnitude(omega as f32) as f64).log10();
+        assert!(
+            (gain_db - expected_db).abs() < 0.01,
+            "3 Hz through the shelf: {gain_db} dB, response says {expected_db} dB"
+        );
+        assert!((gain_db - 6.0).abs() < 0.02, "shelf plateau: {gain_db} dB");
+    }
+
     #[test]
     fn test_filter_is_linear_no_inline_clip() {
         // Filter::run must be LTI — output scales linearly with input.
@@ -384,4 +1177,460 @@ mod tests {
             assert!(out.is_finite(), "Filter output must stay finite");
         }
     }
+
+    #[test]
+    fn test_filter_magnitude_matches_bell_gain() {
+        // A +6 dB bell at 1 kHz reads +6 dB at its centre and ~0 dB far away.
+        let f = Filter::new(44100.0, FilterType::Bell, 1000.0, 0.707, 6.0);
+        let omega = |hz: f32| std::f32::consts::TAU * hz / 44100.0;
+        let centre = 20.0 * f.magnitude(omega(1000.0)).log10();
+        let far = 20.0 * f.magnitude(omega(15000.0)).log10();
+        assert!((centre - 6.0).abs() < 0.05, "centre {centre}");
+        assert!(far.abs() < 0.3, "far {far}");
+    }
+
+    // ── Svf ───────────────────────────────────────────────────────────────────
+
+    /// Steady-state peak of `svf` driven by a unit sine at `freq_hz`.
+    fn svf_sine_peak(svf: &mut Svf, sr: f32, freq_hz: f32) -> f32 {
+        let mut peak = 0.0_f32;
+        for n in 0..8192 {
+            let x = (std::f32::consts::TAU * freq_hz * n as f32 / sr).sin();
+            let y = svf.process(x);
+            if n > 4096 {
+                peak = peak.max(y.abs());
+            }
+        }
+        peak
+    }
+
+    #[test]
+    fn test_svf_new_is_flat() {
+        let mut svf = Svf::new();
+        for &input in &[0.0, 0.5, -0.5, 1.0, -1.0] {
+            let out = svf.process(input);
+            assert!((out - input).abs() < 1e-6, "input={input}, out={out}");
+        }
+    }
+
+    #[test]
+    fn test_svf_bell_hits_its_gain_at_center() {
+        let sr = 48000.0;
+        for gain_db in [-12.0_f32, -3.0, 6.0] {
+            let mut svf = Svf::new();
+            svf.set_bell(sr, 1000.0, 1.0, gain_db);
+            let peak_db = 20.0 * svf_sine_peak(&mut svf, sr, 1000.0).log10();
+            assert!(
+                (peak_db - gain_db).abs() < 0.1,
+                "bell {gain_db} dB measured {peak_db} dB"
+            );
+        }
+    }
+
+    #[test]
+    fn test_svf_set_bell_gain_matches_full_update() {
+        let mut full = Svf::new();
+        full.set_bell(44100.0, 2500.0, 2.0, -7.5);
+        let mut gain_only = Svf::new();
+        gain_only.set_bell(44100.0, 2500.0, 2.0, 3.0);
+        gain_only.set_bell_gain(-7.5);
+        assert_eq!(full, gain_only);
+    }
+
+    #[test]
+    fn test_svf_update_does_not_clear_state() {
+        let mut svf = Svf::new();
+        svf.set_bell(44100.0, 1000.0, 1.0, 6.0);
+        for _ in 0..100 {
+            svf.process(0.7);
+        }
+        let before = svf.clone();
+        svf.set_bell(44100.0, 2000.0, 1.5, -3.0);
+        svf.set_bell_gain(-6.0);
+        assert_eq!(svf.ic1eq, before.ic1eq);
+        assert_eq!(svf.ic2eq, before.ic2eq);
+    }
+
+    #[test]
+    fn test_svf_reset_clears_state() {
+        let mut svf = Svf::new();
+        svf.set_bell(44100.0, 1000.0, 1.0, 6.0);
+        for _ in 0..100 {
+            svf.process(1.0);
+        }
+        svf.reset();
+        assert_eq!((svf.ic1eq, svf.ic2eq), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_svf_per_sample_gain_sweep_is_smooth() {
+        // Sweeping the bell from 0 to -24 dB within 10 ms, one update per
+        // sample, must not step the output: no sample-to-sample jump larger
+        // than the input sine's own steepest step.
+        let sr = 44100.0;
+        let mut svf = Svf::new();
+        svf.set_bell(sr, 1000.0, 1.0, 0.0);
+        let omega = std::f32::consts::TAU * 1000.0 / sr;
+        let max_step = omega; // unit sine's largest per-sample change
+        let sweep = (0.01 * sr) as usize;
+        let mut prev = 0.0_f32;
+        for n in 0..4 * sweep {
+            let gain_db = -24.0 * (n as f32 / sweep as f32).min(1.0);
+            svf.set_bell_gain(gain_db);
+            let y = svf.process((omega * n as f32).sin());
+            assert!(
+                (y - prev).abs() <= max_step * 1.05,
+                "step at {n}: {}",
+                y - prev
+            );
+            prev = y;
+        }
+    }
+
+    #[test]
+    fn test_svf_band_pass_unity_rejects_out_of_band() {
+        let sr = 44100.0;
+        let mut center = Svf::new();
+        center.set_band_pass_unity(sr, 4000.0, 1.5);
+        let center_peak = svf_sine_peak(&mut center, sr, 4000.0);
+        let mut low = Svf::new();
+        low.set_band_pass_unity(sr, 4000.0, 1.5);
+        let low_peak = svf_sine_peak(&mut low, sr, 100.0);
+        assert!((center_peak - 1.0).abs() < 0.01, "center: {center_peak}");
+        assert!(low_peak < 0.1 * center_peak, "100 Hz leaked: {low_peak}");
+    }
+
+    #[test]
+    fn test_svf_band_pass_peak_gain_is_q() {
+        let sr = 44100.0;
+        let mut svf = Svf::new();
+        svf.set_band_pass(sr, 1000.0, 2.0);
+        let peak = svf_sine_peak(&mut svf, sr, 1000.0);
+        assert!((peak - 2.0).abs() < 0.02, "peak: {peak}");
+    }
+
+    #[test]
+    fn test_svf_finite_at_extreme_params() {
+        let mut svf = Svf::new();
+        svf.set_bell(44100.0, 20.0, 0.1, -60.0);
+        for i in 0..200 {
+            let out = svf.process(if i % 2 == 0 { 1.0 } else { -1.0 });
+            assert!(out.is_finite(), "sample {i}: {out}");
+        }
+        // Above Nyquist clamps rather than blowing up.
+        svf.set_bell(44100.0, 40000.0, 1.0, 3.0);
+        assert!(svf.process(0.5).is_finite());
+    }
+
+    // ── crossover ─────────────────────────────────────────────────────────────
+
+    /// Deterministic white-ish noise in [-1, 1).
+    fn noise(n: usize) -> Vec<f32> {
+        let mut state = 0x1234_5678_u32;
+        (0..n)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 8) as f32 / (1u32 << 23) as f32 - 1.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_crossover_bands_sum_to_an_allpass() {
+        // Four LR4 bands summed must equal the input through one
+        // Butterworth-Q all-pass per split, sample for sample.
+        use biquad::{Biquad, DirectForm2Transposed, Type};
+        let sr = 48000.0;
+        let freqs = [120.0, 1000.0, 6000.0];
+        let mut xover = Crossover::lr4(sr, &freqs);
+        let mut reference: Vec<DirectForm2Transposed<f64>> = freqs
+            .iter()
+            .map(|&f| {
+                let c =
+                    super::biquad_coeffs_f64(Type::AllPass, sr, f, std::f32::consts::FRAC_1_SQRT_2)
+                        .unwrap();
+                DirectForm2Transposed::<f64>::new(c)
+            })
+            .collect();
+        let mut bands = [0.0_f32; 4];
+        let mut max_err = 0.0_f32;
+        for x in noise(8192) {
+            xover.split_ch(x, 0, &mut bands);
+            let expected = reference
+                .iter_mut()
+                .fold(x as f64, |y, section| section.run(y)) as f32;
+            max_err = max_err.max((bands.iter().sum::<f32>() - expected).abs());
+        }
+        assert!(
+            max_err < 1e-5,
+            "band sum strayed from the all-pass by {max_err}"
+        );
+    }
+
+    /// Steady-state RMS gain of each band for a sine at `freq`.
+    fn crossover_band_gains(xover: &mut Crossover, sr: f32, freq: f32) -> Vec<f32> {
+        let mut bands = vec![0.0_f32; xover.bands()];
+        let mut energy = vec![0.0_f32; xover.bands() + 1];
+        let omega = std::f32::consts::TAU * freq / sr;
+        for n in 0..(sr as usize / 2) {
+            let x = (omega * n as f32).sin();
+            xover.split_ch(x, 0, &mut bands);
+            if n >= sr as usize / 4 {
+                energy[0] += x * x;
+                for (e, b) in energy[1..].iter_mut().zip(&bands) {
+                    *e += b * b;
+                }
+            }
+        }
+        energy[1..].iter().map(|e| (e / energy[0]).sqrt()).collect()
+    }
+
+    #[test]
+    fn test_crossover_sum_is_flat() {
+        let sr = 48000.0;
+        for order in [Order::Lr2, Order::Lr4] {
+            for freq in [30.0, 120.0, 500.0, 1000.0, 3000.0, 6000.0, 15000.0] {
+                let mut xover = Crossover::new(order, sr, &[120.0, 1000.0, 6000.0]);
+                let mut bands = [0.0_f32; 4];
+                let omega = std::f32::consts::TAU * freq / sr;
+                let (mut in_energy, mut out_energy) = (0.0_f32, 0.0_f32);
+                for n in 0..(sr as usize / 2) {
+                    let x = (omega * n as f32).sin();
+                    xover.split_ch(x, 0, &mut bands);
+                    if n >= sr as usize / 4 {
+                        in_energy += x * x;
+                        out_energy += bands.iter().sum::<f32>().powi(2);
+                    }
+                }
+                let gain = (out_energy / in_energy).sqrt();
+                assert!(
+                    (gain - 1.0).abs() < 0.005,
+                    "{order:?} sum at {freq} Hz: {gain}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_crossover_bands_cross_at_split_frequency() {
+        // At the split, LR4 puts each side at -6 dB; well below it the low
+        // band carries everything.
+        let sr = 48000.0;
+        let gains = crossover_band_gains(&mut Crossover::lr4(sr, &[1000.0]), sr, 1000.0);
+        assert!((gains[0] - 0.5).abs() < 0.005, "low at split: {}", gains[0]);
+        assert!(
+            (gains[1] - 0.5).abs() < 0.005,
+            "high at split: {}",
+            gains[1]
+        );
+        let gains = crossover_band_gains(&mut Crossover::lr4(sr, &[1000.0]), sr, 100.0);
+        assert!(
+            gains[0] > 0.99 && gains[1] < 0.01,
+            "100 Hz: low {}, high {}",
+            gains[0],
+            gains[1]
+        );
+    }
+
+    #[test]
+    fn test_crossover_moving_splits_keeps_the_sum_flat() {
+        // A 12 dB/oct network moved to new split points mid-stream (and
+        // cleared) sums flat at the new points, as a fresh one would.
+        let sr = 48000.0;
+        let mut xover = Crossover::lr2(sr, &[200.0, 2000.0]);
+        let mut bands = [0.0_f32; 3];
+        for x in noise(1024) {
+            xover.split_ch(x, 1, &mut bands);
+        }
+        xover.set_frequencies(sr, &[300.0, 3000.0]);
+        assert_eq!(xover.frequencies(), &[300.0, 3000.0]);
+        xover.reset();
+        for freq in [300.0, 3000.0] {
+            let omega = std::f32::consts::TAU * freq / sr;
+            let (mut in_energy, mut out_energy) = (0.0_f32, 0.0_f32);
+            for n in 0..(sr as usize / 2) {
+                let x = (omega * n as f32).sin();
+                xover.split_ch(x, 1, &mut bands);
+                if n >= sr as usize / 4 {
+                    in_energy += x * x;
+                    out_energy += bands.iter().sum::<f32>().powi(2);
+                }
+            }
+            let gain = (out_energy / in_energy).sqrt();
+            assert!((gain - 1.0).abs() < 0.005, "sum at {freq} Hz: {gain}");
+        }
+    }
+
+    // ── waveshaper ────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_waveshaper_cubic_tracks_the_curve() {
+        // 257 points over ±4 is coarse; cubic must still land well inside
+        // what linear interpolation between the same points manages.
+        let shaper = Waveshaper::from_fn(4.0, 257, f32::tanh);
+        let (mut cubic_err, mut linear_err) = (0.0_f32, 0.0_f32);
+        let step = 8.0 / 256.0;
+        for i in 0..4000 {
+            let x = -4.0 + 8.0 * i as f32 / 4000.0;
+            cubic_err = cubic_err.max((shaper.eval(x) - x.tanh()).abs());
+            let lo = (x / step).floor() * step;
+            let t = (x - lo) / step;
+            let linear = lo.tanh() + ((lo + step).tanh() - lo.tanh()) * t;
+            linear_err = linear_err.max((linear - x.tanh()).abs());
+        }
+        assert!(cubic_err < 1e-4, "cubic error {cubic_err}");
+        assert!(cubic_err < linear_err / 4.0, "{cubic_err} vs {linear_err}");
+        // Past the table it continues along the end segment; NaN stays NaN.
+        assert!((shaper.eval(6.0) - 1.0).abs() < 5e-3);
+        assert!(shaper.eval(f32::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_waveshaper_from_measured_points() {
+        // A measured soft clipper: linear to ±0.5, flattening to ±0.8.
+        let measured = [
+            (-2.0, -0.8),
+            (-1.0, -0.75),
+            (-0.5, -0.5),
+            (0.0, 0.0),
+            (0.5, 0.5),
+            (1.0, 0.75),
+            (2.0, 0.8),
+        ];
+        let shaper = Waveshaper::from_measured(&measured, DEFAULT_POINTS);
+        assert_eq!(shaper.range(), 2.0);
+        for &(x, y) in &measured {
+            assert!((shaper.eval(x) - y).abs() < 1e-3, "{x}: {}", shaper.eval(x));
+        }
+        assert!((shaper.eval(0.25) - 0.25).abs() < 1e-3);
+        assert!((shaper.eval(1.5) - 0.775).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_waveshaper_oversampled_settles_on_the_curve() {
+        // A held input comes out of the 2× path at the curve's value once
+        // the halfband filters fill; the path stays bounded on a hot sine
+        // (halfband ringing overshoots the curve a little).
+        let mut shaper = Waveshaper::from_fn(4.0, DEFAULT_POINTS, f32::tanh).with_oversampling();
+        let mut y = 0.0;
+        for _ in 0..200 {
+            y = shaper.process(0.5);
+        }
+        assert!((y - 0.5_f32.tanh()).abs() < 1e-3, "settled at {y}");
+        shaper.reset();
+        for n in 0..4410 {
+            let x = 3.0 * (std::f32::consts::TAU * 15000.0 * n as f32 / 44100.0).sin();
+            let y = shaper.process(x);
+            assert!(y.is_finite() && y.abs() < 1.5, "sample {n}: {y}");
+        }
+    }
+
+    // ── ADAA ──────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_adaa_antiderivatives_differentiate_back() {
+        let h = 1e-4;
+        let d = |f: &dyn Fn(f64) -> f64, x: f64| (f(x + h) - f(x - h)) / (2.0 * h);
+        let tanh = TanhSaturation { drive: 0.7 };
+        for i in -40..=40 {
+            let x = i as f64 * 0.1 + 0.013;
+            assert!(
+                (d(&|x| Sigmoid.ad1(x), x) - Sigmoid.eval(x)).abs() < 1e-6,
+                "{x}"
+            );
+            assert!(
+                (d(&|x| Sigmoid.ad2(x), x) - Sigmoid.ad1(x)).abs() < 1e-6,
+                "{x}"
+            );
+            assert!((d(&|x| tanh.ad1(x), x) - tanh.eval(x)).abs() < 1e-6, "{x}");
+            assert!(
+                (tanh.eval(x) as f32 - tanh_saturation(x as f32, 0.7)).abs() < 1e-6,
+                "{x}"
+            );
+        }
+    }
+
+    /// Energy of `signal` away from the harmonics of `f0`, via a direct DFT
+    /// at every `bin_hz`-spaced bin.
+    fn alias_energy(signal: &[f32], sr: f32, f0: f32, bin_hz: f32) -> f32 {
+        let n = signal.len();
+        let bins = (sr / 2.0 / bin_hz) as usize;
+        (1..bins)
+            .filter(|&k| {
+                let f = k as f32 * bin_hz;
+                let nearest = (f / f0).round() * f0;
+                (f - nearest).abs() > 2.0 * bin_hz
+            })
+            .map(|k| {
+                let w = std::f32::consts::TAU * k as f32 / n as f32;
+                let (re, im) = signal
+                    .iter()
+                    .enumerate()
+                    .fold((0.0, 0.0), |(re, im), (i, &x)| {
+                        (re + x * (w * i as f32).cos(), im - x * (w * i as f32).sin())
+                    });
+                re * re + im * im
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_adaa_reduces_aliasing() {
+        // A hot 5 kHz sine through a hard-driven sigmoid: folded harmonics
+        // land between the true ones.
+        let sr = 44100.0;
+        let (f0, n) = (5000.0, 2205);
+        let bin_hz = sr / n as f32;
+        let input: Vec<f32> = (0..n + 64)
+            .map(|i| 8.0 * (std::f32::consts::TAU * f0 * i as f32 / sr).sin())
+            .collect();
+        let tail = |y: Vec<f32>| y[64..].to_vec();
+        let naive = tail(input.iter().map(|&x| sigmoid(x)).collect());
+        let mut first = Adaa1::new();
+        let adaa1 = tail(input.iter().map(|&x| first.process(&Sigmoid, x)).collect());
+        let mut second = Adaa2::new();
+        let adaa2 = tail(input.iter().map(|&x| second.process(&Sigmoid, x)).collect());
+
+        let naive = alias_energy(&naive, sr, f0, bin_hz);
+        let adaa1 = alias_energy(&adaa1, sr, f0, bin_hz);
+        let adaa2 = alias_energy(&adaa2, sr, f0, bin_hz);
+        assert!(adaa1 < naive / 4.0, "first order {adaa1} vs naive {naive}");
+        assert!(adaa2 < adaa1, "second order {adaa2} vs first {adaa1}");
+    }
+
+    #[test]
+    fn test_adaa_residual_keeps_the_top_end() {
+        // At a whisper the sigmoid is linear. Plain first-order ADAA then
+        // averages adjacent samples and dulls 15 kHz; the residual form
+        // passes it untouched.
+        let sr = 44100.0;
+        let input: Vec<f32> = (0..4410)
+            .map(|i| 0.001 * (std::f32::consts::TAU * 15000.0 * i as f32 / sr).sin())
+            .collect();
+        let rms = |y: &[f32]| (y.iter().map(|s| s * s).sum::<f32>() / y.len() as f32).sqrt();
+        let mut plain = Adaa1::new();
+        let plain: Vec<f32> = input.iter().map(|&x| plain.process(&Sigmoid, x)).collect();
+        let mut residual = Adaa1::new();
+        let residual: Vec<f32> = input
+            .iter()
+            .map(|&x| residual.process_residual(&Sigmoid, x))
+            .collect();
+        let reference = rms(&input);
+        assert!(rms(&plain) < 0.6 * reference);
+        assert!((rms(&residual) / reference - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_adaa_constant_input_settles_on_the_curve() {
+        // Held input is the ill-conditioned case for both orders.
+        let mut first = Adaa1::new();
+        let mut second = Adaa2::new();
+        let (mut y1, mut y2) = (0.0, 0.0);
+        for _ in 0..4 {
+            y1 = first.process(&Sigmoid, 0.5);
+            y2 = second.process(&Sigmoid, 0.5);
+        }
+        assert!((y1 - sigmoid(0.5)).abs() < 1e-6);
+        assert!((y2 - sigmoid(0.5)).abs() < 1e-6);
+    }
 }
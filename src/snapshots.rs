@@ -0,0 +1,235 @@
+// src/snapshots.rs
+// A/B snapshot comparison.
+//
+// Two full-state snapshots (same shape as a preset: param ID → plain value)
+// that the editor flips between. The live plugin state always belongs to the
+// *active* slot; the inactive slot holds whatever was live when the user
+// last switched away. Switching therefore stores the live state into the
+// active slot and hands back the other slot's state for the editor to apply.
+//
+// Each slot also remembers the smoothed output level measured while it was
+// live, which is what "match gain" uses to level the two sides.
+//
+// Loudness matching goes further and levels on integrated loudness: with it
+// on, each switch records the LUFS measured over the stretch the slot was
+// live and restarts the meter, and the slot coming in has its master gain
+// scaled to sound as loud as the one going out. That keeps "louder sounds
+// better" out of the comparison without anyone pressing MATCH.
+//
+// GUI-thread only — capture/apply go through the editor's ParamPtrs.
+
+use crate::loudness::LOUDNESS_FLOOR_LUFS;
+use std::collections::BTreeMap;
+
+/// Param that loudness matching scales (linear plain value).
+const MASTER_GAIN_ID: &str = "gain";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbSlot {
+    A,
+    B,
+}
+
+impl AbSlot {
+    pub fn other(self) -> Self {
+        match self {
+            Self::A => Self::B,
+            Self::B => Self::A,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::A => 0,
+            Self::B => 1,
+        }
+    }
+}
+
+pub struct AbCompare {
+    active: AbSlot,
+    states: [Option<BTreeMap<String, f32>>; 2],
+    /// Output level (dBFS RMS) last measured while each slot was live.
+    levels_db: [Option<f32>; 2],
+    /// Integrated loudness (LUFS) over each slot's last live stretch.
+    loudness_lufs: [Option<f32>; 2],
+}
+
+impl Default for AbCompare {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AbCompare {
+    pub fn new() -> Self {
+        Self {
+            active: AbSlot::A,
+            states: [None, None],
+            levels_db: [None, None],
+            loudness_lufs: [None, None],
+        }
+    }
+
+    pub fn active(&self) -> AbSlot {
+        self.active
+    }
+
+    /// Make `target` the active slot. `live` is the current plugin state and
+    /// `live_level_db` the current output level; both are recorded against
+    /// the slot being left. Returns the state the editor must apply, or
+    /// `None` when nothing changes (already active, or the target has never
+    /// been visited — in which case it starts as a copy of the live state,
+    /// the usual A/B convention).
+    pub fn select(
+        &mut self,
+        target: AbSlot,
+        live: BTreeMap<String, f32>,
+        live_level_db: f32,
+    ) -> Option<BTreeMap<String, f32>> {
+        if target == self.active {
+            return None;
+        }
+        let leaving = self.active.index();
+        let entering = target.index();
+        self.active = target;
+        self.levels_db[leaving] = Some(live_level_db);
+
+        match self.states[entering].clone() {
+            Some(state) => {
+                self.states[leaving] = Some(live);
+                Some(state)
+            }
+            None => {
+                self.states[entering] = Some(live.clone());
+                self.levels_db[entering] = Some(live_level_db);
+                self.states[leaving] = Some(live);
+                None
+            }
+        }
+    }
+
+    /// Copy the live (active) state over the inactive slot.
+    pub fn copy_to_other(&mut self, live: BTreeMap<String, f32>, live_level_db: f32) {
+        let other = self.active.other().index();
+        self.states[other] = Some(live);
+        self.levels_db[other] = Some(live_level_db);
+        // Unknown until the copy has been heard on its own.
+        self.loudness_lufs[other] = None;
+    }
+
+    /// Gain change (dB) to apply to the live slot so its output level
+    /// matches the level recorded for the inactive slot. `None` until the
+    /// other slot has been measured.
+    pub fn match_gain_offset_db(&self, live_level_db: f32) -> Option<f32> {
+        self.levels_db[self.active.other().index()].map(|other| other - live_level_db)
+    }
+
+    /// Record the integrated loudness measured since the active slot went
+    /// live. Call before `select`. A reading at the meter floor (silence, or
+    /// a flip inside the first 400 ms window) is dropped, so the slot keeps
+    /// its last real figure.
+    pub fn record_loudness(&mut self, integrated_lufs: f32) {
+        if integrated_lufs > LOUDNESS_FLOOR_LUFS {
+            self.loudness_lufs[self.active.index()] = Some(integrated_lufs);
+        }
+    }
+
+    /// Scale the master gain in `state` — the one `select` just handed back
+    /// for the now-active slot — so it plays as loud as the slot just left.
+    /// Returns the offset applied (dB), or `None`, leaving `state` alone,
+    /// until both slots have been measured. The slot's recorded loudness
+    /// moves with its gain, so a flip too quick to measure doesn't apply
+    /// the same offset twice.
+    pub fn match_loudness(&mut self, state: &mut BTreeMap<String, f32>) -> Option<f32> {
+        let live = self.active.index();
+        let target = self.loudness_lufs[self.active.other().index()]?;
+        let offset_db = target - self.loudness_lufs[live]?;
+        let gain = state.get_mut(MASTER_GAIN_ID)?;
+        *gain *= 10.0_f32.powf(offset_db / 20.0);
+        self.loudness_lufs[live] = Some(target);
+        Some(offset_db)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(v: f32) -> BTreeMap<String, f32> {
+        BTreeMap::from([("lf_gain".to_owned(), v)])
+    }
+
+    #[test]
+    fn first_switch_copies_live_state_and_applies_nothing() {
+        let mut ab = AbCompare::new();
+        assert_eq!(ab.select(AbSlot::B, state(1.0), -20.0), None);
+        assert_eq!(ab.active(), AbSlot::B);
+        // Going back to A restores what was live when we left it.
+        assert_eq!(ab.select(AbSlot::A, state(5.0), -18.0), Some(state(1.0)));
+        assert_eq!(ab.select(AbSlot::B, state(1.0), -20.0), Some(state(5.0)));
+    }
+
+    #[test]
+    fn selecting_active_slot_is_a_noop() {
+        let mut ab = AbCompare::new();
+        assert_eq!(ab.select(AbSlot::A, state(2.0), -20.0), None);
+        assert_eq!(ab.active(), AbSlot::A);
+    }
+
+    #[test]
+    fn copy_overwrites_inactive_slot() {
+        let mut ab = AbCompare::new();
+        ab.copy_to_other(state(3.0), -20.0);
+        assert_eq!(ab.select(AbSlot::B, state(7.0), -20.0), Some(state(3.0)));
+    }
+
+    #[test]
+    fn match_gain_uses_other_slots_level() {
+        let mut ab = AbCompare::new();
+        assert_eq!(ab.match_gain_offset_db(-20.0), None);
+        ab.select(AbSlot::B, state(0.0), -20.0);
+        // B is live and 4 dB hotter than A was.
+        assert_eq!(ab.match_gain_offset_db(-16.0), Some(-4.0));
+    }
+
+    fn gain_state(gain: f32) -> BTreeMap<String, f32> {
+        BTreeMap::from([(MASTER_GAIN_ID.to_owned(), gain)])
+    }
+
+    #[test]
+    fn loudness_match_levels_the_incoming_slot() {
+        let mut ab = AbCompare::new();
+        ab.record_loudness(-14.0);
+        ab.select(AbSlot::B, gain_state(1.0), -20.0);
+        // B measured 6 LU hotter than A.
+        ab.record_loudness(-8.0);
+        let mut a = ab.select(AbSlot::A, gain_state(1.0), -20.0).unwrap();
+        // A comes back 6 dB up to meet B.
+        assert_eq!(ab.match_loudness(&mut a), Some(6.0));
+        assert!((a[MASTER_GAIN_ID] - 1.995).abs() < 1e-3);
+
+        // Flipping back before A was measured again: A is assumed to be
+        // at B's level now, so nothing moves twice.
+        ab.record_loudness(LOUDNESS_FLOOR_LUFS);
+        let mut b = ab.select(AbSlot::B, gain_state(2.0), -20.0).unwrap();
+        assert_eq!(ab.match_loudness(&mut b), Some(0.0));
+        assert_eq!(b[MASTER_GAIN_ID], 1.0);
+    }
+
+    #[test]
+    fn loudness_match_waits_for_both_measurements() {
+        let mut ab = AbCompare::new();
+        ab.select(AbSlot::B, gain_state(1.0), -20.0);
+        ab.record_loudness(-10.0);
+        let mut a = ab.select(AbSlot::A, gain_state(1.0), -20.0).unwrap();
+        assert_eq!(ab.match_loudness(&mut a), None);
+        assert_eq!(a[MASTER_GAIN_ID], 1.0);
+
+        // A copy over B forgets B's figure.
+        ab.record_loudness(-12.0);
+        ab.copy_to_other(gain_state(1.0), -20.0);
+        let mut b = ab.select(AbSlot::B, gain_state(1.0), -20.0).unwrap();
+        assert_eq!(ab.match_loudness(&mut b), None);
+    }
+}
@@ -1,14 +1,17 @@
 // src/spectral.rs — Lock-free spectrum analysis pipeline.
 //
-// SpectrumData is written by the audio thread and read by the GUI thread
-// with no locks. Safety is achieved by:
+// The audio thread only copies tapped samples into AnalyzerRing; the FFTs
+// run on nih-plug's background thread, which publishes SpectrumData for the
+// GUI. Neither hand-off takes a lock. Safety is achieved by:
 //   - Storing f32 magnitude values as their raw u32 bits in AtomicU32.
 //   - Using Relaxed ordering for individual bin reads/writes (torn reads
 //     of a single f32 produce a valid f32 — worst case a slightly stale value).
-//   - Using Release/Acquire ordering on `dirty` to establish happens-before
-//     between the audio thread write and the GUI thread read.
+//   - Using Release/Acquire ordering on `dirty` / `written` to establish
+//     happens-before between each writer and its reader.
 
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use crate::telemetry::{spsc_ring, Meter, RingConsumer, RingProducer};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
 
 /// Number of frequency bins published to the GUI.
 /// With FFT_SIZE = 2048 this covers 0 … fs/4 Hz (all useful audio range
@@ -19,11 +22,21 @@ pub const SPECTRUM_BINS: usize = 512;
 /// audio thread and spectral.rs agree on the constant.
 pub const FFT_SIZE: usize = 2048;
 
-/// Lock-free spectrum data shared between the audio thread (writer)
-/// and the GUI thread (reader).
+/// Floor shown before the analyzer has published anything.
+const DEFAULT_FLOOR_DB: f32 = -90.0;
+
+/// Lock-free spectrum data shared between the analyzer worker on the
+/// background thread (writer) and the GUI thread (reader).
 pub struct SpectrumData {
-    /// Magnitude bins stored as f32 bits for lock-free access.
+    /// Per-bin levels in dBFS, stored as f32 bits for lock-free access.
+    /// Mono sum, or the left channel when split.
     bins: Vec<AtomicU32>,
+    /// Right-channel levels; only meaningful while `split` is set.
+    right_bins: Vec<AtomicU32>,
+    /// Analyzer is splitting channels rather than summing them.
+    split: AtomicBool,
+    /// Analyzer floor the levels are clamped to; the display's bottom edge.
+    floor_db: AtomicU32,
     /// Audio thread sets this after writing; GUI clears it after reading.
     dirty: AtomicBool,
 }
@@ -31,12 +44,19 @@ pub struct SpectrumData {
 impl SpectrumData {
     pub fn new() -> Self {
         Self {
-            bins: (0..SPECTRUM_BINS).map(|_| AtomicU32::new(0)).collect(),
+            bins: (0..SPECTRUM_BINS)
+                .map(|_| AtomicU32::new(DEFAULT_FLOOR_DB.to_bits()))
+                .collect(),
+            right_bins: (0..SPECTRUM_BINS)
+                .map(|_| AtomicU32::new(DEFAULT_FLOOR_DB.to_bits()))
+                .collect(),
+            split: AtomicBool::new(false),
+            floor_db: AtomicU32::new(DEFAULT_FLOOR_DB.to_bits()),
             dirty: AtomicBool::new(false),
         }
     }
 
-    /// **Audio thread only.** Publish a slice of magnitude values.
+    /// **Writer only.** Publish a slice of levels.
     /// Length is silently clamped to SPECTRUM_BINS.
     pub fn write_from_slice(&self, magnitudes: &[f32]) {
         let len = magnitudes.len().min(SPECTRUM_BINS);
@@ -48,10 +68,18 @@ impl SpectrumData {
         self.dirty.store(true, Ordering::Release);
     }
 
-    /// **GUI thread only.** Copy magnitude values into `out` if new data
+    /// **Writer only.** Stage right-channel levels. Call before
+    /// `write_from_slice` so its Release store publishes both channels.
+    pub fn write_right_from_slice(&self, magnitudes: &[f32]) {
+        for (bin, &mag) in self.right_bins.iter().zip(magnitudes) {
+            bin.store(mag.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// **GUI thread only.** Copy levels into `out` if new data
     /// is available. Returns `false` when no update was pending.
     pub fn read_into_slice(&self, out: &mut [f32]) -> bool {
-        // Acquire fence: makes all bin stores from the audio thread visible.
+        // Acquire fence: makes all bin stores from the writer visible.
         if !self.dirty.swap(false, Ordering::Acquire) {
             return false;
         }
@@ -61,6 +89,39 @@ impl SpectrumData {
         }
         true
     }
+
+    /// **GUI thread only.** Copy the current levels whether or not they
+    /// are new, leaving the dirty flag to the canvas that draws them.
+    pub fn peek_into_slice(&self, out: &mut [f32]) {
+        for (out_bin, bin) in out.iter_mut().zip(&self.bins) {
+            *out_bin = f32::from_bits(bin.load(Ordering::Acquire));
+        }
+    }
+
+    /// **GUI thread only.** Copy the right-channel levels. Call after a
+    /// `read_into_slice` that returned true so both sides match.
+    pub fn read_right_into_slice(&self, out: &mut [f32]) {
+        for (out_bin, bin) in out.iter_mut().zip(&self.right_bins) {
+            *out_bin = f32::from_bits(bin.load(Ordering::Relaxed));
+        }
+    }
+
+    pub fn set_split(&self, split: bool) {
+        self.split.store(split, Ordering::Relaxed);
+    }
+
+    /// True when the published levels are left/right rather than a sum.
+    pub fn is_split(&self) -> bool {
+        self.split.load(Ordering::Relaxed)
+    }
+
+    pub fn set_floor_db(&self, floor_db: f32) {
+        self.floor_db.store(floor_db.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn floor_db(&self) -> f32 {
+        f32::from_bits(self.floor_db.load(Ordering::Relaxed))
+    }
 }
 
 impl Default for SpectrumData {
@@ -69,11 +130,183 @@ impl Default for SpectrumData {
     }
 }
 
+// ── SpectrumSnapshots ─────────────────────────────────────────────────────────
+//
+// Frozen analyzer curves drawn behind the live spectrum, for comparing a
+// before and an after of an EQ move. A freeze copies the averaged levels as
+// published (the mono sum, or the left channel when split). GUI-only: the
+// editor captures and draws them and they persist with the session, so the
+// audio thread never sees them.
+
+/// Frozen curves kept at once.
+pub const SPECTRUM_SNAPSHOTS: usize = 2;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpectrumSnapshots {
+    /// Levels per bin in dBFS, `SPECTRUM_BINS` long; `None` when empty.
+    slots: [Option<Vec<f32>>; SPECTRUM_SNAPSHOTS],
+}
+
+impl SpectrumSnapshots {
+    pub fn get(&self, slot: usize) -> Option<&[f32]> {
+        self.slots.get(slot)?.as_deref()
+    }
+
+    /// Freeze `data`'s current levels into `slot`.
+    pub fn capture(&mut self, slot: usize, data: &SpectrumData) {
+        if let Some(held) = self.slots.get_mut(slot) {
+            let mut bins = vec![data.floor_db(); SPECTRUM_BINS];
+            data.peek_into_slice(&mut bins);
+            *held = Some(bins);
+        }
+    }
+
+    pub fn clear(&mut self, slot: usize) {
+        if let Some(held) = self.slots.get_mut(slot) {
+            *held = None;
+        }
+    }
+
+    /// Bit N set while slot N holds a curve; the editor's button lens.
+    pub fn held_mask(&self) -> u8 {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, held)| held.is_some())
+            .fold(0, |mask, (slot, _)| mask | 1 << slot)
+    }
+}
+
+// ── AnalyzerRing ──────────────────────────────────────────────────────────────
+//
+// Audio → background sample hand-off for the analyzer. The audio thread is
+// the only writer: it stores a sample, then advances `written` with Release.
+// The worker reads everything up to `written` (Acquire). The ring is several
+// frames deep; if the worker falls further behind than that it skips ahead
+// rather than reading slots the writer is reusing.
+
+/// Samples per channel in `AnalyzerRing`.
+pub const ANALYZER_RING_SIZE: usize = 8 * FFT_SIZE;
+
+pub struct AnalyzerRing {
+    /// Mono sum, or the left channel when split.
+    first: Vec<AtomicU32>,
+    /// Right channel; only written while split.
+    second: Vec<AtomicU32>,
+    /// Total samples pushed (not wrapped).
+    written: AtomicUsize,
+    split: AtomicBool,
+    /// Mono sidechain for the masking analysis, with its own count.
+    sidechain: Vec<AtomicU32>,
+    sidechain_written: AtomicUsize,
+    sample_rate: AtomicU32,
+    /// Audio asks the worker to drop its history (transport reset).
+    reset: AtomicBool,
+    /// An analysis task is queued or running.
+    queued: AtomicBool,
+}
+
+impl AnalyzerRing {
+    pub fn new() -> Self {
+        let slots = || (0..ANALYZER_RING_SIZE).map(|_| AtomicU32::new(0)).collect();
+        Self {
+            first: slots(),
+            second: slots(),
+            written: AtomicUsize::new(0),
+            split: AtomicBool::new(false),
+            sidechain: slots(),
+            sidechain_written: AtomicUsize::new(0),
+            sample_rate: AtomicU32::new(44100.0_f32.to_bits()),
+            reset: AtomicBool::new(false),
+            queued: AtomicBool::new(false),
+        }
+    }
+
+    /// **Audio thread only.**
+    pub fn push(&self, first: f32, second: f32) {
+        let n = self.written.load(Ordering::Relaxed);
+        let i = n % ANALYZER_RING_SIZE;
+        self.first[i].store(first.to_bits(), Ordering::Relaxed);
+        self.second[i].store(second.to_bits(), Ordering::Relaxed);
+        self.written.store(n + 1, Ordering::Release);
+    }
+
+    /// **Audio thread only.**
+    pub fn push_sidechain(&self, sample: f32) {
+        let n = self.sidechain_written.load(Ordering::Relaxed);
+        self.sidechain[n % ANALYZER_RING_SIZE].store(sample.to_bits(), Ordering::Relaxed);
+        self.sidechain_written.store(n + 1, Ordering::Release);
+    }
+
+    pub fn written(&self) -> usize {
+        self.written.load(Ordering::Acquire)
+    }
+
+    /// Sample `index` (a `written` count, not a slot) of both channels.
+    pub fn sample(&self, index: usize) -> (f32, f32) {
+        let i = index % ANALYZER_RING_SIZE;
+        (
+            f32::from_bits(self.first[i].load(Ordering::Relaxed)),
+            f32::from_bits(self.second[i].load(Ordering::Relaxed)),
+        )
+    }
+
+    pub fn sidechain_written(&self) -> usize {
+        self.sidechain_written.load(Ordering::Acquire)
+    }
+
+    pub fn sidechain_sample(&self, index: usize) -> f32 {
+        f32::from_bits(self.sidechain[index % ANALYZER_RING_SIZE].load(Ordering::Relaxed))
+    }
+
+    pub fn set_split(&self, split: bool) {
+        self.split.store(split, Ordering::Relaxed);
+    }
+
+    pub fn is_split(&self) -> bool {
+        self.split.load(Ordering::Relaxed)
+    }
+
+    pub fn set_sample_rate(&self, sample_rate: f32) {
+        self.sample_rate
+            .store(sample_rate.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        f32::from_bits(self.sample_rate.load(Ordering::Relaxed))
+    }
+
+    pub fn request_reset(&self) {
+        self.reset.store(true, Ordering::Relaxed);
+    }
+
+    pub fn take_reset(&self) -> bool {
+        self.reset.swap(false, Ordering::Relaxed)
+    }
+
+    /// **Audio thread.** True if the caller should queue an analysis task:
+    /// at most one is in flight.
+    pub fn try_queue(&self) -> bool {
+        !self.queued.swap(true, Ordering::AcqRel)
+    }
+
+    /// **Worker.** Called when a drain finishes so the next one can queue.
+    pub fn finish_drain(&self) {
+        self.queued.store(false, Ordering::Release);
+    }
+}
+
+impl Default for AnalyzerRing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ── AnalysisResult ────────────────────────────────────────────────────────────
 //
 // Lock-free result of the one-shot sidechain masking analysis.
-// Written exclusively by the audio thread; read exclusively by the GUI thread.
-// Protocol: audio thread writes all fields with Relaxed ordering, then stores
+// Written exclusively by the analyzer worker; read exclusively by the GUI
+// thread. Protocol: the worker writes all fields with Relaxed ordering, then stores
 // `ready = true` with Release ordering. GUI reads `ready` with Acquire ordering
 // before reading the other fields, establishing the happens-before relationship.
 
@@ -142,6 +375,877 @@ impl Default for GainReductionData {
     }
 }
 
+// ── OutputLevelData ───────────────────────────────────────────────────────────
+//
+// Slow-smoothed output RMS (post master gain), written once per buffer by the
+// audio thread. The A/B "match gain" action compares the level recorded for
+// each snapshot, so it wants a steady average rather than a peak meter.
+
+/// Floor reported for silence, so dB math never sees -inf.
+pub const OUTPUT_LEVEL_FLOOR_DB: f32 = -120.0;
+
+/// Lock-free smoothed output level (dBFS RMS) shared with the GUI thread.
+pub struct OutputLevelData {
+    rms_db: Meter,
+}
+
+impl OutputLevelData {
+    pub fn new() -> Self {
+        Self {
+            rms_db: Meter::new(OUTPUT_LEVEL_FLOOR_DB),
+        }
+    }
+
+    pub fn store_db(&self, db: f32) {
+        self.rms_db.store(db.max(OUTPUT_LEVEL_FLOOR_DB));
+    }
+
+    pub fn load_db(&self) -> f32 {
+        self.rms_db.load()
+    }
+}
+
+impl Default for OutputLevelData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── SaturationMeterData ───────────────────────────────────────────────────────
+//
+// Transformer drive feedback: how much the saturators are reshaping the
+// signal, already smoothed with meter ballistics by the module. Written once
+// per buffer; 0.0 while the module is bypassed. The Punch clipper publishes
+// its gain-reduction fraction through the same type.
+
+/// Lock-free saturation activity (linear ratio, 0..=1) shared with the GUI.
+pub struct SaturationMeterData {
+    activity: Meter,
+}
+
+impl SaturationMeterData {
+    pub fn new() -> Self {
+        Self {
+            activity: Meter::new(0.0),
+        }
+    }
+
+    pub fn store(&self, activity: f32) {
+        self.activity.store(activity.clamp(0.0, 1.0));
+    }
+
+    pub fn load(&self) -> f32 {
+        self.activity.load()
+    }
+}
+
+impl Default for SaturationMeterData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── StageLevelData ────────────────────────────────────────────────────────────
+//
+// Per-stage RMS and sample peak for the signal-flow strip, published at
+// the meter rate. Index 0 is the strip input, index N + 1 the level leaving
+// slot N (empty or duplicate slots repeat the level before them) and the
+// last index the final output after Sheen and master gain. The RMS is the
+// last buffer's; the peak is the highest since the previous publish, so no
+// overs slip between meter frames. Both are raw: the editor applies scale
+// and ballistics, see meter_scale.rs. Alongside them, each stage's mid and
+// side RMS ((L + R) / 2 and (L − R) / 2), so width added or taken away by a
+// stage shows as a change in the side level against the mid.
+
+/// Input + seven slots + output.
+pub const STAGE_LEVELS: usize = 9;
+
+/// Lock-free per-stage RMS and peak (dBFS) shared with the GUI thread.
+pub struct StageLevelData {
+    levels_db: [AtomicU32; STAGE_LEVELS],
+    peaks_db: [AtomicU32; STAGE_LEVELS],
+    mid_db: [AtomicU32; STAGE_LEVELS],
+    side_db: [AtomicU32; STAGE_LEVELS],
+}
+
+impl StageLevelData {
+    pub fn new() -> Self {
+        Self {
+            levels_db: std::array::from_fn(|_| AtomicU32::new(OUTPUT_LEVEL_FLOOR_DB.to_bits())),
+            peaks_db: std::array::from_fn(|_| AtomicU32::new(OUTPUT_LEVEL_FLOOR_DB.to_bits())),
+            mid_db: std::array::from_fn(|_| AtomicU32::new(OUTPUT_LEVEL_FLOOR_DB.to_bits())),
+            side_db: std::array::from_fn(|_| AtomicU32::new(OUTPUT_LEVEL_FLOOR_DB.to_bits())),
+        }
+    }
+
+    pub fn store_db(&self, stage: usize, db: f32) {
+        Self::store(&self.levels_db, stage, db);
+    }
+
+    pub fn load_db(&self, stage: usize) -> f32 {
+        Self::load(&self.levels_db, stage)
+    }
+
+    pub fn store_peak_db(&self, stage: usize, db: f32) {
+        Self::store(&self.peaks_db, stage, db);
+    }
+
+    pub fn load_peak_db(&self, stage: usize) -> f32 {
+        Self::load(&self.peaks_db, stage)
+    }
+
+    pub fn store_mid_side_db(&self, stage: usize, mid_db: f32, side_db: f32) {
+        Self::store(&self.mid_db, stage, mid_db);
+        Self::store(&self.side_db, stage, side_db);
+    }
+
+    /// `(mid, side)` RMS of a stage in dBFS.
+    pub fn load_mid_side_db(&self, stage: usize) -> (f32, f32) {
+        (
+            Self::load(&self.mid_db, stage),
+            Self::load(&self.side_db, stage),
+        )
+    }
+
+    fn store(slots: &[AtomicU32; STAGE_LEVELS], stage: usize, db: f32) {
+        if let Some(level) = slots.get(stage) {
+            level.store(db.max(OUTPUT_LEVEL_FLOOR_DB).to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    fn load(slots: &[AtomicU32; STAGE_LEVELS], stage: usize) -> f32 {
+        slots.get(stage).map_or(OUTPUT_LEVEL_FLOOR_DB, |level| {
+            f32::from_bits(level.load(Ordering::Relaxed))
+        })
+    }
+}
+
+impl Default for StageLevelData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── LoudnessData ──────────────────────────────────────────────────────────────
+//
+// BS.1770 loudness for the metering bridge, written once per buffer by the
+// audio thread from `loudness::LoudnessMeter`. The bridge's reset button
+// raises `reset_requested`; the audio thread consumes it at the top of the
+// next buffer so the meter itself is only ever touched from one thread.
+
+/// Lock-free momentary / short-term / integrated loudness (LUFS).
+pub struct LoudnessData {
+    momentary: AtomicU32,
+    short_term: AtomicU32,
+    integrated: AtomicU32,
+    reset_requested: AtomicBool,
+}
+
+impl LoudnessData {
+    pub fn new() -> Self {
+        let floor = crate::loudness::LOUDNESS_FLOOR_LUFS.to_bits();
+        Self {
+            momentary: AtomicU32::new(floor),
+            short_term: AtomicU32::new(floor),
+            integrated: AtomicU32::new(floor),
+            reset_requested: AtomicBool::new(false),
+        }
+    }
+
+    pub fn store(&self, momentary: f32, short_term: f32, integrated: f32) {
+        self.momentary.store(momentary.to_bits(), Ordering::Relaxed);
+        self.short_term
+            .store(short_term.to_bits(), Ordering::Relaxed);
+        self.integrated
+            .store(integrated.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn momentary(&self) -> f32 {
+        f32::from_bits(self.momentary.load(Ordering::Relaxed))
+    }
+
+    pub fn short_term(&self) -> f32 {
+        f32::from_bits(self.short_term.load(Ordering::Relaxed))
+    }
+
+    pub fn integrated(&self) -> f32 {
+        f32::from_bits(self.integrated.load(Ordering::Relaxed))
+    }
+
+    /// GUI side: ask the audio thread to restart integrated loudness.
+    pub fn request_reset(&self) {
+        self.reset_requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Audio side: true once per `request_reset`.
+    pub fn take_reset_request(&self) -> bool {
+        self.reset_requested.swap(false, Ordering::Relaxed)
+    }
+}
+
+impl Default for LoudnessData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── DynamicsData ──────────────────────────────────────────────────────────────
+//
+// Crest factor / PSR / PLR / spread from `dynamics::DynamicsMeter`, written
+// once per buffer next to `LoudnessData`. NaN marks "no reading" (silence,
+// or loudness still at its floor).
+
+/// Readouts in the order they are indexed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DynamicsStat {
+    /// Crest factor of the strip input.
+    InputCrest,
+    /// Crest factor of the final output.
+    OutputCrest,
+    Psr,
+    Plr,
+    Spread,
+}
+
+pub const DYNAMICS_STATS: usize = 5;
+
+/// Lock-free dynamics statistics (dB) shared with the GUI thread.
+pub struct DynamicsData {
+    values: [AtomicU32; DYNAMICS_STATS],
+}
+
+impl DynamicsData {
+    pub fn new() -> Self {
+        Self {
+            values: std::array::from_fn(|_| AtomicU32::new(f32::NAN.to_bits())),
+        }
+    }
+
+    pub fn store(&self, stat: DynamicsStat, db: Option<f32>) {
+        self.values[stat as usize].store(db.unwrap_or(f32::NAN).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn load(&self, stat: DynamicsStat) -> Option<f32> {
+        let db = f32::from_bits(self.values[stat as usize].load(Ordering::Relaxed));
+        (!db.is_nan()).then_some(db)
+    }
+}
+
+impl Default for DynamicsData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── EngagementData ────────────────────────────────────────────────────────────
+//
+// How often each nonlinear stage bends the signal (see engagement.rs), as a
+// smoothed 0..=1 share of samples. Written after the module runs; bypassed
+// stages decay toward zero, stages out of the rack keep their last value.
+
+/// Nonlinear stages with an engagement readout, in display order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EngagementStage {
+    PultecTube,
+    Transformer,
+    PunchClip,
+}
+
+pub const ENGAGEMENT_STAGES: usize = 3;
+
+/// Lock-free per-stage engagement shares shared with the GUI thread.
+pub struct EngagementData {
+    shares: [Meter; ENGAGEMENT_STAGES],
+}
+
+impl EngagementData {
+    pub fn new() -> Self {
+        Self {
+            shares: std::array::from_fn(|_| Meter::new(0.0)),
+        }
+    }
+
+    pub fn store(&self, stage: EngagementStage, share: f32) {
+        self.shares[stage as usize].store(share.clamp(0.0, 1.0));
+    }
+
+    pub fn load(&self, stage: EngagementStage) -> f32 {
+        self.shares[stage as usize].load()
+    }
+
+    pub fn clear(&self) {
+        for share in &self.shares {
+            share.store(0.0);
+        }
+    }
+}
+
+impl Default for EngagementData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── HarmonicData ──────────────────────────────────────────────────────────────
+//
+// The transformer's probed THD and even-harmonic share (see harmonics.rs).
+// The audio thread queues a probe when the settings change; the background
+// worker publishes the result and bumps `generation`, which the GUI polls.
+
+/// Lock-free transformer harmonic readout shared with the GUI thread.
+pub struct HarmonicData {
+    thd: Meter,
+    even_share: Meter,
+    /// Advanced after each publish (Release).
+    generation: AtomicU32,
+    /// A probe task is queued or running.
+    queued: AtomicBool,
+}
+
+impl HarmonicData {
+    pub fn new() -> Self {
+        Self {
+            thd: Meter::new(0.0),
+            even_share: Meter::new(0.0),
+            generation: AtomicU32::new(0),
+            queued: AtomicBool::new(false),
+        }
+    }
+
+    /// **Audio thread.** True if the caller should queue a probe: at most
+    /// one is in flight.
+    pub fn try_queue(&self) -> bool {
+        !self.queued.swap(true, Ordering::AcqRel)
+    }
+
+    /// **Worker.** Called once the settings are read, so a change made
+    /// while the probe runs queues the next one.
+    pub fn finish_read(&self) {
+        self.queued.store(false, Ordering::Release);
+    }
+
+    /// **Worker.**
+    pub fn publish(&self, thd: f32, even_share: f32) {
+        self.thd.store(thd.max(0.0));
+        self.even_share.store(even_share.clamp(0.0, 1.0));
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// (THD, even share) as last published.
+    pub fn load(&self) -> (f32, f32) {
+        (self.thd.load(), self.even_share.load())
+    }
+}
+
+impl Default for HarmonicData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── StereoScopeData ───────────────────────────────────────────────────────────
+//
+// Master-section stereo tap: a ring of decimated L/R points for the
+// goniometer plus a smoothed correlation coefficient for the mono-
+// compatibility meter. The audio thread pushes points and overwrites the
+// oldest; the GUI reads the whole ring oldest → newest each frame. A point
+// torn between its L and R writes is harmless for a scope trace.
+
+/// Number of L/R points kept for the goniometer trace.
+pub const SCOPE_POINTS: usize = 512;
+
+/// Lock-free stereo scope data shared with the GUI thread.
+pub struct StereoScopeData {
+    left: Vec<AtomicU32>,
+    right: Vec<AtomicU32>,
+    /// Index the next point will be written to.
+    write_pos: AtomicUsize,
+    /// Smoothed L/R correlation, -1 (out of phase) ..= +1 (mono).
+    correlation: AtomicU32,
+}
+
+impl StereoScopeData {
+    pub fn new() -> Self {
+        Self {
+            left: (0..SCOPE_POINTS).map(|_| AtomicU32::new(0)).collect(),
+            right: (0..SCOPE_POINTS).map(|_| AtomicU32::new(0)).collect(),
+            write_pos: AtomicUsize::new(0),
+            correlation: AtomicU32::new(1.0_f32.to_bits()),
+        }
+    }
+
+    pub fn push_point(&self, left: f32, right: f32) {
+        let pos = self.write_pos.load(Ordering::Relaxed);
+        self.left[pos].store(left.to_bits(), Ordering::Relaxed);
+        self.right[pos].store(right.to_bits(), Ordering::Relaxed);
+        self.write_pos
+            .store((pos + 1) % SCOPE_POINTS, Ordering::Relaxed);
+    }
+
+    /// Copy the ring into `out`, oldest point first. `out` must hold
+    /// `SCOPE_POINTS` entries.
+    pub fn read_points(&self, out: &mut [(f32, f32)]) {
+        let start = self.write_pos.load(Ordering::Relaxed);
+        for (i, point) in out.iter_mut().enumerate().take(SCOPE_POINTS) {
+            let idx = (start + i) % SCOPE_POINTS;
+            *point = (
+                f32::from_bits(self.left[idx].load(Ordering::Relaxed)),
+                f32::from_bits(self.right[idx].load(Ordering::Relaxed)),
+            );
+        }
+    }
+
+    pub fn store_correlation(&self, correlation: f32) {
+        self.correlation
+            .store(correlation.clamp(-1.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn load_correlation(&self) -> f32 {
+        f32::from_bits(self.correlation.load(Ordering::Relaxed))
+    }
+}
+
+impl Default for StereoScopeData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── GrHistoryData ─────────────────────────────────────────────────────────────
+//
+// Scrolling gain-reduction history for the compressor and DynEQ. The audio
+// thread folds each buffer into fixed-length time columns (peak GR within the
+// column, so short clamps stay visible) and pushes one column per
+// GR_HISTORY_COLUMN_S onto an SPSC ring. The GUI drains the ring into its own
+// columns and reads them oldest → newest.
+
+/// Number of columns kept — GR_HISTORY_LEN × GR_HISTORY_COLUMN_S = 10 s.
+pub const GR_HISTORY_LEN: usize = 200;
+
+/// Duration of one history column in seconds.
+pub const GR_HISTORY_COLUMN_S: f32 = 0.05;
+
+/// One item on the GR history ring.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum GrHistoryItem {
+    /// Zero every column (transport reset / re-initialise).
+    #[default]
+    Clear,
+    /// A finished column: peak GR in dB, positive = attenuation.
+    Column { comp_db: f32, dyneq_db: f32 },
+}
+
+/// Audio-thread half of the GR history. The ring holds a full screen, so
+/// while the editor is closed it fills with the last 10 s and then drops
+/// new columns until the editor drains it again.
+pub type GrHistoryProducer = RingProducer<GrHistoryItem, GR_HISTORY_LEN>;
+
+/// Build the audio thread's producer and the GUI's history. Allocates; call
+/// on the main thread.
+pub fn gr_history_channel() -> (GrHistoryProducer, GrHistoryData) {
+    let (producer, ring) = spsc_ring();
+    let history = GrHistoryData {
+        ring,
+        columns: vec![(0.0, 0.0); GR_HISTORY_LEN],
+        write_pos: 0,
+    };
+    (producer, history)
+}
+
+/// GUI side of the GR history, `(comp_db, dyneq_db)` per column. Shared by
+/// the editor's graphs and bars behind a Mutex the audio thread never
+/// touches.
+pub struct GrHistoryData {
+    ring: RingConsumer<GrHistoryItem, GR_HISTORY_LEN>,
+    columns: Vec<(f32, f32)>,
+    /// Index the next column will be written to.
+    write_pos: usize,
+}
+
+impl GrHistoryData {
+    /// Take in everything the audio thread has pushed since the last call.
+    pub fn update(&mut self) {
+        let Self {
+            ring,
+            columns,
+            write_pos,
+        } = self;
+        for item in ring.drain() {
+            match item {
+                GrHistoryItem::Clear => {
+                    columns.fill((0.0, 0.0));
+                    *write_pos = 0;
+                }
+                GrHistoryItem::Column { comp_db, dyneq_db } => {
+                    columns[*write_pos] = (comp_db.max(0.0), dyneq_db.max(0.0));
+                    *write_pos = (*write_pos + 1) % GR_HISTORY_LEN;
+                }
+            }
+        }
+    }
+
+    /// Copy the columns into `out`, oldest first. `out` must hold
+    /// `GR_HISTORY_LEN` entries.
+    pub fn read_columns(&self, out: &mut [(f32, f32)]) {
+        for (i, column) in out.iter_mut().enumerate().take(GR_HISTORY_LEN) {
+            *column = self.columns[(self.write_pos + i) % GR_HISTORY_LEN];
+        }
+    }
+
+    /// The most recent column.
+    pub fn latest(&self) -> (f32, f32) {
+        self.columns[(self.write_pos + GR_HISTORY_LEN - 1) % GR_HISTORY_LEN]
+    }
+}
+
+/// Audio-thread side of the GR history: accumulates per-buffer GR readings
+/// into columns of `GR_HISTORY_COLUMN_S` and publishes each finished column.
+/// No allocation — safe to call from `process()`.
+pub struct GrHistoryAccumulator {
+    column_len: usize,
+    filled: usize,
+    comp_peak: f32,
+    dyneq_peak: f32,
+}
+
+impl GrHistoryAccumulator {
+    pub fn new(sample_rate: f32) -> Self {
+        let mut acc = Self {
+            column_len: 1,
+            filled: 0,
+            comp_peak: 0.0,
+            dyneq_peak: 0.0,
+        };
+        acc.set_sample_rate(sample_rate);
+        acc
+    }
+
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.column_len = ((sample_rate * GR_HISTORY_COLUMN_S) as usize).max(1);
+        self.reset();
+    }
+
+    pub fn reset(&mut self) {
+        self.filled = 0;
+        self.comp_peak = 0.0;
+        self.dyneq_peak = 0.0;
+    }
+
+    /// Fold one buffer's GR readings in; pushes a column to `history` each
+    /// time `column_len` samples have been covered.
+    pub fn add_block(
+        &mut self,
+        comp_db: f32,
+        dyneq_db: f32,
+        samples: usize,
+        history: &mut GrHistoryProducer,
+    ) {
+        self.comp_peak = self.comp_peak.max(comp_db);
+        self.dyneq_peak = self.dyneq_peak.max(dyneq_db);
+        self.filled += samples;
+        while self.filled >= self.column_len {
+            history.push(GrHistoryItem::Column {
+                comp_db: self.comp_peak,
+                dyneq_db: self.dyneq_peak,
+            });
+            self.filled -= self.column_len;
+            // A buffer longer than a column repeats its reading; otherwise
+            // the next column starts from the current buffer's level.
+            self.comp_peak = comp_db;
+            self.dyneq_peak = dyneq_db;
+        }
+        if self.filled == 0 {
+            self.comp_peak = 0.0;
+            self.dyneq_peak = 0.0;
+        }
+    }
+}
+
+// ── MeterClock ────────────────────────────────────────────────────────────────
+//
+// The editor's refresh rate, applied on the audio side: level, loudness,
+// dynamics and correlation stores and analyzer hand-offs happen only on
+// buffers where the clock fires, so a heavier editor never means more work
+// per buffer. The measurements themselves (loudness gating, smoothing,
+// crest tracking) keep running every buffer; only publishing is decimated.
+
+/// Counts samples between meter publishes.
+pub struct MeterClock {
+    elapsed: usize,
+}
+
+impl MeterClock {
+    pub fn new() -> Self {
+        Self { elapsed: 0 }
+    }
+
+    /// Advance by one `samples`-long buffer; true when this buffer should
+    /// publish. A buffer longer than the interval publishes once.
+    pub fn tick(&mut self, samples: usize, sample_rate: f32, rate_hz: u32) -> bool {
+        let interval = ((sample_rate / rate_hz.max(1) as f32).round() as usize).max(1);
+        self.elapsed += samples;
+        if self.elapsed < interval {
+            return false;
+        }
+        self.elapsed %= interval;
+        true
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0;
+    }
+}
+
+impl Default for MeterClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── MatchData ─────────────────────────────────────────────────────────────────
+//
+// Spectrum-match EQ: long-term third-octave levels of the program (chain
+// input) and a reference. The GUI raises `capture_requested`; the audio
+// thread runs `matching::SpectrumCapture` for a few seconds, then publishes
+// both curves and sets `capture_done` with Release ordering. A reference
+// decoded from a WAV file on the background thread replaces the sidechain
+// curve until the GUI clears it.
+
+/// Third-octave bands, 20 Hz … 20 kHz (see `matching::MATCH_CENTERS_HZ`).
+pub const MATCH_BANDS: usize = 31;
+
+/// Lock-free program / reference levels for spectrum matching, in dB.
+/// Bands above the capture's Nyquist hold NaN.
+pub struct MatchData {
+    program: Vec<AtomicU32>,
+    reference: Vec<AtomicU32>,
+    capture_requested: AtomicBool,
+    capture_done: AtomicBool,
+    /// True while `reference` holds a WAV file's curve rather than the sidechain's.
+    file_reference: AtomicBool,
+    /// Bumped by the background thread after every WAV load attempt.
+    file_generation: AtomicU32,
+    /// 0 after a successful load, otherwise a `matching::ReferenceError` code.
+    file_status: AtomicU32,
+}
+
+impl MatchData {
+    pub fn new() -> Self {
+        Self {
+            program: (0..MATCH_BANDS).map(|_| AtomicU32::new(0)).collect(),
+            reference: (0..MATCH_BANDS).map(|_| AtomicU32::new(0)).collect(),
+            capture_requested: AtomicBool::new(false),
+            capture_done: AtomicBool::new(false),
+            file_reference: AtomicBool::new(false),
+            file_generation: AtomicU32::new(0),
+            file_status: AtomicU32::new(0),
+        }
+    }
+
+    /// GUI side: ask the audio thread to start a capture.
+    pub fn request_capture(&self) {
+        self.capture_done.store(false, Ordering::Relaxed);
+        self.capture_requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Audio side: true once per `request_capture`.
+    pub fn take_capture_request(&self) -> bool {
+        self.capture_requested.swap(false, Ordering::Relaxed)
+    }
+
+    /// Audio side: publish a finished capture. The sidechain curve only
+    /// becomes the reference when no WAV reference is loaded.
+    pub fn publish_capture(&self, program: &[f32; MATCH_BANDS], sidechain: &[f32; MATCH_BANDS]) {
+        store_levels(&self.program, program);
+        if !self.file_reference.load(Ordering::Acquire) {
+            store_levels(&self.reference, sidechain);
+        }
+        self.capture_done.store(true, Ordering::Release);
+    }
+
+    /// GUI side: true once per published capture.
+    pub fn take_capture_done(&self) -> bool {
+        self.capture_done.swap(false, Ordering::Acquire)
+    }
+
+    /// Background side: install a WAV reference curve.
+    pub fn store_file_reference(&self, levels: &[f32; MATCH_BANDS]) {
+        store_levels(&self.reference, levels);
+        self.file_reference.store(true, Ordering::Release);
+        self.file_status.store(0, Ordering::Relaxed);
+        self.file_generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Background side: record a failed WAV load. A previously loaded
+    /// reference stays in place.
+    pub fn store_file_error(&self, code: u32) {
+        self.file_status.store(code, Ordering::Relaxed);
+        self.file_generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// GUI side: fall back to the sidechain as the reference.
+    pub fn clear_file_reference(&self) {
+        self.file_reference.store(false, Ordering::Release);
+    }
+
+    pub fn has_file_reference(&self) -> bool {
+        self.file_reference.load(Ordering::Acquire)
+    }
+
+    pub fn file_generation(&self) -> u32 {
+        self.file_generation.load(Ordering::Acquire)
+    }
+
+    pub fn file_status(&self) -> u32 {
+        self.file_status.load(Ordering::Relaxed)
+    }
+
+    pub fn program(&self) -> [f32; MATCH_BANDS] {
+        load_levels(&self.program)
+    }
+
+    pub fn reference(&self) -> [f32; MATCH_BANDS] {
+        load_levels(&self.reference)
+    }
+}
+
+impl Default for MatchData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn store_levels(dst: &[AtomicU32], levels: &[f32; MATCH_BANDS]) {
+    for (slot, level) in dst.iter().zip(levels) {
+        slot.store(level.to_bits(), Ordering::Relaxed);
+    }
+}
+
+fn load_levels(src: &[AtomicU32]) -> [f32; MATCH_BANDS] {
+    let mut out = [0.0; MATCH_BANDS];
+    for (level, slot) in out.iter_mut().zip(src) {
+        *level = f32::from_bits(slot.load(Ordering::Relaxed));
+    }
+    out
+}
+
+// ── ResponseExportData ────────────────────────────────────────────────────────
+//
+// Outcome of the EQ response export (see response.rs). The background
+// worker records each attempt and bumps `generation`; the GUI polls it for
+// the status line.
+
+pub struct ResponseExportData {
+    /// Bumped after every export attempt (Release).
+    generation: AtomicU32,
+    /// The last attempt wrote its file.
+    written: AtomicBool,
+}
+
+impl ResponseExportData {
+    pub fn new() -> Self {
+        Self {
+            generation: AtomicU32::new(0),
+            written: AtomicBool::new(false),
+        }
+    }
+
+    /// **Worker.**
+    pub fn store(&self, written: bool) {
+        self.written.store(written, Ordering::Relaxed);
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    pub fn written(&self) -> bool {
+        self.written.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for ResponseExportData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── LoudnessLearnData ─────────────────────────────────────────────────────────
+//
+// Loudness-target learn pass (see loudness_target.rs). The GUI raises
+// `learn_requested`; the audio thread measures Punch's input and the strip's
+// output for the length of the pass, then publishes the three readings and
+// sets `learn_done` with Release ordering.
+
+/// Lock-free learn-pass handshake and readings (LUFS / dBFS).
+pub struct LoudnessLearnData {
+    input_lufs: AtomicU32,
+    input_peak_db: AtomicU32,
+    output_lufs: AtomicU32,
+    learn_requested: AtomicBool,
+    learn_done: AtomicBool,
+}
+
+impl LoudnessLearnData {
+    pub fn new() -> Self {
+        let floor = crate::loudness::LOUDNESS_FLOOR_LUFS.to_bits();
+        Self {
+            input_lufs: AtomicU32::new(floor),
+            input_peak_db: AtomicU32::new(floor),
+            output_lufs: AtomicU32::new(floor),
+            learn_requested: AtomicBool::new(false),
+            learn_done: AtomicBool::new(false),
+        }
+    }
+
+    /// GUI side: ask the audio thread to start a pass.
+    pub fn request_learn(&self) {
+        self.learn_done.store(false, Ordering::Relaxed);
+        self.learn_requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Audio side: true once per `request_learn`.
+    pub fn take_learn_request(&self) -> bool {
+        self.learn_requested.swap(false, Ordering::Relaxed)
+    }
+
+    /// Audio side: publish a finished pass.
+    pub fn publish(&self, input_lufs: f32, input_peak_db: f32, output_lufs: f32) {
+        self.input_lufs
+            .store(input_lufs.to_bits(), Ordering::Relaxed);
+        self.input_peak_db
+            .store(input_peak_db.to_bits(), Ordering::Relaxed);
+        self.output_lufs
+            .store(output_lufs.to_bits(), Ordering::Relaxed);
+        self.learn_done.store(true, Ordering::Release);
+    }
+
+    /// GUI side: the readings `(input LUFS, input peak dBFS, output LUFS)`
+    /// of a newly published pass, once per pass.
+    pub fn take_learned(&self) -> Option<(f32, f32, f32)> {
+        if !self.learn_done.swap(false, Ordering::Acquire) {
+            return None;
+        }
+        let load = |value: &AtomicU32| f32::from_bits(value.load(Ordering::Relaxed));
+        Some((
+            load(&self.input_lufs),
+            load(&self.input_peak_db),
+            load(&self.output_lufs),
+        ))
+    }
+}
+
+impl Default for LoudnessLearnData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,6 +1321,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_spectrum_data_split_carries_both_channels() {
+        let sd = SpectrumData::new();
+        assert!(!sd.is_split());
+        sd.set_split(true);
+        sd.write_right_from_slice(&vec![-12.0_f32; SPECTRUM_BINS]);
+        sd.write_from_slice(&vec![-6.0_f32; SPECTRUM_BINS]);
+
+        let mut left = vec![0.0_f32; SPECTRUM_BINS];
+        let mut right = vec![0.0_f32; SPECTRUM_BINS];
+        assert!(sd.read_into_slice(&mut left));
+        sd.read_right_into_slice(&mut right);
+        assert!(sd.is_split());
+        assert!(left.iter().all(|&v| v == -6.0));
+        assert!(right.iter().all(|&v| v == -12.0));
+    }
+
+    #[test]
+    fn test_spectrum_snapshots_freeze_without_eating_the_update() {
+        let sd = SpectrumData::new();
+        sd.write_from_slice(&vec![-30.0_f32; SPECTRUM_BINS]);
+        let mut snapshots = SpectrumSnapshots::default();
+        snapshots.capture(1, &sd);
+        snapshots.capture(SPECTRUM_SNAPSHOTS, &sd);
+        assert_eq!(snapshots.held_mask(), 0b10);
+        assert!(snapshots.get(0).is_none());
+        assert!(snapshots.get(1).unwrap().iter().all(|&v| v == -30.0));
+        // The canvas still sees the levels as new.
+        let mut out = vec![0.0_f32; SPECTRUM_BINS];
+        assert!(sd.read_into_slice(&mut out));
+
+        let json = serde_json::to_string(&snapshots).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SpectrumSnapshots>(&json).unwrap(),
+            snapshots
+        );
+        snapshots.clear(1);
+        assert_eq!(snapshots, SpectrumSnapshots::default());
+    }
+
+    #[test]
+    fn test_analyzer_ring_wraps_and_counts() {
+        let ring = AnalyzerRing::new();
+        for n in 0..ANALYZER_RING_SIZE + 10 {
+            ring.push(n as f32, -(n as f32));
+        }
+        assert_eq!(ring.written(), ANALYZER_RING_SIZE + 10);
+        let last = ANALYZER_RING_SIZE + 9;
+        assert_eq!(ring.sample(last), (last as f32, -(last as f32)));
+        assert!(ring.try_queue());
+        assert!(!ring.try_queue(), "only one drain in flight");
+        ring.finish_drain();
+        assert!(ring.try_queue());
+    }
+
     #[test]
     fn test_spectrum_data_zero_slice_write_read() {
         let sd = SpectrumData::new();
@@ -301,6 +1460,18 @@ mod tests {
         );
     }
 
+    // ── OutputLevelData ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_output_level_starts_at_floor_and_clamps() {
+        let level = OutputLevelData::new();
+        assert_eq!(level.load_db(), OUTPUT_LEVEL_FLOOR_DB);
+        level.store_db(-18.0);
+        assert!((level.load_db() + 18.0).abs() < 1e-6);
+        level.store_db(f32::NEG_INFINITY);
+        assert_eq!(level.load_db(), OUTPUT_LEVEL_FLOOR_DB);
+    }
+
     // ── Constants ─────────────────────────────────────────────────────────────
 
     #[test]
@@ -310,4 +1481,209 @@ mod tests {
         // FFT_SIZE >= 2 × SPECTRUM_BINS ensures proper positive-frequency coverage
         assert!(FFT_SIZE >= SPECTRUM_BINS * 2);
     }
+
+    // ── SaturationMeterData ───────────────────────────────────────────────────
+
+    #[test]
+    fn test_saturation_meter_clamps_to_unit_range() {
+        let sat = SaturationMeterData::new();
+        assert_eq!(sat.load(), 0.0);
+        sat.store(0.25);
+        assert_eq!(sat.load(), 0.25);
+        sat.store(3.0);
+        assert_eq!(sat.load(), 1.0);
+        sat.store(-1.0);
+        assert_eq!(sat.load(), 0.0);
+    }
+
+    // ── StageLevelData ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_stage_levels_clamp_and_ignore_out_of_range() {
+        let levels = StageLevelData::new();
+        assert_eq!(levels.load_db(0), OUTPUT_LEVEL_FLOOR_DB);
+        levels.store_db(3, -12.0);
+        levels.store_db(4, f32::NEG_INFINITY);
+        levels.store_db(STAGE_LEVELS, 0.0);
+        assert_eq!(levels.load_db(3), -12.0);
+        assert_eq!(levels.load_db(4), OUTPUT_LEVEL_FLOOR_DB);
+        assert_eq!(levels.load_db(STAGE_LEVELS), OUTPUT_LEVEL_FLOOR_DB);
+        // Peaks are kept apart from the RMS.
+        levels.store_peak_db(3, -3.0);
+        assert_eq!(levels.load_peak_db(3), -3.0);
+        assert_eq!(levels.load_db(3), -12.0);
+        assert_eq!(levels.load_peak_db(STAGE_LEVELS), OUTPUT_LEVEL_FLOOR_DB);
+        // So are mid and side.
+        levels.store_mid_side_db(3, -15.0, f32::NEG_INFINITY);
+        assert_eq!(levels.load_mid_side_db(3), (-15.0, OUTPUT_LEVEL_FLOOR_DB));
+        assert_eq!(levels.load_db(3), -12.0);
+        assert_eq!(
+            levels.load_mid_side_db(STAGE_LEVELS),
+            (OUTPUT_LEVEL_FLOOR_DB, OUTPUT_LEVEL_FLOOR_DB)
+        );
+    }
+
+    // ── LoudnessData ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_loudness_reset_request_is_taken_once() {
+        let loudness = LoudnessData::new();
+        assert_eq!(loudness.integrated(), crate::loudness::LOUDNESS_FLOOR_LUFS);
+        assert!(!loudness.take_reset_request());
+        loudness.request_reset();
+        assert!(loudness.take_reset_request());
+        assert!(!loudness.take_reset_request());
+    }
+
+    // ── DynamicsData ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_dynamics_data_round_trips_missing_readings() {
+        let dynamics = DynamicsData::new();
+        assert_eq!(dynamics.load(DynamicsStat::Psr), None);
+        dynamics.store(DynamicsStat::Psr, Some(8.5));
+        dynamics.store(DynamicsStat::Plr, None);
+        assert_eq!(dynamics.load(DynamicsStat::Psr), Some(8.5));
+        assert_eq!(dynamics.load(DynamicsStat::Plr), None);
+        assert_eq!(dynamics.load(DynamicsStat::Spread), None);
+    }
+
+    // ── EngagementData ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_engagement_data_clamps_and_clears() {
+        let engagement = EngagementData::new();
+        engagement.store(EngagementStage::Transformer, 0.4);
+        engagement.store(EngagementStage::PunchClip, 1.5);
+        assert_eq!(engagement.load(EngagementStage::Transformer), 0.4);
+        assert_eq!(engagement.load(EngagementStage::PunchClip), 1.0);
+        assert_eq!(engagement.load(EngagementStage::PultecTube), 0.0);
+        engagement.clear();
+        assert_eq!(engagement.load(EngagementStage::Transformer), 0.0);
+    }
+
+    // ── HarmonicData ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_harmonic_data_queues_one_probe_and_counts_publishes() {
+        let harmonics = HarmonicData::new();
+        assert!(harmonics.try_queue());
+        assert!(!harmonics.try_queue(), "second probe waits");
+        harmonics.finish_read();
+        assert!(
+            harmonics.try_queue(),
+            "queue reopens once settings are read"
+        );
+
+        let generation = harmonics.generation();
+        harmonics.publish(0.02, 1.4);
+        assert_eq!(harmonics.generation(), generation.wrapping_add(1));
+        assert_eq!(harmonics.load(), (0.02, 1.0));
+    }
+
+    // ── StereoScopeData ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_scope_reads_oldest_first_after_wrap() {
+        let scope = StereoScopeData::new();
+        for i in 0..(SCOPE_POINTS + 3) {
+            scope.push_point(i as f32, -(i as f32));
+        }
+        let mut out = vec![(0.0_f32, 0.0_f32); SCOPE_POINTS];
+        scope.read_points(&mut out);
+        assert_eq!(out[0], (3.0, -3.0));
+        let last = (SCOPE_POINTS + 2) as f32;
+        assert_eq!(out[SCOPE_POINTS - 1], (last, -last));
+    }
+
+    #[test]
+    fn test_scope_correlation_defaults_to_mono_and_clamps() {
+        let scope = StereoScopeData::new();
+        assert_eq!(scope.load_correlation(), 1.0);
+        scope.store_correlation(-2.0);
+        assert_eq!(scope.load_correlation(), -1.0);
+    }
+
+    // ── GrHistoryData ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_gr_history_columns_follow_sample_rate() {
+        let (mut producer, mut history) = gr_history_channel();
+        // 1 kHz → 50 samples per column.
+        let mut acc = GrHistoryAccumulator::new(1000.0);
+        acc.add_block(6.0, 0.0, 30, &mut producer);
+        acc.add_block(2.0, 1.5, 30, &mut producer);
+        history.update();
+        let mut out = vec![(0.0_f32, 0.0_f32); GR_HISTORY_LEN];
+        history.read_columns(&mut out);
+        // One column completed; it holds the peak of both buffers.
+        assert_eq!(out[GR_HISTORY_LEN - 1], (6.0, 1.5));
+        assert_eq!(out[GR_HISTORY_LEN - 2], (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_meter_clock_publishes_at_the_requested_rate() {
+        for (rate, buffer) in [(15, 64), (30, 256), (60, 64), (60, 512)] {
+            let mut clock = MeterClock::new();
+            let buffers = 48_000 / buffer;
+            let published = (0..buffers)
+                .filter(|_| clock.tick(buffer, 48_000.0, rate))
+                .count();
+            assert!(
+                published.abs_diff(rate as usize) <= 1,
+                "{rate} Hz at {buffer}-sample buffers published {published}/s"
+            );
+        }
+        // Buffers longer than the interval publish every time, once.
+        let mut clock = MeterClock::new();
+        assert!((0..10).all(|_| clock.tick(8192, 48_000.0, 60)));
+    }
+
+    #[test]
+    fn test_gr_history_reads_oldest_first_and_clears() {
+        let (mut producer, mut history) = gr_history_channel();
+        let column = |i: usize| GrHistoryItem::Column {
+            comp_db: i as f32,
+            dyneq_db: 0.0,
+        };
+        // Drained halfway, so the ring never fills and nothing is dropped.
+        for i in 0..(GR_HISTORY_LEN + 2) {
+            assert!(producer.push(column(i)));
+            if i == GR_HISTORY_LEN / 2 {
+                history.update();
+            }
+        }
+        history.update();
+        let mut out = vec![(0.0_f32, 0.0_f32); GR_HISTORY_LEN];
+        history.read_columns(&mut out);
+        assert_eq!(out[0].0, 2.0);
+        assert_eq!(out[GR_HISTORY_LEN - 1].0, (GR_HISTORY_LEN + 1) as f32);
+        assert_eq!(history.latest().0, (GR_HISTORY_LEN + 1) as f32);
+        producer.push(GrHistoryItem::Clear);
+        history.update();
+        history.read_columns(&mut out);
+        assert!(out.iter().all(|&(c, d)| c == 0.0 && d == 0.0));
+    }
+
+    // ── MatchData ─────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_match_file_reference_survives_sidechain_capture() {
+        let data = MatchData::new();
+        let program = [-20.0_f32; MATCH_BANDS];
+        let sidechain = [-30.0_f32; MATCH_BANDS];
+        data.publish_capture(&program, &sidechain);
+        assert!(data.take_capture_done());
+        assert!(!data.take_capture_done());
+        assert_eq!(data.reference()[0], -30.0);
+
+        data.store_file_reference(&[-10.0; MATCH_BANDS]);
+        data.publish_capture(&program, &sidechain);
+        assert_eq!(data.reference()[0], -10.0);
+        assert_eq!(data.program()[0], -20.0);
+
+        data.clear_file_reference();
+        data.publish_capture(&program, &sidechain);
+        assert_eq!(data.reference()[0], -30.0);
+    }
 }
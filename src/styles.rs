@@ -1,5 +1,45 @@
 // src/styles.rs
-// CSS styling for reusable components
+// CSS styling for reusable components, plus the skin registry.
+//
+// Skins work like zoom: every skin's sheet is registered once at editor
+// startup, and the active one is picked by a `skin-*` class on the chassis
+// root. `COMPONENT_STYLES` is the Dark skin and the base every other skin
+// overrides; skin sheets only scope overrides under their root class, so an
+// unstyled selector always falls back to Dark.
+
+use crate::EditorSkin;
+
+/// One entry in the skin registry.
+pub struct SkinInfo {
+    pub skin: EditorSkin,
+    /// Short tag for the header selector.
+    pub tag: &'static str,
+    /// Class toggled on the chassis root while this skin is active.
+    pub class: &'static str,
+    /// Overrides scoped under `class`. Empty for the base (Dark) skin.
+    pub stylesheet: &'static str,
+}
+
+pub const SKINS: &[SkinInfo] = &[
+    SkinInfo {
+        skin: EditorSkin::Dark,
+        tag: "DRK",
+        class: "skin-dark",
+        stylesheet: "",
+    },
+    SkinInfo {
+        skin: EditorSkin::Light,
+        tag: "LGT",
+        class: "skin-light",
+        stylesheet: LIGHT_SKIN_STYLES,
+    },
+    SkinInfo {
+        skin: EditorSkin::Classic,
+        tag: "HW",
+        class: "skin-classic",
+        stylesheet: CLASSIC_SKIN_STYLES,
+    },
+];
 
 pub const COMPONENT_STYLES: &str = r#"
 
@@ -331,6 +371,313 @@ pub const COMPONENT_STYLES: &str = r#"
     color: #c8d0dc;
 }
 
+/* ── Param tooltips & readout strip ────────────────────────────────────────
+   Tooltip card shown when hovering any param control, and the footer strip
+   that pins the last touched parameter. */
+.param-tooltip {
+    background-color: #10131a;
+    border: 1px solid #4a5162;
+    border-radius: 4px;
+    padding: 6px 8px;
+    gap: 2px;
+}
+
+.param-tooltip-name {
+    color: #e8e8e8;
+    font-size: 12px;
+    font-weight: bold;
+}
+
+.param-tooltip-value {
+    color: #ffd080;
+    font-size: 12px;
+}
+
+.param-tooltip-default {
+    color: #8a90a0;
+    font-size: 10px;
+}
+
+.param-readout-strip {
+    background-color: rgba(0, 0, 0, 0.35);
+    border-top: 1px solid #2a2e38;
+    padding-left: 10px;
+    alignment: left;
+}
+
+.param-readout-text {
+    color: #b8bcc8;
+    font-size: 11px;
+}
+
+/* ── Signal flow ───────────────────────────────────────────────────────────
+   Right side of the readout strip: module tags in processing order with a
+   level arrow between stages. Bypassed stages dim; global bypass dims all. */
+.signal-flow {
+    padding-right: 10px;
+}
+
+.signal-flow-endpoint {
+    font-size: 9px;
+    font-weight: 700;
+    color: #8c98a8;
+    letter-spacing: 1px;
+}
+
+.signal-flow-chip {
+    font-size: 9px;
+    font-weight: 700;
+    color: #e0e6ee;
+    background-color: rgba(255, 255, 255, 0.06);
+    border: 1px solid #3a4050;
+    border-radius: 3px;
+    padding: 1px 4px;
+    letter-spacing: 0.6px;
+}
+
+.signal-flow-chip:hover {
+    border-color: #8ab4e0;
+}
+
+.signal-flow-chip-bypassed {
+    color: #5a6270;
+}
+
+.signal-flow-bypassed {
+    opacity: 0.4;
+}
+
+/* ── Param context menu ────────────────────────────────────────────────────
+   Right-click menu on any param control. The backdrop is an invisible
+   full-window catcher that dismisses the menu on an outside click. */
+.param-menu-backdrop {
+    background-color: rgba(0, 0, 0, 0.0);
+}
+
+.param-menu {
+    background-color: #10131a;
+    border: 1px solid #4a5162;
+    border-radius: 4px;
+    padding: 4px;
+    gap: 1px;
+}
+
+.param-menu-title {
+    color: #8a90a0;
+    font-size: 10px;
+    font-weight: bold;
+    height: 16px;
+    padding-left: 6px;
+}
+
+.param-menu-item {
+    border-radius: 3px;
+    padding-left: 6px;
+    alignment: left;
+}
+.param-menu-item:hover {
+    background-color: #2c3340;
+}
+.param-menu-item-label {
+    color: #e8e8e8;
+    font-size: 12px;
+}
+.param-menu-item-disabled .param-menu-item-label {
+    color: #5a606a;
+}
+.param-menu-item-disabled:hover {
+    background-color: rgba(0, 0, 0, 0.0);
+}
+
+.param-menu-separator {
+    height: 1px;
+    background-color: #2a2e38;
+}
+
+.param-menu-entry {
+    background-color: #1b1f27;
+    border: 1px solid #c8a04a;
+    color: #ffd080;
+    font-size: 12px;
+}
+
+/* ── Preset browser ────────────────────────────────────────────────────────
+   Header pill + floating panel. The panel is absolutely positioned over the
+   strip, so it needs an opaque fill (same smear issue as .lunchbox-slots)
+   and a bright border to separate it from the modules underneath. */
+
+.preset-btn {
+    background: linear-gradient(180deg, #222730, #1b1f27);
+    border: 1px solid rgba(255, 255, 255, 0.08);
+    border-radius: 4px;
+    padding: 4px 10px;
+    alignment: center;
+}
+.preset-btn:hover {
+    background: linear-gradient(180deg, #2c3340, #242a36);
+    border-color: rgba(180, 200, 255, 0.3);
+}
+.preset-btn-active {
+    border-color: #c8a04a;
+}
+.preset-btn-label {
+    font-size: 11px;
+    font-weight: 700;
+    color: #c0c8d4;
+    letter-spacing: 0.8px;
+    height: 14px;
+    width: Auto;
+}
+
+/* A/B compare block — same button family as the preset pill; the live side
+   gets the brass accent used by the brand plate. */
+.ab-controls {
+    background: linear-gradient(180deg, rgba(20, 24, 32, 0.55), rgba(14, 17, 22, 0.6));
+    border: 1px solid rgba(255, 255, 255, 0.04);
+    border-radius: 4px;
+    padding: 2px;
+}
+.ab-btn {
+    background: linear-gradient(180deg, #222730, #1b1f27);
+    border: 1px solid rgba(255, 255, 255, 0.08);
+    border-radius: 3px;
+    alignment: center;
+}
+.ab-btn:hover {
+    background: linear-gradient(180deg, #2c3340, #242a36);
+    border-color: rgba(180, 200, 255, 0.3);
+}
+.ab-btn-active {
+    background-color: #c8a04a;
+    border-color: #ffd870;
+}
+.ab-btn-label {
+    font-size: 10px;
+    font-weight: 800;
+    color: #c0c8d4;
+    letter-spacing: 0.6px;
+    height: 14px;
+    width: Auto;
+}
+.ab-btn-active .ab-btn-label {
+    color: #1a1004;
+}
+.history-btn-disabled .ab-btn-label {
+    color: #4a5060;
+}
+
+.preset-browser {
+    background-color: #151922;
+    border: 1px solid #4a5162;
+    border-radius: 6px;
+    padding: 10px;
+}
+
+.preset-browser-title {
+    font-size: 13px;
+    font-weight: 800;
+    color: #e0e6ee;
+    letter-spacing: 1.5px;
+    height: 18px;
+}
+
+.preset-action-btn {
+    background-color: #222730;
+    border: 1px solid rgba(255, 255, 255, 0.08);
+    border-radius: 3px;
+    padding: 0px 8px;
+    alignment: center;
+}
+.preset-action-btn:hover {
+    background-color: #2c3340;
+    border-color: rgba(180, 200, 255, 0.3);
+}
+.preset-action-primary {
+    border-color: #c8a04a;
+}
+.preset-action-label {
+    font-size: 10px;
+    font-weight: 700;
+    color: #c0c8d4;
+    letter-spacing: 0.6px;
+    height: 14px;
+    width: Auto;
+}
+
+.preset-textbox {
+    background-color: #0f1319;
+    border: 1px solid #2a2f38;
+    border-radius: 3px;
+    color: #e0e6ee;
+    font-size: 11px;
+    padding: 0px 6px;
+}
+.preset-textbox:focus {
+    border-color: #40a0d0;
+}
+
+.preset-list {
+    background-color: #10141a;
+    border: 1px solid #252a32;
+    border-radius: 4px;
+}
+
+.preset-section-label {
+    font-size: 10px;
+    font-weight: 700;
+    color: #7a8290;
+    letter-spacing: 1.2px;
+    height: 20px;
+    padding: 4px 8px 0px 8px;
+}
+
+.preset-row {
+    padding: 0px 8px;
+    border-radius: 3px;
+}
+.preset-row:hover {
+    background-color: rgba(255, 255, 255, 0.05);
+}
+.preset-row-name {
+    font-size: 11px;
+    font-weight: 600;
+    color: #d8dee8;
+    height: 14px;
+}
+.preset-row-category {
+    font-size: 9px;
+    color: #7a8290;
+    text-transform: uppercase;
+    height: 12px;
+    width: Auto;
+}
+.preset-row-delete {
+    border-radius: 3px;
+    alignment: center;
+}
+.preset-row-delete:hover {
+    background-color: rgba(255, 80, 80, 0.2);
+}
+.preset-row-delete-glyph {
+    font-size: 10px;
+    color: #8a6060;
+    width: Auto;
+    height: 12px;
+}
+
+.preset-empty-hint {
+    font-size: 10px;
+    color: #6a7280;
+    padding: 2px 8px;
+    height: 18px;
+}
+
+.preset-error {
+    font-size: 10px;
+    color: #ff8070;
+    height: 16px;
+}
+
 /* ── Focus mode ────────────────────────────────────────────────────────────
    The clickable module-name target reads as a normal header by default but
    gains a subtle hover glow so users discover the focus affordance. The
@@ -353,6 +700,20 @@ pub const COMPONENT_STYLES: &str = r#"
     border-color: rgba(255, 220, 100, 0.25);
 }
 
+/* TIPS off in the VIEW band: param tooltips never show. */
+.tooltips-off tooltip {
+    display: none;
+}
+
+/* Focus view: the widened slot and its analyzer strip. */
+.slot-focused {
+    box-shadow: 0px 0px 12px rgba(255, 220, 100, 0.18);
+}
+
+.focus-spectrum {
+    border-radius: 3px;
+}
+
 /* EXIT FOCUS pill in the chassis header. Sits beside the brand block while
    focus mode is active; matches the chain-preset button family but in an
    alert tone so it reads as "leave this mode". */
@@ -610,6 +971,14 @@ pub const COMPONENT_STYLES: &str = r#"
     background-color: rgba(0, 0, 0, 0.28);
 }
 
+/* Keyboard focus (Tab): a ring in the frequency accent. Mouse focus shows
+   nothing, so clicking a control doesn't leave it outlined. */
+.param-access:focus-visible {
+    outline-width: 2px;
+    outline-color: #40a0d0;
+    outline-offset: 1px;
+}
+
 /* Parameter labels for 500 series modules */
 .param-label {
     font-size: 12px;
@@ -650,6 +1019,65 @@ pub const COMPONENT_STYLES: &str = r#"
     color: #66cc66;
 }
 
+/* ── Rotary knob ───────────────────────────────────────────────────────────
+   Arc, ring and pointer are drawn in Rust (components::KnobFace); CSS only
+   styles the value readout. Typed entry uses the shared .param-menu-entry. */
+param-knob {
+    background-color: transparent;
+}
+
+.param-knob-value {
+    font-size: 10px;
+    color: #a8b0bc;
+    text-align: center;
+    background-color: transparent;
+}
+
+param-knob:hover .param-knob-value {
+    color: #ffffff;
+}
+
+/* ── Drive meter ───────────────────────────────────────────────────────────
+   LED ladder under the transformer controls. Segments are drawn in Rust;
+   CSS only frames the row. */
+.drive-meter-row {
+    padding: 4px 6px;
+    background-color: rgba(0, 0, 0, 0.18);
+    border-radius: 3px;
+}
+
+/* Read-only measurement beside a param label (transformer THD / EVEN). */
+.readout-value {
+    font-size: 11px;
+    color: #a8b0bc;
+    text-align: left;
+    font-weight: 600;
+}
+
+.gr-history-panel {
+    padding: 4px 6px;
+    background-color: rgba(0, 0, 0, 0.18);
+    border-radius: 3px;
+}
+
+.gr-history {
+    border-radius: 2px;
+}
+
+.gr-history-key-comp,
+.gr-history-key-dyneq {
+    font-size: 9px;
+    font-weight: bold;
+}
+
+.gr-history-key-comp {
+    color: #f0a040;
+}
+
+.gr-history-key-dyneq {
+    color: #60d060;
+}
+
 /* ── Bypass button ─────────────────────────────────────────────────────────
    Kept simple and clear: dark = on/normal, green = enabled, red = bypassed.
    No box-shadow or transform (vizia-unsupported); we rely on color + border. */
@@ -725,33 +1153,29 @@ pub const COMPONENT_STYLES: &str = r#"
     color: #8a93a0;
 }
 
-/* Always-visible status LED: small glowing dot in the module header.
-   Same color semantics as .active-led-button (green = active, dark = bypassed)
-   but shaped as a round indicator and sized for header placement. Empty-label
-   so the :checked state only reads as color change, not text. Still clickable
-   (vizia CSS lacks pointer-events: none) — that's fine, double-toggle is
-   harmless since it targets the same bypass param. */
+/* Always-visible status LED: small round dot in the module header (and on
+   collapsed tabs / next to the master BYPASS label). A passive indicator —
+   the Rust side toggles `module-led-lit` / `module-led-held` from a lens on
+   the module's bypass param and the global bypass. Unlit = bypassed. */
 .module-led-indicator {
-    background: radial-gradient(circle at 35% 35%, #6ee46e, #2c8a2c 60%, #1e7024);
-    border: 1px solid #4dbd4d;
+    background: radial-gradient(circle at 35% 35%, #2a2e36, #16181d 60%, #0d0f13);
+    border: 1px solid #2d3138;
     border-radius: 7px;
     width: 14px;
     height: 14px;
     min-width: 14px;
     min-height: 14px;
-    padding: 0;
-    color: transparent;
-    font-size: 1px;
+    top: 1s;
+    bottom: 1s;
 }
-.module-led-indicator:hover {
-    border-color: #7ce87c;
-}
-.module-led-indicator:checked {
-    background: radial-gradient(circle at 35% 35%, #2a2e36, #16181d 60%, #0d0f13);
-    border-color: #2d3138;
+.module-led-lit {
+    background: radial-gradient(circle at 35% 35%, #6ee46e, #2c8a2c 60%, #1e7024);
+    border-color: #4dbd4d;
 }
-.module-led-indicator:checked:hover {
-    border-color: #3a4050;
+/* Enabled, but held by the global bypass — amber, like a standby lamp. */
+.module-led-held {
+    background: radial-gradient(circle at 35% 35%, #e8b04a, #a0741e 60%, #7a5614);
+    border-color: #c8963c;
 }
 
 /* Hide button — small "×" glyph in the module header that collapses the slot
@@ -829,10 +1253,10 @@ pub const COMPONENT_STYLES: &str = r#"
     color: #6e7480;
 }
 
-/* Empty rack slot — slim dashed-outline tab same width as a collapsed
-   module tab. Single + glyph centered, with a small SLOT N label below.
-   Clicking focuses the slot, which makes the next sidebar click target
-   it specifically. */
+/* Empty rack slot — an unpopulated 500-series bay the same width as a
+   collapsed module tab: rail screws top and bottom, + glyph and SLOT N
+   label, and the vacant card-edge connector. Clicking focuses the slot,
+   which makes the next sidebar click target it specifically. */
 .slot-empty {
     border-style: dashed !important;
     background: linear-gradient(170deg, #20232a 0%, #181a20 100%) !important;
@@ -869,6 +1293,32 @@ pub const COMPONENT_STYLES: &str = r#"
     height: 12px;
 }
 
+.faceplate-screw {
+    width: 8px;
+    height: 8px;
+    border-radius: 4px;
+    background-color: #3a3e46;
+    border: 1px solid #565b64;
+}
+.empty-slot-connector {
+    width: 10px;
+    height: 44px;
+    border-radius: 2px;
+    background-color: #0c0d10;
+    border: 1px solid #34373e;
+}
+
+/* Insert / remove animation — a slot whose module just changed is built
+   with `slot-settling` (dimmed, dropped a few px as if mid-insertion); the
+   class clears after SLOT_SETTLE_TIME and the transition slides it home. */
+.module-slot {
+    transition: opacity 0.2s ease, translate 0.2s ease;
+}
+.slot-settling {
+    opacity: 0.35;
+    translate: 0px 10px;
+}
+
 /* Collapsed tab layout — 56px wide strip containing the 3-char module tag
    and a full-height expand button. The tab keeps the theme border color so
    the chain remains visually readable even with several modules collapsed. */
@@ -1158,6 +1608,100 @@ scrollbar .thumb:hover {
     justify-content: center;
 }
 
+/* ── Metering bridge ───────────────────────────────────────────────────────
+   Neutral steel so no module accent claims the panel. Meters are drawn in
+   Rust; CSS frames the panels and styles the LUFS readouts. */
+.meter-bridge {
+    background: linear-gradient(165deg, #1a1e24 0%, #12161b 45%, #0c0f13);
+    border: 2px solid #5a7ca0;
+    border-radius: 8px;
+}
+
+.meter-bridge-back-btn {
+    background: linear-gradient(145deg, #2a2e34, #33383f);
+    border: 1px solid #5a7ca0;
+    border-radius: 5px;
+    cursor: pointer;
+    display: flex;
+    align-items: center;
+    justify-content: center;
+}
+
+.meter-bridge-back-btn:hover {
+    background: linear-gradient(145deg, #33383f, #40464e);
+    border-color: #8ab4e0;
+}
+
+.meter-bridge-back-btn-label {
+    font-size: 12px;
+    font-weight: 700;
+    color: #8ab4e0;
+    text-align: center;
+    text-transform: uppercase;
+    letter-spacing: 0.8px;
+}
+
+.meter-bridge-title {
+    font-size: 20px;
+    font-weight: 800;
+    color: #8ab4e0;
+    text-transform: uppercase;
+    letter-spacing: 3px;
+}
+
+.meter-bridge-panel {
+    padding: 8px 10px;
+    background-color: rgba(0, 0, 0, 0.22);
+    border: 1px solid rgba(138, 180, 224, 0.18);
+    border-radius: 6px;
+}
+
+.meter-bridge-lufs-name {
+    font-size: 10px;
+    font-weight: 700;
+    color: #9aa4b0;
+    letter-spacing: 1px;
+}
+
+.meter-bridge-lufs-value {
+    font-size: 18px;
+    font-weight: 700;
+    color: #e0e6ee;
+}
+
+.meter-bridge-reset-btn {
+    background: linear-gradient(145deg, #2a2e34, #1f242c);
+    border: 1px solid #3a4050;
+    border-radius: 4px;
+    color: #8ab4e0;
+    font-size: 10px;
+    font-weight: 700;
+    letter-spacing: 1.2px;
+    text-align: center;
+    padding: 6px 10px;
+}
+
+.meter-bridge-reset-btn:hover {
+    border-color: #8ab4e0;
+    color: #cfe2f6;
+}
+
+.meter-bridge-match-ref {
+    font-size: 10px;
+    font-weight: 700;
+    color: #8ab4e0;
+    letter-spacing: 1px;
+}
+
+.meter-bridge-match-status {
+    font-size: 12px;
+    color: #e0e6ee;
+}
+
+.gr-bar {
+    border-radius: 2px;
+}
+
 /* ── Sheen back view ───────────────────────────────────────────────────────
    Brass theme matches the front-panel plate. Deep warm-charcoal background
    with brass border so the back view feels like the inside of a vintage
@@ -1331,6 +1875,31 @@ scrollbar .thumb:hover {
     color: #ffffff;
 }
 
+/* ── Module ADVANCED drawer ─────────────────────────────────────────────────
+   Header row at the foot of a module card; the body below it only exists
+   while the drawer is open (components::advanced_drawer). */
+.advanced-toggle {
+    background-color: transparent;
+    border-width: 0px;
+    border-top: 1px solid #3a3a3a;
+    padding: 2px 0px 0px 0px;
+}
+
+.advanced-chevron {
+    font-size: 10px;
+    color: #8899aa;
+    text-align: center;
+}
+
+.advanced-toggle:hover .advanced-chevron,
+.advanced-toggle-open .advanced-chevron {
+    color: #ffffff;
+}
+
+.advanced-drawer {
+    padding: 4px 0px 0px 0px;
+}
+
 /* Responsive adjustments */
 @media (max-width: 1200px) {
     .param-control {
@@ -1362,6 +1931,7 @@ scrollbar .thumb:hover {
 .zoom-75 .module-type        { font-size: 10px; }
 .zoom-75 .section-label      { font-size: 9px;  }
 .zoom-75 .param-label        { font-size: 9px;  }
+.zoom-75 .param-knob-value   { font-size: 8px;  }
 .zoom-75 .dyneq-param-label  { font-size: 9px;  }
 .zoom-75 .dyneq-band-title   { font-size: 10px; }
 .zoom-75 .chassis-brand      { font-size: 13px; }
@@ -1371,6 +1941,7 @@ scrollbar .thumb:hover {
 .zoom-125 .module-type       { font-size: 15px; }
 .zoom-125 .section-label     { font-size: 14px; }
 .zoom-125 .param-label       { font-size: 14px; }
+.zoom-125 .param-knob-value  { font-size: 12px; }
 .zoom-125 .dyneq-param-label { font-size: 14px; }
 .zoom-125 .dyneq-band-title  { font-size: 15px; }
 .zoom-125 .chassis-brand     { font-size: 22px; }
@@ -1380,6 +1951,7 @@ scrollbar .thumb:hover {
 .zoom-150 .module-type       { font-size: 18px; }
 .zoom-150 .section-label     { font-size: 16px; }
 .zoom-150 .param-label       { font-size: 16px; }
+.zoom-150 .param-knob-value  { font-size: 14px; }
 .zoom-150 .dyneq-param-label { font-size: 16px; }
 .zoom-150 .dyneq-band-title  { font-size: 18px; }
 .zoom-150 .chassis-brand     { font-size: 26px; }
@@ -1389,9 +1961,215 @@ scrollbar .thumb:hover {
 .zoom-200 .module-type       { font-size: 23px; }
 .zoom-200 .section-label     { font-size: 20px; }
 .zoom-200 .param-label       { font-size: 20px; }
+.zoom-200 .param-knob-value  { font-size: 17px; }
 .zoom-200 .dyneq-param-label { font-size: 20px; }
 .zoom-200 .dyneq-band-title  { font-size: 23px; }
 .zoom-200 .chassis-brand     { font-size: 34px; }
 .zoom-200 .chassis-title     { font-size: 25px; }
 
 "#;
+
+/* Light skin: paper-white surfaces, dark legends. Module cards keep their
+accent borders (set from Rust via the active skin's palette) but swap the
+tinted-dark gradients for pale tints. */
+pub const LIGHT_SKIN_STYLES: &str = r#"
+
+.skin-light.lunchbox-chassis {
+    background: linear-gradient(160deg, #e8eaee, #f4f5f7 60%, #e2e4e8);
+    border-color: #b8bcc4;
+}
+.skin-light .chassis-header {
+    background: linear-gradient(180deg, #fafbfc 0%, #eef0f3 60%, #e4e7eb 100%);
+    border-bottom-color: #c4c8d0;
+    border-top-color: #ffffff;
+}
+.skin-light .strip-scroll {
+    background: #dfe2e7;
+    border-color: #c4c8d0;
+}
+.skin-light .lunchbox-slots {
+    background-color: #dfe2e7;
+}
+.skin-light .module-name,
+.skin-light .section-label,
+.skin-light .param-label,
+.skin-light .dyneq-param-label {
+    color: #23272e;
+}
+.skin-light .module-type,
+.skin-light .param-knob-value {
+    color: #4a505a;
+}
+.skin-light .param-control {
+    background-color: rgba(0, 0, 0, 0.05);
+}
+.skin-light .param-control:hover {
+    background-color: rgba(0, 0, 0, 0.09);
+}
+.skin-light .param-access:focus-visible {
+    outline-color: #186ea0;
+}
+.skin-light .api5500-theme     { background: linear-gradient(165deg, #eef6fa, #e2eef5) !important; }
+.skin-light .buttercomp2-theme { background: linear-gradient(165deg, #fbf1e8, #f4e6d8) !important; }
+.skin-light .pultec-theme      { background: linear-gradient(165deg, #faf6e6, #f2ecd4) !important; }
+.skin-light .dynamic-eq-theme  { background: linear-gradient(165deg, #edf7ec, #e0efdf) !important; }
+.skin-light .transformer-theme { background: linear-gradient(165deg, #f8ede6, #efe0d6) !important; }
+.skin-light .punch-theme       { background: linear-gradient(165deg, #faeaeb, #f2dcde) !important; }
+.skin-light .haas-theme        { background: linear-gradient(165deg, #eef0f8, #e2e6f2) !important; }
+.skin-light .library-sidebar {
+    background-color: #eceef1;
+    border-color: #c4c8d0;
+}
+.skin-light .library-sidebar-header,
+.skin-light .zoom-label,
+.skin-light .signal-flow-label,
+.skin-light .master-label {
+    color: #3a4048;
+}
+.skin-light .master-controls {
+    background: rgba(255, 255, 255, 0.6);
+    border-color: #c4c8d0;
+}
+.skin-light .zoom-btn,
+.skin-light .chain-preset-btn,
+.skin-light .preset-btn,
+.skin-light .ab-btn,
+.skin-light .preset-action-btn {
+    background: linear-gradient(180deg, #ffffff, #eceef1);
+    border-color: #c4c8d0;
+}
+.skin-light .zoom-btn-label,
+.skin-light .chain-preset-tag,
+.skin-light .preset-btn-label,
+.skin-light .ab-btn-label,
+.skin-light .preset-action-label {
+    color: #2a2f38;
+}
+.skin-light .chain-preset-name {
+    color: #5a606a;
+}
+.skin-light .preset-browser {
+    background-color: #f7f8fa;
+    border-color: #b8bcc4;
+}
+.skin-light .preset-browser-title,
+.skin-light .preset-row-name {
+    color: #1e2228;
+}
+.skin-light .param-readout-strip {
+    background-color: rgba(255, 255, 255, 0.55);
+    border-top-color: #c4c8d0;
+}
+.skin-light .param-readout-text {
+    color: #3a4048;
+}
+.skin-light .signal-flow-chip {
+    color: #1e2228;
+    background-color: rgba(0, 0, 0, 0.04);
+    border-color: #b8bcc4;
+}
+.skin-light .signal-flow-chip-bypassed {
+    color: #9aa0aa;
+}
+.skin-light .param-menu {
+    background-color: #f7f8fa;
+    border-color: #b8bcc4;
+}
+.skin-light .param-menu-item:hover {
+    background-color: #dde2ea;
+}
+.skin-light .param-menu-item-label {
+    color: #1e2228;
+}
+.skin-light .meter-bridge {
+    background: linear-gradient(165deg, #eef0f3 0%, #e4e7eb 60%, #dcdfe4);
+    border-color: #7a9cc0;
+}
+.skin-light .meter-bridge-panel {
+    background-color: rgba(255, 255, 255, 0.55);
+    border-color: #c4c8d0;
+}
+.skin-light .meter-bridge-lufs-value {
+    color: #1e2228;
+}
+.skin-light .meter-bridge-lufs-name {
+    color: #4a505a;
+}
+.skin-light .meter-bridge-match-status {
+    color: #1e2228;
+}
+.skin-light .meter-bridge-match-ref {
+    color: #3a6a9a;
+}
+.skin-light .preset-list,
+.skin-light .preset-textbox {
+    background-color: #ffffff;
+    border-color: #c4c8d0;
+    color: #1e2228;
+}
+
+"#;
+
+/* Classic hardware skin: gray-blue steel faceplates, cream legends, black
+rails — the look of a loaded 500-series rack. */
+pub const CLASSIC_SKIN_STYLES: &str = r#"
+
+.skin-classic.lunchbox-chassis {
+    background: linear-gradient(160deg, #1a1c1e, #26292c 60%, #1a1c1e);
+    border-color: #3c4044;
+}
+.skin-classic .chassis-header {
+    background: linear-gradient(180deg, #3a3e42 0%, #2e3236 60%, #25282b 100%);
+    border-bottom-color: #111315;
+    border-top-color: #5a5f64;
+}
+.skin-classic .strip-scroll {
+    background: #0c0d0e;
+    border-color: #000000;
+}
+.skin-classic .lunchbox-slots {
+    background-color: #0c0d0e;
+}
+.skin-classic .api5500-theme,
+.skin-classic .buttercomp2-theme,
+.skin-classic .pultec-theme,
+.skin-classic .dynamic-eq-theme,
+.skin-classic .transformer-theme,
+.skin-classic .punch-theme,
+.skin-classic .haas-theme {
+    background: linear-gradient(180deg, #6e7c88 0%, #62707c 50%, #56636e 100%) !important;
+}
+.skin-classic .module-name,
+.skin-classic .section-label,
+.skin-classic .param-label,
+.skin-classic .dyneq-param-label {
+    color: #f2ead2;
+}
+.skin-classic .module-type,
+.skin-classic .param-knob-value {
+    color: #dcd4bc;
+}
+.skin-classic .param-control {
+    background-color: rgba(0, 0, 0, 0.12);
+}
+.skin-classic .param-control:hover {
+    background-color: rgba(0, 0, 0, 0.2);
+}
+.skin-classic .frequency-control .param-label,
+.skin-classic .gain-control .param-label,
+.skin-classic .ratio-control .param-label {
+    color: #f2ead2;
+}
+.skin-classic .library-sidebar {
+    background-color: #1c1e20;
+    border-color: #3c4044;
+}
+.skin-classic .zoom-btn,
+.skin-classic .chain-preset-btn,
+.skin-classic .preset-btn,
+.skin-classic .ab-btn {
+    background: linear-gradient(180deg, #2c2f32, #1f2123);
+    border-color: #4a4e52;
+}
+
+"#;
@@ -0,0 +1,298 @@
+// src/surround.rs
+// 5.1 surround buses.
+//
+// A 5.1 bus carries six channels, L, R, C, LFE, Ls and Rs in the SMPTE/ITU
+// order nih-plug hands over. It runs as four linking groups, each through
+// its own copy of the chain with the same settings and module order:
+//
+//   • Front (L/R) and Surround (Ls/Rs) — linked like the stereo strip, one
+//     detector and one set of gains per pair
+//   • Centre and LFE — mono
+//
+// The chain works on stereo buffers, so a mono group goes in as its channel
+// plus a copy of it. The linked detectors then hear the channel alone, and
+// the copy is dropped afterwards. Haas and Sheen's width have no image to
+// work on there and stay out. With `global_lfe_saturation` off, the
+// saturation stages (Transformer, Punch, Sheen) skip the LFE: their
+// harmonics land in a band the sub can't reproduce and the main speakers
+// can.
+//
+// The front group runs first, as the strip's own state, and is the one the
+// meters, readouts and listen taps follow. The other groups' state lives
+// here and is swapped in while each runs. The master stages after the
+// chain (auto-gain, master gain, safety, dither, loudness) run once across
+// all six channels.
+//
+// Everything is allocated in `new()`, from `initialize()` for a 5.1 layout;
+// the stereo layouts carry none of it.
+
+use crate::chain::Chain;
+#[cfg(feature = "conditioning")]
+use crate::conditioning::InputConditioning;
+use crate::reorder::OrderFade;
+#[cfg(feature = "sheen")]
+use crate::sheen::SheenModule;
+use nih_plug::buffer::Buffer;
+
+/// Channels on a 5.1 bus.
+pub const CHANNELS: usize = 6;
+
+/// A set of channels processed together with shared detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkGroup {
+    Front,
+    Surround,
+    Centre,
+    Lfe,
+}
+
+impl LinkGroup {
+    /// The groups after the front pair, in processing order.
+    pub const REST: [LinkGroup; 3] = [LinkGroup::Surround, LinkGroup::Centre, LinkGroup::Lfe];
+
+    /// The group's channel indices in the 5.1 buffer.
+    pub fn channels(self) -> &'static [usize] {
+        match self {
+            LinkGroup::Front => &[0, 1],
+            LinkGroup::Surround => &[4, 5],
+            LinkGroup::Centre => &[2],
+            LinkGroup::Lfe => &[3],
+        }
+    }
+
+    pub fn is_mono(self) -> bool {
+        self.channels().len() == 1
+    }
+
+    /// Whether the saturation stages run on this group. Only the LFE can
+    /// opt out; `lfe_saturation` is the option's value.
+    pub fn saturates(self, lfe_saturation: bool) -> bool {
+        self != LinkGroup::Lfe || lfe_saturation
+    }
+}
+
+/// The state one group carries from block to block; the strip's own
+/// fields of the same names hold the front group's.
+pub struct GroupState {
+    pub group: LinkGroup,
+    pub chain: Box<Chain>,
+    pub spare_chain: Box<Chain>,
+    pub order_fade: OrderFade,
+    #[cfg(feature = "sheen")]
+    pub sheen: SheenModule,
+    #[cfg(feature = "conditioning")]
+    pub input_conditioning: InputConditioning,
+}
+
+impl GroupState {
+    fn new(group: LinkGroup, max_block: usize, sample_rate: f32) -> Self {
+        Self {
+            group,
+            chain: Box::new(Chain::new(sample_rate)),
+            spare_chain: Box::new(Chain::new(sample_rate)),
+            order_fade: OrderFade::new(max_block, sample_rate),
+            #[cfg(feature = "sheen")]
+            sheen: SheenModule::new(sample_rate),
+            #[cfg(feature = "conditioning")]
+            input_conditioning: InputConditioning::new(sample_rate),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.chain.reset();
+        self.spare_chain.reset();
+        self.order_fade.reset();
+        #[cfg(feature = "sheen")]
+        self.sheen.reset();
+        #[cfg(feature = "conditioning")]
+        self.input_conditioning.reset();
+    }
+}
+
+/// The groups behind the front pair, and the view their chains run on.
+pub struct Surround {
+    pub groups: [GroupState; 3],
+    pub view: GroupView,
+}
+
+impl Surround {
+    /// Room for blocks of up to `max_block` samples.
+    pub fn new(max_block: usize, sample_rate: f32) -> Self {
+        Self {
+            groups: LinkGroup::REST.map(|group| GroupState::new(group, max_block, sample_rate)),
+            view: GroupView::new(max_block),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for state in &mut self.groups {
+            state.reset();
+        }
+    }
+
+    /// Clear the detector envelopes only; see `Chain::reset_envelopes`.
+    pub fn reset_envelopes(&mut self) {
+        for state in &mut self.groups {
+            state.chain.reset_envelopes();
+            state.spare_chain.reset_envelopes();
+        }
+    }
+}
+
+/// One group's channels of the 5.1 buffer, seen as the stereo buffer the
+/// chain runs on.
+pub struct GroupView {
+    /// A mono group's second channel: a copy of the first.
+    twin: Vec<f32>,
+    /// Points into the host buffer only while `run` runs the chain on it.
+    buffer: Buffer<'static>,
+}
+
+impl GroupView {
+    pub fn new(max_block: usize) -> Self {
+        let mut buffer = Buffer::default();
+        // SAFETY: no slices are handed over; this only reserves the slice
+        // list so later `set_slices` calls don't allocate.
+        unsafe {
+            buffer.set_slices(0, |slices| {
+                slices.clear();
+                slices.reserve(2);
+            });
+        }
+        Self {
+            twin: vec![0.0; max_block],
+            buffer,
+        }
+    }
+
+    /// Run `process` on `group`'s channels of `main`, in place. Does
+    /// nothing when `main` isn't 5.1 or the block is longer than the twin.
+    pub fn run(&mut self, main: &mut Buffer, group: LinkGroup, process: impl FnOnce(&mut Buffer)) {
+        let samples = main.samples();
+        let host = main.as_slice();
+        if host.len() < CHANNELS || samples > self.twin.len() {
+            return;
+        }
+        let [left, right] = match *group.channels() {
+            [l, r] => [host[l].as_mut_ptr(), host[r].as_mut_ptr()],
+            [ch] => {
+                self.twin[..samples].copy_from_slice(&host[ch][..samples]);
+                [host[ch].as_mut_ptr(), self.twin.as_mut_ptr()]
+            }
+            _ => return,
+        };
+        // SAFETY: the slices point at two different host channels, or at
+        // one and `twin`, each `samples` long. `main` stays borrowed for
+        // this whole call, and `buffer` is emptied again below, before it
+        // returns; `twin` is never resized after `new()`.
+        unsafe {
+            let left = std::slice::from_raw_parts_mut(left, samples);
+            let right = std::slice::from_raw_parts_mut(right, samples);
+            self.buffer.set_slices(samples, |slices| {
+                slices.clear();
+                slices.push(left);
+                slices.push(right);
+            });
+        }
+        process(&mut self.buffer);
+        // SAFETY: clearing hands nothing over and keeps capacity.
+        unsafe {
+            self.buffer.set_slices(0, |slices| slices.clear());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 5.1 buffer over `channels`, for the length of the call.
+    fn with_main(channels: &mut [Vec<f32>; CHANNELS], f: impl FnOnce(&mut Buffer)) {
+        let samples = channels[0].len();
+        let mut main = Buffer::default();
+        // SAFETY: the slices borrow `channels`, which outlives `main`.
+        unsafe {
+            main.set_slices(samples, |slices| {
+                slices.clear();
+                slices.extend(channels.iter_mut().map(|ch| &mut ch[..]));
+            });
+        }
+        f(&mut main);
+    }
+
+    #[test]
+    fn groups_cover_every_channel_once() {
+        let mut seen = [0; CHANNELS];
+        for group in [LinkGroup::Front].into_iter().chain(LinkGroup::REST) {
+            for &ch in group.channels() {
+                seen[ch] += 1;
+            }
+        }
+        assert_eq!(seen, [1; CHANNELS]);
+    }
+
+    #[test]
+    fn only_the_lfe_skips_saturation() {
+        for group in [LinkGroup::Front].into_iter().chain(LinkGroup::REST) {
+            assert!(group.saturates(true));
+            assert_eq!(group.saturates(false), group != LinkGroup::Lfe);
+        }
+    }
+
+    #[test]
+    fn a_group_sees_only_its_own_channels() {
+        let mut channels: [Vec<f32>; CHANNELS] = std::array::from_fn(|ch| vec![ch as f32; 8]);
+        let mut view = GroupView::new(8);
+        with_main(&mut channels, |main| {
+            view.run(main, LinkGroup::Surround, |pair| {
+                let pair = pair.as_slice();
+                assert_eq!((pair[0][0], pair[1][0]), (4.0, 5.0));
+                for ch in pair.iter_mut() {
+                    ch.iter_mut().for_each(|s| *s += 10.0);
+                }
+            });
+        });
+        assert_eq!(channels.map(|ch| ch[7]), [0.0, 1.0, 2.0, 3.0, 14.0, 15.0]);
+    }
+
+    #[test]
+    fn a_mono_group_runs_as_a_pair_and_keeps_its_channel() {
+        let mut channels: [Vec<f32>; CHANNELS] = std::array::from_fn(|ch| vec![ch as f32; 8]);
+        let mut view = GroupView::new(8);
+        with_main(&mut channels, |main| {
+            view.run(main, LinkGroup::Lfe, |pair| {
+                let [left, right] = pair.as_slice() else {
+                    panic!("a mono group still runs as a pair");
+                };
+                assert_eq!(left, right);
+                left.iter_mut().for_each(|s| *s = -1.0);
+                right.iter_mut().for_each(|s| *s = 99.0);
+            });
+        });
+        assert_eq!(channels.map(|ch| ch[0]), [0.0, 1.0, 2.0, -1.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn a_stereo_buffer_or_an_oversized_block_is_left_alone() {
+        let mut view = GroupView::new(4);
+        let mut channels: [Vec<f32>; CHANNELS] = std::array::from_fn(|_| vec![0.0; 8]);
+        with_main(&mut channels, |main| {
+            view.run(main, LinkGroup::Centre, |_| {
+                panic!("block longer than the twin")
+            });
+        });
+        let (mut l, mut r) = (vec![0.0; 4], vec![0.0; 4]);
+        let mut stereo = Buffer::default();
+        // SAFETY: the slices borrow `l` and `r`, which outlive `stereo`.
+        unsafe {
+            stereo.set_slices(4, |slices| {
+                slices.clear();
+                slices.push(&mut l[..]);
+                slices.push(&mut r[..]);
+            });
+        }
+        view.run(&mut stereo, LinkGroup::Front, |_| {
+            panic!("not a 5.1 buffer")
+        });
+    }
+}
@@ -0,0 +1,329 @@
+// src/telemetry.rs — Typed lock-free channels from the DSP to the GUI.
+//
+// Three shapes cover everything the editor reads from the audio side:
+//
+//   • Meter          — one f32 (levels, GR, activity). Any number of
+//                      readers; the latest store wins.
+//   • triple_buffer  — a whole value (a spectrum, a scope frame) handed over
+//                      intact. The writer never waits, the reader always
+//                      sees the newest complete value, never a mix of two.
+//   • spsc_ring      — a stream where every item matters (GR history
+//                      columns, events). Bounded; a full ring drops the new
+//                      item rather than block the audio thread.
+//
+// Channels are built once (on the main thread — construction allocates) and
+// split into a writer half for the DSP and a reader half for the GUI. The
+// halves aren't `Clone`, so the single-producer/single-consumer contract is
+// enforced by ownership rather than by a "writer only" comment. Nothing on
+// either side locks or allocates after construction.
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicU32, AtomicU8, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// ── Meter ─────────────────────────────────────────────────────────────────────
+
+/// One f32 shared through its bit pattern. Relaxed ordering: each meter is
+/// independent, and a reader only ever needs a recent value.
+pub struct Meter {
+    bits: AtomicU32,
+}
+
+impl Meter {
+    pub fn new(initial: f32) -> Self {
+        Self {
+            bits: AtomicU32::new(initial.to_bits()),
+        }
+    }
+
+    pub fn store(&self, value: f32) {
+        self.bits.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn load(&self) -> f32 {
+        f32::from_bits(self.bits.load(Ordering::Relaxed))
+    }
+}
+
+impl Default for Meter {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+// ── Triple buffer ─────────────────────────────────────────────────────────────
+//
+// Three slots: the writer owns one (back), the reader owns one (front), and
+// the third (middle) is exchanged through `middle`, whose low bits hold the
+// slot index and whose FRESH bit says the writer has published into it since
+// the reader last took it. Both sides only ever touch the slot they own.
+
+const INDEX_MASK: u8 = 0b011;
+const FRESH: u8 = 0b100;
+
+struct TripleShared<T> {
+    slots: [UnsafeCell<T>; 3],
+    middle: AtomicU8,
+}
+
+// SAFETY: each slot is accessed by exactly one side at a time — the writer
+// through `back`, the reader through `front` — and ownership of a slot only
+// changes hands through the AcqRel swap on `middle`.
+unsafe impl<T: Send> Sync for TripleShared<T> {}
+
+/// Build a triple buffer with every slot set to `initial`.
+pub fn triple_buffer<T: Clone + Send>(initial: T) -> (TripleWriter<T>, TripleReader<T>) {
+    let shared = Arc::new(TripleShared {
+        slots: [
+            UnsafeCell::new(initial.clone()),
+            UnsafeCell::new(initial.clone()),
+            UnsafeCell::new(initial),
+        ],
+        middle: AtomicU8::new(1),
+    });
+    (
+        TripleWriter {
+            shared: shared.clone(),
+            back: 0,
+        },
+        TripleReader { shared, front: 2 },
+    )
+}
+
+/// DSP side of a triple buffer.
+pub struct TripleWriter<T> {
+    shared: Arc<TripleShared<T>>,
+    back: u8,
+}
+
+impl<T> TripleWriter<T> {
+    /// The slot being prepared. It holds whatever was published two swaps
+    /// ago, so overwrite every field that matters before `publish`.
+    pub fn back_mut(&mut self) -> &mut T {
+        // SAFETY: `back` is owned by this (unique, non-Clone) writer.
+        unsafe { &mut *self.shared.slots[self.back as usize].get() }
+    }
+
+    /// Hand the back slot to the reader and take the middle one back.
+    pub fn publish(&mut self) {
+        let previous = self.shared.middle.swap(self.back | FRESH, Ordering::AcqRel);
+        self.back = previous & INDEX_MASK;
+    }
+}
+
+/// GUI side of a triple buffer.
+pub struct TripleReader<T> {
+    shared: Arc<TripleShared<T>>,
+    front: u8,
+}
+
+impl<T> TripleReader<T> {
+    /// Take the newest published value if there is one. Returns `false`
+    /// (and keeps the current value) when nothing new was published.
+    pub fn update(&mut self) -> bool {
+        if self.shared.middle.load(Ordering::Relaxed) & FRESH == 0 {
+            return false;
+        }
+        let previous = self.shared.middle.swap(self.front, Ordering::AcqRel);
+        self.front = previous & INDEX_MASK;
+        true
+    }
+
+    /// The value taken by the last `update` (or the initial value).
+    pub fn read(&self) -> &T {
+        // SAFETY: `front` is owned by this (unique, non-Clone) reader.
+        unsafe { &*self.shared.slots[self.front as usize].get() }
+    }
+}
+
+// SAFETY: each half touches only its own slot (see `TripleShared`), so a
+// half can move to another thread whenever `T` can.
+unsafe impl<T: Send> Send for TripleWriter<T> {}
+unsafe impl<T: Send> Send for TripleReader<T> {}
+
+// ── SPSC ring ─────────────────────────────────────────────────────────────────
+//
+// `head` and `tail` count items ever popped / pushed; the slot is the count
+// modulo N. The producer publishes a slot with a Release store of `tail`,
+// the consumer frees one with a Release store of `head`, and each side
+// Acquire-loads the other's counter before touching a slot.
+
+struct RingShared<T, const N: usize> {
+    slots: [UnsafeCell<T>; N],
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+// SAFETY: a slot is written only by the producer while it's outside
+// head..tail and read only by the consumer while it's inside; the counters'
+// Release/Acquire pairs order those accesses.
+unsafe impl<T: Send, const N: usize> Sync for RingShared<T, N> {}
+
+/// Build a ring holding up to `N` items.
+pub fn spsc_ring<T: Copy + Default + Send, const N: usize>(
+) -> (RingProducer<T, N>, RingConsumer<T, N>) {
+    assert!(N > 0, "an SPSC ring needs at least one slot");
+    let shared = Arc::new(RingShared {
+        slots: std::array::from_fn(|_| UnsafeCell::new(T::default())),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+    });
+    (
+        RingProducer {
+            shared: shared.clone(),
+        },
+        RingConsumer { shared },
+    )
+}
+
+/// DSP side of an SPSC ring.
+pub struct RingProducer<T, const N: usize> {
+    shared: Arc<RingShared<T, N>>,
+}
+
+impl<T: Copy, const N: usize> RingProducer<T, N> {
+    /// Append `item`. Returns `false`, dropping it, when the ring is full.
+    pub fn push(&mut self, item: T) -> bool {
+        let tail = self.shared.tail.load(Ordering::Relaxed);
+        let head = self.shared.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) >= N {
+            return false;
+        }
+        // SAFETY: the slot is outside head..tail, so the consumer isn't
+        // reading it, and only this producer writes.
+        unsafe { *self.shared.slots[tail % N].get() = item };
+        self.shared
+            .tail
+            .store(tail.wrapping_add(1), Ordering::Release);
+        true
+    }
+}
+
+/// GUI side of an SPSC ring.
+pub struct RingConsumer<T, const N: usize> {
+    shared: Arc<RingShared<T, N>>,
+}
+
+impl<T: Copy, const N: usize> RingConsumer<T, N> {
+    /// The oldest unread item.
+    pub fn pop(&mut self) -> Option<T> {
+        let head = self.shared.head.load(Ordering::Relaxed);
+        let tail = self.shared.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        // SAFETY: the slot is inside head..tail, so the producer has
+        // finished writing it and won't touch it until `head` moves past.
+        let item = unsafe { *self.shared.slots[head % N].get() };
+        self.shared
+            .head
+            .store(head.wrapping_add(1), Ordering::Release);
+        Some(item)
+    }
+
+    /// Pop everything currently queued, oldest first.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.pop())
+    }
+}
+
+// SAFETY: see `RingShared`; each half only touches slots on its own side of
+// the counters.
+unsafe impl<T: Send, const N: usize> Send for RingProducer<T, N> {}
+unsafe impl<T: Send, const N: usize> Send for RingConsumer<T, N> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meter_round_trips() {
+        let meter = Meter::new(-120.0);
+        assert_eq!(meter.load(), -120.0);
+        meter.store(-6.5);
+        assert_eq!(meter.load(), -6.5);
+    }
+
+    #[test]
+    fn triple_buffer_hands_over_the_latest_value() {
+        let (mut writer, mut reader) = triple_buffer([0_u32; 4]);
+        assert!(!reader.update());
+        assert_eq!(*reader.read(), [0; 4]);
+
+        *writer.back_mut() = [1; 4];
+        writer.publish();
+        *writer.back_mut() = [2; 4];
+        writer.publish();
+        assert!(reader.update());
+        assert_eq!(*reader.read(), [2; 4]);
+        // Nothing new: keeps the value it has.
+        assert!(!reader.update());
+        assert_eq!(*reader.read(), [2; 4]);
+
+        writer.back_mut()[0] = 7;
+        writer.publish();
+        assert!(reader.update());
+        assert_eq!(reader.read()[0], 7);
+    }
+
+    #[test]
+    fn triple_buffer_never_tears_across_threads() {
+        const FRAMES: u32 = 20_000;
+        let (mut writer, mut reader) = triple_buffer([0_u32; 64]);
+        let producer = std::thread::spawn(move || {
+            for frame in 1..=FRAMES {
+                writer.back_mut().fill(frame);
+                writer.publish();
+            }
+        });
+        let mut last = 0;
+        while last < FRAMES {
+            if reader.update() {
+                let frame = reader.read();
+                assert!(frame.iter().all(|&v| v == frame[0]), "torn frame");
+                assert!(frame[0] > last, "frames went backwards");
+                last = frame[0];
+            } else {
+                std::thread::yield_now();
+            }
+        }
+        producer.join().unwrap();
+    }
+
+    #[test]
+    fn ring_is_fifo_and_drops_when_full() {
+        let (mut producer, mut consumer) = spsc_ring::<u32, 4>();
+        for i in 0..4 {
+            assert!(producer.push(i));
+        }
+        assert!(!producer.push(99), "full ring must refuse");
+        assert_eq!(consumer.pop(), Some(0));
+        assert!(producer.push(4));
+        assert_eq!(consumer.drain().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn ring_delivers_everything_in_order_across_threads() {
+        const ITEMS: u64 = 100_000;
+        let (mut producer, mut consumer) = spsc_ring::<u64, 64>();
+        let thread = std::thread::spawn(move || {
+            for i in 0..ITEMS {
+                while !producer.push(i) {
+                    std::thread::yield_now();
+                }
+            }
+        });
+        let mut expected = 0;
+        while expected < ITEMS {
+            match consumer.pop() {
+                Some(item) => {
+                    assert_eq!(item, expected);
+                    expected += 1;
+                }
+                None => std::thread::yield_now(),
+            }
+        }
+        thread.join().unwrap();
+    }
+}
@@ -1,7 +1,13 @@
-use crate::oversampler::Oversampler;
-use crate::shaping::biquad_coeffs;
-use biquad::{Biquad, DirectForm1, Type};
+use crate::engagement::Engagement;
+use crate::envelope::EnvelopeFollower;
+use crate::oversampler::{factor_for_rate, CascadeDelay, Oversampler};
+use crate::shaping::adaa::{ln_cosh, Adaa1, Antiderivative};
+use crate::shaping::biquad_coeffs_f64;
+use crate::variance::Variance;
+use biquad::{Biquad, DirectForm2Transposed, Type};
+#[cfg(feature = "plugin")]
 use nih_plug::buffer::Buffer;
+#[cfg(feature = "plugin")]
 use nih_plug::prelude::Enum;
 
 /// Oversampling factor for the transformer saturation stage. 4× = 2 halfband
@@ -10,6 +16,80 @@ use nih_plug::prelude::Enum;
 /// half-Nyquist do not fold back, without the CPU cost of 8×/16×.
 const TRANSFORMER_OS_FACTOR: usize = 4;
 
+/// Per-block release coefficient for the saturation-activity meter. Attack is
+/// instant; at ~86 blocks/s (512 @ 44.1 kHz) this decays ~1 dB per 30 ms,
+/// slow enough for the eye to follow drum hits.
+const SAT_METER_RELEASE: f32 = 0.9;
+
+/// Release of the loading compression's peak follower (attack is instant).
+/// 2.3 ms is the 1%-per-sample release it used to run at 44.1 kHz.
+const LOADING_RELEASE_MS: f32 = 2.3;
+
+/// Components vintage variance moves, indices into
+/// `TransformerModule::variance`.
+const VAR_INPUT_DRIVE: usize = 0;
+const VAR_OUTPUT_DRIVE: usize = 1;
+const VAR_LOW_FREQ: usize = 2;
+const VAR_LOW_GAIN: usize = 3;
+const VAR_HIGH_FREQ: usize = 4;
+const VAR_HIGH_GAIN: usize = 5;
+/// This unit's serial number, as far as the tolerances are concerned.
+const VARIANCE_SEED: u32 = 0x7A_1973;
+
+/// Mono-safe mode: share of the saturation amount the side channel gets.
+const SIDE_SATURATION: f32 = 0.3;
+
+/// Age: where the band-limiting filters sit on a pristine core. Age moves
+/// them geometrically toward the model's worn corners.
+const PRISTINE_LOW_CUT_HZ: f32 = 5.0;
+const PRISTINE_HIGH_CUT_HZ: f32 = 40_000.0;
+/// Seeds for the modulation noise generators; never zero. The second only
+/// runs with the channels unlinked.
+const AGE_SEEDS: [u32; 2] = [0x3C6E_F372, 0xA54F_F53A];
+
+/// What a fully worn core of one model does; see `set_age`.
+struct WornCore {
+    /// Corner of the low-end loss (second-order high-pass).
+    low_cut_hz: f32,
+    /// Corner of the top-end loss (second-order low-pass).
+    high_cut_hz: f32,
+    /// Remanent magnetisation, as a bias on the saturators' input.
+    bias: f32,
+    /// Modulation noise riding on the signal, dB below it.
+    noise_db: f32,
+}
+
+impl TransformerModel {
+    fn worn_core(self) -> WornCore {
+        match self {
+            TransformerModel::Vintage => WornCore {
+                low_cut_hz: 40.0,
+                high_cut_hz: 9_000.0,
+                bias: 0.15,
+                noise_db: -46.0,
+            },
+            TransformerModel::Modern => WornCore {
+                low_cut_hz: 25.0,
+                high_cut_hz: 14_000.0,
+                bias: 0.06,
+                noise_db: -58.0,
+            },
+            TransformerModel::British => WornCore {
+                low_cut_hz: 35.0,
+                high_cut_hz: 11_000.0,
+                bias: 0.1,
+                noise_db: -52.0,
+            },
+            TransformerModel::American => WornCore {
+                low_cut_hz: 30.0,
+                high_cut_hz: 10_000.0,
+                bias: 0.12,
+                noise_db: -50.0,
+            },
+        }
+    }
+}
+
 /// Professional Transformer Coloration Module
 ///
 /// Models input and output transformers found in classic channel strips
@@ -25,9 +105,12 @@ pub struct TransformerModule {
     // Output transformer stage
     output_transformer: TransformerStage,
 
-    // Frequency response filters — updated via update_coefficients(), never recreated.
-    low_shelf: DirectForm1<f32>,
-    high_shelf: DirectForm1<f32>,
+    // Frequency response filters, one per channel — updated via
+    // update_coefficients(), never recreated. Transposed direct form II in
+    // f64: the 60–100 Hz low shelf puts its poles right against z = 1,
+    // where f32 coefficients and state quantize audibly.
+    low_shelf: [DirectForm2Transposed<f64>; 2],
+    high_shelf: [DirectForm2Transposed<f64>; 2],
 
     // Per-channel oversamplers for anti-aliased nonlinear saturation. Input
     // and output stages need independent oversamplers because their filter
@@ -38,6 +121,12 @@ pub struct TransformerModule {
     output_os_l: Oversampler,
     output_os_r: Oversampler,
 
+    /// Run the saturators through first-order ADAA at the native rate
+    /// instead of the oversamplers.
+    adaa: bool,
+    input_adaa: [Adaa1; 2],
+    output_adaa: [Adaa1; 2],
+
     // Transformer model
     model: TransformerModel,
 
@@ -45,6 +134,40 @@ pub struct TransformerModule {
     cached_model: TransformerModel,
     cached_low_response: f32,
     cached_high_response: f32,
+
+    // Saturation-activity meter: ratio of the RMS the nonlinearity adds or
+    // removes to the RMS it was fed, with meter ballistics. 0 = linear.
+    sat_activity: f32,
+
+    /// Samples either stage bent since the last `take_engagement`.
+    engagement: Engagement,
+
+    /// Tolerance and drift on the drive gains and response shelves.
+    variance: Variance<6>,
+
+    /// Saturate mid and side instead of left and right, the side more
+    /// gently; see `set_mono_safe`.
+    mono_safe: bool,
+
+    /// Core wear, 0 (pristine) to 1; see `set_age`.
+    age: f32,
+    cached_age: f32,
+    // Band limits of a worn core, f64 for the same reason as the shelves.
+    // Skipped entirely at age 0.
+    age_low_cut: [DirectForm2Transposed<f64>; 2],
+    age_high_cut: [DirectForm2Transposed<f64>; 2],
+    /// Modulation noise depth relative to the signal, 0 at age 0.
+    age_noise: f32,
+    /// xorshift32 state for the modulation noise. Linked, one draw per
+    /// frame is shared by the channels: each channel's own level modulates
+    /// it, and mono material stays mono in either saturation domain.
+    /// Unlinked, the right channel draws from the second.
+    age_rng: [u32; 2],
+
+    /// Channels linked (the default): the stages' loading compression
+    /// follows both channels and mono-safe applies. Unlinked, each channel
+    /// runs as its own mono transformer; see `set_linked`.
+    linked: bool,
 }
 
 /// Individual transformer stage (input or output)
@@ -56,37 +179,60 @@ struct TransformerStage {
     // Harmonic generation state
     harmonic_state: f32,
 
-    // Gentle compression (transformer loading effect)
+    /// Input bias from core age; makes the curve asymmetric. 0 when new.
+    bias: f32,
+
+    // Gentle compression (transformer loading effect). Linked, one follower
+    // sees both channels; unlinked, the right channel has its own.
     compression_amount: f32,
-    envelope: f32,
+    envelope: EnvelopeFollower,
+    envelope_r: EnvelopeFollower,
+
+    // Saturation-activity accumulators for the current block, measured in
+    // the oversampled domain where input and output are sample-aligned.
+    distortion_energy: f32,
+    drive_energy: f32,
+
+    /// Whether the last `process_sample` bent its sample; see
+    /// engagement.rs.
+    bent: bool,
 }
 
 /// Transformer model types
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Enum)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "plugin", derive(Enum))]
 pub enum TransformerModel {
-    #[name = "Vintage"]
+    #[cfg_attr(feature = "plugin", name = "Vintage")]
     Vintage, // Classic vintage sound (Neve-style)
-    #[name = "Modern"]
+    #[cfg_attr(feature = "plugin", name = "Modern")]
     Modern, // Clean modern transformers (API-style)
-    #[name = "British"]
+    #[cfg_attr(feature = "plugin", name = "British")]
     British, // British console sound (SSL-style)
-    #[name = "American"]
+    #[cfg_attr(feature = "plugin", name = "American")]
     American, // American console sound (custom)
 }
 
 impl TransformerStage {
-    fn new() -> Self {
+    fn new(sample_rate: f32) -> Self {
         Self {
             saturation_amount: 0.0,
             drive_gain: 1.0,
             harmonic_state: 0.0,
+            bias: 0.0,
             compression_amount: 0.0,
-            envelope: 0.0,
+            envelope: EnvelopeFollower::new(sample_rate, 0.0, LOADING_RELEASE_MS),
+            envelope_r: EnvelopeFollower::new(sample_rate, 0.0, LOADING_RELEASE_MS),
+            distortion_energy: 0.0,
+            drive_energy: 0.0,
+            bent: false,
         }
     }
 
     /// Process sample through transformer stage with an oversampled
-    /// saturation path for anti-aliasing.
+    /// saturation path for anti-aliasing, or first-order ADAA when `adaa`
+    /// is given. `amount_scale` scales the saturation amount but not the
+    /// drive, which is a plain gain. `follower` picks the loading
+    /// compression's follower: 0 the shared one, 1 the right channel's.
     ///
     /// The saturation step is pointwise (memoryless), so we upsample the
     /// driven signal, apply the model's nonlinearity to each oversampled
@@ -98,49 +244,72 @@ impl TransformerStage {
         input: f32,
         model: TransformerModel,
         os: &mut Oversampler,
+        adaa: Option<&mut Adaa1>,
         scratch: &mut [f32; TRANSFORMER_OS_FACTOR],
+        amount_scale: f32,
+        follower: usize,
     ) -> f32 {
+        self.bent = false;
         if self.saturation_amount < 0.01 {
             return input;
         }
+        let amount = self.saturation_amount * amount_scale;
 
         // Apply input drive
         let driven_signal = input * self.drive_gain;
 
-        // Oversampled saturation: upsample → pointwise nonlinearity → downsample.
-        let saturated = {
+        let saturated = if let Some(adaa) = adaa {
+            // Native-rate ADAA on the curve's nonlinear part; the dry part
+            // passes straight through, so the top end isn't averaged away.
+            let curve = ModelCurve {
+                model,
+                amount,
+                bias: self.bias,
+            };
+            let out = adaa.process_residual(&curve, driven_signal);
+            let deviation = out - driven_signal;
+            self.distortion_energy += deviation * deviation;
+            self.drive_energy += driven_signal * driven_signal;
+            self.bent = Engagement::bent(driven_signal, out);
+            out
+        } else {
+            // Oversampled saturation: upsample → pointwise nonlinearity → downsample.
             let up = os.upsample(driven_signal, 0);
             // Borrow ends at end of this scope; copy to scratch so we can
-            // mutably re-borrow `os` for downsample.
-            for i in 0..TRANSFORMER_OS_FACTOR {
-                scratch[i] = saturate_by_model(up[i], self.saturation_amount, model);
+            // mutably re-borrow `os` for downsample. `up` holds the factor
+            // chosen for the sample rate, at most TRANSFORMER_OS_FACTOR.
+            let factor = up.len();
+            for (out, &x) in scratch.iter_mut().zip(up) {
+                *out = saturate_biased(x, amount, model, self.bias);
+                let deviation = *out - x;
+                self.distortion_energy += deviation * deviation;
+                self.drive_energy += x * x;
+                self.bent |= Engagement::bent(x, *out);
             }
-            os.downsample(&scratch[..TRANSFORMER_OS_FACTOR], 0)
+            os.downsample(&scratch[..factor], 0)
         };
 
         // Gentle transformer compression (loading effect, native rate)
         if self.compression_amount > 0.01 {
-            self.apply_transformer_compression(saturated)
+            self.apply_transformer_compression(saturated, follower)
         } else {
             saturated
         }
     }
 
     /// Apply gentle compression that mimics transformer loading
-    fn apply_transformer_compression(&mut self, input: f32) -> f32 {
-        let abs_input = input.abs();
-
-        // Simple envelope follower
-        if abs_input > self.envelope {
-            self.envelope = abs_input;
+    fn apply_transformer_compression(&mut self, input: f32, follower: usize) -> f32 {
+        let envelope = if follower == 0 {
+            &mut self.envelope
         } else {
-            self.envelope += (abs_input - self.envelope) * 0.01; // Slow release
-        }
+            &mut self.envelope_r
+        };
+        let envelope = envelope.process(input);
 
         // Gentle compression when signal gets hot
         let threshold = 0.7;
-        if self.envelope > threshold {
-            let over_threshold = self.envelope - threshold;
+        if envelope > threshold {
+            let over_threshold = envelope - threshold;
             let compression_ratio = 1.0 + (over_threshold * self.compression_amount * 2.0);
             input / compression_ratio
         } else {
@@ -153,35 +322,123 @@ impl TransformerModule {
     /// Create new transformer module
     pub fn new(sample_rate: f32) -> Self {
         // Initialize frequency response filters (flat by default)
-        let flat_coeff = biquad_coeffs(Type::LowPass, sample_rate, 20000.0, 0.707)
+        let flat_coeff = biquad_coeffs_f64(Type::LowPass, sample_rate, 20000.0, 0.707)
             .expect("LowPass filter should be valid");
 
         // Oversamplers are called once per sample (inline use), so
         // `max_block_size = 1` is sufficient — each upsample/downsample pair
-        // writes into buffer[0..TRANSFORMER_OS_FACTOR].
+        // writes into buffer[0..TRANSFORMER_OS_FACTOR]. High base rates run
+        // fewer stages.
         let make_os = || {
             let mut os = Oversampler::new(TRANSFORMER_OS_FACTOR, 1);
-            os.set_factor(TRANSFORMER_OS_FACTOR);
+            os.set_factor(factor_for_rate(TRANSFORMER_OS_FACTOR, sample_rate));
             os
         };
 
         Self {
             sample_rate,
-            input_transformer: TransformerStage::new(),
-            output_transformer: TransformerStage::new(),
-            low_shelf: DirectForm1::<f32>::new(flat_coeff),
-            high_shelf: DirectForm1::<f32>::new(flat_coeff),
+            input_transformer: TransformerStage::new(sample_rate),
+            output_transformer: TransformerStage::new(sample_rate),
+            low_shelf: std::array::from_fn(|_| DirectForm2Transposed::<f64>::new(flat_coeff)),
+            high_shelf: std::array::from_fn(|_| DirectForm2Transposed::<f64>::new(flat_coeff)),
             input_os_l: make_os(),
             input_os_r: make_os(),
             output_os_l: make_os(),
             output_os_r: make_os(),
+            adaa: false,
+            input_adaa: [Adaa1::new(); 2],
+            output_adaa: [Adaa1::new(); 2],
             model: TransformerModel::Vintage,
             cached_model: TransformerModel::Vintage,
             cached_low_response: f32::NAN, // NAN forces recompute on first call
             cached_high_response: f32::NAN,
+            sat_activity: 0.0,
+            engagement: Engagement::default(),
+            variance: Variance::new(VARIANCE_SEED, sample_rate),
+            mono_safe: false,
+            age: 0.0,
+            cached_age: f32::NAN,
+            age_low_cut: std::array::from_fn(|_| DirectForm2Transposed::<f64>::new(flat_coeff)),
+            age_high_cut: std::array::from_fn(|_| DirectForm2Transposed::<f64>::new(flat_coeff)),
+            age_noise: 0.0,
+            age_rng: AGE_SEEDS,
+            linked: true,
+        }
+    }
+
+    /// Vintage variance: ±2% tolerance and slow drift on the drive gains
+    /// and the response shelves (see variance.rs). Takes effect at the next
+    /// `update_parameters`.
+    pub fn set_variance(&mut self, on: bool) {
+        self.variance.set_enabled(on);
+    }
+
+    /// Anti-alias the saturators with first-order ADAA at the native rate
+    /// instead of 4× oversampling: cheaper, and no halfband group delay,
+    /// at the cost of less suppression for the highest harmonics. The path
+    /// switched to starts from clean state.
+    pub fn set_adaa(&mut self, on: bool) {
+        if on == self.adaa {
+            return;
+        }
+        self.adaa = on;
+        if on {
+            self.input_adaa = [Adaa1::new(); 2];
+            self.output_adaa = [Adaa1::new(); 2];
+        } else {
+            for os in [
+                &mut self.input_os_l,
+                &mut self.input_os_r,
+                &mut self.output_os_l,
+                &mut self.output_os_r,
+            ] {
+                os.reset();
+            }
         }
     }
 
+    /// Mono-safe mode: run a stereo pair through the stages as mid and
+    /// side, with the side saturated at `SIDE_SATURATION` of the amount.
+    /// Saturating L and R apart adds different harmonics to each, which
+    /// lands in the side channel: the image widens and the correlation
+    /// drops on a mix bus. Here the distortion stays in the middle, and a
+    /// mono signal comes out exactly as in L/R mode. Switching mid-stream
+    /// carries filter state across domains, so expect a brief tick.
+    pub fn set_mono_safe(&mut self, on: bool) {
+        self.mono_safe = on;
+    }
+
+    /// Link the channels (the default) or run each as its own mono
+    /// transformer, for two unrelated sources on one track: the loading
+    /// compression follows each channel alone, the age noise draws apart,
+    /// and mono-safe stands aside, as there is no shared image to protect.
+    pub fn set_linked(&mut self, linked: bool) {
+        if self.linked && !linked {
+            for stage in [&mut self.input_transformer, &mut self.output_transformer] {
+                stage.envelope_r = stage.envelope.clone();
+            }
+        }
+        self.linked = linked;
+    }
+
+    /// Core age, 0 (pristine) to 1 (worn), toward the model's `WornCore`:
+    /// the band narrows at both ends, a bias on the saturators' input tips
+    /// the curve so it adds even harmonics (strongest where the stages
+    /// already saturate), and noise rides on the signal the way a tired
+    /// core's Barkhausen noise does. At 0 the module is exactly as before.
+    /// Takes effect at the next `update_parameters`.
+    pub fn set_age(&mut self, age: f32) {
+        let age = age.clamp(0.0, 1.0);
+        if self.age == 0.0 && age > 0.0 {
+            // The band-limiting filters sat idle; don't resume from
+            // whatever they last held.
+            for filter in self.age_low_cut.iter_mut().chain(&mut self.age_high_cut) {
+                filter.reset_state();
+            }
+        }
+        self.age = age;
+    }
+
     /// Update transformer parameters
     pub fn update_parameters(
         &mut self,
@@ -197,20 +454,34 @@ impl TransformerModule {
         self.model = model;
 
         // Input transformer settings - much gentler
-        self.input_transformer.drive_gain = 1.0 + input_drive * 0.8; // 1x to 1.8x gain
+        self.input_transformer.drive_gain =
+            (1.0 + input_drive * 0.8) * self.variance.factor(VAR_INPUT_DRIVE); // 1x to 1.8x gain
         self.input_transformer.saturation_amount = input_saturation * 0.6; // Reduce saturation
         self.input_transformer.compression_amount = transformer_compression * 0.3; // Less compression on input
 
         // Output transformer settings - also gentler
-        self.output_transformer.drive_gain = 1.0 + output_drive * 0.6; // 1x to 1.6x gain
+        self.output_transformer.drive_gain =
+            (1.0 + output_drive * 0.6) * self.variance.factor(VAR_OUTPUT_DRIVE); // 1x to 1.6x gain
         self.output_transformer.saturation_amount = output_saturation * 0.5; // Reduce saturation
         self.output_transformer.compression_amount = transformer_compression * 0.7;
 
-        // Only recompute filter coefficients when model or response values change.
+        let worn = model.worn_core();
+        self.input_transformer.bias = self.age * worn.bias;
+        self.output_transformer.bias = self.age * worn.bias;
+        self.age_noise = self.age * 10.0_f32.powf(worn.noise_db / 20.0);
+        if self.age != self.cached_age || model != self.cached_model {
+            self.cached_age = self.age;
+            self.update_age_response(&worn);
+        }
+
+        // Only recompute filter coefficients when model or response values
+        // change, or when the vintage variance drifted.
         // Comparing f32 for exact equality is valid here: we are checking whether
         // the stored parameter value (same f32 bits) has been updated by the host,
         // not comparing computed results where rounding would be an issue.
-        if model != self.cached_model
+        let drifted = self.variance.take_dirty();
+        if drifted
+            || model != self.cached_model
             || low_frequency_response != self.cached_low_response
             || high_frequency_response != self.cached_high_response
         {
@@ -225,7 +496,7 @@ impl TransformerModule {
     ///
     /// Uses `update_coefficients()` on existing filter objects — no state reset,
     /// no heap allocation. Called only when model or response values change
-    /// (guarded in `update_parameters()`).
+    /// or the variance drifted (guarded in `update_parameters()`).
     fn update_frequency_response(&mut self, low_response: f32, high_response: f32) {
         let low_freq = match self.model {
             TransformerModel::Vintage => 80.0,
@@ -234,11 +505,14 @@ impl TransformerModule {
             TransformerModel::American => 70.0,
         };
         // Always update (even at 0 dB) so that model changes take effect immediately.
-        let low_gain = low_response * 3.0; // ±3 dB
+        let low_freq = low_freq * self.variance.factor(VAR_LOW_FREQ);
+        let low_gain = (low_response * 3.0 * self.variance.factor(VAR_LOW_GAIN)) as f64; // ±3 dB
         if let Ok(coeff) =
-            biquad_coeffs(Type::LowShelf(low_gain), self.sample_rate, low_freq, 0.707)
+            biquad_coeffs_f64(Type::LowShelf(low_gain), self.sample_rate, low_freq, 0.707)
         {
-            self.low_shelf.update_coefficients(coeff);
+            for shelf in &mut self.low_shelf {
+                shelf.update_coefficients(coeff);
+            }
         }
 
         let high_freq = match self.model {
@@ -247,26 +521,83 @@ impl TransformerModule {
             TransformerModel::British => 12000.0,
             TransformerModel::American => 10000.0,
         };
-        let high_gain = high_response * 2.0; // ±2 dB
-        if let Ok(coeff) = biquad_coeffs(
+        let high_freq = high_freq * self.variance.factor(VAR_HIGH_FREQ);
+        let high_gain = (high_response * 2.0 * self.variance.factor(VAR_HIGH_GAIN)) as f64; // ±2 dB
+        if let Ok(coeff) = biquad_coeffs_f64(
             Type::HighShelf(high_gain),
             self.sample_rate,
             high_freq,
             0.707,
         ) {
-            self.high_shelf.update_coefficients(coeff);
+            for shelf in &mut self.high_shelf {
+                shelf.update_coefficients(coeff);
+            }
+        }
+    }
+
+    /// Move the band-limiting filters `age` of the way, on a log scale,
+    /// from the pristine corners to `worn`'s.
+    fn update_age_response(&mut self, worn: &WornCore) {
+        let nyquist_guard = 0.45 * self.sample_rate;
+        let toward = |pristine: f32, worn: f32| pristine * (worn / pristine).powf(self.age);
+        let low = toward(PRISTINE_LOW_CUT_HZ, worn.low_cut_hz);
+        let high = toward(PRISTINE_HIGH_CUT_HZ, worn.high_cut_hz).min(nyquist_guard);
+        if let Ok(coeff) = biquad_coeffs_f64(Type::HighPass, self.sample_rate, low, 0.707) {
+            for filter in &mut self.age_low_cut {
+                filter.update_coefficients(coeff);
+            }
+        }
+        if let Ok(coeff) = biquad_coeffs_f64(Type::LowPass, self.sample_rate, high, 0.707) {
+            for filter in &mut self.age_high_cut {
+                filter.update_coefficients(coeff);
+            }
         }
     }
 
     /// Process audio buffer through transformer module
+    #[cfg(feature = "plugin")]
     pub fn process(&mut self, buffer: &mut Buffer) {
+        self.process_channels(buffer.as_slice());
+    }
+
+    /// Process one block given as per-channel slices. Channels past the
+    /// second share the right channel's state.
+    pub fn process_channels(&mut self, channels: &mut [&mut [f32]]) {
         // Stack scratch for the oversampled saturation path. Reused across
         // every sample; the oversampler writes `TRANSFORMER_OS_FACTOR` values
         // in and reads them back before the next call overwrites.
         let mut scratch = [0.0_f32; TRANSFORMER_OS_FACTOR];
-        for mut samples in buffer.iter_samples() {
-            for (ch, sample) in samples.iter_mut().enumerate() {
+        let len = channels.first().map_or(0, |ch| ch.len());
+        self.variance.advance(len);
+        let mid_side = self.mono_safe && self.linked && channels.len() >= 2;
+        if mid_side {
+            encode_mid_side(channels);
+        }
+        for i in 0..len {
+            let mut noise = [0.0; 2];
+            if self.age > 0.0 {
+                let draws = if self.linked { 1 } else { 2 };
+                for (n, rng) in noise.iter_mut().zip(&mut self.age_rng).take(draws) {
+                    *rng ^= *rng << 13;
+                    *rng ^= *rng >> 17;
+                    *rng ^= *rng << 5;
+                    let uniform = (*rng >> 8) as f32 / (1u32 << 24) as f32 - 0.5;
+                    // A uniform draw has an RMS of 1/√12.
+                    *n = uniform * 12.0_f32.sqrt() * self.age_noise;
+                }
+                if self.linked {
+                    noise[1] = noise[0];
+                }
+            }
+            for (ch, channel) in channels.iter_mut().enumerate() {
                 let ch = ch.min(1);
+                let follower = if self.linked { 0 } else { ch };
+                let amount_scale = if mid_side && ch == 1 {
+                    SIDE_SATURATION
+                } else {
+                    1.0
+                };
+                let sample = &mut channel[i];
                 let mut s = *sample;
 
                 // 1. Input transformer stage (oversampled saturation)
@@ -275,13 +606,20 @@ impl TransformerModule {
                 } else {
                     &mut self.input_os_r
                 };
-                s = self
-                    .input_transformer
-                    .process_sample(s, self.model, in_os, &mut scratch);
+                let in_adaa = self.adaa.then_some(&mut self.input_adaa[ch]);
+                s = self.input_transformer.process_sample(
+                    s,
+                    self.model,
+                    in_os,
+                    in_adaa,
+                    &mut scratch,
+                    amount_scale,
+                    follower,
+                );
 
                 // 2. Frequency response modeling (native rate)
-                s = self.low_shelf.run(s);
-                s = self.high_shelf.run(s);
+                let shelved = self.high_shelf[ch].run(self.low_shelf[ch].run(s as f64));
+                s = shelved as f32;
 
                 // 3. Output transformer stage (oversampled saturation)
                 let out_os = if ch == 0 {
@@ -289,25 +627,130 @@ impl TransformerModule {
                 } else {
                     &mut self.output_os_r
                 };
-                s = self
-                    .output_transformer
-                    .process_sample(s, self.model, out_os, &mut scratch);
+                let out_adaa = self.adaa.then_some(&mut self.output_adaa[ch]);
+                s = self.output_transformer.process_sample(
+                    s,
+                    self.model,
+                    out_os,
+                    out_adaa,
+                    &mut scratch,
+                    amount_scale,
+                    follower,
+                );
+
+                // 4. Core age: modulation noise, then the worn band limits.
+                // The low cut also takes out the DC the bias leaves.
+                if self.age > 0.0 {
+                    s += noise[ch] * s.abs();
+                    let limited = self.age_high_cut[ch].run(self.age_low_cut[ch].run(s as f64));
+                    s = limited as f32;
+                }
 
                 *sample = s;
+                self.engagement
+                    .tally(self.input_transformer.bent || self.output_transformer.bent);
             }
         }
+        if mid_side {
+            decode_mid_side(channels);
+        }
+
+        self.update_sat_activity();
+    }
+
+    /// Fold this block's saturation accumulators into the activity meter.
+    fn update_sat_activity(&mut self) {
+        let distortion =
+            self.input_transformer.distortion_energy + self.output_transformer.distortion_energy;
+        let drive = self.input_transformer.drive_energy + self.output_transformer.drive_energy;
+        for stage in [&mut self.input_transformer, &mut self.output_transformer] {
+            stage.distortion_energy = 0.0;
+            stage.drive_energy = 0.0;
+        }
+
+        let ratio = if drive > 1e-12 {
+            (distortion / drive).sqrt().min(1.0)
+        } else {
+            0.0
+        };
+        self.sat_activity = if ratio > self.sat_activity {
+            ratio
+        } else {
+            self.sat_activity * SAT_METER_RELEASE + ratio * (1.0 - SAT_METER_RELEASE)
+        };
+    }
+
+    /// Saturation activity for the GUI drive meter: RMS of what the
+    /// saturators changed relative to the RMS they were fed (0 = linear,
+    /// 0.1 ≈ harmonics 20 dB below the signal).
+    pub fn saturation_activity(&self) -> f32 {
+        self.sat_activity
+    }
+
+    /// One trip through the oversampler per stage that saturates; a stage
+    /// below its threshold, or either under ADAA, passes straight through.
+    pub fn cascade_delay(&self) -> CascadeDelay {
+        if self.adaa {
+            return CascadeDelay::NONE;
+        }
+        let passes = [&self.input_transformer, &self.output_transformer]
+            .iter()
+            .filter(|stage| stage.saturation_amount >= 0.01)
+            .count();
+        CascadeDelay {
+            factor: self.input_os_l.factor(),
+            passes,
+        }
+    }
+
+    /// How often either stage engaged since the last call; see
+    /// engagement.rs.
+    pub fn take_engagement(&mut self) -> Engagement {
+        self.engagement.take()
     }
 
     /// Reset transformer state
     pub fn reset(&mut self) {
-        self.input_transformer.envelope = 0.0;
+        self.input_transformer.envelope.reset();
         self.input_transformer.harmonic_state = 0.0;
-        self.output_transformer.envelope = 0.0;
+        self.output_transformer.envelope.reset();
         self.output_transformer.harmonic_state = 0.0;
+        for stage in [&mut self.input_transformer, &mut self.output_transformer] {
+            stage.envelope_r.reset();
+            stage.distortion_energy = 0.0;
+            stage.drive_energy = 0.0;
+        }
+        self.sat_activity = 0.0;
+        self.engagement = Engagement::default();
         self.input_os_l.reset();
         self.input_os_r.reset();
         self.output_os_l.reset();
         self.output_os_r.reset();
+        self.input_adaa = [Adaa1::new(); 2];
+        self.output_adaa = [Adaa1::new(); 2];
+        for filter in self.age_low_cut.iter_mut().chain(&mut self.age_high_cut) {
+            filter.reset_state();
+        }
+        self.variance.reset();
+    }
+}
+
+/// L/R → M/S in place on the first two channels: M = (L + R) / 2,
+/// S = (L − R) / 2.
+fn encode_mid_side(channels: &mut [&mut [f32]]) {
+    if let [left, right, ..] = channels {
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            (*l, *r) = (0.5 * (*l + *r), 0.5 * (*l - *r));
+        }
+    }
+}
+
+/// M/S → L/R in place, the inverse of `encode_mid_side`.
+fn decode_mid_side(channels: &mut [&mut [f32]]) {
+    if let [mid, side, ..] = channels {
+        for (m, s) in mid.iter_mut().zip(side.iter_mut()) {
+            (*m, *s) = (*m + *s, *m - *s);
+        }
     }
 }
 
@@ -323,6 +766,82 @@ fn saturate_by_model(input: f32, amount: f32, model: TransformerModel) -> f32 {
     }
 }
 
+/// The model curve with its input biased by `bias` and shifted back so
+/// silence stays silent: f(x + b) − f(b). Zero bias is the plain curve.
+#[inline]
+fn saturate_biased(input: f32, amount: f32, model: TransformerModel, bias: f32) -> f32 {
+    if bias == 0.0 {
+        return saturate_by_model(input, amount, model);
+    }
+    saturate_by_model(input + bias, amount, model) - saturate_by_model(bias, amount, model)
+}
+
+/// One model's saturation curve at a given amount and bias, with the
+/// closed-form antiderivative the ADAA path needs. Each term of
+/// `unbiased_ad1` integrates the matching term of the model function
+/// below, with the same constants.
+struct ModelCurve {
+    model: TransformerModel,
+    amount: f32,
+    bias: f32,
+}
+
+impl Antiderivative for ModelCurve {
+    #[inline]
+    fn eval(&self, x: f64) -> f64 {
+        saturate_biased(x as f32, self.amount, self.model, self.bias) as f64
+    }
+
+    fn ad1(&self, x: f64) -> f64 {
+        // ∫ f(x + b) − f(b) dx = F(x + b) − f(b)·x
+        let b = self.bias as f64;
+        let offset = saturate_by_model(self.bias, self.amount, self.model) as f64;
+        self.unbiased_ad1(x + b) - offset * x
+    }
+}
+
+impl ModelCurve {
+    fn unbiased_ad1(&self, x: f64) -> f64 {
+        let a = self.amount as f64;
+        if self.amount < 0.01 {
+            return 0.5 * x * x;
+        }
+        let abs3 = |x: f64| x.abs().powi(3) / 3.0; // ∫ x·|x|
+        match self.model {
+            TransformerModel::Vintage => {
+                let g = 1.0 + a * 2.0;
+                let wet = ln_cosh(g * x) / g + a * 0.1 * g * g * abs3(x);
+                (1.0 - a * 0.7) * 0.5 * x * x + a * 0.7 * wet
+            }
+            TransformerModel::Modern => {
+                let g = 1.0 + a * 1.5;
+                let d = g * x;
+                let c = if d > 0.0 { a } else { a * 0.8 };
+                let wet = (c * d * d).ln_1p() / (2.0 * c * g);
+                (1.0 - a * 0.5) * 0.5 * x * x + a * 0.5 * wet
+            }
+            TransformerModel::British => {
+                let g = 1.0 + a * 1.2;
+                let b = a * 0.8;
+                let d = (g * x).abs();
+                let wet = (d / b - (b * d).ln_1p() / (b * b)) / g + a * 0.05 * g * g * abs3(x);
+                (1.0 - a * 0.6) * 0.5 * x * x + a * 0.6 * wet
+            }
+            TransformerModel::American => {
+                let g = 1.0 + a * 1.8;
+                let d = (g * x).abs();
+                let soft = if d > 0.5 {
+                    0.125 + 0.5 * (d - 0.5) + 0.5 * ln_cosh(d - 0.5)
+                } else {
+                    0.5 * d * d
+                };
+                let wet = soft / g + a * 0.08 * g.powi(3) * x.powi(4) / 4.0;
+                (1.0 - a * 0.6) * 0.5 * x * x + a * 0.6 * wet
+            }
+        }
+    }
+}
+
 // Transformer saturation models
 
 /// Vintage transformer saturation (Neve-style)
@@ -585,12 +1104,12 @@ mod tests {
     fn test_transformer_module_reset_clears_envelopes() {
         let mut t = TransformerModule::new(44100.0);
         t.update_parameters(TransformerModel::Vintage, 0.5, 0.8, 0.5, 0.8, 0.0, 0.0, 0.5);
-        // Manually set envelope state
-        t.input_transformer.envelope = 0.9;
-        t.output_transformer.envelope = 0.7;
+        // Build up some envelope state
+        t.input_transformer.envelope.follow(0.9);
+        t.output_transformer.envelope.follow(0.7);
         t.reset();
-        assert!((t.input_transformer.envelope - 0.0).abs() < 1e-9);
-        assert!((t.output_transformer.envelope - 0.0).abs() < 1e-9);
+        assert!((t.input_transformer.envelope.value() - 0.0).abs() < 1e-9);
+        assert!((t.output_transformer.envelope.value() - 0.0).abs() < 1e-9);
     }
 
     #[test]
@@ -640,15 +1159,304 @@ mod tests {
         let mut scratch = [0.0_f32; TRANSFORMER_OS_FACTOR];
         let mut os = Oversampler::new(TRANSFORMER_OS_FACTOR, 1);
         os.set_factor(TRANSFORMER_OS_FACTOR);
-        let mut stage = TransformerStage::new();
+        let mut stage = TransformerStage::new(44100.0);
         stage.drive_gain = 1.8;
         stage.saturation_amount = 0.6;
         stage.compression_amount = 0.3;
         for i in 0..1024 {
             let x = (2.0 * core::f32::consts::PI * 0.4 * i as f32).sin(); // ~17.6 kHz
-            let y = stage.process_sample(x, TransformerModel::Vintage, &mut os, &mut scratch);
+            let y = stage.process_sample(
+                x,
+                TransformerModel::Vintage,
+                &mut os,
+                None,
+                &mut scratch,
+                1.0,
+                0,
+            );
             assert!(y.is_finite(), "non-finite sample {y} at i={i}");
             assert!(y.abs() < 10.0, "implausibly large sample {y} at i={i}");
         }
     }
+
+    /// Run `left`/`right` through a hot transformer (no loading
+    /// compression), in mono-safe mode or not.
+    fn render_pair(mono_safe: bool, left: &[f32], right: &[f32]) -> (Vec<f32>, Vec<f32>, f32) {
+        let mut t = TransformerModule::new(48000.0);
+        t.set_mono_safe(mono_safe);
+        t.update_parameters(TransformerModel::Vintage, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0);
+        let (mut l, mut r) = (left.to_vec(), right.to_vec());
+        t.process_channels(&mut [&mut l[..], &mut r[..]]);
+        (l, r, t.saturation_activity())
+    }
+
+    #[test]
+    fn test_mono_safe_leaves_mono_alone_and_spares_the_side() {
+        let tone: Vec<f32> = (0..4800)
+            .map(|i| 0.8 * (std::f32::consts::TAU * 220.0 * i as f32 / 48000.0).sin())
+            .collect();
+        // Mono in: both modes agree.
+        let (l, r, _) = render_pair(false, &tone, &tone);
+        let (ms_l, ms_r, _) = render_pair(true, &tone, &tone);
+        for (a, b) in l.iter().zip(&ms_l).chain(r.iter().zip(&ms_r)) {
+            assert!((a - b).abs() < 1e-5, "{a} vs {b}");
+        }
+        // Pure side in: mono-safe bends it far less.
+        let inverted: Vec<f32> = tone.iter().map(|x| -x).collect();
+        let (_, _, lr_activity) = render_pair(false, &tone, &inverted);
+        let (_, _, ms_activity) = render_pair(true, &tone, &inverted);
+        assert!(
+            ms_activity < 0.6 * lr_activity,
+            "side activity {ms_activity} vs L/R {lr_activity}"
+        );
+    }
+
+    #[test]
+    fn test_unlinked_channels_load_on_their_own() {
+        // Hot left, quiet right, with loading compression. Linked, the
+        // left's level loads the right too; unlinked, the right comes out
+        // as it would beside a silent left.
+        let hot: Vec<f32> = (0..4800)
+            .map(|i| 0.95 * (std::f32::consts::TAU * 220.0 * i as f32 / 48000.0).sin())
+            .collect();
+        let quiet: Vec<f32> = hot.iter().map(|x| 0.2 * x).collect();
+        let render = |linked: bool, left: &[f32]| {
+            let mut t = TransformerModule::new(48000.0);
+            t.set_linked(linked);
+            t.update_parameters(TransformerModel::Vintage, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0);
+            let (mut l, mut r) = (left.to_vec(), quiet.clone());
+            t.process_channels(&mut [&mut l[..], &mut r[..]]);
+            r
+        };
+        let alone = render(false, &vec![0.0; 4800]);
+        let unlinked = render(false, &hot);
+        assert!(unlinked
+            .iter()
+            .zip(&alone)
+            .all(|(a, b)| (a - b).abs() < 1e-6));
+        let linked = render(true, &hot);
+        let moved = linked
+            .iter()
+            .zip(&alone)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0_f32, f32::max);
+        assert!(moved > 1e-3, "linked right unmoved: {moved}");
+    }
+
+    #[test]
+    fn test_transformer_high_sample_rates_run_fewer_os_stages() {
+        for (sr, factor) in [(88200.0, 2), (96000.0, 2), (192000.0, 1)] {
+            let mut t = TransformerModule::new(sr);
+            assert_eq!(t.input_os_l.factor(), factor, "at {sr}");
+            t.update_parameters(TransformerModel::Vintage, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0);
+            let mut scratch = [0.0_f32; TRANSFORMER_OS_FACTOR];
+            for i in 0..1024 {
+                let x = 0.9 * (2.0 * core::f32::consts::PI * 0.2 * i as f32).sin();
+                let y = t.input_transformer.process_sample(
+                    x,
+                    TransformerModel::Vintage,
+                    &mut t.input_os_l,
+                    None,
+                    &mut scratch,
+                    1.0,
+                    0,
+                );
+                assert!(y.is_finite() && y.abs() < 10.0, "{y} at i={i}, sr={sr}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_sat_activity_tracks_drive_and_decays() {
+        let mut t = TransformerModule::new(44100.0);
+        t.update_parameters(TransformerModel::Vintage, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0);
+        let mut scratch = [0.0_f32; TRANSFORMER_OS_FACTOR];
+        for i in 0..512 {
+            let x = 0.9 * (2.0 * core::f32::consts::PI * 0.01 * i as f32).sin();
+            t.input_transformer.process_sample(
+                x,
+                TransformerModel::Vintage,
+                &mut t.input_os_l,
+                None,
+                &mut scratch,
+                1.0,
+                0,
+            );
+        }
+        t.update_sat_activity();
+        let hot = t.saturation_activity();
+        assert!(hot > 0.01, "hot drive should register, got {hot}");
+        assert!(hot <= 1.0);
+
+        // Silent blocks release the meter instead of dropping it to zero.
+        t.update_sat_activity();
+        let released = t.saturation_activity();
+        assert!(released < hot && released > 0.0);
+
+        t.reset();
+        assert_eq!(t.saturation_activity(), 0.0);
+    }
+
+    #[test]
+    fn test_engagement_counts_each_native_sample_once() {
+        let mut t = TransformerModule::new(44100.0);
+        let omega = 2.0 * core::f32::consts::PI * 1000.0 / 44100.0;
+        let sine = |amplitude: f32| -> Vec<f32> {
+            (0..1024)
+                .map(|i| amplitude * (omega * i as f32).sin())
+                .collect()
+        };
+
+        // No saturation: every sample counted, none engaged.
+        t.update_parameters(TransformerModel::Vintage, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let (mut l, mut r) = (sine(0.9), sine(0.9));
+        t.process_channels(&mut [&mut l[..], &mut r[..]]);
+        let clean = t.take_engagement();
+        assert_eq!(
+            clean.total, 2048,
+            "once per sample, not per oversampled frame"
+        );
+        assert_eq!(clean.engaged, 0);
+
+        t.update_parameters(TransformerModel::Vintage, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0);
+        let (mut l, mut r) = (sine(0.9), sine(0.9));
+        t.process_channels(&mut [&mut l[..], &mut r[..]]);
+        let hot = t.take_engagement().fraction().unwrap();
+        assert!(hot > 0.5, "full drive bends most of a hot sine, got {hot}");
+    }
+
+    #[test]
+    fn test_low_shelf_holds_its_gain_at_high_sample_rates() {
+        let mut t = TransformerModule::new(192000.0);
+        // Modern: +3 dB shelf at 60 Hz, everything else neutral.
+        t.update_parameters(TransformerModel::Modern, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+        let mut y = 0.0;
+        for _ in 0..192_000 {
+            y = t.low_shelf[0].run(1.0);
+        }
+        let gain_db = 20.0 * y.log10();
+        assert!((gain_db - 3.0).abs() < 0.01, "DC gain: {gain_db} dB");
+        // Each channel keeps its own state.
+        assert_eq!(t.low_shelf[1].run(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_model_curve_antiderivatives_differentiate_back() {
+        let models = [
+            TransformerModel::Vintage,
+            TransformerModel::Modern,
+            TransformerModel::British,
+            TransformerModel::American,
+        ];
+        let h = 1e-4;
+        for model in models {
+            for (amount, bias) in [(0.0, 0.0), (0.05, 0.0), (0.3, 0.0), (0.6, 0.0), (0.6, 0.15)] {
+                let curve = ModelCurve {
+                    model,
+                    amount,
+                    bias,
+                };
+                for i in -30..=30 {
+                    let x = i as f64 * 0.1 + 0.013;
+                    let slope = (curve.ad1(x + h) - curve.ad1(x - h)) / (2.0 * h);
+                    let expected = saturate_biased(x as f32, amount, model, bias) as f64;
+                    assert!(
+                        (slope - expected).abs() < 1e-4 * expected.abs().max(1.0),
+                        "{model:?} amount {amount} bias {bias} at {x}: {slope} vs {expected}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_transformer_adaa_matches_oversampled_level() {
+        // At 200 Hz neither anti-aliasing path changes the tone; the ADAA
+        // module should land within a fraction of a dB of the oversampled one.
+        let render = |adaa: bool| {
+            let mut t = TransformerModule::new(48000.0);
+            t.set_adaa(adaa);
+            t.update_parameters(TransformerModel::British, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0);
+            let mut l: Vec<f32> = (0..48000)
+                .map(|i| 0.8 * (std::f32::consts::TAU * 200.0 * i as f32 / 48000.0).sin())
+                .collect();
+            let mut r = l.clone();
+            t.process_channels(&mut [&mut l[..], &mut r[..]]);
+            (l[24000..].iter().map(|s| s * s).sum::<f32>() / 24000.0).sqrt()
+        };
+        let (oversampled, adaa) = (render(false), render(true));
+        let diff_db = 20.0 * (adaa / oversampled).log10();
+        assert!(diff_db.abs() < 0.1, "ADAA is {diff_db} dB off");
+    }
+
+    /// Amplitude of `freq` in `x` at 48 kHz, by correlation over whole
+    /// periods.
+    fn tone_level(x: &[f32], freq: f32) -> f32 {
+        let (mut re, mut im) = (0.0, 0.0);
+        for (i, s) in x.iter().enumerate() {
+            let phase = std::f32::consts::TAU * freq * i as f32 / 48000.0;
+            re += s * phase.cos();
+            im += s * phase.sin();
+        }
+        2.0 * (re * re + im * im).sqrt() / x.len() as f32
+    }
+
+    /// One second of `freq` at `level` through Vintage at moderate drive,
+    /// aged `age`; returns the second half.
+    fn render_aged(age: f32, freq: f32, level: f32) -> Vec<f32> {
+        let mut t = TransformerModule::new(48000.0);
+        t.set_age(age);
+        t.update_parameters(TransformerModel::Vintage, 0.5, 0.5, 0.5, 0.5, 0.0, 0.0, 0.0);
+        let mut x: Vec<f32> = (0..48000)
+            .map(|i| level * (std::f32::consts::TAU * freq * i as f32 / 48000.0).sin())
+            .collect();
+        t.process_channels(&mut [&mut x[..]]);
+        x.split_off(24000)
+    }
+
+    #[test]
+    fn test_age_wears_the_extremes_and_adds_even_harmonics() {
+        // Band edges: flat when new, clearly down when worn (Vintage wears
+        // to 40 Hz and 9 kHz).
+        for freq in [20.0, 16_000.0] {
+            let new = tone_level(&render_aged(0.0, freq, 0.1), freq);
+            let worn = tone_level(&render_aged(1.0, freq, 0.1), freq);
+            assert!(worn < 0.6 * new, "{freq} Hz: worn {worn} vs new {new}");
+        }
+        let new = tone_level(&render_aged(0.0, 1000.0, 0.1), 1000.0);
+        let worn = tone_level(&render_aged(1.0, 1000.0, 0.1), 1000.0);
+        assert!((worn / new - 1.0).abs() < 0.05, "1 kHz: {worn} vs {new}");
+
+        // The bias tips the curve: the 2nd harmonic grows with age.
+        let second = |age: f32| tone_level(&render_aged(age, 1000.0, 0.7), 2000.0);
+        let (new, half, worn) = (second(0.0), second(0.5), second(1.0));
+        assert!(
+            half > 2.0 * new && worn > half,
+            "2nd harmonic {new} → {half} → {worn}"
+        );
+    }
+
+    #[test]
+    fn test_age_noise_rides_on_the_signal() {
+        // Silence stays silent: the noise is modulated by the signal.
+        assert!(render_aged(1.0, 1000.0, 0.0).iter().all(|&s| s == 0.0));
+        // With the saturators idle, whatever isn't the tone is the noise:
+        // well below the tone when worn, nothing when new.
+        let residual = |age: f32| {
+            let mut t = TransformerModule::new(48000.0);
+            t.set_age(age);
+            t.update_parameters(TransformerModel::Vintage, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+            let mut x: Vec<f32> = (0..48000)
+                .map(|i| 0.5 * (std::f32::consts::TAU * 1000.0 * i as f32 / 48000.0).sin())
+                .collect();
+            t.process_channels(&mut [&mut x[..]]);
+            let x = &x[24000..];
+            let tone = tone_level(x, 1000.0);
+            let power = x.iter().map(|&s| s as f64 * s as f64).sum::<f64>() / x.len() as f64;
+            (power - 0.5 * (tone * tone) as f64).max(0.0).sqrt() as f32 / tone
+        };
+        let (new, worn) = (residual(0.0), residual(1.0));
+        assert!(new < 1e-3, "new residual {new}");
+        assert!(worn > 1e-3 && worn < 0.01, "worn residual {worn}");
+    }
 }
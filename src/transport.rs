@@ -0,0 +1,78 @@
+// src/transport.rs
+// Transport discontinuity detection.
+//
+// Loop playback wraps the song position back to the loop start. Compressor
+// and DynEQ envelopes (and the analyzer's averages) would otherwise carry
+// the gain reduction from the end of the loop across the seam into its
+// first beat, so each pass sounds different from the next. The watcher
+// reports when playback starts or its position lands somewhere other than
+// where the previous buffer ended; `lib.rs` then clears the envelopes.
+
+/// Position error tolerated before a buffer counts as a jump. Some hosts
+/// derive the sample position from beats and drift by a sample or two.
+const JUMP_TOLERANCE: i64 = 64;
+
+#[derive(Default)]
+pub struct TransportWatch {
+    was_playing: bool,
+    /// Where the next buffer should start if playback simply continues.
+    expected_pos: Option<i64>,
+}
+
+impl TransportWatch {
+    /// Feed one host buffer's transport state. True when playback just
+    /// started or jumped (loop seam, locate, scrub) since the last buffer.
+    pub fn update(&mut self, playing: bool, pos_samples: Option<i64>, samples: usize) -> bool {
+        let started = playing && !self.was_playing;
+        let jumped = match (playing && self.was_playing, pos_samples, self.expected_pos) {
+            (true, Some(pos), Some(expected)) => (pos - expected).abs() > JUMP_TOLERANCE,
+            _ => false,
+        };
+        self.was_playing = playing;
+        self.expected_pos = match (playing, pos_samples) {
+            (true, Some(pos)) => Some(pos + samples as i64),
+            _ => None,
+        };
+        started || jumped
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn continuous_playback_is_not_a_jump() {
+        let mut watch = TransportWatch::default();
+        assert!(!watch.update(false, Some(0), 512));
+        assert!(watch.update(true, Some(0), 512), "start of playback");
+        for block in 1..100 {
+            assert!(!watch.update(true, Some(block * 512), 512));
+        }
+        // Drift of a sample or two from beat-derived positions is ignored.
+        assert!(!watch.update(true, Some(100 * 512 + 2), 512));
+    }
+
+    #[test]
+    fn loop_seam_and_restart_are_reported() {
+        let mut watch = TransportWatch::default();
+        watch.update(true, Some(48_000), 480);
+        assert!(!watch.update(true, Some(48_480), 480));
+        assert!(watch.update(true, Some(48_000), 480), "loop seam");
+        assert!(!watch.update(true, Some(48_480), 480));
+        assert!(!watch.update(false, Some(48_960), 480), "stop");
+        assert!(watch.update(true, Some(0), 480), "restart");
+    }
+
+    #[test]
+    fn hosts_without_a_position_only_report_starts() {
+        let mut watch = TransportWatch::default();
+        assert!(watch.update(true, None, 256));
+        assert!(!watch.update(true, None, 256));
+        assert!(!watch.update(true, None, 256));
+    }
+}
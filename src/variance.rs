@@ -0,0 +1,190 @@
+// src/variance.rs
+// Vintage variance: component tolerance and thermal drift for the analog
+// models (Pultec, Transformer).
+//
+// Each modelled component (a corner frequency, a gain) gets a scale factor
+// within ±TOLERANCE of 1. Three quarters of the spread is a fixed tolerance,
+// as if the unit came off the line a little off-spec; the rest is a slow
+// drift, each component on its own 30–90 s cycle, like parts warming up.
+//
+// Everything comes from a fixed per-module seed and the number of samples
+// processed since `reset()`, so a render from the start of a session comes
+// out bit-identical every time.
+
+/// Largest deviation of any component from its nominal value.
+pub const TOLERANCE: f32 = 0.02;
+/// Share of the spread that is fixed tolerance; the rest drifts.
+const STATIC_SHARE: f32 = 0.75;
+/// Drift cycle range, in seconds.
+const DRIFT_PERIOD_MIN_S: f32 = 30.0;
+const DRIFT_PERIOD_MAX_S: f32 = 90.0;
+/// Factor change that asks the owner to recompute its coefficients. Drift
+/// is slow, so this refreshes a handful of times per second at most.
+const REFRESH_STEP: f32 = 1.0e-4;
+
+/// `N` components' worth of tolerance and drift.
+pub struct Variance<const N: usize> {
+    enabled: bool,
+    sample_rate: f32,
+    /// Fixed tolerance per component, in [-1, 1].
+    tolerance: [f32; N],
+    /// Drift phase per component at `reset()`, in cycles.
+    phase: [f32; N],
+    /// Drift cycle length per component, in seconds.
+    period_s: [f32; N],
+    /// Samples processed since `reset()`.
+    elapsed: u64,
+    /// Factors at the last refresh.
+    factors: [f32; N],
+    dirty: bool,
+}
+
+impl<const N: usize> Variance<N> {
+    pub fn new(seed: u32, sample_rate: f32) -> Self {
+        let mut rng = seed;
+        let mut unit = || {
+            // xorshift32; a zero seed would stick at zero.
+            rng = if rng == 0 { 0x9E37_79B9 } else { rng };
+            rng ^= rng << 13;
+            rng ^= rng >> 17;
+            rng ^= rng << 5;
+            rng as f32 / u32::MAX as f32
+        };
+        let tolerance = std::array::from_fn(|_| unit() * 2.0 - 1.0);
+        let phase = std::array::from_fn(|_| unit());
+        let period_s = std::array::from_fn(|_| {
+            DRIFT_PERIOD_MIN_S + unit() * (DRIFT_PERIOD_MAX_S - DRIFT_PERIOD_MIN_S)
+        });
+        Self {
+            enabled: false,
+            sample_rate,
+            tolerance,
+            phase,
+            period_s,
+            elapsed: 0,
+            factors: [1.0; N],
+            dirty: false,
+        }
+    }
+
+    /// Turn variance on or off. Off, every factor is exactly 1.
+    pub fn set_enabled(&mut self, on: bool) {
+        if on != self.enabled {
+            self.enabled = on;
+            self.refresh();
+        }
+    }
+
+    /// Restart the drift from its seeded phase.
+    pub fn reset(&mut self) {
+        self.elapsed = 0;
+        self.refresh();
+    }
+
+    /// Account for `samples` processed; flags a refresh once the drift
+    /// has moved far enough to matter.
+    pub fn advance(&mut self, samples: usize) {
+        if !self.enabled {
+            return;
+        }
+        self.elapsed += samples as u64;
+        let moved = self
+            .compute()
+            .iter()
+            .zip(&self.factors)
+            .any(|(new, old)| (new - old).abs() > REFRESH_STEP);
+        if moved {
+            self.refresh();
+        }
+    }
+
+    /// True once after the factors changed: the owner should recompute
+    /// whatever it derives from them.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Scale factor for component `i` (1 ± TOLERANCE).
+    pub fn factor(&self, i: usize) -> f32 {
+        self.factors[i]
+    }
+
+    fn refresh(&mut self) {
+        self.factors = self.compute();
+        self.dirty = true;
+    }
+
+    fn compute(&self) -> [f32; N] {
+        if !self.enabled {
+            return [1.0; N];
+        }
+        let t = self.elapsed as f64 / self.sample_rate as f64;
+        std::array::from_fn(|i| {
+            let cycles = self.phase[i] as f64 + t / self.period_s[i] as f64;
+            let drift = (std::f64::consts::TAU * cycles.fract()).sin() as f32;
+            let spread = STATIC_SHARE * self.tolerance[i] + (1.0 - STATIC_SHARE) * drift;
+            1.0 + TOLERANCE * spread
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 48_000.0;
+
+    #[test]
+    fn off_is_exactly_nominal() {
+        let mut v = Variance::<4>::new(7, SR);
+        v.advance(480_000);
+        assert!((0..4).all(|i| v.factor(i) == 1.0));
+    }
+
+    #[test]
+    fn factors_stay_within_tolerance_and_drift_slowly() {
+        let mut v = Variance::<8>::new(0x5EED, SR);
+        v.set_enabled(true);
+        assert!(v.take_dirty());
+        let start: Vec<f32> = (0..8).map(|i| v.factor(i)).collect();
+        let mut refreshes = 0;
+        // Two minutes in 512-sample buffers.
+        for _ in 0..(120.0 * SR / 512.0) as usize {
+            v.advance(512);
+            refreshes += v.take_dirty() as usize;
+            for i in 0..8 {
+                let f = v.factor(i);
+                assert!((f - 1.0).abs() <= TOLERANCE + 1e-6, "component {i}: {f}");
+            }
+        }
+        assert!(refreshes > 10, "drift should move the factors");
+        assert!(refreshes < 120 * 20, "refreshed {refreshes} times");
+        assert!((0..8).any(|i| (v.factor(i) - start[i]).abs() > 1e-3));
+        // Components differ from each other.
+        assert!(start.windows(2).any(|w| (w[0] - w[1]).abs() > 1e-3));
+    }
+
+    #[test]
+    fn same_seed_and_timeline_give_the_same_factors() {
+        let run = || {
+            let mut v = Variance::<3>::new(42, SR);
+            v.set_enabled(true);
+            for _ in 0..1000 {
+                v.advance(256);
+            }
+            (0..3).map(|i| v.factor(i)).collect::<Vec<_>>()
+        };
+        assert_eq!(run(), run());
+
+        let mut v = Variance::<3>::new(42, SR);
+        v.set_enabled(true);
+        let first: Vec<f32> = (0..3).map(|i| v.factor(i)).collect();
+        v.advance(1_000_000);
+        v.reset();
+        assert_eq!((0..3).map(|i| v.factor(i)).collect::<Vec<_>>(), first);
+        assert_ne!(
+            Variance::<3>::new(43, SR).tolerance,
+            Variance::<3>::new(42, SR).tolerance
+        );
+    }
+}
@@ -0,0 +1,297 @@
+// src/variation.rs
+// Variations: a seeded, constrained randomizer behind the editor's VARY
+// buttons.
+//
+// A variation redraws the continuous controls of one module (or of every
+// module at once) from musically sane ranges rather than each parameter's
+// full travel: EQ gains stay within a few dB, each band's frequency lands in
+// the region that band is for, compressor thresholds sit where they bite
+// without crushing. Switches, models, bypasses, routing, I/O trims and the
+// Sheen coat are never touched, so a variation changes how the rack is set,
+// not what is in it or how loud it comes out.
+//
+// Every value is derived from the seed and the parameter ID alone, so a
+// seed always gives the same settings, and a module varied on its own
+// lands where it would have in a whole-strip variation with that seed.
+
+use crate::ModuleType;
+use std::collections::BTreeMap;
+
+/// How a parameter is drawn, in plain units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Draw {
+    /// Uniform between the bounds.
+    Linear(f32, f32),
+    /// Log-uniform between the bounds: frequencies and times, where an
+    /// octave near the bottom counts as much as one near the top.
+    Log(f32, f32),
+    /// Boost/cut within ± the bound, leaning towards small moves.
+    Gain(f32),
+}
+
+impl Draw {
+    /// Map two independent uniforms in [0, 1) to a value.
+    fn sample(self, u: f32, v: f32) -> f32 {
+        match self {
+            Draw::Linear(lo, hi) => lo + (hi - lo) * u,
+            Draw::Log(lo, hi) => lo * (hi / lo).powf(u),
+            // Difference of two uniforms: triangular, peaked at 0 dB.
+            Draw::Gain(max) => (u - v) * max,
+        }
+    }
+}
+
+/// The module a parameter belongs to and how it's drawn, or `None` for
+/// parameters a variation leaves alone.
+fn rule(id: &str) -> Option<(ModuleType, Draw)> {
+    use Draw::*;
+
+    if let Some(rest) = id.strip_prefix("dyneq_band") {
+        let (band, name) = rest.split_once('_')?;
+        // Each band keeps to the lower part of its own range, where the
+        // problems it's usually there for live.
+        let (lo, hi) = match band {
+            "1" => (60.0, 250.0),
+            "2" => (250.0, 1200.0),
+            "3" => (1200.0, 5000.0),
+            "4" => (5000.0, 12000.0),
+            _ => return None,
+        };
+        let draw = match name {
+            "freq" => Log(lo, hi),
+            "threshold" => Linear(-30.0, -10.0),
+            "ratio" => Log(1.5, 4.0),
+            "range" => Linear(2.0, 8.0),
+            "sensitivity" => Linear(0.3, 0.7),
+            "attack" => Log(1.0, 30.0),
+            "release" => Log(50.0, 400.0),
+            "gain" => Gain(4.0),
+            "q" => Log(0.7, 2.5),
+            _ => return None,
+        };
+        return Some((ModuleType::DynamicEQ, draw));
+    }
+
+    let (module, draw) = match id {
+        "lf_freq" => (ModuleType::Api5500EQ, Log(40.0, 150.0)),
+        "lf_gain" => (ModuleType::Api5500EQ, Gain(4.0)),
+        "lmf_freq" => (ModuleType::Api5500EQ, Log(150.0, 500.0)),
+        "lmf_gain" => (ModuleType::Api5500EQ, Gain(3.0)),
+        "lmf_q" => (ModuleType::Api5500EQ, Log(0.5, 2.0)),
+        "mf_freq" => (ModuleType::Api5500EQ, Log(500.0, 2500.0)),
+        "mf_gain" => (ModuleType::Api5500EQ, Gain(3.0)),
+        "mf_q" => (ModuleType::Api5500EQ, Log(0.5, 2.0)),
+        "hmf_freq" => (ModuleType::Api5500EQ, Log(2000.0, 6000.0)),
+        "hmf_gain" => (ModuleType::Api5500EQ, Gain(3.0)),
+        "hmf_q" => (ModuleType::Api5500EQ, Log(0.5, 2.0)),
+        "hf_freq" => (ModuleType::Api5500EQ, Log(6000.0, 14000.0)),
+        "hf_gain" => (ModuleType::Api5500EQ, Gain(4.0)),
+
+        // `comp_output` and the FET output are make-up gain: left alone.
+        "comp_compress" => (ModuleType::ButterComp2, Linear(0.1, 0.6)),
+        "comp_dry_wet" => (ModuleType::ButterComp2, Linear(0.4, 1.0)),
+        "comp_vca_thresh" => (ModuleType::ButterComp2, Linear(-30.0, -8.0)),
+        "comp_vca_ratio" => (ModuleType::ButterComp2, Log(1.5, 6.0)),
+        "comp_vca_atk" => (ModuleType::ButterComp2, Log(1.0, 40.0)),
+        "comp_vca_rel" => (ModuleType::ButterComp2, Log(50.0, 600.0)),
+        "comp_opt_thresh" => (ModuleType::ButterComp2, Linear(-30.0, -8.0)),
+        "comp_opt_speed" => (ModuleType::ButterComp2, Linear(0.2, 0.8)),
+        "comp_opt_char" => (ModuleType::ButterComp2, Linear(0.0, 1.0)),
+        "comp_fet_input" => (ModuleType::ButterComp2, Linear(0.0, 20.0)),
+        "comp_fet_atk" => (ModuleType::ButterComp2, Log(0.05, 0.8)),
+        "comp_fet_rel" => (ModuleType::ButterComp2, Log(50.0, 1100.0)),
+
+        "pultec_lf_boost_freq" => (ModuleType::PultecEQ, Log(30.0, 100.0)),
+        "pultec_lf_boost_gain" => (ModuleType::PultecEQ, Linear(0.0, 6.0)),
+        "pultec_lf_bw" => (ModuleType::PultecEQ, Linear(0.2, 0.8)),
+        "pultec_lf_cut_freq" => (ModuleType::PultecEQ, Log(30.0, 100.0)),
+        "pultec_lf_cut_gain" => (ModuleType::PultecEQ, Linear(0.0, 4.0)),
+        "pultec_lf_cut_bw" => (ModuleType::PultecEQ, Linear(0.2, 0.8)),
+        "pultec_hf_boost_freq" => (ModuleType::PultecEQ, Log(8000.0, 16000.0)),
+        "pultec_hf_boost_gain" => (ModuleType::PultecEQ, Linear(0.0, 5.0)),
+        "pultec_hf_boost_bandwidth" => (ModuleType::PultecEQ, Linear(0.2, 0.8)),
+        "pultec_hf_cut_freq" => (ModuleType::PultecEQ, Log(10000.0, 20000.0)),
+        "pultec_hf_cut_gain" => (ModuleType::PultecEQ, Linear(0.0, 3.0)),
+        "pultec_tube_drive" => (ModuleType::PultecEQ, Linear(0.1, 0.5)),
+
+        "transformer_input_drive" => (ModuleType::Transformer, Linear(0.0, 0.6)),
+        "transformer_input_saturation" => (ModuleType::Transformer, Linear(0.0, 0.6)),
+        "transformer_output_drive" => (ModuleType::Transformer, Linear(0.0, 0.5)),
+        "transformer_output_saturation" => (ModuleType::Transformer, Linear(0.0, 0.5)),
+        "transformer_low_response" => (ModuleType::Transformer, Linear(-0.5, 0.5)),
+        "transformer_high_response" => (ModuleType::Transformer, Linear(-0.5, 0.5)),
+        "transformer_compression" => (ModuleType::Transformer, Linear(0.0, 0.5)),
+
+        // Input/output gain stay put so a variation doesn't jump in level.
+        "punch_threshold" => (ModuleType::Punch, Linear(-4.0, -0.1)),
+        "punch_softness" => (ModuleType::Punch, Linear(0.1, 0.7)),
+        "punch_attack" => (ModuleType::Punch, Linear(-0.3, 0.5)),
+        "punch_sustain" => (ModuleType::Punch, Linear(-0.4, 0.3)),
+        "punch_attack_time" => (ModuleType::Punch, Log(1.0, 15.0)),
+        "punch_release_time" => (ModuleType::Punch, Log(40.0, 250.0)),
+        "punch_sensitivity" => (ModuleType::Punch, Linear(0.3, 0.7)),
+        "punch_mix" => (ModuleType::Punch, Linear(0.5, 1.0)),
+
+        "haas_mid_gain" => (ModuleType::Haas, Gain(1.5)),
+        "haas_side_gain" => (ModuleType::Haas, Gain(2.0)),
+        "haas_comb_depth" => (ModuleType::Haas, Linear(0.0, 0.4)),
+        "haas_comb_time" => (ModuleType::Haas, Log(3.0, 15.0)),
+        "haas_mix" => (ModuleType::Haas, Linear(0.5, 1.0)),
+
+        _ => return None,
+    };
+    Some((module, draw))
+}
+
+/// Draw a variation over `ids` (normally every parameter ID the plugin
+/// has). `scope` limits it to one module; `None` varies the whole strip.
+/// Returns plain values keyed by ID, ready for the editor's apply path.
+pub fn generate<'a>(
+    seed: u32,
+    scope: Option<ModuleType>,
+    ids: impl IntoIterator<Item = &'a str>,
+) -> BTreeMap<String, f32> {
+    ids.into_iter()
+        .filter_map(|id| {
+            let (module, draw) = rule(id)?;
+            if scope.is_some_and(|scope| scope != module) {
+                return None;
+            }
+            let mut rng = param_seed(seed, id);
+            let u = next_unit(&mut rng);
+            let v = next_unit(&mut rng);
+            Some((id.to_owned(), draw.sample(u, v)))
+        })
+        .collect()
+}
+
+/// Next seed in a sequence of variations, so VARY without a typed seed
+/// keeps stepping through fresh ones.
+pub fn next_seed(seed: u32) -> u32 {
+    let mut rng = seed.wrapping_add(0x9E37_79B9);
+    next_u32(&mut rng)
+}
+
+/// FNV-1a of the ID mixed with the seed: an independent stream per
+/// parameter, so which other parameters are drawn never shifts a value.
+fn param_seed(seed: u32, id: &str) -> u32 {
+    let mut hash = 0x811C_9DC5u32 ^ seed;
+    for byte in id.bytes() {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    if hash == 0 {
+        0x9E37_79B9
+    } else {
+        hash
+    }
+}
+
+/// xorshift32 step, as in `variance.rs`.
+fn next_u32(rng: &mut u32) -> u32 {
+    *rng ^= *rng << 13;
+    *rng ^= *rng >> 17;
+    *rng ^= *rng << 5;
+    *rng
+}
+
+fn next_unit(rng: &mut u32) -> f32 {
+    (next_u32(rng) >> 8) as f32 / (1u32 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDS: &[&str] = &[
+        "eq_bypass",
+        "lf_freq",
+        "lf_gain",
+        "mf_q",
+        "hf_freq",
+        "comp_model",
+        "comp_compress",
+        "comp_output",
+        "pultec_lf_boost_freq",
+        "pultec_print",
+        "dyneq_band1_freq",
+        "dyneq_band4_freq",
+        "dyneq_band2_mode",
+        "transformer_model",
+        "transformer_input_drive",
+        "punch_input_gain",
+        "punch_threshold",
+        "haas_comb_time",
+        "sheen_air_db",
+        "module_order_1",
+        "gain",
+    ];
+
+    #[test]
+    fn same_seed_same_variation() {
+        let a = generate(1234, None, IDS.iter().copied());
+        let b = generate(1234, None, IDS.iter().copied());
+        let c = generate(1235, None, IDS.iter().copied());
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn values_stay_in_their_rules() {
+        for seed in 0..500 {
+            for (id, value) in generate(seed, None, IDS.iter().copied()) {
+                let (_, draw) = rule(&id).unwrap();
+                let (lo, hi) = match draw {
+                    Draw::Linear(lo, hi) | Draw::Log(lo, hi) => (lo, hi),
+                    Draw::Gain(max) => (-max, max),
+                };
+                assert!(
+                    value >= lo - 1e-3 && value <= hi + 1e-3,
+                    "{id} = {value} outside {lo}..{hi} (seed {seed})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn switches_routing_and_levels_are_left_alone() {
+        let values = generate(7, None, IDS.iter().copied());
+        for id in [
+            "eq_bypass",
+            "comp_model",
+            "comp_output",
+            "pultec_print",
+            "dyneq_band2_mode",
+            "transformer_model",
+            "punch_input_gain",
+            "sheen_air_db",
+            "module_order_1",
+            "gain",
+        ] {
+            assert!(!values.contains_key(id), "{id} was varied");
+        }
+    }
+
+    #[test]
+    fn module_scope_matches_the_strip() {
+        let strip = generate(99, None, IDS.iter().copied());
+        let eq = generate(99, Some(ModuleType::Api5500EQ), IDS.iter().copied());
+        assert_eq!(eq.len(), 4);
+        for (id, value) in &eq {
+            assert_eq!(rule(id).unwrap().0, ModuleType::Api5500EQ);
+            assert_eq!(strip[id], *value);
+        }
+    }
+
+    #[test]
+    fn gains_lean_towards_small_moves() {
+        let values: Vec<f32> = (0..2000)
+            .map(|seed| generate(seed, None, ["lf_gain"])["lf_gain"])
+            .collect();
+        let small = values.iter().filter(|v| v.abs() < 2.0).count();
+        // Triangular over ±4 dB puts 75% inside ±2 dB (uniform: 50%).
+        assert!(small > 1400, "{small} of 2000 within ±2 dB");
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        assert!(mean.abs() < 0.2, "mean {mean}");
+    }
+}
@@ -0,0 +1,170 @@
+// src/view_state.rs
+// Which view the editor window shows, as one state machine.
+//
+//   Rack ──focus(slot)──▶ Focus(slot) ──focus(same slot) / exit──▶ Rack
+//     │                        │
+//     └──────open(panel)───────┴──▶ Panel { panel, focus } ──close──▶ back
+//
+// `Rack` is the overview: every slot at its hide-flag width. `Focus` widens
+// one slot into the focused editing view (analyzer on top, ADVANCED drawer
+// open) and collapses the rest of the chain to the mini strip of tabs. A
+// `Panel` (DynEQ back view, Sheen back view, metering bridge) replaces the
+// rack and remembers the focus beneath it, so closing the DynEQ editor
+// opened from a focused DynEQ lands back on that focus view.
+//
+// Every way in or out (header clicks, the library sidebar, 1..7, Esc, the
+// OPEN / close buttons, chain and preset loads) is a `ViewInput`, so panels
+// can't stack and focus can't leak past a load. Kept free of vizia so the
+// transitions are unit-tested on their own.
+
+/// A full-window view that replaces the rack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Panel {
+    DynEq,
+    Sheen,
+    MeterBridge,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EditorView {
+    #[default]
+    Rack,
+    Focus(usize),
+    Panel {
+        panel: Panel,
+        /// Focused slot to return to when the panel closes.
+        focus: Option<usize>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViewInput {
+    /// Focus a slot (keys 1..7, library sidebar). Leaves any panel.
+    Focus(usize),
+    /// Module header click: focus the slot, or return to the rack if it
+    /// already is the focus.
+    ToggleFocus(usize),
+    /// Drop focus but keep any panel open (chain / preset load, eject).
+    Unfocus,
+    Open(Panel),
+    Close(Panel),
+    Toggle(Panel),
+    /// Esc or EXIT FOCUS: straight back to the rack.
+    Exit,
+}
+
+impl EditorView {
+    pub fn next(self, input: ViewInput) -> Self {
+        match input {
+            ViewInput::Focus(slot) => Self::Focus(slot),
+            ViewInput::ToggleFocus(slot) => match self {
+                Self::Focus(current) if current == slot => Self::Rack,
+                _ => Self::Focus(slot),
+            },
+            ViewInput::Unfocus => match self {
+                Self::Focus(_) => Self::Rack,
+                Self::Panel { panel, .. } => Self::Panel { panel, focus: None },
+                Self::Rack => Self::Rack,
+            },
+            ViewInput::Open(panel) => Self::Panel {
+                panel,
+                focus: self.focused_slot(),
+            },
+            ViewInput::Close(panel) => match self {
+                Self::Panel { panel: open, focus } if open == panel => {
+                    focus.map_or(Self::Rack, Self::Focus)
+                }
+                other => other,
+            },
+            ViewInput::Toggle(panel) => {
+                let input = if self.is_open(panel) {
+                    ViewInput::Close(panel)
+                } else {
+                    ViewInput::Open(panel)
+                };
+                self.next(input)
+            }
+            ViewInput::Exit => Self::Rack,
+        }
+    }
+
+    /// Slot the rack is focused on. Still set under a panel, so opening
+    /// and closing one doesn't rebuild the hidden rack.
+    pub fn focused_slot(self) -> Option<usize> {
+        match self {
+            Self::Rack => None,
+            Self::Focus(slot) => Some(slot),
+            Self::Panel { focus, .. } => focus,
+        }
+    }
+
+    /// The panel covering the rack, if any.
+    pub fn panel(self) -> Option<Panel> {
+        match self {
+            Self::Panel { panel, .. } => Some(panel),
+            _ => None,
+        }
+    }
+
+    pub fn is_open(self, panel: Panel) -> bool {
+        self.panel() == Some(panel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_click_toggles_focus() {
+        let view = EditorView::Rack.next(ViewInput::ToggleFocus(2));
+        assert_eq!(view, EditorView::Focus(2));
+        assert_eq!(view.next(ViewInput::ToggleFocus(4)), EditorView::Focus(4));
+        assert_eq!(view.next(ViewInput::ToggleFocus(2)), EditorView::Rack);
+    }
+
+    #[test]
+    fn closing_a_panel_returns_to_the_focus_beneath() {
+        let view = EditorView::Focus(3).next(ViewInput::Open(Panel::DynEq));
+        assert!(view.is_open(Panel::DynEq));
+        assert_eq!(view.focused_slot(), Some(3));
+        assert_eq!(
+            view.next(ViewInput::Close(Panel::DynEq)),
+            EditorView::Focus(3)
+        );
+        // Closing a panel that isn't open changes nothing.
+        assert_eq!(view.next(ViewInput::Close(Panel::Sheen)), view);
+        assert_eq!(
+            EditorView::Rack
+                .next(ViewInput::Open(Panel::Sheen))
+                .next(ViewInput::Close(Panel::Sheen)),
+            EditorView::Rack
+        );
+    }
+
+    #[test]
+    fn panels_replace_each_other() {
+        let view = EditorView::Focus(1)
+            .next(ViewInput::Open(Panel::Sheen))
+            .next(ViewInput::Toggle(Panel::MeterBridge));
+        assert_eq!(view.panel(), Some(Panel::MeterBridge));
+        assert_eq!(view.focused_slot(), Some(1));
+        assert_eq!(
+            view.next(ViewInput::Toggle(Panel::MeterBridge)),
+            EditorView::Focus(1)
+        );
+    }
+
+    #[test]
+    fn unfocus_keeps_the_panel_and_exit_drops_everything() {
+        let view = EditorView::Focus(0).next(ViewInput::Open(Panel::MeterBridge));
+        let unfocused = view.next(ViewInput::Unfocus);
+        assert_eq!(unfocused.panel(), Some(Panel::MeterBridge));
+        assert_eq!(unfocused.focused_slot(), None);
+        assert_eq!(
+            EditorView::Focus(5).next(ViewInput::Unfocus),
+            EditorView::Rack
+        );
+        assert_eq!(view.next(ViewInput::Exit), EditorView::Rack);
+    }
+}
@@ -0,0 +1,423 @@
+// src/wasm.rs
+// Browser build of the DSP core (the `wasm` feature, built without
+// `plugin` for wasm32-unknown-unknown). The demo page in site/public/demo
+// drives it through a handful of plain C-ABI exports, so the build needs no
+// bindings generator:
+//
+//   bcs_new(sample_rate, max_frames) → strip handle
+//   bcs_channel(strip, ch)           → pointer to channel `ch`'s buffer, which
+//                                      JS views as a Float32Array of max_frames
+//   bcs_set_param / bcs_get_param    → one setting, by module and index
+//   bcs_set_enabled                  → switch a module in or out of the chain
+//   bcs_process(strip, frames)       → run the chain in place over both buffers
+//
+// The chain is the plugin's default order with the plugin-only modules left
+// out: API 5500 → Pultec → Transformer → Punch → Dynamic EQ. Every module
+// starts switched out, as in the plugin, and with the plugin's defaults.
+// Settings are the modules' `update_parameters` arguments in order; enum
+// arguments are passed as their variant index.
+
+use crate::dsp::{
+    Api5500, ClipMode, DynamicBandParams, DynamicEQ, DynamicMode, EqFirShared, OversamplingFactor,
+    PultecEQ, PunchModule, TransformerModel, TransformerModule,
+};
+
+/// Module ids, in chain order.
+const API5500: usize = 0;
+const PULTEC: usize = 1;
+const TRANSFORMER: usize = 2;
+const PUNCH: usize = 3;
+const DYNAMIC_EQ: usize = 4;
+const MODULES: usize = 5;
+
+/// Dynamic EQ settings per band: mode, detector freq, freq, Q, threshold,
+/// ratio, attack, release, gain, enabled (0/1).
+const DYNEQ_BAND_SETTINGS: usize = 10;
+const DYNEQ_BANDS: usize = 4;
+
+const API5500_DEFAULTS: &[f32] = &[
+    100.0, 0.0, 200.0, 0.0, 0.7, 1000.0, 0.0, 0.7, 3000.0, 0.0, 0.7, 10000.0, 0.0,
+];
+const PULTEC_DEFAULTS: &[f32] = &[
+    60.0, 0.0, 0.67, 100.0, 0.0, 0.5, 10000.0, 0.0, 0.5, 10000.0, 0.0, 0.2,
+];
+/// Model (Vintage), then the seven amounts.
+const TRANSFORMER_DEFAULTS: &[f32] = &[0.0, 0.2, 0.3, 0.1, 0.4, 0.0, 0.0, 0.3];
+/// Clip mode 1 is Soft, oversampling 2 is 8×.
+const PUNCH_DEFAULTS: &[f32] = &[
+    -0.1, 1.0, 0.3, 2.0, 0.0, 0.0, 5.0, 100.0, 0.5, 0.0, 0.0, 1.0, 20.0,
+];
+#[rustfmt::skip]
+const DYNEQ_DEFAULTS: &[f32] = &[
+    0.0, 200.0, 200.0, 1.0, -18.0, 4.0, 10.0, 100.0, 0.0, 1.0,
+    0.0, 800.0, 800.0, 1.0, -18.0, 4.0, 10.0, 100.0, 0.0, 1.0,
+    0.0, 3000.0, 3000.0, 1.0, -18.0, 4.0, 10.0, 100.0, 0.0, 1.0,
+    0.0, 8000.0, 8000.0, 1.0, -18.0, 4.0, 10.0, 100.0, 0.0, 1.0,
+];
+
+const DEFAULTS: [&[f32]; MODULES] = [
+    API5500_DEFAULTS,
+    PULTEC_DEFAULTS,
+    TRANSFORMER_DEFAULTS,
+    PUNCH_DEFAULTS,
+    DYNEQ_DEFAULTS,
+];
+
+fn transformer_model(index: f32) -> TransformerModel {
+    match index as u32 {
+        1 => TransformerModel::Modern,
+        2 => TransformerModel::British,
+        3 => TransformerModel::American,
+        _ => TransformerModel::Vintage,
+    }
+}
+
+fn clip_mode(index: f32) -> ClipMode {
+    match index as u32 {
+        0 => ClipMode::Hard,
+        2 => ClipMode::Cubic,
+        _ => ClipMode::Soft,
+    }
+}
+
+fn oversampling(index: f32) -> OversamplingFactor {
+    match index as u32 {
+        0 => OversamplingFactor::X1,
+        1 => OversamplingFactor::X4,
+        3 => OversamplingFactor::X16,
+        _ => OversamplingFactor::X8,
+    }
+}
+
+fn dynamic_mode(index: f32) -> DynamicMode {
+    match index as u32 {
+        1 => DynamicMode::ExpandUpward,
+        2 => DynamicMode::Gate,
+        _ => DynamicMode::CompressDownward,
+    }
+}
+
+/// The modules plus a stereo working buffer the page writes into and reads
+/// back from.
+pub struct WasmStrip {
+    api5500: Api5500,
+    eq_fir: EqFirShared,
+    pultec: PultecEQ,
+    transformer: TransformerModule,
+    punch: PunchModule,
+    dynamic_eq: DynamicEQ,
+    settings: [Vec<f32>; MODULES],
+    enabled: [bool; MODULES],
+    /// Settings changed since they were last handed to the module.
+    dirty: [bool; MODULES],
+    channels: [Vec<f32>; 2],
+}
+
+impl WasmStrip {
+    pub fn new(sample_rate: f32, max_frames: usize) -> Self {
+        Self {
+            api5500: Api5500::new(sample_rate),
+            eq_fir: EqFirShared::new(),
+            pultec: PultecEQ::new(sample_rate),
+            transformer: TransformerModule::new(sample_rate),
+            punch: PunchModule::new(sample_rate),
+            dynamic_eq: DynamicEQ::new(sample_rate),
+            settings: DEFAULTS.map(|defaults| defaults.to_vec()),
+            enabled: [false; MODULES],
+            dirty: [true; MODULES],
+            channels: [vec![0.0; max_frames], vec![0.0; max_frames]],
+        }
+    }
+
+    pub fn channel(&mut self, channel: usize) -> Option<&mut [f32]> {
+        self.channels.get_mut(channel).map(|ch| &mut ch[..])
+    }
+
+    /// Store one setting. Returns false for an unknown module or index.
+    pub fn set_param(&mut self, module: usize, index: usize, value: f32) -> bool {
+        match self.settings.get_mut(module).and_then(|s| s.get_mut(index)) {
+            Some(setting) if value.is_finite() => {
+                *setting = value;
+                self.dirty[module] = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// One setting, or NaN for an unknown module or index.
+    pub fn param(&self, module: usize, index: usize) -> f32 {
+        self.settings
+            .get(module)
+            .and_then(|s| s.get(index))
+            .copied()
+            .unwrap_or(f32::NAN)
+    }
+
+    pub fn set_enabled(&mut self, module: usize, on: bool) {
+        let Some(&was_on) = self.enabled.get(module) else {
+            return;
+        };
+        // A module switched back in starts from silence, not from whatever
+        // its filters held when it was switched out.
+        if on && !was_on {
+            self.reset_module(module);
+        }
+        self.enabled[module] = on;
+    }
+
+    /// Run the first `frames` samples of both buffers through the chain.
+    pub fn process(&mut self, frames: usize) {
+        for module in 0..MODULES {
+            if self.dirty[module] {
+                self.apply(module);
+                self.dirty[module] = false;
+            }
+        }
+
+        let frames = frames.min(self.channels[0].len());
+        let [left, right] = &mut self.channels;
+        let channels = &mut [&mut left[..frames], &mut right[..frames]];
+        if self.enabled[API5500] {
+            self.api5500.process_channels(channels, &self.eq_fir);
+        }
+        if self.enabled[PULTEC] {
+            self.pultec.process_channels(channels);
+        }
+        if self.enabled[TRANSFORMER] {
+            self.transformer.process_channels(channels);
+        }
+        if self.enabled[PUNCH] {
+            self.punch.process_channels(channels);
+        }
+        if self.enabled[DYNAMIC_EQ] {
+            self.dynamic_eq.process_channels(channels);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for module in 0..MODULES {
+            self.reset_module(module);
+        }
+    }
+
+    fn reset_module(&mut self, module: usize) {
+        match module {
+            API5500 => self.api5500.reset(),
+            PULTEC => self.pultec.reset(),
+            TRANSFORMER => self.transformer.reset(),
+            PUNCH => self.punch.reset(),
+            DYNAMIC_EQ => self.dynamic_eq.reset(),
+            _ => {}
+        }
+    }
+
+    fn apply(&mut self, module: usize) {
+        let s = &self.settings[module];
+        match module {
+            API5500 => self.api5500.update_parameters(
+                s[0], s[1], s[2], s[3], s[4], s[5], s[6], s[7], s[8], s[9], s[10], s[11], s[12],
+            ),
+            PULTEC => self.pultec.update_parameters(
+                s[0], s[1], s[2], s[3], s[4], s[5], s[6], s[7], s[8], s[9], s[10], s[11],
+            ),
+            TRANSFORMER => self.transformer.update_parameters(
+                transformer_model(s[0]),
+                s[1],
+                s[2],
+                s[3],
+                s[4],
+                s[5],
+                s[6],
+                s[7],
+            ),
+            PUNCH => self.punch.update_parameters(
+                s[0],
+                clip_mode(s[1]),
+                s[2],
+                oversampling(s[3]),
+                s[4],
+                s[5],
+                s[6],
+                s[7],
+                s[8],
+                s[9],
+                s[10],
+                s[11],
+                s[12],
+            ),
+            DYNAMIC_EQ => {
+                let bands: [DynamicBandParams; DYNEQ_BANDS] = std::array::from_fn(|band| {
+                    let b = &s[band * DYNEQ_BAND_SETTINGS..];
+                    DynamicBandParams {
+                        mode: dynamic_mode(b[0]),
+                        detector_freq: b[1],
+                        freq: b[2],
+                        q: b[3],
+                        threshold_db: b[4],
+                        ratio: b[5],
+                        attack_ms: b[6],
+                        release_ms: b[7],
+                        gain_db: b[8],
+                        enabled: b[9] >= 0.5,
+                        solo: false,
+                    }
+                });
+                self.dynamic_eq.update_parameters(&bands);
+            }
+            _ => {}
+        }
+    }
+}
+
+// ── C ABI ─────────────────────────────────────────────────────────────────────
+
+/// Create a strip for `sample_rate` whose buffers hold `max_frames` samples.
+#[no_mangle]
+pub extern "C" fn bcs_new(sample_rate: f32, max_frames: u32) -> *mut WasmStrip {
+    Box::into_raw(Box::new(WasmStrip::new(sample_rate, max_frames as usize)))
+}
+
+/// # Safety
+/// `strip` must come from `bcs_new` and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn bcs_free(strip: *mut WasmStrip) {
+    if !strip.is_null() {
+        // SAFETY: the caller passes a pointer from `bcs_new`, freed once.
+        drop(unsafe { Box::from_raw(strip) });
+    }
+}
+
+/// Channel 0 (left) or 1 (right)'s buffer, or null for any other channel.
+///
+/// # Safety
+/// `strip` must be a live pointer from `bcs_new`.
+#[no_mangle]
+pub unsafe extern "C" fn bcs_channel(strip: *mut WasmStrip, channel: u32) -> *mut f32 {
+    // SAFETY: the caller passes a live pointer from `bcs_new`.
+    let strip = unsafe { &mut *strip };
+    strip
+        .channel(channel as usize)
+        .map_or(std::ptr::null_mut(), |ch| ch.as_mut_ptr())
+}
+
+/// # Safety
+/// `strip` must be a live pointer from `bcs_new`.
+#[no_mangle]
+pub unsafe extern "C" fn bcs_set_param(
+    strip: *mut WasmStrip,
+    module: u32,
+    index: u32,
+    value: f32,
+) -> bool {
+    // SAFETY: the caller passes a live pointer from `bcs_new`.
+    let strip = unsafe { &mut *strip };
+    strip.set_param(module as usize, index as usize, value)
+}
+
+/// # Safety
+/// `strip` must be a live pointer from `bcs_new`.
+#[no_mangle]
+pub unsafe extern "C" fn bcs_get_param(strip: *const WasmStrip, module: u32, index: u32) -> f32 {
+    // SAFETY: the caller passes a live pointer from `bcs_new`.
+    let strip = unsafe { &*strip };
+    strip.param(module as usize, index as usize)
+}
+
+/// # Safety
+/// `strip` must be a live pointer from `bcs_new`.
+#[no_mangle]
+pub unsafe extern "C" fn bcs_set_enabled(strip: *mut WasmStrip, module: u32, on: bool) {
+    // SAFETY: the caller passes a live pointer from `bcs_new`.
+    let strip = unsafe { &mut *strip };
+    strip.set_enabled(module as usize, on);
+}
+
+/// Process the first `frames` samples of both channel buffers in place.
+///
+/// # Safety
+/// `strip` must be a live pointer from `bcs_new`, and no JS view of its
+/// buffers may be written while this runs (wasm is single-threaded, so
+/// this only rules out shared-memory builds).
+#[no_mangle]
+pub unsafe extern "C" fn bcs_process(strip: *mut WasmStrip, frames: u32) {
+    // SAFETY: the caller passes a live pointer from `bcs_new`.
+    let strip = unsafe { &mut *strip };
+    strip.process(frames as usize);
+}
+
+/// Clear every module's state, e.g. before rendering a new file.
+///
+/// # Safety
+/// `strip` must be a live pointer from `bcs_new`.
+#[no_mangle]
+pub unsafe extern "C" fn bcs_reset(strip: *mut WasmStrip) {
+    // SAFETY: the caller passes a live pointer from `bcs_new`.
+    let strip = unsafe { &mut *strip };
+    strip.reset();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(strip: &mut WasmStrip, frames: usize) {
+        for ch in 0..2 {
+            let buf = strip.channel(ch).unwrap();
+            for (i, s) in buf[..frames].iter_mut().enumerate() {
+                *s = 0.8 * (i as f32 * 0.03).sin();
+            }
+        }
+    }
+
+    #[test]
+    fn test_disabled_chain_passes_audio_through() {
+        let mut strip = WasmStrip::new(48000.0, 1024);
+        sine(&mut strip, 1024);
+        let before = strip.channel(0).unwrap().to_vec();
+        strip.process(1024);
+        assert_eq!(strip.channel(0).unwrap(), &before[..]);
+    }
+
+    #[test]
+    fn test_full_chain_stays_finite_and_bounded() {
+        let mut strip = WasmStrip::new(44100.0, 2048);
+        for module in 0..MODULES {
+            strip.set_enabled(module, true);
+        }
+        assert!(strip.set_param(PULTEC, 1, 10.0), "LF boost");
+        assert!(strip.set_param(PUNCH, 0, -6.0), "clip threshold");
+        for _ in 0..4 {
+            sine(&mut strip, 2048);
+            strip.process(2048);
+        }
+        let left = strip.channel(0).unwrap();
+        assert!(left.iter().all(|s| s.is_finite() && s.abs() < 4.0));
+    }
+
+    #[test]
+    fn test_params_round_trip_and_reject_bad_input() {
+        let mut strip = WasmStrip::new(48000.0, 64);
+        assert_eq!(strip.param(API5500, 0), 100.0);
+        assert!(strip.set_param(API5500, 1, 6.0));
+        assert_eq!(strip.param(API5500, 1), 6.0);
+        assert_eq!(
+            strip.param(DYNAMIC_EQ, DYNEQ_BANDS * DYNEQ_BAND_SETTINGS - 1),
+            1.0
+        );
+        assert!(!strip.set_param(API5500, 13, 1.0), "past the last setting");
+        assert!(!strip.set_param(MODULES, 0, 1.0), "unknown module");
+        assert!(!strip.set_param(PUNCH, 0, f32::NAN), "non-finite value");
+        assert!(strip.param(MODULES, 0).is_nan());
+        assert!(strip.channel(2).is_none());
+    }
+
+    #[test]
+    fn test_process_clamps_frames_to_the_buffer() {
+        let mut strip = WasmStrip::new(48000.0, 256);
+        strip.set_enabled(PUNCH, true);
+        sine(&mut strip, 256);
+        strip.process(10_000);
+        assert!(strip.channel(1).unwrap().iter().all(|s| s.is_finite()));
+    }
+}